@@ -0,0 +1,124 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use xcstrings_mcp::store::{TranslationUpdate, XcStringsStore};
+
+const KEYS: usize = 3_000;
+const LANGUAGES: [&str; 8] = ["en", "de", "fr", "es", "it", "ja", "ko", "zh-Hans"];
+const WRITERS: usize = 2;
+const READERS: usize = 8;
+const DURATION: Duration = Duration::from_secs(3);
+/// Pause between a reader's requests, like a client polling the catalog.
+const READ_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Measures read and write throughput on one catalog while web and MCP clients edit
+/// different keys at once. Run with `cargo bench --bench store_contention`.
+fn main() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(4)
+        .enable_all()
+        .build()
+        .expect("runtime");
+    runtime.block_on(run());
+}
+
+async fn run() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = Arc::new(
+        XcStringsStore::load_or_create(dir.path().join("Localizable.xcstrings"))
+            .await
+            .expect("create catalog"),
+    );
+    let operations = (0..KEYS).flat_map(|index| {
+        LANGUAGES.iter().map(
+            move |language| xcstrings_mcp::store::BatchOperation::Upsert {
+                key: format!("key.{index}"),
+                language: language.to_string(),
+                update: Box::new(TranslationUpdate::from_value_state(
+                    Some(format!("Value {index} in {language}")),
+                    None,
+                )),
+                comment: None,
+                position: xcstrings_mcp::store::InsertPosition::End,
+            },
+        )
+    });
+    store
+        .apply_batch(operations.collect())
+        .await
+        .expect("seed catalog");
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let reads = Arc::new(AtomicU64::new(0));
+    let writes = Arc::new(AtomicU64::new(0));
+    let slowest_read = Arc::new(AtomicU64::new(0));
+    let mut tasks = Vec::new();
+    for writer in 0..WRITERS {
+        let (store, stop, writes) = (store.clone(), stop.clone(), writes.clone());
+        tasks.push(tokio::spawn(async move {
+            let mut round = 0;
+            while !stop.load(Ordering::Relaxed) {
+                let key = format!("key.{}", (round * WRITERS + writer) % KEYS);
+                store
+                    .upsert_translation(
+                        &key,
+                        "de",
+                        TranslationUpdate::from_value_state(Some(format!("Wert {round}")), None),
+                    )
+                    .await
+                    .expect("write");
+                writes.fetch_add(1, Ordering::Relaxed);
+                round += 1;
+            }
+        }));
+    }
+    for reader in 0..READERS {
+        let (store, stop, reads, slowest) = (
+            store.clone(),
+            stop.clone(),
+            reads.clone(),
+            slowest_read.clone(),
+        );
+        tasks.push(tokio::spawn(async move {
+            let mut round = reader;
+            while !stop.load(Ordering::Relaxed) {
+                let started = Instant::now();
+                let key = format!("key.{}", round % KEYS);
+                store.get_translation(&key, "fr").await.expect("read");
+                let micros = started.elapsed().as_micros() as u64;
+                slowest.fetch_max(micros, Ordering::Relaxed);
+                reads.fetch_add(1, Ordering::Relaxed);
+                round += READERS;
+                tokio::time::sleep(READ_INTERVAL).await;
+            }
+        }));
+    }
+
+    tokio::time::sleep(DURATION).await;
+    stop.store(true, Ordering::Relaxed);
+    for task in tasks {
+        task.await.expect("task");
+    }
+    let seconds = DURATION.as_secs_f64();
+    println!(
+        "{KEYS} keys x {} languages, {WRITERS} writers, {READERS} readers",
+        LANGUAGES.len()
+    );
+    println!(
+        "writes/s: {:.0}",
+        writes.load(Ordering::Relaxed) as f64 / seconds
+    );
+    println!(
+        "reads/s: {:.0}",
+        reads.load(Ordering::Relaxed) as f64 / seconds
+    );
+    println!(
+        "slowest read: {:.1} ms",
+        slowest_read.load(Ordering::Relaxed) as f64 / 1000.0
+    );
+}
@@ -0,0 +1,43 @@
+use std::{path::Path, process::Command};
+
+fn main() {
+    let sha = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=XCSTRINGS_GIT_SHA={sha}");
+
+    // Honour reproducible-build timestamps when the packager sets one.
+    let built_at = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .and_then(|epoch| chrono::DateTime::from_timestamp(epoch, 0))
+        .unwrap_or_else(chrono::Utc::now);
+    println!(
+        "cargo:rustc-env=XCSTRINGS_BUILD_DATE={}",
+        built_at.format("%Y-%m-%dT%H:%M:%SZ")
+    );
+
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    // Rebuild when HEAD moves; missing paths would force a rebuild every time.
+    let head = Path::new(".git/HEAD");
+    if head.exists() {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        if let Some(reference) = std::fs::read_to_string(head)
+            .ok()
+            .and_then(|head| head.strip_prefix("ref: ").map(|r| r.trim().to_string()))
+        {
+            let reference = Path::new(".git").join(reference);
+            if reference.exists() {
+                println!("cargo:rerun-if-changed={}", reference.display());
+            }
+        }
+    } else {
+        println!("cargo:rerun-if-changed=build.rs");
+    }
+}
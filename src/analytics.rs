@@ -0,0 +1,356 @@
+use std::cmp::Ordering;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::store::{TranslationRecord, TranslationValue};
+
+/// Longest texts listed per language when the caller doesn't say.
+pub const DEFAULT_TOP: usize = 10;
+
+/// How much longer translations are than the source text, per language.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LengthReport {
+    pub source_language: String,
+    pub languages: Vec<LanguageLengths>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageLengths {
+    pub language: String,
+    /// Texts compared with their source: top-level values and each variation case.
+    pub compared: usize,
+    /// Mean of translation length divided by source length; `1.3` means 30% longer.
+    pub average_ratio: f64,
+    pub max_ratio: f64,
+    /// Texts that grew the most relative to their source, longest first.
+    pub longest: Vec<LongTranslation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LongTranslation {
+    pub key: String,
+    /// Variation case the text belongs to, such as `plural.other`; empty for the value.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub variation: String,
+    pub source: String,
+    pub translation: String,
+    pub ratio: f64,
+}
+
+/// Compares every translation with the source text of its key, in characters. Variation
+/// cases are compared with the same case in the source, or with the source value when the
+/// source doesn't vary. Source texts default to the key, as in Xcode, and keys marked
+/// `shouldTranslate: false` are skipped. Each language lists its `top` longest texts.
+pub fn length_report(
+    records: &[TranslationRecord],
+    source_language: &str,
+    top: usize,
+) -> LengthReport {
+    let mut languages: Vec<(String, Vec<LongTranslation>)> = Vec::new();
+    for record in records {
+        if record.should_translate == Some(false) {
+            continue;
+        }
+        let source_texts = record
+            .translations
+            .get(source_language)
+            .map(texts)
+            .unwrap_or_default();
+        let source_value = source_texts
+            .iter()
+            .find(|(variation, _)| variation.is_empty())
+            .map_or(record.key.as_str(), |(_, text)| text.as_str());
+
+        for (language, value) in &record.translations {
+            if language == source_language {
+                continue;
+            }
+            let index = match languages.iter().position(|(known, _)| known == language) {
+                Some(index) => index,
+                None => {
+                    languages.push((language.clone(), Vec::new()));
+                    languages.len() - 1
+                }
+            };
+            for (variation, translation) in texts(value) {
+                let source = source_texts
+                    .iter()
+                    .find(|(source_variation, _)| *source_variation == variation)
+                    .map_or(source_value, |(_, text)| text.as_str());
+                let source_length = source.chars().count();
+                if source_length == 0 {
+                    continue;
+                }
+                let ratio = translation.chars().count() as f64 / source_length as f64;
+                languages[index].1.push(LongTranslation {
+                    key: record.key.clone(),
+                    variation,
+                    source: source.to_string(),
+                    translation,
+                    ratio,
+                });
+            }
+        }
+    }
+
+    languages.sort_by(|a, b| a.0.cmp(&b.0));
+    let languages = languages
+        .into_iter()
+        .map(|(language, mut texts)| {
+            let compared = texts.len();
+            let average_ratio = if compared == 0 {
+                0.0
+            } else {
+                texts.iter().map(|text| text.ratio).sum::<f64>() / compared as f64
+            };
+            texts.sort_by(|a, b| b.ratio.partial_cmp(&a.ratio).unwrap_or(Ordering::Equal));
+            let max_ratio = texts.first().map_or(0.0, |text| text.ratio);
+            texts.truncate(top);
+            for text in &mut texts {
+                text.ratio = round(text.ratio);
+            }
+            LanguageLengths {
+                language,
+                compared,
+                average_ratio: round(average_ratio),
+                max_ratio: round(max_ratio),
+                longest: texts,
+            }
+        })
+        .collect();
+    LengthReport {
+        source_language: source_language.to_string(),
+        languages,
+    }
+}
+
+/// Vendor cost of translating what is left in each language, priced per source word.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CostEstimate {
+    pub source_language: String,
+    pub rate_per_word: f64,
+    /// Whether only untranslated keys were counted, leaving out those needing review.
+    pub only_untranslated: bool,
+    pub languages: Vec<LanguageCost>,
+    pub total_words: usize,
+    pub total_cost: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageCost {
+    pub language: String,
+    /// Keys that still need work in the language.
+    pub keys: usize,
+    /// Words in the source text of those keys, counting every variation case.
+    pub words: usize,
+    pub cost: f64,
+}
+
+/// Prices the source words of keys that are untranslated in each of `languages` and, unless
+/// `only_untranslated`, of keys whose translation is new, needs review, or is stale. Keys
+/// marked `shouldTranslate: false` are skipped unless `include_do_not_translate`.
+pub fn estimate_cost(
+    records: &[TranslationRecord],
+    source_language: &str,
+    languages: &[String],
+    rate_per_word: f64,
+    only_untranslated: bool,
+    include_do_not_translate: bool,
+) -> CostEstimate {
+    let mut estimate = CostEstimate {
+        source_language: source_language.to_string(),
+        rate_per_word,
+        only_untranslated,
+        languages: Vec::new(),
+        total_words: 0,
+        total_cost: 0.0,
+    };
+    for language in languages {
+        let mut cost = LanguageCost {
+            language: language.clone(),
+            keys: 0,
+            words: 0,
+            cost: 0.0,
+        };
+        for record in records {
+            if record.should_translate == Some(false) && !include_do_not_translate {
+                continue;
+            }
+            let needs_work = match record.translations.get(language) {
+                None => true,
+                Some(value) => {
+                    texts(value).is_empty() || (!only_untranslated && in_progress(value))
+                }
+            };
+            if needs_work {
+                cost.keys += 1;
+                cost.words += source_words(record, source_language);
+            }
+        }
+        cost.cost = round(cost.words as f64 * rate_per_word);
+        estimate.total_words += cost.words;
+        estimate.languages.push(cost);
+    }
+    estimate.total_cost = round(estimate.total_words as f64 * rate_per_word);
+    estimate
+}
+
+/// Words in the source text of a record; the key is the text when there is no source value.
+fn source_words(record: &TranslationRecord, source_language: &str) -> usize {
+    let texts = record
+        .translations
+        .get(source_language)
+        .map(texts)
+        .unwrap_or_default();
+    if texts.is_empty() {
+        return count_words(&record.key);
+    }
+    texts.iter().map(|(_, text)| count_words(text)).sum()
+}
+
+/// Counts whitespace-separated words with at least one letter or digit, leaving out format
+/// specifiers such as `%@` and `%lld`.
+pub fn count_words(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|word| !word.starts_with('%') && word.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+/// Whether any part of a translation is new, waiting for review, or stale.
+fn in_progress(value: &TranslationValue) -> bool {
+    matches!(
+        value.state.as_deref(),
+        Some("new" | "needs_review" | "needs-review" | "needs-translation" | "stale")
+    ) || value
+        .variations
+        .values()
+        .flat_map(|cases| cases.values())
+        .any(in_progress)
+}
+
+/// Non-empty texts of a translation keyed by variation path, such as `device.iphone`.
+pub(crate) fn texts(value: &TranslationValue) -> Vec<(String, String)> {
+    fn collect(value: &TranslationValue, path: &str, out: &mut Vec<(String, String)>) {
+        if let Some(text) = value.value.as_deref().filter(|text| !text.is_empty()) {
+            out.push((path.to_string(), text.to_string()));
+        }
+        for (selector, cases) in &value.variations {
+            for (case, nested) in cases {
+                let nested_path = match path {
+                    "" => format!("{selector}.{case}"),
+                    _ => format!("{path}.{selector}.{case}"),
+                };
+                collect(nested, &nested_path, out);
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    collect(value, "", &mut out);
+    out
+}
+
+/// Rounds ratios and prices to two decimals.
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+
+    fn text(value: &str) -> TranslationValue {
+        TranslationValue {
+            value: Some(value.to_string()),
+            ..TranslationValue::default()
+        }
+    }
+
+    fn record(key: &str, translations: Vec<(&str, TranslationValue)>) -> TranslationRecord {
+        TranslationRecord {
+            key: key.to_string(),
+            ordinal: 0,
+            comment: None,
+            extraction_state: None,
+            should_translate: None,
+            metadata: None,
+            modified: None,
+            matches: Vec::new(),
+            translations: translations
+                .into_iter()
+                .map(|(language, value)| (language.to_string(), value))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn ranks_translations_by_growth_over_the_source() {
+        let mut plural = TranslationValue::default();
+        plural.variations.insert(
+            "plural".to_string(),
+            IndexMap::from([
+                ("one".to_string(), text("1 Artikel")),
+                ("other".to_string(), text("%lld Artikel")),
+            ]),
+        );
+        let records = vec![
+            record(
+                "save",
+                vec![("en", text("Save")), ("de", text("Speichern"))],
+            ),
+            record("Cancel", vec![("de", text("Abbrechen"))]),
+            record("items", vec![("en", text("%lld items")), ("de", plural)]),
+        ];
+
+        let report = length_report(&records, "en", 2);
+        assert_eq!(report.languages.len(), 1);
+        let german = &report.languages[0];
+        assert_eq!(german.compared, 4);
+        assert_eq!(german.max_ratio, 2.25);
+        let longest: Vec<_> = german
+            .longest
+            .iter()
+            .map(|text| (text.key.as_str(), text.variation.as_str(), text.ratio))
+            .collect();
+        assert_eq!(longest, [("save", "", 2.25), ("Cancel", "", 1.5)]);
+        assert_eq!(german.average_ratio, 1.46);
+    }
+
+    #[test]
+    fn prices_source_words_of_unfinished_keys() {
+        let mut stale = text("Speichern");
+        stale.state = Some("needs_review".to_string());
+        let records = vec![
+            record("save", vec![("en", text("Save changes")), ("de", stale)]),
+            record("Delete %lld files", vec![]),
+            record("done", vec![("en", text("Done")), ("de", text("Fertig"))]),
+        ];
+        let languages = ["de".to_string()];
+
+        let estimate = estimate_cost(&records, "en", &languages, 0.1, false, false);
+        assert_eq!(estimate.languages[0].keys, 2);
+        assert_eq!(estimate.total_words, 4);
+        assert_eq!(estimate.total_cost, 0.4);
+
+        let estimate = estimate_cost(&records, "en", &languages, 0.1, true, false);
+        assert_eq!(estimate.languages[0].keys, 1);
+        assert_eq!(estimate.languages[0].words, 2);
+        assert_eq!(estimate.languages[0].cost, 0.2);
+
+        let mut brand = record("Acme", vec![("en", text("Acme"))]);
+        brand.should_translate = Some(false);
+        let records = [records, vec![brand]].concat();
+        let estimate = estimate_cost(&records, "en", &languages, 0.1, true, false);
+        assert_eq!(estimate.languages[0].keys, 1);
+        let estimate = estimate_cost(&records, "en", &languages, 0.1, true, true);
+        assert_eq!(estimate.languages[0].keys, 2);
+    }
+}
@@ -1,42 +1,203 @@
+use serde::Deserialize;
 use serde_json::Value;
 use std::io::{self, Write};
 
+/// Layout choices for written catalogs, from the `format` section of the config file. The
+/// defaults match current Xcode output; adjust them to byte-match an older Xcode and keep
+/// diffs minimal.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FormatOptions {
+    /// Spaces per indentation level.
+    pub indent: usize,
+    /// Whether the file ends with a newline after the closing brace.
+    pub trailing_newline: bool,
+    /// Apple's `"key" : value` spacing; `"key": value` when false.
+    pub space_before_colon: bool,
+    /// Writes characters outside ASCII as `\uXXXX` escapes.
+    pub escape_non_ascii: bool,
+    /// Ends lines with `\r\n` instead of `\n`.
+    pub crlf: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            trailing_newline: false,
+            space_before_colon: true,
+            escape_non_ascii: false,
+            crlf: false,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// These options with the escaping and line endings found in an existing file, so
+    /// saving it again doesn't rewrite every line.
+    pub fn with_style(&self, style: SourceStyle) -> Self {
+        Self {
+            escape_non_ascii: style.escape_non_ascii.unwrap_or(self.escape_non_ascii),
+            crlf: style.crlf.unwrap_or(self.crlf),
+            ..self.clone()
+        }
+    }
+}
+
+/// Escaping and line endings of a catalog as read from disk; `None` where the file doesn't
+/// tell, such as the escaping of an ASCII-only file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SourceStyle {
+    pub escape_non_ascii: Option<bool>,
+    pub crlf: Option<bool>,
+}
+
+impl SourceStyle {
+    pub fn detect(raw: &str) -> Self {
+        let mut detector = StyleDetector::default();
+        detector.feed(raw.as_bytes());
+        detector.finish()
+    }
+}
+
+/// Finds the [`SourceStyle`] of a file fed in pieces as it's read, so its text never has to
+/// be held whole.
+#[derive(Debug, Default)]
+pub struct StyleDetector {
+    crlf: Option<bool>,
+    previous: u8,
+    escape: Escape,
+    escaped: bool,
+    literal: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+enum Escape {
+    #[default]
+    None,
+    Backslash,
+    /// Inside `\u`, with the hex digits read so far and their value, or `None` once one
+    /// isn't a hex digit.
+    Unicode {
+        digits: u8,
+        code: Option<u32>,
+    },
+}
+
+impl StyleDetector {
+    pub fn feed(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            // Literal characters settle the escaping, so only the line ending is left.
+            if self.literal && self.crlf.is_some() {
+                return;
+            }
+            if self.crlf.is_none() && byte == b'\n' {
+                self.crlf = Some(self.previous == b'\r');
+            }
+            self.previous = byte;
+            self.escape = match self.escape {
+                // The escaped character is consumed, so `\\u` isn't mistaken for `\u`.
+                Escape::Backslash if byte == b'u' => Escape::Unicode {
+                    digits: 0,
+                    code: Some(0),
+                },
+                Escape::Backslash => Escape::None,
+                Escape::Unicode { digits, code } => {
+                    let code = code.zip(char::from(byte).to_digit(16));
+                    let code = code.map(|(code, digit)| code * 16 + digit);
+                    if digits < 3 {
+                        Escape::Unicode {
+                            digits: digits + 1,
+                            code,
+                        }
+                    } else {
+                        self.escaped |= code.is_some_and(|code| code > 0x7f);
+                        Escape::None
+                    }
+                }
+                Escape::None if byte == b'\\' => Escape::Backslash,
+                Escape::None => {
+                    // Literal characters mean the file isn't escaped, even if some escapes
+                    // remain.
+                    self.literal |= !byte.is_ascii();
+                    Escape::None
+                }
+            };
+        }
+    }
+
+    pub fn finish(&self) -> SourceStyle {
+        SourceStyle {
+            escape_non_ascii: if self.literal {
+                Some(false)
+            } else {
+                self.escaped.then_some(true)
+            },
+            crlf: self.crlf,
+        }
+    }
+}
+
 /// Formats JSON with Apple's preferred style for .xcstrings files:
 /// - Spaces before colons
 /// - 2-space indentation
 /// - Preserves key order when using IndexMap
 pub fn to_apple_format(value: &Value) -> String {
+    to_apple_format_with(value, &FormatOptions::default())
+}
+
+/// Formats JSON like [`to_apple_format`], with the layout adjusted by `options`.
+pub fn to_apple_format_with(value: &Value, options: &FormatOptions) -> String {
     let mut buffer = Vec::new();
-    write_value(&mut buffer, value, 0).expect("Failed to write JSON");
-    String::from_utf8(buffer).expect("Invalid UTF-8")
+    write_value(&mut buffer, value, 0, options).expect("Failed to write JSON");
+    if options.trailing_newline {
+        buffer.push(b'\n');
+    }
+    let output = String::from_utf8(buffer).expect("Invalid UTF-8");
+    // Newlines inside strings are escaped, so every raw newline is a line break.
+    if options.crlf {
+        output.replace('\n', "\r\n")
+    } else {
+        output
+    }
 }
 
-fn write_value<W: Write>(writer: &mut W, value: &Value, indent_level: usize) -> io::Result<()> {
+fn write_value<W: Write>(
+    writer: &mut W,
+    value: &Value,
+    indent_level: usize,
+    options: &FormatOptions,
+) -> io::Result<()> {
     match value {
         Value::Null => write!(writer, "null"),
         Value::Bool(b) => write!(writer, "{}", b),
         Value::Number(n) => write!(writer, "{}", n),
-        Value::String(s) => write!(writer, "\"{}\"", escape_string(s)),
-        Value::Array(arr) => write_array(writer, arr, indent_level),
-        Value::Object(obj) => write_object(writer, obj, indent_level),
+        Value::String(s) => write!(writer, "\"{}\"", escape_string(s, options)),
+        Value::Array(arr) => write_array(writer, arr, indent_level, options),
+        Value::Object(obj) => write_object(writer, obj, indent_level, options),
     }
 }
 
-fn write_array<W: Write>(writer: &mut W, array: &[Value], indent_level: usize) -> io::Result<()> {
+fn write_array<W: Write>(
+    writer: &mut W,
+    array: &[Value],
+    indent_level: usize,
+    options: &FormatOptions,
+) -> io::Result<()> {
     if array.is_empty() {
         return write!(writer, "[]");
     }
 
     writeln!(writer, "[")?;
     for (i, value) in array.iter().enumerate() {
-        write_indent(writer, indent_level + 1)?;
-        write_value(writer, value, indent_level + 1)?;
+        write_indent(writer, indent_level + 1, options)?;
+        write_value(writer, value, indent_level + 1, options)?;
         if i < array.len() - 1 {
             write!(writer, ",")?;
         }
         writeln!(writer)?;
     }
-    write_indent(writer, indent_level)?;
+    write_indent(writer, indent_level, options)?;
     write!(writer, "]")
 }
 
@@ -44,6 +205,7 @@ fn write_object<W: Write>(
     writer: &mut W,
     obj: &serde_json::Map<String, Value>,
     indent_level: usize,
+    options: &FormatOptions,
 ) -> io::Result<()> {
     if obj.is_empty() {
         return write!(writer, "{{}}");
@@ -52,27 +214,29 @@ fn write_object<W: Write>(
     writeln!(writer, "{{")?;
     let entries: Vec<_> = obj.iter().collect();
     for (i, (key, value)) in entries.iter().enumerate() {
-        write_indent(writer, indent_level + 1)?;
+        write_indent(writer, indent_level + 1, options)?;
         // Apple format: space before colon
-        write!(writer, "\"{}\" : ", escape_string(key))?;
-        write_value(writer, value, indent_level + 1)?;
+        let separator = if options.space_before_colon {
+            " : "
+        } else {
+            ": "
+        };
+        write!(writer, "\"{}\"{separator}", escape_string(key, options))?;
+        write_value(writer, value, indent_level + 1, options)?;
         if i < entries.len() - 1 {
             write!(writer, ",")?;
         }
         writeln!(writer)?;
     }
-    write_indent(writer, indent_level)?;
+    write_indent(writer, indent_level, options)?;
     write!(writer, "}}")
 }
 
-fn write_indent<W: Write>(writer: &mut W, level: usize) -> io::Result<()> {
-    for _ in 0..level {
-        write!(writer, "  ")?; // 2 spaces per indent level
-    }
-    Ok(())
+fn write_indent<W: Write>(writer: &mut W, level: usize, options: &FormatOptions) -> io::Result<()> {
+    write!(writer, "{:width$}", "", width = level * options.indent)
 }
 
-fn escape_string(s: &str) -> String {
+fn escape_string(s: &str, options: &FormatOptions) -> String {
     let mut result = String::with_capacity(s.len());
     for ch in s.chars() {
         match ch {
@@ -86,6 +250,13 @@ fn escape_string(s: &str) -> String {
             c if c.is_control() => {
                 result.push_str(&format!("\\u{:04x}", c as u32));
             }
+            c if options.escape_non_ascii && !c.is_ascii() => {
+                // Characters beyond the BMP become a UTF-16 surrogate pair, as in JSON.
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    result.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
             c => result.push(c),
         }
     }
@@ -147,4 +318,89 @@ mod tests {
         let formatted = to_apple_format(&value);
         assert!(formatted.contains("Line 1\\nLine 2\\t\\\"quoted\\\""));
     }
+
+    #[test]
+    fn test_format_options() {
+        let value = json!({ "strings": { "café": ["😀"] } });
+        assert_eq!(
+            to_apple_format(&value),
+            "{\n  \"strings\" : {\n    \"café\" : [\n      \"😀\"\n    ]\n  }\n}"
+        );
+
+        let options = FormatOptions {
+            indent: 4,
+            trailing_newline: true,
+            space_before_colon: false,
+            escape_non_ascii: true,
+            crlf: false,
+        };
+        assert_eq!(
+            to_apple_format_with(&value, &options),
+            "{\n    \"strings\": {\n        \"caf\\u00e9\": [\n            \"\\ud83d\\ude00\"\n        ]\n    }\n}\n"
+        );
+        let parsed: FormatOptions = serde_json::from_str(r#"{ "indent": 4 }"#).unwrap();
+        assert_eq!(
+            parsed,
+            FormatOptions {
+                indent: 4,
+                ..FormatOptions::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_source_style() {
+        let value = json!({ "strings": { "café": "\n" } });
+        let escaped_crlf = to_apple_format_with(
+            &value,
+            &FormatOptions {
+                escape_non_ascii: true,
+                crlf: true,
+                ..FormatOptions::default()
+            },
+        );
+        assert_eq!(
+            escaped_crlf,
+            "{\r\n  \"strings\" : {\r\n    \"caf\\u00e9\" : \"\\n\"\r\n  }\r\n}"
+        );
+        let style = SourceStyle::detect(&escaped_crlf);
+        assert_eq!(
+            style,
+            SourceStyle {
+                escape_non_ascii: Some(true),
+                crlf: Some(true),
+            }
+        );
+        assert_eq!(
+            to_apple_format_with(&value, &FormatOptions::default().with_style(style)),
+            escaped_crlf
+        );
+
+        let plain = to_apple_format(&value);
+        assert_eq!(
+            SourceStyle::detect(&plain),
+            SourceStyle {
+                escape_non_ascii: Some(false),
+                crlf: Some(false),
+            }
+        );
+        // An escaped backslash followed by `u` is not an escape.
+        assert_eq!(
+            SourceStyle::detect(r#"{"a" : "\\u00e9"}"#),
+            SourceStyle::default()
+        );
+        let configured = FormatOptions {
+            escape_non_ascii: true,
+            ..FormatOptions::default()
+        };
+        assert_eq!(configured.with_style(SourceStyle::detect("{}")), configured);
+
+        // Escapes and line endings split across reads are still found.
+        for split in 1..escaped_crlf.len() {
+            let mut detector = StyleDetector::default();
+            detector.feed(&escaped_crlf.as_bytes()[..split]);
+            detector.feed(&escaped_crlf.as_bytes()[split..]);
+            assert_eq!(detector.finish(), style, "split at {split}");
+        }
+    }
 }
@@ -0,0 +1,113 @@
+use std::{collections::BTreeMap, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use utoipa::ToSchema;
+
+/// Who works on what in one catalog, kept in a sidecar JSON file next to it so it can be
+/// committed with the catalog without touching the `.xcstrings` format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Assignments {
+    /// Language → the translator who owns it.
+    #[serde(default)]
+    pub owners: BTreeMap<String, String>,
+    /// Key → the translator it is assigned to.
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
+}
+
+impl Assignments {
+    /// Reads the sidecar at `path`; a missing file means nothing is assigned yet.
+    pub async fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path).await {
+            Ok(raw) => serde_json::from_str(&raw).map_err(io::Error::other),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Writes the sidecar, or removes it once nothing is assigned.
+    pub async fn save(&self, path: &Path) -> io::Result<()> {
+        if self.owners.is_empty() && self.keys.is_empty() {
+            return match fs::remove_file(path).await {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            };
+        }
+        let mut raw = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        raw.push('\n');
+        fs::write(path, raw).await
+    }
+
+    /// Assigns `keys` to `assignee`, or unassigns them when it is `None`.
+    pub fn assign_keys<'a>(
+        &mut self,
+        keys: impl IntoIterator<Item = &'a str>,
+        assignee: Option<&str>,
+    ) {
+        assign(&mut self.keys, keys, assignee);
+    }
+
+    /// Makes `owner` responsible for `languages`, or clears their owner when it is `None`.
+    pub fn assign_languages<'a>(
+        &mut self,
+        languages: impl IntoIterator<Item = &'a str>,
+        owner: Option<&str>,
+    ) {
+        assign(&mut self.owners, languages, owner);
+    }
+
+    /// Whether `key` is assigned to `assignee`, ignoring case.
+    pub fn is_assigned(&self, key: &str, assignee: &str) -> bool {
+        self.keys
+            .get(key)
+            .is_some_and(|current| current.eq_ignore_ascii_case(assignee.trim()))
+    }
+}
+
+fn assign<'a>(
+    map: &mut BTreeMap<String, String>,
+    items: impl IntoIterator<Item = &'a str>,
+    assignee: Option<&str>,
+) {
+    for item in items {
+        match assignee {
+            Some(assignee) => {
+                map.insert(item.to_string(), assignee.to_string());
+            }
+            None => {
+                map.remove(item);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn saves_assignments_and_removes_empty_sidecars() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("Localizable.assignments.json");
+        assert_eq!(
+            Assignments::load(&path).await.unwrap(),
+            Assignments::default()
+        );
+
+        let mut assignments = Assignments::default();
+        assignments.assign_keys(["login.title", "login.button"], Some("alice"));
+        assignments.assign_languages(["it"], Some("bruno"));
+        assignments.save(&path).await.unwrap();
+        let loaded = Assignments::load(&path).await.unwrap();
+        assert_eq!(loaded, assignments);
+        assert!(loaded.is_assigned("login.title", "Alice"));
+        assert_eq!(loaded.owners["it"], "bruno");
+
+        assignments.assign_keys(["login.title", "login.button"], None);
+        assignments.assign_languages(["it"], None);
+        assignments.save(&path).await.unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::{fs, io::AsyncWriteExt, sync::Mutex};
+use utoipa::ToSchema;
+
+/// One change recorded in the audit log, stored as a single JSON line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub timestamp: String,
+    /// Which connection made the change, e.g. an MCP session id or `stdio`.
+    pub session: String,
+    /// Client-reported name of the agent or tool behind the session, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    /// Correlation id of the HTTP request that made the change, matching the
+    /// `x-request-id` response header and request logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub details: serde_json::Value,
+}
+
+impl AuditEntry {
+    pub fn new(session: impl Into<String>, action: impl Into<String>) -> Self {
+        Self {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            session: session.into(),
+            client: None,
+            request_id: None,
+            action: action.into(),
+            path: None,
+            details: serde_json::Value::Null,
+        }
+    }
+}
+
+/// Append-only JSON Lines log of catalog changes, shared by every session.
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl AuditLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub async fn record(&self, entry: &AuditEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await
+    }
+
+    /// Reads every entry back in the order it was written, skipping lines that fail to parse.
+    pub async fn entries(&self) -> std::io::Result<Vec<AuditEntry>> {
+        let raw = match fs::read_to_string(&self.path).await {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        Ok(raw
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// The latest `limit` entries that changed the catalog at `path`, newest first, leaving
+    /// out those recorded before `since`.
+    pub async fn activity(
+        &self,
+        path: &Path,
+        since: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> std::io::Result<Vec<AuditEntry>> {
+        let mut entries = self.entries().await?;
+        entries.retain(|entry| {
+            entry.path.as_deref().map(Path::new) == Some(path)
+                && since.is_none_or(|since| {
+                    DateTime::parse_from_rfc3339(&entry.timestamp)
+                        .is_ok_and(|timestamp| timestamp >= since)
+                })
+        });
+        entries.reverse();
+        entries.truncate(limit);
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn appends_and_reads_entries() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let log = AuditLog::new(dir.path().join("audit.jsonl"));
+        assert!(log.entries().await.expect("read empty log").is_empty());
+
+        let mut first = AuditEntry::new("session-a", "upsert_translation");
+        first.client = Some("agent-a".into());
+        first.request_id = Some("req-1".into());
+        first.path = Some("Localizable.xcstrings".into());
+        first.details = serde_json::json!({ "key": "greeting", "language": "fr" });
+        let second = AuditEntry::new("session-b", "delete_key");
+
+        log.record(&first).await.expect("record first");
+        log.record(&second).await.expect("record second");
+
+        let entries = log.entries().await.expect("read log");
+        assert_eq!(entries, vec![first, second]);
+    }
+
+    #[tokio::test]
+    async fn activity_is_scoped_to_one_catalog_newest_first() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let log = AuditLog::new(dir.path().join("audit.jsonl"));
+        let entry = |timestamp: &str, action: &str, path: &str| {
+            let mut entry = AuditEntry::new("web", action);
+            entry.timestamp = timestamp.to_string();
+            entry.path = Some(path.to_string());
+            entry
+        };
+        for entry in [
+            entry("2026-01-01T09:00:00.000Z", "create_key", "/a.xcstrings"),
+            entry("2026-01-02T09:00:00.000Z", "delete_key", "/b.xcstrings"),
+            entry("2026-01-03T09:00:00.000Z", "rename_key", "/a.xcstrings"),
+            entry("2026-01-04T09:00:00.000Z", "apply_batch", "/a.xcstrings"),
+        ] {
+            log.record(&entry).await.expect("record");
+        }
+
+        let actions = |entries: Vec<AuditEntry>| -> Vec<String> {
+            entries.into_iter().map(|entry| entry.action).collect()
+        };
+        let path = Path::new("/a.xcstrings");
+        assert_eq!(
+            actions(log.activity(path, None, 10).await.expect("activity")),
+            ["apply_batch", "rename_key", "create_key"]
+        );
+        assert_eq!(
+            actions(log.activity(path, None, 1).await.expect("activity")),
+            ["apply_batch"]
+        );
+        // The same instant as the rename, in another offset.
+        let since = "2026-01-03T10:00:00+01:00".parse().expect("timestamp");
+        assert_eq!(
+            actions(log.activity(path, Some(since), 10).await.expect("activity")),
+            ["apply_batch", "rename_key"]
+        );
+    }
+}
@@ -0,0 +1,822 @@
+use std::{env, path::PathBuf};
+
+use clap::{builder::BoolishValueParser, Args, Parser, Subcommand, ValueEnum};
+
+use crate::{
+    analytics::DEFAULT_TOP,
+    export::Format,
+    qa::{QaRule, Severity},
+};
+
+#[cfg(feature = "sync")]
+use crate::sync::SyncDirection;
+
+/// Command-line options. Every flag falls back to the environment variable shown in `--help`.
+/// Without a subcommand the MCP server starts; with one, that task runs once and exits.
+#[derive(Debug, Clone, Parser)]
+#[command(
+    name = "xcstrings_mcp",
+    version,
+    about = "MCP server and web UI for Xcode String Catalogs"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Catalog to open by default; same as --path
+    #[arg(value_name = "CATALOG")]
+    pub catalog: Option<PathBuf>,
+
+    /// Catalog to open by default. Without one, catalogs are discovered under the working
+    /// directory and tools take a path argument
+    #[arg(
+        long,
+        env = "STRINGS_PATH",
+        value_name = "FILE",
+        help_heading = "Catalogs"
+    )]
+    pub path: Option<PathBuf>,
+
+    /// Only open the catalogs built by this .xcodeproj or .xcworkspace, labelled with their
+    /// targets, instead of every catalog under the working directory
+    #[arg(
+        long,
+        env = "XCODE_PROJECT",
+        value_name = "PATH",
+        help_heading = "Catalogs"
+    )]
+    pub project: Option<PathBuf>,
+
+    /// Refuse every edit from MCP tools and the web API, and hide tools that write
+    #[arg(
+        long,
+        env = "READONLY",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Catalogs"
+    )]
+    pub readonly: bool,
+
+    /// Comma-separated glob patterns of catalogs that refuse edits, added to the config
+    /// file's readOnlyPaths, e.g. Vendor/**.xcstrings
+    #[arg(
+        long,
+        env = "READONLY_PATHS",
+        value_name = "GLOBS",
+        help_heading = "Catalogs"
+    )]
+    pub readonly_paths: Option<String>,
+
+    /// Make states follow new → translated → needs_review → reviewed, with approvals and
+    /// rejections only through approve_translation and reject_translation [default: false]
+    #[arg(
+        long,
+        env = "REVIEW_WORKFLOW",
+        value_name = "BOOL",
+        help_heading = "Catalogs"
+    )]
+    pub review_workflow: Option<bool>,
+
+    /// Mark a key's translated and reviewed translations needs_review when an upsert changes
+    /// its source text, as Xcode does [default: false]
+    #[arg(
+        long,
+        env = "REVIEW_ON_SOURCE_CHANGE",
+        value_name = "BOOL",
+        help_heading = "Catalogs"
+    )]
+    pub review_on_source_change: Option<bool>,
+
+    /// Refuse to create keys without a developer comment, so translators always have
+    /// context [default: false]
+    #[arg(
+        long,
+        env = "REQUIRE_COMMENT",
+        value_name = "BOOL",
+        help_heading = "Catalogs"
+    )]
+    pub require_comment: Option<bool>,
+
+    /// Commit every change to the git repository holding its catalog, naming the tool, keys,
+    /// and client in the message [default: false]
+    #[arg(
+        long,
+        env = "GIT_AUTO_COMMIT",
+        value_name = "BOOL",
+        help_heading = "Catalogs"
+    )]
+    pub git_auto_commit: Option<bool>,
+
+    /// Poll interval in milliseconds for edits made outside the server; 0 disables it
+    #[arg(
+        long,
+        env = "WATCH_INTERVAL_MS",
+        value_name = "MS",
+        default_value_t = DEFAULT_WATCH_INTERVAL_MS,
+        help_heading = "Catalogs"
+    )]
+    pub watch_interval_ms: u64,
+
+    /// Seconds between background QA sweeps of every catalog; 0 disables them
+    #[arg(
+        long,
+        env = "QA_SWEEP_INTERVAL_SECS",
+        value_name = "SECS",
+        default_value_t = 0,
+        help_heading = "Catalogs"
+    )]
+    pub qa_sweep_interval_secs: u64,
+
+    /// Append every change to this JSON Lines file
+    #[arg(
+        long,
+        env = "AUDIT_LOG",
+        value_name = "FILE",
+        help_heading = "Catalogs"
+    )]
+    pub audit_log: Option<PathBuf>,
+
+    /// Config file; defaults to .xcstrings-mcp.json in the working directory
+    #[arg(long, env = "CONFIG", value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Web UI host; setting this or --web-port enables the web UI [default: 127.0.0.1]
+    #[arg(long, env = "WEB_HOST", value_name = "HOST", help_heading = "Web UI")]
+    pub web_host: Option<String>,
+
+    /// Web UI port; setting this or --web-host enables the web UI, and 0 picks a free port
+    /// [default: 8787]
+    #[arg(long, env = "WEB_PORT", value_name = "PORT", help_heading = "Web UI")]
+    pub web_port: Option<u16>,
+
+    /// Serve the web UI on this Unix domain socket instead of a TCP port
+    #[arg(
+        long,
+        env = "WEB_SOCKET_PATH",
+        value_name = "FILE",
+        conflicts_with_all = ["web_host", "web_port"],
+        help_heading = "Web UI"
+    )]
+    pub web_socket: Option<PathBuf>,
+
+    /// Serve web UI files from this directory, falling back to the embedded ones
+    #[arg(
+        long,
+        env = "WEB_ASSETS_DIR",
+        value_name = "DIR",
+        help_heading = "Web UI"
+    )]
+    pub web_assets_dir: Option<PathBuf>,
+
+    /// Write the web UI's URL to this file once it is listening; useful with --web-port 0
+    #[arg(
+        long,
+        env = "WEB_URL_FILE",
+        value_name = "FILE",
+        help_heading = "Web UI"
+    )]
+    pub web_url_file: Option<PathBuf>,
+
+    /// Open the web UI in the default browser once it is listening
+    #[arg(
+        long,
+        env = "WEB_OPEN",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "Web UI"
+    )]
+    pub open: bool,
+
+    /// Bearer token required by the web API
+    #[arg(
+        long,
+        env = "WEB_AUTH_TOKEN",
+        value_name = "TOKEN",
+        hide_env_values = true,
+        help_heading = "Web UI"
+    )]
+    pub web_auth_token: Option<String>,
+
+    /// Basic-auth user for the web API; requires a password
+    #[arg(
+        long,
+        env = "WEB_AUTH_USER",
+        value_name = "USER",
+        help_heading = "Web UI"
+    )]
+    pub web_auth_user: Option<String>,
+
+    /// Basic-auth password for the web API
+    #[arg(
+        long,
+        env = "WEB_AUTH_PASSWORD",
+        value_name = "PASSWORD",
+        hide_env_values = true,
+        help_heading = "Web UI"
+    )]
+    pub web_auth_password: Option<String>,
+
+    /// Secret that signs translation webhooks from a TMS; enables the webhook endpoint
+    #[arg(
+        long,
+        env = "WEBHOOK_SECRET",
+        value_name = "SECRET",
+        hide_env_values = true,
+        help_heading = "Web UI"
+    )]
+    pub webhook_secret: Option<String>,
+
+    /// PEM certificate chain for serving the web UI over HTTPS
+    #[arg(
+        long,
+        env = "WEB_TLS_CERT",
+        value_name = "FILE",
+        requires = "web_tls_key",
+        help_heading = "Web UI"
+    )]
+    pub web_tls_cert: Option<PathBuf>,
+
+    /// PEM private key for --web-tls-cert
+    #[arg(
+        long,
+        env = "WEB_TLS_KEY",
+        value_name = "FILE",
+        requires = "web_tls_cert",
+        help_heading = "Web UI"
+    )]
+    pub web_tls_key: Option<PathBuf>,
+
+    /// Serve MCP on stdin and stdout [default: true, or false when MCP over HTTP is enabled]
+    #[arg(long, env = "MCP_STDIO", value_name = "BOOL", help_heading = "MCP")]
+    pub mcp_stdio: Option<bool>,
+
+    /// Serve MCP over streamable HTTP; implied by --mcp-http-host and --mcp-http-port
+    #[arg(
+        long,
+        env = "MCP_HTTP",
+        value_parser = BoolishValueParser::new(),
+        help_heading = "MCP"
+    )]
+    pub mcp_http: bool,
+
+    /// Serve MCP over streamable HTTP on this host [default: 127.0.0.1]
+    #[arg(long, env = "MCP_HTTP_HOST", value_name = "HOST", help_heading = "MCP")]
+    pub mcp_http_host: Option<String>,
+
+    /// Serve MCP over streamable HTTP on this port [default: 8788]
+    #[arg(long, env = "MCP_HTTP_PORT", value_name = "PORT", help_heading = "MCP")]
+    pub mcp_http_port: Option<u16>,
+
+    /// PEM certificate chain for the MCP HTTP transport; defaults to the web UI's
+    #[arg(
+        long,
+        env = "MCP_HTTP_TLS_CERT",
+        value_name = "FILE",
+        requires = "mcp_http_tls_key",
+        help_heading = "MCP"
+    )]
+    pub mcp_http_tls_cert: Option<PathBuf>,
+
+    /// PEM private key for --mcp-http-tls-cert
+    #[arg(
+        long,
+        env = "MCP_HTTP_TLS_KEY",
+        value_name = "FILE",
+        requires = "mcp_http_tls_cert",
+        help_heading = "MCP"
+    )]
+    pub mcp_http_tls_key: Option<PathBuf>,
+
+    /// Comma-separated MCP tools to hide, added to the config file's list
+    #[arg(
+        long,
+        env = "DISABLED_TOOLS",
+        value_name = "TOOLS",
+        help_heading = "MCP"
+    )]
+    pub disabled_tools: Option<String>,
+
+    /// Byte budget for list tool responses; 0 disables the limit [default: 65536]
+    #[arg(
+        long,
+        env = "MAX_RESPONSE_BYTES",
+        value_name = "BYTES",
+        help_heading = "MCP"
+    )]
+    pub max_response_bytes: Option<usize>,
+
+    /// Ask the client to confirm destructive tools; set false for unattended runs [default: true]
+    #[arg(
+        long,
+        env = "CONFIRM_DESTRUCTIVE",
+        value_name = "BOOL",
+        help_heading = "MCP"
+    )]
+    pub confirm_destructive: Option<bool>,
+
+    /// Format of the log lines written to stderr or the log file
+    #[arg(
+        long,
+        env = "LOG_FORMAT",
+        value_enum,
+        default_value_t = LogFormat::Text,
+        help_heading = "Logging"
+    )]
+    pub log_format: LogFormat,
+
+    /// Write logs to this file instead of stderr, rotating it when it grows too large
+    #[arg(long, env = "LOG_FILE", value_name = "FILE", help_heading = "Logging")]
+    pub log_file: Option<PathBuf>,
+
+    /// Size at which the log file is rotated
+    #[arg(
+        long,
+        env = "LOG_FILE_MAX_BYTES",
+        value_name = "BYTES",
+        default_value_t = DEFAULT_LOG_FILE_MAX_BYTES,
+        value_parser = clap::value_parser!(u64).range(1..),
+        help_heading = "Logging"
+    )]
+    pub log_file_max_bytes: u64,
+
+    /// Rotated log files to keep, named FILE.1 (newest) to FILE.N
+    #[arg(
+        long,
+        env = "LOG_FILE_KEEP",
+        value_name = "COUNT",
+        default_value_t = DEFAULT_LOG_FILE_KEEP,
+        help_heading = "Logging"
+    )]
+    pub log_file_keep: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// One-shot tasks for scripts and CI. Each exits 0 on success, 1 when a check fails or
+/// input was skipped, and 2 on errors.
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Write translations to JSON, CSV, or .strings
+    Export(ExportArgs),
+    /// Apply translations from a JSON, CSV, or .strings file; exits 1 if some were skipped
+    Import(ImportArgs),
+    /// Run the QA checks; exits 1 when any error is found
+    Validate(ValidateArgs),
+    /// Gate a release on completion thresholds and QA errors; exits 1 when not ready
+    ReleaseCheck(ReleaseCheckArgs),
+    /// Show key counts and per-language completion
+    Stats(StatsArgs),
+    /// Show how much longer translations are than the source, and the longest ones
+    Lengths(LengthsArgs),
+    /// Compare two catalogs; exits 1 when they differ
+    Diff(DiffArgs),
+    /// Copy keys and translations missing from TARGET out of SOURCE; exits 1 on conflicts
+    Merge(MergeArgs),
+    /// Rewrite catalogs the way Xcode formats them
+    Fmt(FmtArgs),
+    /// Sort catalog keys alphabetically
+    Sort(RewriteArgs),
+    /// Push source strings to, or pull translations from, the service in the config file
+    #[cfg(feature = "sync")]
+    Sync(SyncArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ExportArgs {
+    pub catalog: PathBuf,
+    /// Languages to export, comma-separated [default: all]
+    #[arg(long, short, value_delimiter = ',')]
+    pub language: Vec<String>,
+    #[arg(long, short, value_enum, default_value_t = Format::Json)]
+    pub format: Format,
+    /// Only export values in these states, comma-separated, e.g. `translated,reviewed`
+    /// to leave out unreviewed text [default: any]
+    #[arg(long, short, value_delimiter = ',')]
+    pub state: Vec<String>,
+    /// Write to this file instead of stdout
+    #[arg(long, short, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ImportArgs {
+    pub catalog: PathBuf,
+    /// File to read translations from
+    pub input: PathBuf,
+    /// Input format [default: from the file extension]
+    #[arg(long, short, value_enum)]
+    pub format: Option<Format>,
+    /// Language of a .strings file or flat JSON, or the only CSV column to import
+    #[arg(long, short)]
+    pub language: Option<String>,
+    /// State given to imported values
+    #[arg(long, default_value = "translated")]
+    pub state: String,
+    /// Add keys that aren't in the catalog instead of skipping them
+    #[arg(long)]
+    pub create_keys: bool,
+    /// Report what would change without saving
+    #[arg(long)]
+    pub dry_run: bool,
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ValidateArgs {
+    pub catalog: PathBuf,
+    /// Rules to run, comma-separated: plural, placeholders, terminology, lint, keys, consistency, spelling [default: all]
+    #[arg(long, value_delimiter = ',', value_parser = parse_rule)]
+    pub rule: Vec<QaRule>,
+    /// Only report findings for this language
+    #[arg(long, short)]
+    pub language: Option<String>,
+    /// Fail when a finding is at least this severe
+    #[arg(long, value_enum, default_value_t = FailOn::Error)]
+    pub fail_on: FailOn,
+    /// Fail when a language is less complete than a percentage, e.g. de=90; comma-separated
+    #[arg(
+        long,
+        value_name = "LANG=PERCENT",
+        value_delimiter = ',',
+        value_parser = parse_completion
+    )]
+    pub min_completion: Vec<CompletionThreshold>,
+    #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+    pub output: ReportFormat,
+    /// Same as --output json
+    #[arg(long, conflicts_with = "output")]
+    pub json: bool,
+}
+
+impl ValidateArgs {
+    pub fn report_format(&self) -> ReportFormat {
+        if self.json {
+            ReportFormat::Json
+        } else {
+            self.output
+        }
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ReleaseCheckArgs {
+    pub catalog: PathBuf,
+    /// Completion each language needs, e.g. de=95; comma-separated [default: qa.minCompletion
+    /// from the config file, or 100% for every language]
+    #[arg(
+        long,
+        value_name = "LANG=PERCENT",
+        value_delimiter = ',',
+        value_parser = parse_completion
+    )]
+    pub min_completion: Vec<CompletionThreshold>,
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    Error,
+    Warning,
+    Info,
+    /// Only fail on completion thresholds
+    Never,
+}
+
+impl FailOn {
+    /// Whether a finding of `severity` fails the check.
+    pub fn fails(self, severity: Severity) -> bool {
+        let threshold = match self {
+            Self::Error => Severity::Error,
+            Self::Warning => Severity::Warning,
+            Self::Info => Severity::Info,
+            Self::Never => return false,
+        };
+        severity <= threshold
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// One line per finding
+    Text,
+    Json,
+    /// GitHub Actions workflow commands, shown as annotations on the run and the pull request
+    Github,
+}
+
+/// Minimum completion percentage required for a language.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionThreshold {
+    pub language: String,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct StatsArgs {
+    pub catalog: PathBuf,
+    #[arg(long)]
+    pub json: bool,
+    /// Count keys marked shouldTranslate=false too
+    #[arg(long)]
+    pub include_do_not_translate: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct LengthsArgs {
+    pub catalog: PathBuf,
+    /// Only report on this language
+    #[arg(long, short)]
+    pub language: Option<String>,
+    /// Longest translations to list per language
+    #[arg(long, default_value_t = DEFAULT_TOP)]
+    pub top: usize,
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[cfg(feature = "sync")]
+#[derive(Debug, Clone, Args)]
+pub struct SyncArgs {
+    pub direction: SyncDirection,
+    pub catalog: PathBuf,
+    /// Configured provider to use [default: the only one configured]
+    #[arg(long)]
+    pub provider: Option<String>,
+    /// Languages to pull, comma-separated [default: all but the source]
+    #[arg(long, short, value_delimiter = ',')]
+    pub language: Vec<String>,
+    /// Show what would change without uploading or writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct DiffArgs {
+    pub before: PathBuf,
+    pub after: PathBuf,
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct MergeArgs {
+    pub target: PathBuf,
+    pub source: PathBuf,
+    /// Replace translations that differ instead of keeping the target's
+    #[arg(long)]
+    pub overwrite: bool,
+    /// Report what would change without saving
+    #[arg(long)]
+    pub dry_run: bool,
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct RewriteArgs {
+    #[arg(required = true)]
+    pub catalogs: Vec<PathBuf>,
+    /// Only report files that would change; exits 1 if any would
+    #[arg(long)]
+    pub check: bool,
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct FmtArgs {
+    #[command(flatten)]
+    pub rewrite: RewriteArgs,
+    /// Also sort keys alphabetically
+    #[arg(long)]
+    pub sort: bool,
+}
+
+fn parse_rule(raw: &str) -> Result<QaRule, String> {
+    serde_json::from_value(serde_json::Value::String(raw.trim().to_string())).map_err(|_| {
+        "expected plural, placeholders, terminology, lint, keys, consistency, or spelling"
+            .to_string()
+    })
+}
+
+fn parse_completion(raw: &str) -> Result<CompletionThreshold, String> {
+    let (language, percentage) = raw
+        .split_once('=')
+        .ok_or_else(|| "expected LANG=PERCENT, e.g. de=90".to_string())?;
+    let percentage: f64 = percentage
+        .trim()
+        .trim_end_matches('%')
+        .parse()
+        .map_err(|_| format!("'{percentage}' is not a number"))?;
+    if !(0.0..=100.0).contains(&percentage) || language.trim().is_empty() {
+        return Err("expected a language and a percentage from 0 to 100".to_string());
+    }
+    Ok(CompletionThreshold {
+        language: language.trim().to_string(),
+        percentage,
+    })
+}
+
+pub const DEFAULT_WATCH_INTERVAL_MS: u64 = 2000;
+pub const DEFAULT_LOG_FILE_MAX_BYTES: u64 = 10 * 1024 * 1024;
+pub const DEFAULT_LOG_FILE_KEEP: usize = 5;
+
+/// Environment variables that were read before the prefix was dropped, paired with their
+/// current names.
+const LEGACY_ENV_VARS: &[(&str, &str)] = &[
+    ("XCSTRINGS_PATH", "STRINGS_PATH"),
+    ("XCSTRINGS_WATCH_INTERVAL_MS", "WATCH_INTERVAL_MS"),
+    ("XCSTRINGS_AUDIT_LOG", "AUDIT_LOG"),
+    ("XCSTRINGS_CONFIG", "CONFIG"),
+    ("XCSTRINGS_WEB_HOST", "WEB_HOST"),
+    ("XCSTRINGS_WEB_PORT", "WEB_PORT"),
+    ("XCSTRINGS_WEB_SOCKET_PATH", "WEB_SOCKET_PATH"),
+    ("XCSTRINGS_WEB_ASSETS_DIR", "WEB_ASSETS_DIR"),
+    ("XCSTRINGS_WEB_AUTH_TOKEN", "WEB_AUTH_TOKEN"),
+    ("XCSTRINGS_WEB_AUTH_USER", "WEB_AUTH_USER"),
+    ("XCSTRINGS_WEB_AUTH_PASSWORD", "WEB_AUTH_PASSWORD"),
+    ("XCSTRINGS_WEB_TLS_CERT", "WEB_TLS_CERT"),
+    ("XCSTRINGS_WEB_TLS_KEY", "WEB_TLS_KEY"),
+    ("XCSTRINGS_MCP_HTTP_HOST", "MCP_HTTP_HOST"),
+    ("XCSTRINGS_MCP_HTTP_PORT", "MCP_HTTP_PORT"),
+    ("XCSTRINGS_MCP_HTTP_TLS_CERT", "MCP_HTTP_TLS_CERT"),
+    ("XCSTRINGS_MCP_HTTP_TLS_KEY", "MCP_HTTP_TLS_KEY"),
+    ("XCSTRINGS_DISABLED_TOOLS", "DISABLED_TOOLS"),
+    ("XCSTRINGS_MAX_RESPONSE_BYTES", "MAX_RESPONSE_BYTES"),
+    ("XCSTRINGS_CONFIRM_DESTRUCTIVE", "CONFIRM_DESTRUCTIVE"),
+];
+
+/// Copies each legacy `XCSTRINGS_*` variable to its current name when that isn't set, so
+/// [`Cli::parse`] sees it. Call this before starting any threads.
+pub fn apply_legacy_env() {
+    for (legacy, primary) in LEGACY_ENV_VARS {
+        if env::var_os(primary).is_none() {
+            if let Some(value) = env::var_os(legacy) {
+                env::set_var(primary, value);
+            }
+        }
+    }
+}
+
+impl Cli {
+    /// The default catalog, preferring `--path` over the positional argument.
+    pub fn catalog_path(&self) -> Option<PathBuf> {
+        self.path.clone().or_else(|| self.catalog.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn command_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_flags() {
+        let cli = Cli::try_parse_from([
+            "xcstrings_mcp",
+            "--path",
+            "App/Localizable.xcstrings",
+            "--web-port",
+            "9000",
+            "--readonly",
+            "--log-format",
+            "json",
+            "--log-file",
+            "logs/server.log",
+            "--confirm-destructive",
+            "false",
+            "--mcp-http",
+            "--mcp-stdio",
+            "true",
+            "--",
+            "Other.xcstrings",
+        ])
+        .expect("parse flags");
+        assert_eq!(
+            cli.catalog_path(),
+            Some(PathBuf::from("App/Localizable.xcstrings"))
+        );
+        assert_eq!(cli.web_port, Some(9000));
+        assert!(cli.web_host.is_none());
+        assert!(cli.readonly);
+        assert_eq!(cli.log_format, LogFormat::Json);
+        assert_eq!(cli.log_file, Some(PathBuf::from("logs/server.log")));
+        assert_eq!(cli.log_file_max_bytes, DEFAULT_LOG_FILE_MAX_BYTES);
+        assert_eq!(cli.confirm_destructive, Some(false));
+        assert!(cli.mcp_http);
+        assert_eq!(cli.mcp_stdio, Some(true));
+        assert_eq!(cli.watch_interval_ms, DEFAULT_WATCH_INTERVAL_MS);
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        let cli = Cli::try_parse_from([
+            "xcstrings_mcp",
+            "validate",
+            "Localizable.xcstrings",
+            "--rule",
+            "plural,lint",
+            "--json",
+        ])
+        .expect("parse validate");
+        let Some(Command::Validate(args)) = cli.command else {
+            panic!("expected validate, got {:?}", cli.command);
+        };
+        assert_eq!(args.rule, vec![QaRule::Plural, QaRule::Lint]);
+        assert_eq!(args.report_format(), ReportFormat::Json);
+        assert_eq!(args.fail_on, FailOn::Error);
+
+        let cli = Cli::try_parse_from([
+            "xcstrings_mcp",
+            "validate",
+            "Localizable.xcstrings",
+            "--fail-on",
+            "warning",
+            "--min-completion",
+            "de=90,fr=75.5%",
+            "--output",
+            "github",
+        ])
+        .expect("parse validate thresholds");
+        let Some(Command::Validate(args)) = cli.command else {
+            panic!("expected validate, got {:?}", cli.command);
+        };
+        assert!(args.fail_on.fails(Severity::Error));
+        assert!(!args.fail_on.fails(Severity::Info));
+        assert_eq!(
+            args.min_completion,
+            vec![
+                CompletionThreshold {
+                    language: "de".into(),
+                    percentage: 90.0
+                },
+                CompletionThreshold {
+                    language: "fr".into(),
+                    percentage: 75.5
+                },
+            ]
+        );
+        assert_eq!(args.report_format(), ReportFormat::Github);
+
+        let cli = Cli::try_parse_from(["xcstrings_mcp", "fmt", "a.xcstrings", "b.xcstrings"])
+            .expect("parse fmt");
+        assert!(
+            matches!(cli.command, Some(Command::Fmt(args)) if args.rewrite.catalogs.len() == 2 && !args.sort)
+        );
+        assert!(Cli::try_parse_from(["xcstrings_mcp", "sort"]).is_err());
+        assert!(Cli::try_parse_from([
+            "xcstrings_mcp",
+            "validate",
+            "a.xcstrings",
+            "--rule",
+            "grammar"
+        ])
+        .is_err());
+        for threshold in ["de", "de=ninety", "de=120", "=90"] {
+            assert!(
+                Cli::try_parse_from([
+                    "xcstrings_mcp",
+                    "validate",
+                    "a.xcstrings",
+                    "--min-completion",
+                    threshold
+                ])
+                .is_err(),
+                "{threshold} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_conflicting_and_incomplete_flags() {
+        for args in [
+            &[
+                "xcstrings_mcp",
+                "--web-socket",
+                "web.sock",
+                "--web-port",
+                "9000",
+            ][..],
+            &["xcstrings_mcp", "--web-tls-cert", "cert.pem"][..],
+            &["xcstrings_mcp", "--log-format", "xml"][..],
+            &["xcstrings_mcp", "--log-file-max-bytes", "0"][..],
+        ] {
+            assert!(Cli::try_parse_from(args).is_err(), "{args:?} should fail");
+        }
+    }
+}
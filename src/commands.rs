@@ -0,0 +1,1038 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    fmt::Write as _,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{bail, Context};
+use serde::Serialize;
+use tokio::fs;
+
+#[cfg(feature = "sync")]
+use crate::{
+    cli::SyncArgs,
+    sync::{self, SyncDirection},
+};
+use crate::{
+    cli::{
+        Command, DiffArgs, ExportArgs, ImportArgs, LengthsArgs, MergeArgs, ReleaseCheckArgs,
+        ReportFormat, RewriteArgs, StatsArgs, ValidateArgs,
+    },
+    config::ServerConfig,
+    diff::{self, CatalogDiff},
+    export::{self, Format},
+    qa::{Finding, QaReport, Severity},
+    state,
+    store::{
+        BatchOperation, InsertPosition, MergeOutcome, StoreError, TranslationRecord,
+        TranslationUpdate, TranslationValue, XcStringsStore,
+    },
+};
+
+/// Exit status when a subcommand couldn't run, as opposed to a check that failed.
+pub const EXIT_ERROR: u8 = 2;
+
+/// What a subcommand prints on stdout and whether its check passed.
+#[derive(Debug)]
+pub struct CommandOutput {
+    pub stdout: String,
+    pub success: bool,
+}
+
+impl CommandOutput {
+    fn new(stdout: String, success: bool) -> Self {
+        Self { stdout, success }
+    }
+
+    fn json(value: &impl Serialize, success: bool) -> anyhow::Result<Self> {
+        Ok(Self::new(
+            serde_json::to_string_pretty(value)? + "\n",
+            success,
+        ))
+    }
+
+    pub fn exit_code(&self) -> ExitCode {
+        if self.success {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Runs a subcommand once. `config` is the explicit config file, which supplies the QA
+/// glossary for `validate`, the services for `sync`, and the layout of written catalogs.
+pub async fn run(command: Command, config: Option<&Path>) -> anyhow::Result<CommandOutput> {
+    let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let (config, _) = ServerConfig::discover(config, &cwd)?;
+    let config = &config;
+    match command {
+        Command::Export(args) => export(args, config).await,
+        Command::Import(args) => import(args, config).await,
+        Command::Validate(args) => validate(args, config).await,
+        Command::ReleaseCheck(args) => release_check(args, config).await,
+        Command::Stats(args) => stats(args, config).await,
+        Command::Lengths(args) => lengths(args, config).await,
+        Command::Diff(args) => diff(args, config).await,
+        Command::Merge(args) => merge(args, config).await,
+        Command::Fmt(args) => {
+            rewrite(args.rewrite, Rewrite::Format { sort: args.sort }, config).await
+        }
+        Command::Sort(args) => rewrite(args, Rewrite::Sort, config).await,
+        #[cfg(feature = "sync")]
+        Command::Sync(args) => sync(args, config).await,
+    }
+}
+
+/// Loads an existing catalog; unlike the server, the CLI never starts from an empty one.
+async fn open(path: &Path, config: &ServerConfig) -> anyhow::Result<XcStringsStore> {
+    if !path.is_file() {
+        bail!("{} is not a file", path.display());
+    }
+    Ok(XcStringsStore::load_or_create(path)
+        .await
+        .with_context(|| format!("failed to load {}", path.display()))?
+        .with_format_options(config.format.clone())
+        .with_state_validation(config.state_validation)
+        .with_placeholder_validation(config.placeholder_validation))
+}
+
+/// The source language first, then the others alphabetically.
+async fn languages(store: &XcStringsStore) -> Vec<String> {
+    let source = store.source_language().await;
+    let mut languages = store.list_languages().await;
+    languages.retain(|language| *language != source);
+    languages.insert(0, source);
+    languages
+}
+
+/// Serialized name of a QA rule or severity, e.g. `placeholders`.
+fn label(value: &impl Serialize) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+async fn export(args: ExportArgs, config: &ServerConfig) -> anyhow::Result<CommandOutput> {
+    let store = open(&args.catalog, config).await?;
+    let languages = if args.language.is_empty() {
+        languages(&store).await
+    } else {
+        args.language
+    };
+    let states = args
+        .state
+        .iter()
+        .map(|name| {
+            state::normalize(name).map(str::to_string).with_context(|| {
+                format!(
+                    "unknown state '{name}'; expected one of {}",
+                    state::KNOWN_STATES.join(", ")
+                )
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let records = store.list_records(None).await;
+    let rendered = export::export(&records, &languages, args.format, &states)?;
+    match args.output {
+        Some(path) => {
+            fs::write(&path, rendered)
+                .await
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            Ok(CommandOutput::new(
+                format!("Exported {} keys to {}\n", records.len(), path.display()),
+                true,
+            ))
+        }
+        None => Ok(CommandOutput::new(rendered, true)),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct TranslationRef {
+    key: String,
+    language: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ImportReport {
+    dry_run: bool,
+    imported: Vec<TranslationRef>,
+    unchanged: usize,
+    /// Keys not in the catalog, skipped because `--create-keys` wasn't given.
+    skipped_keys: Vec<String>,
+}
+
+async fn import(args: ImportArgs, config: &ServerConfig) -> anyhow::Result<CommandOutput> {
+    let store = open(&args.catalog, config).await?;
+    let format = args
+        .format
+        .or_else(|| Format::from_path(&args.input))
+        .with_context(|| {
+            format!(
+                "can't tell the format of {}; pass --format",
+                args.input.display()
+            )
+        })?;
+    let input = fs::read_to_string(&args.input)
+        .await
+        .with_context(|| format!("failed to read {}", args.input.display()))?;
+    let values = export::parse(&input, format, args.language.as_deref())
+        .with_context(|| format!("failed to parse {}", args.input.display()))?;
+
+    let records: HashMap<String, TranslationRecord> = store
+        .list_records(None)
+        .await
+        .into_iter()
+        .map(|record| (record.key.clone(), record))
+        .collect();
+    let mut report = ImportReport {
+        dry_run: args.dry_run,
+        ..ImportReport::default()
+    };
+    let mut operations = Vec::new();
+    for imported in values {
+        let current = match records.get(&imported.key) {
+            Some(record) => record
+                .translations
+                .get(&imported.language)
+                .and_then(|translation| translation.value.as_deref()),
+            None if args.create_keys => None,
+            None => {
+                if !report.skipped_keys.contains(&imported.key) {
+                    report.skipped_keys.push(imported.key);
+                }
+                continue;
+            }
+        };
+        if current == Some(imported.value.as_str()) {
+            report.unchanged += 1;
+            continue;
+        }
+        report.imported.push(TranslationRef {
+            key: imported.key.clone(),
+            language: imported.language.clone(),
+        });
+        operations.push(BatchOperation::Upsert {
+            key: imported.key,
+            language: imported.language,
+            comment: None,
+            position: InsertPosition::End,
+            update: Box::new(TranslationUpdate::from_value_state(
+                Some(imported.value),
+                Some(args.state.clone()),
+            )),
+        });
+    }
+
+    if !args.dry_run && !operations.is_empty() {
+        let outcome = store.apply_batch(operations).await?;
+        if let Some(Err(err)) = outcome.results.into_iter().find(Result::is_err) {
+            return Err(err.into());
+        }
+    }
+
+    let success = report.skipped_keys.is_empty();
+    if args.json {
+        return CommandOutput::json(&report, success);
+    }
+    let mut out = format!(
+        "{} {} translations ({} unchanged)\n",
+        if args.dry_run {
+            "Would import"
+        } else {
+            "Imported"
+        },
+        report.imported.len(),
+        report.unchanged
+    );
+    if !report.skipped_keys.is_empty() {
+        let _ = writeln!(
+            out,
+            "Skipped {} keys not in the catalog (pass --create-keys to add them): {}",
+            report.skipped_keys.len(),
+            report.skipped_keys.join(", ")
+        );
+    }
+    Ok(CommandOutput::new(out, success))
+}
+
+/// Outcome of one `--min-completion` threshold.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CompletionCheck {
+    language: String,
+    percentage: f64,
+    required: f64,
+    passed: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ValidateReport {
+    #[serde(flatten)]
+    report: QaReport,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    completion: Vec<CompletionCheck>,
+    passed: bool,
+}
+
+async fn validate(args: ValidateArgs, config: &ServerConfig) -> anyhow::Result<CommandOutput> {
+    let store = open(&args.catalog, config).await?;
+    let known_languages = store.list_languages().await;
+    let check_language = |language: &String| {
+        if known_languages.contains(language) {
+            Ok(())
+        } else {
+            Err(StoreError::LanguageMissing(language.clone()))
+        }
+    };
+
+    let mut report = store.validate(&args.rule, &config.qa).await;
+    if let Some(language) = &args.language {
+        check_language(language)?;
+        report.retain(|finding| finding.language.as_deref() == Some(language));
+    }
+    let percentages = store.get_translation_percentages().await;
+    let mut completion = Vec::new();
+    for threshold in &args.min_completion {
+        check_language(&threshold.language)?;
+        let percentage = percentages
+            .get(&threshold.language)
+            .copied()
+            .unwrap_or_default();
+        completion.push(CompletionCheck {
+            language: threshold.language.clone(),
+            percentage,
+            required: threshold.percentage,
+            passed: percentage >= threshold.percentage,
+        });
+    }
+
+    let passed = findings(&report).all(|finding| !args.fail_on.fails(finding.severity))
+        && completion.iter().all(|check| check.passed);
+    let result = ValidateReport {
+        report,
+        completion,
+        passed,
+    };
+    match args.report_format() {
+        ReportFormat::Json => CommandOutput::json(&result, passed),
+        ReportFormat::Text => Ok(CommandOutput::new(render_validation(&result), passed)),
+        ReportFormat::Github => {
+            let text = fs::read_to_string(&args.catalog).await?;
+            let file = args.catalog.display().to_string();
+            Ok(CommandOutput::new(
+                render_annotations(&result, &file, &key_lines(&text)),
+                passed,
+            ))
+        }
+    }
+}
+
+fn findings(report: &QaReport) -> impl Iterator<Item = &Finding> {
+    report
+        .errors
+        .iter()
+        .chain(&report.warnings)
+        .chain(&report.info)
+}
+
+fn render_validation(result: &ValidateReport) -> String {
+    let mut out = String::new();
+    for finding in findings(&result.report) {
+        write_finding(&mut out, finding);
+    }
+    for check in result.completion.iter().filter(|check| !check.passed) {
+        let _ = writeln!(out, "{}", completion_message(check));
+    }
+    let summary = &result.report.summary;
+    let _ = writeln!(
+        out,
+        "{} errors, {} warnings, {} info",
+        summary.errors, summary.warnings, summary.info
+    );
+    out
+}
+
+fn write_finding(out: &mut String, finding: &Finding) {
+    let _ = write!(out, "{}: {}", label(&finding.severity), finding.key);
+    if let Some(language) = &finding.language {
+        let _ = write!(out, " [{language}]");
+    }
+    if let Some(location) = &finding.location {
+        let _ = write!(out, " {location}");
+    }
+    let _ = writeln!(out, " ({}): {}", label(&finding.rule), finding.message);
+}
+
+fn completion_message(check: &CompletionCheck) -> String {
+    format!(
+        "{} is {:.1}% translated, below the required {}%",
+        check.language, check.percentage, check.required
+    )
+}
+
+/// Renders findings as GitHub Actions workflow commands, which show up as annotations
+/// on the catalog in pull requests.
+fn render_annotations(
+    result: &ValidateReport,
+    file: &str,
+    lines: &HashMap<String, usize>,
+) -> String {
+    let mut out = String::new();
+    for finding in findings(&result.report) {
+        let level = match finding.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "notice",
+        };
+        let mut message = finding.key.clone();
+        if let Some(language) = &finding.language {
+            let _ = write!(message, " [{language}]");
+        }
+        if let Some(location) = &finding.location {
+            let _ = write!(message, " {location}");
+        }
+        let _ = write!(message, ": {}", finding.message);
+        let line = lines
+            .get(&finding.key)
+            .map(|line| format!(",line={line}"))
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "::{level} file={}{line},title={}::{}",
+            escape_property(file),
+            escape_property(&format!("xcstrings {}", label(&finding.rule))),
+            escape_data(&message)
+        );
+    }
+    for check in result.completion.iter().filter(|check| !check.passed) {
+        let _ = writeln!(
+            out,
+            "::error file={},title={}::{}",
+            escape_property(file),
+            escape_property("xcstrings completion"),
+            escape_data(&completion_message(check))
+        );
+    }
+    let summary = &result.report.summary;
+    let _ = writeln!(
+        out,
+        "{} errors, {} warnings, {} info",
+        summary.errors, summary.warnings, summary.info
+    );
+    out
+}
+
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// 1-based line of each key's entry in a catalog formatted the way Xcode writes it, where
+/// keys under `strings` are the only members indented by four spaces.
+fn key_lines(text: &str) -> HashMap<String, usize> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let entry = line
+                .strip_prefix("    ")
+                .filter(|rest| rest.starts_with('"'))?;
+            let key = serde_json::Deserializer::from_str(entry)
+                .into_iter::<String>()
+                .next()?
+                .ok()?;
+            Some((key, index + 1))
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+struct Stats {
+    source_language: String,
+    keys: usize,
+    languages: Vec<LanguageStats>,
+}
+
+#[derive(Debug, Serialize)]
+struct LanguageStats {
+    language: String,
+    /// Share of translatable keys with a value, from 0 to 100.
+    percentage: f64,
+    untranslated: usize,
+}
+
+async fn release_check(
+    args: ReleaseCheckArgs,
+    config: &ServerConfig,
+) -> anyhow::Result<CommandOutput> {
+    let store = open(&args.catalog, config).await?;
+    let thresholds: BTreeMap<String, f64> = args
+        .min_completion
+        .into_iter()
+        .map(|threshold| (threshold.language, threshold.percentage))
+        .collect();
+    let readiness = store.release_readiness(&thresholds, &config.qa).await;
+    if args.json {
+        return CommandOutput::json(&readiness, readiness.ready);
+    }
+
+    let mut out = String::new();
+    for finding in &readiness.errors {
+        write_finding(&mut out, finding);
+    }
+    for language in &readiness.languages {
+        let _ = writeln!(
+            out,
+            "{}: {:.1}% translated, {}% required",
+            language.language, language.completion, language.required
+        );
+    }
+    if readiness.ready {
+        out.push_str("Ready for release\n");
+    } else {
+        let _ = writeln!(
+            out,
+            "Not ready for release: {}",
+            readiness.problems().join("; ")
+        );
+    }
+    Ok(CommandOutput::new(out, readiness.ready))
+}
+
+async fn stats(args: StatsArgs, config: &ServerConfig) -> anyhow::Result<CommandOutput> {
+    let store = open(&args.catalog, config).await?;
+    let percentages = store
+        .get_translation_percentages_with(args.include_do_not_translate)
+        .await;
+    let untranslated = store
+        .list_untranslated_with(args.include_do_not_translate)
+        .await;
+    let stats = Stats {
+        source_language: store.source_language().await,
+        keys: store.keys().await.len(),
+        languages: languages(&store)
+            .await
+            .into_iter()
+            .map(|language| LanguageStats {
+                percentage: percentages.get(&language).copied().unwrap_or_default(),
+                untranslated: untranslated.get(&language).map_or(0, Vec::len),
+                language,
+            })
+            .collect(),
+    };
+
+    if args.json {
+        return CommandOutput::json(&stats, true);
+    }
+    let mut out = format!(
+        "{} keys, source language {}\n",
+        stats.keys, stats.source_language
+    );
+    let width = stats
+        .languages
+        .iter()
+        .map(|language| language.language.len())
+        .max()
+        .unwrap_or_default();
+    for language in &stats.languages {
+        let _ = writeln!(
+            out,
+            "{:<width$}  {:>5.1}%  {} untranslated",
+            language.language, language.percentage, language.untranslated
+        );
+    }
+    Ok(CommandOutput::new(out, true))
+}
+
+async fn lengths(args: LengthsArgs, config: &ServerConfig) -> anyhow::Result<CommandOutput> {
+    let store = open(&args.catalog, config).await?;
+    let mut report = store.length_report(args.top).await;
+    if let Some(language) = &args.language {
+        report
+            .languages
+            .retain(|lengths| lengths.language == *language);
+    }
+
+    if args.json {
+        return CommandOutput::json(&report, true);
+    }
+    let mut out = String::new();
+    for language in &report.languages {
+        let _ = writeln!(
+            out,
+            "{}: average {:.2}x, max {:.2}x over {} texts",
+            language.language, language.average_ratio, language.max_ratio, language.compared
+        );
+        for text in &language.longest {
+            let key = match text.variation.as_str() {
+                "" => text.key.clone(),
+                variation => format!("{} [{variation}]", text.key),
+            };
+            let _ = writeln!(
+                out,
+                "  {:>5.2}x  {key}: {:?} -> {:?}",
+                text.ratio, text.source, text.translation
+            );
+        }
+    }
+    Ok(CommandOutput::new(out, true))
+}
+
+async fn diff(args: DiffArgs, config: &ServerConfig) -> anyhow::Result<CommandOutput> {
+    let before = open(&args.before, config).await?.list_records(None).await;
+    let after = open(&args.after, config).await?.list_records(None).await;
+    let diff = diff::diff_records(&before, &after);
+    let success = diff.is_empty();
+    if args.json {
+        return CommandOutput::json(&diff, success);
+    }
+    Ok(CommandOutput::new(render_diff(&diff), success))
+}
+
+fn render_diff(diff: &CatalogDiff) -> String {
+    fn describe(value: Option<&TranslationValue>) -> String {
+        match value {
+            None => "(missing)".to_string(),
+            Some(value) => match &value.value {
+                Some(text) => format!("{text:?}"),
+                None if !value.variations.is_empty() => "(variations)".to_string(),
+                None => "(empty)".to_string(),
+            },
+        }
+    }
+
+    let mut out = String::new();
+    for key in &diff.added_keys {
+        let _ = writeln!(out, "+ {key}");
+    }
+    for key in &diff.removed_keys {
+        let _ = writeln!(out, "- {key}");
+    }
+    for key in &diff.changed_keys {
+        let _ = writeln!(out, "~ {}", key.key);
+        if let Some(comment) = &key.comment {
+            let _ = writeln!(
+                out,
+                "    comment: {:?} -> {:?}",
+                comment.before.as_deref().unwrap_or_default(),
+                comment.after.as_deref().unwrap_or_default()
+            );
+        }
+        if let Some(should_translate) = &key.should_translate {
+            let _ = writeln!(
+                out,
+                "    shouldTranslate: {:?} -> {:?}",
+                should_translate.before, should_translate.after
+            );
+        }
+        for translation in &key.translations {
+            let _ = writeln!(
+                out,
+                "    {}: {} -> {}",
+                translation.language,
+                describe(translation.before.as_ref()),
+                describe(translation.after.as_ref())
+            );
+        }
+    }
+    if diff.is_empty() {
+        out.push_str("No differences\n");
+    }
+    out
+}
+
+#[cfg(feature = "sync")]
+async fn sync(args: SyncArgs, config: &ServerConfig) -> anyhow::Result<CommandOutput> {
+    let store = open(&args.catalog, config).await?;
+    let provider = config.sync.provider(args.provider.as_deref())?;
+    let report = match args.direction {
+        SyncDirection::Push => sync::push(&store, provider.as_ref(), args.dry_run).await?,
+        SyncDirection::Pull => {
+            sync::pull(&store, provider.as_ref(), &args.language, args.dry_run).await?
+        }
+    };
+    if args.json {
+        return CommandOutput::json(&report, true);
+    }
+
+    let prefix = if args.dry_run { "would " } else { "" };
+    let mut out = String::new();
+    for key in &report.added {
+        let _ = writeln!(out, "{prefix}add {key}");
+    }
+    for key in &report.updated {
+        let _ = writeln!(out, "{prefix}update {key}");
+    }
+    for key in &report.skipped {
+        let _ = writeln!(out, "skip {key} (source text only in variations)");
+    }
+    for translation in &report.pulled {
+        let _ = writeln!(
+            out,
+            "{prefix}pull {} ({}): {:?} [{}]",
+            translation.key, translation.language, translation.value, translation.state
+        );
+    }
+    for key in &report.unknown_keys {
+        let _ = writeln!(out, "unknown key {key}");
+    }
+    let _ = writeln!(out, "{} unchanged", report.unchanged);
+    Ok(CommandOutput::new(out, true))
+}
+
+async fn merge(args: MergeArgs, config: &ServerConfig) -> anyhow::Result<CommandOutput> {
+    let target = open(&args.target, config).await?;
+    let source = open(&args.source, config).await?;
+    let outcome: MergeOutcome = target
+        .merge_from(&source, args.overwrite, !args.dry_run)
+        .await?;
+    let success = outcome.conflicts.is_empty();
+    if args.json {
+        return CommandOutput::json(&outcome, success);
+    }
+    let mut out = format!(
+        "{} {} keys and {} translations\n",
+        if args.dry_run { "Would add" } else { "Added" },
+        outcome.added_keys.len(),
+        outcome.merged.len()
+    );
+    for conflict in &outcome.conflicts {
+        let _ = writeln!(
+            out,
+            "Kept {} [{}]; the source differs (pass --overwrite to replace it)",
+            conflict.key, conflict.language
+        );
+    }
+    Ok(CommandOutput::new(out, success))
+}
+
+#[derive(Clone, Copy)]
+enum Rewrite {
+    Format { sort: bool },
+    Sort,
+}
+
+#[derive(Debug, Serialize)]
+struct RewrittenFile {
+    path: PathBuf,
+    changed: bool,
+}
+
+async fn rewrite(
+    args: RewriteArgs,
+    rewrite: Rewrite,
+    config: &ServerConfig,
+) -> anyhow::Result<CommandOutput> {
+    let mut files = Vec::new();
+    for path in args.catalogs {
+        let store = open(&path, config).await?;
+        let changed = match rewrite {
+            Rewrite::Format { sort } => {
+                let sorted = sort && store.sort_keys(!args.check).await?;
+                store.reformat(!args.check).await? || sorted
+            }
+            Rewrite::Sort => store.sort_keys(!args.check).await?,
+        };
+        files.push(RewrittenFile { path, changed });
+    }
+
+    let success = !args.check || files.iter().all(|file| !file.changed);
+    if args.json {
+        return CommandOutput::json(&files, success);
+    }
+    let verb = match (rewrite, args.check) {
+        (Rewrite::Format { .. }, true) => "Would reformat",
+        (Rewrite::Format { .. }, false) => "Reformatted",
+        (Rewrite::Sort, true) => "Would sort",
+        (Rewrite::Sort, false) => "Sorted",
+    };
+    let mut out = String::new();
+    for file in files.iter().filter(|file| file.changed) {
+        let _ = writeln!(out, "{verb} {}", file.path.display());
+    }
+    let changed = files.iter().filter(|file| file.changed).count();
+    let _ = writeln!(
+        out,
+        "{changed} of {} files {}",
+        files.len(),
+        if args.check {
+            "would change"
+        } else {
+            "changed"
+        }
+    );
+    Ok(CommandOutput::new(out, success))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::cli::Cli;
+
+    const CATALOG: &str = r#"{
+  "sourceLanguage" : "en",
+  "strings" : {
+    "zeta" : {
+      "localizations" : {
+        "en" : { "stringUnit" : { "state" : "translated", "value" : "Zeta %@" } },
+        "fr" : { "stringUnit" : { "state" : "translated", "value" : "Zêta %d" } }
+      }
+    },
+    "alpha" : {
+      "localizations" : {
+        "en" : { "stringUnit" : { "state" : "translated", "value" : "Alpha" } }
+      }
+    }
+  },
+  "version" : "1.0"
+}"#;
+
+    async fn run_args(args: &[&str]) -> anyhow::Result<CommandOutput> {
+        let cli = Cli::try_parse_from(["xcstrings_mcp"].iter().chain(args))?;
+        run(cli.command.expect("subcommand"), None).await
+    }
+
+    #[tokio::test]
+    async fn checks_report_failures_through_success_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Localizable.xcstrings");
+        std::fs::write(&path, CATALOG).unwrap();
+        let path = path.to_str().unwrap();
+
+        let validated = run_args(&["validate", path, "--json"]).await.unwrap();
+        assert!(!validated.success);
+        let report: serde_json::Value = serde_json::from_str(&validated.stdout).unwrap();
+        assert_eq!(report["summary"]["errors"], 1);
+        assert_eq!(report["errors"][0]["rule"], "placeholders");
+
+        for command in [&["fmt"][..], &["fmt", "--sort"], &["sort"]] {
+            let checked = run_args(&[command, &["--check", path]].concat())
+                .await
+                .unwrap();
+            assert!(!checked.success, "{command:?} --check");
+            assert_eq!(std::fs::read_to_string(path).unwrap(), CATALOG);
+        }
+        assert!(run_args(&["sort", path]).await.unwrap().success);
+        assert!(
+            run_args(&["fmt", "--sort", "--check", path])
+                .await
+                .unwrap()
+                .success
+        );
+        let stats = run_args(&["stats", path, "--json"]).await.unwrap();
+        let stats: serde_json::Value = serde_json::from_str(&stats.stdout).unwrap();
+        assert_eq!(stats["keys"], 2);
+        assert_eq!(stats["languages"][1]["language"], "fr");
+        assert_eq!(stats["languages"][1]["untranslated"], 1);
+        let lengths = run_args(&["lengths", path, "--language", "fr"])
+            .await
+            .unwrap();
+        assert!(
+            lengths.stdout.starts_with("fr: average "),
+            "{}",
+            lengths.stdout
+        );
+
+        let missing = dir.path().join("Missing.xcstrings");
+        assert!(run_args(&["stats", missing.to_str().unwrap()])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn fmt_uses_configured_formatting() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Localizable.xcstrings");
+        std::fs::write(&path, CATALOG).unwrap();
+        let config = dir.path().join("config.json");
+        std::fs::write(
+            &config,
+            r#"{ "format": { "indent": 4, "trailingNewline": true, "spaceBeforeColon": false } }"#,
+        )
+        .unwrap();
+
+        let cli = Cli::try_parse_from(["xcstrings_mcp", "fmt", path.to_str().unwrap()]).unwrap();
+        let output = run(cli.command.expect("subcommand"), Some(&config))
+            .await
+            .unwrap();
+        assert!(output.success);
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(
+            written.starts_with("{\n    \"sourceLanguage\": \"en\",\n"),
+            "{written}"
+        );
+        assert!(written.ends_with("}\n"));
+    }
+
+    #[tokio::test]
+    async fn validate_thresholds_and_annotations() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Localizable.xcstrings");
+        std::fs::write(&path, CATALOG).unwrap();
+        let path = path.to_str().unwrap();
+
+        let lenient = run_args(&[
+            "validate",
+            path,
+            "--fail-on",
+            "never",
+            "--min-completion",
+            "fr=50",
+        ])
+        .await
+        .unwrap();
+        assert!(lenient.success, "{}", lenient.stdout);
+
+        let strict = run_args(&[
+            "validate",
+            path,
+            "--fail-on",
+            "never",
+            "--min-completion",
+            "fr=60",
+            "--json",
+        ])
+        .await
+        .unwrap();
+        assert!(!strict.success);
+        let report: serde_json::Value = serde_json::from_str(&strict.stdout).unwrap();
+        assert_eq!(report["completion"][0]["language"], "fr");
+        assert_eq!(report["completion"][0]["passed"], false);
+        assert_eq!(report["passed"], false);
+        assert_eq!(report["summary"]["errors"], 1);
+
+        let annotated = run_args(&[
+            "validate",
+            path,
+            "--output",
+            "github",
+            "--min-completion",
+            "fr=60",
+        ])
+        .await
+        .unwrap();
+        assert!(!annotated.success);
+        let lines: Vec<&str> = annotated.stdout.lines().collect();
+        assert!(
+            lines[0].starts_with(&format!(
+                "::error file={path},line=4,title=xcstrings placeholders::zeta [fr]"
+            )),
+            "{}",
+            lines[0]
+        );
+        assert!(lines.iter().any(|line| line.starts_with("::error")
+            && line.ends_with("::fr is 50.0%25 translated, below the required 60%25")));
+
+        assert!(run_args(&["validate", path, "--min-completion", "ja=10"])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn release_check_gates_on_completion_and_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Localizable.xcstrings");
+        std::fs::write(&path, CATALOG).unwrap();
+        let path = path.to_str().unwrap();
+
+        let output = run_args(&["release-check", path]).await.unwrap();
+        assert!(!output.success);
+        assert!(
+            output.stdout.contains("fr is 50.0% complete, below 100%"),
+            "{}",
+            output.stdout
+        );
+        assert!(output.stdout.contains("fr has 1 QA error(s)"));
+
+        let output = run_args(&[
+            "release-check",
+            path,
+            "--min-completion",
+            "en=100",
+            "--json",
+        ])
+        .await
+        .unwrap();
+        assert!(output.success, "{}", output.stdout);
+        let report: serde_json::Value = serde_json::from_str(&output.stdout).unwrap();
+        assert_eq!(report["ready"], true);
+        assert_eq!(report["languages"][0]["language"], "en");
+    }
+
+    #[tokio::test]
+    async fn import_diff_and_merge_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("Original.xcstrings");
+        let edited = dir.path().join("Edited.xcstrings");
+        std::fs::write(&original, CATALOG).unwrap();
+        std::fs::write(&edited, CATALOG).unwrap();
+        let strings = dir.path().join("fr.strings");
+        std::fs::write(&strings, "\"alpha\" = \"Alpha FR\";\n\"typo\" = \"x\";\n").unwrap();
+        let [original, edited, strings] =
+            [&original, &edited, &strings].map(|path| path.to_str().unwrap().to_string());
+
+        let imported = run_args(&[
+            "import",
+            &edited,
+            &strings,
+            "-l",
+            "fr",
+            "--state",
+            "needs_review",
+            "--json",
+        ])
+        .await
+        .unwrap();
+        assert!(!imported.success, "unknown keys are reported");
+        let report: serde_json::Value = serde_json::from_str(&imported.stdout).unwrap();
+        assert_eq!(report["imported"].as_array().unwrap().len(), 1);
+        assert_eq!(report["skipped_keys"], serde_json::json!(["typo"]));
+
+        let approved = run_args(&["export", &edited, "-l", "fr", "--state", "translated"])
+            .await
+            .unwrap();
+        let approved: serde_json::Value = serde_json::from_str(&approved.stdout).unwrap();
+        assert_eq!(approved, serde_json::json!({ "zeta": "Zêta %d" }));
+        let in_review = run_args(&["export", &edited, "-l", "fr", "-s", "review"])
+            .await
+            .unwrap();
+        assert!(
+            in_review.stdout.contains("Alpha FR"),
+            "{}",
+            in_review.stdout
+        );
+        assert!(run_args(&["export", &edited, "-s", "bogus"]).await.is_err());
+
+        let diffed = run_args(&["diff", &original, &edited, "--json"])
+            .await
+            .unwrap();
+        assert!(!diffed.success);
+        let diff: serde_json::Value = serde_json::from_str(&diffed.stdout).unwrap();
+        assert_eq!(diff["changed_keys"][0]["key"], "alpha");
+        assert_eq!(
+            diff["changed_keys"][0]["translations"][0]["after"]["value"],
+            "Alpha FR"
+        );
+
+        let merged = run_args(&["merge", &original, &edited]).await.unwrap();
+        assert!(merged.success, "{}", merged.stdout);
+        assert!(
+            run_args(&["diff", &original, &edited])
+                .await
+                .unwrap()
+                .success
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::{
+    apple_json_formatter::FormatOptions,
+    qa::{PlaceholderValidation, QaOptions},
+    state::StateValidation,
+};
+
+#[cfg(feature = "sync")]
+use crate::sync::SyncConfig;
+#[cfg(any(feature = "mcp", feature = "web"))]
+use crate::tls::TlsFiles;
+#[cfg(feature = "web")]
+use crate::web::WebAuth;
+
+/// File picked up from the working directory when no config path is given explicitly.
+pub const DEFAULT_CONFIG_FILE: &str = ".xcstrings-mcp.json";
+
+/// Settings loaded from the optional JSON config file. Environment variables layer on top.
+/// Settings for features left out of the build are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerConfig {
+    /// MCP tools that are neither advertised nor callable, e.g. `["delete_key", "remove_language"]`.
+    #[serde(default)]
+    pub disabled_tools: Vec<String>,
+    /// Byte budget for list tool responses; `0` disables the limit.
+    #[serde(default)]
+    pub max_response_bytes: Option<usize>,
+    /// Whether destructive tools ask for confirmation via MCP elicitation (defaults to true).
+    #[serde(default)]
+    pub confirm_destructive: Option<bool>,
+    /// Glob patterns of catalogs that refuse edits, e.g. `["Vendor/**.xcstrings"]`, relative
+    /// to the search root unless absolute.
+    #[serde(default)]
+    pub read_only_paths: Vec<String>,
+    /// Labels shown for catalogs in file lists instead of the package or project name and
+    /// file name, keyed by path relative to the search root unless absolute, e.g.
+    /// `{"Modules/Auth/Localizable.xcstrings": "Auth"}`.
+    #[serde(default)]
+    pub catalog_labels: BTreeMap<String, String>,
+    /// Whether translation states must follow the review workflow (defaults to false).
+    #[serde(default)]
+    pub review_workflow: Option<bool>,
+    /// Whether rewording a source string sends its translations back to review (defaults to
+    /// false).
+    #[serde(default)]
+    pub review_on_source_change: Option<bool>,
+    /// Whether new keys must be created with a developer comment (defaults to false).
+    #[serde(default)]
+    pub require_comment: Option<bool>,
+    /// Whether every change is committed to the catalog's git repository (defaults to false).
+    #[serde(default)]
+    pub git_auto_commit: Option<bool>,
+    /// Layout of written catalogs, for byte-matching a particular Xcode version.
+    #[serde(default)]
+    pub format: FormatOptions,
+    /// Whether upserts with an unknown state are corrected with a warning (`"warn"`, the
+    /// default) or refused (`"strict"`).
+    #[serde(default)]
+    pub state_validation: StateValidation,
+    /// Whether upserts whose format specifiers don't match the source are saved with a
+    /// warning (`"warn"`, the default), refused (`"strict"`), or not checked (`"off"`).
+    #[serde(default)]
+    pub placeholder_validation: PlaceholderValidation,
+    /// Settings for `validate_catalog`, such as the terminology glossary.
+    #[serde(default)]
+    pub qa: QaOptions,
+    /// Translation management services reachable through `sync_catalog` and `sync`.
+    #[cfg(feature = "sync")]
+    #[serde(default)]
+    pub sync: SyncConfig,
+    /// Credentials for the web API; `WEB_AUTH_*` environment variables override them.
+    #[cfg(feature = "web")]
+    #[serde(default)]
+    pub web_auth: WebAuth,
+    /// Secret for HMAC signatures on inbound translation webhooks; `WEBHOOK_SECRET`
+    /// overrides it.
+    #[cfg(feature = "web")]
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+    /// Certificate and key for serving the web UI over HTTPS.
+    #[cfg(feature = "web")]
+    #[serde(default)]
+    pub web_tls: Option<TlsFiles>,
+    /// Certificate and key for the MCP HTTP transport; defaults to `webTls`.
+    #[cfg(feature = "mcp")]
+    #[serde(default)]
+    pub mcp_http_tls: Option<TlsFiles>,
+}
+
+impl ServerConfig {
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        let mut config: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("invalid config file {}", path.display()))?;
+        // Dictionary paths are relative to the config file.
+        let base = path.parent().unwrap_or(Path::new("."));
+        config.qa.load_dictionaries(base)?;
+        Ok(config)
+    }
+
+    /// Loads the explicit config path, or the default file in `dir` when it exists.
+    pub fn discover(
+        explicit: Option<&Path>,
+        dir: &Path,
+    ) -> anyhow::Result<(Self, Option<PathBuf>)> {
+        if let Some(path) = explicit {
+            return Ok((Self::from_file(path)?, Some(path.to_path_buf())));
+        }
+        let candidate = dir.join(DEFAULT_CONFIG_FILE);
+        if candidate.is_file() {
+            return Ok((Self::from_file(&candidate)?, Some(candidate)));
+        }
+        Ok((Self::default(), None))
+    }
+
+    /// Adds tools from a comma-separated list such as `DISABLED_TOOLS=delete_key,remove_language`.
+    pub fn disable_tools_from_list(&mut self, list: &str) {
+        merge_list(&mut self.disabled_tools, list);
+    }
+
+    /// Adds patterns from a comma-separated list such as `READONLY_PATHS=Vendor/**.xcstrings`.
+    pub fn protect_paths_from_list(&mut self, list: &str) {
+        merge_list(&mut self.read_only_paths, list);
+    }
+}
+
+fn merge_list(values: &mut Vec<String>, list: &str) {
+    for value in list
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        if !values.iter().any(|existing| existing == value) {
+            values.push(value.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_disabled_tools_and_merges_env_list() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join(DEFAULT_CONFIG_FILE),
+            r#"{ "disabledTools": ["delete_key"], "maxResponseBytes": 4096, "readOnlyPaths": ["Vendor/**"], "catalogLabels": { "Modules/Auth/Localizable.xcstrings": "Auth" }, "format": { "trailingNewline": true }, "stateValidation": "strict", "placeholderValidation": "off" }"#,
+        )
+        .expect("write config");
+
+        let (mut config, path) = ServerConfig::discover(None, dir.path()).expect("load config");
+        assert_eq!(path, Some(dir.path().join(DEFAULT_CONFIG_FILE)));
+        config.disable_tools_from_list(" remove_language, delete_key ,,");
+        assert_eq!(config.disabled_tools, vec!["delete_key", "remove_language"]);
+        assert_eq!(config.max_response_bytes, Some(4096));
+        assert_eq!(
+            config.catalog_labels["Modules/Auth/Localizable.xcstrings"],
+            "Auth"
+        );
+        assert!(config.format.trailing_newline);
+        assert_eq!(config.format.indent, 2);
+        assert_eq!(config.state_validation, StateValidation::Strict);
+        assert_eq!(config.placeholder_validation, PlaceholderValidation::Off);
+        config.protect_paths_from_list("Vendor/**, /opt/Shared.xcstrings");
+        assert_eq!(
+            config.read_only_paths,
+            vec!["Vendor/**", "/opt/Shared.xcstrings"]
+        );
+    }
+
+    #[test]
+    fn missing_default_file_yields_defaults() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (config, path) = ServerConfig::discover(None, dir.path()).expect("load config");
+        assert_eq!(config, ServerConfig::default());
+        assert!(path.is_none());
+        assert!(
+            ServerConfig::discover(Some(&dir.path().join("missing.json")), dir.path()).is_err()
+        );
+    }
+}
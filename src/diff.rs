@@ -0,0 +1,195 @@
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::store::{TranslationRecord, TranslationValue};
+
+/// Structural differences between two versions of a catalog. Key order is ignored.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CatalogDiff {
+    /// Keys only in the newer catalog, in its order.
+    pub added_keys: Vec<String>,
+    /// Keys only in the older catalog, in its order.
+    pub removed_keys: Vec<String>,
+    /// Keys in both catalogs whose comment, `shouldTranslate`, or translations differ.
+    pub changed_keys: Vec<KeyDiff>,
+}
+
+impl CatalogDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_keys.is_empty() && self.removed_keys.is_empty() && self.changed_keys.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeyDiff {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<Change<Option<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub should_translate: Option<Change<Option<bool>>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub translations: Vec<TranslationDiff>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change<T> {
+    pub before: T,
+    pub after: T,
+}
+
+/// One language of a key that was added, removed, or edited. `before` is `None` when the
+/// language was added and `after` is `None` when it was removed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranslationDiff {
+    pub language: String,
+    pub before: Option<TranslationValue>,
+    pub after: Option<TranslationValue>,
+}
+
+/// Compares the records of an older and a newer catalog, as returned by
+/// [`crate::store::XcStringsStore::list_records`].
+pub fn diff_records(before: &[TranslationRecord], after: &[TranslationRecord]) -> CatalogDiff {
+    let old: IndexMap<&str, &TranslationRecord> = before
+        .iter()
+        .map(|record| (record.key.as_str(), record))
+        .collect();
+    let new: IndexMap<&str, &TranslationRecord> = after
+        .iter()
+        .map(|record| (record.key.as_str(), record))
+        .collect();
+
+    let mut diff = CatalogDiff {
+        removed_keys: old
+            .keys()
+            .filter(|key| !new.contains_key(*key))
+            .map(|key| key.to_string())
+            .collect(),
+        ..CatalogDiff::default()
+    };
+    for (key, record) in &new {
+        match old.get(key) {
+            None => diff.added_keys.push(key.to_string()),
+            Some(previous) => {
+                if let Some(changed) = diff_record(previous, record) {
+                    diff.changed_keys.push(changed);
+                }
+            }
+        }
+    }
+    diff
+}
+
+fn change<T: PartialEq>(before: T, after: T) -> Option<Change<T>> {
+    (before != after).then_some(Change { before, after })
+}
+
+fn diff_record(before: &TranslationRecord, after: &TranslationRecord) -> Option<KeyDiff> {
+    let mut translations: Vec<TranslationDiff> = after
+        .translations
+        .iter()
+        .filter(|(language, value)| before.translations.get(*language) != Some(*value))
+        .map(|(language, value)| TranslationDiff {
+            language: language.clone(),
+            before: before.translations.get(language).cloned(),
+            after: Some(value.clone()),
+        })
+        .collect();
+    translations.extend(
+        before
+            .translations
+            .iter()
+            .filter(|(language, _)| !after.translations.contains_key(*language))
+            .map(|(language, value)| TranslationDiff {
+                language: language.clone(),
+                before: Some(value.clone()),
+                after: None,
+            }),
+    );
+
+    let diff = KeyDiff {
+        key: after.key.clone(),
+        comment: change(before.comment.clone(), after.comment.clone()),
+        should_translate: change(before.should_translate, after.should_translate),
+        translations,
+    };
+    (diff.comment.is_some() || diff.should_translate.is_some() || !diff.translations.is_empty())
+        .then_some(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        key: &str,
+        comment: Option<&str>,
+        translations: &[(&str, &str)],
+    ) -> TranslationRecord {
+        TranslationRecord {
+            key: key.to_string(),
+            ordinal: 0,
+            comment: comment.map(str::to_string),
+            extraction_state: None,
+            should_translate: None,
+            metadata: None,
+            modified: None,
+            matches: Vec::new(),
+            translations: translations
+                .iter()
+                .map(|(language, value)| {
+                    (
+                        language.to_string(),
+                        TranslationValue {
+                            value: Some(value.to_string()),
+                            ..TranslationValue::default()
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn reports_added_removed_and_changed_keys() {
+        let before = [
+            record("kept", None, &[("en", "Kept"), ("fr", "Gardé")]),
+            record("edited", None, &[("en", "Old"), ("de", "Alt")]),
+            record("removed", None, &[("en", "Gone")]),
+        ];
+        let after = [
+            record("added", None, &[("en", "New")]),
+            record("edited", Some("Title"), &[("en", "New"), ("fr", "Nouveau")]),
+            record("kept", None, &[("fr", "Gardé"), ("en", "Kept")]),
+        ];
+
+        let diff = diff_records(&before, &after);
+        assert_eq!(diff.added_keys, vec!["added"]);
+        assert_eq!(diff.removed_keys, vec!["removed"]);
+        assert_eq!(diff.changed_keys.len(), 1);
+        let edited = &diff.changed_keys[0];
+        assert_eq!(edited.key, "edited");
+        assert_eq!(
+            edited.comment,
+            Some(Change {
+                before: None,
+                after: Some("Title".to_string())
+            })
+        );
+        let languages: Vec<_> = edited
+            .translations
+            .iter()
+            .map(|change| {
+                (
+                    change.language.as_str(),
+                    change.before.is_some(),
+                    change.after.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            languages,
+            vec![("en", true, true), ("fr", false, true), ("de", true, false)]
+        );
+        assert!(diff_records(&before, &before).is_empty());
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::audit::AuditEntry;
+
+/// Keys named per section of the Markdown before the rest are only counted.
+const LISTED_KEYS: usize = 20;
+
+/// What changed in one catalog over a period, summarized from its audit-log entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Digest {
+    /// Audit-log entries summarized.
+    pub changes: usize,
+    /// Agents and sessions that made the changes.
+    pub clients: BTreeSet<String>,
+    /// Keys added with `create_key` or in a batch, minus those deleted again.
+    pub new_keys: BTreeSet<String>,
+    /// Keys whose translation was created or edited, per language.
+    pub translated: BTreeMap<String, BTreeSet<String>>,
+    pub deleted_keys: BTreeSet<String>,
+    /// Keys whose translation was removed, per language.
+    pub deleted_translations: BTreeMap<String, BTreeSet<String>>,
+    /// Old and new names of renamed keys, in the order they were renamed.
+    pub renamed_keys: Vec<(String, String)>,
+    /// How often each other action was taken, such as `set_comment`.
+    pub other: BTreeMap<String, usize>,
+}
+
+impl Digest {
+    /// Tallies `entries`, which should all belong to one catalog, oldest first.
+    pub fn from_entries(entries: &[AuditEntry]) -> Self {
+        let mut digest = Self::default();
+        for entry in entries {
+            digest.changes += 1;
+            digest.clients.insert(
+                entry
+                    .client
+                    .clone()
+                    .unwrap_or_else(|| entry.session.clone()),
+            );
+            let details = &entry.details;
+            let key = details.get("key").and_then(Value::as_str);
+            let language = details.get("language").and_then(Value::as_str);
+            match (entry.action.as_str(), key, language) {
+                ("upsert_translation" | "set_device_variant", Some(key), Some(language)) => {
+                    digest.translation_upserted(key, language)
+                }
+                ("delete_translation", Some(key), Some(language)) => {
+                    digest.translation_deleted(key, language)
+                }
+                ("create_key", Some(key), _) => digest.key_created(key),
+                ("delete_key", Some(key), _) => digest.key_deleted(key),
+                ("rename_key", Some(key), _) => {
+                    let new_key = details.get("newKey").and_then(Value::as_str);
+                    match new_key {
+                        Some(new_key) => digest.key_renamed(key, new_key),
+                        None => digest.other_action(&entry.action),
+                    }
+                }
+                ("apply_batch", _, _) => {
+                    let changes = details.get("changes").and_then(Value::as_array);
+                    for change in changes.into_iter().flatten() {
+                        digest.batch_change(change);
+                    }
+                }
+                (action, _, _) => digest.other_action(action),
+            }
+        }
+        digest
+    }
+
+    fn translation_upserted(&mut self, key: &str, language: &str) {
+        self.translated
+            .entry(language.to_string())
+            .or_default()
+            .insert(key.to_string());
+    }
+
+    fn translation_deleted(&mut self, key: &str, language: &str) {
+        self.deleted_translations
+            .entry(language.to_string())
+            .or_default()
+            .insert(key.to_string());
+    }
+
+    fn key_created(&mut self, key: &str) {
+        self.deleted_keys.remove(key);
+        self.new_keys.insert(key.to_string());
+    }
+
+    /// Records a deleted key; one created in the same period simply never appears.
+    fn key_deleted(&mut self, key: &str) {
+        for keys in self.translated.values_mut() {
+            keys.remove(key);
+        }
+        self.translated.retain(|_, keys| !keys.is_empty());
+        if !self.new_keys.remove(key) {
+            self.deleted_keys.insert(key.to_string());
+        }
+    }
+
+    /// Records a rename, carrying what was tallied under the old name over to the new one.
+    fn key_renamed(&mut self, old_key: &str, new_key: &str) {
+        if self.new_keys.remove(old_key) {
+            self.new_keys.insert(new_key.to_string());
+        }
+        for keys in self.translated.values_mut() {
+            if keys.remove(old_key) {
+                keys.insert(new_key.to_string());
+            }
+        }
+        self.renamed_keys
+            .push((old_key.to_string(), new_key.to_string()));
+    }
+
+    fn other_action(&mut self, action: &str) {
+        *self.other.entry(action.to_string()).or_default() += 1;
+    }
+
+    /// Tallies one serialized [`crate::store::StoreChange`] from a batch.
+    fn batch_change(&mut self, change: &Value) {
+        let field = |name: &str| change.get(name).and_then(Value::as_str);
+        match (
+            field("type").unwrap_or_default(),
+            field("key"),
+            field("language"),
+        ) {
+            ("translation_upserted", Some(key), Some(language)) => {
+                self.translation_upserted(key, language)
+            }
+            ("translation_deleted", Some(key), Some(language)) => {
+                self.translation_deleted(key, language)
+            }
+            ("key_created", Some(key), _) => self.key_created(key),
+            ("key_deleted", Some(key), _) => self.key_deleted(key),
+            (kind, _, _) => self.other_action(kind),
+        }
+    }
+
+    /// Renders the digest as Markdown for Slack or release notes, headed by `title`, such
+    /// as the catalog name and period.
+    pub fn to_markdown(&self, title: &str) -> String {
+        let mut out = format!("## {title}\n\n");
+        if self.changes == 0 {
+            out.push_str("No changes.\n");
+            return out;
+        }
+        let _ = writeln!(
+            out,
+            "{} {} by {}.",
+            self.changes,
+            plural(self.changes, "change", "changes"),
+            self.clients.iter().cloned().collect::<Vec<_>>().join(", ")
+        );
+        if !self.new_keys.is_empty() {
+            let _ = write!(out, "\n**New keys ({}):** ", self.new_keys.len());
+            out.push_str(&key_list(&self.new_keys));
+            out.push('\n');
+        }
+        if !self.translated.is_empty() {
+            out.push_str("\n**Translations updated:**\n");
+            for (language, keys) in &self.translated {
+                let _ = writeln!(
+                    out,
+                    "- {language}: {} {}",
+                    keys.len(),
+                    plural(keys.len(), "key", "keys")
+                );
+            }
+        }
+        if !self.renamed_keys.is_empty() {
+            out.push_str("\n**Renamed keys:**\n");
+            for (old_key, new_key) in &self.renamed_keys {
+                let _ = writeln!(out, "- `{old_key}` → `{new_key}`");
+            }
+        }
+        if !self.deleted_keys.is_empty() {
+            let _ = write!(out, "\n**Deleted keys ({}):** ", self.deleted_keys.len());
+            out.push_str(&key_list(&self.deleted_keys));
+            out.push('\n');
+        }
+        if !self.deleted_translations.is_empty() {
+            out.push_str("\n**Deleted translations:**\n");
+            for (language, keys) in &self.deleted_translations {
+                let _ = writeln!(out, "- {language}: {}", key_list(keys));
+            }
+        }
+        if !self.other.is_empty() {
+            let other: Vec<_> = self
+                .other
+                .iter()
+                .map(|(action, count)| format!("{action} ×{count}"))
+                .collect();
+            let _ = writeln!(out, "\n**Other changes:** {}", other.join(", "));
+        }
+        out
+    }
+}
+
+fn plural<'a>(count: usize, one: &'a str, other: &'a str) -> &'a str {
+    if count == 1 {
+        one
+    } else {
+        other
+    }
+}
+
+/// `` `a`, `b` ``, ending in "and N more" past [`LISTED_KEYS`].
+fn key_list(keys: &BTreeSet<String>) -> String {
+    let mut listed: Vec<_> = keys
+        .iter()
+        .take(LISTED_KEYS)
+        .map(|key| format!("`{key}`"))
+        .collect();
+    if keys.len() > LISTED_KEYS {
+        listed.push(format!("and {} more", keys.len() - LISTED_KEYS));
+    }
+    listed.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(client: &str, action: &str, details: Value) -> AuditEntry {
+        let mut entry = AuditEntry::new("session", action);
+        entry.client = Some(client.to_string());
+        entry.details = details;
+        entry
+    }
+
+    #[test]
+    fn summarizes_entries_as_markdown() {
+        let entries = [
+            entry(
+                "agent-a",
+                "create_key",
+                serde_json::json!({ "key": "login.title" }),
+            ),
+            entry(
+                "agent-a",
+                "upsert_translation",
+                serde_json::json!({ "key": "login.title", "language": "de" }),
+            ),
+            entry(
+                "agent-a",
+                "upsert_translation",
+                serde_json::json!({ "key": "login.title", "language": "de" }),
+            ),
+            entry(
+                "web",
+                "apply_batch",
+                serde_json::json!({ "changes": [
+                    { "type": "translation_upserted", "key": "greeting", "language": "fr" },
+                    { "type": "key_deleted", "key": "legacy" },
+                ] }),
+            ),
+            entry(
+                "web",
+                "delete_translation",
+                serde_json::json!({ "key": "greeting", "language": "it" }),
+            ),
+            entry(
+                "agent-a",
+                "set_comment",
+                serde_json::json!({ "key": "greeting" }),
+            ),
+        ];
+        let digest = Digest::from_entries(&entries);
+        assert_eq!(digest.changes, 6);
+        assert_eq!(digest.translated["de"].len(), 1);
+
+        assert_eq!(
+            digest.to_markdown("Localizable.xcstrings since 2026-03-01"),
+            "## Localizable.xcstrings since 2026-03-01\n\n\
+             6 changes by agent-a, web.\n\n\
+             **New keys (1):** `login.title`\n\n\
+             **Translations updated:**\n- de: 1 key\n- fr: 1 key\n\n\
+             **Deleted keys (1):** `legacy`\n\n\
+             **Deleted translations:**\n- it: `greeting`\n\n\
+             **Other changes:** set_comment ×1\n"
+        );
+        assert_eq!(
+            Digest::default().to_markdown("Empty"),
+            "## Empty\n\nNo changes.\n"
+        );
+    }
+
+    #[test]
+    fn keys_created_and_deleted_in_the_period_cancel_out() {
+        let entries = [
+            entry("a", "create_key", serde_json::json!({ "key": "draft" })),
+            entry(
+                "a",
+                "upsert_translation",
+                serde_json::json!({ "key": "draft", "language": "en" }),
+            ),
+            entry(
+                "a",
+                "rename_key",
+                serde_json::json!({ "key": "draft", "newKey": "final" }),
+            ),
+            entry("a", "delete_key", serde_json::json!({ "key": "final" })),
+        ];
+        let digest = Digest::from_entries(&entries);
+        assert!(digest.new_keys.is_empty());
+        assert!(digest.deleted_keys.is_empty());
+        assert!(digest.translated.is_empty());
+        assert_eq!(digest.renamed_keys, [("draft".into(), "final".into())]);
+    }
+}
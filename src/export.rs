@@ -0,0 +1,453 @@
+use std::path::Path;
+
+use anyhow::{bail, Context};
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+use indexmap::IndexMap;
+
+use crate::{state, store::TranslationRecord};
+
+/// File formats translations can be exported to and imported from. Only plain string values
+/// are carried; plural and device variations stay in the catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum Format {
+    /// `{"key": "value"}` for one language, `{"key": {"fr": "value"}}` for several
+    Json,
+    /// A `key` column, a `comment` column, and one column per language
+    Csv,
+    /// Apple `.strings` file for a single language
+    Strings,
+}
+
+impl Format {
+    /// Picks the format matching a file's extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            "strings" => Some(Self::Strings),
+            _ => None,
+        }
+    }
+}
+
+/// A translation read from an import file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedValue {
+    pub key: String,
+    pub language: String,
+    pub value: String,
+}
+
+fn value<'a>(record: &'a TranslationRecord, language: &str, states: &[String]) -> Option<&'a str> {
+    let translation = record.translations.get(language)?;
+    if !states.is_empty() {
+        let current = translation.state.as_deref().and_then(state::normalize)?;
+        if !states.iter().any(|wanted| wanted == current) {
+            return None;
+        }
+    }
+    translation.value.as_deref()
+}
+
+/// Renders the values of `languages` for every record. Keys without a value in any of
+/// them are left out of JSON and `.strings` output, and get empty cells in CSV. A
+/// non-empty `states`, such as `["translated", "reviewed"]`, also leaves out values in
+/// any other state or without one, so release builds can skip unreviewed text.
+pub fn export(
+    records: &[TranslationRecord],
+    languages: &[String],
+    format: Format,
+    states: &[String],
+) -> anyhow::Result<String> {
+    match format {
+        Format::Json => {
+            let output: serde_json::Value = if let [language] = languages {
+                records
+                    .iter()
+                    .filter_map(|record| {
+                        Some((record.key.clone(), value(record, language, states)?.into()))
+                    })
+                    .collect::<serde_json::Map<_, _>>()
+                    .into()
+            } else {
+                records
+                    .iter()
+                    .filter_map(|record| {
+                        let values: serde_json::Map<_, _> = languages
+                            .iter()
+                            .filter_map(|language| {
+                                Some((language.clone(), value(record, language, states)?.into()))
+                            })
+                            .collect();
+                        (!values.is_empty()).then(|| (record.key.clone(), values.into()))
+                    })
+                    .collect::<serde_json::Map<_, _>>()
+                    .into()
+            };
+            Ok(serde_json::to_string_pretty(&output)? + "\n")
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record(
+                ["key", "comment"]
+                    .into_iter()
+                    .chain(languages.iter().map(String::as_str)),
+            )?;
+            for record in records {
+                writer.write_record(
+                    [record.key.as_str(), record.comment.as_deref().unwrap_or("")]
+                        .into_iter()
+                        .chain(
+                            languages
+                                .iter()
+                                .map(|language| value(record, language, states).unwrap_or("")),
+                        ),
+                )?;
+            }
+            Ok(String::from_utf8(writer.into_inner()?)?)
+        }
+        Format::Strings => {
+            let [language] = languages else {
+                bail!(".strings files hold exactly one language; pass --language");
+            };
+            let mut output = String::new();
+            for record in records {
+                let Some(value) = value(record, language, states) else {
+                    continue;
+                };
+                if let Some(comment) = &record.comment {
+                    output.push_str(&format!("/* {} */\n", comment.replace("*/", "* /")));
+                }
+                output.push_str(&format!(
+                    "\"{}\" = \"{}\";\n\n",
+                    escape_strings(&record.key),
+                    escape_strings(value)
+                ));
+            }
+            Ok(output)
+        }
+    }
+}
+
+/// Reads translations from `input`. `language` is required for JSON with plain string
+/// values and for `.strings`; for CSV it selects one column instead of all of them.
+pub fn parse(
+    input: &str,
+    format: Format,
+    language: Option<&str>,
+) -> anyhow::Result<Vec<ImportedValue>> {
+    match format {
+        Format::Json => {
+            let parsed: IndexMap<String, serde_json::Value> = serde_json::from_str(input)
+                .context("expected a JSON object keyed by string key")?;
+            let mut values = Vec::new();
+            for (key, entry) in parsed {
+                match entry {
+                    serde_json::Value::String(value) => {
+                        let Some(language) = language else {
+                            bail!("'{key}' maps to a plain string; pass --language to import it");
+                        };
+                        values.push(ImportedValue {
+                            key,
+                            language: language.to_string(),
+                            value,
+                        });
+                    }
+                    serde_json::Value::Object(translations) => {
+                        for (entry_language, value) in translations {
+                            if language.is_some_and(|only| only != entry_language) {
+                                continue;
+                            }
+                            let serde_json::Value::String(value) = value else {
+                                bail!("'{key}' has a non-string value for '{entry_language}'");
+                            };
+                            values.push(ImportedValue {
+                                key: key.clone(),
+                                language: entry_language,
+                                value,
+                            });
+                        }
+                    }
+                    _ => bail!("'{key}' must map to a string or an object of languages"),
+                }
+            }
+            Ok(values)
+        }
+        Format::Csv => {
+            let mut reader = csv::Reader::from_reader(input.as_bytes());
+            let headers = reader.headers()?.clone();
+            if headers.get(0) != Some("key") {
+                bail!("the first CSV column must be 'key'");
+            }
+            let columns: Vec<(usize, &str)> = headers
+                .iter()
+                .enumerate()
+                .skip(1)
+                .filter(|(_, name)| *name != "comment")
+                .filter(|(_, name)| language.is_none_or(|only| only == *name))
+                .collect();
+            if let Some(language) = language.filter(|_| columns.is_empty()) {
+                bail!("the CSV has no '{language}' column");
+            }
+            let mut values = Vec::new();
+            for row in reader.records() {
+                let row = row?;
+                let key = &row[0];
+                for (index, language) in &columns {
+                    let value = row.get(*index).unwrap_or_default();
+                    if !value.is_empty() {
+                        values.push(ImportedValue {
+                            key: key.to_string(),
+                            language: language.to_string(),
+                            value: value.to_string(),
+                        });
+                    }
+                }
+            }
+            Ok(values)
+        }
+        Format::Strings => {
+            let Some(language) = language else {
+                bail!(".strings files hold one language; pass --language");
+            };
+            Ok(parse_strings(input)?
+                .into_iter()
+                .map(|(key, value)| ImportedValue {
+                    key,
+                    language: language.to_string(),
+                    value,
+                })
+                .collect())
+        }
+    }
+}
+
+fn escape_strings(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Parses `"key" = "value";` pairs, skipping `/* */` and `//` comments.
+fn parse_strings(input: &str) -> anyhow::Result<Vec<(String, String)>> {
+    let mut chars = input.chars().peekable();
+    let mut line = 1;
+    let mut pairs = Vec::new();
+
+    // Skips whitespace and comments, returning the next significant character.
+    let skip = |chars: &mut std::iter::Peekable<std::str::Chars>, line: &mut usize| {
+        while let Some(&ch) = chars.peek() {
+            if ch == '\n' {
+                *line += 1;
+            }
+            if ch.is_whitespace() {
+                chars.next();
+                continue;
+            }
+            if ch != '/' {
+                break;
+            }
+            let mut lookahead = chars.clone();
+            lookahead.next();
+            match lookahead.next() {
+                Some('*') => {
+                    chars.nth(1);
+                    let mut previous = '\0';
+                    for ch in chars.by_ref() {
+                        if ch == '\n' {
+                            *line += 1;
+                        }
+                        if previous == '*' && ch == '/' {
+                            break;
+                        }
+                        previous = ch;
+                    }
+                }
+                Some('/') => {
+                    for ch in chars.by_ref() {
+                        if ch == '\n' {
+                            *line += 1;
+                            break;
+                        }
+                    }
+                }
+                _ => break,
+            }
+        }
+        chars.peek().copied()
+    };
+    let quoted = |chars: &mut std::iter::Peekable<std::str::Chars>,
+                  line: &mut usize|
+     -> anyhow::Result<String> {
+        if chars.next() != Some('"') {
+            bail!("line {line}: expected a quoted string");
+        }
+        let mut text = String::new();
+        loop {
+            match chars.next() {
+                None => bail!("line {line}: unterminated string"),
+                Some('"') => return Ok(text),
+                Some('\\') => match chars.next() {
+                    Some('n') => text.push('\n'),
+                    Some('r') => text.push('\r'),
+                    Some('t') => text.push('\t'),
+                    Some('u' | 'U') => {
+                        let hex: String = chars.by_ref().take(4).collect();
+                        let code = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .with_context(|| format!("line {line}: invalid escape \\u{hex}"))?;
+                        text.push(code);
+                    }
+                    Some(other) => text.push(other),
+                    None => bail!("line {line}: unterminated string"),
+                },
+                Some(ch) => {
+                    if ch == '\n' {
+                        *line += 1;
+                    }
+                    text.push(ch);
+                }
+            }
+        }
+    };
+
+    while skip(&mut chars, &mut line).is_some() {
+        let key = quoted(&mut chars, &mut line)?;
+        if skip(&mut chars, &mut line) != Some('=') {
+            bail!("line {line}: expected '=' after \"{key}\"");
+        }
+        chars.next();
+        skip(&mut chars, &mut line);
+        let value = quoted(&mut chars, &mut line)?;
+        if skip(&mut chars, &mut line) != Some(';') {
+            bail!("line {line}: expected ';' after the value of \"{key}\"");
+        }
+        chars.next();
+        pairs.push((key, value));
+    }
+    Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::TranslationValue;
+
+    fn records() -> Vec<TranslationRecord> {
+        let translation = |value: &str| TranslationValue {
+            value: Some(value.to_string()),
+            ..TranslationValue::default()
+        };
+        vec![
+            TranslationRecord {
+                key: "greeting".to_string(),
+                ordinal: 0,
+                comment: Some("Shown on \"launch\"".to_string()),
+                extraction_state: None,
+                should_translate: None,
+                metadata: None,
+                modified: None,
+                matches: Vec::new(),
+                translations: [
+                    ("en".to_string(), translation("Hello\n\"there\"")),
+                    ("fr".to_string(), translation("Bonjour, à tous")),
+                ]
+                .into_iter()
+                .collect(),
+            },
+            TranslationRecord {
+                key: "farewell".to_string(),
+                ordinal: 1,
+                comment: None,
+                extraction_state: None,
+                should_translate: None,
+                metadata: None,
+                modified: None,
+                matches: Vec::new(),
+                translations: [("en".to_string(), translation("Bye"))]
+                    .into_iter()
+                    .collect(),
+            },
+        ]
+    }
+
+    #[test]
+    fn exported_files_import_back() {
+        let records = records();
+        let fr = vec!["fr".to_string()];
+        let both = vec!["en".to_string(), "fr".to_string()];
+        let expected_fr = vec![ImportedValue {
+            key: "greeting".to_string(),
+            language: "fr".to_string(),
+            value: "Bonjour, à tous".to_string(),
+        }];
+
+        for format in [Format::Json, Format::Csv, Format::Strings] {
+            let exported = export(&records, &fr, format, &[]).expect("export");
+            assert_eq!(
+                parse(&exported, format, Some("fr")).expect("parse"),
+                expected_fr,
+                "{format:?}"
+            );
+        }
+
+        for format in [Format::Json, Format::Csv] {
+            let exported = export(&records, &both, format, &[]).expect("export");
+            let imported = parse(&exported, format, None).expect("parse");
+            assert_eq!(imported.len(), 3, "{format:?}");
+            assert_eq!(imported[0].value, "Hello\n\"there\"");
+        }
+        assert!(export(&records, &both, Format::Strings, &[]).is_err());
+    }
+
+    #[test]
+    fn state_filter_leaves_out_other_states() {
+        let mut records = records();
+        records[0].translations.get_mut("fr").unwrap().state = Some("needs_review".to_string());
+        records[0].translations.get_mut("en").unwrap().state = Some("translated".to_string());
+        let both = vec!["en".to_string(), "fr".to_string()];
+        let translated = vec!["translated".to_string()];
+
+        let exported = export(&records, &both, Format::Json, &translated).expect("export");
+        let imported = parse(&exported, Format::Json, None).expect("parse");
+        assert_eq!(imported.len(), 1, "{exported}");
+        assert_eq!(imported[0].language, "en");
+
+        let csv = export(&records, &both, Format::Csv, &translated).expect("export");
+        assert_eq!(parse(&csv, Format::Csv, None).expect("parse"), imported);
+        let review = vec!["needs_review".to_string()];
+        let fr = vec!["fr".to_string()];
+        let strings = export(&records, &fr, Format::Strings, &review).expect("export");
+        assert!(
+            strings.contains("\"greeting\" = \"Bonjour, à tous\";"),
+            "{strings}"
+        );
+    }
+
+    #[test]
+    fn parses_strings_comments_and_escapes() {
+        let input = "// header\n/* Title\n   comment */\n\"title\" = \"Caf\\u00e9 \\\"Menu\\\"\";\n\"a\"=\"b\";";
+        assert_eq!(
+            parse_strings(input).expect("parse"),
+            vec![
+                ("title".to_string(), "Café \"Menu\"".to_string()),
+                ("a".to_string(), "b".to_string()),
+            ]
+        );
+        let err = parse_strings("\"title\" \"x\";").unwrap_err();
+        assert!(err.to_string().contains("line 1"), "{err}");
+    }
+}
@@ -0,0 +1,65 @@
+use std::{collections::BTreeSet, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use utoipa::ToSchema;
+
+/// Languages signed off for a release, kept in a sidecar JSON file next to the catalog.
+/// Translations in a frozen language can only change through a forced write.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FrozenLanguages {
+    #[serde(default)]
+    pub languages: BTreeSet<String>,
+}
+
+impl FrozenLanguages {
+    /// Reads the sidecar at `path`; a missing file means no language is frozen.
+    pub async fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path).await {
+            Ok(raw) => serde_json::from_str(&raw).map_err(io::Error::other),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Writes the sidecar, or removes it once no language is frozen.
+    pub async fn save(&self, path: &Path) -> io::Result<()> {
+        if self.languages.is_empty() {
+            return match fs::remove_file(path).await {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            };
+        }
+        let mut raw = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        raw.push('\n');
+        fs::write(path, raw).await
+    }
+
+    pub fn contains(&self, language: &str) -> bool {
+        self.languages.contains(language)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn round_trips_and_removes_empty_sidecars() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("Localizable.frozen.json");
+        let mut frozen = FrozenLanguages::load(&path).await.unwrap();
+        assert!(!frozen.contains("it"));
+
+        frozen.languages.insert("it".to_string());
+        frozen.save(&path).await.unwrap();
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(raw, "{\n  \"languages\": [\n    \"it\"\n  ]\n}\n");
+        assert!(FrozenLanguages::load(&path).await.unwrap().contains("it"));
+
+        frozen.languages.clear();
+        frozen.save(&path).await.unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,455 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use tokio::process::Command;
+use utoipa::ToSchema;
+
+use crate::{audit::AuditEntry, store::TranslationValue};
+
+/// Commits examined for a key's history when the caller doesn't say.
+pub const DEFAULT_DEPTH: usize = 100;
+
+/// Keys named in a commit subject before the rest are summarized as a count.
+const SUBJECT_KEYS: usize = 3;
+
+/// Argument names that hold the keys a change touched.
+const KEY_FIELDS: [&str; 4] = ["key", "keys", "newKey", "new_key"];
+
+/// How one translation changed across the commits that touched its catalog.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyHistory {
+    pub key: String,
+    pub language: String,
+    /// The translation as the server holds it now.
+    pub current: Option<TranslationValue>,
+    /// Whether `current` differs from the translation in the latest commit.
+    pub uncommitted: bool,
+    /// Commits that added, changed, or removed the translation, newest first.
+    pub changes: Vec<KeyChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyChange {
+    pub commit: String,
+    pub author: String,
+    /// Author date in RFC 3339.
+    pub date: String,
+    pub summary: String,
+    /// The translation after the commit; `None` when the commit removed it.
+    pub value: Option<TranslationValue>,
+}
+
+/// Translations into one language whose source text changed after they did, so they may
+/// be out of date whatever their state says.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OutdatedReport {
+    pub language: String,
+    pub source_language: String,
+    /// Commits searched; changes older than these aren't seen.
+    pub commits: usize,
+    /// Outdated translations in catalog order.
+    pub outdated: Vec<OutdatedTranslation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OutdatedTranslation {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// The commit that last changed the source text; `None` when that change isn't
+    /// committed yet.
+    pub source_changed: Option<CommitSummary>,
+    /// The commit that last changed the translation; `None` when that is older than the
+    /// commits searched.
+    pub translation_changed: Option<CommitSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSummary {
+    pub commit: String,
+    pub author: String,
+    /// Author date in RFC 3339.
+    pub date: String,
+    pub summary: String,
+}
+
+impl From<&FileRevision> for CommitSummary {
+    fn from(revision: &FileRevision) -> Self {
+        Self {
+            commit: revision.commit.clone(),
+            author: revision.author.clone(),
+            date: revision.date.clone(),
+            summary: revision.summary.clone(),
+        }
+    }
+}
+
+/// A commit that touched a file, with the file's path in that commit relative to the
+/// repository root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRevision {
+    pub commit: String,
+    pub author: String,
+    pub date: String,
+    pub summary: String,
+    pub path: String,
+}
+
+/// Lists the last `depth` commits that touched `file`, newest first, following renames.
+/// Returns `None` when the file isn't inside a git work tree.
+pub async fn file_log(
+    file: &Path,
+    depth: usize,
+) -> io::Result<Option<(PathBuf, Vec<FileRevision>)>> {
+    let dir = match file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let Some(root) = git(dir, &["rev-parse", "--show-toplevel"]).await? else {
+        return Ok(None);
+    };
+    let name = file
+        .file_name()
+        .ok_or_else(|| io::Error::other(format!("'{}' is not a file", file.display())))?;
+    let depth = format!("--max-count={depth}");
+    let args = [
+        "log",
+        "--follow",
+        "--name-only",
+        "--format=%x1e%H%x1f%an%x1f%aI%x1f%s",
+        depth.as_str(),
+        "--",
+    ];
+    let mut command = Command::new("git");
+    command.current_dir(dir).args(args).arg(name);
+    let Some(log) = output(command).await? else {
+        return Ok(None);
+    };
+    Ok(Some((PathBuf::from(root.trim_end()), parse_log(&log))))
+}
+
+/// The root of the work tree holding `file`, and the file's path relative to it. Returns
+/// `None` when the file isn't inside a work tree.
+pub async fn locate(file: &Path) -> io::Result<Option<(PathBuf, String)>> {
+    let dir = match file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let Some(found) = git(dir, &["rev-parse", "--show-toplevel", "--show-prefix"]).await? else {
+        return Ok(None);
+    };
+    let mut lines = found.lines();
+    let root = lines.next().unwrap_or_default();
+    let prefix = lines.next().unwrap_or_default();
+    let name = file.file_name().unwrap_or_default().to_string_lossy();
+    Ok(Some((PathBuf::from(root), format!("{prefix}{name}"))))
+}
+
+/// Resolves a branch, tag, or other revision to the hash of the commit it names, or `None`
+/// when it names none.
+pub async fn resolve_commit(root: &Path, reference: &str) -> io::Result<Option<String>> {
+    // Keep revisions from being read as options.
+    if reference.is_empty() || reference.starts_with('-') {
+        return Ok(None);
+    }
+    let revision = format!("{reference}^{{commit}}");
+    Ok(git(root, &["rev-parse", "--verify", "--quiet", &revision])
+        .await?
+        .map(|hash| hash.trim_end().to_string()))
+}
+
+/// Reads `path` as it was in `commit`; `None` when the file didn't exist there.
+pub async fn show(root: &Path, commit: &str, path: &str) -> io::Result<Option<String>> {
+    git(root, &["show", &format!("{commit}:{path}")]).await
+}
+
+/// Builds the history of one translation from its value in each revision, newest first.
+/// A revision is a change when its value differs from the one in the revision before it;
+/// the oldest revision is a change only when it holds the translation.
+pub fn changes(revisions: Vec<(FileRevision, Option<TranslationValue>)>) -> Vec<KeyChange> {
+    let mut changes = Vec::new();
+    let mut revisions = revisions.into_iter().peekable();
+    while let Some((revision, value)) = revisions.next() {
+        let changed = match revisions.peek() {
+            Some((_, previous)) => *previous != value,
+            None => value.is_some(),
+        };
+        if changed {
+            changes.push(KeyChange {
+                commit: revision.commit,
+                author: revision.author,
+                date: revision.date,
+                summary: revision.summary,
+                value,
+            });
+        }
+    }
+    changes
+}
+
+/// Finds the newest version that differs from the one before it, among versions listed
+/// newest first; `None` when they are all the same. Unlike [`changes`], the oldest version
+/// never counts, since what came before it is unknown.
+pub fn last_change<T: PartialEq>(versions: &[T]) -> Option<usize> {
+    versions.windows(2).position(|pair| pair[0] != pair[1])
+}
+
+/// Stages `paths` and commits them alone, leaving anything else in the index for the user.
+/// The first path is the catalog and must be inside a work tree; later ones (sidecars) are
+/// skipped when git knows nothing about them. The repository's hooks run as for any commit,
+/// and one rejecting it fails the call. Returns the new commit's hash, or `None` when none of
+/// the paths changed.
+pub async fn commit_paths(paths: &[PathBuf], message: &str) -> io::Result<Option<String>> {
+    let Some(catalog) = paths.first() else {
+        return Ok(None);
+    };
+    let dir = match catalog.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    if git(dir, &["rev-parse", "--show-toplevel"]).await?.is_none() {
+        return Err(io::Error::other(format!(
+            "'{}' is not inside a git repository",
+            catalog.display()
+        )));
+    }
+
+    let mut staged = Vec::with_capacity(paths.len());
+    for path in paths {
+        let mut command = Command::new("git");
+        command
+            .current_dir(dir)
+            .args(["add", "--all", "--"])
+            .arg(path);
+        if output(command).await?.is_some() {
+            staged.push(path.as_path());
+        }
+    }
+    // `diff --quiet` succeeds only when nothing differs from HEAD.
+    let mut command = Command::new("git");
+    command
+        .current_dir(dir)
+        .args(["diff", "--cached", "--quiet", "--"])
+        .args(&staged);
+    if output(command).await?.is_some() {
+        return Ok(None);
+    }
+
+    let mut command = Command::new("git");
+    command
+        .current_dir(dir)
+        .args(["commit", "--quiet", "--message", message, "--"])
+        .args(&staged);
+    let result = command.output().await?;
+    if !result.status.success() {
+        return Err(io::Error::other(format!(
+            "git commit failed: {}",
+            String::from_utf8_lossy(&result.stderr).trim()
+        )));
+    }
+    Ok(git(dir, &["rev-parse", "HEAD"])
+        .await?
+        .map(|hash| hash.trim_end().to_string()))
+}
+
+/// Describes a recorded change as a commit message: the action and keys in the subject,
+/// then `Tool`, `Catalog`, `Keys`, `Client`, `Session`, and `Request` trailers.
+pub fn commit_message(entry: &AuditEntry) -> String {
+    let mut keys = Vec::new();
+    touched_keys(&entry.details, &mut keys);
+    let catalog = entry
+        .path
+        .as_deref()
+        .and_then(|path| Path::new(path).file_name())
+        .map(|name| name.to_string_lossy().to_string());
+
+    let mut subject = format!("{}: ", entry.action);
+    match keys.len() {
+        0 => subject.push_str(catalog.as_deref().unwrap_or("catalog")),
+        count if count <= SUBJECT_KEYS => subject.push_str(&keys.join(", ")),
+        count => subject.push_str(&format!(
+            "{} and {} more",
+            keys[..SUBJECT_KEYS].join(", "),
+            count - SUBJECT_KEYS
+        )),
+    }
+
+    let mut trailers = vec![format!("Tool: {}", entry.action)];
+    if let Some(catalog) = catalog {
+        trailers.push(format!("Catalog: {catalog}"));
+    }
+    if !keys.is_empty() {
+        trailers.push(format!("Keys: {}", keys.join(", ")));
+    }
+    if let Some(client) = &entry.client {
+        trailers.push(format!("Client: {client}"));
+    }
+    trailers.push(format!("Session: {}", entry.session));
+    if let Some(request_id) = &entry.request_id {
+        trailers.push(format!("Request: {request_id}"));
+    }
+    format!("{subject}\n\n{}\n", trailers.join("\n"))
+}
+
+/// Collects the keys named anywhere in a change's arguments, such as each operation of a
+/// batch, without duplicates.
+fn touched_keys(details: &serde_json::Value, keys: &mut Vec<String>) {
+    match details {
+        serde_json::Value::Object(fields) => {
+            for (name, value) in fields {
+                if KEY_FIELDS.contains(&name.as_str()) {
+                    let named = match value {
+                        serde_json::Value::Array(items) => items.iter().collect(),
+                        value => vec![value],
+                    };
+                    for key in named.into_iter().filter_map(serde_json::Value::as_str) {
+                        if !keys.iter().any(|known| known == key) {
+                            keys.push(key.to_string());
+                        }
+                    }
+                } else {
+                    touched_keys(value, keys);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                touched_keys(item, keys);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_log(log: &str) -> Vec<FileRevision> {
+    log.split('\x1e')
+        .filter_map(|entry| {
+            let mut lines = entry.lines();
+            let mut header = lines.next()?.split('\x1f');
+            let path = lines.find(|line| !line.is_empty())?;
+            Some(FileRevision {
+                commit: header.next()?.to_string(),
+                author: header.next()?.to_string(),
+                date: header.next()?.to_string(),
+                summary: header.next().unwrap_or_default().to_string(),
+                path: path.to_string(),
+            })
+        })
+        .collect()
+}
+
+async fn git(dir: &Path, args: &[&str]) -> io::Result<Option<String>> {
+    let mut command = Command::new("git");
+    command.current_dir(dir).args(args);
+    output(command).await
+}
+
+/// Runs git and returns its standard output, or `None` when it exits with an error.
+async fn output(mut command: Command) -> io::Result<Option<String>> {
+    let output = command.output().await.map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => io::Error::other("git is not installed"),
+        _ => err,
+    })?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    String::from_utf8(output.stdout)
+        .map(Some)
+        .map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revision(commit: &str) -> FileRevision {
+        FileRevision {
+            commit: commit.to_string(),
+            author: "Ada".to_string(),
+            date: "2026-01-01T00:00:00+00:00".to_string(),
+            summary: format!("Commit {commit}"),
+            path: "Localizable.xcstrings".to_string(),
+        }
+    }
+
+    fn text(value: &str) -> Option<TranslationValue> {
+        Some(TranslationValue {
+            value: Some(value.to_string()),
+            ..TranslationValue::default()
+        })
+    }
+
+    #[test]
+    fn parses_log_entries_with_their_paths() {
+        let log = concat!(
+            "\x1eabc\x1fAda\x1f2026-01-02T00:00:00+00:00\x1fRename\n\nApp/Localizable.xcstrings\n",
+            "\x1edef\x1fBo\x1f2026-01-01T00:00:00+00:00\x1fAdd\n\nLocalizable.xcstrings\n",
+        );
+        let revisions = parse_log(log);
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].commit, "abc");
+        assert_eq!(revisions[0].path, "App/Localizable.xcstrings");
+        assert_eq!(revisions[1].author, "Bo");
+        assert_eq!(revisions[1].summary, "Add");
+    }
+
+    #[test]
+    fn commit_messages_name_the_tool_keys_and_client() {
+        let mut entry = AuditEntry::new("stdio", "batch_update");
+        entry.client = Some("agent-a".to_string());
+        entry.path = Some("/work/App/Localizable.xcstrings".to_string());
+        entry.details = serde_json::json!({
+            "operations": [
+                { "op": "upsert", "key": "a", "language": "de" },
+                { "op": "upsert", "key": "b", "language": "de" },
+                { "op": "delete_key", "key": "a" },
+                { "op": "rename_key", "key": "c", "newKey": "d" },
+            ]
+        });
+        assert_eq!(
+            commit_message(&entry),
+            "batch_update: a, b, c and 1 more\n\nTool: batch_update\nCatalog: Localizable.xcstrings\n\
+             Keys: a, b, c, d\nClient: agent-a\nSession: stdio\n"
+        );
+
+        let entry = AuditEntry::new("web", "add_language");
+        assert_eq!(
+            commit_message(&entry),
+            "add_language: catalog\n\nTool: add_language\nSession: web\n"
+        );
+    }
+
+    #[test]
+    fn last_change_ignores_the_oldest_version() {
+        assert_eq!(last_change(&[text("b"), text("b"), text("a")]), Some(1));
+        assert_eq!(last_change(&[text("b"), text("a"), text("a")]), Some(0));
+        assert_eq!(last_change(&[text("a"), text("a")]), None);
+        assert_eq!(last_change::<Option<TranslationValue>>(&[None]), None);
+    }
+
+    #[test]
+    fn keeps_only_commits_that_changed_the_value() {
+        let history = changes(vec![
+            (revision("e"), None),
+            (revision("d"), text("Speichern")),
+            (revision("c"), text("Sichern")),
+            (revision("b"), text("Sichern")),
+            (revision("a"), None),
+        ]);
+        let commits: Vec<_> = history
+            .iter()
+            .map(|change| (change.commit.as_str(), change.value.is_some()))
+            .collect();
+        assert_eq!(commits, [("e", false), ("d", true), ("b", true)]);
+    }
+}
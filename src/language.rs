@@ -0,0 +1,373 @@
+/// Language subtags Xcode can localize into: every ISO 639-1 code plus the ISO 639-2/3 codes
+/// Apple lists in its language picker. The English names are only used for suggestions.
+const LANGUAGES: &[(&str, &str)] = &[
+    ("aa", "Afar"),
+    ("ab", "Abkhazian"),
+    ("ae", "Avestan"),
+    ("af", "Afrikaans"),
+    ("ak", "Akan"),
+    ("am", "Amharic"),
+    ("an", "Aragonese"),
+    ("ar", "Arabic"),
+    ("as", "Assamese"),
+    ("ast", "Asturian"),
+    ("av", "Avaric"),
+    ("ay", "Aymara"),
+    ("az", "Azerbaijani"),
+    ("ba", "Bashkir"),
+    ("be", "Belarusian"),
+    ("bg", "Bulgarian"),
+    ("bi", "Bislama"),
+    ("bm", "Bambara"),
+    ("bn", "Bangla"),
+    ("bo", "Tibetan"),
+    ("br", "Breton"),
+    ("brx", "Bodo"),
+    ("bs", "Bosnian"),
+    ("ca", "Catalan"),
+    ("ce", "Chechen"),
+    ("ceb", "Cebuano"),
+    ("ch", "Chamorro"),
+    ("chr", "Cherokee"),
+    ("ckb", "Central Kurdish"),
+    ("co", "Corsican"),
+    ("cr", "Cree"),
+    ("cs", "Czech"),
+    ("cu", "Church Slavic"),
+    ("cv", "Chuvash"),
+    ("cy", "Welsh"),
+    ("da", "Danish"),
+    ("de", "German"),
+    ("doi", "Dogri"),
+    ("dv", "Divehi"),
+    ("dz", "Dzongkha"),
+    ("ee", "Ewe"),
+    ("el", "Greek"),
+    ("en", "English"),
+    ("eo", "Esperanto"),
+    ("es", "Spanish"),
+    ("et", "Estonian"),
+    ("eu", "Basque"),
+    ("fa", "Persian"),
+    ("ff", "Fula"),
+    ("fi", "Finnish"),
+    ("fil", "Filipino"),
+    ("fj", "Fijian"),
+    ("fo", "Faroese"),
+    ("fr", "French"),
+    ("fy", "Western Frisian"),
+    ("ga", "Irish"),
+    ("gd", "Scottish Gaelic"),
+    ("gl", "Galician"),
+    ("gn", "Guarani"),
+    ("gsw", "Swiss German"),
+    ("gu", "Gujarati"),
+    ("gv", "Manx"),
+    ("ha", "Hausa"),
+    ("haw", "Hawaiian"),
+    ("he", "Hebrew"),
+    ("hi", "Hindi"),
+    ("ho", "Hiri Motu"),
+    ("hr", "Croatian"),
+    ("ht", "Haitian Creole"),
+    ("hu", "Hungarian"),
+    ("hy", "Armenian"),
+    ("hz", "Herero"),
+    ("ia", "Interlingua"),
+    ("id", "Indonesian"),
+    ("ie", "Interlingue"),
+    ("ig", "Igbo"),
+    ("ii", "Sichuan Yi"),
+    ("ik", "Inupiaq"),
+    ("io", "Ido"),
+    ("is", "Icelandic"),
+    ("it", "Italian"),
+    ("iu", "Inuktitut"),
+    ("ja", "Japanese"),
+    ("jv", "Javanese"),
+    ("ka", "Georgian"),
+    ("kea", "Kabuverdianu"),
+    ("kg", "Kongo"),
+    ("ki", "Kikuyu"),
+    ("kj", "Kuanyama"),
+    ("kk", "Kazakh"),
+    ("kl", "Kalaallisut"),
+    ("km", "Khmer"),
+    ("kn", "Kannada"),
+    ("ko", "Korean"),
+    ("kok", "Konkani"),
+    ("kr", "Kanuri"),
+    ("ks", "Kashmiri"),
+    ("ku", "Kurdish"),
+    ("kv", "Komi"),
+    ("kw", "Cornish"),
+    ("ky", "Kyrgyz"),
+    ("la", "Latin"),
+    ("lb", "Luxembourgish"),
+    ("lg", "Ganda"),
+    ("li", "Limburgish"),
+    ("ln", "Lingala"),
+    ("lo", "Lao"),
+    ("lt", "Lithuanian"),
+    ("lu", "Luba-Katanga"),
+    ("lv", "Latvian"),
+    ("mai", "Maithili"),
+    ("mfe", "Morisyen"),
+    ("mg", "Malagasy"),
+    ("mh", "Marshallese"),
+    ("mi", "Maori"),
+    ("mk", "Macedonian"),
+    ("ml", "Malayalam"),
+    ("mn", "Mongolian"),
+    ("mni", "Manipuri"),
+    ("mr", "Marathi"),
+    ("ms", "Malay"),
+    ("mt", "Maltese"),
+    ("my", "Burmese"),
+    ("na", "Nauru"),
+    ("nb", "Norwegian Bokmal"),
+    ("nd", "North Ndebele"),
+    ("nds", "Low German"),
+    ("ne", "Nepali"),
+    ("ng", "Ndonga"),
+    ("nl", "Dutch"),
+    ("nn", "Norwegian Nynorsk"),
+    ("no", "Norwegian"),
+    ("nr", "South Ndebele"),
+    ("nv", "Navajo"),
+    ("ny", "Nyanja"),
+    ("oc", "Occitan"),
+    ("oj", "Ojibwa"),
+    ("om", "Oromo"),
+    ("or", "Odia"),
+    ("os", "Ossetic"),
+    ("pa", "Punjabi"),
+    ("pi", "Pali"),
+    ("pl", "Polish"),
+    ("ps", "Pashto"),
+    ("pt", "Portuguese"),
+    ("qu", "Quechua"),
+    ("rm", "Romansh"),
+    ("rn", "Rundi"),
+    ("ro", "Romanian"),
+    ("ru", "Russian"),
+    ("rw", "Kinyarwanda"),
+    ("sa", "Sanskrit"),
+    ("sat", "Santali"),
+    ("sc", "Sardinian"),
+    ("sd", "Sindhi"),
+    ("se", "Northern Sami"),
+    ("sg", "Sango"),
+    ("si", "Sinhala"),
+    ("sk", "Slovak"),
+    ("sl", "Slovenian"),
+    ("sm", "Samoan"),
+    ("sn", "Shona"),
+    ("so", "Somali"),
+    ("sq", "Albanian"),
+    ("sr", "Serbian"),
+    ("ss", "Swati"),
+    ("st", "Southern Sotho"),
+    ("su", "Sundanese"),
+    ("sv", "Swedish"),
+    ("sw", "Swahili"),
+    ("ta", "Tamil"),
+    ("te", "Telugu"),
+    ("tg", "Tajik"),
+    ("th", "Thai"),
+    ("ti", "Tigrinya"),
+    ("tk", "Turkmen"),
+    ("tl", "Tagalog"),
+    ("tn", "Tswana"),
+    ("to", "Tongan"),
+    ("tr", "Turkish"),
+    ("ts", "Tsonga"),
+    ("tt", "Tatar"),
+    ("tw", "Twi"),
+    ("ty", "Tahitian"),
+    ("ug", "Uyghur"),
+    ("uk", "Ukrainian"),
+    ("ur", "Urdu"),
+    ("uz", "Uzbek"),
+    ("ve", "Venda"),
+    ("vi", "Vietnamese"),
+    ("vo", "Volapuk"),
+    ("wa", "Walloon"),
+    ("wo", "Wolof"),
+    ("xh", "Xhosa"),
+    ("yi", "Yiddish"),
+    ("yo", "Yoruba"),
+    ("yue", "Cantonese"),
+    ("za", "Zhuang"),
+    ("zh", "Chinese"),
+    ("zu", "Zulu"),
+];
+
+/// Most suggestions returned for a code that could not be recognized.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Why a language code was rejected, with likely intended codes when the input looks like
+/// a typo or a language name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCode {
+    pub reason: String,
+    pub suggestions: Vec<String>,
+}
+
+/// Validates a BCP-47 language tag and returns it in the form Xcode writes: `_` becomes `-`,
+/// the language is lowercase, the script title case, and the region uppercase
+/// (`zh_hans_cn` becomes `zh-Hans-CN`).
+pub fn normalize(code: &str) -> Result<String, InvalidCode> {
+    let subtags: Vec<&str> = code.trim().split(['-', '_']).collect();
+    let language = subtags[0].to_ascii_lowercase();
+    let invalid = |reason: String| InvalidCode {
+        reason,
+        suggestions: suggest(&subtags),
+    };
+
+    if !is_known(&language) {
+        return Err(invalid(format!("'{}' is not a known language", subtags[0])));
+    }
+    let mut normalized = language;
+    let mut seen_script = false;
+    let mut seen_region = false;
+    for subtag in &subtags[1..] {
+        normalized.push('-');
+        let letters = subtag.chars().all(|ch| ch.is_ascii_alphabetic());
+        let digits = subtag.chars().all(|ch| ch.is_ascii_digit());
+        match subtag.len() {
+            4 if letters && !seen_script && !seen_region => {
+                seen_script = true;
+                normalized.push_str(&subtag[..1].to_ascii_uppercase());
+                normalized.push_str(&subtag[1..].to_ascii_lowercase());
+            }
+            2 if letters && !seen_region => {
+                seen_region = true;
+                normalized.push_str(&subtag.to_ascii_uppercase());
+            }
+            3 if digits && !seen_region => {
+                seen_region = true;
+                normalized.push_str(subtag);
+            }
+            5..=8 if subtag.chars().all(|ch| ch.is_ascii_alphanumeric()) => {
+                normalized.push_str(&subtag.to_ascii_lowercase());
+            }
+            4 if subtag.starts_with(|ch: char| ch.is_ascii_digit())
+                && subtag.chars().all(|ch| ch.is_ascii_alphanumeric()) =>
+            {
+                normalized.push_str(&subtag.to_ascii_lowercase());
+            }
+            _ => {
+                return Err(invalid(format!(
+                    "'{subtag}' is not a valid script, region, or variant subtag"
+                )))
+            }
+        }
+    }
+    Ok(normalized)
+}
+
+/// Locales `code` falls back to before the source language, most specific first:
+/// `zh-Hans-CN` yields `zh-Hans` then `zh`.
+pub fn parents(code: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(code), |code| code.rfind('-').map(|end| &code[..end])).skip(1)
+}
+
+fn is_known(language: &str) -> bool {
+    LANGUAGES.iter().any(|(code, _)| *code == language)
+}
+
+/// Codes the user probably meant: languages whose English name is the input or close to it
+/// (`French`, `Frnech`), or the two-letter code a three-letter one starts with (`eng`).
+fn suggest(subtags: &[&str]) -> Vec<String> {
+    let input = subtags[0].to_ascii_lowercase();
+    let mut candidates: Vec<(usize, &str)> = LANGUAGES
+        .iter()
+        .filter_map(|(code, name)| {
+            let name = name.to_ascii_lowercase();
+            let distance = edit_distance(&input, &name);
+            // Allow one typo per four letters so short inputs don't match everything.
+            (input.len() >= 4 && distance <= input.len() / 4 + 1).then_some((distance, *code))
+        })
+        .collect();
+    if input.len() == 3 && is_known(&input[..2]) {
+        candidates.push((1, &input[..2]));
+    }
+    candidates.sort();
+    candidates.dedup_by_key(|(_, code)| *code);
+
+    // Keep the rest of the tag when it is valid on its own, so `Frnech_CA` suggests `fr-CA`.
+    let rest = subtags[1..].join("-");
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .filter_map(|(_, code)| {
+            if rest.is_empty() {
+                Some(code.to_string())
+            } else {
+                normalize(&format!("{code}-{rest}")).ok()
+            }
+        })
+        .collect()
+}
+
+/// Levenshtein distance where swapping two adjacent letters counts as one edit.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_separators_and_case() {
+        assert_eq!(normalize("en_US").unwrap(), "en-US");
+        assert_eq!(normalize(" zh_hans_cn ").unwrap(), "zh-Hans-CN");
+        assert_eq!(normalize("ES-419").unwrap(), "es-419");
+        assert_eq!(normalize("fil").unwrap(), "fil");
+        assert_eq!(normalize("de-CH-1996").unwrap(), "de-CH-1996");
+        assert!(normalize("en-US-GB").is_err());
+        assert!(normalize("en-").is_err());
+    }
+
+    #[test]
+    fn parents_drop_one_subtag_at_a_time() {
+        assert_eq!(
+            parents("zh-Hans-CN").collect::<Vec<_>>(),
+            vec!["zh-Hans", "zh"]
+        );
+        assert_eq!(parents("fr").count(), 0);
+    }
+
+    #[test]
+    fn suggests_codes_for_typos_and_names() {
+        let err = normalize("Frnech").unwrap_err();
+        assert_eq!(err.suggestions, vec!["fr"]);
+        assert_eq!(
+            normalize("German_CH").unwrap_err().suggestions,
+            vec!["de-CH"]
+        );
+        assert_eq!(normalize("eng").unwrap_err().suggestions, vec!["en"]);
+        assert!(normalize("qq").unwrap_err().suggestions.is_empty());
+    }
+}
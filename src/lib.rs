@@ -1,4 +1,59 @@
+pub mod analytics;
 pub mod apple_json_formatter;
+pub mod assignments;
+pub mod audit;
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "cli")]
+pub mod commands;
+#[cfg(feature = "cli")]
+pub mod config;
+pub mod diff;
+pub mod digest;
+pub mod export;
+pub mod frozen;
+#[cfg(feature = "git")]
+pub mod git;
+pub mod language;
+#[cfg(feature = "mcp")]
+pub mod logging;
+#[cfg(feature = "mcp")]
 pub mod mcp_server;
+pub mod metadata;
+pub mod modified;
+pub mod patch;
+pub mod qa;
+pub mod repair;
+#[cfg(any(feature = "mcp", feature = "web"))]
+pub mod request_trace;
+pub mod similar;
+pub mod spelling;
+pub mod state;
 pub mod store;
+pub mod suggest;
+#[cfg(feature = "sync")]
+pub mod sync;
+pub mod terminology;
+#[cfg(any(feature = "mcp", feature = "web"))]
+pub mod tls;
+#[cfg(feature = "web")]
 pub mod web;
+pub mod workflow;
+pub mod xcodeproj;
+
+/// One `.xcstrings` catalog: load it, query and edit translations, validate it, and save
+/// it in Xcode's format. The MCP server and web UI edit catalogs through the same type, so
+/// tools linking the crate get identical behavior.
+pub use store::XcStringsStore as Catalog;
+/// The catalogs under a search root, discovered and opened on demand, with shared settings
+/// such as formatting and the review workflow.
+pub use store::XcStringsStoreManager as CatalogManager;
+pub use store::{
+    BatchOperation, InsertPosition, StoreError, TransactionOperation, TranslationRecord,
+    TranslationUpdate, TranslationValue,
+};
+
+/// Converting translations to and from JSON, CSV, and `.strings` files.
+pub use export::{export, parse as import, Format as ExportFormat, ImportedValue};
+/// Catalog checks: rules, their options, and the findings they report.
+pub use qa::{Finding, QaOptions, QaReport, QaRule, Severity};
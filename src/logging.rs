@@ -0,0 +1,282 @@
+use std::{
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use rmcp::model::LoggingLevel;
+use serde_json::{Map, Value};
+use tokio::sync::broadcast;
+use tracing::{
+    field::{Field, Visit},
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{fmt::MakeWriter, layer::Context, Layer};
+
+const LOG_CHANNEL_CAPACITY: usize = 512;
+
+/// Only events emitted by this crate are forwarded; transport internals stay on stderr.
+const FORWARDED_TARGET_PREFIX: &str = "xcstrings_mcp";
+
+/// A tracing event converted into the shape of an MCP `notifications/message` payload.
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    pub level: LoggingLevel,
+    pub logger: String,
+    pub data: Value,
+}
+
+/// Tracing layer that publishes this crate's events so they can be forwarded to MCP clients.
+#[derive(Clone)]
+pub struct McpLogLayer {
+    sender: broadcast::Sender<LogRecord>,
+}
+
+impl McpLogLayer {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(LOG_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LogRecord> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for McpLogLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for McpLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if !metadata.target().starts_with(FORWARDED_TARGET_PREFIX)
+            || self.sender.receiver_count() == 0
+        {
+            return;
+        }
+
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+        let _ = self.sender.send(LogRecord {
+            level: logging_level(*metadata.level()),
+            logger: metadata.target().to_string(),
+            data: Value::Object(visitor.fields),
+        });
+    }
+}
+
+pub fn logging_level(level: Level) -> LoggingLevel {
+    match level {
+        Level::ERROR => LoggingLevel::Error,
+        Level::WARN => LoggingLevel::Warning,
+        Level::INFO => LoggingLevel::Info,
+        Level::DEBUG | Level::TRACE => LoggingLevel::Debug,
+    }
+}
+
+/// Numeric severity following the syslog ordering used by MCP (higher is more severe).
+pub fn severity(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+/// Append-only log file that is renamed to `<path>.1` once it would grow past `max_bytes`.
+/// Older rotations shift to `<path>.2` and so on, and only `keep` of them are kept.
+pub struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+    state: Mutex<RotatingState>,
+}
+
+struct RotatingState {
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    pub fn open(path: impl Into<PathBuf>, max_bytes: u64, keep: usize) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+        let file = open_append(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            keep,
+            state: Mutex::new(RotatingState { file, size }),
+        })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&self, state: &mut RotatingState) -> io::Result<()> {
+        state.file.flush()?;
+        if self.keep == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            for index in (1..self.keep).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        state.file = open_append(&self.path)?;
+        state.size = 0;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+impl Write for &RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // Each formatted event arrives in one write, so events are never split across files.
+        if state.size > 0 && state.size + buf.len() as u64 > self.max_bytes {
+            self.rotate(&mut state)?;
+        }
+        state.file.write_all(buf)?;
+        state.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .file
+            .flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFile {
+    type Writer = &'a RotatingFile;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self
+    }
+}
+
+#[derive(Default)]
+struct JsonVisitor {
+    fields: Map<String, Value>,
+}
+
+impl Visit for JsonVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.fields
+            .insert(field.name().to_string(), Value::from(format!("{value:?}")));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields
+            .insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.fields
+            .insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.fields
+            .insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.fields
+            .insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.fields
+            .insert(field.name().to_string(), Value::from(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn forwards_crate_events_with_fields() {
+        let layer = McpLogLayer::new();
+        let mut records = layer.subscribe();
+        let subscriber = tracing_subscriber::registry().with(layer.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(
+                target: "xcstrings_mcp::store",
+                path = "Localizable.xcstrings",
+                "Catalog changed on disk"
+            );
+            tracing::warn!(target: "hyper::proto", "not forwarded");
+        });
+
+        let record = records.try_recv().expect("crate event forwarded");
+        assert_eq!(record.level, LoggingLevel::Warning);
+        assert_eq!(record.logger, "xcstrings_mcp::store");
+        assert_eq!(record.data["message"], "Catalog changed on disk");
+        assert_eq!(record.data["path"], "Localizable.xcstrings");
+        assert!(records.try_recv().is_err());
+    }
+
+    #[test]
+    fn rotating_file_keeps_recent_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs/server.log");
+        let log = RotatingFile::open(&path, 10, 2).expect("open log");
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            (&log).write_all(line.as_bytes()).unwrap();
+        }
+        let read = |path: &Path| fs::read_to_string(path).unwrap();
+        assert_eq!(read(&path), "fourth\n");
+        assert_eq!(read(&log.rotated_path(1)), "third\n");
+        assert_eq!(read(&log.rotated_path(2)), "second\n");
+        assert!(!log.rotated_path(3).exists());
+
+        drop(log);
+        let reopened = RotatingFile::open(&path, 10, 2).expect("reopen log");
+        (&reopened).write_all(b"fifth\n").unwrap();
+        assert_eq!(read(&path), "fifth\n");
+        assert_eq!(read(&reopened.rotated_path(1)), "fourth\n");
+    }
+
+    #[test]
+    fn severity_follows_syslog_order() {
+        assert!(severity(LoggingLevel::Debug) < severity(LoggingLevel::Info));
+        assert!(severity(LoggingLevel::Warning) < severity(LoggingLevel::Error));
+        assert_eq!(logging_level(Level::TRACE), LoggingLevel::Debug);
+    }
+}
@@ -1,21 +1,130 @@
-use std::{env, ffi::OsStr, net::SocketAddr, path::PathBuf, sync::Arc};
+use std::process::ExitCode;
+#[cfg(feature = "web")]
+use std::process::Stdio;
+#[cfg(feature = "mcp")]
+use std::{env, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 
+use clap::Parser;
+use tracing_subscriber::EnvFilter;
+use xcstrings_mcp::{
+    cli::{self, Cli},
+    commands,
+};
+
+#[cfg(feature = "mcp")]
+use anyhow::Context;
+#[cfg(feature = "mcp")]
 use rmcp::service::ServiceExt;
-use tokio::signal;
+#[cfg(feature = "mcp")]
+use tokio::{signal, task::JoinSet};
+#[cfg(feature = "mcp")]
 use tracing::{error, info, warn};
+#[cfg(feature = "mcp")]
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
+#[cfg(feature = "web")]
+use xcstrings_mcp::web;
+#[cfg(feature = "mcp")]
+use xcstrings_mcp::{
+    apple_json_formatter::FormatOptions,
+    audit::AuditLog,
+    cli::LogFormat,
+    config::ServerConfig,
+    logging::{McpLogLayer, RotatingFile},
+    mcp_server::{self, XcStringsMcpServer, DEFAULT_MAX_RESPONSE_BYTES},
+    qa::PlaceholderValidation,
+    state::StateValidation,
+    store::XcStringsStoreManager,
+    tls::TlsFiles,
+};
 
-use anyhow::Context;
-use xcstrings_mcp::{mcp_server::XcStringsMcpServer, store::XcStringsStoreManager, web};
+fn main() -> ExitCode {
+    // Legacy variable names are copied with `set_var`, which must happen before the
+    // runtime starts its worker threads.
+    cli::apply_legacy_env();
+    let mut cli = Cli::parse();
+    let runtime = match tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let Some(command) = cli.command.take() else {
+        #[cfg(feature = "mcp")]
+        return match runtime.block_on(run(cli)) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("Error: {err:?}");
+                ExitCode::FAILURE
+            }
+        };
+        #[cfg(not(feature = "mcp"))]
+        {
+            eprintln!(
+                "error: built without the `mcp` feature, so only subcommands can run; see --help"
+            );
+            return ExitCode::from(commands::EXIT_ERROR);
+        }
+    };
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
         .without_time()
+        .with_writer(std::io::stderr)
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+    match runtime.block_on(commands::run(command, cli.config.as_deref())) {
+        Ok(output) => {
+            print!("{}", output.stdout);
+            output.exit_code()
+        }
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            ExitCode::from(commands::EXIT_ERROR)
+        }
+    }
+}
+
+#[cfg(feature = "mcp")]
+async fn run(cli: Cli) -> anyhow::Result<()> {
+    // stdout carries the MCP stdio transport, so local logs go to stderr (or the log file)
+    // while the forwarding layer relays this crate's events to the client.
+    let mcp_logs = McpLogLayer::new();
+    let local_logs = match &cli.log_file {
+        None => {
+            let layer = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+            match cli.log_format {
+                LogFormat::Text => layer.without_time().boxed(),
+                LogFormat::Json => layer.json().boxed(),
+            }
+        }
+        Some(path) => {
+            let file = RotatingFile::open(path, cli.log_file_max_bytes, cli.log_file_keep)
+                .with_context(|| format!("failed to open log file {}", path.display()))?;
+            let layer = tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(file);
+            match cli.log_format {
+                LogFormat::Text => layer.boxed(),
+                LogFormat::Json => layer.json().boxed(),
+            }
+        }
+    };
+    tracing_subscriber::registry()
+        .with(local_logs.with_filter(EnvFilter::from_default_env()))
+        .with(mcp_logs.clone())
         .init();
 
-    let config = Config::from_env()?;
-    match (&config.path, &config.web_addr) {
+    let config = Config::from_cli(cli)?;
+    let web_target = match (&config.web_socket_path, &config.web_addr) {
+        (Some(socket), _) => Some(format!("unix:{}", socket.display())),
+        (None, Some(addr)) => Some(addr.to_string()),
+        (None, None) => None,
+    };
+    match (&config.path, &web_target) {
         (Some(path), Some(web_addr)) => {
             info!(path = %path.display(), web_addr = %web_addr, "Starting xcstrings MCP server with web UI");
         }
@@ -30,11 +139,61 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    let stores = Arc::new(
-        XcStringsStoreManager::new(config.path.clone())
+    let mut manager = XcStringsStoreManager::new(config.path.clone())
+        .await
+        .map_err(|err| anyhow::anyhow!(err))?;
+    if let Some(project) = &config.project {
+        info!(project = %project.display(), "Discovering catalogs from Xcode project");
+        manager = manager
+            .with_project(project.clone())
             .await
-            .map_err(|err| anyhow::anyhow!(err))?,
-    );
+            .map_err(|err| anyhow::anyhow!(err))?;
+    }
+    if let Some(audit_path) = &config.audit_log {
+        info!(path = %audit_path.display(), "Recording changes in audit log");
+        manager = manager.with_audit_log(AuditLog::new(audit_path.clone()));
+    }
+    if config.read_only {
+        info!("Catalogs are read-only; edits are refused");
+        manager = manager.with_read_only(true);
+    }
+    if !config.server.read_only_paths.is_empty() {
+        info!(patterns = ?config.server.read_only_paths, "Protecting catalogs from edits");
+        manager = manager.with_read_only_paths(config.server.read_only_paths.clone());
+    }
+    if !config.server.catalog_labels.is_empty() {
+        manager = manager.with_catalog_labels(config.server.catalog_labels.clone());
+    }
+    if config.review_workflow {
+        info!("Translation edits must follow the review workflow");
+        manager = manager.with_review_workflow(true);
+    }
+    if config.review_on_source_change {
+        info!("Source text changes send translations back to review");
+        manager = manager.with_review_on_source_change(true);
+    }
+    if config.require_comment {
+        info!("New keys need a developer comment");
+        manager = manager.with_require_comment(true);
+    }
+    #[cfg(feature = "git")]
+    if config.git_auto_commit {
+        info!("Committing every change to git");
+        manager = manager.with_git_auto_commit(true);
+    }
+    if config.server.format != FormatOptions::default() {
+        info!(format = ?config.server.format, "Writing catalogs with custom formatting");
+        manager = manager.with_format_options(config.server.format.clone());
+    }
+    if config.server.state_validation == StateValidation::Strict {
+        info!("Refusing translations with unknown states");
+        manager = manager.with_state_validation(StateValidation::Strict);
+    }
+    if config.server.placeholder_validation != PlaceholderValidation::default() {
+        info!(validation = ?config.server.placeholder_validation, "Changing how format specifiers of upserts are checked");
+        manager = manager.with_placeholder_validation(config.server.placeholder_validation);
+    }
+    let stores = Arc::new(manager);
 
     if config.path.is_none() {
         let discovered = stores.available_paths().await;
@@ -48,25 +207,56 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    let _web_handle = if let Some(addr) = config.web_addr {
-        let manager = stores.clone();
-        Some(tokio::spawn(async move {
-            if let Err(err) = web::serve(addr, manager).await {
-                warn!(
-                    ?err,
-                    "Web server failed to start or stopped (MCP server continues to work)"
-                );
-            }
-        }))
+    let _watcher_handle = config
+        .watch_interval
+        .map(|interval| stores.spawn_watcher(interval));
+    let _qa_sweep_handle = config.qa_sweep_interval.map(|interval| {
+        info!(seconds = interval.as_secs(), "Running background QA sweeps");
+        stores.spawn_qa_sweeps(interval, config.server.qa.clone())
+    });
+
+    // Load certificates up front so a bad file stops startup with a clear error.
+    let mcp_http_tls = config
+        .mcp_http_tls
+        .as_ref()
+        .filter(|_| config.mcp_http_addr.is_some())
+        .map(TlsFiles::load)
+        .transpose()?;
+    #[cfg(feature = "web")]
+    let _web_handle = start_web(&config, web_target.is_some(), stores.clone()).await?;
+
+    if !config.server.disabled_tools.is_empty() {
+        info!(tools = ?config.server.disabled_tools, "Disabling MCP tools");
+    }
+    let server = XcStringsMcpServer::new(stores.clone())
+        .with_log_layer(mcp_logs)
+        .with_disabled_tools(&config.server.disabled_tools)
+        .with_max_response_bytes(config.max_response_bytes)
+        .with_destructive_confirmation(config.confirm_destructive)
+        .with_qa_options(config.server.qa.clone());
+    #[cfg(feature = "sync")]
+    let server = server.with_sync_config(config.server.sync.clone());
+    let server = if config.read_only {
+        server.with_read_only()
     } else {
-        None
+        server
     };
-
-    let mcp_handle = {
-        let server = XcStringsMcpServer::new(stores.clone());
-        tokio::spawn(async move {
+    // Each transport gets its own sessions over the same stores, so an edit made through
+    // one is visible to the other.
+    let mut transports = JoinSet::new();
+    if let Some(addr) = config.mcp_http_addr {
+        let server = server.clone();
+        transports.spawn(async move {
+            if let Err(err) = mcp_server::serve_http(addr, server, mcp_http_tls).await {
+                error!(?err, "MCP HTTP transport failed to start or stopped");
+            }
+        });
+    }
+    if config.mcp_stdio {
+        let server = server.new_session();
+        transports.spawn(async move {
             let transport = (tokio::io::stdin(), tokio::io::stdout());
-            match server.router().serve(transport).await {
+            match server.serve(transport).await {
                 Ok(running) => {
                     if let Err(err) = running.waiting().await {
                         error!(?err, "MCP service finished with error");
@@ -76,14 +266,14 @@ async fn main() -> anyhow::Result<()> {
                     error!(?err, "Failed to start MCP service");
                 }
             }
-        })
-    };
+        });
+    }
 
     tokio::select! {
         _ = signal::ctrl_c() => {
             warn!("Received Ctrl+C — shutting down");
         }
-        _ = mcp_handle => {
+        _ = transports.join_next() => {
             warn!("MCP task exited");
         }
     }
@@ -91,50 +281,346 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Starts the web UI in the background on the configured socket or address. Failing to
+/// bind only logs a warning, since the MCP server keeps working without it.
+#[cfg(feature = "web")]
+async fn start_web(
+    config: &Config,
+    enabled: bool,
+    stores: Arc<XcStringsStoreManager>,
+) -> anyhow::Result<Option<tokio::task::JoinHandle<()>>> {
+    let web_tls = config
+        .web_tls
+        .as_ref()
+        .filter(|_| config.web_addr.is_some())
+        .map(TlsFiles::load)
+        .transpose()?;
+    let web_options = web::WebOptions {
+        auth: config.server.web_auth.clone(),
+        qa: config.server.qa.clone(),
+        assets_dir: config.web_assets_dir.clone(),
+        webhook_secret: config.server.webhook_secret.clone(),
+    };
+    if enabled && web_options.auth.is_enabled() {
+        info!("Web API requires authentication");
+    }
+    if enabled && web_options.webhook_secret.is_some() {
+        info!("Accepting signed translation webhooks");
+    }
+    if let Some(dir) = web_options.assets_dir.as_ref().filter(|_| enabled) {
+        info!(dir = %dir.display(), "Serving web UI files from disk");
+    }
+    let handle = match (config.web_socket_path.clone(), config.web_addr) {
+        #[cfg(unix)]
+        (Some(socket_path), _) => {
+            if config.web_tls.is_some() {
+                warn!("WEB_TLS_CERT/WEB_TLS_KEY are ignored when serving on WEB_SOCKET_PATH");
+            }
+            let manager = stores.clone();
+            Some(tokio::spawn(async move {
+                if let Err(err) = web::serve_unix(&socket_path, manager, web_options).await {
+                    warn!(
+                        ?err,
+                        "Web server failed to start or stopped (MCP server continues to work)"
+                    );
+                }
+            }))
+        }
+        (None, Some(addr)) => match web::bind(addr, &web_options).await {
+            Ok(listener) => {
+                // The bound port differs from WEB_PORT when it is 0.
+                let url = web::url(listener.local_addr()?, web_tls.is_some());
+                if let Some(file) = &config.web_url_file {
+                    if let Err(err) = std::fs::write(file, format!("{url}\n")) {
+                        warn!(?err, path = %file.display(), "Failed to write the web UI URL");
+                    }
+                }
+                if config.open_browser {
+                    open_browser(&url);
+                }
+                let manager = stores.clone();
+                Some(tokio::spawn(async move {
+                    if let Err(err) =
+                        web::serve_listener(listener, manager, web_options, web_tls).await
+                    {
+                        warn!(?err, "Web server stopped (MCP server continues to work)");
+                    }
+                }))
+            }
+            Err(err) => {
+                warn!(
+                    ?err,
+                    "Web server failed to start (MCP server continues to work)"
+                );
+                None
+            }
+        },
+        _ => None,
+    };
+    if config.web_addr.is_none() && (config.open_browser || config.web_url_file.is_some()) {
+        warn!("--open and WEB_URL_FILE only apply to a web UI served on WEB_HOST/WEB_PORT");
+    }
+    Ok(handle)
+}
+
+#[cfg(feature = "mcp")]
 struct Config {
     path: Option<PathBuf>,
+    project: Option<PathBuf>,
     web_addr: Option<SocketAddr>,
+    web_socket_path: Option<PathBuf>,
+    #[cfg(feature = "web")]
+    web_assets_dir: Option<PathBuf>,
+    #[cfg(feature = "web")]
+    web_url_file: Option<PathBuf>,
+    #[cfg(feature = "web")]
+    open_browser: bool,
+    watch_interval: Option<Duration>,
+    qa_sweep_interval: Option<Duration>,
+    mcp_http_addr: Option<SocketAddr>,
+    mcp_stdio: bool,
+    audit_log: Option<PathBuf>,
+    #[cfg(feature = "web")]
+    web_tls: Option<TlsFiles>,
+    mcp_http_tls: Option<TlsFiles>,
+    server: ServerConfig,
+    max_response_bytes: Option<usize>,
+    confirm_destructive: bool,
+    read_only: bool,
+    review_workflow: bool,
+    review_on_source_change: bool,
+    require_comment: bool,
+    #[cfg(feature = "git")]
+    git_auto_commit: bool,
 }
 
+#[cfg(feature = "mcp")]
 impl Config {
-    fn from_env() -> anyhow::Result<Self> {
-        let mut args = env::args_os().skip(1);
+    fn from_cli(cli: Cli) -> anyhow::Result<Self> {
+        let path = cli.catalog_path();
 
-        let path = if let Ok(path) = env_var("STRINGS_PATH", "XCSTRINGS_PATH") {
-            Some(PathBuf::from(path))
-        } else {
-            let mut candidate = args.next();
-            if matches!(candidate.as_ref(), Some(arg) if arg == OsStr::new("--")) {
-                candidate = args.next();
+        // Only enable web server if a host or port is explicitly set
+        let web_addr =
+            socket_addr(cli.web_host, cli.web_port, 8787).context("invalid web address")?;
+
+        // Unix socket for the web API; replaces WEB_HOST/WEB_PORT so no TCP port is opened
+        let web_socket_path = non_blank_path(cli.web_socket);
+        if web_socket_path.is_some() && cfg!(not(unix)) {
+            anyhow::bail!("WEB_SOCKET_PATH is only supported on Unix platforms");
+        }
+        #[cfg(not(feature = "web"))]
+        if web_addr.is_some() || web_socket_path.is_some() {
+            anyhow::bail!("built without the `web` feature, so the web UI can't be served");
+        }
+
+        // Static files that override the embedded web UI, for editing it without a rebuild
+        #[cfg(feature = "web")]
+        let web_assets_dir = non_blank_path(cli.web_assets_dir);
+        #[cfg(feature = "web")]
+        if let Some(dir) = &web_assets_dir {
+            if !dir.is_dir() {
+                anyhow::bail!("WEB_ASSETS_DIR {} is not a directory", dir.display());
             }
-            candidate.map(PathBuf::from)
-        };
+        }
+
+        // Polling interval for detecting catalog edits made outside this server; 0 disables it
+        let watch_interval =
+            (cli.watch_interval_ms > 0).then(|| Duration::from_millis(cli.watch_interval_ms));
+        let qa_sweep_interval = (cli.qa_sweep_interval_secs > 0)
+            .then(|| Duration::from_secs(cli.qa_sweep_interval_secs));
+
+        // Serve MCP over streamable HTTP when enabled or when a host or port is configured
+        let mcp_http_host = cli
+            .mcp_http_host
+            .or_else(|| cli.mcp_http.then(|| "127.0.0.1".to_string()));
+        let mcp_http_addr = socket_addr(mcp_http_host, cli.mcp_http_port, 8788)
+            .context("invalid MCP HTTP address")?;
+        // stdio stays the default transport unless HTTP replaces it; both can run together
+        let mcp_stdio = cli.mcp_stdio.unwrap_or(mcp_http_addr.is_none());
+        if !mcp_stdio && mcp_http_addr.is_none() {
+            anyhow::bail!("MCP_STDIO=false requires the MCP HTTP transport (set MCP_HTTP=true)");
+        }
+
+        let audit_log = non_blank_path(cli.audit_log);
+
+        let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let (mut server, config_path) = ServerConfig::discover(cli.config.as_deref(), &cwd)?;
+        if let Some(config_path) = config_path {
+            info!(path = %config_path.display(), "Loaded config file");
+        }
+        if let Some(list) = &cli.disabled_tools {
+            server.disable_tools_from_list(list);
+        }
+        if let Some(list) = &cli.readonly_paths {
+            server.protect_paths_from_list(list);
+        }
 
-        // Only enable web server if environment variables are explicitly set
-        let web_addr = if env_var("WEB_HOST", "XCSTRINGS_WEB_HOST").is_ok()
-            || env_var("WEB_PORT", "XCSTRINGS_WEB_PORT").is_ok()
+        let max_response_bytes = cli
+            .max_response_bytes
+            .or(server.max_response_bytes)
+            .unwrap_or(DEFAULT_MAX_RESPONSE_BYTES);
+        let max_response_bytes = (max_response_bytes > 0).then_some(max_response_bytes);
+
+        // Set CONFIRM_DESTRUCTIVE=false in CI so destructive tools never wait for a prompt
+        let confirm_destructive = cli
+            .confirm_destructive
+            .or(server.confirm_destructive)
+            .unwrap_or(true);
+
+        let review_workflow = cli
+            .review_workflow
+            .or(server.review_workflow)
+            .unwrap_or(false);
+        let review_on_source_change = cli
+            .review_on_source_change
+            .or(server.review_on_source_change)
+            .unwrap_or(false);
+        let require_comment = cli
+            .require_comment
+            .or(server.require_comment)
+            .unwrap_or(false);
+        let git_auto_commit = cli
+            .git_auto_commit
+            .or(server.git_auto_commit)
+            .unwrap_or(false);
+        #[cfg(not(feature = "git"))]
+        if git_auto_commit {
+            anyhow::bail!("built without the `git` feature, so changes can't be committed");
+        }
+
+        #[cfg(feature = "web")]
         {
-            let host = env_var("WEB_HOST", "XCSTRINGS_WEB_HOST")
-                .unwrap_or_else(|_| "127.0.0.1".to_string());
-            let port =
-                env_var("WEB_PORT", "XCSTRINGS_WEB_PORT").unwrap_or_else(|_| "8787".to_string());
-
-            let port: u16 = port.parse().context("invalid web port")?;
-            let addr: SocketAddr = format!("{}:{}", host, port)
-                .parse()
-                .context("invalid web address")?;
-            Some(addr)
-        } else {
-            None
-        };
+            // Bearer token and/or basic-auth credentials for the web API
+            if let Some(token) = cli.web_auth_token {
+                server.web_auth.token = Some(token);
+            }
+            if let Some(username) = cli.web_auth_user {
+                server.web_auth.username = Some(username);
+            }
+            if let Some(password) = cli.web_auth_password {
+                server.web_auth.password = Some(password);
+            }
+            let web_auth = &mut server.web_auth;
+            for value in [
+                &mut web_auth.token,
+                &mut web_auth.username,
+                &mut web_auth.password,
+            ] {
+                if value
+                    .as_deref()
+                    .is_some_and(|value| value.trim().is_empty())
+                {
+                    *value = None;
+                }
+            }
+            if web_auth.username.is_some() != web_auth.password.is_some() {
+                anyhow::bail!("WEB_AUTH_USER and WEB_AUTH_PASSWORD must be set together");
+            }
+            if let Some(secret) = cli.webhook_secret {
+                server.webhook_secret = Some(secret);
+            }
+            if server
+                .webhook_secret
+                .as_deref()
+                .is_some_and(|secret| secret.trim().is_empty())
+            {
+                server.webhook_secret = None;
+            }
+        }
+
+        // Certificate pairs from the command line replace the config file's
+        #[cfg(feature = "web")]
+        let web_tls = tls_files(cli.web_tls_cert, cli.web_tls_key).or(server.web_tls.clone());
+        #[cfg(not(feature = "web"))]
+        let web_tls = tls_files(cli.web_tls_cert, cli.web_tls_key);
+        let mcp_http_tls = tls_files(cli.mcp_http_tls_cert, cli.mcp_http_tls_key)
+            .or(server.mcp_http_tls.clone())
+            .or_else(|| web_tls.clone());
+
+        Ok(Self {
+            path,
+            project: non_blank_path(cli.project),
+            web_addr,
+            web_socket_path,
+            #[cfg(feature = "web")]
+            web_assets_dir,
+            #[cfg(feature = "web")]
+            web_url_file: non_blank_path(cli.web_url_file),
+            #[cfg(feature = "web")]
+            open_browser: cli.open,
+            watch_interval,
+            qa_sweep_interval,
+            mcp_http_addr,
+            mcp_stdio,
+            audit_log,
+            #[cfg(feature = "web")]
+            web_tls,
+            mcp_http_tls,
+            server,
+            max_response_bytes,
+            confirm_destructive,
+            read_only: cli.readonly,
+            review_workflow,
+            review_on_source_change,
+            require_comment,
+            #[cfg(feature = "git")]
+            git_auto_commit,
+        })
+    }
+}
+
+/// Opens `url` with the platform's default handler, without waiting for the browser.
+#[cfg(feature = "web")]
+fn open_browser(url: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    let spawned = command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match spawned {
+        // Reap the launcher in the background so it doesn't linger as a zombie.
+        Ok(mut child) => {
+            std::thread::spawn(move || child.wait());
+        }
+        Err(err) => warn!(?err, %url, "Failed to open the web UI in a browser"),
+    }
+}
 
-        Ok(Self { path, web_addr })
+/// Address to listen on when either the host or the port is given, defaulting the other.
+#[cfg(feature = "mcp")]
+fn socket_addr(
+    host: Option<String>,
+    port: Option<u16>,
+    default_port: u16,
+) -> anyhow::Result<Option<SocketAddr>> {
+    if host.is_none() && port.is_none() {
+        return Ok(None);
     }
+    let host = host.unwrap_or_else(|| "127.0.0.1".to_string());
+    let port = port.unwrap_or(default_port);
+    Ok(Some(format!("{host}:{port}").parse()?))
+}
+
+#[cfg(feature = "mcp")]
+fn non_blank_path(path: Option<PathBuf>) -> Option<PathBuf> {
+    path.filter(|path| !path.as_os_str().to_string_lossy().trim().is_empty())
 }
 
-fn env_var(primary: &str, legacy: &str) -> Result<String, env::VarError> {
-    env::var(primary).or_else(|primary_err| match primary_err {
-        env::VarError::NotPresent => env::var(legacy),
-        err => Err(err),
+/// Clap requires the certificate and key together, so one without the other can't occur.
+#[cfg(feature = "mcp")]
+fn tls_files(cert: Option<PathBuf>, key: Option<PathBuf>) -> Option<TlsFiles> {
+    Some(TlsFiles {
+        cert: cert?,
+        key: key?,
     })
 }
@@ -1,63 +1,798 @@
-use std::{collections::BTreeMap, future::Future, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
 
+use axum_server::tls_rustls::RustlsConfig;
 use rmcp::{
     handler::server::{
-        router::Router,
-        tool::{Parameters, ToolRouter},
+        tool::{ToolCallContext, ToolRouter},
+        wrapper::Parameters,
+    },
+    model::{
+        AnnotateAble, CallToolRequestParam, CallToolResult, CompleteRequestParam, CompleteResult,
+        CompletionInfo, Content, JsonObject, ListResourcesResult, ListToolsResult, LoggingLevel,
+        LoggingMessageNotificationParam, PaginatedRequestParam, RawResource,
+        ReadResourceRequestParam, ReadResourceResult, Reference, Resource, ResourceContents,
+        ResourceUpdatedNotificationParam, ServerCapabilities, ServerInfo, SetLevelRequestParam,
+        SubscribeRequestParam, UnsubscribeRequestParam,
     },
-    model::{CallToolResult, Content, ServerCapabilities, ServerInfo},
-    tool, tool_handler, tool_router, ErrorData as McpError,
+    service::{ElicitationError, NotificationContext, RequestContext},
+    tool, tool_router,
+    transport::streamable_http_server::{
+        session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+    },
+    ErrorData as McpError, Peer, RoleServer, ServiceError,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json;
-
+use tokio::{
+    net::TcpListener,
+    sync::{broadcast, RwLock},
+    task::JoinHandle,
+};
+use tracing::{info, warn};
+
+use crate::analytics::DEFAULT_TOP;
+use crate::audit::AuditEntry;
+use crate::digest::Digest;
+#[cfg(feature = "git")]
+use crate::git;
+use crate::logging::{self, LogRecord, McpLogLayer};
+use crate::metadata::KeyMetadata;
+use crate::modified;
+use crate::patch::PatchOperation;
+use crate::qa::{QaOptions, QaRule};
+use crate::request_trace;
+use crate::similar;
 use crate::store::{
-    StoreError, SubstitutionUpdate, TranslationSummary, TranslationUpdate, TranslationValue,
-    XcStringsStore, XcStringsStoreManager,
+    self, BatchOperation, InsertPosition, KeyNamespace, StoreError, StoreEvent, SubstitutionUpdate,
+    TransactionOperation, TranslationSummary, TranslationUpdate, TranslationValue, UpsertOutcome,
+    VariationWarning, XcStringsStore, XcStringsStoreManager, DEVICES,
 };
+use crate::suggest;
+#[cfg(feature = "sync")]
+use crate::sync::{self, SyncConfig, SyncDirection, SyncError};
+use crate::terminology;
 
 #[derive(Clone)]
 pub struct XcStringsMcpServer {
     stores: Arc<XcStringsStoreManager>,
     tool_router: ToolRouter<Self>,
+    subscriptions: Arc<RwLock<HashSet<String>>>,
+    log_level: Arc<RwLock<LoggingLevel>>,
+    log_layer: Option<McpLogLayer>,
+    max_response_bytes: Option<usize>,
+    confirm_destructive: bool,
+    /// Whether writing tools were removed by [`Self::with_read_only`].
+    read_only: bool,
+    /// When the server was created, for the uptime `server_status` reports.
+    started: Instant,
+    qa_options: Arc<QaOptions>,
+    #[cfg(feature = "sync")]
+    sync_config: Arc<SyncConfig>,
+}
+
+/// Answer requested from the user through elicitation before a destructive tool runs.
+#[derive(Debug, Deserialize, JsonSchema)]
+struct DestructiveConfirmation {
+    /// Set to true to go ahead with the change
+    confirm: bool,
 }
 
+rmcp::elicit_safe!(DestructiveConfirmation);
+
 const DEFAULT_LIST_LIMIT: usize = 100;
+/// Default byte budget for list responses (64 KiB keeps even small-context agents comfortable).
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 64 * 1024;
+/// Room reserved for the counters and notice that wrap the items of a list response.
+const RESPONSE_ENVELOPE_BYTES: usize = 512;
+const CATALOG_URI_PREFIX: &str = "file://";
+const CATALOG_CHANGED_LOGGER: &str = "catalog_changed";
+/// Logger name for errors and warnings reported by background QA sweeps.
+const QA_FINDINGS_LOGGER: &str = "qa_findings";
+const DEFAULT_LOG_LEVEL: LoggingLevel = LoggingLevel::Info;
+const MCP_HTTP_PATH: &str = "/mcp";
+const SESSION_ID_HEADER: &str = "mcp-session-id";
+/// Session label used in the audit log for the single stdio connection.
+const STDIO_SESSION: &str = "stdio";
+/// Imports replacing more existing translations than this ask the user first, like
+/// destructive tools.
+#[cfg(feature = "sync")]
+const CONFIRM_OVERWRITES_ABOVE: usize = 20;
+
+/// Builds the resource URI advertised for a catalog on disk.
+pub fn catalog_uri(path: &Path) -> String {
+    format!(
+        "{CATALOG_URI_PREFIX}{}",
+        path.to_string_lossy().replace('\\', "/")
+    )
+}
+
+fn catalog_path_from_uri(uri: &str) -> Option<PathBuf> {
+    uri.strip_prefix(CATALOG_URI_PREFIX)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
 
 impl XcStringsMcpServer {
     pub fn new(stores: Arc<XcStringsStoreManager>) -> Self {
         Self {
             stores,
-            tool_router: Self::tool_router(),
+            tool_router: Self::all_tools(),
+            subscriptions: Arc::new(RwLock::new(HashSet::new())),
+            log_level: Arc::new(RwLock::new(DEFAULT_LOG_LEVEL)),
+            log_layer: None,
+            max_response_bytes: Some(DEFAULT_MAX_RESPONSE_BYTES),
+            confirm_destructive: true,
+            read_only: false,
+            started: Instant::now(),
+            qa_options: Arc::new(QaOptions::default()),
+            #[cfg(feature = "sync")]
+            sync_config: Arc::new(SyncConfig::default()),
+        }
+    }
+
+    /// The core tools plus those of the git and sync features built in.
+    fn all_tools() -> ToolRouter<Self> {
+        let router = Self::tool_router();
+        #[cfg(feature = "git")]
+        let router = router + Self::git_tool_router();
+        #[cfg(feature = "sync")]
+        let router = router + Self::sync_tool_router();
+        router
+    }
+
+    /// Supplies project settings, such as the glossary, used by `validate_catalog`.
+    pub fn with_qa_options(mut self, qa_options: QaOptions) -> Self {
+        self.qa_options = Arc::new(qa_options);
+        self
+    }
+
+    /// Supplies the translation management services `sync_catalog` can reach.
+    #[cfg(feature = "sync")]
+    pub fn with_sync_config(mut self, sync_config: SyncConfig) -> Self {
+        self.sync_config = Arc::new(sync_config);
+        self
+    }
+
+    /// Controls whether destructive tools ask the user to confirm via elicitation first.
+    /// Disable it for unattended runs such as CI.
+    pub fn with_destructive_confirmation(mut self, enabled: bool) -> Self {
+        self.confirm_destructive = enabled;
+        self
+    }
+
+    /// Caps the size of list responses; `None` removes the limit.
+    pub fn with_max_response_bytes(mut self, max_response_bytes: Option<usize>) -> Self {
+        self.max_response_bytes = max_response_bytes;
+        self
+    }
+
+    /// Forwards this crate's tracing events to each connected client.
+    pub fn with_log_layer(mut self, log_layer: McpLogLayer) -> Self {
+        self.log_layer = Some(log_layer);
+        self
+    }
+
+    /// Removes tools from the router so they are neither advertised nor callable.
+    pub fn with_disabled_tools<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for name in names {
+            let name = name.as_ref();
+            if self.tool_router.has_route(name) {
+                self.tool_router.remove_route(name);
+            } else {
+                warn!(tool = name, "Ignoring unknown tool in disabled tools list");
+            }
+        }
+        self
+    }
+
+    /// Removes every tool not annotated as read-only, for servers that must not edit catalogs.
+    pub fn with_read_only(mut self) -> Self {
+        let writers: Vec<_> = self
+            .tool_router
+            .list_all()
+            .into_iter()
+            .map(|tool| tool.name.to_string())
+            .filter(|name| !self.is_read_only_tool(name))
+            .collect();
+        for name in writers {
+            self.tool_router.remove_route(&name);
+        }
+        self.read_only = true;
+        self
+    }
+
+    /// Creates the handler for a new client session. Stores, tools, and logging are shared;
+    /// resource subscriptions and the requested log level belong to the session.
+    pub fn new_session(&self) -> Self {
+        Self {
+            stores: self.stores.clone(),
+            tool_router: self.tool_router.clone(),
+            subscriptions: Arc::new(RwLock::new(HashSet::new())),
+            log_level: Arc::new(RwLock::new(DEFAULT_LOG_LEVEL)),
+            log_layer: self.log_layer.clone(),
+            max_response_bytes: self.max_response_bytes,
+            confirm_destructive: self.confirm_destructive,
+            read_only: self.read_only,
+            started: self.started,
+            qa_options: self.qa_options.clone(),
+            #[cfg(feature = "sync")]
+            sync_config: self.sync_config.clone(),
+        }
+    }
+
+    /// Forwards server log records to the connected client as `notifications/message`,
+    /// dropping anything below the level requested through `logging/setLevel`.
+    pub fn spawn_log_forwarder(
+        &self,
+        peer: Peer<RoleServer>,
+        mut records: broadcast::Receiver<LogRecord>,
+    ) -> JoinHandle<()> {
+        let log_level = self.log_level.clone();
+        tokio::spawn(async move {
+            loop {
+                let record = match records.recv().await {
+                    Ok(record) => record,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                if !Self::should_log(&log_level, record.level).await {
+                    continue;
+                }
+                let message = LoggingMessageNotificationParam {
+                    level: record.level,
+                    logger: Some(record.logger),
+                    data: record.data,
+                };
+                if peer.notify_logging_message(message).await.is_err() {
+                    break;
+                }
+            }
+        })
+    }
+
+    async fn should_log(log_level: &RwLock<LoggingLevel>, level: LoggingLevel) -> bool {
+        logging::severity(level) >= logging::severity(*log_level.read().await)
+    }
+
+    /// Forwards store change events to the connected client: `notifications/resources/updated`
+    /// for subscribed catalogs, a `catalog_changed` log message whenever another process
+    /// modified a catalog on disk, and a `qa_findings` warning when a QA sweep finds new issues.
+    pub fn spawn_change_notifier(&self, peer: Peer<RoleServer>) -> JoinHandle<()> {
+        let mut events = self.stores.subscribe();
+        let subscriptions = self.subscriptions.clone();
+        let log_level = self.log_level.clone();
+        tokio::spawn(async move {
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                if Self::forward_event(&peer, &subscriptions, &log_level, &event)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        })
+    }
+
+    async fn forward_event(
+        peer: &Peer<RoleServer>,
+        subscriptions: &RwLock<HashSet<String>>,
+        log_level: &RwLock<LoggingLevel>,
+        event: &StoreEvent,
+    ) -> Result<(), ServiceError> {
+        let uri = catalog_uri(event.path());
+        let changed = !matches!(event, StoreEvent::QaFindings { .. });
+        if changed && subscriptions.read().await.contains(&uri) {
+            peer.notify_resource_updated(ResourceUpdatedNotificationParam { uri: uri.clone() })
+                .await?;
+        }
+
+        let (level, logger, data) = match event {
+            StoreEvent::Saved { .. } => return Ok(()),
+            StoreEvent::ExternalChange { path } => (
+                LoggingLevel::Info,
+                CATALOG_CHANGED_LOGGER,
+                serde_json::json!({
+                    "event": CATALOG_CHANGED_LOGGER,
+                    "path": path,
+                    "uri": uri,
+                }),
+            ),
+            StoreEvent::QaFindings { path, findings } => (
+                LoggingLevel::Warning,
+                QA_FINDINGS_LOGGER,
+                serde_json::json!({
+                    "event": QA_FINDINGS_LOGGER,
+                    "path": path,
+                    "uri": uri,
+                    "findings": findings,
+                }),
+            ),
+        };
+        if !Self::should_log(log_level, level).await {
+            return Ok(());
+        }
+        peer.notify_logging_message(LoggingMessageNotificationParam {
+            level,
+            logger: Some(logger.to_string()),
+            data,
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Resolves the catalog used for argument completion: an explicit `path` argument wins,
+    /// then the default catalog, then the only discovered catalog.
+    async fn completion_store(&self, path: Option<&str>) -> Option<Arc<XcStringsStore>> {
+        if let Some(path) = path.filter(|path| !path.trim().is_empty()) {
+            return self.stores.store_for(Some(path)).await.ok();
+        }
+        if self.stores.default_path().is_some() {
+            return self.stores.store_for(None).await.ok();
+        }
+        match self.stores.available_paths().await.as_slice() {
+            [only] => self
+                .stores
+                .store_for(Some(&only.to_string_lossy()))
+                .await
+                .ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns completion candidates for a tool argument, matched case-insensitively.
+    async fn completion_candidates(
+        &self,
+        argument: &str,
+        value: &str,
+        path: Option<&str>,
+    ) -> Vec<String> {
+        let needle = value.to_lowercase();
+        match argument {
+            "path" => self
+                .stores
+                .available_paths()
+                .await
+                .into_iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .filter(|path| path.to_lowercase().contains(&needle))
+                .collect(),
+            "language" | "oldLanguage" | "newLanguage" => {
+                let Some(store) = self.completion_store(path).await else {
+                    return Vec::new();
+                };
+                store
+                    .list_languages()
+                    .await
+                    .into_iter()
+                    .filter(|language| language.to_lowercase().starts_with(&needle))
+                    .collect()
+            }
+            "key" => {
+                let Some(store) = self.completion_store(path).await else {
+                    return Vec::new();
+                };
+                // Prefix matches first, then keys containing the typed text anywhere
+                let (mut prefixed, contained): (Vec<String>, Vec<String>) = store
+                    .keys()
+                    .await
+                    .into_iter()
+                    .filter(|key| key.to_lowercase().contains(&needle))
+                    .partition(|key| key.to_lowercase().starts_with(&needle));
+                prefixed.extend(contained);
+                prefixed
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn catalog_resource(path: &Path) -> Resource {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        let mut resource = RawResource::new(catalog_uri(path), name);
+        resource.description = Some(format!("String catalog at {}", path.display()));
+        resource.mime_type = Some("application/json".to_string());
+        resource.no_annotation()
+    }
+
+    fn is_read_only_tool(&self, name: &str) -> bool {
+        self.tool_router
+            .map
+            .get(name)
+            .and_then(|route| route.attr.annotations.as_ref())
+            .and_then(|annotations| annotations.read_only_hint)
+            .unwrap_or(false)
+    }
+
+    /// Describes what a tool call is about to destroy, for the tools that must be confirmed.
+    fn confirmation_message(&self, tool: &str, arguments: Option<&JsonObject>) -> Option<String> {
+        if !self.confirm_destructive {
+            return None;
+        }
+        let argument = |name: &str| {
+            arguments
+                .and_then(|arguments| arguments.get(name))
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let dry_run = arguments
+            .and_then(|arguments| arguments.get("dryRun"))
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+        let path = argument("path");
+        let target = if path.is_empty() {
+            "the default catalog".to_string()
+        } else {
+            path
+        };
+        match tool {
+            "delete_key" => Some(format!(
+                "Delete key '{}' and all of its translations from {target}?",
+                argument("key")
+            )),
+            "delete_translation" => Some(format!(
+                "Delete the '{}' translation of key '{}' from {target}?",
+                argument("language"),
+                argument("key")
+            )),
+            "remove_language" => Some(format!(
+                "Remove language '{}' and every translation in it from {target}?",
+                argument("language")
+            )),
+            "update_language" => Some(format!(
+                "Rename language '{}' to '{}' in {target}? Every translation in it moves to the \
+                 new code, and tools still using the old one will stop finding them.",
+                argument("oldLanguage"),
+                argument("newLanguage")
+            )),
+            "restore_snapshot" => Some(format!(
+                "Replace {target} with snapshot '{}'? Changes made since the snapshot will be lost.",
+                argument("label")
+            )),
+            "apply_patch" => {
+                let ops = arguments
+                    .and_then(|arguments| arguments.get("ops"))
+                    .and_then(|ops| ops.as_array())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let removed: Vec<&str> = ops
+                    .iter()
+                    .filter(|op| op.get("op").and_then(|op| op.as_str()) == Some("remove"))
+                    .filter_map(|op| op.get("path").and_then(|path| path.as_str()))
+                    .collect();
+                let removing = if removed.is_empty() {
+                    String::new()
+                } else {
+                    format!(", removing {}", removed.join(", "))
+                };
+                Some(format!(
+                    "Apply {} patch operations to {target}{removing}? Values they replace or \
+                     remove will be lost.",
+                    ops.len()
+                ))
+            }
+            "set_raw_entry" => Some(format!(
+                "Replace key '{}' in {target} with the given entry? Localizations and fields it \
+                 leaves out will be removed.",
+                argument("key")
+            )),
+            "apply_transaction" => {
+                let operations = arguments
+                    .and_then(|arguments| arguments.get("operations"))
+                    .and_then(|operations| operations.as_array())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let mut catalogs = BTreeSet::new();
+                for operation in operations {
+                    for field in ["path", "from", "to"] {
+                        if let Some(path) = operation.get(field).and_then(|path| path.as_str()) {
+                            catalogs.insert(path);
+                        }
+                    }
+                }
+                let deletes = operations
+                    .iter()
+                    .filter(|operation| {
+                        matches!(
+                            operation.get("op").and_then(|op| op.as_str()),
+                            Some("delete_key" | "delete_translation")
+                        )
+                    })
+                    .count();
+                Some(format!(
+                    "Apply {} operations ({deletes} deletions) to {} in one transaction? \
+                     Deleted keys and translations will be lost.",
+                    operations.len(),
+                    catalogs.into_iter().collect::<Vec<_>>().join(", ")
+                ))
+            }
+            "repair_catalog" if !dry_run => Some(format!(
+                "Repair and rewrite {target}? Duplicate members, variations the schema \
+                 doesn't allow, unused substitutions, and empty entries are removed; use dryRun \
+                 to list them first."
+            )),
+            "clean_catalog" if !dry_run => Some(format!(
+                "Remove empty localizations, and keys with nothing left in them, from {target}? \
+                 Placeholders other tools created will be lost; use dryRun to list them first."
+            )),
+            "rename_namespace" if !dry_run => {
+                let scope = if arguments
+                    .and_then(|arguments| arguments.get("allCatalogs"))
+                    .and_then(|value| value.as_bool())
+                    .unwrap_or(false)
+                {
+                    "every catalog".to_string()
+                } else {
+                    target
+                };
+                Some(format!(
+                    "Rename every key under '{}' to '{}' in {scope}? References to the old keys \
+                     in code will stop resolving.",
+                    argument("oldPrefix"),
+                    argument("newPrefix")
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Asks before a call that would replace existing translations, which takes a look at
+    /// the catalog first. Calls that fail here are left for the tool to report.
+    async fn overwrite_confirmation(
+        &self,
+        tool: &str,
+        arguments: Option<&JsonObject>,
+    ) -> Option<String> {
+        if !self.confirm_destructive {
+            return None;
+        }
+        let arguments = serde_json::Value::Object(arguments?.clone());
+        match tool {
+            "upsert_translation" => {
+                self.upsert_confirmation(serde_json::from_value(arguments).ok()?)
+                    .await
+            }
+            "sync_catalog" => self.pull_confirmation(arguments).await,
+            _ => None,
+        }
+    }
+
+    /// Asks before an upsert that changes or clears a value the translation already has;
+    /// creating a translation or setting the same value again goes ahead.
+    async fn upsert_confirmation(&self, params: UpsertTranslationParams) -> Option<String> {
+        let value = params.value?;
+        let store = self.store_for(Some(params.path.as_str())).await.ok()?;
+        let previous = store
+            .get_translation(&params.key, &params.language)
+            .await
+            .ok()?
+            .value?;
+        if value.as_ref() == Some(&previous) {
+            return None;
         }
+        Some(format!(
+            "Replace the '{}' translation of key '{}' in {}? Its current value '{previous}' \
+             will be lost.",
+            params.language, params.key, params.path
+        ))
     }
 
-    pub fn router(&self) -> Router<Self> {
-        Router::new(self.clone()).with_tools(self.tool_router.clone())
+    /// Asks before a `sync_catalog` pull that would replace more than
+    /// [`CONFIRM_OVERWRITES_ABOVE`] existing translations, found with a dry run.
+    #[cfg(feature = "sync")]
+    async fn pull_confirmation(&self, arguments: serde_json::Value) -> Option<String> {
+        let params: SyncCatalogParams = serde_json::from_value(arguments).ok()?;
+        if params.direction != SyncDirection::Pull || params.dry_run {
+            return None;
+        }
+        let store = self.store_for(Some(params.path.as_str())).await.ok()?;
+        let provider = self.sync_config.provider(params.provider.as_deref()).ok()?;
+        let report = sync::pull(&store, provider.as_ref(), &params.languages, true)
+            .await
+            .ok()?;
+        let overwritten = report.overwritten();
+        (overwritten > CONFIRM_OVERWRITES_ABOVE).then(|| {
+            format!(
+                "Pull translations from {} into {}? {overwritten} existing translations will \
+                 be replaced; use dryRun to review them first.",
+                report.provider, params.path
+            )
+        })
+    }
+
+    #[cfg(not(feature = "sync"))]
+    async fn pull_confirmation(&self, _arguments: serde_json::Value) -> Option<String> {
+        None
+    }
+
+    /// The question to ask before running `tool`, if it must be confirmed.
+    async fn confirmation(&self, tool: &str, arguments: Option<&JsonObject>) -> Option<String> {
+        match self.confirmation_message(tool, arguments) {
+            Some(message) => Some(message),
+            None => self.overwrite_confirmation(tool, arguments).await,
+        }
+    }
+
+    /// Asks the user to confirm a destructive call. Returns the refusal to send back instead of
+    /// running the tool, or `None` to proceed. Clients without elicitation support proceed and
+    /// rely on the tool's destructive annotation for their own confirmation UI.
+    async fn confirm_with_user(peer: &Peer<RoleServer>, message: String) -> Option<CallToolResult> {
+        let refusal = |reason: String| {
+            Some(CallToolResult::error(vec![Content::text(format!(
+                "Operation not performed: {reason}"
+            ))]))
+        };
+        match peer.elicit::<DestructiveConfirmation>(message).await {
+            Ok(Some(DestructiveConfirmation { confirm: true })) => None,
+            Err(ElicitationError::CapabilityNotSupported) => None,
+            Ok(_) | Err(ElicitationError::UserDeclined) | Err(ElicitationError::UserCancelled) => {
+                refusal("the user did not confirm it".to_string())
+            }
+            Err(err) => refusal(format!("confirmation failed ({err})")),
+        }
+    }
+
+    fn session_id(context: &RequestContext<RoleServer>) -> String {
+        context
+            .extensions
+            .get::<axum::http::request::Parts>()
+            .and_then(|parts| parts.headers.get(SESSION_ID_HEADER))
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+            .unwrap_or_else(|| STDIO_SESSION.to_string())
+    }
+
+    /// Records a successful mutating tool call in the audit log and git auto-commits, tagged
+    /// with the session that made it so changes from concurrent agents can be told apart.
+    async fn record_audit(
+        &self,
+        tool: &str,
+        arguments: Option<JsonObject>,
+        context: &RequestContext<RoleServer>,
+    ) {
+        if !self.stores.records_changes() {
+            return;
+        }
+
+        let mut details = arguments.unwrap_or_default();
+        let path = details
+            .remove("path")
+            .and_then(|value| value.as_str().map(str::to_string))
+            .filter(|path| !path.trim().is_empty())
+            .or_else(|| {
+                self.stores
+                    .default_path()
+                    .map(|path| path.to_string_lossy().to_string())
+            });
+
+        let mut entry = AuditEntry::new(Self::session_id(context), tool);
+        entry.client = context
+            .peer
+            .peer_info()
+            .map(|info| info.client_info.name.clone());
+        entry.request_id = context
+            .extensions
+            .get::<axum::http::request::Parts>()
+            .and_then(|parts| request_trace::request_id(&parts.headers));
+        entry.path = path;
+        if !details.is_empty() {
+            entry.details = serde_json::Value::Object(details);
+        }
+
+        self.stores.record_change(&entry).await;
+    }
+
+    #[cfg(feature = "sync")]
+    fn sync_error_to_mcp(err: SyncError) -> McpError {
+        match err {
+            SyncError::Store(err) => Self::error_to_mcp(err),
+            err @ SyncError::Config(_) => McpError::invalid_request(err.to_string(), None),
+            err => McpError::internal_error(err.to_string(), None),
+        }
     }
 
     fn error_to_mcp(err: StoreError) -> McpError {
         match err {
+            // `data.missing` tells agents whether to create the key, add the language, or
+            // only translate the key.
             StoreError::TranslationMissing { key, language } => McpError::resource_not_found(
                 format!("Translation '{key}' ({language}) not found"),
-                None,
+                Some(
+                    serde_json::json!({ "missing": "translation", "key": key, "language": language }),
+                ),
+            ),
+            StoreError::KeyMissing(key) => McpError::resource_not_found(
+                format!("Key '{key}' not found"),
+                Some(serde_json::json!({ "missing": "key", "key": key })),
             ),
-            StoreError::KeyMissing(key) => {
-                McpError::resource_not_found(format!("Key '{key}' not found"), None)
-            }
             StoreError::KeyExists(key) => {
                 McpError::invalid_params(format!("Key '{key}' already exists"), None)
             }
-            StoreError::LanguageMissing(language) => {
-                McpError::resource_not_found(format!("Language '{language}' not found"), None)
-            }
+            StoreError::LanguageMissing(language) => McpError::resource_not_found(
+                format!("Language '{language}' not found"),
+                Some(serde_json::json!({ "missing": "language", "language": language })),
+            ),
             StoreError::LanguageExists(language) => {
                 McpError::invalid_params(format!("Language '{language}' already exists"), None)
             }
             StoreError::InvalidLanguage(msg) => {
                 McpError::invalid_params(format!("Invalid language: {msg}"), None)
             }
+            StoreError::InvalidDevice(device) => McpError::invalid_params(
+                format!(
+                    "Unknown device '{device}'; expected one of {}",
+                    DEVICES.join(", ")
+                ),
+                None,
+            ),
+            StoreError::InvalidVariation(msg) => {
+                McpError::invalid_params(format!("Invalid variation: {msg}"), None)
+            }
+            StoreError::WorkflowViolation {
+                key,
+                language,
+                reason,
+            } => McpError::invalid_request(
+                format!("Review workflow: '{key}' ({language}) {reason}"),
+                None,
+            ),
+            err @ (StoreError::PlaceholderMismatch { .. } | StoreError::CommentRequired(_)) => {
+                McpError::invalid_params(err.to_string(), None)
+            }
+            err @ (StoreError::LanguageFrozen(_) | StoreError::LanguageIncomplete { .. }) => {
+                McpError::invalid_request(err.to_string(), None)
+            }
+            err @ StoreError::NotInGitRepository(_) => {
+                McpError::invalid_request(err.to_string(), None)
+            }
+            err @ StoreError::GitRevisionMissing(_) => {
+                McpError::resource_not_found(err.to_string(), None)
+            }
+            StoreError::InvalidMetadata(msg) => {
+                McpError::invalid_params(format!("Invalid metadata: {msg}"), None)
+            }
+            StoreError::InvalidState(msg) => {
+                McpError::invalid_params(format!("Invalid state: {msg}"), None)
+            }
+            StoreError::InvalidNamespace(msg) => {
+                McpError::invalid_params(format!("Invalid namespace: {msg}"), None)
+            }
+            StoreError::InvalidPatch(msg) => {
+                McpError::invalid_params(format!("Invalid patch: {msg}"), None)
+            }
+            StoreError::NamespaceCollision(keys) => McpError::invalid_params(
+                format!(
+                    "Renaming would overwrite existing keys: {}",
+                    keys.join(", ")
+                ),
+                Some(serde_json::json!({ "collisions": keys })),
+            ),
+            StoreError::InvalidLanguageCode {
+                code,
+                reason,
+                suggestions,
+            } => McpError::invalid_params(
+                format!("Invalid language code '{code}': {reason}"),
+                Some(serde_json::json!({ "suggestions": suggestions })),
+            ),
             StoreError::CannotRemoveSourceLanguage(language) => McpError::invalid_params(
                 format!("Cannot remove source language '{language}'"),
                 None,
@@ -66,6 +801,25 @@ impl XcStringsMcpServer {
                 format!("Cannot rename source language '{language}'"),
                 None,
             ),
+            StoreError::InvalidSnapshotLabel(msg) => {
+                McpError::invalid_params(format!("Invalid snapshot label: {msg}"), None)
+            }
+            StoreError::SnapshotMissing(label) => {
+                McpError::resource_not_found(format!("Snapshot '{label}' not found"), None)
+            }
+            StoreError::InvalidCatalogPath(msg) => {
+                McpError::invalid_params(format!("Invalid catalog path: {msg}"), None)
+            }
+            StoreError::CatalogNotRegistered(path) => {
+                McpError::resource_not_found(format!("Catalog '{path}' was not registered"), None)
+            }
+            StoreError::ReadOnly(path) => {
+                McpError::invalid_request(format!("Catalog '{path}' is read-only"), None)
+            }
+            StoreError::Protected { path, rule } => McpError::invalid_request(
+                format!("Catalog '{path}' is protected by the read-only rule '{rule}'"),
+                None,
+            ),
             StoreError::PathRequired => McpError::invalid_params(
                 "xcstrings path must be provided via tool arguments".to_string(),
                 None,
@@ -74,12 +828,101 @@ impl XcStringsMcpServer {
         }
     }
 
+    /// Selects the `offset`/`limit` window of `items`, then trims it further when the rendered
+    /// response would exceed the byte budget, leaving a notice and the offset to resume from.
+    fn paginate<T: Serialize>(
+        &self,
+        items: Vec<T>,
+        offset: usize,
+        limit: usize,
+    ) -> TranslationListResponse<T> {
+        let total = items.len();
+        let mut page: Vec<T> = items.into_iter().skip(offset).take(limit).collect();
+        let requested = page.len();
+
+        if let Some(budget) = self.max_response_bytes {
+            let mut used = RESPONSE_ENVELOPE_BYTES;
+            let fits = page
+                .iter()
+                .take_while(|item| {
+                    used += nested_json_len(*item);
+                    used <= budget
+                })
+                .count();
+            page.truncate(fits);
+        }
+
+        let returned = page.len();
+        let next_offset = offset.saturating_add(returned);
+        let notice = match self.max_response_bytes {
+            Some(budget) if returned < requested && returned == 0 => Some(format!(
+                "A single item exceeds the {budget}-byte response limit. Narrow the query or \
+                 fetch individual entries with get_translation."
+            )),
+            Some(budget) if returned < requested => Some(format!(
+                "Response trimmed to {returned} of {requested} requested items to stay under \
+                 the {budget}-byte limit. Call again with offset={next_offset} to continue, or \
+                 narrow the query."
+            )),
+            _ => None,
+        };
+
+        TranslationListResponse {
+            items: page,
+            total,
+            returned,
+            offset,
+            truncated: next_offset < total,
+            next_offset: (next_offset < total && returned > 0).then_some(next_offset),
+            notice,
+        }
+    }
+
     async fn store_for(&self, path: Option<&str>) -> Result<Arc<XcStringsStore>, McpError> {
         self.stores
             .store_for(path)
             .await
             .map_err(Self::error_to_mcp)
     }
+
+    /// Drops the summaries of keys not assigned to `assignee`, when one is given.
+    async fn retain_assigned(
+        store: &XcStringsStore,
+        summaries: &mut Vec<TranslationSummary>,
+        assignee: Option<&str>,
+    ) -> Result<(), McpError> {
+        let Some(assignee) = assignee.map(str::trim).filter(|name| !name.is_empty()) else {
+            return Ok(());
+        };
+        let assignments = store.assignments().await.map_err(Self::error_to_mcp)?;
+        summaries.retain(|summary| assignments.is_assigned(&summary.key, assignee));
+        Ok(())
+    }
+
+    /// Drops the summaries of keys not changed since `since`, when it is given.
+    fn retain_modified_since(
+        summaries: &mut Vec<TranslationSummary>,
+        since: Option<&str>,
+    ) -> Result<(), McpError> {
+        let Some(raw) = since.map(str::trim).filter(|since| !since.is_empty()) else {
+            return Ok(());
+        };
+        let since = modified::parse_since(raw).ok_or_else(|| {
+            McpError::invalid_params(
+                format!(
+                    "Invalid modified_since '{raw}'; expected an RFC 3339 timestamp or YYYY-MM-DD"
+                ),
+                None,
+            )
+        })?;
+        summaries.retain(|summary| {
+            summary
+                .modified
+                .as_deref()
+                .is_some_and(|modified| modified::is_since(modified, since))
+        });
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -90,6 +933,61 @@ struct ListTranslationsParams {
     /// Optional maximum number of items to return (defaults to 100)
     #[serde(default)]
     pub limit: Option<u32>,
+    /// Optional number of items to skip, for paging through large results
+    #[serde(default)]
+    pub offset: Option<u32>,
+    /// Optional translator; keeps only the keys assigned to them
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Optional RFC 3339 timestamp or YYYY-MM-DD date; keeps only the keys changed through
+    /// the server since then
+    #[serde(default)]
+    pub modified_since: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ListKeyNamespacesParams {
+    pub path: String,
+    /// Optional namespace to list the sub-namespaces of, e.g. `checkout` (defaults to the root)
+    pub prefix: Option<String>,
+    /// Optional number of levels to include below the prefix (defaults to 1)
+    #[serde(default)]
+    pub depth: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct SetDeviceVariantParams {
+    pub path: String,
+    pub key: String,
+    pub language: String,
+    /// One of iphone, ipod, ipad, mac, applewatch, appletv, applevision, or other
+    pub device: String,
+    /// Text shown on this device; omit or pass null to remove the device case
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ListDeviceVariantsParams {
+    pub path: String,
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct RenameNamespaceParams {
+    pub path: String,
+    /// Prefix to move, e.g. `checkout` for `checkout.title` and `checkout.payment.card`
+    #[serde(rename = "oldPrefix")]
+    pub old_prefix: String,
+    /// Prefix the keys move to, e.g. `payment`
+    #[serde(rename = "newPrefix")]
+    pub new_prefix: String,
+    /// Rename in every catalog the server knows about instead of only `path`
+    #[serde(default, rename = "allCatalogs")]
+    pub all_catalogs: bool,
+    /// Only report the renames and collisions without changing any file
+    #[serde(default, rename = "dryRun")]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -112,6 +1010,21 @@ struct UpsertTranslationParams {
     pub variations: Option<BTreeMap<String, BTreeMap<String, VariationUpdateParam>>>,
     #[serde(default)]
     pub substitutions: Option<BTreeMap<String, Option<SubstitutionUpdateParam>>>,
+    /// Writes even when the language is frozen; use only for sign-off fixes.
+    #[serde(default)]
+    pub force: bool,
+    /// Set to false to only update existing keys, failing on unknown ones instead of
+    /// creating them. Defaults to true.
+    #[serde(default)]
+    pub allow_create: Option<bool>,
+    /// Developer comment for a key this upsert creates, telling translators where the
+    /// string appears; existing keys keep theirs. The server may require it for new keys.
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// Where a key this upsert creates goes: "end" (the default), "sorted", or
+    /// {"after": "other.key"} to group it with a related key
+    #[serde(default)]
+    pub insert_position: InsertPosition,
 }
 
 #[derive(Debug, Deserialize, JsonSchema, Clone)]
@@ -128,9 +1041,11 @@ struct VariationUpdateParam {
 
 impl VariationUpdateParam {
     fn into_update(self) -> TranslationUpdate {
-        let mut update = TranslationUpdate::default();
-        update.state = self.state;
-        update.value = self.value;
+        let mut update = TranslationUpdate {
+            state: self.state,
+            value: self.value,
+            ..Default::default()
+        };
         if let Some(variations) = self.variations {
             update.variations = Some(
                 variations
@@ -173,11 +1088,13 @@ struct SubstitutionUpdateParam {
 
 impl SubstitutionUpdateParam {
     fn into_update(self) -> SubstitutionUpdate {
-        let mut update = SubstitutionUpdate::default();
-        update.value = self.value;
-        update.state = self.state;
-        update.arg_num = self.arg_num;
-        update.format_specifier = self.format_specifier;
+        let mut update = SubstitutionUpdate {
+            value: self.value,
+            state: self.state,
+            arg_num: self.arg_num,
+            format_specifier: self.format_specifier,
+            ..Default::default()
+        };
         if let Some(variations) = self.variations {
             update.variations = Some(
                 variations
@@ -198,9 +1115,11 @@ impl SubstitutionUpdateParam {
 
 impl UpsertTranslationParams {
     fn into_update(self) -> TranslationUpdate {
-        let mut update = TranslationUpdate::default();
-        update.state = self.state;
-        update.value = self.value;
+        let mut update = TranslationUpdate {
+            state: self.state,
+            value: self.value,
+            ..Default::default()
+        };
         if let Some(variations) = self.variations {
             update.variations = Some(
                 variations
@@ -232,78 +1151,465 @@ struct DeleteTranslationParams {
     pub path: String,
     pub key: String,
     pub language: String,
+    /// Deletes even when the language is frozen.
+    #[serde(default)]
+    pub force: bool,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-struct DeleteKeyParams {
+struct CreateKeyParams {
     pub path: String,
     pub key: String,
+    /// Developer comment telling translators where the string appears and what it means
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// Optional initial value in the catalog's source language
+    #[serde(default)]
+    pub value: Option<String>,
+    /// Where the key goes: "end" (the default), "sorted", or {"after": "other.key"} to group
+    /// it with a related key
+    #[serde(default)]
+    pub insert_position: InsertPosition,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-struct SetCommentParams {
+struct DeleteKeyParams {
     pub path: String,
     pub key: String,
-    pub comment: Option<String>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-struct SetTranslationStateParams {
+struct ApplyPatchParams {
     pub path: String,
-    pub key: String,
-    pub language: String,
-    pub state: Option<String>,
+    /// RFC 6902 operations applied in order; paths start with a key, e.g.
+    /// `/greeting/localizations/de/stringUnit/value`
+    pub ops: Vec<PatchOperation>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-struct SetExtractionStateParams {
-    pub path: String,
-    pub key: String,
-    #[serde(rename = "extractionState")]
-    pub extraction_state: Option<String>,
+struct ApplyTransactionParams {
+    /// Edits applied in order, each naming its catalog
+    pub operations: Vec<TransactionOperationParam>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-struct ListKeysParams {
+#[serde(tag = "op", rename_all = "snake_case")]
+enum TransactionOperationParam {
+    /// Same fields as upsert_translation; force and allow_create are ignored
+    Upsert(Box<UpsertTranslationParams>),
+    DeleteTranslation {
+        path: String,
+        key: String,
+        language: String,
+    },
+    DeleteKey {
+        path: String,
+        key: String,
+    },
+    /// Moves a key with its comment and translations from the catalog at `from` to the one
+    /// at `to`
+    MoveKey {
+        key: String,
+        from: String,
+        to: String,
+        #[serde(default)]
+        insert_position: InsertPosition,
+    },
+}
+
+impl TransactionOperationParam {
+    fn into_operation(self) -> TransactionOperation {
+        let edit = |path: String, operation| TransactionOperation::Edit {
+            path: Some(path),
+            operation,
+        };
+        match self {
+            Self::Upsert(params) => edit(
+                params.path.clone(),
+                BatchOperation::Upsert {
+                    key: params.key.clone(),
+                    language: params.language.clone(),
+                    comment: params.comment.clone(),
+                    position: params.insert_position.clone(),
+                    update: Box::new(params.into_update()),
+                },
+            ),
+            Self::DeleteTranslation {
+                path,
+                key,
+                language,
+            } => edit(path, BatchOperation::DeleteTranslation { key, language }),
+            Self::DeleteKey { path, key } => edit(path, BatchOperation::DeleteKey { key }),
+            Self::MoveKey {
+                key,
+                from,
+                to,
+                insert_position,
+            } => TransactionOperation::MoveKey {
+                key,
+                from: Some(from),
+                to: Some(to),
+                position: insert_position,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct GetRawEntryParams {
     pub path: String,
-    /// Optional case-insensitive search query
-    pub query: Option<String>,
-    /// Optional maximum number of items to return (defaults to 100)
-    #[serde(default)]
-    pub limit: Option<u32>,
+    pub key: String,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-struct ListLanguagesParams {
+struct SetRawEntryParams {
     pub path: String,
+    pub key: String,
+    /// The whole entry as the .xcstrings file stores it, e.g.
+    /// {"comment": "...", "localizations": {"de": {"stringUnit": {"state": "translated", "value": "..."}}}}
+    pub json: serde_json::Value,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-struct AddLanguageParams {
+struct SetCommentParams {
     pub path: String,
-    pub language: String,
+    pub key: String,
+    pub comment: Option<String>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-struct RemoveLanguageParams {
+struct RejectTranslationParams {
     pub path: String,
+    pub key: String,
     pub language: String,
+    /// Why the translation needs another pass; recorded in the audit log.
+    pub reason: String,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-struct UpdateLanguageParams {
+struct AssignKeysParams {
     pub path: String,
-    #[serde(rename = "oldLanguage")]
-    pub old_language: String,
-    #[serde(rename = "newLanguage")]
-    pub new_language: String,
+    /// Translator to assign; omit or leave blank to remove the assignments instead.
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Keys to assign to the translator.
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// Languages the translator owns.
+    #[serde(default)]
+    pub languages: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, JsonSchema)]
-struct ListUntranslatedParams {
+struct ListAssignmentsParams {
     pub path: String,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+struct SetMetadataParams {
+    pub path: String,
+    pub key: String,
+    /// Replaces all of the key's metadata; pass `{}` to remove it.
+    #[serde(default)]
+    pub metadata: KeyMetadata,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct SetTranslationStateParams {
+    pub path: String,
+    pub key: String,
+    pub language: String,
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct SetExtractionStateParams {
+    pub path: String,
+    pub key: String,
+    #[serde(rename = "extractionState")]
+    pub extraction_state: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ListKeysParams {
+    pub path: String,
+    /// Optional case-insensitive search query
+    pub query: Option<String>,
+    /// Optional maximum number of items to return (defaults to 100)
+    #[serde(default)]
+    pub limit: Option<u32>,
+    /// Optional number of items to skip, for paging through large results
+    #[serde(default)]
+    pub offset: Option<u32>,
+    /// Optional translator; keeps only the keys assigned to them
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Optional RFC 3339 timestamp or YYYY-MM-DD date; keeps only the keys changed through
+    /// the server since then
+    #[serde(default)]
+    pub modified_since: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ListLanguageValuesParams {
+    pub path: String,
+    pub language: String,
+    /// Optional case-insensitive search query, matched like `list_translations`
+    pub query: Option<String>,
+    /// Optional state; keeps only the translations in it, e.g. "needs_review"
+    #[serde(default)]
+    pub state: Option<String>,
+    /// Keep only the keys without a translation into the language (defaults to false)
+    #[serde(default, rename = "missingOnly")]
+    pub missing_only: bool,
+    /// Optional maximum number of items to return (defaults to 100)
+    #[serde(default)]
+    pub limit: Option<u32>,
+    /// Optional number of items to skip, for paging through large results
+    #[serde(default)]
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct SuggestTranslationsParams {
+    pub path: String,
+    pub key: String,
+    pub language: String,
+    /// Optional maximum number of suggestions to return (defaults to 10)
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct CountKeysParams {
+    pub path: String,
+    /// Optional case-insensitive search query, matched like `list_keys`
+    pub query: Option<String>,
+    /// Optional translator; counts only the keys assigned to them
+    #[serde(default)]
+    pub assignee: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct KeyExistsParams {
+    pub path: String,
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct FindSimilarKeysParams {
+    pub path: String,
+    /// The key name as guessed
+    pub key: String,
+    /// Optional most edits a match may be from `key` (defaults to 3); keys sharing most of
+    /// its words match regardless
+    #[serde(default)]
+    pub max_distance: Option<u32>,
+    /// Optional maximum number of keys to return (defaults to 10)
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct FindKeyByValueParams {
+    pub path: String,
+    /// The text as it appears in the app, such as a string copied from a screenshot
+    pub text: String,
+    /// Optional language of the text (defaults to the source language)
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ListLanguagesParams {
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct AddLanguageParams {
+    pub path: String,
+    pub language: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct RemoveLanguageParams {
+    pub path: String,
+    pub language: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct UpdateLanguageParams {
+    pub path: String,
+    #[serde(rename = "oldLanguage")]
+    pub old_language: String,
+    #[serde(rename = "newLanguage")]
+    pub new_language: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct FreezeLanguageParams {
+    pub path: String,
+    pub language: String,
+    /// Freezes even when keys are still untranslated, new, in review, or stale.
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ListUntranslatedParams {
+    pub path: String,
+    /// Also list keys marked shouldTranslate=false, which are left out by default
+    #[serde(default, rename = "includeDoNotTranslate")]
+    pub include_do_not_translate: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ValidateCatalogParams {
+    pub path: String,
+    /// Optional subset of rules to run (defaults to all)
+    #[serde(default)]
+    pub rules: Option<Vec<QaRule>>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct CheckReleaseReadinessParams {
+    pub path: String,
+    /// Language → completion percentage it must reach, e.g. `{"de": 95, "fr": 100}`
+    /// (defaults to `qa.minCompletion` in the config file, or 100% for every language)
+    #[serde(default)]
+    pub thresholds: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct LengthReportParams {
+    pub path: String,
+    /// Optional language to report on (defaults to every language)
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Optional number of longest texts to list per language (defaults to 10)
+    #[serde(default)]
+    pub top: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct EstimateCostParams {
+    pub path: String,
+    /// Optional language to price (defaults to every language but the source)
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Vendor price per source word, e.g. 0.12
+    #[serde(rename = "ratePerWord")]
+    pub rate_per_word: f64,
+    /// Count only untranslated keys, leaving out those needing review (defaults to false)
+    #[serde(default, rename = "onlyUntranslated")]
+    pub only_untranslated: bool,
+    /// Also price keys marked shouldTranslate=false (defaults to false)
+    #[serde(default, rename = "includeDoNotTranslate")]
+    pub include_do_not_translate: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ExtractTerminologyParams {
+    pub path: String,
+    /// Optional number of keys a phrase must appear in to be a term (defaults to 3, at least 2)
+    #[serde(default, rename = "minOccurrences")]
+    pub min_occurrences: Option<u32>,
+    /// Optional maximum number of terms to return (defaults to 100)
+    #[serde(default)]
+    pub limit: Option<u32>,
+    /// Optional number of terms to skip, for paging through large results
+    #[serde(default)]
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct DigestParams {
+    pub path: String,
+    /// Start of the period: an RFC 3339 timestamp, or a YYYY-MM-DD date meaning its start in UTC
+    pub since: String,
+}
+
+#[cfg(feature = "git")]
+#[derive(Debug, Deserialize, JsonSchema)]
+struct KeyHistoryParams {
+    pub path: String,
+    pub key: String,
+    pub language: String,
+    /// Optional number of recent commits to search (defaults to 100)
+    #[serde(default)]
+    pub depth: Option<u32>,
+}
+
+#[cfg(feature = "git")]
+#[derive(Debug, Deserialize, JsonSchema)]
+struct ListOutdatedParams {
+    pub path: String,
+    pub language: String,
+    /// Optional number of recent commits to search (defaults to 100)
+    #[serde(default)]
+    pub depth: Option<u32>,
+}
+
+#[cfg(feature = "git")]
+#[derive(Debug, Deserialize, JsonSchema)]
+struct DiffAgainstRefParams {
+    pub path: String,
+    /// Branch, tag, or commit to compare the current catalog with, e.g. `main` or `HEAD~3`
+    #[serde(rename = "ref")]
+    pub reference: String,
+}
+
+#[cfg(feature = "sync")]
+#[derive(Debug, Deserialize, JsonSchema)]
+struct SyncCatalogParams {
+    pub path: String,
+    /// `push` uploads source strings; `pull` downloads translations into the catalog
+    pub direction: SyncDirection,
+    /// Configured provider to use (defaults to the only one, currently `crowdin`)
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Languages to pull (defaults to every language but the source)
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// Report what would change without uploading or writing anything (defaults to false)
+    #[serde(default, rename = "dryRun")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct FormatCatalogParams {
+    pub path: String,
+    /// Also order keys alphabetically (defaults to false)
+    #[serde(default, rename = "sortKeys")]
+    pub sort_keys: bool,
+    /// Only report whether the file would change (defaults to false)
+    #[serde(default, rename = "dryRun")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct CleanCatalogParams {
+    pub path: String,
+    /// Only report what would be removed (defaults to false)
+    #[serde(default, rename = "dryRun")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct RepairCatalogParams {
+    pub path: String,
+    /// Only report the problems without fixing them (defaults to false)
+    #[serde(default, rename = "dryRun")]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct SnapshotParams {
+    pub path: String,
+    /// Snapshot name made of letters, digits, '.', '_' or '-', e.g. "before-bulk-edit"
+    pub label: String,
+}
+
 fn to_json_text<T: serde::Serialize>(value: &T) -> String {
     serde_json::to_string_pretty(value).unwrap_or_else(|err| {
         serde_json::json!({
@@ -314,23 +1620,67 @@ fn to_json_text<T: serde::Serialize>(value: &T) -> String {
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 struct TranslationListResponse<T> {
     items: Vec<T>,
     total: usize,
     returned: usize,
+    offset: usize,
     truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_offset: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notice: Option<String>,
+}
+
+/// Size of `value` once pretty-printed as an element of a list response, including the
+/// indentation and separator it picks up there.
+fn nested_json_len<T: Serialize>(value: &T) -> usize {
+    let text = to_json_text(value);
+    let lines = text.lines().count();
+    text.len() + lines * 4 + 2
 }
 
 fn render_json<T: serde::Serialize>(value: &T) -> CallToolResult {
     CallToolResult::success(vec![Content::text(to_json_text(value))])
 }
 
-fn render_translation_value(value: Option<TranslationValue>) -> CallToolResult {
+fn render_translation_value(value: TranslationValue) -> CallToolResult {
     render_json(&value)
 }
 
-fn render_languages(languages: Vec<String>) -> CallToolResult {
-    render_json(&serde_json::json!({ "languages": languages }))
+/// Renders the saved translation, plus a `warnings` array when variations were dropped and
+/// a `needsReview` array when rewording the source sent translations back to review.
+fn render_upsert(outcome: UpsertOutcome) -> CallToolResult {
+    #[derive(Serialize)]
+    struct Rendered {
+        #[serde(flatten)]
+        value: TranslationValue,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<VariationWarning>,
+        #[serde(rename = "needsReview", skip_serializing_if = "Vec::is_empty")]
+        needs_review: Vec<String>,
+    }
+    render_json(&Rendered {
+        value: outcome.value,
+        warnings: outcome.warnings,
+        needs_review: outcome.needs_review,
+    })
+}
+
+/// Lists the namespaces below `namespace` down to `depth` levels, parents before children.
+fn flatten_namespaces(namespace: &KeyNamespace, depth: u32, out: &mut Vec<serde_json::Value>) {
+    for child in &namespace.children {
+        out.push(serde_json::json!({
+            "namespace": child.namespace,
+            "totalKeys": child.total_keys,
+            "directKeys": child.direct_keys,
+            "completion": child.completion,
+        }));
+        if depth > 1 {
+            flatten_namespaces(child, depth - 1, out);
+        }
+    }
 }
 
 fn render_ok_message(message: &str) -> CallToolResult {
@@ -339,7 +1689,10 @@ fn render_ok_message(message: &str) -> CallToolResult {
 
 #[tool_router]
 impl XcStringsMcpServer {
-    #[tool(description = "List translation entries, optionally filtered by a search query")]
+    #[tool(
+        description = "List translation entries, optionally filtered by a search query",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
     async fn list_translations(
         &self,
         params: Parameters<ListTranslationsParams>,
@@ -353,20 +1706,19 @@ impl XcStringsMcpServer {
             .unwrap_or(DEFAULT_LIST_LIMIT);
         let limit = if limit == 0 { usize::MAX } else { limit };
 
-        let summaries = store.list_summaries(query).await;
-        let total = summaries.len();
-        let items: Vec<TranslationSummary> = summaries.into_iter().take(limit).collect();
-        let truncated = total > items.len();
-        let response = TranslationListResponse {
-            returned: items.len(),
-            total,
-            truncated,
-            items,
-        };
+        let offset = params.offset.unwrap_or(0) as usize;
+
+        let mut summaries: Vec<TranslationSummary> = store.list_summaries(query).await;
+        Self::retain_assigned(&store, &mut summaries, params.assignee.as_deref()).await?;
+        Self::retain_modified_since(&mut summaries, params.modified_since.as_deref())?;
+        let response = self.paginate(summaries, offset, limit);
         Ok(render_json(&response))
     }
 
-    #[tool(description = "List translation keys only, optionally filtered by a search query")]
+    #[tool(
+        description = "List translation keys only, optionally filtered by a search query",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
     async fn list_keys(
         &self,
         params: Parameters<ListKeysParams>,
@@ -380,311 +1732,2163 @@ impl XcStringsMcpServer {
             .unwrap_or(DEFAULT_LIST_LIMIT);
         let limit = if limit == 0 { usize::MAX } else { limit };
 
-        let summaries = store.list_summaries(query).await;
-        let total = summaries.len();
-        let keys: Vec<String> = summaries.into_iter().take(limit).map(|s| s.key).collect();
-        let truncated = total > keys.len();
-        let response = serde_json::json!({
-            "keys": keys,
-            "total": total,
-            "returned": keys.len(),
-            "truncated": truncated
+        let offset = params.offset.unwrap_or(0) as usize;
+
+        let mut summaries = store.list_summaries(query).await;
+        Self::retain_assigned(&store, &mut summaries, params.assignee.as_deref()).await?;
+        Self::retain_modified_since(&mut summaries, params.modified_since.as_deref())?;
+        let keys: Vec<String> = summaries.into_iter().map(|s| s.key).collect();
+        let page = self.paginate(keys, offset, limit);
+        let mut response = serde_json::json!({
+            "keys": page.items,
+            "total": page.total,
+            "returned": page.returned,
+            "offset": page.offset,
+            "truncated": page.truncated
         });
+        if let Some(next_offset) = page.next_offset {
+            response["nextOffset"] = next_offset.into();
+        }
+        if let Some(notice) = page.notice {
+            response["notice"] = notice.into();
+        }
         Ok(render_json(&response))
     }
 
-    #[tool(description = "Fetch a single translation by key and language")]
-    async fn get_translation(
+    #[tool(
+        description = "List every key's value and state in one language, without the other languages, to work through a catalog one language at a time. Keys without a translation into the language are listed without value or state; missingOnly keeps only those",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn list_language_values(
         &self,
-        params: Parameters<GetTranslationParams>,
+        params: Parameters<ListLanguageValuesParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
         let store = self.store_for(Some(params.path.as_str())).await?;
-        let value = store
-            .get_translation(&params.key, &params.language)
+        let limit = params
+            .limit
+            .map(|value| value as usize)
+            .unwrap_or(DEFAULT_LIST_LIMIT);
+        let limit = if limit == 0 { usize::MAX } else { limit };
+        let offset = params.offset.unwrap_or(0) as usize;
+
+        let mut values = store
+            .list_language_values(&params.language, params.query.as_deref())
             .await
             .map_err(Self::error_to_mcp)?;
-        Ok(render_translation_value(value))
+        if params.missing_only {
+            values.retain(|value| value.value.is_none() && value.state.is_none());
+        }
+        if let Some(state) = params
+            .state
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            values.retain(|value| value.state.as_deref() == Some(state));
+        }
+        Ok(render_json(&self.paginate(values, offset, limit)))
     }
 
-    #[tool(description = "Create or update a translation")]
-    async fn upsert_translation(
+    #[tool(
+        description = "Count the keys matching a search query without listing them",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn count_keys(
         &self,
-        params: Parameters<UpsertTranslationParams>,
+        params: Parameters<CountKeysParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
-        let path = params.path.clone();
-        let key = params.key.clone();
-        let language = params.language.clone();
-        let update = params.into_update();
-        let store = self.store_for(Some(path.as_str())).await?;
-        let updated = store
-            .upsert_translation(&key, &language, update)
-            .await
-            .map_err(Self::error_to_mcp)?;
-        Ok(render_translation_value(Some(updated)))
+        let query = params.query.as_deref();
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let count = match params.assignee.as_deref().map(str::trim) {
+            Some(assignee) if !assignee.is_empty() => {
+                let assignments = store.assignments().await.map_err(Self::error_to_mcp)?;
+                store
+                    .matching_keys(query)
+                    .await
+                    .iter()
+                    .filter(|key| assignments.is_assigned(key, assignee))
+                    .count()
+            }
+            _ => store.count_keys(query).await,
+        };
+        Ok(render_json(&serde_json::json!({ "count": count })))
     }
 
-    #[tool(description = "Delete a translation for a given language")]
-    async fn delete_translation(
+    #[tool(
+        description = "Check whether a key exists, without fetching its translations",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn key_exists(
         &self,
-        params: Parameters<DeleteTranslationParams>,
+        params: Parameters<KeyExistsParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
         let store = self.store_for(Some(params.path.as_str())).await?;
-        store
-            .delete_translation(&params.key, &params.language)
-            .await
-            .map_err(Self::error_to_mcp)?;
-        Ok(render_ok_message("Translation deleted"))
+        let exists = store.contains_key(&params.key).await;
+        Ok(render_json(
+            &serde_json::json!({ "key": params.key, "exists": exists }),
+        ))
     }
 
-    #[tool(description = "Delete an entire translation key across all languages")]
-    async fn delete_key(
+    #[tool(
+        description = "Find existing keys close to a guessed name, by edit distance and shared words, closest first. Use it before creating a key to avoid near-duplicates",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn find_similar_keys(
         &self,
-        params: Parameters<DeleteKeyParams>,
+        params: Parameters<FindSimilarKeysParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
         let store = self.store_for(Some(params.path.as_str())).await?;
-        store
-            .delete_key(&params.key)
-            .await
-            .map_err(Self::error_to_mcp)?;
-        Ok(render_ok_message("Key deleted"))
+        let max_distance = params
+            .max_distance
+            .map_or(similar::DEFAULT_MAX_DISTANCE, |value| value as usize);
+        let limit = params
+            .limit
+            .map_or(similar::DEFAULT_LIMIT, |value| value as usize);
+        let matches = store.similar_keys(&params.key, max_distance, limit).await;
+        Ok(render_json(&serde_json::json!({
+            "key": params.key,
+            "exists": matches.first().is_some_and(|m| m.key == params.key),
+            "matches": matches,
+        })))
     }
 
-    #[tool(description = "Set or clear the developer comment for a translation key")]
-    async fn set_comment(
+    #[tool(
+        description = "Find the keys whose text equals the given text, exactly or ignoring case and surrounding whitespace, to answer where a UI string is defined. Searches the source language unless a language is given",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn find_key_by_value(
         &self,
-        params: Parameters<SetCommentParams>,
+        params: Parameters<FindKeyByValueParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
         let store = self.store_for(Some(params.path.as_str())).await?;
-        store
-            .set_comment(&params.key, params.comment.clone())
+        let matches = store
+            .find_keys_by_value(&params.text, params.language.as_deref())
             .await
             .map_err(Self::error_to_mcp)?;
-        Ok(render_ok_message("Comment updated"))
+        Ok(render_json(&serde_json::json!({ "matches": matches })))
     }
 
-    #[tool(description = "Set or clear the translation state for a language entry")]
-    async fn set_translation_state(
+    #[tool(
+        description = "Group keys by dot-separated prefix (e.g. checkout.payment) with key counts and per-language completion for each namespace",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn list_key_namespaces(
         &self,
-        params: Parameters<SetTranslationStateParams>,
+        params: Parameters<ListKeyNamespacesParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
         let store = self.store_for(Some(params.path.as_str())).await?;
-        let updated = store
-            .set_translation_state(&params.key, &params.language, params.state.clone())
+        let mut namespace = store.key_namespaces().await;
+        let prefix = params.prefix.as_deref().map(str::trim).unwrap_or_default();
+        if !prefix.is_empty() {
+            let mut wanted = String::new();
+            for segment in prefix.split('.') {
+                if !wanted.is_empty() {
+                    wanted.push('.');
+                }
+                wanted.push_str(segment);
+                namespace = namespace
+                    .children
+                    .into_iter()
+                    .find(|child| child.namespace == wanted)
+                    .ok_or_else(|| {
+                        McpError::invalid_params(
+                            format!("No keys under namespace '{prefix}'"),
+                            None,
+                        )
+                    })?;
+            }
+        }
+        let mut namespaces = Vec::new();
+        flatten_namespaces(
+            &namespace,
+            params.depth.unwrap_or(1).max(1),
+            &mut namespaces,
+        );
+        Ok(render_json(&serde_json::json!({
+            "namespace": namespace.namespace,
+            "totalKeys": namespace.total_keys,
+            "directKeys": namespace.direct_keys,
+            "completion": namespace.completion,
+            "namespaces": namespaces,
+        })))
+    }
+
+    #[tool(
+        description = "Fetch a single translation by key and language. When it doesn't exist, the error's data.missing is `key` (create the key), `language` (no key has this language yet), or `translation` (only this key lacks it)",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn get_translation(
+        &self,
+        params: Parameters<GetTranslationParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let value = store
+            .get_translation(&params.key, &params.language)
             .await
             .map_err(Self::error_to_mcp)?;
-        Ok(render_translation_value(Some(updated)))
+        Ok(render_translation_value(value))
     }
 
-    #[tool(description = "Set or clear the extraction state for a string key")]
-    async fn set_extraction_state(
+    #[tool(
+        description = "Fetch a translation with locale fallback (fr-CA, then fr, then the source language) and report which locale supplied it",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn get_translation_resolved(
         &self,
-        params: Parameters<SetExtractionStateParams>,
+        params: Parameters<GetTranslationParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
         let store = self.store_for(Some(params.path.as_str())).await?;
-        store
-            .set_extraction_state(&params.key, params.extraction_state.clone())
+        let resolved = store
+            .get_translation_resolved(&params.key, &params.language)
             .await
             .map_err(Self::error_to_mcp)?;
-        Ok(render_ok_message("Extraction state updated"))
+        Ok(render_json(&resolved))
     }
 
-    #[tool(description = "List all languages present in the xcstrings file")]
-    async fn list_languages(
+    #[tool(
+        description = "Suggest translations of a key into a language, ranked best first, each with where it came from: keys in any catalog with the same source text (same_source) or a similar one (memory), and glossary terms in the source text (glossary). Pick or adapt one instead of translating from scratch, keeping wording consistent",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn suggest_translations(
         &self,
-        params: Parameters<ListLanguagesParams>,
+        params: Parameters<SuggestTranslationsParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
+        let limit = params
+            .limit
+            .map_or(suggest::DEFAULT_LIMIT, |limit| limit as usize);
         let store = self.store_for(Some(params.path.as_str())).await?;
-        store.reload().await.expect("reload store");
-        let languages = store.list_languages().await;
-        Ok(render_languages(languages))
+        let source_text = store
+            .source_text(&params.key)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        let suggestions = self
+            .stores
+            .suggest_translations(
+                &store,
+                &params.key,
+                &params.language,
+                &self.qa_options.glossary,
+                limit,
+            )
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&serde_json::json!({
+            "key": params.key,
+            "language": params.language,
+            "sourceText": source_text,
+            "suggestions": suggestions,
+        })))
     }
 
-    #[tool(description = "Add a new language to the xcstrings file")]
-    async fn add_language(
+    #[tool(
+        description = "Create or update a translation",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn upsert_translation(
         &self,
-        params: Parameters<AddLanguageParams>,
+        params: Parameters<UpsertTranslationParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
-        let store = self.store_for(Some(params.path.as_str())).await?;
-        store
-            .add_language(&params.language)
+        let path = params.path.clone();
+        let key = params.key.clone();
+        let language = params.language.clone();
+        let force = params.force;
+        let allow_create = params.allow_create.unwrap_or(true);
+        let comment = params.comment.clone();
+        let position = params.insert_position.clone();
+        let update = params.into_update();
+        let store = self.store_for(Some(path.as_str())).await?;
+        let store = if force {
+            Arc::new(store.forcing())
+        } else {
+            store
+        };
+        let outcome = store
+            .upsert_translation_with(&key, &language, update, comment, &position, allow_create)
             .await
             .map_err(Self::error_to_mcp)?;
-        Ok(render_ok_message(&format!(
-            "Language '{}' added successfully",
-            params.language
-        )))
+        Ok(render_upsert(outcome))
     }
 
-    #[tool(description = "Remove a language from the xcstrings file")]
-    async fn remove_language(
+    #[tool(
+        description = "Delete a translation for a given language",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn delete_translation(
         &self,
-        params: Parameters<RemoveLanguageParams>,
+        params: Parameters<DeleteTranslationParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
         let store = self.store_for(Some(params.path.as_str())).await?;
+        let store = if params.force {
+            Arc::new(store.forcing())
+        } else {
+            store
+        };
         store
-            .remove_language(&params.language)
+            .delete_translation(&params.key, &params.language)
             .await
             .map_err(Self::error_to_mcp)?;
-        Ok(render_ok_message(&format!(
-            "Language '{}' removed successfully",
-            params.language
-        )))
+        Ok(render_ok_message("Translation deleted"))
     }
 
-    #[tool(description = "Update/rename a language in the xcstrings file")]
-    async fn update_language(
+    #[tool(
+        description = "Set the text a key shows on one device (iphone, ipod, ipad, mac, applewatch, appletv, applevision, other) in a language, or remove that device case when value is omitted. Rejects edits that would mix device and plural variations at the top level",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn set_device_variant(
         &self,
-        params: Parameters<UpdateLanguageParams>,
+        params: Parameters<SetDeviceVariantParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
         let store = self.store_for(Some(params.path.as_str())).await?;
-        store
-            .update_language(&params.old_language, &params.new_language)
+        let updated = store
+            .set_device_variant(&params.key, &params.language, &params.device, params.value)
             .await
             .map_err(Self::error_to_mcp)?;
-        Ok(render_ok_message(&format!(
-            "Language '{}' renamed to '{}' successfully",
-            params.old_language, params.new_language
-        )))
+        Ok(render_translation_value(updated))
     }
 
     #[tool(
-        description = "List untranslated keys per language (empty values or duplicates across languages)"
+        description = "List the device-specific variants of a key, per language",
+        annotations(read_only_hint = true, open_world_hint = false)
     )]
-    async fn list_untranslated(
+    async fn list_device_variants(
         &self,
-        params: Parameters<ListUntranslatedParams>,
+        params: Parameters<ListDeviceVariantsParams>,
     ) -> Result<CallToolResult, McpError> {
         let params = params.0;
         let store = self.store_for(Some(params.path.as_str())).await?;
-        let untranslated = store.list_untranslated().await;
-        Ok(render_json(&untranslated))
+        let variants = store
+            .list_device_variants(&params.key)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(
+            &serde_json::json!({ "key": params.key, "languages": variants }),
+        ))
     }
-}
 
-impl From<StoreError> for McpError {
-    fn from(value: StoreError) -> Self {
-        XcStringsMcpServer::error_to_mcp(value)
+    #[tool(
+        description = "Rename every key under a dot-separated prefix (checkout.* to payment.*) in one save, failing without changes if a new key already exists. Use dryRun to preview and allCatalogs to rename across every catalog",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn rename_namespace(
+        &self,
+        params: Parameters<RenameNamespaceParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let apply = !params.dry_run;
+        let results = if params.all_catalogs {
+            self.stores
+                .rename_namespace_everywhere(&params.old_prefix, &params.new_prefix, apply)
+                .await
+                .map_err(Self::error_to_mcp)?
+        } else {
+            let store = self.store_for(Some(params.path.as_str())).await?;
+            let outcome = store
+                .rename_namespace(&params.old_prefix, &params.new_prefix, apply)
+                .await
+                .map_err(Self::error_to_mcp)?;
+            vec![(store.path().to_path_buf(), outcome)]
+        };
+        let catalogs: Vec<_> = results
+            .into_iter()
+            .map(|(path, outcome)| {
+                serde_json::json!({
+                    "path": path,
+                    "renamed": outcome.renamed,
+                    "collisions": outcome.collisions,
+                })
+            })
+            .collect();
+        Ok(render_json(&serde_json::json!({
+            "dryRun": params.dry_run,
+            "catalogs": catalogs,
+        })))
     }
-}
 
-#[tool_handler(router = self.tool_router)]
-impl rmcp::ServerHandler for XcStringsMcpServer {
-    fn get_info(&self) -> ServerInfo {
-        let mut info = ServerInfo::default();
-        info.instructions = Some(
-            "Manage translations in Localizable.xcstrings using the provided MCP tools.".into(),
-        );
-        info.capabilities = ServerCapabilities::builder().enable_tools().build();
-        info
+    #[tool(
+        description = "Create a key without translations, as Xcode does for strings added by hand, optionally with a comment and a source-language value. Use insert_position to place it after a related key or in sorted order; otherwise it goes at the end of the catalog. Returns the new record, whose ordinal is its position in the file",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn create_key(
+        &self,
+        params: Parameters<CreateKeyParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let key = params.key.trim();
+        if key.is_empty() {
+            return Err(McpError::invalid_params("key must not be empty", None));
+        }
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let record = store
+            .create_key(key, params.comment, params.value, &params.insert_position)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&record))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::store::{TranslationUpdate, XcStringsStoreManager};
-    use std::{
-        collections::BTreeMap,
-        path::PathBuf,
-        sync::{
-            atomic::{AtomicUsize, Ordering},
-            Arc,
-        },
-        time::{SystemTime, UNIX_EPOCH},
-    };
+    #[tool(
+        description = "Delete an entire translation key across all languages",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn delete_key(
+        &self,
+        params: Parameters<DeleteKeyParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        store
+            .delete_key(&params.key)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_ok_message("Key deleted"))
+    }
 
-    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+    #[tool(
+        description = "Apply RFC 6902 JSON Patch operations (add, remove, replace, move, copy, test) to the catalog's keys, for edits no other tool covers. Paths are JSON Pointers into the strings object of the .xcstrings file, starting with a key, such as /greeting/localizations/de/variations/plural/one/stringUnit/value or /greeting/comment; use ~1 for '/' in keys. Patched keys must still follow the catalog format, changed translations get the same checks as upsert_translation, and the patch is saved all at once or not at all",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn apply_patch(
+        &self,
+        params: Parameters<ApplyPatchParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let outcome = store
+            .apply_patch(&params.ops)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&serde_json::json!({
+            "changes": outcome.changes,
+            "warnings": outcome.warnings,
+        })))
+    }
 
-    fn fresh_store_path(label: &str) -> PathBuf {
-        let mut dir = std::env::temp_dir();
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos();
-        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
-        dir.push(format!("xcstrings_mcp_server_{label}_{nanos}_{id}"));
-        std::fs::create_dir_all(&dir).expect("create dir");
-        dir.join("Localizable.xcstrings")
+    #[tool(
+        description = "Apply upserts, deletions, and key moves across several catalogs at once, such as moving a feature's keys to another catalog while updating their values. Every operation gets the same checks as the matching single tool; if any fails, nothing is written to any catalog. Returns what changed in each catalog and each operation's result",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn apply_transaction(
+        &self,
+        params: Parameters<ApplyTransactionParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let operations: Vec<_> = params
+            .0
+            .operations
+            .into_iter()
+            .map(TransactionOperationParam::into_operation)
+            .collect();
+        if operations.is_empty() {
+            return Err(McpError::invalid_params(
+                "operations must not be empty",
+                None,
+            ));
+        }
+        let outcome = self
+            .stores
+            .apply_transaction(operations)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        #[derive(Serialize)]
+        struct Rendered {
+            change: store::StoreChange,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            value: Option<TranslationValue>,
+            #[serde(skip_serializing_if = "Vec::is_empty")]
+            warnings: Vec<VariationWarning>,
+            #[serde(rename = "needsReview", skip_serializing_if = "Vec::is_empty")]
+            needs_review: Vec<String>,
+        }
+        let mut results = Vec::with_capacity(outcome.results.len());
+        for (index, result) in outcome.results.into_iter().enumerate() {
+            match result {
+                Ok(applied) => results.push(Rendered {
+                    change: applied.change,
+                    value: applied.value,
+                    warnings: applied.warnings,
+                    needs_review: applied.needs_review,
+                }),
+                Err(err) => {
+                    let mut error = Self::error_to_mcp(err);
+                    error.message = format!(
+                        "Operation {index} failed, so nothing was written: {}",
+                        error.message
+                    )
+                    .into();
+                    return Err(error);
+                }
+            }
+        }
+        let saved: Vec<_> = outcome
+            .saved
+            .into_iter()
+            .map(|(path, changes)| {
+                serde_json::json!({ "path": path.to_string_lossy(), "changes": changes })
+            })
+            .collect();
+        Ok(render_json(&serde_json::json!({
+            "saved": saved,
+            "results": results,
+        })))
     }
 
-    fn parse_json(result: &CallToolResult) -> serde_json::Value {
-        let text = result
-            .content
-            .as_ref()
-            .expect("content available")
-            .first()
-            .expect("content entry")
-            .as_text()
-            .expect("text content")
-            .text
-            .clone();
-        serde_json::from_str(&text).expect("valid json payload")
+    #[tool(
+        description = "Get the JSON of one key exactly as the .xcstrings file stores it, including every variation and substitution, to edit with set_raw_entry",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn get_raw_entry(
+        &self,
+        params: Parameters<GetRawEntryParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let entry = store
+            .raw_entry(&params.key)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&entry))
     }
 
-    #[tokio::test]
-    async fn list_translations_tool_returns_records() {
-        let path = fresh_store_path("list_translations");
-        let path_str = path.to_str().unwrap().to_string();
-        let manager = Arc::new(
-            XcStringsStoreManager::new(None)
-                .await
-                .expect("create manager"),
-        );
-        let store = manager
-            .store_for(Some(path_str.as_str()))
+    #[tool(
+        description = "Replace the whole entry of a key, or create it, with JSON in the .xcstrings format as returned by get_raw_entry. For structures the other tools can't express, such as deeply nested variations. Unknown fields and values of the wrong type are rejected, and changed translations get the same checks as upsert_translation",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn set_raw_entry(
+        &self,
+        params: Parameters<SetRawEntryParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let outcome = store
+            .set_raw_entry(&params.key, params.json)
             .await
-            .expect("load store");
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&serde_json::json!({
+            "changes": outcome.changes,
+            "warnings": outcome.warnings,
+        })))
+    }
+
+    #[tool(
+        description = "Set or clear the developer comment for a translation key",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn set_comment(
+        &self,
+        params: Parameters<SetCommentParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
         store
-            .upsert_translation(
-                "greeting",
-                "en",
-                TranslationUpdate::from_value_state(Some("Hello".into()), None),
-            )
+            .set_comment(&params.key, params.comment.clone())
             .await
-            .expect("save translation");
-        let server = XcStringsMcpServer::new(manager.clone());
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_ok_message("Comment updated"))
+    }
 
-        let result = server
-            .list_translations(Parameters(ListTranslationsParams {
-                path: path_str.clone(),
-                query: None,
-                limit: None,
-            }))
+    #[tool(
+        description = "Set the structured translator metadata of a key (maxLength, screenshot, tone, and extra pairs). It is stored as the last line of the comment, such as `max_length=20; tone=formal`, and the rest of the comment is kept",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn set_metadata(
+        &self,
+        params: Parameters<SetMetadataParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let comment = store
+            .set_metadata(&params.key, &params.metadata)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&serde_json::json!({
+            "key": params.key,
+            "comment": comment,
+            "metadata": params.metadata,
+        })))
+    }
+
+    #[tool(
+        description = "Approve a translation that is waiting for review (state needs_review), marking it reviewed",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn approve_translation(
+        &self,
+        params: Parameters<GetTranslationParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let value = store
+            .approve_translation(&params.key, &params.language)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_translation_value(value))
+    }
+
+    #[tool(
+        description = "Reject a translation that is in review or reviewed, sending it back to state new. A reason is required and is recorded in the audit log",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn reject_translation(
+        &self,
+        params: Parameters<RejectTranslationParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let value = store
+            .reject_translation(&params.key, &params.language, &params.reason)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_translation_value(value))
+    }
+
+    #[tool(
+        description = "Assign keys to a translator and make them the owner of languages, stored in a <catalog>.assignments.json sidecar; omit assignee to unassign",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn assign_keys(
+        &self,
+        params: Parameters<AssignKeysParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        if params.keys.is_empty() && params.languages.is_empty() {
+            return Err(McpError::invalid_params(
+                "pass at least one key or language to assign",
+                None,
+            ));
+        }
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let assignments = store
+            .assign(&params.keys, &params.languages, params.assignee.as_deref())
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&assignments))
+    }
+
+    #[tool(
+        description = "List language owners and key assignees of a catalog",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn list_assignments(
+        &self,
+        params: Parameters<ListAssignmentsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let store = self.store_for(Some(params.0.path.as_str())).await?;
+        let assignments = store.assignments().await.map_err(Self::error_to_mcp)?;
+        Ok(render_json(&assignments))
+    }
+
+    #[tool(
+        description = "Set or clear the translation state for a language entry",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn set_translation_state(
+        &self,
+        params: Parameters<SetTranslationStateParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let updated = store
+            .set_translation_state(&params.key, &params.language, params.state.clone())
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_translation_value(updated))
+    }
+
+    #[tool(
+        description = "Set or clear the extraction state for a string key",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn set_extraction_state(
+        &self,
+        params: Parameters<SetExtractionStateParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        store
+            .set_extraction_state(&params.key, params.extraction_state.clone())
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_ok_message("Extraction state updated"))
+    }
+
+    #[tool(
+        description = "Check that the server is up and what it can do before starting a workflow: version, uptime, default catalog, how many catalogs were discovered and are open, how many have unsaved or outside changes, and whether edits are refused",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn server_status(&self) -> Result<CallToolResult, McpError> {
+        let stores = self.stores.loaded_stores().await;
+        let mut dirty_stores = 0;
+        for store in &stores {
+            if store.has_pending_save() || store.has_external_changes().await {
+                dirty_stores += 1;
+            }
+        }
+        let mut response = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "uptimeSeconds": self.started.elapsed().as_secs(),
+            "searchRoot": self.stores.search_root().to_string_lossy(),
+            "files": self.stores.available_paths().await.len(),
+            "loadedStores": stores.len(),
+            "dirtyStores": dirty_stores,
+            "readOnly": self.read_only || self.stores.is_read_only(),
+            "readOnlyPaths": self.stores.read_only_paths(),
+        });
+        if let Some(default) = self.stores.default_path() {
+            response["default"] = default.to_string_lossy().into();
+        }
+        if let Some(project) = self.stores.project() {
+            response["project"] = project.to_string_lossy().into();
+        }
+        Ok(render_json(&response))
+    }
+
+    #[tool(
+        description = "List the xcstrings files this server can open, with the Xcode targets that build each one when serving a project",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn list_files(&self) -> Result<CallToolResult, McpError> {
+        let paths = self
+            .stores
+            .refresh_discovered_paths()
+            .await
+            .map_err(Self::error_to_mcp)?;
+        let mut files = Vec::with_capacity(paths.len());
+        for path in &paths {
+            let mut entry = serde_json::json!({
+                "path": path.to_string_lossy(),
+                "label": self.stores.catalog_label(path).await,
+            });
+            let targets = self.stores.targets_for(path).await;
+            if !targets.is_empty() {
+                entry["targets"] = targets.into();
+            }
+            files.push(entry);
+        }
+        let mut response = serde_json::json!({ "files": files });
+        if let Some(default) = self.stores.default_path() {
+            response["default"] = default.to_string_lossy().into();
+        }
+        if let Some(project) = self.stores.project() {
+            response["project"] = project.to_string_lossy().into();
+        }
+        Ok(render_json(&response))
+    }
+
+    #[tool(
+        description = "List all languages present in the xcstrings file",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn list_languages(
+        &self,
+        params: Parameters<ListLanguagesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        store.reload().await.expect("reload store");
+        let languages = store.list_languages().await;
+        Ok(render_json(&serde_json::json!({
+            "languages": languages,
+            "frozen": store.frozen_languages(),
+        })))
+    }
+
+    #[tool(
+        description = "For each regional language (e.g. fr-CA), list the keys whose text differs from its parent locale and those that merely repeat it",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn list_regional_overrides(
+        &self,
+        params: Parameters<ListLanguagesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let regions = store.regional_overrides().await;
+        Ok(render_json(&serde_json::json!({ "regions": regions })))
+    }
+
+    #[tool(
+        description = "Add a new language to the xcstrings file",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn add_language(
+        &self,
+        params: Parameters<AddLanguageParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let language = store
+            .add_language(&params.language)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_ok_message(&format!(
+            "Language '{language}' added successfully"
+        )))
+    }
+
+    #[tool(
+        description = "Remove a language from the xcstrings file",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn remove_language(
+        &self,
+        params: Parameters<RemoveLanguageParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        store
+            .remove_language(&params.language)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_ok_message(&format!(
+            "Language '{}' removed successfully",
+            params.language
+        )))
+    }
+
+    #[tool(
+        description = "Update/rename a language in the xcstrings file",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn update_language(
+        &self,
+        params: Parameters<UpdateLanguageParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let new_language = store
+            .update_language(&params.old_language, &params.new_language)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_ok_message(&format!(
+            "Language '{}' renamed to '{new_language}' successfully",
+            params.old_language
+        )))
+    }
+
+    #[tool(
+        description = "Freeze a language signed off for a release so later edits to it fail unless forced. Refuses while keys are untranslated, new, in review, or stale, unless force is set",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn freeze_language(
+        &self,
+        params: Parameters<FreezeLanguageParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let frozen = store
+            .freeze_language(&params.language, params.force)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&serde_json::json!({ "frozen": frozen })))
+    }
+
+    #[tool(
+        description = "Unfreeze a language so it can be edited again",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn unfreeze_language(
+        &self,
+        params: Parameters<RemoveLanguageParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let frozen = store
+            .unfreeze_language(&params.language)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&serde_json::json!({ "frozen": frozen })))
+    }
+
+    #[tool(
+        description = "List untranslated keys per language (empty values or duplicates across languages), leaving out keys marked shouldTranslate=false unless includeDoNotTranslate is set",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn list_untranslated(
+        &self,
+        params: Parameters<ListUntranslatedParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let untranslated = store
+            .list_untranslated_with(params.include_do_not_translate)
+            .await;
+        Ok(render_json(&untranslated))
+    }
+
+    #[tool(
+        description = "Run the QA suite (plural, placeholder, terminology, lint, key hygiene, translation consistency, and spelling checks) and return findings grouped by severity",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn validate_catalog(
+        &self,
+        params: Parameters<ValidateCatalogParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let rules = params.rules.unwrap_or_default();
+        let report = store.validate(&rules, &self.qa_options).await;
+        Ok(render_json(&report))
+    }
+
+    #[tool(
+        description = "Release gate to run before archiving a build: fails with the details as error data when a required language is below its completion threshold or any QA error is outstanding, and returns the per-language results when the catalog is ready",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn check_release_readiness(
+        &self,
+        params: Parameters<CheckReleaseReadinessParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        if let Some((language, _)) = params
+            .thresholds
+            .iter()
+            .find(|(_, percentage)| !(0.0..=100.0).contains(*percentage))
+        {
+            return Err(McpError::invalid_params(
+                format!("threshold for {language} must be a percentage from 0 to 100"),
+                None,
+            ));
+        }
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let readiness = store
+            .release_readiness(&params.thresholds, &self.qa_options)
+            .await;
+        if !readiness.ready {
+            return Err(McpError::invalid_request(
+                format!("Not ready for release: {}", readiness.problems().join("; ")),
+                serde_json::to_value(&readiness).ok(),
+            ));
+        }
+        Ok(render_json(&readiness))
+    }
+
+    #[tool(
+        description = "Report how much longer translations are than the source text: per-language average and maximum expansion ratio, and the translations that grew the most, to spot layouts that may break",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn length_report(
+        &self,
+        params: Parameters<LengthReportParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let top = params.top.map_or(DEFAULT_TOP, |top| top as usize);
+        let mut report = store.length_report(top).await;
+        if let Some(language) = params.language.as_deref() {
+            report
+                .languages
+                .retain(|lengths| lengths.language == language);
+        }
+        Ok(render_json(&report))
+    }
+
+    #[tool(
+        description = "Estimate vendor cost per language from the source word count of keys that are untranslated or, unless onlyUntranslated is set, new, in review, or stale",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn estimate_cost(
+        &self,
+        params: Parameters<EstimateCostParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        if !params.rate_per_word.is_finite() || params.rate_per_word < 0.0 {
+            return Err(McpError::invalid_params(
+                "ratePerWord must be a non-negative number",
+                None,
+            ));
+        }
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let estimate = store
+            .estimate_cost(
+                params.language.as_deref(),
+                params.rate_per_word,
+                params.only_untranslated,
+                params.include_do_not_translate,
+            )
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&estimate))
+    }
+
+    #[tool(
+        description = "Mine phrases that recur in the source text of many keys, most frequent first, each with the translation its keys most consistently use per language and a confidence from 0 to 1. Terms already in the glossary are left out. Candidates only: have a person approve them before adding them to the glossary",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn extract_terminology(
+        &self,
+        params: Parameters<ExtractTerminologyParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let limit = params
+            .limit
+            .map(|value| value as usize)
+            .unwrap_or(DEFAULT_LIST_LIMIT);
+        let limit = if limit == 0 { usize::MAX } else { limit };
+        let offset = params.offset.unwrap_or(0) as usize;
+        let min_occurrences = params
+            .min_occurrences
+            .map_or(terminology::DEFAULT_MIN_OCCURRENCES, |value| value as usize);
+        let terms = store
+            .extract_terminology(min_occurrences, &self.qa_options.glossary)
+            .await;
+        Ok(render_json(&self.paginate(terms, offset, limit)))
+    }
+
+    #[tool(
+        description = "Summarize the changes made to a catalog since a date as Markdown for Slack or release notes: who made them, new keys, translations updated per language, renames, and deletions. Built from the audit log, so it requires AUDIT_LOG",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn digest(&self, params: Parameters<DigestParams>) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let Some(audit_log) = self.stores.audit_log() else {
+            return Err(McpError::invalid_request(
+                "Digests come from the audit log; set AUDIT_LOG to record it",
+                None,
+            ));
+        };
+        let raw = params.since.trim();
+        let since = modified::parse_since(raw).ok_or_else(|| {
+            McpError::invalid_params(
+                format!("Invalid since '{raw}'; expected an RFC 3339 timestamp or YYYY-MM-DD"),
+                None,
+            )
+        })?;
+        let mut entries = audit_log
+            .activity(store.path(), Some(since), usize::MAX)
+            .await
+            .map_err(|err| Self::error_to_mcp(StoreError::ReadFailed(err)))?;
+        entries.reverse();
+        let name = store.path().file_name().map_or_else(
+            || store.path().display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let digest = Digest::from_entries(&entries);
+        Ok(render_ok_message(
+            &digest.to_markdown(&format!("{name} since {raw}")),
+        ))
+    }
+
+    #[tool(
+        description = "Rewrite a catalog the way Xcode formats it after normalizing it, optionally sorting keys, and report whether the file changed. Use dryRun to check formatting without writing",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn format_catalog(
+        &self,
+        params: Parameters<FormatCatalogParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let apply = !params.dry_run;
+        let sorted = match params.sort_keys {
+            true => store.sort_keys(apply).await.map_err(Self::error_to_mcp)?,
+            false => false,
+        };
+        let reformatted = store.reformat(apply).await.map_err(Self::error_to_mcp)?;
+        Ok(render_json(&serde_json::json!({
+            "path": store.path(),
+            "dryRun": params.dry_run,
+            "changed": sorted || reformatted,
+            "sorted": sorted,
+            "reformatted": reformatted,
+        })))
+    }
+
+    #[tool(
+        description = "Remove empty localizations, and keys left with no localizations, comment, or flags. Ordinary saves keep these so placeholders created by other tools survive; use dryRun to list them without writing",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn clean_catalog(
+        &self,
+        params: Parameters<CleanCatalogParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let removed = store
+            .clean(!params.dry_run)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&serde_json::json!({
+            "path": store.path(),
+            "dryRun": params.dry_run,
+            "changed": !removed.is_empty(),
+            "removed": removed,
+        })))
+    }
+
+    #[tool(
+        description = "Check a catalog file for structural problems that loading silently drops or tolerates: duplicate JSON keys, variations the schema doesn't allow, substitutions with a missing or wrong argNum or that no %#@name@ uses, and empty localizations or keys. Fixes what it can and rewrites the file; use dryRun to only get the report",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn repair_catalog(
+        &self,
+        params: Parameters<RepairCatalogParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let report = store
+            .repair(!params.dry_run)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&serde_json::json!({
+            "path": store.path(),
+            "dryRun": params.dry_run,
+            "repaired": report.repaired,
+            "issues": report.issues,
+        })))
+    }
+
+    #[tool(
+        description = "Save a named snapshot of the catalog so it can be restored later with restore_snapshot; reusing a label overwrites that snapshot",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn snapshot_catalog(
+        &self,
+        params: Parameters<SnapshotParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let snapshot = store
+            .snapshot(&params.label)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        let snapshots = store.list_snapshots().await.map_err(Self::error_to_mcp)?;
+        Ok(render_json(&serde_json::json!({
+            "label": params.label,
+            "snapshotPath": snapshot.to_string_lossy(),
+            "snapshots": snapshots,
+        })))
+    }
+
+    #[tool(
+        description = "Replace the catalog with a snapshot previously saved by snapshot_catalog",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn restore_snapshot(
+        &self,
+        params: Parameters<SnapshotParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        store
+            .restore_snapshot(&params.label)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_ok_message(&format!(
+            "Snapshot '{}' restored",
+            params.label
+        )))
+    }
+}
+
+/// Tools reading the catalog's git history.
+#[cfg(feature = "git")]
+#[tool_router(router = git_tool_router)]
+impl XcStringsMcpServer {
+    #[tool(
+        description = "Show when a translation was added, changed, or removed, using the git history of a catalog inside a repository: commit, author, date, summary, and the value after each change, newest first",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn key_history(
+        &self,
+        params: Parameters<KeyHistoryParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let depth = params
+            .depth
+            .map_or(git::DEFAULT_DEPTH, |depth| depth as usize);
+        let history = store
+            .key_history(&params.key, &params.language, depth)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&history))
+    }
+
+    #[tool(
+        description = "List translations into a language whose source text changed in a later commit than the translation, using the git history of a catalog inside a repository. These may be out of date even when their state says translated. Each entry names the commits that last changed the source and the translation",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn list_outdated(
+        &self,
+        params: Parameters<ListOutdatedParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let depth = params
+            .depth
+            .map_or(git::DEFAULT_DEPTH, |depth| depth as usize);
+        let report = store
+            .list_outdated(&params.language, depth)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&report))
+    }
+
+    #[tool(
+        description = "Compare the catalog with its committed version at a git branch, tag, or commit: keys added and removed since then, and changed comments, shouldTranslate flags, and translations with their before and after values",
+        annotations(read_only_hint = true, open_world_hint = false)
+    )]
+    async fn diff_against_ref(
+        &self,
+        params: Parameters<DiffAgainstRefParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let diff = store
+            .diff_against_ref(&params.reference)
+            .await
+            .map_err(Self::error_to_mcp)?;
+        Ok(render_json(&diff))
+    }
+}
+
+/// Tools reaching translation management services.
+#[cfg(feature = "sync")]
+#[tool_router(router = sync_tool_router)]
+impl XcStringsMcpServer {
+    #[tool(
+        description = "Sync with a translation management service configured in the config file: push uploads new and changed source strings, pull downloads translations (approved ones become translated, others needs_review). Use dryRun to preview",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = true
+        )
+    )]
+    async fn sync_catalog(
+        &self,
+        params: Parameters<SyncCatalogParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = params.0;
+        let store = self.store_for(Some(params.path.as_str())).await?;
+        let provider = self
+            .sync_config
+            .provider(params.provider.as_deref())
+            .map_err(Self::sync_error_to_mcp)?;
+        let report = match params.direction {
+            SyncDirection::Push => sync::push(&store, provider.as_ref(), params.dry_run).await,
+            SyncDirection::Pull => {
+                sync::pull(&store, provider.as_ref(), &params.languages, params.dry_run).await
+            }
+        }
+        .map_err(Self::sync_error_to_mcp)?;
+        Ok(render_json(&report))
+    }
+}
+
+impl From<StoreError> for McpError {
+    fn from(value: StoreError) -> Self {
+        XcStringsMcpServer::error_to_mcp(value)
+    }
+}
+
+/// Serves MCP over streamable HTTP at `/mcp`, giving every client its own session. Pass
+/// `tls` to serve HTTPS instead.
+pub async fn serve_http(
+    addr: SocketAddr,
+    server: XcStringsMcpServer,
+    tls: Option<RustlsConfig>,
+) -> anyhow::Result<()> {
+    let service = StreamableHttpService::new(
+        move || Ok(server.new_session()),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+    let app = request_trace::layer(axum::Router::new().nest_service(MCP_HTTP_PATH, service));
+    info!(%addr, path = MCP_HTTP_PATH, tls = tls.is_some(), "Starting MCP HTTP transport");
+    if let Some(tls) = tls {
+        axum_server::bind_rustls(addr, tls)
+            .serve(app.into_make_service())
+            .await?;
+    } else {
+        let listener = TcpListener::bind(addr).await?;
+        axum::serve(listener, app.into_make_service()).await?;
+    }
+    Ok(())
+}
+
+impl rmcp::ServerHandler for XcStringsMcpServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            instructions: Some(
+                "Manage translations in Localizable.xcstrings using the provided MCP tools.".into(),
+            ),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .enable_logging()
+                .enable_completions()
+                .build(),
+            ..Default::default()
+        }
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let name = request.name.clone();
+        let arguments = request.arguments.clone();
+        if self.tool_router.has_route(&name) {
+            if let Some(message) = self.confirmation(&name, arguments.as_ref()).await {
+                if let Some(refusal) = Self::confirm_with_user(&context.peer, message).await {
+                    return Ok(refusal);
+                }
+            }
+        }
+
+        let audit_context = context.clone();
+        let (result, normalized) = store::track_normalization(
+            self.tool_router
+                .call(ToolCallContext::new(self, request, context)),
+        )
+        .await;
+        let mut result = result?;
+
+        if result.is_error != Some(true) && !self.is_read_only_tool(&name) {
+            self.record_audit(&name, arguments, &audit_context).await;
+        }
+        // Saves can drop empty entries and placeholders beyond what the tool was asked to
+        // change; list them after the tool's own output rather than losing them silently.
+        if !normalized.is_empty() {
+            result.content.push(Content::text(to_json_text(
+                &serde_json::json!({ "normalizedEntries": normalized }),
+            )));
+        }
+        Ok(result)
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tool_router.list_all()))
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        self.spawn_change_notifier(context.peer.clone());
+        if let Some(log_layer) = &self.log_layer {
+            self.spawn_log_forwarder(context.peer, log_layer.subscribe());
+        }
+    }
+
+    async fn complete(
+        &self,
+        request: CompleteRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CompleteResult, McpError> {
+        let path = request
+            .context
+            .as_ref()
+            .and_then(|context| context.arguments.as_ref())
+            .and_then(|arguments| arguments.get("path").cloned())
+            .or_else(|| match &request.r#ref {
+                Reference::Resource(resource) => catalog_path_from_uri(&resource.uri)
+                    .map(|path| path.to_string_lossy().to_string()),
+                Reference::Prompt(_) => None,
+            });
+
+        let mut values = self
+            .completion_candidates(
+                &request.argument.name,
+                &request.argument.value,
+                path.as_deref(),
+            )
+            .await;
+        let total = values.len();
+        values.truncate(CompletionInfo::MAX_VALUES);
+        let has_more = total > values.len();
+
+        Ok(CompleteResult {
+            completion: CompletionInfo {
+                values,
+                total: Some(total as u32),
+                has_more: Some(has_more),
+            },
+        })
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let mut paths = self
+            .stores
+            .refresh_discovered_paths()
+            .await
+            .map_err(Self::error_to_mcp)?;
+        for store in self.stores.loaded_stores().await {
+            let path = store.path().to_path_buf();
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let resources = paths
+            .iter()
+            .map(|path| Self::catalog_resource(path))
+            .collect();
+        Ok(ListResourcesResult::with_all_items(resources))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let path = catalog_path_from_uri(&request.uri).ok_or_else(|| {
+            McpError::resource_not_found(format!("Unknown resource '{}'", request.uri), None)
+        })?;
+        let store = self.store_for(Some(&path.to_string_lossy())).await?;
+        let text = store.to_apple_json().await;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(text, request.uri)],
+        })
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        let path = catalog_path_from_uri(&request.uri).ok_or_else(|| {
+            McpError::invalid_params(format!("Unknown resource '{}'", request.uri), None)
+        })?;
+        let store = self.store_for(Some(&path.to_string_lossy())).await?;
+        self.subscriptions
+            .write()
+            .await
+            .insert(catalog_uri(store.path()));
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        let mut subscriptions = self.subscriptions.write().await;
+        if !subscriptions.remove(&request.uri) {
+            if let Some(path) = catalog_path_from_uri(&request.uri) {
+                let normalized = std::fs::canonicalize(&path).unwrap_or(path);
+                subscriptions.remove(&catalog_uri(&normalized));
+            }
+        }
+        Ok(())
+    }
+
+    async fn set_level(
+        &self,
+        request: SetLevelRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        *self.log_level.write().await = request.level;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{TranslationUpdate, XcStringsStoreManager};
+    use std::{
+        collections::BTreeMap,
+        path::PathBuf,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    fn fresh_store_path(label: &str) -> PathBuf {
+        let mut dir = std::env::temp_dir();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        dir.push(format!("xcstrings_mcp_server_{label}_{nanos}_{id}"));
+        std::fs::create_dir_all(&dir).expect("create dir");
+        dir.join("Localizable.xcstrings")
+    }
+
+    fn parse_json(result: &CallToolResult) -> serde_json::Value {
+        let text = result
+            .content
+            .first()
+            .expect("content entry")
+            .as_text()
+            .expect("text content")
+            .text
+            .clone();
+        serde_json::from_str(&text).expect("valid json payload")
+    }
+
+    #[tokio::test]
+    async fn list_translations_tool_returns_records() {
+        let path = fresh_store_path("list_translations");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        store
+            .upsert_translation(
+                "greeting",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .expect("save translation");
+        let server = XcStringsMcpServer::new(manager.clone());
+
+        let result = server
+            .list_translations(Parameters(ListTranslationsParams {
+                path: path_str.clone(),
+                query: None,
+                limit: None,
+                offset: None,
+                assignee: None,
+                modified_since: None,
+            }))
+            .await
+            .expect("tool success");
+
+        let payload = parse_json(&result);
+        assert_eq!(payload.get("total").and_then(|v| v.as_u64()), Some(1));
+        assert_eq!(payload.get("returned").and_then(|v| v.as_u64()), Some(1));
+        let items = payload
+            .get("items")
+            .and_then(|v| v.as_array())
+            .expect("array payload");
+        assert_eq!(items.len(), 1);
+        let item = &items[0];
+        assert_eq!(item.get("key").and_then(|v| v.as_str()), Some("greeting"));
+        assert!(item.get("translations").is_none());
+        let languages = item
+            .get("languages")
+            .and_then(|v| v.as_array())
+            .expect("languages array");
+        assert_eq!(languages.len(), 1);
+        assert_eq!(languages[0].as_str(), Some("en"));
+        assert_eq!(
+            item.get("hasVariations").and_then(|v| v.as_bool()),
+            Some(false)
+        );
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn list_translations_trims_to_response_budget() {
+        let path = fresh_store_path("list_budget");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        for index in 0..50 {
+            store
+                .upsert_translation(
+                    &format!("key.{index:02}"),
+                    "en",
+                    TranslationUpdate::from_value_state(Some("Value".into()), None),
+                )
+                .await
+                .expect("save translation");
+        }
+        let server = XcStringsMcpServer::new(manager.clone()).with_max_response_bytes(Some(2048));
+
+        let result = server
+            .list_translations(Parameters(ListTranslationsParams {
+                path: path_str.clone(),
+                query: None,
+                limit: Some(0),
+                offset: None,
+                assignee: None,
+                modified_since: None,
+            }))
             .await
             .expect("tool success");
+        let text = &result.content[0].as_text().expect("text content").text;
+        assert!(text.len() <= 2048, "response is {} bytes", text.len());
+
+        let payload = parse_json(&result);
+        let returned = payload["returned"].as_u64().expect("returned");
+        assert!(returned > 0 && returned < 50);
+        assert_eq!(payload["total"].as_u64(), Some(50));
+        assert_eq!(payload["truncated"].as_bool(), Some(true));
+        assert_eq!(payload["nextOffset"].as_u64(), Some(returned));
+        assert!(payload["notice"]
+            .as_str()
+            .expect("notice")
+            .contains(&format!("offset={returned}")));
+
+        let next = server
+            .list_keys(Parameters(ListKeysParams {
+                path: path_str.clone(),
+                query: None,
+                limit: Some(5),
+                offset: Some(returned as u32),
+                assignee: None,
+                modified_since: None,
+            }))
+            .await
+            .expect("next page");
+        let payload = parse_json(&next);
+        assert_eq!(
+            payload["keys"][0].as_str(),
+            Some(format!("key.{returned:02}").as_str())
+        );
+        assert!(payload.get("notice").is_none());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn tools_advertise_safety_annotations() {
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let server = XcStringsMcpServer::new(manager);
+        let tools = server.tool_router.list_all();
+        let annotations = |name: &str| {
+            tools
+                .iter()
+                .find(|tool| tool.name == name)
+                .and_then(|tool| tool.annotations.clone())
+                .unwrap_or_else(|| panic!("annotations for {name}"))
+        };
+
+        for name in [
+            "list_files",
+            "list_translations",
+            "list_keys",
+            "count_keys",
+            "key_exists",
+            "find_similar_keys",
+            "find_key_by_value",
+            "list_key_namespaces",
+            "get_translation",
+            "get_translation_resolved",
+            "list_device_variants",
+            "list_assignments",
+            "list_languages",
+            "list_regional_overrides",
+            "list_untranslated",
+            "validate_catalog",
+            "check_release_readiness",
+            "length_report",
+            "estimate_cost",
+            #[cfg(feature = "git")]
+            "key_history",
+            #[cfg(feature = "git")]
+            "diff_against_ref",
+            #[cfg(feature = "git")]
+            "list_outdated",
+        ] {
+            assert_eq!(annotations(name).read_only_hint, Some(true), "{name}");
+        }
+
+        for name in [
+            "delete_key",
+            "delete_translation",
+            "remove_language",
+            "restore_snapshot",
+        ] {
+            let hints = annotations(name);
+            assert_eq!(hints.read_only_hint, Some(false), "{name}");
+            assert_eq!(hints.destructive_hint, Some(true), "{name}");
+        }
+
+        // Upserts replace existing values, so they're destructive too.
+        let upsert = annotations("upsert_translation");
+        assert_eq!(upsert.destructive_hint, Some(true));
+        assert_eq!(upsert.idempotent_hint, Some(true));
+
+        // Only syncing reaches a service outside the local catalogs.
+        assert!(tools.iter().all(|tool| {
+            let open_world = tool
+                .annotations
+                .as_ref()
+                .and_then(|hints| hints.open_world_hint);
+            open_world == Some(tool.name == "sync_catalog")
+        }));
+    }
+
+    #[tokio::test]
+    async fn disabled_tools_are_not_advertised() {
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let server = XcStringsMcpServer::new(manager).with_disabled_tools([
+            "delete_key",
+            "remove_language",
+            "rename_key",
+        ]);
+        let names: Vec<_> = server
+            .tool_router
+            .list_all()
+            .into_iter()
+            .map(|tool| tool.name.to_string())
+            .collect();
+
+        assert!(!names.contains(&"delete_key".to_string()));
+        assert!(!names.contains(&"remove_language".to_string()));
+        assert!(names.contains(&"upsert_translation".to_string()));
+        assert!(!server.new_session().tool_router.has_route("delete_key"));
+    }
+
+    #[tokio::test]
+    async fn destructive_tools_ask_for_confirmation() {
+        let path = fresh_store_path("confirmations");
+        let manager = Arc::new(
+            XcStringsStoreManager::new(Some(path.clone()))
+                .await
+                .expect("create manager"),
+        );
+        manager
+            .default_store()
+            .await
+            .unwrap()
+            .upsert_translation(
+                "title",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .unwrap();
+        let server = XcStringsMcpServer::new(manager);
+        let message = |tool: &str, arguments: serde_json::Value| {
+            let arguments = arguments.as_object().cloned();
+            server.confirmation_message(tool, arguments.as_ref())
+        };
+
+        for (tool, arguments, expected) in [
+            (
+                "delete_key",
+                serde_json::json!({"key": "title"}),
+                "Delete key 'title'",
+            ),
+            (
+                "delete_translation",
+                serde_json::json!({"key": "title", "language": "fr"}),
+                "'fr' translation of key 'title'",
+            ),
+            (
+                "remove_language",
+                serde_json::json!({"language": "fr"}),
+                "'fr'",
+            ),
+            (
+                "update_language",
+                serde_json::json!({"oldLanguage": "pt", "newLanguage": "pt-BR"}),
+                "'pt' to 'pt-BR'",
+            ),
+            (
+                "restore_snapshot",
+                serde_json::json!({"label": "before"}),
+                "'before'",
+            ),
+            (
+                "apply_patch",
+                serde_json::json!({"path": "A.xcstrings", "ops": [{"op": "remove", "path": "/title"}]}),
+                "removing /title",
+            ),
+            (
+                "set_raw_entry",
+                serde_json::json!({"key": "title"}),
+                "key 'title'",
+            ),
+            (
+                "apply_transaction",
+                serde_json::json!({"operations": [
+                    {"op": "delete_key", "path": "A.xcstrings", "key": "title"},
+                    {"op": "move_key", "key": "name", "from": "A.xcstrings", "to": "B.xcstrings"},
+                ]}),
+                "2 operations (1 deletions) to A.xcstrings, B.xcstrings",
+            ),
+            (
+                "repair_catalog",
+                serde_json::json!({}),
+                "the default catalog",
+            ),
+            (
+                "clean_catalog",
+                serde_json::json!({}),
+                "the default catalog",
+            ),
+            (
+                "rename_namespace",
+                serde_json::json!({"oldPrefix": "checkout", "newPrefix": "payment", "allCatalogs": true}),
+                "'checkout' to 'payment' in every catalog",
+            ),
+        ] {
+            let message = message(tool, arguments).unwrap_or_default();
+            assert!(message.contains(expected), "{tool}: {message}");
+        }
+        // Every tool annotated as destructive asks first.
+        let destructive = server.tool_router.map.iter().filter(|(_, route)| {
+            route
+                .attr
+                .annotations
+                .as_ref()
+                .and_then(|annotations| annotations.destructive_hint)
+                == Some(true)
+        });
+        let upsert = |value: &str| serde_json::json!({"path": path, "key": "title", "language": "en", "value": value});
+        for (tool, _) in destructive {
+            let arguments = match tool.as_ref() {
+                "upsert_translation" => upsert("Hi"),
+                _ => serde_json::json!({}),
+            };
+            assert!(
+                server
+                    .confirmation(tool, arguments.as_object())
+                    .await
+                    .is_some(),
+                "{tool} doesn't ask for confirmation"
+            );
+        }
+        let replace = server
+            .confirmation("upsert_translation", upsert("Hi").as_object())
+            .await
+            .unwrap_or_default();
+        assert!(replace.contains("'Hello' will be lost"), "{replace}");
+
+        // Previews and non-destructive tools go ahead.
+        for tool in ["repair_catalog", "clean_catalog", "rename_namespace"] {
+            assert_eq!(
+                message(tool, serde_json::json!({"dryRun": true})),
+                None,
+                "{tool}"
+            );
+        }
+        // Upserts that create a translation or keep its value don't replace anything.
+        for arguments in [
+            upsert("Hello"),
+            serde_json::json!({"path": path, "key": "subtitle", "language": "en", "value": "Hi"}),
+        ] {
+            assert_eq!(
+                server
+                    .confirmation("upsert_translation", arguments.as_object())
+                    .await,
+                None,
+                "{arguments}"
+            );
+        }
+        let server = server.clone().with_destructive_confirmation(false);
+        assert_eq!(
+            server.confirmation_message("delete_key", serde_json::json!({}).as_object()),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn read_only_server_advertises_only_read_only_tools() {
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let server = XcStringsMcpServer::new(manager).with_read_only();
+        let names: Vec<_> = server
+            .tool_router
+            .list_all()
+            .into_iter()
+            .map(|tool| tool.name.to_string())
+            .collect();
+
+        assert!(names.contains(&"list_translations".to_string()));
+        assert!(names.contains(&"validate_catalog".to_string()));
+        assert!(!names.contains(&"upsert_translation".to_string()));
+        assert!(!names.contains(&"snapshot_catalog".to_string()));
+        assert!(names.iter().all(|name| server.is_read_only_tool(name)));
+    }
+
+    #[tokio::test]
+    async fn server_status_reports_catalogs_and_capabilities() {
+        let path = fresh_store_path("server_status");
+        let manager = Arc::new(
+            XcStringsStoreManager::new(Some(path.clone()))
+                .await
+                .expect("create manager")
+                .with_read_only_paths(["Vendor/**".to_string()]),
+        );
+        manager
+            .default_store()
+            .await
+            .expect("load store")
+            .upsert_translation(
+                "greeting",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .expect("save translation");
 
-        let payload = parse_json(&result);
-        assert_eq!(payload.get("total").and_then(|v| v.as_u64()), Some(1));
-        assert_eq!(payload.get("returned").and_then(|v| v.as_u64()), Some(1));
-        let items = payload
-            .get("items")
-            .and_then(|v| v.as_array())
-            .expect("array payload");
-        assert_eq!(items.len(), 1);
-        let item = &items[0];
-        assert_eq!(item.get("key").and_then(|v| v.as_str()), Some("greeting"));
-        assert!(item.get("translations").is_none());
-        let languages = item
-            .get("languages")
-            .and_then(|v| v.as_array())
-            .expect("languages array");
-        assert_eq!(languages.len(), 1);
-        assert_eq!(languages[0].as_str(), Some("en"));
+        let server = XcStringsMcpServer::new(manager.clone());
+        let status = parse_json(&server.server_status().await.expect("status"));
+        assert_eq!(status["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(status["default"], path.to_string_lossy().as_ref());
+        assert_eq!(status["loadedStores"], 1);
+        assert_eq!(status["dirtyStores"], 0);
+        assert_eq!(status["readOnly"], false);
+        assert_eq!(status["readOnlyPaths"], serde_json::json!(["Vendor/**"]));
+
+        std::fs::write(&path, "{}").expect("edit outside the server");
+        let server = server.with_read_only();
+        let status = parse_json(&server.server_status().await.expect("status"));
+        assert_eq!(status["dirtyStores"], 1);
+        assert_eq!(status["readOnly"], true);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn catalog_uri_round_trips_to_path() {
+        let path = PathBuf::from("/tmp/project/Localizable.xcstrings");
+        let uri = catalog_uri(&path);
+        assert_eq!(uri, "file:///tmp/project/Localizable.xcstrings");
+        assert_eq!(catalog_path_from_uri(&uri), Some(path));
+        assert_eq!(catalog_path_from_uri("file://"), None);
         assert_eq!(
-            item.get("hasVariations").and_then(|v| v.as_bool()),
-            Some(false)
+            catalog_path_from_uri("https://example.com/a.xcstrings"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn log_level_filters_forwarded_messages() {
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let server = XcStringsMcpServer::new(manager);
+
+        assert!(XcStringsMcpServer::should_log(&server.log_level, LoggingLevel::Info).await);
+        assert!(!XcStringsMcpServer::should_log(&server.log_level, LoggingLevel::Debug).await);
+
+        *server.log_level.write().await = LoggingLevel::Error;
+        assert!(!XcStringsMcpServer::should_log(&server.log_level, LoggingLevel::Warning).await);
+        assert!(XcStringsMcpServer::should_log(&server.log_level, LoggingLevel::Critical).await);
+    }
+
+    #[tokio::test]
+    async fn completion_suggests_languages_and_keys() {
+        let path = fresh_store_path("completion");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        for (key, language, value) in [
+            ("settings.title", "en", "Settings"),
+            ("account.settings", "en", "Account settings"),
+            ("settings.title", "fr", "Réglages"),
+            ("settings.title", "fr-CA", "Paramètres"),
+        ] {
+            store
+                .upsert_translation(
+                    key,
+                    language,
+                    TranslationUpdate::from_value_state(Some(value.into()), None),
+                )
+                .await
+                .expect("seed translation");
+        }
+        let server = XcStringsMcpServer::new(manager.clone());
+
+        let languages = server
+            .completion_candidates("language", "FR", Some(path_str.as_str()))
+            .await;
+        assert_eq!(languages, vec!["fr".to_string(), "fr-CA".to_string()]);
+
+        let keys = server
+            .completion_candidates("key", "sett", Some(path_str.as_str()))
+            .await;
+        assert_eq!(
+            keys,
+            vec!["settings.title".to_string(), "account.settings".to_string()]
         );
+
+        let unknown = server
+            .completion_candidates("comment", "", Some(path_str.as_str()))
+            .await;
+        assert!(unknown.is_empty());
+
+        // Without a path and without a default catalog there is nothing to complete from
+        let no_catalog = server.completion_candidates("key", "sett", None).await;
+        assert!(no_catalog.is_empty());
+
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
     }
 
@@ -726,41 +3930,426 @@ mod tests {
         let result = server
             .list_keys(Parameters(ListKeysParams {
                 path: path_str.clone(),
-                query: None,
+                query: None,
+                limit: None,
+                offset: None,
+                assignee: None,
+                modified_since: None,
+            }))
+            .await
+            .expect("tool success");
+
+        let payload = parse_json(&result);
+        let keys = payload
+            .get("keys")
+            .and_then(|v| v.as_array())
+            .expect("keys array");
+        let key_values: Vec<&str> = keys
+            .iter()
+            .map(|v| v.as_str().expect("string key"))
+            .collect();
+        assert_eq!(keys.len(), 2);
+        assert!(key_values.contains(&"greeting"));
+        assert!(key_values.contains(&"farewell"));
+
+        // Query should filter down to a single key
+        let result = server
+            .list_keys(Parameters(ListKeysParams {
+                path: path_str.clone(),
+                query: Some("well".to_string()),
+                limit: None,
+                offset: None,
+                assignee: None,
+                modified_since: None,
+            }))
+            .await
+            .expect("filtered success");
+        let payload = parse_json(&result);
+        let keys = payload
+            .get("keys")
+            .and_then(|v| v.as_array())
+            .expect("keys array");
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].as_str(), Some("farewell"));
+
+        // Assignee should keep only the keys assigned to that translator
+        server
+            .assign_keys(Parameters(AssignKeysParams {
+                path: path_str.clone(),
+                assignee: Some("alice".to_string()),
+                keys: vec!["greeting".to_string()],
+                languages: Vec::new(),
+            }))
+            .await
+            .expect("assign success");
+        let result = server
+            .list_keys(Parameters(ListKeysParams {
+                path: path_str.clone(),
+                query: None,
+                limit: None,
+                offset: None,
+                assignee: Some("Alice".to_string()),
+                modified_since: None,
+            }))
+            .await
+            .expect("assignee success");
+        let payload = parse_json(&result);
+        assert_eq!(payload["keys"], serde_json::json!(["greeting"]));
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn list_language_values_tool_returns_one_language() {
+        let path = fresh_store_path("list_language_values_tool");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        let set = |value: &str, state: &str| {
+            TranslationUpdate::from_value_state(Some(value.into()), Some(state.into()))
+        };
+        for (key, language, value, state) in [
+            ("greeting", "en", "Hello", "translated"),
+            ("greeting", "de", "Hallo", "needs_review"),
+            ("farewell", "en", "Bye", "translated"),
+        ] {
+            store
+                .upsert_translation(key, language, set(value, state))
+                .await
+                .expect("save translation");
+        }
+        let server = XcStringsMcpServer::new(manager.clone());
+        let params = |language: &str, state: Option<&str>, missing_only: bool| {
+            Parameters(ListLanguageValuesParams {
+                path: path_str.clone(),
+                language: language.to_string(),
+                query: None,
+                state: state.map(str::to_string),
+                missing_only,
+                limit: None,
+                offset: None,
+            })
+        };
+
+        let payload = parse_json(
+            &server
+                .list_language_values(params("de", None, false))
+                .await
+                .expect("list de"),
+        );
+        assert_eq!(
+            payload["items"],
+            serde_json::json!([
+                { "key": "greeting", "value": "Hallo", "state": "needs_review", "hasVariations": false },
+                { "key": "farewell", "hasVariations": false },
+            ])
+        );
+
+        let missing = parse_json(
+            &server
+                .list_language_values(params("de", None, true))
+                .await
+                .expect("missing de"),
+        );
+        assert_eq!(missing["items"][0]["key"], "farewell");
+        assert_eq!(missing["total"], 1);
+        let review = parse_json(
+            &server
+                .list_language_values(params("de", Some("needs_review"), false))
+                .await
+                .expect("de in review"),
+        );
+        assert_eq!(review["items"][0]["key"], "greeting");
+        assert_eq!(review["total"], 1);
+
+        let err = server
+            .list_language_values(params("ja", None, false))
+            .await
+            .expect_err("unknown language");
+        assert!(err.message.contains("ja"), "{err:?}");
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn suggest_translations_tool_draws_on_every_catalog_and_the_glossary() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let main = dir.path().join("Localizable.xcstrings");
+        std::fs::write(
+            &main,
+            r#"{"sourceLanguage":"en","strings":{"checkout.save":{"localizations":{"en":{"stringUnit":{"state":"translated","value":"Save changes"}}}}},"version":"1.0"}"#,
+        )
+        .expect("write main catalog");
+        std::fs::create_dir(dir.path().join("Settings")).expect("create dir");
+        std::fs::write(
+            dir.path().join("Settings/Settings.xcstrings"),
+            r#"{"sourceLanguage":"en","strings":{"settings.save":{"localizations":{"de":{"stringUnit":{"state":"translated","value":"Änderungen sichern"}},"en":{"stringUnit":{"state":"translated","value":"Save changes."}}}}},"version":"1.0"}"#,
+        )
+        .expect("write other catalog");
+        let manager = Arc::new(
+            XcStringsStoreManager::new(Some(main.clone()))
+                .await
+                .expect("create manager"),
+        );
+        let glossary = BTreeMap::from([(
+            "changes".to_string(),
+            BTreeMap::from([("de".to_string(), "Änderungen".to_string())]),
+        )]);
+        let server = XcStringsMcpServer::new(manager).with_qa_options(QaOptions {
+            glossary,
+            ..QaOptions::default()
+        });
+
+        let payload = parse_json(
+            &server
+                .suggest_translations(Parameters(SuggestTranslationsParams {
+                    path: main.to_string_lossy().to_string(),
+                    key: "checkout.save".to_string(),
+                    language: "de".to_string(),
+                    limit: None,
+                }))
+                .await
+                .expect("suggest"),
+        );
+        assert_eq!(payload["sourceText"], "Save changes");
+        let suggestions = payload["suggestions"].as_array().expect("suggestions");
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0]["text"], "Änderungen sichern");
+        assert_eq!(suggestions[0]["source"], "same_source");
+        assert_eq!(suggestions[0]["key"], "settings.save");
+        assert_eq!(suggestions[1]["source"], "glossary");
+        assert_eq!(suggestions[1]["sourceText"], "changes");
+    }
+
+    #[tokio::test]
+    async fn extract_terminology_tool_pairs_terms_with_translations() {
+        let path = fresh_store_path("extract_terminology_tool");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        for (key, en, fr) in [
+            ("sync.title", "Sync your photos", "Synchroniser vos photos"),
+            ("share.title", "Share photos", "Partager des photos"),
+            ("delete.title", "Delete photos", "Supprimer des photos"),
+            ("greeting", "Hello", "Bonjour"),
+        ] {
+            for (language, value) in [("en", en), ("fr", fr)] {
+                store
+                    .upsert_translation(
+                        key,
+                        language,
+                        TranslationUpdate::from_value_state(Some(value.into()), None),
+                    )
+                    .await
+                    .expect("save translation");
+            }
+        }
+        let server = XcStringsMcpServer::new(manager.clone());
+        let extract = |min_occurrences: Option<u32>| {
+            server.extract_terminology(Parameters(ExtractTerminologyParams {
+                path: path_str.clone(),
+                min_occurrences,
+                limit: None,
+                offset: None,
+            }))
+        };
+
+        let payload = parse_json(&extract(None).await.expect("extract"));
+        assert_eq!(payload["total"], 1);
+        let term = &payload["items"][0];
+        assert_eq!(term["term"], "photos");
+        assert_eq!(term["occurrences"], 3);
+        assert_eq!(
+            term["translations"]["fr"],
+            serde_json::json!({ "text": "photos", "support": 3, "confidence": 1.0 })
+        );
+        let payload = parse_json(&extract(Some(4)).await.expect("extract"));
+        assert_eq!(payload["total"], 0);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn count_keys_and_key_exists_tools_answer_without_listing() {
+        let path = fresh_store_path("count_keys_tool");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        for (key, value) in [
+            ("paywall.title", "Subscribe now"),
+            ("paywall.cancel", "Cancel subscription"),
+            ("greeting", "Hello"),
+        ] {
+            store
+                .upsert_translation(
+                    key,
+                    "en",
+                    TranslationUpdate::from_value_state(Some(value.into()), None),
+                )
+                .await
+                .expect("save translation");
+        }
+        let server = XcStringsMcpServer::new(manager.clone());
+        let count = |query: Option<&str>, assignee: Option<&str>| {
+            server.count_keys(Parameters(CountKeysParams {
+                path: path_str.clone(),
+                query: query.map(str::to_string),
+                assignee: assignee.map(str::to_string),
+            }))
+        };
+
+        let result = count(Some("SUBSCRI"), None).await.expect("count");
+        assert_eq!(parse_json(&result), serde_json::json!({ "count": 2 }));
+        let result = count(None, None).await.expect("count all");
+        assert_eq!(parse_json(&result)["count"], 3);
+        server
+            .assign_keys(Parameters(AssignKeysParams {
+                path: path_str.clone(),
+                assignee: Some("alice".to_string()),
+                keys: vec!["paywall.title".to_string(), "greeting".to_string()],
+                languages: Vec::new(),
+            }))
+            .await
+            .expect("assign success");
+        let result = count(Some("subscri"), Some("alice"))
+            .await
+            .expect("count assigned");
+        assert_eq!(parse_json(&result)["count"], 1);
+
+        for (key, exists) in [("greeting", true), ("greting", false)] {
+            let result = server
+                .key_exists(Parameters(KeyExistsParams {
+                    path: path_str.clone(),
+                    key: key.to_string(),
+                }))
+                .await
+                .expect("exists");
+            assert_eq!(
+                parse_json(&result),
+                serde_json::json!({ "key": key, "exists": exists })
+            );
+        }
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn find_similar_keys_tool_suggests_near_misses() {
+        let path = fresh_store_path("find_similar_keys_tool");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        for key in ["login.button", "login.title", "onboarding.skip"] {
+            store
+                .upsert_translation(
+                    key,
+                    "en",
+                    TranslationUpdate::from_value_state(Some("Text".into()), None),
+                )
+                .await
+                .expect("save translation");
+        }
+        let server = XcStringsMcpServer::new(manager.clone());
+
+        let result = server
+            .find_similar_keys(Parameters(FindSimilarKeysParams {
+                path: path_str.clone(),
+                key: "button.login".into(),
+                max_distance: None,
                 limit: None,
             }))
             .await
             .expect("tool success");
-
         let payload = parse_json(&result);
-        let keys = payload
-            .get("keys")
-            .and_then(|v| v.as_array())
-            .expect("keys array");
-        let key_values: Vec<&str> = keys
-            .iter()
-            .map(|v| v.as_str().expect("string key"))
-            .collect();
-        assert_eq!(keys.len(), 2);
-        assert!(key_values.contains(&"greeting"));
-        assert!(key_values.contains(&"farewell"));
+        assert_eq!(payload["exists"], false);
+        assert_eq!(payload["matches"][0]["key"], "login.button");
+        assert_eq!(payload["matches"][0]["tokenOverlap"], 1.0);
+        assert_eq!(payload["matches"].as_array().unwrap().len(), 1);
 
-        // Query should filter down to a single key
         let result = server
-            .list_keys(Parameters(ListKeysParams {
+            .find_similar_keys(Parameters(FindSimilarKeysParams {
                 path: path_str.clone(),
-                query: Some("well".to_string()),
-                limit: None,
+                key: "login.title".into(),
+                max_distance: Some(0),
+                limit: Some(5),
             }))
             .await
-            .expect("filtered success");
+            .expect("tool success");
         let payload = parse_json(&result);
-        let keys = payload
-            .get("keys")
-            .and_then(|v| v.as_array())
-            .expect("keys array");
-        assert_eq!(keys.len(), 1);
-        assert_eq!(keys[0].as_str(), Some("farewell"));
+        assert_eq!(payload["exists"], true);
+        assert_eq!(payload["matches"][0]["distance"], 0);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn find_key_by_value_tool_reports_where_text_is_defined() {
+        let path = fresh_store_path("find_key_by_value_tool");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        store
+            .upsert_translation(
+                "paywall.cta",
+                "en",
+                TranslationUpdate::from_value_state(Some("Start free trial".into()), None),
+            )
+            .await
+            .expect("save translation");
+        let server = XcStringsMcpServer::new(manager.clone());
+        let find = |language: Option<&str>| {
+            server.find_key_by_value(Parameters(FindKeyByValueParams {
+                path: path_str.clone(),
+                text: "START FREE TRIAL".into(),
+                language: language.map(str::to_string),
+            }))
+        };
+
+        let payload = parse_json(&find(None).await.expect("tool success"));
+        assert_eq!(
+            payload,
+            serde_json::json!({ "matches": [{
+                "key": "paywall.cta",
+                "language": "en",
+                "value": "Start free trial",
+                "exact": false,
+            }] })
+        );
+        let err = find(Some("ja")).await.unwrap_err();
+        assert_eq!(err.code, rmcp::model::ErrorCode::RESOURCE_NOT_FOUND);
 
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
     }
@@ -812,6 +4401,107 @@ mod tests {
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
     }
 
+    #[tokio::test]
+    async fn list_key_namespaces_tool_drills_into_prefixes() {
+        let path = fresh_store_path("list_key_namespaces");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        for key in ["checkout.title", "checkout.payment.card", "settings.title"] {
+            store
+                .upsert_translation(
+                    key,
+                    "en",
+                    TranslationUpdate::from_value_state(Some("Text".into()), None),
+                )
+                .await
+                .expect("save translation");
+        }
+        let server = XcStringsMcpServer::new(manager.clone());
+        let params = |prefix: Option<&str>, depth: Option<u32>| {
+            Parameters(ListKeyNamespacesParams {
+                path: path_str.clone(),
+                prefix: prefix.map(str::to_string),
+                depth,
+            })
+        };
+
+        let payload = parse_json(
+            &server
+                .list_key_namespaces(params(None, None))
+                .await
+                .expect("tool success"),
+        );
+        assert_eq!(payload["totalKeys"], 3);
+        let names: Vec<_> = payload["namespaces"]
+            .as_array()
+            .expect("namespaces")
+            .iter()
+            .map(|namespace| namespace["namespace"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(names, vec!["checkout", "settings"]);
+
+        let payload = parse_json(
+            &server
+                .list_key_namespaces(params(Some("checkout"), Some(2)))
+                .await
+                .expect("tool success"),
+        );
+        assert_eq!(payload["directKeys"], 1);
+        assert_eq!(payload["namespaces"][0]["namespace"], "checkout.payment");
+        assert!(server
+            .list_key_namespaces(params(Some("checkout.missing"), None))
+            .await
+            .is_err());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn upsert_translation_tool_can_refuse_new_keys() {
+        let path = fresh_store_path("upsert_without_create");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let server = XcStringsMcpServer::new(manager.clone());
+        let upsert = |key: &str, allow_create: Option<bool>| {
+            server.upsert_translation(Parameters(UpsertTranslationParams {
+                path: path_str.clone(),
+                key: key.to_string(),
+                language: "en".into(),
+                value: Some(Some("Log in".into())),
+                state: None,
+                variations: None,
+                substitutions: None,
+                force: false,
+                allow_create,
+                comment: None,
+                insert_position: InsertPosition::End,
+            }))
+        };
+
+        upsert("login.button", None)
+            .await
+            .expect("create by default");
+        let error = upsert("loginn.button", Some(false)).await.unwrap_err();
+        assert_eq!(error.code, rmcp::model::ErrorCode::RESOURCE_NOT_FOUND);
+        assert_eq!(error.data.expect("error data")["missing"], "key");
+        upsert("login.button", Some(false))
+            .await
+            .expect("update existing key");
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
     #[tokio::test]
     async fn upsert_translation_tool_supports_plural_variations() {
         let path = fresh_store_path("upsert_plural");
@@ -859,6 +4549,10 @@ mod tests {
                 state: None,
                 variations: Some(variations),
                 substitutions: None,
+                force: false,
+                allow_create: None,
+                comment: None,
+                insert_position: InsertPosition::End,
             }))
             .await
             .expect("tool success");
@@ -866,8 +4560,7 @@ mod tests {
         let translation = store
             .get_translation("items", "en")
             .await
-            .expect("fetch translation")
-            .expect("translation exists");
+            .expect("fetch translation");
 
         let plural = translation
             .variations
@@ -984,11 +4677,10 @@ mod tests {
         let translation = store
             .get_translation("message", "fr")
             .await
-            .expect("fetch translation")
-            .expect("translation exists");
+            .expect("fetch translation");
 
         assert_eq!(translation.value.as_deref(), Some("Bonjour"));
-        assert_eq!(translation.state.as_deref(), Some("needs-review"));
+        assert_eq!(translation.state.as_deref(), Some("needs_review"));
 
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
     }
@@ -1021,8 +4713,7 @@ mod tests {
         let translation = store
             .get_translation("welcome", "es")
             .await
-            .expect("fetch translation")
-            .expect("translation exists");
+            .expect("fetch translation");
 
         assert_eq!(translation.state.as_deref(), Some("needs-translation"));
         assert_eq!(translation.value.as_deref(), Some(""));
@@ -1066,7 +4757,7 @@ mod tests {
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -1082,8 +4773,7 @@ mod tests {
         let placeholder = store
             .get_translation("greeting", "fr")
             .await
-            .expect("lookup succeeds")
-            .expect("placeholder created");
+            .expect("lookup succeeds");
         assert_eq!(placeholder.state.as_deref(), Some("needs-translation"));
         assert_eq!(placeholder.value.as_deref(), Some(""));
 
@@ -1101,7 +4791,7 @@ mod tests {
         let languages = store.list_languages().await;
         assert!(languages.contains(&"fr".to_string()));
 
-        let greeting_fr = store.get_translation("greeting", "fr").await.unwrap();
+        let greeting_fr = store.get_translation("greeting", "fr").await.ok();
         let greeting_fr = greeting_fr.expect("translation exists");
         assert_eq!(greeting_fr.value.as_deref(), Some("Bonjour"));
         assert_eq!(greeting_fr.state.as_deref(), Some("translated"));
@@ -1177,7 +4867,7 @@ mod tests {
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -1192,7 +4882,7 @@ mod tests {
         let languages = store.list_languages().await;
         assert!(!languages.contains(&"fr".to_string()));
 
-        let greeting_fr = store.get_translation("greeting", "fr").await.unwrap();
+        let greeting_fr = store.get_translation("greeting", "fr").await.ok();
         assert!(greeting_fr.is_none());
 
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
@@ -1267,7 +4957,7 @@ mod tests {
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -1285,7 +4975,7 @@ mod tests {
         assert!(!languages.contains(&"fr".to_string()));
         assert!(languages.contains(&"fr-FR".to_string()));
 
-        let greeting_fr_fr = store.get_translation("greeting", "fr-FR").await.unwrap();
+        let greeting_fr_fr = store.get_translation("greeting", "fr-FR").await.ok();
         assert!(greeting_fr_fr.is_some());
         assert_eq!(greeting_fr_fr.unwrap().value.as_deref(), Some("Bonjour"));
 
@@ -1357,12 +5047,13 @@ mod tests {
                 path: path_str.clone(),
                 key: "greeting".to_string(),
                 language: "en".to_string(),
+                force: false,
             }))
             .await
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -1374,11 +5065,11 @@ mod tests {
         store.reload().await.expect("reload store");
 
         // Verify the translation was deleted
-        let greeting_en = store.get_translation("greeting", "en").await.unwrap();
+        let greeting_en = store.get_translation("greeting", "en").await.ok();
         assert!(greeting_en.is_none());
 
         // Verify the French translation still exists
-        let greeting_fr = store.get_translation("greeting", "fr").await.unwrap();
+        let greeting_fr = store.get_translation("greeting", "fr").await.ok();
         assert!(greeting_fr.is_some());
         assert_eq!(greeting_fr.unwrap().value.as_deref(), Some("Bonjour"));
 
@@ -1402,6 +5093,7 @@ mod tests {
                 path: path_str.clone(),
                 key: "nonexistent_key".to_string(),
                 language: "en".to_string(),
+                force: false,
             }))
             .await;
 
@@ -1446,6 +5138,7 @@ mod tests {
                 path: path_str.clone(),
                 key: "greeting".to_string(),
                 language: "fr".to_string(),
+                force: false,
             }))
             .await;
 
@@ -1491,12 +5184,13 @@ mod tests {
                 path: path_str.clone(),
                 key: key_with_format.to_string(),
                 language: "en".to_string(),
+                force: false,
             }))
             .await
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -1508,7 +5202,7 @@ mod tests {
         store.reload().await.expect("reload store");
 
         // Verify the translation was deleted
-        let translation = store.get_translation(key_with_format, "en").await.unwrap();
+        let translation = store.get_translation(key_with_format, "en").await.ok();
         assert!(translation.is_none());
 
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
@@ -1580,12 +5274,13 @@ mod tests {
                     path: path_str.clone(),
                     key: key.to_string(),
                     language: "en".to_string(),
+                    force: false,
                 }))
                 .await
                 .expect("tool success");
 
             // Verify success message
-            let content = result.content.as_ref().expect("content available");
+            let content = &result.content;
             let text = content
                 .first()
                 .expect("content entry")
@@ -1597,7 +5292,7 @@ mod tests {
             store.reload().await.expect("reload store");
 
             // Verify the translation was deleted
-            let translation = store.get_translation(key, "en").await.unwrap();
+            let translation = store.get_translation(key, "en").await.ok();
             assert!(
                 translation.is_none(),
                 "Translation should be deleted for key: {}",
@@ -1645,12 +5340,13 @@ mod tests {
                 path: path_str.clone(),
                 key: "single_lang_key".to_string(),
                 language: "en".to_string(),
+                force: false,
             }))
             .await
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -1703,12 +5399,13 @@ mod tests {
                 path: path_str.clone(),
                 key: unicode_key.to_string(),
                 language: "en".to_string(),
+                force: false,
             }))
             .await
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -1720,7 +5417,7 @@ mod tests {
         store.reload().await.expect("reload store");
 
         // Verify the translation was deleted
-        let translation = store.get_translation(unicode_key, "en").await.unwrap();
+        let translation = store.get_translation(unicode_key, "en").await.ok();
         assert!(translation.is_none());
 
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
@@ -1769,12 +5466,13 @@ mod tests {
                     path: path_str.clone(),
                     key: key.to_string(),
                     language: "en".to_string(),
+                    force: false,
                 }))
                 .await
                 .expect("tool success");
 
             // Verify success message
-            let content = result.content.as_ref().expect("content available");
+            let content = &result.content;
             let text = content
                 .first()
                 .expect("content entry")
@@ -1786,7 +5484,7 @@ mod tests {
             store.reload().await.expect("reload store");
 
             // Verify the translation was deleted
-            let translation = store.get_translation(key, "en").await.unwrap();
+            let translation = store.get_translation(key, "en").await.ok();
             assert!(
                 translation.is_none(),
                 "Translation should be deleted for whitespace key: {:?}",
@@ -1847,12 +5545,16 @@ mod tests {
                 state: None,
                 variations: Some(variations),
                 substitutions: None,
+                force: false,
+                allow_create: None,
+                comment: None,
+                insert_position: InsertPosition::End,
             }))
             .await
             .expect("upsert with variations");
 
         // Verify the translation with variations exists
-        let translation = store.get_translation("item_count", "en").await.unwrap();
+        let translation = store.get_translation("item_count", "en").await.ok();
         assert!(translation.is_some());
         let translation = translation.unwrap();
         assert!(translation.variations.contains_key("plural"));
@@ -1863,12 +5565,13 @@ mod tests {
                 path: path_str.clone(),
                 key: "item_count".to_string(),
                 language: "en".to_string(),
+                force: false,
             }))
             .await
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -1880,7 +5583,7 @@ mod tests {
         store.reload().await.expect("reload store");
 
         // Verify the translation was deleted
-        let translation = store.get_translation("item_count", "en").await.unwrap();
+        let translation = store.get_translation("item_count", "en").await.ok();
         assert!(translation.is_none());
 
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
@@ -1925,15 +5628,16 @@ mod tests {
                 state: None,
                 variations: None,
                 substitutions: Some(substitutions),
+                force: false,
+                allow_create: None,
+                comment: None,
+                insert_position: InsertPosition::End,
             }))
             .await
             .expect("upsert with substitutions");
 
         // Verify the translation with substitutions exists
-        let translation = store
-            .get_translation("download_progress", "en")
-            .await
-            .unwrap();
+        let translation = store.get_translation("download_progress", "en").await.ok();
         assert!(translation.is_some());
         let translation = translation.unwrap();
         assert!(translation.substitutions.contains_key("count"));
@@ -1944,12 +5648,13 @@ mod tests {
                 path: path_str.clone(),
                 key: "download_progress".to_string(),
                 language: "en".to_string(),
+                force: false,
             }))
             .await
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -1961,10 +5666,7 @@ mod tests {
         store.reload().await.expect("reload store");
 
         // Verify the translation was deleted
-        let translation = store
-            .get_translation("download_progress", "en")
-            .await
-            .unwrap();
+        let translation = store.get_translation("download_progress", "en").await.ok();
         assert!(translation.is_none());
 
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
@@ -2032,6 +5734,10 @@ mod tests {
                 state: None,
                 variations: Some(variations),
                 substitutions: Some(substitutions),
+                force: false,
+                allow_create: None,
+                comment: None,
+                insert_position: InsertPosition::End,
             }))
             .await
             .expect("upsert complex translation");
@@ -2040,7 +5746,7 @@ mod tests {
         let translation = store
             .get_translation("complex_download_status", "en")
             .await
-            .unwrap();
+            .ok();
         assert!(translation.is_some());
         let translation = translation.unwrap();
         assert!(translation.variations.contains_key("plural"));
@@ -2052,12 +5758,13 @@ mod tests {
                 path: path_str.clone(),
                 key: "complex_download_status".to_string(),
                 language: "en".to_string(),
+                force: false,
             }))
             .await
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -2072,7 +5779,7 @@ mod tests {
         let translation = store
             .get_translation("complex_download_status", "en")
             .await
-            .unwrap();
+            .ok();
         assert!(translation.is_none());
 
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
@@ -2105,7 +5812,7 @@ mod tests {
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -2163,7 +5870,7 @@ mod tests {
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -2235,7 +5942,7 @@ mod tests {
             .expect("tool success");
 
         // Verify success message
-        let content = result.content.as_ref().expect("content available");
+        let content = &result.content;
         let text = content
             .first()
             .expect("content entry")
@@ -2251,6 +5958,258 @@ mod tests {
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
     }
 
+    #[tokio::test]
+    async fn validate_catalog_tool_groups_findings_by_severity() {
+        let path = fresh_store_path("validate_catalog_tool");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        for (language, value) in [("en", "%lld items"), ("fr", "%d éléments")] {
+            store
+                .upsert_translation(
+                    "items",
+                    language,
+                    TranslationUpdate::from_value_state(Some(value.into()), None),
+                )
+                .await
+                .expect("seed translation");
+        }
+        let server = XcStringsMcpServer::new(manager.clone());
+
+        let result = server
+            .validate_catalog(Parameters(ValidateCatalogParams {
+                path: path_str.clone(),
+                rules: Some(vec![QaRule::Placeholders]),
+            }))
+            .await
+            .expect("tool success");
+        let payload = parse_json(&result);
+        assert_eq!(payload["rules"], serde_json::json!(["placeholders"]));
+        assert_eq!(payload["summary"]["errors"].as_u64(), Some(1));
+        assert_eq!(payload["errors"][0]["key"].as_str(), Some("items"));
+        assert_eq!(payload["errors"][0]["language"].as_str(), Some("fr"));
+        assert!(payload["warnings"].as_array().expect("warnings").is_empty());
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn check_release_readiness_fails_with_details() {
+        let path = fresh_store_path("release_readiness");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        for (key, language, value) in [
+            ("items", "en", "%lld items"),
+            ("items", "de", "%lld Elemente"),
+            ("title", "en", "Title"),
+        ] {
+            store
+                .upsert_translation(
+                    key,
+                    language,
+                    TranslationUpdate::from_value_state(Some(value.into()), None),
+                )
+                .await
+                .expect("seed translation");
+        }
+        let server = XcStringsMcpServer::new(manager.clone());
+        let check = |thresholds: &[(&str, f64)]| {
+            server.check_release_readiness(Parameters(CheckReleaseReadinessParams {
+                path: path_str.clone(),
+                thresholds: thresholds
+                    .iter()
+                    .map(|(language, percentage)| (language.to_string(), *percentage))
+                    .collect(),
+            }))
+        };
+
+        let result = check(&[("de", 50.0)]).await.expect("ready");
+        assert_eq!(parse_json(&result)["ready"], true);
+
+        let err = check(&[]).await.expect_err("de is half translated");
+        assert!(
+            err.message.contains("de is 50.0% complete"),
+            "{}",
+            err.message
+        );
+        let data = err.data.expect("readiness data");
+        assert_eq!(data["ready"], false);
+        assert_eq!(data["languages"][0]["language"], "de");
+
+        assert!(check(&[("de", 150.0)]).await.is_err());
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn get_translation_tool_reports_what_is_missing() {
+        let path = fresh_store_path("get_translation_missing");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        for (key, language) in [("greeting", "en"), ("farewell", "fr")] {
+            store
+                .upsert_translation(
+                    key,
+                    language,
+                    TranslationUpdate::from_value_state(Some("Hi".into()), None),
+                )
+                .await
+                .expect("seed translation");
+        }
+        let server = XcStringsMcpServer::new(manager.clone());
+        let get = |key: &str, language: &str| {
+            server.get_translation(Parameters(GetTranslationParams {
+                path: path_str.clone(),
+                key: key.to_string(),
+                language: language.to_string(),
+            }))
+        };
+
+        let found = get("greeting", "en").await.expect("translation");
+        assert_eq!(parse_json(&found)["value"], "Hi");
+        for (key, language, missing) in [
+            ("unknown", "en", "key"),
+            ("greeting", "de", "language"),
+            ("greeting", "fr", "translation"),
+        ] {
+            let error = get(key, language).await.unwrap_err();
+            assert_eq!(error.code, rmcp::model::ErrorCode::RESOURCE_NOT_FOUND);
+            assert_eq!(error.data.expect("error data")["missing"], missing);
+        }
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn snapshot_tools_roll_back_changes() {
+        let path = fresh_store_path("snapshot_tools");
+        let path_str = path.to_str().unwrap().to_string();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        store
+            .upsert_translation(
+                "greeting",
+                "fr",
+                TranslationUpdate::from_value_state(Some("Bonjour".into()), None),
+            )
+            .await
+            .expect("seed translation");
+        let server = XcStringsMcpServer::new(manager.clone());
+        let params = |label: &str| {
+            Parameters(SnapshotParams {
+                path: path_str.clone(),
+                label: label.to_string(),
+            })
+        };
+
+        let saved = server
+            .snapshot_catalog(params("checkpoint"))
+            .await
+            .expect("snapshot");
+        let payload = parse_json(&saved);
+        assert_eq!(payload["snapshots"], serde_json::json!(["checkpoint"]));
+
+        store.delete_key("greeting").await.expect("delete key");
+        server
+            .restore_snapshot(params("checkpoint"))
+            .await
+            .expect("restore");
+        assert_eq!(store.keys().await, vec!["greeting".to_string()]);
+
+        let missing = server.restore_snapshot(params("nope")).await.unwrap_err();
+        assert_eq!(missing.code, rmcp::model::ErrorCode::RESOURCE_NOT_FOUND);
+        let invalid = server.snapshot_catalog(params("../x")).await.unwrap_err();
+        assert_eq!(invalid.code, rmcp::model::ErrorCode::INVALID_PARAMS);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn format_catalog_tool_checks_then_rewrites() {
+        let path = fresh_store_path("format_catalog");
+        let path_str = path.to_str().unwrap().to_string();
+        // Compact JSON with keys out of order, as a hand edit might leave it.
+        std::fs::write(
+            &path,
+            r#"{"sourceLanguage":"en","strings":{"b":{"comment":"B"},"a":{"comment":"A"}},"version":"1.0"}"#,
+        )
+        .expect("write catalog");
+        let manager = Arc::new(
+            XcStringsStoreManager::new(None)
+                .await
+                .expect("create manager"),
+        );
+        let server = XcStringsMcpServer::new(manager);
+        let params = |sort_keys: bool, dry_run: bool| {
+            Parameters(FormatCatalogParams {
+                path: path_str.clone(),
+                sort_keys,
+                dry_run,
+            })
+        };
+
+        let check = parse_json(
+            &server
+                .format_catalog(params(true, true))
+                .await
+                .expect("check"),
+        );
+        assert_eq!(check["changed"], true);
+        assert_eq!(check["sorted"], true);
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .starts_with(r#"{"sourceLanguage""#));
+
+        let applied = parse_json(
+            &server
+                .format_catalog(params(true, false))
+                .await
+                .expect("format"),
+        );
+        assert_eq!(applied["changed"], true);
+        let formatted = std::fs::read_to_string(&path).unwrap();
+        assert!(formatted.find("\"a\"").unwrap() < formatted.find("\"b\"").unwrap());
+
+        let again = parse_json(
+            &server
+                .format_catalog(params(true, true))
+                .await
+                .expect("recheck"),
+        );
+        assert_eq!(again["changed"], false);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
     #[tokio::test]
     async fn list_untranslated_tool_returns_untranslated_keys() {
         let path = fresh_store_path("list_untranslated_tool");
@@ -2319,6 +6278,7 @@ mod tests {
         let result = server
             .list_untranslated(Parameters(ListUntranslatedParams {
                 path: path_str.clone(),
+                include_do_not_translate: false,
             }))
             .await
             .expect("tool success");
@@ -2358,6 +6318,7 @@ mod tests {
         let result = server
             .list_untranslated(Parameters(ListUntranslatedParams {
                 path: path_str.clone(),
+                include_do_not_translate: false,
             }))
             .await
             .expect("tool success");
@@ -2430,6 +6391,7 @@ mod tests {
         let result = server
             .list_untranslated(Parameters(ListUntranslatedParams {
                 path: path_str.clone(),
+                include_do_not_translate: false,
             }))
             .await
             .expect("tool success");
@@ -0,0 +1,182 @@
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Translator context kept on the last line of a key's comment as `name=value` pairs
+/// separated by `;`, such as `max_length=20; screenshot=login.png; tone=formal`. Xcode shows
+/// the line as part of the comment, so it survives round trips through Xcode untouched.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyMetadata {
+    /// Longest translation, in characters, that fits the UI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u32>,
+    /// Screenshot showing where the string appears.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub screenshot: Option<String>,
+    /// Register translators should use, such as `formal` or `playful`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tone: Option<String>,
+    /// Any other pairs, in comment order.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    #[schemars(with = "std::collections::BTreeMap<String, String>")]
+    pub extra: IndexMap<String, String>,
+}
+
+impl KeyMetadata {
+    /// Parses a metadata line, or returns `None` when any segment isn't a `name=value` pair.
+    fn parse_line(line: &str) -> Option<Self> {
+        let mut metadata = KeyMetadata::default();
+        let mut pairs = 0;
+        for segment in line.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            let (name, value) = segment.split_once('=')?;
+            let (name, value) = (name.trim(), value.trim());
+            if !is_name(name) {
+                return None;
+            }
+            pairs += 1;
+            match (name, value.parse::<u32>()) {
+                ("max_length", Ok(max_length)) => metadata.max_length = Some(max_length),
+                ("screenshot", _) => metadata.screenshot = Some(value.to_string()),
+                ("tone", _) => metadata.tone = Some(value.to_string()),
+                _ => {
+                    metadata.extra.insert(name.to_string(), value.to_string());
+                }
+            }
+        }
+        (pairs > 0).then_some(metadata)
+    }
+
+    /// Formats the metadata as a comment line, or `None` when it's empty.
+    fn to_line(&self) -> Option<String> {
+        let mut pairs = Vec::new();
+        if let Some(max_length) = self.max_length {
+            pairs.push(format!("max_length={max_length}"));
+        }
+        if let Some(screenshot) = &self.screenshot {
+            pairs.push(format!("screenshot={screenshot}"));
+        }
+        if let Some(tone) = &self.tone {
+            pairs.push(format!("tone={tone}"));
+        }
+        pairs.extend(
+            self.extra
+                .iter()
+                .map(|(name, value)| format!("{name}={value}")),
+        );
+        (!pairs.is_empty()).then(|| pairs.join("; "))
+    }
+
+    /// Explains why the metadata can't be written to a comment, if it can't.
+    pub fn check(&self) -> Result<(), String> {
+        for name in self.extra.keys() {
+            if !is_name(name) {
+                return Err(format!(
+                    "metadata name '{name}' must start with a letter and contain only letters, \
+                     digits, and underscores"
+                ));
+            }
+            if matches!(name.as_str(), "max_length" | "screenshot" | "tone") {
+                return Err(format!("set '{name}' with its own field, not in extra"));
+            }
+        }
+        let values = self
+            .screenshot
+            .iter()
+            .chain(&self.tone)
+            .chain(self.extra.values());
+        for value in values {
+            if value.trim().is_empty() || value.contains([';', '\n', '\r']) {
+                return Err(format!(
+                    "metadata value '{value}' must be non-empty and can't contain ';' or line breaks"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Splits a comment into its free text and the metadata on its last line, if any.
+pub fn parse_comment(comment: &str) -> (&str, Option<KeyMetadata>) {
+    let trimmed = comment.trim_end();
+    let (text, line) = match trimmed.rsplit_once('\n') {
+        Some((text, line)) => (text.trim_end(), line),
+        None => ("", trimmed),
+    };
+    match KeyMetadata::parse_line(line) {
+        Some(metadata) => (text, Some(metadata)),
+        None => (comment, None),
+    }
+}
+
+/// Replaces the metadata line of `comment`, keeping its free text. Returns `None` when
+/// neither text nor metadata is left.
+pub fn with_metadata(comment: Option<&str>, metadata: &KeyMetadata) -> Option<String> {
+    let text = comment
+        .map(|comment| parse_comment(comment).0)
+        .unwrap_or("");
+    match (text.is_empty(), metadata.to_line()) {
+        (true, line) => line,
+        (false, None) => Some(text.to_string()),
+        (false, Some(line)) => Some(format!("{text}\n{line}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_metadata_from_the_last_comment_line() {
+        let (text, metadata) = parse_comment(
+            "Login button\nmax_length=20; screenshot=login.png; tone=formal; owner=auth",
+        );
+        assert_eq!(text, "Login button");
+        let metadata = metadata.unwrap();
+        assert_eq!(metadata.max_length, Some(20));
+        assert_eq!(metadata.screenshot.as_deref(), Some("login.png"));
+        assert_eq!(metadata.tone.as_deref(), Some("formal"));
+        assert_eq!(metadata.extra["owner"], "auth");
+
+        assert_eq!(parse_comment("tone=casual").0, "");
+        assert!(parse_comment("Shown when x = 0; rarely").1.is_none());
+        assert!(parse_comment("Title of the login screen").1.is_none());
+    }
+
+    #[test]
+    fn rewrites_only_the_metadata_line() {
+        let metadata = KeyMetadata {
+            max_length: Some(12),
+            ..KeyMetadata::default()
+        };
+        assert_eq!(
+            with_metadata(Some("Login button\ntone=formal"), &metadata).as_deref(),
+            Some("Login button\nmax_length=12")
+        );
+        assert_eq!(
+            with_metadata(None, &metadata).as_deref(),
+            Some("max_length=12")
+        );
+        assert_eq!(
+            with_metadata(Some("Login button\ntone=formal"), &KeyMetadata::default()).as_deref(),
+            Some("Login button")
+        );
+        assert_eq!(
+            with_metadata(Some("tone=formal"), &KeyMetadata::default()),
+            None
+        );
+
+        let invalid = KeyMetadata {
+            tone: Some("formal; polite".to_string()),
+            ..KeyMetadata::default()
+        };
+        assert!(invalid.check().is_err());
+    }
+}
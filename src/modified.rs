@@ -0,0 +1,158 @@
+use std::{collections::BTreeMap, io, path::Path};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use utoipa::ToSchema;
+
+/// When each key and translation last changed through this server, kept in a sidecar JSON
+/// file next to the catalog since the `.xcstrings` format has no field for it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifiedIndex {
+    #[serde(default)]
+    pub keys: BTreeMap<String, KeyModified>,
+}
+
+/// Last-modified timestamps of one key, as RFC 3339 UTC timestamps.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyModified {
+    /// When anything about the key last changed: a translation, its comment, or its flags.
+    pub at: String,
+    /// When the translation into each language last changed.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub languages: BTreeMap<String, String>,
+}
+
+impl KeyModified {
+    /// Whether the key changed at or after `since`.
+    pub fn is_since(&self, since: DateTime<Utc>) -> bool {
+        is_since(&self.at, since)
+    }
+}
+
+/// The current time in the format the index stores.
+pub fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Whether the stored `timestamp` is at or after `since`; unreadable timestamps never are.
+pub fn is_since(timestamp: &str, since: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(timestamp).is_ok_and(|timestamp| timestamp >= since)
+}
+
+/// Reads a `modified_since` filter: an RFC 3339 timestamp, or a `YYYY-MM-DD` date meaning
+/// its start in UTC.
+pub fn parse_since(since: &str) -> Option<DateTime<Utc>> {
+    let since = since.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(since) {
+        return Some(timestamp.to_utc());
+    }
+    NaiveDate::parse_from_str(since, "%Y-%m-%d")
+        .ok()
+        .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+}
+
+impl ModifiedIndex {
+    /// Reads the sidecar at `path`; a missing file means nothing has been tracked yet.
+    pub async fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path).await {
+            Ok(raw) => serde_json::from_str(&raw).map_err(io::Error::other),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Writes the sidecar, or removes it once no key is tracked.
+    pub async fn save(&self, path: &Path) -> io::Result<()> {
+        if self.keys.is_empty() {
+            return match fs::remove_file(path).await {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            };
+        }
+        let mut raw = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        raw.push('\n');
+        fs::write(path, raw).await
+    }
+
+    pub fn get(&self, key: &str) -> Option<&KeyModified> {
+        self.keys.get(key)
+    }
+
+    /// Marks `key`, and its translation into `language` when given, as changed `at`.
+    pub fn touch(&mut self, key: &str, language: Option<&str>, at: &str) {
+        let entry = self.keys.entry(key.to_string()).or_default();
+        entry.at = at.to_string();
+        if let Some(language) = language {
+            entry.languages.insert(language.to_string(), at.to_string());
+        }
+    }
+
+    /// Marks `key` as changed `at` and forgets its translation into `language`.
+    pub fn remove_translation(&mut self, key: &str, language: &str, at: &str) {
+        self.touch(key, None, at);
+        if let Some(entry) = self.keys.get_mut(key) {
+            entry.languages.remove(language);
+        }
+    }
+
+    pub fn remove_key(&mut self, key: &str) {
+        self.keys.remove(key);
+    }
+
+    /// Moves the timestamps of `old_key` to `new_key`, marking it as changed `at`.
+    pub fn rename_key(&mut self, old_key: &str, new_key: &str, at: &str) {
+        let entry = self.keys.remove(old_key).unwrap_or_default();
+        self.keys.insert(new_key.to_string(), entry);
+        self.touch(new_key, None, at);
+    }
+
+    pub fn remove_language(&mut self, language: &str) {
+        for entry in self.keys.values_mut() {
+            entry.languages.remove(language);
+        }
+    }
+
+    pub fn rename_language(&mut self, old_language: &str, new_language: &str) {
+        for entry in self.keys.values_mut() {
+            if let Some(at) = entry.languages.remove(old_language) {
+                entry.languages.insert(new_language.to_string(), at);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tracks_keys_and_languages_and_round_trips() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("Localizable.modified.json");
+        let mut index = ModifiedIndex::load(&path).await.unwrap();
+        assert!(index.get("greeting").is_none());
+
+        index.touch("greeting", Some("fr"), "2026-03-01T10:00:00.000Z");
+        index.touch("greeting", None, "2026-03-02T10:00:00.000Z");
+        index.rename_language("fr", "fr-CA");
+        index.rename_key("greeting", "welcome", "2026-03-03T10:00:00.000Z");
+        index.save(&path).await.unwrap();
+
+        let index = ModifiedIndex::load(&path).await.unwrap();
+        let welcome = index.get("welcome").expect("renamed key");
+        assert_eq!(welcome.at, "2026-03-03T10:00:00.000Z");
+        assert_eq!(welcome.languages["fr-CA"], "2026-03-01T10:00:00.000Z");
+        assert!(welcome.is_since(parse_since("2026-03-03").unwrap()));
+        assert!(welcome.is_since(parse_since("2026-03-03T11:00:00+01:00").unwrap()));
+        assert!(!welcome.is_since(parse_since("2026-03-04").unwrap()));
+        assert!(parse_since("last week").is_none());
+
+        let mut index = index;
+        index.remove_key("welcome");
+        index.save(&path).await.unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,371 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::store::XcStringEntry;
+
+/// One RFC 6902 operation on a catalog's `strings` object. Paths are JSON Pointers whose
+/// first segment is a key, followed by the fields Xcode writes for it, such as
+/// `/greeting/localizations/de/stringUnit/value` or
+/// `/items/localizations/fr/variations/plural/one`. `~1` stands for `/` and `~0` for `~`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Sets the member at `path`, creating it or replacing what was there.
+    Add { path: String, value: Value },
+    /// Removes the member at `path`, which must exist.
+    Remove { path: String },
+    /// Replaces the member at `path`, which must exist.
+    Replace { path: String, value: Value },
+    /// Removes the member at `from` and adds it at `path`.
+    Move { from: String, path: String },
+    /// Adds a copy of the member at `from` at `path`.
+    Copy { from: String, path: String },
+    /// Fails the whole patch unless the member at `path` equals `value`.
+    Test { path: String, value: Value },
+}
+
+impl PatchOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Add { .. } => "add",
+            Self::Remove { .. } => "remove",
+            Self::Replace { .. } => "replace",
+            Self::Move { .. } => "move",
+            Self::Copy { .. } => "copy",
+            Self::Test { .. } => "test",
+        }
+    }
+
+    fn path(&self) -> &str {
+        match self {
+            Self::Add { path, .. }
+            | Self::Remove { path }
+            | Self::Replace { path, .. }
+            | Self::Move { path, .. }
+            | Self::Copy { path, .. }
+            | Self::Test { path, .. } => path,
+        }
+    }
+
+    fn from(&self) -> Option<&str> {
+        match self {
+            Self::Move { from, .. } | Self::Copy { from, .. } => Some(from),
+            _ => None,
+        }
+    }
+}
+
+/// The keys `operations` read or write, in first-mention order. Fails when a pointer is
+/// malformed or addresses the whole `strings` object.
+pub fn keys(operations: &[PatchOperation]) -> Result<Vec<String>, String> {
+    let mut keys: Vec<String> = Vec::new();
+    for (index, operation) in operations.iter().enumerate() {
+        for path in operation.from().into_iter().chain([operation.path()]) {
+            let tokens =
+                parse_pointer(path).map_err(|reason| describe(index, operation, reason))?;
+            let Some(key) = tokens.into_iter().next() else {
+                return Err(describe(
+                    index,
+                    operation,
+                    "paths must start with a key; the whole catalog can't be patched".to_string(),
+                ));
+            };
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+    Ok(keys)
+}
+
+/// The pointer to `key` itself, escaping any `/` and `~` in it.
+pub fn key_path(key: &str) -> String {
+    format!("/{}", escape(key))
+}
+
+/// Applies `operations` in order to `target`, the JSON of the keys they touch. Stops at the
+/// first one that fails, leaving `target` partly patched.
+pub fn apply(target: &mut Value, operations: &[PatchOperation]) -> Result<(), String> {
+    for (index, operation) in operations.iter().enumerate() {
+        apply_one(target, operation).map_err(|reason| describe(index, operation, reason))?;
+    }
+    Ok(())
+}
+
+/// Reads a patched key back into a catalog entry, refusing values of the wrong type and
+/// fields Xcode doesn't write.
+pub fn entry(key: &str, value: &Value) -> Result<XcStringEntry, String> {
+    let entry: XcStringEntry =
+        serde_json::from_value(value.clone()).map_err(|err| format!("key '{key}': {err}"))?;
+    match unknown_field(value, Shape::Entry, &key_path(key)) {
+        Some(path) => Err(format!("'{path}' isn't part of the string catalog format")),
+        None => Ok(entry),
+    }
+}
+
+fn describe(index: usize, operation: &PatchOperation, reason: String) -> String {
+    format!(
+        "operation {index} ({} {}): {reason}",
+        operation.name(),
+        operation.path()
+    )
+}
+
+fn apply_one(target: &mut Value, operation: &PatchOperation) -> Result<(), String> {
+    match operation {
+        PatchOperation::Add { path, value } => add(target, &parse_pointer(path)?, value.clone()),
+        PatchOperation::Remove { path } => remove(target, &parse_pointer(path)?).map(drop),
+        PatchOperation::Replace { path, value } => {
+            let tokens = parse_pointer(path)?;
+            remove(target, &tokens)?;
+            add(target, &tokens, value.clone())
+        }
+        PatchOperation::Move { from, path } => {
+            let (from, tokens) = (parse_pointer(from)?, parse_pointer(path)?);
+            if tokens.len() > from.len() && tokens.starts_with(&from) {
+                return Err("can't move a member into itself".to_string());
+            }
+            let value = remove(target, &from)?;
+            add(target, &tokens, value)
+        }
+        PatchOperation::Copy { from, path } => {
+            let value = get(target, &parse_pointer(from)?)?.clone();
+            add(target, &parse_pointer(path)?, value)
+        }
+        PatchOperation::Test { path, value } => {
+            if get(target, &parse_pointer(path)?)? != value {
+                return Err("test failed; the current value differs".to_string());
+            }
+            Ok(())
+        }
+    }
+}
+
+fn parse_pointer(path: &str) -> Result<Vec<String>, String> {
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = path.strip_prefix('/') else {
+        return Err(format!(
+            "'{path}' is not a JSON Pointer; it must start with '/'"
+        ));
+    };
+    Ok(rest
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+pub(crate) fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn get<'a>(target: &'a Value, tokens: &[String]) -> Result<&'a Value, String> {
+    tokens.iter().try_fold(target, |value, token| {
+        value
+            .as_object()
+            .and_then(|object| object.get(token))
+            .ok_or_else(|| missing(tokens))
+    })
+}
+
+/// The object holding the member `tokens` points at, and the member's name.
+fn parent<'a>(
+    target: &'a mut Value,
+    tokens: &'a [String],
+) -> Result<(&'a mut Map<String, Value>, &'a str), String> {
+    let (last, parents) = tokens
+        .split_last()
+        .ok_or_else(|| "the whole catalog can't be patched".to_string())?;
+    let mut value = target;
+    for (depth, token) in parents.iter().enumerate() {
+        value = value
+            .as_object_mut()
+            .and_then(|object| object.get_mut(token))
+            .ok_or_else(|| missing(&tokens[..=depth]))?;
+    }
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| format!("'{}' is not an object", pointer(parents)))?;
+    Ok((object, last))
+}
+
+fn add(target: &mut Value, tokens: &[String], value: Value) -> Result<(), String> {
+    let (object, name) = parent(target, tokens)?;
+    object.insert(name.to_string(), value);
+    Ok(())
+}
+
+fn remove(target: &mut Value, tokens: &[String]) -> Result<Value, String> {
+    let (object, name) = parent(target, tokens)?;
+    object.shift_remove(name).ok_or_else(|| missing(tokens))
+}
+
+fn missing(tokens: &[String]) -> String {
+    format!("'{}' doesn't exist", pointer(tokens))
+}
+
+fn pointer(tokens: &[String]) -> String {
+    tokens
+        .iter()
+        .map(|token| format!("/{}", escape(token)))
+        .collect()
+}
+
+/// The parts of a catalog entry, by the fields Xcode writes for each.
+#[derive(Clone, Copy)]
+enum Shape {
+    Entry,
+    Localization,
+    Substitution,
+    StringUnit,
+}
+
+/// What a field of a [`Shape`] holds.
+enum Field {
+    Scalar,
+    Nested(Shape),
+    /// An object of names, such as languages, each holding the shape.
+    Named(Shape),
+    /// Selectors such as `plural`, each holding cases that are localizations.
+    Variations,
+}
+
+impl Shape {
+    fn field(self, name: &str) -> Option<Field> {
+        let field = match (self, name) {
+            (Self::Entry, "comment" | "extractionState" | "shouldTranslate") => Field::Scalar,
+            (Self::Entry, "localizations") => Field::Named(Self::Localization),
+            (Self::Localization | Self::Substitution, "stringUnit") => {
+                Field::Nested(Self::StringUnit)
+            }
+            (Self::Localization | Self::Substitution, "variations") => Field::Variations,
+            (Self::Localization, "substitutions") => Field::Named(Self::Substitution),
+            (Self::Substitution, "argNum" | "formatSpecifier") => Field::Scalar,
+            (Self::StringUnit, "state" | "value") => Field::Scalar,
+            _ => return None,
+        };
+        Some(field)
+    }
+}
+
+/// The first field under `value` that a catalog entry doesn't have. Types are left to
+/// deserialization.
+fn unknown_field(value: &Value, shape: Shape, path: &str) -> Option<String> {
+    let object = value.as_object()?;
+    object.iter().find_map(|(name, value)| {
+        let path = format!("{path}/{}", escape(name));
+        match shape.field(name) {
+            None => Some(path),
+            Some(Field::Scalar) => None,
+            Some(Field::Nested(shape)) => unknown_field(value, shape, &path),
+            Some(Field::Named(shape)) => named(value, &path, |value, path| {
+                unknown_field(value, shape, path)
+            }),
+            Some(Field::Variations) => named(value, &path, |cases, path| {
+                named(cases, path, |case, path| {
+                    unknown_field(case, Shape::Localization, path)
+                })
+            }),
+        }
+    })
+}
+
+/// Runs `check` on each member of the object `value`, returning the first finding.
+fn named(
+    value: &Value,
+    path: &str,
+    check: impl Fn(&Value, &str) -> Option<String>,
+) -> Option<String> {
+    value
+        .as_object()?
+        .iter()
+        .find_map(|(name, value)| check(value, &format!("{path}/{}", escape(name))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn operations(raw: Value) -> Vec<PatchOperation> {
+        serde_json::from_value(raw).expect("operations")
+    }
+
+    #[test]
+    fn applies_operations_in_order() {
+        let mut target = json!({
+            "greeting": { "localizations": { "de": { "stringUnit": { "state": "translated", "value": "Hallo" } } } },
+            "a/b": { "comment": "Slash" },
+        });
+        let ops = operations(json!([
+            { "op": "test", "path": "/greeting/localizations/de/stringUnit/value", "value": "Hallo" },
+            { "op": "copy", "from": "/greeting/localizations/de", "path": "/greeting/localizations/de-AT" },
+            { "op": "replace", "path": "/greeting/localizations/de/stringUnit/value", "value": "Guten Tag" },
+            { "op": "move", "from": "/a~1b", "path": "/farewell" },
+            { "op": "add", "path": "/farewell/shouldTranslate", "value": false },
+            { "op": "remove", "path": "/farewell/comment" },
+        ]));
+        assert_eq!(keys(&ops).unwrap(), ["greeting", "a/b", "farewell"]);
+
+        apply(&mut target, &ops).unwrap();
+        assert_eq!(
+            target,
+            json!({
+                "greeting": { "localizations": {
+                    "de": { "stringUnit": { "state": "translated", "value": "Guten Tag" } },
+                    "de-AT": { "stringUnit": { "state": "translated", "value": "Hallo" } },
+                } },
+                "farewell": { "shouldTranslate": false },
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_bad_pointers_failed_tests_and_unknown_fields() {
+        let mut target = json!({ "greeting": { "comment": "Hi" } });
+        let err = keys(&operations(json!([{ "op": "remove", "path": "" }]))).unwrap_err();
+        assert!(err.contains("whole catalog"), "{err}");
+        let err = keys(&operations(json!([{ "op": "remove", "path": "greeting" }]))).unwrap_err();
+        assert!(err.contains("must start with '/'"), "{err}");
+
+        let err = apply(
+            &mut target,
+            &operations(json!([
+                { "op": "remove", "path": "/greeting/comment" },
+                { "op": "test", "path": "/greeting/comment", "value": "Hi" },
+            ])),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "operation 1 (test /greeting/comment): '/greeting/comment' doesn't exist"
+        );
+        let err = apply(
+            &mut target,
+            &operations(
+                json!([{ "op": "add", "path": "/greeting/localizations/de", "value": {} }]),
+            ),
+        )
+        .unwrap_err();
+        assert!(
+            err.contains("'/greeting/localizations' doesn't exist"),
+            "{err}"
+        );
+
+        let err = entry(
+            "greeting",
+            &json!({ "localizations": { "de": { "stringUnit": { "value": "Hallo", "stat": "new" } } } }),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "'/greeting/localizations/de/stringUnit/stat' isn't part of the string catalog format"
+        );
+        assert!(entry("greeting", &json!({ "shouldTranslate": "no" })).is_err());
+        assert!(entry("greeting", &json!({ "comment": "Hi", "localizations": {} })).is_ok());
+        let err = entry("greeting", &json!({ "localisations": {} })).unwrap_err();
+        assert!(err.contains("'/greeting/localisations'"), "{err}");
+    }
+}
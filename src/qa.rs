@@ -0,0 +1,1360 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    spelling::{self, Dictionary},
+    store::{XcLocalization, XcStringsFile},
+};
+
+/// QA checks that can be selected individually when validating a catalog.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum QaRule {
+    /// Plural variations cover the categories each language needs.
+    Plural,
+    /// Format specifiers in translations match the source string.
+    Placeholders,
+    /// Glossary terms in the source use the approved translation.
+    Terminology,
+    /// Whitespace, punctuation, empty values, and untranslated copies.
+    Lint,
+    /// Keys that differ only by case, surrounding whitespace, or Unicode normalization.
+    Keys,
+    /// Keys with the same source text translated differently.
+    Consistency,
+    /// Words in translations missing from the language's dictionary, when one is configured.
+    Spelling,
+}
+
+impl QaRule {
+    pub const ALL: [QaRule; 7] = [
+        QaRule::Plural,
+        QaRule::Placeholders,
+        QaRule::Terminology,
+        QaRule::Lint,
+        QaRule::Keys,
+        QaRule::Consistency,
+        QaRule::Spelling,
+    ];
+}
+
+/// What upserts do when a translation's format specifiers don't match its source text,
+/// from the `placeholderValidation` config setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaceholderValidation {
+    /// Saves the translation and reports a warning per mismatch.
+    #[default]
+    Warn,
+    /// Refuses the edit.
+    Strict,
+    /// Saves the translation without checking.
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Finding {
+    pub rule: QaRule,
+    pub severity: Severity,
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Variation or substitution path inside the localization, e.g. `plural.one`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema)]
+pub struct QaSummary {
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+}
+
+/// Findings grouped by severity, most severe first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QaReport {
+    pub rules: Vec<QaRule>,
+    pub summary: QaSummary,
+    pub errors: Vec<Finding>,
+    pub warnings: Vec<Finding>,
+    pub info: Vec<Finding>,
+}
+
+impl QaReport {
+    fn push(&mut self, finding: Finding) {
+        match finding.severity {
+            Severity::Error => {
+                self.summary.errors += 1;
+                self.errors.push(finding);
+            }
+            Severity::Warning => {
+                self.summary.warnings += 1;
+                self.warnings.push(finding);
+            }
+            Severity::Info => {
+                self.summary.info += 1;
+                self.info.push(finding);
+            }
+        }
+    }
+
+    /// Keeps only the findings matching `keep`, updating the summary to match.
+    pub fn retain(&mut self, keep: impl Fn(&Finding) -> bool) {
+        self.errors.retain(&keep);
+        self.warnings.retain(&keep);
+        self.info.retain(&keep);
+        self.summary = QaSummary {
+            errors: self.errors.len(),
+            warnings: self.warnings.len(),
+            info: self.info.len(),
+        };
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
+}
+
+/// Inputs for QA rules that depend on project configuration.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct QaOptions {
+    /// Source term → language → approved translation.
+    #[serde(default)]
+    pub glossary: BTreeMap<String, BTreeMap<String, String>>,
+    /// Language → completion percentage it must reach before release, e.g. `{"de": 95}`,
+    /// checked by `check_release_readiness`.
+    #[serde(default, rename = "minCompletion")]
+    pub min_completion: BTreeMap<String, f64>,
+    /// Language → Hunspell `.dic` file, with its `.aff` file next to it, for the `spelling`
+    /// rule, e.g. `{"de": "dictionaries/de_DE.dic"}`.
+    #[serde(default)]
+    pub dictionaries: BTreeMap<String, PathBuf>,
+    /// The dictionaries read by [`QaOptions::load_dictionaries`], by language.
+    #[serde(skip)]
+    pub spelling: BTreeMap<String, Arc<Dictionary>>,
+}
+
+impl QaOptions {
+    /// Reads every configured dictionary, resolving relative paths against `base`, so a bad
+    /// path fails at startup rather than on the first validation.
+    pub fn load_dictionaries(&mut self, base: &Path) -> anyhow::Result<()> {
+        for (language, path) in &self.dictionaries {
+            let dictionary = Dictionary::load(&base.join(path))?;
+            self.spelling.insert(language.clone(), Arc::new(dictionary));
+        }
+        Ok(())
+    }
+
+    /// The dictionary for `language`, or for its base language, so `de` covers `de-CH`.
+    fn dictionary(&self, language: &str) -> Option<&Dictionary> {
+        let base = language.split(['-', '_']).next().unwrap_or(language);
+        self.spelling
+            .get(language)
+            .or_else(|| self.spelling.get(base))
+            .map(Arc::as_ref)
+    }
+}
+
+/// Whether a catalog can ship: every required language is complete enough and has no QA
+/// errors.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseReadiness {
+    pub ready: bool,
+    pub languages: Vec<LanguageReadiness>,
+    /// QA errors in the required languages and in keys as a whole, such as duplicates.
+    pub errors: Vec<Finding>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageReadiness {
+    pub language: String,
+    /// Percentage of translatable keys with a value.
+    pub completion: f64,
+    /// Percentage the language must reach.
+    pub required: f64,
+    pub qa_errors: usize,
+    pub ready: bool,
+}
+
+impl ReleaseReadiness {
+    /// One line per reason the catalog isn't ready, e.g. `de is 80.0% complete, below 95%`.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for language in &self.languages {
+            if language.completion < language.required {
+                problems.push(format!(
+                    "{} is {:.1}% complete, below {}%",
+                    language.language, language.completion, language.required
+                ));
+            }
+            if language.qa_errors > 0 {
+                problems.push(format!(
+                    "{} has {} QA error(s)",
+                    language.language, language.qa_errors
+                ));
+            }
+        }
+        let key_errors = self
+            .errors
+            .iter()
+            .filter(|finding| finding.language.is_none())
+            .count();
+        if key_errors > 0 {
+            problems.push(format!("{key_errors} QA error(s) in keys"));
+        }
+        problems
+    }
+}
+
+/// Checks each language of `thresholds` against its completion in `percentages` and the
+/// errors in `report`. With no thresholds, every language in `percentages` must be 100%
+/// complete. A language missing from the catalog counts as 0% complete.
+pub fn release_readiness(
+    report: &QaReport,
+    percentages: &BTreeMap<String, f64>,
+    thresholds: &BTreeMap<String, f64>,
+) -> ReleaseReadiness {
+    let thresholds = if thresholds.is_empty() {
+        percentages
+            .keys()
+            .map(|language| (language.clone(), 100.0))
+            .collect()
+    } else {
+        thresholds.clone()
+    };
+    let errors: Vec<Finding> = report
+        .errors
+        .iter()
+        .filter(|finding| {
+            finding
+                .language
+                .as_ref()
+                .is_none_or(|language| thresholds.contains_key(language))
+        })
+        .cloned()
+        .collect();
+    let languages: Vec<LanguageReadiness> = thresholds
+        .into_iter()
+        .map(|(language, required)| {
+            let completion = percentages.get(&language).copied().unwrap_or_default();
+            let qa_errors = errors
+                .iter()
+                .filter(|finding| finding.language.as_ref() == Some(&language))
+                .count();
+            LanguageReadiness {
+                ready: completion >= required && qa_errors == 0,
+                language,
+                completion,
+                required,
+                qa_errors,
+            }
+        })
+        .collect();
+    ReleaseReadiness {
+        ready: errors.is_empty() && languages.iter().all(|language| language.ready),
+        languages,
+        errors,
+    }
+}
+
+/// Runs the selected rules (all of them when `rules` is empty) over every entry in the catalog.
+pub fn validate(doc: &XcStringsFile, rules: &[QaRule], options: &QaOptions) -> QaReport {
+    let rules: BTreeSet<QaRule> = if rules.is_empty() {
+        QaRule::ALL.into_iter().collect()
+    } else {
+        rules.iter().copied().collect()
+    };
+    let mut report = QaReport {
+        rules: rules.iter().copied().collect(),
+        ..Default::default()
+    };
+
+    let source_language = doc.source_language.as_str();
+    for (key, entry) in &doc.strings {
+        let source_units = entry
+            .localizations
+            .get(source_language)
+            .map(units)
+            .unwrap_or_default();
+        // Keys without a source localization use the key itself as the source text.
+        let source_text = source_units.get("").cloned().unwrap_or_else(|| key.clone());
+
+        let mut findings = Vec::new();
+        for (language, localization) in &entry.localizations {
+            let is_source = language == source_language;
+            let translatable = entry.should_translate.unwrap_or(true);
+            let context = UnitContext {
+                key,
+                language,
+                source_units: &source_units,
+                source_text: &source_text,
+            };
+
+            if rules.contains(&QaRule::Plural) {
+                let source = entry
+                    .localizations
+                    .get(source_language)
+                    .filter(|_| !is_source);
+                check_plurals(&context, localization, source, &mut findings);
+            }
+            if is_source || !translatable {
+                if rules.contains(&QaRule::Lint) {
+                    check_empty_values(&context, localization, &mut findings);
+                }
+                continue;
+            }
+            if rules.contains(&QaRule::Placeholders) {
+                check_placeholders(&context, localization, &mut findings);
+            }
+            if rules.contains(&QaRule::Terminology) {
+                check_terminology(&context, localization, &options.glossary, &mut findings);
+            }
+            if rules.contains(&QaRule::Lint) {
+                check_empty_values(&context, localization, &mut findings);
+                check_lint(&context, localization, &mut findings);
+            }
+            if rules.contains(&QaRule::Spelling) {
+                check_spelling(&context, localization, options, &mut findings);
+            }
+        }
+        for finding in findings {
+            report.push(finding);
+        }
+    }
+    if rules.contains(&QaRule::Keys) {
+        for finding in check_key_hygiene(doc.strings.keys()) {
+            report.push(finding);
+        }
+    }
+    if rules.contains(&QaRule::Consistency) {
+        for finding in check_consistency(doc) {
+            report.push(finding);
+        }
+    }
+
+    report
+}
+
+struct UnitContext<'a> {
+    key: &'a str,
+    language: &'a str,
+    source_units: &'a BTreeMap<String, String>,
+    source_text: &'a str,
+}
+
+impl UnitContext<'_> {
+    fn finding(
+        &self,
+        rule: QaRule,
+        severity: Severity,
+        location: &str,
+        message: String,
+    ) -> Finding {
+        Finding {
+            rule,
+            severity,
+            key: self.key.to_string(),
+            language: Some(self.language.to_string()),
+            location: (!location.is_empty()).then(|| location.to_string()),
+            message,
+        }
+    }
+
+    /// Source text to compare a translated unit against: the same variation case when the source
+    /// has it, then the source's `other` case of that selector, then the top-level source value.
+    fn source_for(&self, location: &str) -> &str {
+        if let Some(value) = self.source_units.get(location) {
+            return value;
+        }
+        if let Some((selector, _)) = location.rsplit_once('.') {
+            if let Some(value) = self.source_units.get(&format!("{selector}.other")) {
+                return value;
+            }
+        }
+        self.source_text
+    }
+}
+
+/// Flattens a localization into `location → value` pairs; the top-level value has location "".
+fn units(localization: &XcLocalization) -> BTreeMap<String, String> {
+    let mut collected = BTreeMap::new();
+    collect_units(localization, "", &mut collected);
+    collected
+}
+
+fn collect_units(localization: &XcLocalization, prefix: &str, out: &mut BTreeMap<String, String>) {
+    if let Some(value) = localization
+        .string_unit
+        .as_ref()
+        .and_then(|unit| unit.value.clone())
+    {
+        out.insert(prefix.to_string(), value);
+    }
+    collect_variation_units(&localization.variations, prefix, out);
+    for (name, substitution) in &localization.substitutions {
+        let location = join_location(prefix, &format!("substitutions.{name}"));
+        if let Some(value) = substitution
+            .string_unit
+            .as_ref()
+            .and_then(|unit| unit.value.clone())
+        {
+            out.insert(location.clone(), value);
+        }
+        collect_variation_units(&substitution.variations, &location, out);
+    }
+}
+
+fn collect_variation_units(
+    variations: &indexmap::IndexMap<String, indexmap::IndexMap<String, XcLocalization>>,
+    prefix: &str,
+    out: &mut BTreeMap<String, String>,
+) {
+    for (selector, cases) in variations {
+        for (case, nested) in cases {
+            collect_units(
+                nested,
+                &join_location(prefix, &format!("{selector}.{case}")),
+                out,
+            );
+        }
+    }
+}
+
+fn join_location(prefix: &str, segment: &str) -> String {
+    if prefix.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{prefix}.{segment}")
+    }
+}
+
+/// Every `plural` variation set in a localization, keyed by where it sits.
+fn plural_sets(localization: &XcLocalization) -> BTreeMap<String, BTreeSet<String>> {
+    let mut sets = BTreeMap::new();
+    collect_plural_sets(&localization.variations, "", &mut sets);
+    for (name, substitution) in &localization.substitutions {
+        collect_plural_sets(
+            &substitution.variations,
+            &format!("substitutions.{name}"),
+            &mut sets,
+        );
+    }
+    sets
+}
+
+fn collect_plural_sets(
+    variations: &indexmap::IndexMap<String, indexmap::IndexMap<String, XcLocalization>>,
+    prefix: &str,
+    out: &mut BTreeMap<String, BTreeSet<String>>,
+) {
+    for (selector, cases) in variations {
+        let location = join_location(prefix, selector);
+        if selector == "plural" {
+            out.insert(location.clone(), cases.keys().cloned().collect());
+        }
+        for (case, nested) in cases {
+            collect_plural_sets(&nested.variations, &format!("{location}.{case}"), out);
+        }
+    }
+}
+
+/// CLDR cardinal plural categories used by Xcode string catalogs for a language.
+pub fn plural_categories(language: &str) -> &'static [&'static str] {
+    let base = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or(language)
+        .to_ascii_lowercase();
+    match base.as_str() {
+        "ja" | "ko" | "zh" | "th" | "vi" | "id" | "ms" | "lo" | "km" | "my" | "yue" => &["other"],
+        "ru" | "uk" | "be" | "pl" | "lt" | "cs" | "sk" => &["one", "few", "many", "other"],
+        "fr" | "es" | "it" | "pt" => &["one", "many", "other"],
+        "hr" | "sr" | "bs" | "ro" => &["one", "few", "other"],
+        "sl" => &["one", "two", "few", "other"],
+        "he" => &["one", "two", "other"],
+        "lv" => &["zero", "one", "other"],
+        "ga" => &["one", "two", "few", "many", "other"],
+        "ar" | "cy" => &["zero", "one", "two", "few", "many", "other"],
+        _ => &["one", "other"],
+    }
+}
+
+fn check_plurals(
+    context: &UnitContext<'_>,
+    localization: &XcLocalization,
+    source: Option<&XcLocalization>,
+    findings: &mut Vec<Finding>,
+) {
+    let required = plural_categories(context.language);
+    let sets = plural_sets(localization);
+    for (location, cases) in &sets {
+        if !cases.contains("other") {
+            findings.push(context.finding(
+                QaRule::Plural,
+                Severity::Error,
+                location,
+                "Plural variation is missing the required 'other' case".to_string(),
+            ));
+        }
+        let missing: Vec<&str> = required
+            .iter()
+            .copied()
+            .filter(|category| *category != "other" && !cases.contains(*category))
+            .collect();
+        if !missing.is_empty() {
+            findings.push(context.finding(
+                QaRule::Plural,
+                Severity::Warning,
+                location,
+                format!(
+                    "Plural variation is missing {} for '{}': {}",
+                    if missing.len() == 1 {
+                        "a category"
+                    } else {
+                        "categories"
+                    },
+                    context.language,
+                    missing.join(", ")
+                ),
+            ));
+        }
+        let unused: Vec<&str> = cases
+            .iter()
+            .map(String::as_str)
+            .filter(|case| !required.contains(case))
+            .collect();
+        if !unused.is_empty() {
+            findings.push(context.finding(
+                QaRule::Plural,
+                Severity::Info,
+                location,
+                format!(
+                    "'{}' does not use plural {}: {}",
+                    context.language,
+                    if unused.len() == 1 {
+                        "category"
+                    } else {
+                        "categories"
+                    },
+                    unused.join(", ")
+                ),
+            ));
+        }
+    }
+
+    let Some(source) = source else {
+        return;
+    };
+    let has_value = localization
+        .string_unit
+        .as_ref()
+        .and_then(|unit| unit.value.as_deref())
+        .is_some_and(|value| !value.is_empty());
+    for location in plural_sets(source).keys() {
+        if !sets.contains_key(location) && has_value {
+            findings.push(context.finding(
+                QaRule::Plural,
+                Severity::Warning,
+                location,
+                "Source string varies by plural but this translation does not".to_string(),
+            ));
+        }
+    }
+}
+
+/// Extracts printf-style and `%#@name@` specifiers, normalized so equivalent forms compare equal
+/// (`%1$@` and `%@` match; `%i` and `%d` match; length modifiers such as `ll` are kept).
+pub fn format_specifiers(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut specifiers = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        if chars[index] != '%' {
+            index += 1;
+            continue;
+        }
+        let mut cursor = index + 1;
+        if chars.get(cursor) == Some(&'%') {
+            index = cursor + 1;
+            continue;
+        }
+        if chars.get(cursor) == Some(&'#') && chars.get(cursor + 1) == Some(&'@') {
+            if let Some(end) = chars[cursor + 2..].iter().position(|c| *c == '@') {
+                let name: String = chars[cursor + 2..cursor + 2 + end].iter().collect();
+                specifiers.push(format!("#@{name}@"));
+                index = cursor + 3 + end;
+                continue;
+            }
+        }
+
+        // Optional positional argument: digits followed by '$'.
+        let digits = chars[cursor..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        if digits > 0 && chars.get(cursor + digits) == Some(&'$') {
+            cursor += digits + 1;
+        }
+        while chars
+            .get(cursor)
+            .is_some_and(|c| matches!(c, '-' | '+' | '#' | '0' | '\''))
+        {
+            cursor += 1;
+        }
+        while chars
+            .get(cursor)
+            .is_some_and(|c| c.is_ascii_digit() || *c == '.' || *c == '*')
+        {
+            cursor += 1;
+        }
+        let mut length = String::new();
+        while let Some(c) = chars
+            .get(cursor)
+            .filter(|c| matches!(c, 'h' | 'l' | 'q' | 'z' | 't' | 'j' | 'L'))
+        {
+            length.push(*c);
+            cursor += 1;
+        }
+        match chars.get(cursor) {
+            Some(conversion) if "@dDiuUxXoOfFeEgGcCsSpaA".contains(*conversion) => {
+                let conversion = if *conversion == 'i' { 'd' } else { *conversion };
+                specifiers.push(format!("{length}{conversion}"));
+                index = cursor + 1;
+            }
+            _ => index += 1,
+        }
+    }
+    specifiers
+}
+
+/// Format specifier mismatches between a translation of `key` and its `source` localization,
+/// or the key itself when there is none, as the `placeholders` rule reports them.
+pub fn placeholder_findings(
+    key: &str,
+    language: &str,
+    source: Option<&XcLocalization>,
+    localization: &XcLocalization,
+) -> Vec<Finding> {
+    let source_units = source.map(units).unwrap_or_default();
+    let source_text = source_units
+        .get("")
+        .cloned()
+        .unwrap_or_else(|| key.to_string());
+    let context = UnitContext {
+        key,
+        language,
+        source_units: &source_units,
+        source_text: &source_text,
+    };
+    let mut findings = Vec::new();
+    check_placeholders(&context, localization, &mut findings);
+    findings
+}
+
+fn check_placeholders(
+    context: &UnitContext<'_>,
+    localization: &XcLocalization,
+    findings: &mut Vec<Finding>,
+) {
+    for (location, value) in units(localization) {
+        if value.is_empty() {
+            continue;
+        }
+        let source = context.source_for(&location);
+        let mut expected = format_specifiers(source);
+        let mut actual = format_specifiers(&value);
+        expected.sort();
+        actual.sort();
+        if expected != actual {
+            findings.push(context.finding(
+                QaRule::Placeholders,
+                Severity::Error,
+                &location,
+                format!(
+                    "Format specifiers [{}] do not match the source [{}]",
+                    actual.join(", "),
+                    expected.join(", ")
+                ),
+            ));
+        }
+    }
+}
+
+fn check_terminology(
+    context: &UnitContext<'_>,
+    localization: &XcLocalization,
+    glossary: &BTreeMap<String, BTreeMap<String, String>>,
+    findings: &mut Vec<Finding>,
+) {
+    if glossary.is_empty() {
+        return;
+    }
+    for (location, value) in units(localization) {
+        if value.is_empty() {
+            continue;
+        }
+        let source = context.source_for(&location).to_lowercase();
+        let translated = value.to_lowercase();
+        for (term, translations) in glossary {
+            let Some(expected) = translations.get(context.language) else {
+                continue;
+            };
+            if contains_word(&source, &term.to_lowercase())
+                && !translated.contains(&expected.to_lowercase())
+            {
+                findings.push(context.finding(
+                    QaRule::Terminology,
+                    Severity::Warning,
+                    &location,
+                    format!("Glossary term '{term}' should be translated as '{expected}'"),
+                ));
+            }
+        }
+    }
+}
+
+/// Flags words missing from the language's dictionary. Words of the source text and of the
+/// glossary are taken as correct, which covers product names and copied placeholders, as are
+/// acronyms and words with capitals inside, such as `iCloud`.
+fn check_spelling(
+    context: &UnitContext<'_>,
+    localization: &XcLocalization,
+    options: &QaOptions,
+    findings: &mut Vec<Finding>,
+) {
+    let Some(dictionary) = options.dictionary(context.language) else {
+        return;
+    };
+    let glossary: BTreeSet<String> = options
+        .glossary
+        .iter()
+        .flat_map(|(term, translations)| std::iter::once(term).chain(translations.values()))
+        .flat_map(|text| spelling::words(text))
+        .map(str::to_lowercase)
+        .collect();
+    for (location, value) in units(localization) {
+        let source: BTreeSet<String> = spelling::words(context.source_for(&location))
+            .into_iter()
+            .map(str::to_lowercase)
+            .collect();
+        let mut misspelled: Vec<&str> = Vec::new();
+        for word in spelling::words(&value) {
+            let lowercase = word.to_lowercase();
+            let inner_capital = word.chars().skip(1).any(char::is_uppercase);
+            if inner_capital
+                || word.chars().count() < 2
+                || source.contains(&lowercase)
+                || glossary.contains(&lowercase)
+                || misspelled.contains(&word)
+                || dictionary.check(word)
+            {
+                continue;
+            }
+            misspelled.push(word);
+        }
+        if misspelled.is_empty() {
+            continue;
+        }
+        findings.push(context.finding(
+            QaRule::Spelling,
+            Severity::Warning,
+            &location,
+            format!(
+                "Possibly misspelled: {}",
+                misspelled
+                    .iter()
+                    .map(|word| format!("'{word}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ));
+    }
+}
+
+/// Substring match that only accepts whole words.
+pub(crate) fn contains_word(haystack: &str, needle: &str) -> bool {
+    if needle.is_empty() {
+        return false;
+    }
+    haystack.match_indices(needle).any(|(start, _)| {
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + needle.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+fn check_empty_values(
+    context: &UnitContext<'_>,
+    localization: &XcLocalization,
+    findings: &mut Vec<Finding>,
+) {
+    let Some(unit) = localization.string_unit.as_ref() else {
+        return;
+    };
+    let empty = unit.value.as_deref().is_none_or(str::is_empty);
+    if empty && unit.state.as_deref() == Some("translated") {
+        findings.push(context.finding(
+            QaRule::Lint,
+            Severity::Warning,
+            "",
+            "Marked as translated but the value is empty".to_string(),
+        ));
+    }
+}
+
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ':', ';', '!', '?', '…', '。', '！', '？', '：'];
+
+fn check_lint(
+    context: &UnitContext<'_>,
+    localization: &XcLocalization,
+    findings: &mut Vec<Finding>,
+) {
+    for (location, value) in units(localization) {
+        if value.is_empty() {
+            continue;
+        }
+        let source = context.source_for(&location);
+
+        if value.starts_with(char::is_whitespace) != source.starts_with(char::is_whitespace)
+            || value.ends_with(char::is_whitespace) != source.ends_with(char::is_whitespace)
+        {
+            findings.push(context.finding(
+                QaRule::Lint,
+                Severity::Warning,
+                &location,
+                "Leading or trailing whitespace differs from the source".to_string(),
+            ));
+        }
+
+        if value.contains("  ") && !source.contains("  ") {
+            findings.push(context.finding(
+                QaRule::Lint,
+                Severity::Info,
+                &location,
+                "Contains double spaces not present in the source".to_string(),
+            ));
+        }
+
+        let source_end = source.trim_end().chars().next_back();
+        let value_end = value.trim_end().chars().next_back();
+        let source_punct = source_end.filter(|c| TRAILING_PUNCTUATION.contains(c));
+        let value_punct = value_end.filter(|c| TRAILING_PUNCTUATION.contains(c));
+        if source_punct.is_some() != value_punct.is_some() {
+            findings.push(context.finding(
+                QaRule::Lint,
+                Severity::Info,
+                &location,
+                "Trailing punctuation differs from the source".to_string(),
+            ));
+        }
+
+        if value == source && value.chars().filter(|c| c.is_alphabetic()).count() > 3 {
+            findings.push(context.finding(
+                QaRule::Lint,
+                Severity::Info,
+                &location,
+                "Translation is identical to the source".to_string(),
+            ));
+        }
+    }
+}
+
+/// Combining marks with the Latin letters they precompose with, as `(mark, bases, composed)`
+/// where the nth base and mark form the nth composed letter. Enough to match keys typed with
+/// decomposed accents (`e` + U+0301) against precomposed ones (`é`).
+const COMPOSITIONS: &[(char, &str, &str)] = &[
+    ('\u{300}', "AEIOUaeiou", "ÀÈÌÒÙàèìòù"),
+    (
+        '\u{301}',
+        "AEIOUYaeiouyCcLlNnRrSsZz",
+        "ÁÉÍÓÚÝáéíóúýĆćĹĺŃńŔŕŚśŹź",
+    ),
+    (
+        '\u{302}',
+        "AEIOUaeiouCcGgHhJjSsWwYy",
+        "ÂÊÎÔÛâêîôûĈĉĜĝĤĥĴĵŜŝŴŵŶŷ",
+    ),
+    ('\u{303}', "ANOanoIiUu", "ÃÑÕãñõĨĩŨũ"),
+    ('\u{304}', "AaEeIiOoUu", "ĀāĒēĪīŌōŪū"),
+    ('\u{306}', "AaEeGgIiOoUu", "ĂăĔĕĞğĬĭŎŏŬŭ"),
+    ('\u{307}', "CcEeGgIZz", "ĊċĖėĠġİŻż"),
+    ('\u{308}', "AEIOUaeiouyY", "ÄËÏÖÜäëïöüÿŸ"),
+    ('\u{30a}', "AaUu", "ÅåŮů"),
+    ('\u{30b}', "OoUu", "ŐőŰű"),
+    ('\u{30c}', "CcDdEeLlNnRrSsTtZz", "ČčĎďĚěĽľŇňŘřŠšŤťŽž"),
+    ('\u{327}', "CcGgKkLlNnRrSsTt", "ÇçĢģĶķĻļŅņŖŗŞşŢţ"),
+    ('\u{328}', "AaEeIiUu", "ĄąĘęĮįŲų"),
+];
+
+/// Replaces base letter + combining mark pairs from [`COMPOSITIONS`] with the precomposed letter.
+fn compose(text: &str) -> String {
+    let mut composed = String::with_capacity(text.len());
+    for ch in text.chars() {
+        let letter = COMPOSITIONS
+            .iter()
+            .find(|(mark, _, _)| *mark == ch)
+            .and_then(|(_, bases, letters)| {
+                let base = composed.chars().next_back()?;
+                letters.chars().nth(bases.chars().position(|b| b == base)?)
+            });
+        match letter {
+            Some(letter) => {
+                composed.pop();
+                composed.push(letter);
+            }
+            None => composed.push(ch),
+        }
+    }
+    composed
+}
+
+/// Flags every key that collides with an earlier one once surrounding whitespace, case, and
+/// accent composition are ignored, since such keys almost always resolve to the wrong entry.
+fn check_key_hygiene<'a>(keys: impl Iterator<Item = &'a String>) -> Vec<Finding> {
+    let mut first_seen: BTreeMap<String, &str> = BTreeMap::new();
+    let mut findings = Vec::new();
+    for key in keys {
+        let trimmed = key.trim();
+        let folded = compose(trimmed).to_lowercase();
+        let Some(&earlier) = first_seen.get(&folded) else {
+            first_seen.insert(folded, key);
+            continue;
+        };
+
+        let earlier_trimmed = earlier.trim();
+        let mut differences = Vec::new();
+        let padding = |text: &'a str| {
+            let trimmed = text.trim();
+            let start = text.len() - text.trim_start().len();
+            (&text[..start], &text[start + trimmed.len()..])
+        };
+        if padding(key) != padding(earlier) {
+            differences.push("surrounding whitespace");
+        }
+        if compose(trimmed) != compose(earlier_trimmed) {
+            differences.push("case");
+        }
+        if trimmed.to_lowercase() != earlier_trimmed.to_lowercase() {
+            differences.push("Unicode normalization");
+        }
+        findings.push(Finding {
+            rule: QaRule::Keys,
+            severity: Severity::Warning,
+            key: key.clone(),
+            language: None,
+            location: None,
+            message: format!(
+                "Differs from key '{earlier}' only by {}",
+                differences.join(" and ")
+            ),
+        });
+    }
+    findings
+}
+
+/// Flags translations that differ from the one most keys with the same source text use in
+/// that language, the most repeated source texts first. Only plain values are compared, and
+/// keys marked `shouldTranslate: false` are skipped.
+fn check_consistency(doc: &XcStringsFile) -> Vec<Finding> {
+    // (source text, language) → translation → keys using it, in catalog order.
+    let mut groups: BTreeMap<(String, String), indexmap::IndexMap<String, Vec<String>>> =
+        BTreeMap::new();
+    for (key, entry) in &doc.strings {
+        if entry.should_translate == Some(false) {
+            continue;
+        }
+        let value = |language: &str| {
+            entry
+                .localizations
+                .get(language)
+                .and_then(|localization| localization.string_unit.as_ref())
+                .and_then(|unit| unit.value.as_deref())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let source = match entry.localizations.get(&doc.source_language) {
+            Some(_) => value(&doc.source_language),
+            None => Some(key.trim()),
+        };
+        let Some(source) = source.filter(|source| !source.is_empty()) else {
+            continue;
+        };
+        for language in entry.localizations.keys() {
+            if *language == doc.source_language {
+                continue;
+            }
+            let Some(translation) = value(language) else {
+                continue;
+            };
+            groups
+                .entry((source.to_string(), language.clone()))
+                .or_default()
+                .entry(translation.to_string())
+                .or_default()
+                .push(key.clone());
+        }
+    }
+
+    let mut groups: Vec<_> = groups
+        .into_iter()
+        .filter(|(_, translations)| translations.len() > 1)
+        .collect();
+    groups.sort_by_key(|(_, translations)| {
+        std::cmp::Reverse(translations.values().map(Vec::len).sum::<usize>())
+    });
+    let mut findings = Vec::new();
+    for ((source, language), translations) in groups {
+        // The first of the most used translations, so ties go to the earliest key.
+        let most = translations
+            .values()
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default();
+        let (usual, usual_keys) = translations
+            .iter()
+            .find(|(_, keys)| keys.len() == most)
+            .expect("a most used translation");
+        let others = if most == 1 {
+            format!("key '{}'", usual_keys[0])
+        } else {
+            format!("{most} other keys, such as '{}'", usual_keys[0])
+        };
+        for (translation, keys) in &translations {
+            if translation == usual {
+                continue;
+            }
+            for key in keys {
+                findings.push(Finding {
+                    rule: QaRule::Consistency,
+                    severity: Severity::Warning,
+                    key: key.clone(),
+                    language: Some(language.clone()),
+                    location: None,
+                    message: format!(
+                        "'{source}' is translated as '{translation}' here but as '{usual}' in {others}"
+                    ),
+                });
+            }
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::XcStringEntry;
+    use serde_json::json;
+
+    fn catalog(entries: serde_json::Value) -> XcStringsFile {
+        let mut doc = XcStringsFile::default();
+        for (key, entry) in entries.as_object().expect("entries object") {
+            let entry: XcStringEntry = serde_json::from_value(entry.clone()).expect("entry");
+            doc.strings.insert(key.clone(), entry);
+        }
+        doc
+    }
+
+    fn unit(value: &str) -> serde_json::Value {
+        json!({ "stringUnit": { "state": "translated", "value": value } })
+    }
+
+    #[test]
+    fn release_readiness_needs_completion_and_no_errors() {
+        let doc = catalog(json!({
+            "items": {
+                "localizations": {
+                    "en": unit("%lld items"),
+                    "fr": unit("%d éléments"),
+                    "de": unit("%lld Elemente")
+                }
+            },
+            "title": {
+                "localizations": { "en": unit("Title"), "fr": unit("Titre") }
+            }
+        }));
+        let report = validate(&doc, &[], &QaOptions::default());
+        let percentages: BTreeMap<String, f64> = [("en", 100.0), ("fr", 100.0), ("de", 50.0)]
+            .map(|(language, percentage)| (language.to_string(), percentage))
+            .into();
+        let thresholds = |pairs: &[(&str, f64)]| -> BTreeMap<String, f64> {
+            pairs
+                .iter()
+                .map(|(language, percentage)| (language.to_string(), *percentage))
+                .collect()
+        };
+
+        let readiness = release_readiness(&report, &percentages, &thresholds(&[("de", 50.0)]));
+        assert!(readiness.ready, "{readiness:?}");
+        assert!(readiness.errors.is_empty());
+
+        let readiness = release_readiness(
+            &report,
+            &percentages,
+            &thresholds(&[("de", 90.0), ("fr", 100.0), ("ja", 0.0)]),
+        );
+        assert!(!readiness.ready);
+        let verdicts: Vec<(&str, bool, usize)> = readiness
+            .languages
+            .iter()
+            .map(|language| {
+                (
+                    language.language.as_str(),
+                    language.ready,
+                    language.qa_errors,
+                )
+            })
+            .collect();
+        assert_eq!(
+            verdicts,
+            [("de", false, 0), ("fr", false, 1), ("ja", true, 0)]
+        );
+
+        assert_eq!(
+            readiness.problems(),
+            ["de is 50.0% complete, below 90%", "fr has 1 QA error(s)"]
+        );
+
+        let readiness = release_readiness(&report, &percentages, &BTreeMap::new());
+        assert_eq!(readiness.languages.len(), 3);
+        assert!(!readiness.ready);
+    }
+
+    #[test]
+    fn extracts_normalized_format_specifiers() {
+        assert_eq!(
+            format_specifiers("%1$@ has %lld items (%i%%) %#@files@"),
+            vec!["@", "lld", "d", "#@files@"]
+        );
+        assert!(format_specifiers("100% sure").is_empty());
+    }
+
+    #[test]
+    fn flags_placeholder_mismatches() {
+        let doc = catalog(json!({
+            "items": {
+                "localizations": {
+                    "en": unit("%lld items"),
+                    "fr": unit("%d éléments"),
+                    "de": unit("%lld Elemente")
+                }
+            }
+        }));
+        let report = validate(&doc, &[QaRule::Placeholders], &QaOptions::default());
+        assert_eq!(report.summary.errors, 1);
+        assert_eq!(report.errors[0].language.as_deref(), Some("fr"));
+        assert_eq!(report.errors[0].rule, QaRule::Placeholders);
+    }
+
+    #[test]
+    fn flags_missing_plural_categories() {
+        let doc = catalog(json!({
+            "files": {
+                "localizations": {
+                    "en": { "variations": { "plural": {
+                        "one": unit("%lld file"),
+                        "other": unit("%lld files")
+                    } } },
+                    "ru": { "variations": { "plural": {
+                        "one": unit("%lld файл"),
+                        "other": unit("%lld файлов")
+                    } } },
+                    "ja": { "variations": { "plural": {
+                        "one": unit("%lld ファイル"),
+                        "other": unit("%lld ファイル")
+                    } } },
+                    "fr": unit("%lld fichiers"),
+                    "es": { "variations": { "plural": {
+                        "one": unit("%lld archivo"),
+                        "other": unit("%lld archivos")
+                    } } }
+                }
+            }
+        }));
+        let report = validate(&doc, &[QaRule::Plural], &QaOptions::default());
+        let by_language = |findings: &[Finding], language: &str| {
+            findings
+                .iter()
+                .filter(|finding| finding.language.as_deref() == Some(language))
+                .count()
+        };
+        assert_eq!(by_language(&report.warnings, "ru"), 1);
+        assert!(report.warnings[0].message.contains("few, many"));
+        assert_eq!(by_language(&report.warnings, "fr"), 1);
+        let spanish: Vec<&str> = report
+            .warnings
+            .iter()
+            .filter(|finding| finding.language.as_deref() == Some("es"))
+            .map(|finding| finding.message.as_str())
+            .collect();
+        assert_eq!(
+            spanish,
+            ["Plural variation is missing a category for 'es': many"]
+        );
+        assert_eq!(by_language(&report.info, "ja"), 1);
+        assert!(report.errors.is_empty());
+    }
+
+    #[test]
+    fn checks_glossary_terms_and_lint() {
+        let doc = catalog(json!({
+            "settings.title": {
+                "localizations": {
+                    "en": unit("Open Settings"),
+                    "fr": unit("Ouvrir les paramètres "),
+                    "de": unit("Open Settings")
+                }
+            }
+        }));
+        let options = QaOptions {
+            glossary: BTreeMap::from([(
+                "Settings".to_string(),
+                BTreeMap::from([("fr".to_string(), "Réglages".to_string())]),
+            )]),
+            ..QaOptions::default()
+        };
+        let report = validate(&doc, &[], &options);
+        assert_eq!(report.rules, QaRule::ALL.to_vec());
+        assert!(report
+            .warnings
+            .iter()
+            .any(|finding| finding.rule == QaRule::Terminology
+                && finding.message.contains("Réglages")));
+        assert!(report.warnings.iter().any(
+            |finding| finding.rule == QaRule::Lint && finding.language.as_deref() == Some("fr")
+        ));
+        assert!(report
+            .info
+            .iter()
+            .any(|finding| finding.language.as_deref() == Some("de")
+                && finding.message.contains("identical")));
+    }
+
+    #[test]
+    fn flags_repeated_source_texts_translated_differently() {
+        let doc = catalog(json!({
+            "alert.cancel": { "localizations": { "en": unit("Cancel"), "de": unit("Abbrechen") } },
+            "order.cancel": { "localizations": { "en": unit("Cancel"), "de": unit("Stornieren") } },
+            "sheet.cancel": { "localizations": { "en": unit("Cancel"), "de": unit("Abbrechen ") } },
+            "form.save": { "localizations": { "en": unit("Save"), "de": unit("Sichern") } },
+            "menu.save": { "localizations": { "en": unit("Save"), "de": unit("Speichern") } },
+            "debug.cancel": {
+                "shouldTranslate": false,
+                "localizations": { "en": unit("Cancel"), "de": unit("Cancel") }
+            }
+        }));
+        let report = validate(&doc, &[QaRule::Consistency], &QaOptions::default());
+        let messages: Vec<_> = report
+            .warnings
+            .iter()
+            .map(|finding| (finding.key.as_str(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (
+                    "order.cancel",
+                    "'Cancel' is translated as 'Stornieren' here but as 'Abbrechen' in 2 other keys, such as 'alert.cancel'"
+                ),
+                (
+                    "menu.save",
+                    "'Save' is translated as 'Speichern' here but as 'Sichern' in key 'form.save'"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_words_missing_from_the_dictionary() {
+        let doc = catalog(json!({
+            "settings.title": {
+                "localizations": {
+                    "en": unit("Open %@ Settings in iCloud"),
+                    "de": unit("%@: Einstelungen in iCloud öffnen"),
+                    "de-CH": unit("%@ Einstellungen öffnen, ABC"),
+                    "fr": unit("Ouvrir les réglages")
+                }
+            },
+            "settings.help": {
+                "shouldTranslate": false,
+                "localizations": { "en": unit("Help"), "de": unit("Hilfe") }
+            }
+        }));
+        let dictionary = Dictionary::parse("SFX S Y 1\nSFX S 0 en .\n", "Einstellung/S\nöffnen\n");
+        let mut options = QaOptions {
+            glossary: BTreeMap::from([(
+                "Settings".to_string(),
+                BTreeMap::from([("de".to_string(), "Einstelungen".to_string())]),
+            )]),
+            spelling: BTreeMap::from([("de".to_string(), Arc::new(dictionary))]),
+            ..QaOptions::default()
+        };
+        let report = validate(&doc, &[QaRule::Spelling], &options);
+        assert!(report.is_clean(), "{report:?}");
+
+        options.glossary.clear();
+        let report = validate(&doc, &[QaRule::Spelling], &options);
+        let messages: Vec<_> = report
+            .warnings
+            .iter()
+            .map(|finding| (finding.language.as_deref(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            [(Some("de"), "Possibly misspelled: 'Einstelungen'")]
+        );
+    }
+
+    #[test]
+    fn flags_near_duplicate_keys() {
+        let doc = catalog(json!({
+            "login": { "localizations": { "en": unit("Log in") } },
+            "Login ": { "localizations": { "en": unit("Log In") } },
+            "cafe\u{301}.title": {},
+            "Café.title": {},
+            "logout": {}
+        }));
+        let report = validate(&doc, &[QaRule::Keys], &QaOptions::default());
+        let messages: Vec<_> = report
+            .warnings
+            .iter()
+            .map(|finding| (finding.key.as_str(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (
+                    "Login ",
+                    "Differs from key 'login' only by surrounding whitespace and case"
+                ),
+                (
+                    "Café.title",
+                    "Differs from key 'cafe\u{301}.title' only by case and Unicode normalization"
+                ),
+            ]
+        );
+    }
+}
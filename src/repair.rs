@@ -0,0 +1,312 @@
+use std::{collections::HashSet, fmt};
+
+use serde::{
+    de::{DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    Deserializer, Serialize,
+};
+use utoipa::ToSchema;
+
+use crate::{
+    patch::escape,
+    qa::format_specifiers,
+    store::{XcLocalization, XcSubstitution},
+};
+
+/// Structural problems found in a catalog file and whether they were fixed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairReport {
+    /// Whether the repaired catalog was written; false for a dry run or a clean file.
+    pub repaired: bool,
+    pub issues: Vec<RepairIssue>,
+}
+
+impl RepairReport {
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// One problem in a catalog file, located by a JSON Pointer into the file, such as
+/// `/strings/greeting/localizations/de/substitutions/count`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairIssue {
+    pub kind: RepairKind,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    pub message: String,
+    /// Whether repairing fixes it; otherwise it needs a manual edit.
+    pub fixable: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairKind {
+    /// A member appears twice in one object; parsing keeps the last one.
+    DuplicateKey,
+    /// A variation where the schema doesn't allow it, such as `device` inside `plural`.
+    InvalidVariation,
+    /// A substitution whose `argNum` is missing or names no argument of the string.
+    InvalidArgNum,
+    /// A substitution no `%#@name@` in the string refers to.
+    UnusedSubstitution,
+    /// A localization with neither a value nor variations.
+    EmptyLocalization,
+    /// A key with no localizations, comment, or flags.
+    EmptyEntry,
+}
+
+impl RepairIssue {
+    pub(crate) fn new(kind: RepairKind, tokens: &[&str], message: String) -> Self {
+        let key = (tokens.first() == Some(&"strings"))
+            .then(|| tokens.get(1).map(|key| key.to_string()))
+            .flatten();
+        let language = (tokens.get(2) == Some(&"localizations"))
+            .then(|| tokens.get(3).map(|language| language.to_string()))
+            .flatten();
+        let fixable = kind != RepairKind::InvalidArgNum;
+        Self {
+            kind,
+            path: tokens
+                .iter()
+                .map(|token| format!("/{}", escape(token)))
+                .collect(),
+            key,
+            language,
+            message,
+            fixable,
+        }
+    }
+}
+
+/// Members that appear more than once in the same object of `raw`, which parsing silently
+/// collapses into the last one. Malformed JSON reports nothing; parsing the catalog fails
+/// with a clearer error.
+pub fn duplicate_keys(raw: &str) -> Vec<RepairIssue> {
+    let mut found = Vec::new();
+    let mut deserializer = serde_json::Deserializer::from_str(raw);
+    let scan = DuplicateScan {
+        tokens: Vec::new(),
+        found: &mut found,
+    };
+    if scan.deserialize(&mut deserializer).is_err() {
+        return Vec::new();
+    }
+    found
+        .into_iter()
+        .map(|tokens| {
+            let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+            let message = format!(
+                "'{}' appears more than once; only the last one is kept",
+                tokens.last().copied().unwrap_or_default()
+            );
+            RepairIssue::new(RepairKind::DuplicateKey, &tokens, message)
+        })
+        .collect()
+}
+
+/// Walks a JSON document, recording the path of every repeated object member.
+struct DuplicateScan<'a> {
+    tokens: Vec<String>,
+    found: &'a mut Vec<Vec<String>>,
+}
+
+impl<'de> DeserializeSeed<'de> for DuplicateScan<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for DuplicateScan<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any JSON value")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut seen = HashSet::new();
+        while let Some(name) = map.next_key::<String>()? {
+            let mut tokens = self.tokens.clone();
+            tokens.push(name.clone());
+            if !seen.insert(name) {
+                self.found.push(tokens.clone());
+            }
+            map.next_value_seed(DuplicateScan {
+                tokens,
+                found: self.found,
+            })?;
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut index = 0;
+        loop {
+            let mut tokens = self.tokens.clone();
+            tokens.push(index.to_string());
+            let scan = DuplicateScan {
+                tokens,
+                found: self.found,
+            };
+            if seq.next_element_seed(scan)?.is_none() {
+                return Ok(());
+            }
+            index += 1;
+        }
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+/// Checks the substitutions of `key`'s localization into `language` against the strings
+/// that use them. Substitutions nothing refers to are removed, and a bad `argNum` is set to
+/// the argument the substitution's `%#@name@` stands for when the string settles it.
+pub fn repair_substitutions(
+    key: &str,
+    language: &str,
+    loc: &mut XcLocalization,
+) -> Vec<RepairIssue> {
+    let mut issues = Vec::new();
+    if loc.substitutions.is_empty() {
+        return issues;
+    }
+    let texts: Vec<String> = unit_texts(loc).into_iter().map(str::to_string).collect();
+    let arguments = texts
+        .iter()
+        .map(|text| format_specifiers(text).len())
+        .max()
+        .unwrap_or_default();
+
+    loc.substitutions.retain(|name, substitution| {
+        let tokens = [
+            "strings",
+            key,
+            "localizations",
+            language,
+            "substitutions",
+            name.as_str(),
+        ];
+        let token = format!("#@{name}@");
+        if !texts.iter().any(|text| text.contains(&token)) {
+            issues.push(RepairIssue::new(
+                RepairKind::UnusedSubstitution,
+                &tokens,
+                format!("No %#@{name}@ in the string uses this substitution"),
+            ));
+            return false;
+        }
+        if let Some(message) = arg_num_problem(substitution, arguments) {
+            let mut issue = RepairIssue::new(RepairKind::InvalidArgNum, &tokens, message);
+            if let Some(argument) = texts.iter().find_map(|text| argument_of(text, name)) {
+                substitution.arg_num = Some(argument as i64);
+                issue.fixable = true;
+                issue.message = format!("{}; %#@{name}@ is argument {argument}", issue.message);
+            }
+            issues.push(issue);
+        }
+        true
+    });
+    issues
+}
+
+/// The 1-based argument `%#@name@` stands for in `text`: its position among the format
+/// specifiers, or the one `%N$#@name@` names.
+fn argument_of(text: &str, name: &str) -> Option<usize> {
+    let token = format!("#@{name}@");
+    let before = &text[..text.find(&token)?];
+    if let Some(digits) = before.strip_suffix('$') {
+        let start = digits.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        return match digits[..start].ends_with('%') {
+            true => digits[start..].parse().ok(),
+            false => None,
+        };
+    }
+    if !before.ends_with('%') {
+        return None;
+    }
+    format_specifiers(text)
+        .iter()
+        .position(|specifier| *specifier == token)
+        .map(|position| position + 1)
+}
+
+fn arg_num_problem(substitution: &XcSubstitution, arguments: usize) -> Option<String> {
+    match substitution.arg_num {
+        None => Some("argNum is missing".to_string()),
+        Some(arg_num) if arg_num < 1 || arg_num as usize > arguments => Some(format!(
+            "argNum {arg_num} names no argument; the string has {arguments}"
+        )),
+        Some(_) => None,
+    }
+}
+
+/// The text of the localization's string unit and of each variation.
+fn unit_texts(loc: &XcLocalization) -> Vec<&str> {
+    let mut texts: Vec<&str> = loc
+        .string_unit
+        .iter()
+        .filter_map(|unit| unit.value.as_deref())
+        .collect();
+    for cases in loc.variations.values() {
+        for case in cases.values() {
+            texts.extend(unit_texts(case));
+        }
+    }
+    texts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_members_repeated_in_one_object() {
+        let raw = r#"{"strings":{"a":{"localizations":{"de":{},"fr":{},"de":{}}},"b":{},"a":{}},"list":[{"x":1,"x":2}]}"#;
+        let paths: Vec<_> = duplicate_keys(raw)
+            .into_iter()
+            .map(|issue| (issue.path, issue.key, issue.language))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                (
+                    "/strings/a/localizations/de".to_string(),
+                    Some("a".to_string()),
+                    Some("de".to_string())
+                ),
+                ("/strings/a".to_string(), Some("a".to_string()), None),
+                ("/list/0/x".to_string(), None, None),
+            ]
+        );
+        assert!(duplicate_keys("{not json").is_empty());
+    }
+}
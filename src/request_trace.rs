@@ -0,0 +1,53 @@
+use axum::{
+    body::Body,
+    http::{HeaderMap, HeaderName, Request},
+    Router,
+};
+use tower_http::{
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::{DefaultOnResponse, TraceLayer},
+    LatencyUnit,
+};
+use tracing::Level;
+
+/// Header carrying each HTTP request's correlation id. Clients may send their own; otherwise
+/// a UUID is assigned. The id is echoed on the response and recorded in audit entries.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Returns the correlation id assigned to a request.
+pub fn request_id(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Assigns request ids and logs method, path, status, and latency for every request.
+pub fn layer(router: Router) -> Router {
+    let header = HeaderName::from_static(REQUEST_ID_HEADER);
+    // Layers added last run first: assign the id, then open the span, then echo it back.
+    router
+        .layer(PropagateRequestIdLayer::new(header.clone()))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(|request: &Request<Body>| {
+                    let request_id = request
+                        .headers()
+                        .get(REQUEST_ID_HEADER)
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or_default();
+                    tracing::info_span!(
+                        "http",
+                        method = %request.method(),
+                        path = %request.uri().path(),
+                        request_id,
+                    )
+                })
+                .on_response(
+                    DefaultOnResponse::new()
+                        .level(Level::INFO)
+                        .latency_unit(LatencyUnit::Millis),
+                ),
+        )
+        .layer(SetRequestIdLayer::new(header, MakeRequestUuid))
+}
@@ -0,0 +1,133 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use crate::language::edit_distance;
+
+/// Edits a key may be from the requested one when the caller doesn't say.
+pub const DEFAULT_MAX_DISTANCE: usize = 3;
+/// Keys returned when the caller doesn't say.
+pub const DEFAULT_LIMIT: usize = 10;
+/// Share of words two keys must have in common to match regardless of edit distance, so
+/// `button.login` finds `login.button`.
+const MIN_TOKEN_OVERLAP: f64 = 0.5;
+
+/// An existing key close to the one asked for.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarKey {
+    pub key: String,
+    /// Case-insensitive edits between the two keys, counting a swap of adjacent letters as one.
+    pub distance: usize,
+    /// Words the keys share, as a share of the words in either, from `0` to `1`.
+    pub token_overlap: f64,
+}
+
+/// The keys within `max_distance` edits of `key` or sharing most of its words, closest first.
+pub fn rank<'a>(
+    key: &str,
+    keys: impl IntoIterator<Item = &'a String>,
+    max_distance: usize,
+    limit: usize,
+) -> Vec<SimilarKey> {
+    let wanted = key.to_lowercase();
+    let wanted_tokens = tokens(key);
+    let wanted_len = wanted.chars().count();
+    let mut matches: Vec<SimilarKey> = keys
+        .into_iter()
+        .filter_map(|candidate| {
+            let token_overlap = overlap(&wanted_tokens, &tokens(candidate));
+            let lowered = candidate.to_lowercase();
+            // Keys of very different lengths can't be close, so skip the distance for them.
+            let too_long = lowered.chars().count().abs_diff(wanted_len) > max_distance;
+            if too_long && token_overlap < MIN_TOKEN_OVERLAP {
+                return None;
+            }
+            let distance = edit_distance(&wanted, &lowered);
+            (distance <= max_distance || token_overlap >= MIN_TOKEN_OVERLAP).then(|| SimilarKey {
+                key: candidate.clone(),
+                distance,
+                token_overlap: (token_overlap * 100.0).round() / 100.0,
+            })
+        })
+        .collect();
+    // Shared words discount the distance, so a key with its words reordered ranks with typos.
+    let score = |m: &SimilarKey| m.distance as f64 * (1.0 - m.token_overlap);
+    matches.sort_by(|a, b| {
+        score(a)
+            .total_cmp(&score(b))
+            .then(a.distance.cmp(&b.distance))
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+/// Lowercase words of a key, split at punctuation and camelCase humps:
+/// `settings.loginButton_title` has `settings`, `login`, `button`, and `title`.
+fn tokens(key: &str) -> BTreeSet<String> {
+    let mut tokens = BTreeSet::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in key.chars() {
+        let boundary = !c.is_alphanumeric() || (c.is_uppercase() && previous_lower);
+        if boundary && !current.is_empty() {
+            tokens.insert(std::mem::take(&mut current));
+        }
+        if c.is_alphanumeric() {
+            current.extend(c.to_lowercase());
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+    if !current.is_empty() {
+        tokens.insert(current);
+    }
+    tokens
+}
+
+fn overlap(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_typos_and_reordered_words() {
+        let keys: Vec<String> = [
+            "login.button",
+            "login.title",
+            "logout.button",
+            "settings.loginButton",
+            "checkout.payment.title",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        let found = rank("loginn.button", &keys, DEFAULT_MAX_DISTANCE, DEFAULT_LIMIT);
+        let names: Vec<_> = found.iter().map(|m| m.key.as_str()).collect();
+        assert_eq!(names, ["login.button", "logout.button"]);
+        assert_eq!(found[0].distance, 1);
+
+        let found = rank("Button.Login", &keys, 1, DEFAULT_LIMIT);
+        assert_eq!(found[0].key, "login.button");
+        assert_eq!(found[0].token_overlap, 1.0);
+        assert_eq!(found[1].key, "settings.loginButton");
+        assert_eq!(found[1].token_overlap, 0.67);
+
+        assert_eq!(rank("login.button", &keys, 0, 1)[0].distance, 0);
+        assert!(rank("onboarding.skip", &keys, 2, DEFAULT_LIMIT).is_empty());
+    }
+
+    #[test]
+    fn splits_keys_into_words() {
+        let words: Vec<_> = tokens("settings.loginButton_title2").into_iter().collect();
+        assert_eq!(words, ["button", "login", "settings", "title2"]);
+        assert_eq!(tokens("Done.").into_iter().collect::<Vec<_>>(), ["done"]);
+    }
+}
@@ -0,0 +1,554 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+    path::Path,
+};
+
+use anyhow::Context;
+
+/// Shortest part of a compound word when the `.aff` file doesn't set `COMPOUNDMIN`.
+const DEFAULT_COMPOUND_MIN: usize = 3;
+
+type Flag = u32;
+
+/// How flags are written in a dictionary, from the `FLAG` setting of its `.aff` file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum FlagType {
+    /// One character per flag.
+    #[default]
+    Short,
+    /// Two characters per flag.
+    Long,
+    /// Comma-separated numbers.
+    Numeric,
+}
+
+/// A word list in Hunspell's format: a `.dic` file of stems with affix flags, and an `.aff`
+/// file of the prefix and suffix rules those flags stand for. Only what spell-checking
+/// needs is read: affixes with one prefix and one suffix at most, compounds formed with
+/// `COMPOUNDFLAG` or `COMPOUNDBEGIN`, `COMPOUNDMIDDLE`, and `COMPOUNDEND`, and the
+/// `FORBIDDENWORD`, `NEEDAFFIX`, `ONLYINCOMPOUND`, and `KEEPCASE` flags. Suggestions,
+/// `COMPOUNDRULE`, and conversion tables are ignored.
+#[derive(Clone, Default, PartialEq)]
+pub struct Dictionary {
+    /// Stem → flags of each of its entries; homonyms get an entry each.
+    words: HashMap<String, Vec<BTreeSet<Flag>>>,
+    prefixes: Vec<Affix>,
+    suffixes: Vec<Affix>,
+    forbidden: Option<Flag>,
+    need_affix: Option<Flag>,
+    only_in_compound: Option<Flag>,
+    keep_case: Option<Flag>,
+    compound: Option<Flag>,
+    compound_begin: Option<Flag>,
+    compound_middle: Option<Flag>,
+    compound_end: Option<Flag>,
+    compound_min: usize,
+}
+
+impl fmt::Debug for Dictionary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dictionary")
+            .field("words", &self.words.len())
+            .field("prefixes", &self.prefixes.len())
+            .field("suffixes", &self.suffixes.len())
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Affix {
+    flag: Flag,
+    /// Whether the affix combines with an affix of the other kind.
+    cross_product: bool,
+    /// Removed from the stem before `add` is attached.
+    strip: String,
+    add: String,
+    /// What the end of the stem (for a suffix) or its start (for a prefix) must look like.
+    condition: Vec<CharClass>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum CharClass {
+    Any,
+    Set { chars: Vec<char>, negated: bool },
+}
+
+impl CharClass {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CharClass::Any => true,
+            CharClass::Set { chars, negated } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+/// Which part of a compound word a stem stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Part {
+    Whole,
+    Begin,
+    Middle,
+    End,
+}
+
+impl Dictionary {
+    /// Reads the `.dic` file at `path` and the `.aff` file with the same name next to it.
+    /// Files declaring `SET ISO8859-1` are decoded as Latin-1, any other as UTF-8.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let aff_path = path.with_extension("aff");
+        let aff = std::fs::read(&aff_path)
+            .with_context(|| format!("failed to read affix file {}", aff_path.display()))?;
+        let dic = std::fs::read(path)
+            .with_context(|| format!("failed to read dictionary {}", path.display()))?;
+        let latin1 = String::from_utf8_lossy(&aff)
+            .lines()
+            .any(|line| line.split_whitespace().eq(["SET", "ISO8859-1"]));
+        let decode = |bytes: &[u8]| match latin1 {
+            true => bytes.iter().map(|byte| char::from(*byte)).collect(),
+            false => String::from_utf8_lossy(bytes).into_owned(),
+        };
+        Ok(Self::parse(&decode(&aff), &decode(&dic)))
+    }
+
+    /// Builds a dictionary from the contents of an `.aff` and a `.dic` file.
+    pub fn parse(aff: &str, dic: &str) -> Self {
+        let mut dictionary = Self {
+            compound_min: DEFAULT_COMPOUND_MIN,
+            ..Self::default()
+        };
+        let mut flag_type = FlagType::Short;
+        let mut aliases: Vec<BTreeSet<Flag>> = Vec::new();
+        let mut cross_products: HashMap<(bool, Flag), bool> = HashMap::new();
+        for line in aff.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["FLAG", "long", ..] => flag_type = FlagType::Long,
+                ["FLAG", "num", ..] => flag_type = FlagType::Numeric,
+                ["AF", flags, ..] if flags.parse::<usize>().is_err() || !aliases.is_empty() => {
+                    aliases.push(parse_flags(flags, flag_type).into_iter().collect());
+                }
+                // The first `AF` line only gives the number of aliases.
+                ["AF", _, ..] => aliases.push(BTreeSet::new()),
+                ["COMPOUNDMIN", min, ..] => {
+                    dictionary.compound_min = min.parse().unwrap_or(DEFAULT_COMPOUND_MIN).max(1);
+                }
+                [kind @ ("PFX" | "SFX"), flag, strip, add, rest @ ..] => {
+                    let Some(flag) = parse_flags(flag, flag_type).first().copied() else {
+                        continue;
+                    };
+                    let prefix = *kind == "PFX";
+                    // A header line such as `SFX A Y 4` says whether the affix combines.
+                    if matches!(*strip, "Y" | "N")
+                        && add.parse::<usize>().is_ok()
+                        && rest.is_empty()
+                    {
+                        cross_products.insert((prefix, flag), *strip == "Y");
+                        continue;
+                    }
+                    let cross_product = cross_products
+                        .get(&(prefix, flag))
+                        .copied()
+                        .unwrap_or_default();
+                    let affixes = match prefix {
+                        true => &mut dictionary.prefixes,
+                        false => &mut dictionary.suffixes,
+                    };
+                    let add = add.split('/').next().unwrap_or_default();
+                    affixes.push(Affix {
+                        flag,
+                        cross_product,
+                        strip: empty_if_zero(strip),
+                        add: empty_if_zero(add),
+                        condition: parse_condition(rest.first().copied().unwrap_or(".")),
+                    });
+                }
+                [name, flag, ..] => {
+                    let flag = parse_flags(flag, flag_type).first().copied();
+                    match *name {
+                        "FORBIDDENWORD" => dictionary.forbidden = flag,
+                        "NEEDAFFIX" => dictionary.need_affix = flag,
+                        "ONLYINCOMPOUND" => dictionary.only_in_compound = flag,
+                        "KEEPCASE" => dictionary.keep_case = flag,
+                        "COMPOUNDFLAG" => dictionary.compound = flag,
+                        "COMPOUNDBEGIN" => dictionary.compound_begin = flag,
+                        "COMPOUNDMIDDLE" => dictionary.compound_middle = flag,
+                        "COMPOUNDEND" => dictionary.compound_end = flag,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut lines = dic.lines();
+        // The first line is the number of words, an estimate some files leave out.
+        let first = lines
+            .next()
+            .filter(|line| line.trim().parse::<usize>().is_err());
+        for line in first.into_iter().chain(lines) {
+            let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+            let (word, flags) = split_entry(entry);
+            if word.is_empty() {
+                continue;
+            }
+            let flags = match flags {
+                Some(flags) if !aliases.is_empty() => flags
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| aliases.get(index))
+                    .cloned()
+                    .unwrap_or_default(),
+                Some(flags) => parse_flags(flags, flag_type).into_iter().collect(),
+                None => BTreeSet::new(),
+            };
+            dictionary.words.entry(word).or_default().push(flags);
+        }
+        dictionary
+    }
+
+    /// Whether `word` is spelled correctly: a dictionary word, possibly with affixes, in
+    /// its own case, lowercase, or capitalized, or a compound of such words. Hyphenated
+    /// words are accepted when each part is.
+    pub fn check(&self, word: &str) -> bool {
+        let word = word.replace('’', "'");
+        if self.check_cased(&word) || self.check_compound(&word) {
+            return true;
+        }
+        if word.contains('-') {
+            return word
+                .split('-')
+                .filter(|part| !part.is_empty())
+                .all(|part| self.check_cased(part) || self.check_compound(part));
+        }
+        // Elisions such as French `l'application` are checked without the article.
+        if let Some((article, rest)) = word.rsplit_once('\'') {
+            return article.chars().count() <= 3 && !rest.is_empty() && self.check(rest);
+        }
+        false
+    }
+
+    fn check_cased(&self, word: &str) -> bool {
+        if self.is_forbidden(word) {
+            return false;
+        }
+        if self.check_form(word, Part::Whole, false) {
+            return true;
+        }
+        let lowercase = word.to_lowercase();
+        if lowercase == word {
+            return false;
+        }
+        // Capitalized and uppercase words may stand for lowercase entries.
+        let capitalized = capitalize(&lowercase);
+        [lowercase, capitalized]
+            .iter()
+            .filter(|form| form.as_str() != word)
+            .any(|form| !self.is_forbidden(form) && self.check_form(form, Part::Whole, true))
+    }
+
+    /// Splits `word` into two or more parts that may begin, continue, and end a compound.
+    fn check_compound(&self, word: &str) -> bool {
+        if self.compound.is_none() && self.compound_begin.is_none() {
+            return false;
+        }
+        let chars: Vec<char> = word.chars().collect();
+        self.compound_from(&chars, 0)
+    }
+
+    fn compound_from(&self, chars: &[char], start: usize) -> bool {
+        let min = self.compound_min;
+        for end in (start + min)..=chars.len().saturating_sub(min) {
+            let part: String = chars[start..end].iter().collect();
+            let part_kind = if start == 0 {
+                Part::Begin
+            } else {
+                Part::Middle
+            };
+            if !self.check_part(&part, part_kind, start > 0) {
+                continue;
+            }
+            let last: String = chars[end..].iter().collect();
+            if self.check_part(&last, Part::End, true) || self.compound_from(chars, end) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Whether a compound part is a word with the flag for its position. Parts after the
+    /// first are written in lowercase but may stand for capitalized nouns.
+    fn check_part(&self, part: &str, kind: Part, inner: bool) -> bool {
+        if self.check_form(part, kind, false) {
+            return true;
+        }
+        inner && self.check_form(&capitalize(part), kind, true)
+    }
+
+    /// Whether `word` is a stem, or a stem with a prefix, a suffix, or both, allowed in the
+    /// `part` of a compound. `recased` forms can't match `KEEPCASE` entries.
+    fn check_form(&self, word: &str, part: Part, recased: bool) -> bool {
+        if self.has_stem(word, part, recased, &[]) {
+            return true;
+        }
+        for suffix in &self.suffixes {
+            if let Some(stem) = strip_suffix(word, suffix) {
+                if self.has_stem(&stem, part, recased, &[suffix.flag]) {
+                    return true;
+                }
+            }
+        }
+        for prefix in &self.prefixes {
+            let Some(rest) = strip_prefix(word, prefix) else {
+                continue;
+            };
+            if self.has_stem(&rest, part, recased, &[prefix.flag]) {
+                return true;
+            }
+            if !prefix.cross_product {
+                continue;
+            }
+            for suffix in self.suffixes.iter().filter(|suffix| suffix.cross_product) {
+                if let Some(stem) = strip_suffix(&rest, suffix) {
+                    if self.has_stem(&stem, part, recased, &[prefix.flag, suffix.flag]) {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Whether an entry for `stem` carries every flag in `required` and may be used in
+    /// `part`.
+    fn has_stem(&self, stem: &str, part: Part, recased: bool, required: &[Flag]) -> bool {
+        let Some(entries) = self.words.get(stem) else {
+            return false;
+        };
+        entries.iter().any(|flags| {
+            let has = |flag: Option<Flag>| flag.is_some_and(|flag| flags.contains(&flag));
+            if has(self.forbidden) || (recased && has(self.keep_case)) {
+                return false;
+            }
+            if !required.iter().all(|flag| flags.contains(flag)) {
+                return false;
+            }
+            match part {
+                Part::Whole => {
+                    !has(self.only_in_compound) && (!required.is_empty() || !has(self.need_affix))
+                }
+                Part::Begin => has(self.compound) || has(self.compound_begin),
+                Part::Middle => has(self.compound) || has(self.compound_middle),
+                Part::End => has(self.compound) || has(self.compound_end),
+            }
+        })
+    }
+
+    fn is_forbidden(&self, word: &str) -> bool {
+        let Some(forbidden) = self.forbidden else {
+            return false;
+        };
+        self.words
+            .get(word)
+            .is_some_and(|entries| entries.iter().any(|flags| flags.contains(&forbidden)))
+    }
+}
+
+/// The words of `text` worth spell-checking: runs of letters, apostrophes, and hyphens,
+/// leaving out format specifiers, links, e-mail addresses, and anything with a digit.
+pub fn words(text: &str) -> Vec<&str> {
+    text.split_whitespace()
+        .filter(|chunk| !chunk.contains(['%', '@']) && !chunk.contains("://"))
+        .flat_map(|chunk| {
+            chunk.split(|c: char| !(c.is_alphanumeric() || matches!(c, '\'' | '’' | '-')))
+        })
+        .map(|word| word.trim_matches(['\'', '’', '-']))
+        .filter(|word| {
+            word.chars().any(char::is_alphabetic) && !word.chars().any(|c| c.is_numeric())
+        })
+        .collect()
+}
+
+fn parse_flags(raw: &str, flag_type: FlagType) -> Vec<Flag> {
+    match flag_type {
+        FlagType::Short => raw.chars().map(Flag::from).collect(),
+        FlagType::Long => raw
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .map(|pair| pair.iter().fold(0, |flag, c| (flag << 16) | Flag::from(*c)))
+            .collect(),
+        FlagType::Numeric => raw
+            .split(',')
+            .filter_map(|number| number.trim().parse().ok())
+            .collect(),
+    }
+}
+
+/// Splits a `.dic` entry into its word and flags at the first unescaped `/`.
+fn split_entry(entry: &str) -> (String, Option<&str>) {
+    let mut escaped = false;
+    for (index, c) in entry.char_indices() {
+        match c {
+            '\\' => escaped = !escaped,
+            '/' if !escaped && index > 0 => {
+                let word = entry[..index].replace("\\/", "/");
+                return (word, Some(&entry[index + 1..]));
+            }
+            _ => escaped = false,
+        }
+    }
+    (entry.replace("\\/", "/"), None)
+}
+
+fn empty_if_zero(field: &str) -> String {
+    if field == "0" {
+        String::new()
+    } else {
+        field.to_string()
+    }
+}
+
+/// Parses a condition such as `[^aeiou]y` into one class per character; `.` matches any.
+fn parse_condition(raw: &str) -> Vec<CharClass> {
+    if raw == "." {
+        return Vec::new();
+    }
+    let mut classes = Vec::new();
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => classes.push(CharClass::Any),
+            '[' => {
+                let mut set: Vec<char> = chars.by_ref().take_while(|c| *c != ']').collect();
+                let negated = set.first() == Some(&'^');
+                if negated {
+                    set.remove(0);
+                }
+                classes.push(CharClass::Set {
+                    chars: set,
+                    negated,
+                });
+            }
+            c => classes.push(CharClass::Set {
+                chars: vec![c],
+                negated: false,
+            }),
+        }
+    }
+    classes
+}
+
+/// The stem `word` was formed from by `suffix`, when the suffix applies.
+fn strip_suffix(word: &str, suffix: &Affix) -> Option<String> {
+    let base = word.strip_suffix(suffix.add.as_str())?;
+    let stem = format!("{base}{}", suffix.strip);
+    if base.is_empty() && suffix.strip.is_empty() {
+        return None;
+    }
+    let chars: Vec<char> = stem.chars().collect();
+    let start = chars.len().checked_sub(suffix.condition.len())?;
+    let matches = suffix
+        .condition
+        .iter()
+        .zip(&chars[start..])
+        .all(|(class, c)| class.matches(*c));
+    matches.then_some(stem)
+}
+
+/// The stem `word` was formed from by `prefix`, when the prefix applies.
+fn strip_prefix(word: &str, prefix: &Affix) -> Option<String> {
+    let base = word.strip_prefix(prefix.add.as_str())?;
+    if base.is_empty() && prefix.strip.is_empty() {
+        return None;
+    }
+    let stem = format!("{}{base}", prefix.strip);
+    let matches = stem.chars().count() >= prefix.condition.len()
+        && prefix
+            .condition
+            .iter()
+            .zip(stem.chars())
+            .all(|(class, c)| class.matches(c));
+    matches.then_some(stem)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "SET UTF-8
+FORBIDDENWORD !
+COMPOUNDFLAG C
+COMPOUNDMIN 3
+PFX U Y 1
+PFX U 0 un .
+SFX S Y 2
+SFX S 0 s [^y]
+SFX S y ies y
+SFX G N 1
+SFX G e ing e
+";
+    const DIC: &str = "6
+color/SU
+city/S
+save/G
+iPhone
+Konto/C
+Einstellung/CS
+colour/!
+";
+
+    #[test]
+    fn accepts_stems_affixes_case_and_compounds() {
+        let dictionary = Dictionary::parse(AFF, DIC);
+        for word in [
+            "color",
+            "colors",
+            "uncolors",
+            "Colors",
+            "COLOR",
+            "cities",
+            "saving",
+            "iPhone",
+            "Kontoeinstellungs",
+            "city-colors",
+            "l’city",
+        ] {
+            assert!(dictionary.check(word), "{word}");
+        }
+        for word in [
+            "colour",
+            "citys",
+            "saveing",
+            "iphone",
+            "kolor",
+            "unsaving",
+            "city-kolor",
+        ] {
+            assert!(!dictionary.check(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn skips_placeholders_links_and_numbers() {
+        assert_eq!(
+            words("Öffne %@ auf https://example.com, ruf 3x an — l’app's “Konto-Einstellungen”!"),
+            [
+                "Öffne",
+                "auf",
+                "ruf",
+                "an",
+                "l’app's",
+                "Konto-Einstellungen"
+            ]
+        );
+    }
+}
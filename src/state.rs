@@ -0,0 +1,98 @@
+use serde::Deserialize;
+
+use crate::{language::edit_distance, workflow};
+
+/// States Xcode writes, plus the placeholder state of languages added without a value and
+/// the review workflow's `reviewed`.
+pub const KNOWN_STATES: &[&str] = &[
+    workflow::NEW,
+    workflow::TRANSLATED,
+    workflow::NEEDS_REVIEW,
+    workflow::REVIEWED,
+    "stale",
+    "needs-translation",
+];
+
+/// Words agents and tools use for a state that isn't one of [`KNOWN_STATES`].
+const SYNONYMS: &[(&str, &str)] = &[
+    ("done", workflow::TRANSLATED),
+    ("ok", workflow::TRANSLATED),
+    ("complete", workflow::TRANSLATED),
+    ("completed", workflow::TRANSLATED),
+    ("final", workflow::TRANSLATED),
+    ("review", workflow::NEEDS_REVIEW),
+    ("in_review", workflow::NEEDS_REVIEW),
+    ("approved", workflow::REVIEWED),
+    ("untranslated", workflow::NEW),
+    ("missing", workflow::NEW),
+    ("todo", workflow::NEW),
+    ("outdated", "stale"),
+];
+
+/// Most edits a misspelled state may be from the state it's corrected to.
+const MAX_TYPO_DISTANCE: usize = 2;
+
+/// What upserts do with a state outside [`KNOWN_STATES`], from the `stateValidation`
+/// config setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StateValidation {
+    /// Saves the state it most likely means, or the state as given when there's none, and
+    /// reports a warning.
+    #[default]
+    Warn,
+    /// Refuses the edit.
+    Strict,
+}
+
+/// The known state `state` stands for: itself when known, or the state it's a variant,
+/// synonym, or misspelling of (`Needs-Review`, `done`, `translted`).
+pub fn normalize(state: &str) -> Option<&'static str> {
+    let folded = fold(state);
+    if let Some(known) = KNOWN_STATES.iter().find(|known| fold(known) == folded) {
+        return Some(known);
+    }
+    if let Some((_, known)) = SYNONYMS.iter().find(|(synonym, _)| *synonym == folded) {
+        return Some(known);
+    }
+    // Short words are a couple of edits from too many states to guess.
+    if folded.len() <= MAX_TYPO_DISTANCE {
+        return None;
+    }
+    let mut candidates: Vec<(usize, &'static str)> = KNOWN_STATES
+        .iter()
+        .map(|known| (edit_distance(&folded, &fold(known)), *known))
+        .filter(|(distance, _)| *distance <= MAX_TYPO_DISTANCE)
+        .collect();
+    candidates.sort();
+    match candidates[..] {
+        [(_, known)] => Some(known),
+        [(best, known), (next, _), ..] if best < next => Some(known),
+        _ => None,
+    }
+}
+
+/// Lowercase, with spaces and hyphens read as underscores.
+fn fold(state: &str) -> String {
+    state.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_variants_synonyms_and_typos() {
+        for state in KNOWN_STATES {
+            assert_eq!(normalize(state), Some(*state));
+        }
+        assert_eq!(normalize(" Needs-Review "), Some("needs_review"));
+        assert_eq!(normalize("needs_translation"), Some("needs-translation"));
+        assert_eq!(normalize("done"), Some("translated"));
+        assert_eq!(normalize("OK"), Some("translated"));
+        assert_eq!(normalize("translted"), Some("translated"));
+        assert_eq!(normalize("stael"), Some("stale"));
+        assert_eq!(normalize("banana"), None);
+        assert_eq!(normalize("x"), None);
+    }
+}
@@ -1,18 +1,53 @@
 use std::{
-    collections::{BTreeSet, HashMap},
-    env, io,
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    env,
+    future::Future,
+    io,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime},
 };
 
 use indexmap::IndexMap;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{self};
 use thiserror::Error;
-use tokio::{fs, sync::RwLock, task};
-
-use crate::apple_json_formatter;
+use tokio::{
+    fs,
+    sync::{broadcast, Mutex, OwnedMutexGuard, RwLock, RwLockWriteGuard},
+    task,
+};
+use utoipa::ToSchema;
+
+use crate::{
+    analytics::{self, CostEstimate, LengthReport},
+    apple_json_formatter::{self, FormatOptions, SourceStyle, StyleDetector},
+    assignments::Assignments,
+    audit::{AuditEntry, AuditLog},
+    frozen::FrozenLanguages,
+    language,
+    metadata::{self, KeyMetadata},
+    modified::{self, KeyModified, ModifiedIndex},
+    patch,
+    qa::{self, Finding, PlaceholderValidation, QaOptions, QaReport, QaRule, ReleaseReadiness},
+    repair::{self, RepairIssue, RepairKind, RepairReport},
+    similar::{self, SimilarKey},
+    state::{self, StateValidation},
+    suggest::{self, MemoryEntry, Suggestion},
+    terminology::{self, TermCandidate},
+    workflow, xcodeproj,
+};
+#[cfg(feature = "git")]
+use crate::{
+    diff::{self, CatalogDiff},
+    git::{self, KeyHistory, OutdatedReport, OutdatedTranslation},
+};
 
 #[derive(Debug, Error)]
 pub enum StoreError {
@@ -34,16 +69,133 @@ pub enum StoreError {
     LanguageExists(String),
     #[error("invalid language: {0}")]
     InvalidLanguage(String),
+    #[error("unknown device '{0}'; expected one of {}", DEVICES.join(", "))]
+    InvalidDevice(String),
+    #[error("invalid variation: {0}")]
+    InvalidVariation(String),
+    #[error("invalid metadata: {0}")]
+    InvalidMetadata(String),
+    #[error("invalid state: {0}")]
+    InvalidState(String),
+    #[error("invalid namespace: {0}")]
+    InvalidNamespace(String),
+    #[error("invalid patch: {0}")]
+    InvalidPatch(String),
+    #[error("renaming would overwrite existing keys: {}", .0.join(", "))]
+    NamespaceCollision(Vec<String>),
+    #[error("invalid language code '{code}': {reason}{}", did_you_mean(.suggestions))]
+    InvalidLanguageCode {
+        code: String,
+        reason: String,
+        suggestions: Vec<String>,
+    },
     #[error("cannot remove source language '{0}'")]
     CannotRemoveSourceLanguage(String),
     #[error("cannot rename source language '{0}'")]
     CannotRenameSourceLanguage(String),
+    #[error("invalid snapshot label: {0}")]
+    InvalidSnapshotLabel(String),
+    #[error("snapshot '{0}' not found")]
+    SnapshotMissing(String),
+    #[error("invalid catalog path: {0}")]
+    InvalidCatalogPath(String),
+    #[error("catalog '{0}' was not registered")]
+    CatalogNotRegistered(String),
+    #[error("catalog '{0}' is read-only")]
+    ReadOnly(String),
+    #[error("catalog '{path}' is protected by the read-only rule '{rule}'")]
+    Protected { path: String, rule: String },
+    #[error("invalid Xcode project: {0}")]
+    InvalidProject(String),
+    #[error("review workflow: '{key}' ({language}) {reason}")]
+    WorkflowViolation {
+        key: String,
+        language: String,
+        reason: String,
+    },
+    #[error("format specifiers of '{key}' ({language}) don't match the source: {reason}")]
+    PlaceholderMismatch {
+        key: String,
+        language: String,
+        reason: String,
+    },
+    #[error(
+        "key '{0}' is new and needs a developer comment; pass `comment` saying where the \
+         string appears and what it means, so translators have context"
+    )]
+    CommentRequired(String),
+    #[error("language '{0}' is frozen; unfreeze it or force the write")]
+    LanguageFrozen(String),
+    #[error(
+        "language '{language}' isn't complete; {} keys still need work: {}",
+        .keys.len(),
+        preview_keys(.keys)
+    )]
+    LanguageIncomplete { language: String, keys: Vec<String> },
+    #[error("catalog '{0}' is not inside a git repository")]
+    NotInGitRepository(String),
+    #[error("git revision '{0}' not found")]
+    GitRevisionMissing(String),
+    #[error("catalog changed since it was loaded; refetch and retry")]
+    RevisionMismatch,
+}
+
+/// Lists the first few keys of a long list, for error messages.
+fn preview_keys(keys: &[String]) -> String {
+    const SHOWN: usize = 5;
+    let mut preview = keys
+        .iter()
+        .take(SHOWN)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if keys.len() > SHOWN {
+        preview.push_str(", …");
+    }
+    preview
+}
+
+fn did_you_mean(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(" (did you mean {}?)", suggestions.join(", "))
+    }
+}
+
+/// Validates a language code given to a write operation and returns its canonical form.
+fn normalize_language_code(code: &str) -> Result<String, StoreError> {
+    if code.trim().is_empty() {
+        return Err(StoreError::InvalidLanguage(
+            "Language code cannot be empty".to_string(),
+        ));
+    }
+    language::normalize(code).map_err(|err| StoreError::InvalidLanguageCode {
+        code: code.trim().to_string(),
+        reason: err.reason,
+        suggestions: err.suggestions,
+    })
 }
 
 const DEFAULT_VERSION: &str = "1.0";
 const DEFAULT_SOURCE_LANGUAGE: &str = "en";
 const DEFAULT_TRANSLATION_STATE: &str = "translated";
 const NEEDS_TRANSLATION_STATE: &str = "needs-translation";
+/// Extraction state Xcode assigns to keys added by hand rather than extracted from code.
+const MANUAL_EXTRACTION_STATE: &str = "manual";
+/// Device identifiers Xcode accepts as cases of a `device` variation.
+pub const DEVICES: &[&str] = &[
+    "iphone",
+    "ipod",
+    "ipad",
+    "mac",
+    "applewatch",
+    "appletv",
+    "applevision",
+    "other",
+];
+/// Directory, next to each catalog, that holds its named snapshots.
+pub const SNAPSHOT_DIR: &str = ".xcstrings-snapshots";
 
 fn default_version() -> String {
     DEFAULT_VERSION.to_string()
@@ -62,7 +214,8 @@ pub enum FormatVersion {
 
 #[derive(Debug, Clone)]
 pub struct XcStringsFile {
-    // Store the original JSON to preserve field order
+    // Top-level fields in their original order. Fields parsed below hold a `null`
+    // placeholder, so the strings aren't kept twice.
     raw: IndexMap<String, serde_json::Value>,
     // Cached parsed values for easy access
     pub version: String,
@@ -97,42 +250,209 @@ impl Default for XcStringsFile {
     }
 }
 
+impl<'de> Deserialize<'de> for XcStringsFile {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(XcStringsFileVisitor)
+    }
+}
+
+struct XcStringsFileVisitor;
+
+impl<'de> serde::de::Visitor<'de> for XcStringsFileVisitor {
+    type Value = XcStringsFile;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a string catalog object")
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> Result<XcStringsFile, A::Error> {
+        let mut file = XcStringsFile {
+            raw: IndexMap::new(),
+            version: default_version(),
+            format_version: None,
+            source_language: default_source_language(),
+            strings: IndexMap::new(),
+        };
+        while let Some(field) = map.next_key::<String>()? {
+            // Header fields of the wrong type fall back to their defaults rather than
+            // failing the whole load.
+            match field.as_str() {
+                "version" => {
+                    if let serde_json::Value::String(version) = map.next_value()? {
+                        file.version = version;
+                    }
+                }
+                "formatVersion" => {
+                    file.format_version = serde_json::from_value(map.next_value()?).ok();
+                }
+                "sourceLanguage" => {
+                    if let serde_json::Value::String(language) = map.next_value()? {
+                        file.source_language = language;
+                    }
+                }
+                "strings" => file.strings = map.next_value()?,
+                _ => {
+                    let value = map.next_value()?;
+                    file.raw.insert(field, value);
+                    continue;
+                }
+            }
+            file.raw.insert(field, serde_json::Value::Null);
+        }
+        Ok(file)
+    }
+}
+
 impl XcStringsFile {
-    fn from_json_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
-        // Parse into IndexMap to preserve order
-        let raw: IndexMap<String, serde_json::Value> = serde_json::from_value(value.clone())?;
-
-        // Extract fields
-        let version = raw
-            .get("version")
-            .and_then(|v| v.as_str())
-            .unwrap_or("1.0")
-            .to_string();
-
-        let format_version = raw
-            .get("formatVersion")
-            .and_then(|v| serde_json::from_value(v.clone()).ok());
-
-        let source_language = raw
-            .get("sourceLanguage")
-            .and_then(|v| v.as_str())
-            .unwrap_or("en")
-            .to_string();
-
-        let strings = raw
-            .get("strings")
-            .and_then(|v| serde_json::from_value(v.clone()).ok())
-            .unwrap_or_default();
+    /// Where `position` puts the new key `key`; fails if it names a key that doesn't exist.
+    fn insertion_index(&self, key: &str, position: &InsertPosition) -> Result<usize, StoreError> {
+        match position {
+            InsertPosition::End => Ok(self.strings.len()),
+            InsertPosition::Sorted => Ok(self
+                .strings
+                .keys()
+                .position(|existing| existing.as_str() > key)
+                .unwrap_or(self.strings.len())),
+            InsertPosition::After(after) => self
+                .strings
+                .get_index_of(after)
+                .map(|index| index + 1)
+                .ok_or_else(|| StoreError::KeyMissing(after.clone())),
+        }
+    }
 
-        Ok(Self {
-            raw,
-            version,
-            format_version,
-            source_language,
-            strings,
+    /// Upserts one translation; if this creates `key`, it goes at `position` with `comment`.
+    fn upsert_translation(
+        &mut self,
+        key: &str,
+        language: &str,
+        update: TranslationUpdate,
+        comment: Option<String>,
+        position: &InsertPosition,
+    ) -> Result<UpsertOutcome, StoreError> {
+        if !self.strings.contains_key(key) {
+            let index = self.insertion_index(key, position)?;
+            let entry = XcStringEntry {
+                comment: comment.filter(|comment| !comment.trim().is_empty()),
+                ..Default::default()
+            };
+            self.strings.shift_insert(index, key.to_string(), entry);
+        }
+        let loc = self
+            .strings
+            .entry(key.to_string())
+            .or_default()
+            .localizations
+            .entry(language.to_string())
+            .or_default();
+        apply_update(loc, update);
+        let mut warnings = Vec::new();
+        normalize_localization(loc, &mut warnings);
+        Ok(UpsertOutcome {
+            value: TranslationValue::from_localization(loc),
+            warnings,
+            needs_review: Vec::new(),
         })
     }
 
+    fn delete_translation(&mut self, key: &str, language: &str) -> Result<(), StoreError> {
+        let entry = self
+            .strings
+            .get_mut(key)
+            .filter(|entry| entry.localizations.contains_key(language))
+            .ok_or_else(|| StoreError::TranslationMissing {
+                key: key.to_string(),
+                language: language.to_string(),
+            })?;
+        entry.localizations.shift_remove(language);
+        if entry.localizations.is_empty() {
+            self.strings.shift_remove(key);
+        }
+        Ok(())
+    }
+
+    fn delete_key(&mut self, key: &str) -> Result<(), StoreError> {
+        self.strings
+            .shift_remove(key)
+            .map(|_| ())
+            .ok_or_else(|| StoreError::KeyMissing(key.to_string()))
+    }
+
+    fn apply(&mut self, operation: BatchOperation) -> Result<BatchApplied, StoreError> {
+        match operation {
+            BatchOperation::Upsert {
+                key,
+                language,
+                update,
+                comment,
+                position,
+            } => {
+                let outcome =
+                    self.upsert_translation(&key, &language, *update, comment, &position)?;
+                Ok(BatchApplied {
+                    change: StoreChange::TranslationUpserted { key, language },
+                    value: Some(outcome.value),
+                    warnings: outcome.warnings,
+                    needs_review: outcome.needs_review,
+                })
+            }
+            BatchOperation::DeleteTranslation { key, language } => {
+                self.delete_translation(&key, &language)?;
+                Ok(BatchApplied {
+                    change: StoreChange::TranslationDeleted { key, language },
+                    value: None,
+                    warnings: Vec::new(),
+                    needs_review: Vec::new(),
+                })
+            }
+            BatchOperation::DeleteKey { key } => {
+                self.delete_key(&key)?;
+                Ok(BatchApplied {
+                    change: StoreChange::KeyDeleted { key },
+                    value: None,
+                    warnings: Vec::new(),
+                    needs_review: Vec::new(),
+                })
+            }
+        }
+    }
+
+    fn find_replace(&mut self, search: &FindReplace) -> Vec<Replacement> {
+        let mut replacements = Vec::new();
+        for (key, entry) in self.strings.iter_mut() {
+            for (language, loc) in entry.localizations.iter_mut() {
+                if search
+                    .language
+                    .as_deref()
+                    .is_some_and(|only| only != language)
+                {
+                    continue;
+                }
+                let mut found = Vec::new();
+                replace_in_localization(loc, "", search, &mut found);
+                replacements.extend(found.into_iter().map(|(location, before, after)| {
+                    Replacement {
+                        key: key.clone(),
+                        language: language.clone(),
+                        location,
+                        before,
+                        after,
+                    }
+                }));
+            }
+        }
+        replacements
+    }
+
+    /// Parses a catalog straight into typed entries, without building a JSON tree of the
+    /// whole file first.
+    fn parse(raw: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(raw)
+    }
+
     fn to_json_value(&self) -> serde_json::Value {
         // Convert back using the preserved raw structure
         let mut raw = self.raw.clone();
@@ -221,7 +541,7 @@ pub struct XcStringUnit {
     pub value: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, ToSchema)]
 pub struct TranslationValue {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<String>,
@@ -230,6 +550,7 @@ pub struct TranslationValue {
     #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub substitutions: IndexMap<String, SubstitutionValue>,
     #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    #[schema(no_recursion)]
     pub variations: IndexMap<String, IndexMap<String, TranslationValue>>,
 }
 
@@ -243,7 +564,7 @@ pub struct TranslationUpdate {
     pub variations: Option<IndexMap<String, IndexMap<String, TranslationUpdate>>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, ToSchema)]
 pub struct SubstitutionValue {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<String>,
@@ -254,6 +575,7 @@ pub struct SubstitutionValue {
     #[serde(rename = "formatSpecifier", skip_serializing_if = "Option::is_none")]
     pub format_specifier: Option<String>,
     #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    #[schema(no_recursion)]
     pub variations: IndexMap<String, IndexMap<String, TranslationValue>>,
 }
 
@@ -299,6 +621,20 @@ impl TranslationValue {
             variations,
         }
     }
+
+    /// The cases of the `width` variation by width in ems, narrowest first. Cases that
+    /// aren't whole numbers are left out.
+    pub fn width_variations(&self) -> Vec<(u32, &TranslationValue)> {
+        let mut widths: Vec<_> = self
+            .variations
+            .get("width")
+            .into_iter()
+            .flatten()
+            .filter_map(|(case, value)| Some((case.parse().ok()?, value)))
+            .collect();
+        widths.sort_by_key(|(width, _)| *width);
+        widths
+    }
 }
 
 impl TranslationUpdate {
@@ -334,10 +670,16 @@ impl TranslationUpdate {
         let selector = selector.into();
         let case = case.into();
         let variations = self.variations.get_or_insert_with(IndexMap::new);
-        let selector_entry = variations.entry(selector).or_insert_with(IndexMap::new);
+        let selector_entry = variations.entry(selector).or_default();
         selector_entry.insert(case, update);
         self
     }
+
+    /// Adds the text shown when the UI is at least `width` ems wide, as a case of the `width`
+    /// variation.
+    pub fn add_width_variation(self, width: u32, update: TranslationUpdate) -> Self {
+        self.add_variation("width", width.to_string(), update)
+    }
 }
 
 impl SubstitutionValue {
@@ -502,62 +844,118 @@ enum VariationContext {
     TopLevel,
     NestedUnderPlural,
     NestedUnderDevice,
+    NestedUnderWidth,
 }
 
-/// Validates and normalizes variations according to xcstrings schema constraints:
-/// - At top level: Cannot have both "plural" and "device" variations
-/// - Nested under "plural": Cannot have "device" variations
-/// - Nested under "device": Cannot have another "device" variation (but can have "plural")
+/// Something an upsert changed to fit the xcstrings schema: a variation removed because
+/// it isn't allowed where it was, or an unknown state.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct VariationWarning {
+    /// Where the removed selector was, relative to the localization, such as
+    /// `variations.plural.one.variations.device`.
+    pub path: String,
+    pub message: String,
+}
+
+/// Validates and normalizes variations according to xcstrings schema constraints. A
+/// localization varies by one of `device`, `width`, and `plural` at each level, and they
+/// nest only in that order:
+/// - At top level: Cannot have both "plural" and "device" variations, nor "width" alongside
+///   either of them
+/// - Nested under "plural": Cannot have "device" or "width" variations
+/// - Nested under "device": Cannot have another "device" variation (but can have "width" or
+///   "plural")
+/// - Nested under "width": Cannot have "device" or another "width" variation (but can have
+///   "plural")
+///
+/// `path` is the prefix of the variations within the localization, and every removed
+/// selector is reported to `warnings`.
 fn validate_and_normalize_variations(
     variations: &mut IndexMap<String, IndexMap<String, XcLocalization>>,
     context: VariationContext,
+    path: &str,
+    warnings: &mut Vec<VariationWarning>,
 ) {
     // First, recursively normalize nested localizations
     for (selector, cases) in variations.iter_mut() {
         // Determine context for nested variations
         let nested_context = match (context, selector.as_str()) {
-            (VariationContext::TopLevel, "plural") => VariationContext::NestedUnderPlural,
+            (_, "plural") => VariationContext::NestedUnderPlural,
             (VariationContext::TopLevel, "device") => VariationContext::NestedUnderDevice,
-            (VariationContext::NestedUnderDevice, "plural") => VariationContext::NestedUnderPlural,
+            (VariationContext::TopLevel | VariationContext::NestedUnderDevice, "width") => {
+                VariationContext::NestedUnderWidth
+            }
             _ => context, // Other selectors maintain current context
         };
 
-        cases.retain(|_, nested| {
-            // Recursively normalize nested localizations
-            !normalize_localization_inner(nested, nested_context)
-        });
+        for (case, nested) in cases.iter_mut() {
+            let nested_path = format!("{path}variations.{selector}.{case}.");
+            normalize_localization_inner(nested, nested_context, &nested_path, warnings);
+        }
     }
 
     // Apply context-specific validation rules
-    match context {
-        VariationContext::TopLevel => {
-            // Cannot have both "plural" and "device" at top level
-            if variations.contains_key("plural") && variations.contains_key("device") {
-                eprintln!("Warning: Invalid variation combination - cannot have both 'plural' and 'device' at top level. Removing 'device'.");
-                variations.shift_remove("device");
-            }
+    let message = match context {
+        // Cannot have both "plural" and "device" at top level
+        VariationContext::TopLevel if variations.contains_key("plural") => {
+            "a localization can't vary by both plural and device; removed the device variations"
         }
+        VariationContext::TopLevel => "",
+        // Cannot have "device" when nested under "plural"
         VariationContext::NestedUnderPlural => {
-            // Cannot have "device" when nested under "plural"
-            if variations.contains_key("device") {
-                eprintln!("Warning: Invalid variation - cannot have 'device' nested under 'plural'. Removing 'device'.");
-                variations.shift_remove("device");
-            }
+            "device variations can't be nested under plural ones; removed them"
         }
+        // Cannot have another "device" when already nested under "device"
         VariationContext::NestedUnderDevice => {
-            // Cannot have another "device" when already nested under "device"
-            if variations.contains_key("device") {
-                eprintln!("Warning: Invalid variation - cannot have 'device' nested under another 'device'. Removing nested 'device'.");
-                variations.shift_remove("device");
-            }
+            "device variations can't be nested under another device variation; removed them"
         }
-    }
+        // Cannot have "device" when nested under "width"
+        VariationContext::NestedUnderWidth => {
+            "device variations can't be nested under width ones; removed them"
+        }
+    };
+    remove_selector(variations, "device", message, path, warnings);
 
-    // Remove empty variation sets
-    variations.retain(|_, cases| !cases.is_empty());
+    let message = match context {
+        // Cannot have "width" alongside "plural" or "device" at the same level
+        _ if variations.contains_key("plural") || variations.contains_key("device") => {
+            "a localization can't vary by width alongside plural or device; removed the width \
+             variations"
+        }
+        VariationContext::TopLevel | VariationContext::NestedUnderDevice => "",
+        // Cannot have "width" when nested under "plural"
+        VariationContext::NestedUnderPlural => {
+            "width variations can't be nested under plural ones; removed them"
+        }
+        // Cannot have another "width" when already nested under "width"
+        VariationContext::NestedUnderWidth => {
+            "width variations can't be nested under another width variation; removed them"
+        }
+    };
+    remove_selector(variations, "width", message, path, warnings);
+}
+
+/// Removes the `selector` variations with a warning, unless `message` is empty.
+fn remove_selector(
+    variations: &mut IndexMap<String, IndexMap<String, XcLocalization>>,
+    selector: &str,
+    message: &str,
+    path: &str,
+    warnings: &mut Vec<VariationWarning>,
+) {
+    if !message.is_empty() && variations.shift_remove(selector).is_some() {
+        warnings.push(VariationWarning {
+            path: format!("{path}variations.{selector}"),
+            message: message.to_string(),
+        });
+    }
 }
 
-fn normalize_substitution(sub: &mut XcSubstitution) -> bool {
+fn normalize_substitution(
+    sub: &mut XcSubstitution,
+    path: &str,
+    warnings: &mut Vec<VariationWarning>,
+) {
     if let Some(unit) = sub.string_unit.as_mut() {
         sanitize_string_unit(unit);
     }
@@ -572,16 +970,26 @@ fn normalize_substitution(sub: &mut XcSubstitution) -> bool {
     }
 
     // Validate and normalize variations (substitutions follow same rules as top-level)
-    validate_and_normalize_variations(&mut sub.variations, VariationContext::TopLevel);
-
-    substitution_is_empty(sub)
+    validate_and_normalize_variations(
+        &mut sub.variations,
+        VariationContext::TopLevel,
+        path,
+        warnings,
+    );
 }
 
-fn normalize_localization(loc: &mut XcLocalization) -> bool {
-    normalize_localization_inner(loc, VariationContext::TopLevel)
+/// Brings a localization in line with the schema without dropping anything that holds a
+/// value or state; see [`prune_localization`] for removing what's empty.
+fn normalize_localization(loc: &mut XcLocalization, warnings: &mut Vec<VariationWarning>) {
+    normalize_localization_inner(loc, VariationContext::TopLevel, "", warnings)
 }
 
-fn normalize_localization_inner(loc: &mut XcLocalization, context: VariationContext) -> bool {
+fn normalize_localization_inner(
+    loc: &mut XcLocalization,
+    context: VariationContext,
+    path: &str,
+    warnings: &mut Vec<VariationWarning>,
+) {
     if let Some(unit) = loc.string_unit.as_mut() {
         sanitize_string_unit(unit);
     }
@@ -596,21 +1004,159 @@ fn normalize_localization_inner(loc: &mut XcLocalization, context: VariationCont
     }
 
     // Validate and normalize variations with appropriate context
-    validate_and_normalize_variations(&mut loc.variations, context);
+    validate_and_normalize_variations(&mut loc.variations, context, path, warnings);
 
-    loc.substitutions
-        .retain(|_, sub| !normalize_substitution(sub));
+    for (name, sub) in loc.substitutions.iter_mut() {
+        normalize_substitution(sub, &format!("{path}substitutions.{name}."), warnings);
+    }
+}
 
+/// Removes empty variation cases and substitutions from `loc`, returning whether nothing
+/// is left of it.
+fn prune_localization(loc: &mut XcLocalization) -> bool {
+    prune_variations(&mut loc.variations);
+    loc.substitutions.retain(|_, sub| {
+        prune_variations(&mut sub.variations);
+        !substitution_is_empty(sub)
+    });
     localization_is_empty(loc)
 }
 
+fn prune_variations(variations: &mut IndexMap<String, IndexMap<String, XcLocalization>>) {
+    for cases in variations.values_mut() {
+        cases.retain(|_, nested| !prune_localization(nested));
+    }
+    variations.retain(|_, cases| !cases.is_empty());
+}
+
+/// Whether a localization holds text the OS would show, unlike an empty placeholder.
+fn supplies_text(loc: &XcLocalization) -> bool {
+    extract_translation_value(loc).is_some_and(|value| !value.is_empty())
+        || !loc.variations.is_empty()
+        || !loc.substitutions.is_empty()
+}
+
+/// Trims a namespace argument and any trailing `.`, rejecting empty ones.
+fn namespace_prefix(raw: &str) -> Result<String, StoreError> {
+    let prefix = raw.trim().trim_end_matches('.');
+    if prefix.is_empty() {
+        return Err(StoreError::InvalidNamespace(
+            "Namespace cannot be empty".to_string(),
+        ));
+    }
+    Ok(prefix.to_string())
+}
+
+fn plan_namespace_rename(
+    doc: &XcStringsFile,
+    old_prefix: &str,
+    new_prefix: &str,
+) -> NamespaceRename {
+    let renamed: Vec<RenamedKey> = doc
+        .strings
+        .keys()
+        .filter_map(|key| {
+            let rest = key.strip_prefix(old_prefix)?.strip_prefix('.')?;
+            Some(RenamedKey {
+                old_key: key.clone(),
+                new_key: format!("{new_prefix}.{rest}"),
+            })
+        })
+        .collect();
+    let moving: BTreeSet<&str> = renamed.iter().map(|r| r.old_key.as_str()).collect();
+    let collisions = renamed
+        .iter()
+        .filter(|r| doc.strings.contains_key(&r.new_key) && !moving.contains(r.new_key.as_str()))
+        .map(|r| r.new_key.clone())
+        .collect();
+    NamespaceRename {
+        renamed,
+        collisions,
+    }
+}
+
+/// Picks the localization of `entry` the OS would show for `language`, returning the locale
+/// that supplied it. See [`XcStringsStore::get_translation_resolved`].
+fn resolve(
+    key: &str,
+    entry: &XcStringEntry,
+    language: &str,
+    source_language: &str,
+) -> (String, TranslationValue) {
+    std::iter::once(language)
+        .chain(language::parents(language))
+        .chain(std::iter::once(source_language))
+        .find_map(|candidate| {
+            let loc = entry.localizations.get(candidate)?;
+            supplies_text(loc).then(|| {
+                (
+                    candidate.to_string(),
+                    TranslationValue::from_localization(loc),
+                )
+            })
+        })
+        .unwrap_or_else(|| {
+            (
+                source_language.to_string(),
+                TranslationValue {
+                    value: Some(key.to_string()),
+                    ..TranslationValue::default()
+                },
+            )
+        })
+}
+
+/// The text of `key` in `language` with states cleared, when `language` is the source
+/// language and the key has text in it; otherwise `None`.
+fn source_value(doc: &XcStringsFile, key: &str, language: &str) -> Option<TranslationValue> {
+    if language != doc.source_language {
+        return None;
+    }
+    let loc = doc.strings.get(key)?.localizations.get(language)?;
+    supplies_text(loc).then(|| text_only(&TranslationValue::from_localization(loc)))
+}
+
+/// Drops review states so values can be compared by their text alone.
+fn text_only(value: &TranslationValue) -> TranslationValue {
+    let variations = |variations: &IndexMap<String, IndexMap<String, TranslationValue>>| {
+        variations
+            .iter()
+            .map(|(selector, cases)| {
+                let cases = cases
+                    .iter()
+                    .map(|(case, nested)| (case.clone(), text_only(nested)))
+                    .collect();
+                (selector.clone(), cases)
+            })
+            .collect()
+    };
+    TranslationValue {
+        state: None,
+        value: value.value.clone(),
+        substitutions: value
+            .substitutions
+            .iter()
+            .map(|(name, sub)| {
+                let sub = SubstitutionValue {
+                    state: None,
+                    variations: variations(&sub.variations),
+                    ..sub.clone()
+                };
+                (name.clone(), sub)
+            })
+            .collect(),
+        variations: variations(&value.variations),
+    }
+}
+
 fn placeholder_localization() -> XcLocalization {
-    let mut loc = XcLocalization::default();
-    loc.string_unit = Some(XcStringUnit {
-        state: Some(NEEDS_TRANSLATION_STATE.to_string()),
-        value: Some(String::new()),
-    });
-    loc
+    XcLocalization {
+        string_unit: Some(XcStringUnit {
+            state: Some(NEEDS_TRANSLATION_STATE.to_string()),
+            value: Some(String::new()),
+        }),
+        ..Default::default()
+    }
 }
 
 /// Extracts the main translation value from a localization.
@@ -619,6 +1165,115 @@ fn extract_translation_value(loc: &XcLocalization) -> Option<String> {
     loc.string_unit.as_ref()?.value.clone()
 }
 
+/// Overall state of a localization: its own string unit state, or for variation-only
+/// localizations the first nested state that is not `translated`.
+fn localization_state(loc: &XcLocalization) -> Option<String> {
+    fn collect<'a>(loc: &'a XcLocalization, states: &mut Vec<&'a str>) {
+        states.extend(
+            loc.string_unit
+                .as_ref()
+                .and_then(|unit| unit.state.as_deref()),
+        );
+        for case in loc.variations.values().flat_map(|cases| cases.values()) {
+            collect(case, states);
+        }
+        for sub in loc.substitutions.values() {
+            states.extend(
+                sub.string_unit
+                    .as_ref()
+                    .and_then(|unit| unit.state.as_deref()),
+            );
+            for case in sub.variations.values().flat_map(|cases| cases.values()) {
+                collect(case, states);
+            }
+        }
+    }
+
+    if let Some(state) = loc.string_unit.as_ref().and_then(|unit| unit.state.clone()) {
+        return Some(state);
+    }
+    let mut states = Vec::new();
+    collect(loc, &mut states);
+    states
+        .iter()
+        .find(|state| **state != DEFAULT_TRANSLATION_STATE)
+        .or_else(|| states.first())
+        .map(|state| state.to_string())
+}
+
+/// Sets the state of every string unit in `loc`, including variations and substitutions.
+fn set_states(loc: &mut XcLocalization, state: &str) {
+    let units = loc.string_unit.iter_mut().chain(
+        loc.substitutions
+            .values_mut()
+            .filter_map(|sub| sub.string_unit.as_mut()),
+    );
+    for unit in units {
+        unit.state = Some(state.to_string());
+    }
+    let cases = loc
+        .variations
+        .values_mut()
+        .flat_map(|cases| cases.values_mut());
+    let substitution_cases = loc
+        .substitutions
+        .values_mut()
+        .flat_map(|sub| sub.variations.values_mut())
+        .flat_map(|cases| cases.values_mut());
+    for case in cases.chain(substitution_cases) {
+        set_states(case, state);
+    }
+}
+
+/// An entry removed or trimmed beyond the requested edit, such as a key `remove_language`
+/// left without localizations, or one [`XcStringsStore::clean`] found empty. Results of
+/// writes list these so nothing disappears silently; see [`track_normalization`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedEntry {
+    pub key: String,
+    /// Whether the whole entry was removed because nothing was left in it.
+    pub removed: bool,
+    /// Languages whose localizations were removed because they were empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dropped_languages: Vec<String>,
+}
+
+tokio::task_local! {
+    static NORMALIZED: RefCell<Vec<NormalizedEntry>>;
+}
+
+/// Runs `future`, returning alongside its output the entries normalization changed in the
+/// saves it made.
+pub async fn track_normalization<F: Future>(future: F) -> (F::Output, Vec<NormalizedEntry>) {
+    NORMALIZED
+        .scope(RefCell::new(Vec::new()), async move {
+            let output = future.await;
+            (output, NORMALIZED.with(RefCell::take))
+        })
+        .await
+}
+
+/// Adds `entries` to `into`, combining entries for the same key.
+fn merge_normalized(into: &mut Vec<NormalizedEntry>, entries: Vec<NormalizedEntry>) {
+    for entry in entries {
+        match into.iter_mut().find(|existing| existing.key == entry.key) {
+            Some(existing) => {
+                existing.removed |= entry.removed;
+                for language in entry.dropped_languages {
+                    if !existing.dropped_languages.contains(&language) {
+                        existing.dropped_languages.push(language);
+                    }
+                }
+            }
+            None => into.push(entry),
+        }
+    }
+}
+
+/// Fills in missing catalog fields and brings every localization in line with the schema.
+/// Nothing holding a value or state is dropped, so placeholders other tools create survive
+/// saves; [`prune_strings_file`] removes what's empty on request.
 fn normalize_strings_file(doc: &mut XcStringsFile) {
     if doc.version.trim().is_empty() {
         doc.version = default_version();
@@ -628,19 +1283,85 @@ fn normalize_strings_file(doc: &mut XcStringsFile) {
         doc.source_language = default_source_language();
     }
 
-    doc.strings.retain(|_, entry| {
-        entry
-            .localizations
-            .retain(|_, loc| !normalize_localization(loc));
+    for (key, entry) in doc.strings.iter_mut() {
+        for (language, loc) in entry.localizations.iter_mut() {
+            let mut warnings = Vec::new();
+            normalize_localization(loc, &mut warnings);
+            for warning in warnings {
+                tracing::warn!(key, language, path = %warning.path, "{}", warning.message);
+            }
+        }
+    }
+}
 
-        if entry.localizations.is_empty() {
-            entry.comment.is_some()
-                || entry.extraction_state.is_some()
-                || entry.should_translate.is_some()
-        } else {
-            true
+/// Drops empty localizations and entries, returning what was dropped.
+fn prune_strings_file(doc: &mut XcStringsFile) -> Vec<NormalizedEntry> {
+    let mut pruned = Vec::new();
+    doc.strings.retain(|key, entry| {
+        let mut dropped_languages = Vec::new();
+        entry.localizations.retain(|language, loc| {
+            let empty = prune_localization(loc);
+            if empty {
+                dropped_languages.push(language.clone());
+            }
+            !empty
+        });
+
+        let keep = !entry.localizations.is_empty()
+            || entry.comment.is_some()
+            || entry.extraction_state.is_some()
+            || entry.should_translate.is_some();
+        if !keep || !dropped_languages.is_empty() {
+            pruned.push(NormalizedEntry {
+                key: key.clone(),
+                removed: !keep,
+                dropped_languages,
+            });
         }
+        keep
     });
+    pruned
+}
+
+/// Parses `raw` and fixes what [`XcStringsStore::repair`] can, returning the fixed catalog
+/// and every problem found.
+fn repair_document(raw: &str) -> Result<(XcStringsFile, Vec<RepairIssue>), StoreError> {
+    let mut issues = repair::duplicate_keys(raw);
+    let mut doc = XcStringsFile::parse(raw)?;
+    for (key, entry) in doc.strings.iter_mut() {
+        for (language, loc) in entry.localizations.iter_mut() {
+            issues.extend(repair::repair_substitutions(key, language, loc));
+            let mut warnings = Vec::new();
+            normalize_localization(loc, &mut warnings);
+            for warning in warnings {
+                let mut tokens = vec!["strings", key.as_str(), "localizations", language.as_str()];
+                tokens.extend(warning.path.split('.'));
+                issues.push(RepairIssue::new(
+                    RepairKind::InvalidVariation,
+                    &tokens,
+                    warning.message,
+                ));
+            }
+        }
+    }
+    for normalized in prune_strings_file(&mut doc) {
+        let key = normalized.key.as_str();
+        for language in &normalized.dropped_languages {
+            issues.push(RepairIssue::new(
+                RepairKind::EmptyLocalization,
+                &["strings", key, "localizations", language],
+                "The localization has no value or variations".to_string(),
+            ));
+        }
+        if normalized.removed {
+            issues.push(RepairIssue::new(
+                RepairKind::EmptyEntry,
+                &["strings", key],
+                "The key has no localizations, comment, or flags".to_string(),
+            ));
+        }
+    }
+    Ok((doc, issues))
 }
 
 fn apply_update(target: &mut XcLocalization, update: TranslationUpdate) {
@@ -669,9 +1390,7 @@ fn apply_update(target: &mut XcLocalization, update: TranslationUpdate) {
                 .unwrap_or_default();
 
             for (case_key, nested_update) in cases_update {
-                let mut nested_loc = selector_entry
-                    .shift_remove(&case_key)
-                    .unwrap_or_else(XcLocalization::default);
+                let mut nested_loc = selector_entry.shift_remove(&case_key).unwrap_or_default();
                 apply_update(&mut nested_loc, nested_update);
 
                 if localization_is_empty(&nested_loc) {
@@ -691,9 +1410,6 @@ fn apply_update(target: &mut XcLocalization, update: TranslationUpdate) {
                 .into_iter()
                 .filter(|(_, cases)| !cases.is_empty()),
         );
-
-        // Validate the resulting variations
-        validate_and_normalize_variations(&mut target.variations, VariationContext::TopLevel);
     }
 
     if let Some(substitutions) = update.substitutions {
@@ -704,7 +1420,7 @@ fn apply_update(target: &mut XcLocalization, update: TranslationUpdate) {
                 Some(sub_update) => {
                     let mut substitution = existing_substitutions
                         .shift_remove(&name)
-                        .unwrap_or_else(XcSubstitution::default);
+                        .unwrap_or_default();
                     apply_substitution_update(&mut substitution, sub_update);
 
                     if !substitution_is_empty(&substitution) {
@@ -759,9 +1475,7 @@ fn apply_substitution_update(target: &mut XcSubstitution, update: SubstitutionUp
                 .unwrap_or_default();
 
             for (case_key, nested_update) in cases_update {
-                let mut nested_loc = selector_entry
-                    .shift_remove(&case_key)
-                    .unwrap_or_else(XcLocalization::default);
+                let mut nested_loc = selector_entry.shift_remove(&case_key).unwrap_or_default();
                 apply_update(&mut nested_loc, nested_update);
 
                 if localization_is_empty(&nested_loc) {
@@ -781,9 +1495,6 @@ fn apply_substitution_update(target: &mut XcSubstitution, update: SubstitutionUp
                 .into_iter()
                 .filter(|(_, cases)| !cases.is_empty()),
         );
-
-        // Validate the resulting variations for substitutions (same rules as TopLevel)
-        validate_and_normalize_variations(&mut target.variations, VariationContext::TopLevel);
     }
 }
 
@@ -800,6 +1511,15 @@ fn substitution_is_empty(sub: &XcSubstitution) -> bool {
         && sub.format_specifier.is_none()
 }
 
+/// Whether the key or any of its translations contains the lowercased `query`.
+fn entry_matches(key: &str, entry: &XcStringEntry, query: &str) -> bool {
+    key.to_lowercase().contains(query)
+        || entry
+            .localizations
+            .values()
+            .any(|loc| localization_contains(loc, query))
+}
+
 fn localization_contains(loc: &XcLocalization, query: &str) -> bool {
     if loc
         .string_unit
@@ -839,863 +1559,6361 @@ fn substitution_contains(sub: &XcSubstitution, query: &str) -> bool {
     })
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TranslationRecord {
-    pub key: String,
-    pub comment: Option<String>,
-    #[serde(rename = "extractionState")]
-    pub extraction_state: Option<String>,
-    #[serde(rename = "shouldTranslate")]
-    pub should_translate: Option<bool>,
-    pub translations: IndexMap<String, TranslationValue>,
+/// Replaces every occurrence of the search text, or returns `None` when there is none.
+fn replace_text(text: &str, search: &FindReplace) -> Option<String> {
+    if search.find.is_empty() {
+        return None;
+    }
+    if search.case_sensitive {
+        return text
+            .contains(&search.find)
+            .then(|| text.replace(&search.find, &search.replace));
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut replaced = false;
+    while let Some(ch) = rest.chars().next() {
+        if let Some(len) = prefix_len_ignoring_case(rest, &search.find) {
+            result.push_str(&search.replace);
+            rest = &rest[len..];
+            replaced = true;
+        } else {
+            result.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+    replaced.then_some(result)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TranslationSummary {
-    pub key: String,
-    pub comment: Option<String>,
-    pub languages: Vec<String>,
-    #[serde(rename = "hasVariations")]
-    pub has_variations: bool,
+/// Byte length of the prefix of `text` that equals `pattern` ignoring case.
+fn prefix_len_ignoring_case(text: &str, pattern: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for expected in pattern.chars() {
+        let (_, actual) = chars.next()?;
+        if !actual.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(text.len(), |(index, _)| index))
 }
 
-#[derive(Clone)]
-pub struct XcStringsStore {
-    path: PathBuf,
-    data: Arc<RwLock<XcStringsFile>>,
+fn replace_in_unit(
+    unit: Option<&mut XcStringUnit>,
+    location: &str,
+    search: &FindReplace,
+    found: &mut Vec<(String, String, String)>,
+) {
+    let Some(value) = unit.and_then(|unit| unit.value.as_mut()) else {
+        return;
+    };
+    if let Some(after) = replace_text(value, search) {
+        let before = std::mem::replace(value, after.clone());
+        found.push((location.to_string(), before, after));
+    }
 }
 
-#[derive(Clone)]
-pub struct XcStringsStoreManager {
-    default_path: Option<PathBuf>,
-    search_root: PathBuf,
-    stores: Arc<RwLock<HashMap<PathBuf, Arc<XcStringsStore>>>>,
-    discovered_paths: Arc<RwLock<Vec<PathBuf>>>,
+fn replace_in_variations(
+    variations: &mut IndexMap<String, IndexMap<String, XcLocalization>>,
+    location: &str,
+    search: &FindReplace,
+    found: &mut Vec<(String, String, String)>,
+) {
+    for (selector, cases) in variations.iter_mut() {
+        for (case, nested) in cases.iter_mut() {
+            let nested_location = join_location(location, &format!("variations.{selector}.{case}"));
+            replace_in_localization(nested, &nested_location, search, found);
+        }
+    }
 }
 
-impl XcStringsStoreManager {
-    pub async fn new(default_path: Option<PathBuf>) -> Result<Self, StoreError> {
-        let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-        let normalized_default = default_path.map(|path| {
-            if path.is_absolute() {
-                path
-            } else {
-                cwd.join(path)
-            }
-        });
-
-        let search_root = normalized_default
-            .as_ref()
-            .and_then(|path| path.parent().map(|p| p.to_path_buf()))
-            .unwrap_or_else(|| cwd.clone());
-
-        let manager = Self {
-            default_path: normalized_default,
-            search_root,
-            stores: Arc::new(RwLock::new(HashMap::new())),
-            discovered_paths: Arc::new(RwLock::new(Vec::new())),
-        };
+fn replace_in_localization(
+    loc: &mut XcLocalization,
+    location: &str,
+    search: &FindReplace,
+    found: &mut Vec<(String, String, String)>,
+) {
+    replace_in_unit(loc.string_unit.as_mut(), location, search, found);
+    replace_in_variations(&mut loc.variations, location, search, found);
+    for (name, sub) in loc.substitutions.iter_mut() {
+        let sub_location = join_location(location, &format!("substitutions.{name}"));
+        replace_in_unit(sub.string_unit.as_mut(), &sub_location, search, found);
+        replace_in_variations(&mut sub.variations, &sub_location, search, found);
+    }
+}
 
-        manager.refresh_discovered_paths().await?;
+/// Every occurrence of `query` (ignoring case) in `key` and its translations.
+fn search_matches(key: &str, entry: &XcStringEntry, query: &str) -> Vec<SearchMatch> {
+    let mut matches = Vec::new();
+    push_search_matches(key, query, None, "", &mut matches);
+    for (language, loc) in &entry.localizations {
+        localization_search_matches(loc, query, language, "", &mut matches);
+    }
+    matches
+}
 
-        if manager.default_path.is_some() {
-            manager.store_for(None).await?;
+fn localization_search_matches(
+    loc: &XcLocalization,
+    query: &str,
+    language: &str,
+    location: &str,
+    matches: &mut Vec<SearchMatch>,
+) {
+    if let Some(value) = loc
+        .string_unit
+        .as_ref()
+        .and_then(|unit| unit.value.as_ref())
+    {
+        push_search_matches(value, query, Some(language), location, matches);
+    }
+    variations_search_matches(&loc.variations, query, language, location, matches);
+    for (name, sub) in &loc.substitutions {
+        let sub_location = join_location(location, &format!("substitutions.{name}"));
+        if let Some(value) = sub
+            .string_unit
+            .as_ref()
+            .and_then(|unit| unit.value.as_ref())
+        {
+            push_search_matches(value, query, Some(language), &sub_location, matches);
         }
-
-        Ok(manager)
+        variations_search_matches(&sub.variations, query, language, &sub_location, matches);
     }
+}
 
-    fn resolve_path(&self, raw: &str) -> PathBuf {
-        let path = PathBuf::from(raw);
-        if path.is_absolute() {
-            path
-        } else {
-            self.search_root.join(path)
+fn variations_search_matches(
+    variations: &IndexMap<String, IndexMap<String, XcLocalization>>,
+    query: &str,
+    language: &str,
+    location: &str,
+    matches: &mut Vec<SearchMatch>,
+) {
+    for (selector, cases) in variations {
+        for (case, nested) in cases {
+            let nested_location = join_location(location, &format!("variations.{selector}.{case}"));
+            localization_search_matches(nested, query, language, &nested_location, matches);
         }
     }
+}
 
-    fn normalize_path(&self, path: PathBuf) -> PathBuf {
-        std::fs::canonicalize(&path).unwrap_or(path)
+/// Records each non-overlapping occurrence of `query` in `text`, scanning like
+/// [`replace_text`] so offsets stay on character boundaries whatever the case.
+fn push_search_matches(
+    text: &str,
+    query: &str,
+    language: Option<&str>,
+    location: &str,
+    matches: &mut Vec<SearchMatch>,
+) {
+    let mut start = 0;
+    while let Some(ch) = text[start..].chars().next() {
+        match prefix_len_ignoring_case(&text[start..], query) {
+            Some(len) => {
+                matches.push(SearchMatch {
+                    language: language.map(str::to_string),
+                    location: location.to_string(),
+                    start,
+                    end: start + len,
+                    fragment: text[start..start + len].to_string(),
+                });
+                start += len;
+            }
+            None => start += ch.len_utf8(),
+        }
     }
+}
 
-    pub fn default_path(&self) -> Option<PathBuf> {
-        self.default_path.clone()
+fn join_location(parent: &str, child: &str) -> String {
+    if parent.is_empty() {
+        child.to_string()
+    } else {
+        format!("{parent}.{child}")
     }
+}
 
-    pub fn search_root(&self) -> &Path {
-        &self.search_root
-    }
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TranslationRecord {
+    pub key: String,
+    /// Position of the key in the catalog file, counting from 0, whatever order a listing
+    /// is sorted in.
+    #[serde(default)]
+    pub ordinal: usize,
+    pub comment: Option<String>,
+    #[serde(rename = "extractionState")]
+    pub extraction_state: Option<String>,
+    #[serde(rename = "shouldTranslate")]
+    pub should_translate: Option<bool>,
+    /// Structured metadata parsed from the last line of the comment.
+    #[serde(default)]
+    pub metadata: Option<KeyMetadata>,
+    pub translations: IndexMap<String, TranslationValue>,
+    /// When the key and its translations last changed through the server, if tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<KeyModified>,
+    /// Where the search query was found, when the record comes from a search.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matches: Vec<SearchMatch>,
+}
 
-    pub async fn available_paths(&self) -> Vec<PathBuf> {
-        self.discovered_paths.read().await.clone()
+impl TranslationRecord {
+    fn from_entry(ordinal: usize, key: &str, entry: &XcStringEntry) -> Self {
+        TranslationRecord {
+            key: key.to_string(),
+            ordinal,
+            comment: entry.comment.clone(),
+            metadata: entry
+                .comment
+                .as_deref()
+                .and_then(|comment| metadata::parse_comment(comment).1),
+            extraction_state: entry.extraction_state.clone(),
+            should_translate: entry.should_translate,
+            translations: entry
+                .localizations
+                .iter()
+                .map(|(lang, loc)| (lang.clone(), TranslationValue::from_localization(loc)))
+                .collect(),
+            modified: None,
+            matches: Vec::new(),
+        }
     }
+}
 
-    pub async fn refresh_discovered_paths(&self) -> Result<Vec<PathBuf>, StoreError> {
-        let root = self.search_root.clone();
-        let default_path = self.default_path.clone();
+/// One occurrence of a search query in a key or a translation, for highlighting hits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchMatch {
+    /// Language of the matching text; absent when the key itself matched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Where the text sits inside the localization, such as `variations.plural.one` or
+    /// `substitutions.count`; empty for the top-level string unit or the key.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub location: String,
+    /// Byte offset where the match starts in the key or text.
+    pub start: usize,
+    /// Byte offset just past the match.
+    pub end: usize,
+    /// The matched text as written, which may differ from the query in case.
+    pub fragment: String,
+}
 
-        let discovered = task::spawn_blocking(move || -> Result<Vec<PathBuf>, io::Error> {
-            let mut matches = discover_xcstrings(&root);
+/// A translation whose text is the one looked up by [`XcStringsStore::find_keys_by_value`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueMatch {
+    pub key: String,
+    pub language: String,
+    /// Variation case holding the text, such as `plural.one`; empty for the value itself.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub variation: String,
+    pub value: String,
+    /// Whether the text matches byte for byte, rather than ignoring case and surrounding
+    /// whitespace.
+    pub exact: bool,
+}
 
-            if let Some(default_path) = default_path {
-                let normalized = std::fs::canonicalize(&default_path).unwrap_or(default_path);
-                if !matches.iter().any(|existing| existing == &normalized) {
-                    matches.push(normalized);
-                }
-            }
+/// One key's text in a single language, as [`XcStringsStore::list_language_values`] lists
+/// it. Both fields are absent when the key has no localization in the language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageValue {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Whether the text varies by plural, device, or substitution; `get_translation`
+    /// returns the variations.
+    pub has_variations: bool,
+}
 
-            matches.sort();
-            matches.dedup();
-            Ok(matches)
-        })
-        .await
-        .map_err(|err| {
-            StoreError::ReadFailed(io::Error::new(io::ErrorKind::Other, err.to_string()))
-        })??;
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TranslationSummary {
+    pub key: String,
+    /// Position of the key in the catalog file; see [`TranslationRecord::ordinal`].
+    #[serde(default)]
+    pub ordinal: usize,
+    pub comment: Option<String>,
+    pub languages: Vec<String>,
+    #[serde(rename = "hasVariations")]
+    pub has_variations: bool,
+    /// Translation state per language; variation-only localizations report their first
+    /// nested state other than `translated`.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub states: IndexMap<String, String>,
+    /// When the key last changed through the server, if tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    /// Where the search query was found, when the summary comes from a search.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matches: Vec<SearchMatch>,
+}
 
-        {
-            let mut guard = self.discovered_paths.write().await;
-            *guard = discovered.clone();
-        }
+/// Where a new key goes in the catalog. Keys keep their order in the file, so teams can
+/// group a feature's strings together.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InsertPosition {
+    /// After every existing key.
+    #[default]
+    End,
+    /// Where it sorts among the existing keys, right before the first key that sorts after
+    /// it, as [`XcStringsStore::sort_keys`] orders them.
+    Sorted,
+    /// Right after an existing key, such as a related one: `{"after": "login.title"}`.
+    After(String),
+}
 
-        Ok(discovered)
-    }
+/// Change notifications published by stores whenever a catalog's contents change.
+/// One edit in [`XcStringsStore::apply_batch`].
+#[derive(Debug, Clone)]
+pub enum BatchOperation {
+    Upsert {
+        key: String,
+        language: String,
+        update: Box<TranslationUpdate>,
+        /// Comment for the key if the upsert creates it.
+        comment: Option<String>,
+        /// Where the key goes if the upsert creates it.
+        position: InsertPosition,
+    },
+    DeleteTranslation {
+        key: String,
+        language: String,
+    },
+    DeleteKey {
+        key: String,
+    },
+}
 
-    pub async fn store_for(&self, path: Option<&str>) -> Result<Arc<XcStringsStore>, StoreError> {
-        let resolved_path = match path {
-            Some(raw) => self.resolve_path(raw),
-            None => self.default_path.clone().ok_or(StoreError::PathRequired)?,
-        };
-        let resolved_path = self.normalize_path(resolved_path);
+/// A batch operation that succeeded, with the resulting translation for upserts.
+#[derive(Debug, Clone)]
+pub struct BatchApplied {
+    pub change: StoreChange,
+    pub value: Option<TranslationValue>,
+    /// Variations an upsert dropped because the schema doesn't allow them.
+    pub warnings: Vec<VariationWarning>,
+    /// Languages an upsert of the source text sent back to review; see
+    /// [`XcStringsStore::with_review_on_source_change`].
+    pub needs_review: Vec<String>,
+}
 
-        {
-            let stores = self.stores.read().await;
-            if let Some(store) = stores.get(&resolved_path) {
-                // Try to reload to ensure we have the latest file contents
-                // If reload fails, still return the cached store
-                let _ = store.reload().await;
-                return Ok(store.clone());
-            }
+impl BatchApplied {
+    /// Adds what this operation changed to the changes a save reports, including the
+    /// translations it sent back to review.
+    fn saved_changes(&self, changes: &mut Vec<StoreChange>) {
+        changes.push(self.change.clone());
+        if let StoreChange::TranslationUpserted { key, .. } = &self.change {
+            changes.extend(self.needs_review.iter().map(|language| {
+                StoreChange::TranslationUpserted {
+                    key: key.clone(),
+                    language: language.clone(),
+                }
+            }));
         }
-
-        let store = Arc::new(XcStringsStore::load_or_create(&resolved_path).await?);
-        let mut stores = self.stores.write().await;
-        let entry = stores
-            .entry(resolved_path.clone())
-            .or_insert_with(|| store.clone());
-        Ok(entry.clone())
-    }
-
-    pub async fn default_store(&self) -> Result<Arc<XcStringsStore>, StoreError> {
-        self.store_for(None).await
     }
 }
 
-fn discover_xcstrings(root: &Path) -> Vec<PathBuf> {
-    if !root.exists() {
-        return Vec::new();
-    }
-
-    let mut results = Vec::new();
-    let mut stack = vec![root.to_path_buf()];
+/// The translation saved by [`XcStringsStore::upsert_translation`], and the variations
+/// dropped from the update because the schema doesn't allow them.
+#[derive(Debug, Clone)]
+pub struct UpsertOutcome {
+    pub value: TranslationValue,
+    pub warnings: Vec<VariationWarning>,
+    /// Languages sent back to review because the upsert reworded the source text; see
+    /// [`XcStringsStore::with_review_on_source_change`].
+    pub needs_review: Vec<String>,
+}
 
-    while let Some(dir) = stack.pop() {
-        let entries = match std::fs::read_dir(&dir) {
-            Ok(entries) => entries,
-            Err(_) => continue,
-        };
+/// Per-operation results of a batch, in request order. The batch was written only when
+/// `committed` is true, which requires every operation to succeed.
+#[derive(Debug)]
+pub struct BatchOutcome {
+    pub committed: bool,
+    pub results: Vec<Result<BatchApplied, StoreError>>,
+}
 
-        for entry in entries.flatten() {
-            let path = entry.path();
-            let file_type = match entry.file_type() {
-                Ok(kind) => kind,
-                Err(_) => continue,
-            };
+/// One edit in [`XcStringsStoreManager::apply_transaction`]. Paths name catalogs the way
+/// [`XcStringsStoreManager::store_for`] takes them; `None` is the default catalog.
+#[derive(Debug, Clone)]
+pub enum TransactionOperation {
+    /// An edit to one catalog.
+    Edit {
+        path: Option<String>,
+        operation: BatchOperation,
+    },
+    /// Moves `key` with its comment and translations from one catalog to another, or to
+    /// `position` within the same catalog. `position` can't be after `key` itself.
+    MoveKey {
+        key: String,
+        from: Option<String>,
+        to: Option<String>,
+        position: InsertPosition,
+    },
+}
 
-            if file_type.is_dir() {
-                if let Some(name) = path.file_name().and_then(|value| value.to_str()) {
-                    let lowered = name.to_ascii_lowercase();
-                    if lowered == "target" || lowered == ".git" || lowered == "node_modules" {
-                        continue;
-                    }
-                }
-                stack.push(path);
-            } else if file_type.is_file() {
-                let is_xcstrings = path
-                    .extension()
-                    .and_then(|ext| ext.to_str())
-                    .map(|ext| ext.eq_ignore_ascii_case("xcstrings"))
-                    .unwrap_or(false);
-                if is_xcstrings {
-                    let normalized = std::fs::canonicalize(&path).unwrap_or(path);
-                    results.push(normalized);
-                }
-            }
+impl TransactionOperation {
+    fn paths(&self) -> Vec<Option<&str>> {
+        match self {
+            Self::Edit { path, .. } => vec![path.as_deref()],
+            Self::MoveKey { from, to, .. } => vec![from.as_deref(), to.as_deref()],
         }
     }
+}
 
-    results
+/// Per-operation results of a transaction, in request order; a move reports the key it
+/// created. Nothing was written unless `committed`, which requires every operation to
+/// succeed.
+#[derive(Debug)]
+pub struct TransactionOutcome {
+    pub committed: bool,
+    pub results: Vec<Result<BatchApplied, StoreError>>,
+    /// Each catalog written and what changed in it, once committed.
+    pub saved: Vec<(PathBuf, Vec<StoreChange>)>,
 }
 
-impl XcStringsStore {
-    pub async fn load_or_create(path: impl AsRef<Path>) -> Result<Self, StoreError> {
-        let path = path.as_ref().to_path_buf();
-        if let Some(parent) = path.parent() {
-            if !parent.exists() {
-                fs::create_dir_all(parent).await?;
-            }
-        }
+/// What [`XcStringsStore::apply_patch`] changed, and what it adjusted to fit the schema.
+#[derive(Debug, Clone, Default)]
+pub struct PatchOutcome {
+    pub changes: Vec<StoreChange>,
+    /// Variations dropped and states corrected, with paths prefixed by key and language.
+    pub warnings: Vec<VariationWarning>,
+}
 
-        let mut doc = if path.exists() {
-            let raw = fs::read_to_string(&path).await?;
-            let value: serde_json::Value = serde_json::from_str(&raw)?;
-            XcStringsFile::from_json_value(value)?
-        } else {
-            XcStringsFile::default()
-        };
+/// Parameters for [`XcStringsStore::find_replace`].
+#[derive(Debug, Clone, Default)]
+pub struct FindReplace {
+    pub find: String,
+    pub replace: String,
+    pub case_sensitive: bool,
+    /// Only replace in this language's translations.
+    pub language: Option<String>,
+}
 
-        normalize_strings_file(&mut doc);
+/// One string value rewritten by a find/replace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct Replacement {
+    pub key: String,
+    pub language: String,
+    /// Where the value sits inside the localization, such as `variations.plural.one`;
+    /// empty for the top-level string unit.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub location: String,
+    pub before: String,
+    pub after: String,
+}
 
-        Ok(Self {
-            path,
-            data: Arc::new(RwLock::new(doc)),
-        })
-    }
+/// A translation touched by [`XcStringsStore::merge_from`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MergedTranslation {
+    pub key: String,
+    pub language: String,
+}
 
-    pub fn path(&self) -> &Path {
-        &self.path
-    }
+/// What merging another catalog changed, or would change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MergeOutcome {
+    /// Keys missing from the target, copied over with every localization and comment.
+    pub added_keys: Vec<String>,
+    /// Translations the target lacked, or replaced because `overwrite` was set.
+    pub merged: Vec<MergedTranslation>,
+    /// Translations that differ from the target's but were kept because `overwrite` wasn't set.
+    pub conflicts: Vec<MergedTranslation>,
+}
 
-    pub async fn reload(&self) -> Result<(), StoreError> {
-        let raw = fs::read_to_string(&self.path).await?;
-        let value: serde_json::Value = serde_json::from_str(&raw)?;
-        let mut doc = XcStringsFile::from_json_value(value)?;
-        normalize_strings_file(&mut doc);
-        *self.data.write().await = doc;
-        Ok(())
+impl MergeOutcome {
+    pub fn is_empty(&self) -> bool {
+        self.added_keys.is_empty() && self.merged.is_empty()
     }
+}
 
-    pub async fn list_languages(&self) -> Vec<String> {
-        let doc = self.data.read().await;
-        let mut langs: BTreeSet<String> = BTreeSet::new();
-        langs.insert(doc.source_language.clone());
-        for entry in doc.strings.values() {
-            langs.extend(entry.localizations.keys().cloned());
-        }
-        langs.into_iter().collect()
-    }
+/// A translation looked up with locale fallback by [`XcStringsStore::get_translation_resolved`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedTranslation {
+    pub requested_language: String,
+    /// The locale that supplied `value`: the requested one, a parent such as `fr` for
+    /// `fr-CA`, or the source language.
+    pub resolved_language: String,
+    pub fallback: bool,
+    pub value: TranslationValue,
+}
 
-    /// Returns a map of languages to their untranslated keys.
-    /// A translation is considered untranslated if:
-    /// - The value is empty/None
-    /// - No localization exists for that language
-    pub async fn list_untranslated(&self) -> HashMap<String, Vec<String>> {
-        let doc = self.data.read().await;
-        let mut result: HashMap<String, Vec<String>> = HashMap::new();
+/// A key moved by [`XcStringsStore::rename_namespace`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedKey {
+    pub old_key: String,
+    pub new_key: String,
+}
 
-        // Get all languages
-        let mut langs: BTreeSet<String> = BTreeSet::new();
-        langs.insert(doc.source_language.clone());
-        for entry in doc.strings.values() {
-            langs.extend(entry.localizations.keys().cloned());
-        }
+/// What renaming a namespace changed, or would change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct NamespaceRename {
+    pub renamed: Vec<RenamedKey>,
+    /// Existing keys the rename would overwrite; when non-empty nothing is written.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub collisions: Vec<String>,
+}
 
-        // For each key, check which languages have untranslated values
-        for (key, entry) in doc.strings.iter() {
-            // Check each language for untranslated status
-            for lang in langs.iter() {
-                let is_untranslated = if let Some(localization) = entry.localizations.get(lang) {
-                    match extract_translation_value(localization) {
-                        None => true,                            // No value
-                        Some(value) if value.is_empty() => true, // Empty value
-                        Some(_) => false, // Has a value (even if same as other languages)
-                    }
-                } else {
-                    true // No localization for this language
-                };
-
-                if is_untranslated {
-                    result
-                        .entry(lang.clone())
-                        .or_insert_with(Vec::new)
-                        .push(key.clone());
-                }
-            }
-        }
-
-        result
-    }
-
-    /// Returns a map of languages to their translation percentage (0-100)
-    /// Keys marked as should_translate=false are excluded from the calculation
-    /// A translation is considered complete if it has a non-empty value
-    pub async fn get_translation_percentages(&self) -> HashMap<String, f64> {
-        let doc = self.data.read().await;
-        let mut result: HashMap<String, f64> = HashMap::new();
+/// Keys grouped under a dot-separated prefix, from [`XcStringsStore::key_namespaces`].
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyNamespace {
+    /// Full prefix such as `checkout.payment`; empty for the root.
+    pub namespace: String,
+    /// Keys at any depth below this namespace.
+    pub total_keys: usize,
+    /// Keys directly inside this namespace, like `checkout.title` for `checkout`.
+    pub direct_keys: usize,
+    /// Percentage of the translatable keys below this namespace with a value, per language.
+    pub completion: BTreeMap<String, f64>,
+    #[schema(no_recursion)]
+    pub children: Vec<KeyNamespace>,
+}
 
-        // Get all languages
-        let mut langs: BTreeSet<String> = BTreeSet::new();
-        langs.insert(doc.source_language.clone());
-        for entry in doc.strings.values() {
-            langs.extend(entry.localizations.keys().cloned());
-        }
+#[derive(Default)]
+struct NamespaceCounts {
+    total: usize,
+    direct: usize,
+    translatable: usize,
+    translated: BTreeMap<String, usize>,
+    children: BTreeMap<String, NamespaceCounts>,
+}
 
-        // Count only keys that should be translated
-        let translatable_keys: Vec<&String> = doc
-            .strings
+impl NamespaceCounts {
+    fn into_namespace(self, namespace: String, languages: &BTreeSet<String>) -> KeyNamespace {
+        let completion = languages
             .iter()
-            .filter(|(_, entry)| entry.should_translate.unwrap_or(true))
-            .map(|(key, _)| key)
+            .filter(|_| self.translatable > 0)
+            .map(|language| {
+                let translated = self.translated.get(language).copied().unwrap_or(0);
+                let percentage = translated as f64 / self.translatable as f64 * 100.0;
+                (language.clone(), percentage)
+            })
             .collect();
-
-        if translatable_keys.is_empty() {
-            return result;
+        let children = self
+            .children
+            .into_iter()
+            .map(|(segment, counts)| {
+                let child = if namespace.is_empty() {
+                    segment
+                } else {
+                    format!("{namespace}.{segment}")
+                };
+                counts.into_namespace(child, languages)
+            })
+            .collect();
+        KeyNamespace {
+            namespace,
+            total_keys: self.total,
+            direct_keys: self.direct,
+            completion,
+            children,
         }
+    }
+}
 
-        let total_keys = translatable_keys.len() as f64;
+/// How a regional locale relates to the parent it falls back to, from
+/// [`XcStringsStore::regional_overrides`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionalOverrides {
+    pub language: String,
+    pub parent: String,
+    /// Keys whose regional text differs from what the parent supplies.
+    pub overridden_keys: Vec<String>,
+    /// Keys whose regional text matches the parent and could be dropped to inherit it.
+    pub redundant_keys: Vec<String>,
+    /// Keys without a regional value, which already fall back to the parent.
+    pub inherited: usize,
+}
 
-        for lang in langs.iter() {
-            let mut translated_count = 0;
+/// What a save changed, so listeners can update without re-reading the catalog.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StoreChange {
+    TranslationUpserted {
+        key: String,
+        language: String,
+    },
+    TranslationDeleted {
+        key: String,
+        language: String,
+    },
+    KeyCreated {
+        key: String,
+    },
+    KeyDeleted {
+        key: String,
+    },
+    KeyRenamed {
+        #[serde(rename = "oldKey")]
+        old_key: String,
+        #[serde(rename = "newKey")]
+        new_key: String,
+    },
+    CommentUpdated {
+        key: String,
+    },
+    ExtractionStateUpdated {
+        key: String,
+    },
+    ShouldTranslateUpdated {
+        key: String,
+    },
+    LanguageAdded {
+        language: String,
+    },
+    LanguageRemoved {
+        language: String,
+    },
+    LanguageRenamed {
+        #[serde(rename = "oldLanguage")]
+        old_language: String,
+        #[serde(rename = "newLanguage")]
+        new_language: String,
+    },
+    SnapshotRestored {
+        label: String,
+    },
+    /// Keys reordered alphabetically without changing their contents.
+    KeysSorted,
+    /// The file was rewritten in canonical formatting without changing its contents.
+    Reformatted,
+    /// Structural problems in the file were fixed by [`XcStringsStore::repair`].
+    Repaired,
+    /// Several edits saved together by a batch request.
+    Batch {
+        #[schema(no_recursion)]
+        changes: Vec<StoreChange>,
+    },
+}
 
-            for key in translatable_keys.iter() {
-                let entry = &doc.strings[*key];
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StoreEvent {
+    /// The catalog was written by this server.
+    Saved { path: PathBuf, change: StoreChange },
+    /// The catalog was modified on disk by another process and has been reloaded.
+    ExternalChange { path: PathBuf },
+    /// A background QA sweep found errors or warnings the previous sweep didn't report.
+    QaFindings {
+        path: PathBuf,
+        findings: Vec<Finding>,
+    },
+}
 
-                // Check if this language has a valid translation (non-empty value)
-                let is_translated = if let Some(localization) = entry.localizations.get(lang) {
-                    match extract_translation_value(localization) {
-                        None => false,
-                        Some(value) if value.is_empty() => false,
-                        Some(_) => true, // Has a non-empty value
-                    }
-                } else {
-                    false
-                };
+impl StoreEvent {
+    pub fn path(&self) -> &Path {
+        match self {
+            StoreEvent::Saved { path, .. }
+            | StoreEvent::ExternalChange { path }
+            | StoreEvent::QaFindings { path, .. } => path,
+        }
+    }
+}
 
-                if is_translated {
-                    translated_count += 1;
-                }
-            }
+const EVENT_CHANNEL_CAPACITY: usize = 256;
 
-            let percentage = (translated_count as f64 / total_keys) * 100.0;
-            result.insert(lang.clone(), percentage);
-        }
+/// Cheap snapshot of on-disk metadata used to detect writes made by other processes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileFingerprint {
+    modified: Option<SystemTime>,
+    len: u64,
+}
 
-        result
+impl FileFingerprint {
+    async fn read(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).await.ok()?;
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
     }
+}
 
-    pub async fn add_language(&self, language: &str) -> Result<(), StoreError> {
-        let trimmed = language.trim();
-        if trimmed.is_empty() {
-            return Err(StoreError::InvalidLanguage(
-                "Language code cannot be empty".to_string(),
-            ));
-        }
-        let language = trimmed.to_string();
+#[derive(Clone)]
+pub struct XcStringsStore {
+    path: PathBuf,
+    /// The catalog. Saves serialize a shared snapshot after releasing the lock, so reads
+    /// aren't held up by them.
+    data: Arc<RwLock<Arc<XcStringsFile>>>,
+    /// Held from the start of an edit until its save is serialized, so the next edit
+    /// doesn't have to copy a snapshot that's still being read. Reloads take it too, and
+    /// it's always taken before `fingerprint` and `data`.
+    edit_lock: Arc<Mutex<()>>,
+    fingerprint: Arc<Mutex<Option<FileFingerprint>>>,
+    /// Incremented by every committed edit; see [`PendingSave`].
+    generation: Arc<AtomicU64>,
+    /// Generation of the last version written to disk, so a save that finishes after a newer
+    /// one doesn't overwrite it. Only updated while holding `fingerprint`.
+    written_generation: Arc<AtomicU64>,
+    /// Hash of the catalog as last read or committed, exposed as [`Self::revision`]. Edits
+    /// set it before releasing the edit lock, so the next edit compares against it.
+    revision: Arc<AtomicU64>,
+    /// Revisions an edit must start from; see [`Self::expecting`].
+    expected: Option<Arc<[String]>>,
+    /// Hash of the catalog as the last edit through an [`Self::expecting`] handle left it,
+    /// or `0`.
+    written: Arc<AtomicU64>,
+    events: broadcast::Sender<StoreEvent>,
+    /// Rejects every edit and snapshot with [`StoreError::ReadOnly`] when set.
+    read_only: bool,
+    /// Read-only path rule matching this catalog, from [`XcStringsStoreManager::with_read_only_paths`].
+    protected_by: Option<String>,
+    /// Whether edits must follow the review workflow; see [`Self::with_review_workflow`].
+    review_workflow: bool,
+    /// Whether rewording the source sends translations back to review; see
+    /// [`Self::with_review_on_source_change`].
+    review_on_source_change: bool,
+    /// Languages refusing edits unless `force` is set; mirrors [`Self::frozen_path`].
+    frozen: Arc<std::sync::RwLock<FrozenLanguages>>,
+    /// Lets edits through to frozen languages; see [`Self::forcing`].
+    force: bool,
+    /// When keys and translations last changed; mirrors [`Self::modified_path`].
+    modified: Arc<std::sync::RwLock<ModifiedIndex>>,
+    /// Serializes read-modify-write cycles of the sidecar files.
+    sidecar_lock: Arc<Mutex<()>>,
+    /// Layout used whenever the catalog is written.
+    format: FormatOptions,
+    /// What upserts do with unknown states; see [`Self::with_state_validation`].
+    state_validation: StateValidation,
+    /// What upserts do with mismatched format specifiers; see
+    /// [`Self::with_placeholder_validation`].
+    placeholder_validation: PlaceholderValidation,
+    /// Whether new keys need a comment; see [`Self::with_require_comment`].
+    require_comment: bool,
+    /// Escaping and line endings of the file as last read, which saves keep.
+    style: Arc<std::sync::Mutex<SourceStyle>>,
+    /// Entries an edit removed wholesale, such as keys `remove_language` left empty, for
+    /// the next save to report.
+    normalized: Arc<std::sync::Mutex<Vec<NormalizedEntry>>>,
+}
 
-        let mut doc = self.data.write().await;
+/// Path of a catalog's `<dir>/<file stem>.<kind>.json` sidecar file.
+fn sidecar_path(catalog: &Path, kind: &str) -> PathBuf {
+    let parent = catalog.parent().unwrap_or_else(|| Path::new("."));
+    let stem = catalog
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "catalog".to_string());
+    parent.join(format!("{stem}.{kind}.json"))
+}
 
-        // Check if language already exists
-        let mut existing_langs: BTreeSet<String> = BTreeSet::new();
-        existing_langs.insert(doc.source_language.clone());
-        for entry in doc.strings.values() {
-            existing_langs.extend(entry.localizations.keys().cloned());
+/// Writes every file or none: the new contents go to temporary files beside the targets,
+/// which are then renamed into place. If a rename fails, the files already replaced get
+/// their previous contents back.
+async fn replace_files(files: &[(&Path, &str)]) -> io::Result<()> {
+    let mut originals = Vec::with_capacity(files.len());
+    for (path, _) in files {
+        originals.push(match fs::read(path).await {
+            Ok(raw) => Some(raw),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err),
+        });
+    }
+    let temps: Vec<PathBuf> = files
+        .iter()
+        .map(|(path, _)| transaction_temp_path(path))
+        .collect();
+    for (index, ((_, contents), temp)) in files.iter().zip(&temps).enumerate() {
+        if let Err(err) = fs::write(temp, contents).await {
+            for temp in &temps[..index] {
+                let _ = fs::remove_file(temp).await;
+            }
+            return Err(err);
         }
-
-        if existing_langs.contains(&language) {
-            return Err(StoreError::LanguageExists(language));
+    }
+    for (index, ((path, _), temp)) in files.iter().zip(&temps).enumerate() {
+        if let Err(err) = fs::rename(temp, path).await {
+            for ((path, _), original) in files[..index].iter().zip(&originals) {
+                let _ = match original {
+                    Some(raw) => fs::write(path, raw).await,
+                    None => fs::remove_file(path).await,
+                };
+            }
+            for temp in &temps[index..] {
+                let _ = fs::remove_file(temp).await;
+            }
+            return Err(err);
         }
+    }
+    Ok(())
+}
 
-        // Add placeholder localizations for the new language so editors can immediately
-        // surface translation slots without clobbering existing values.
-        for entry in doc.strings.values_mut() {
-            entry
-                .localizations
-                .entry(language.clone())
-                .or_insert_with(placeholder_localization);
-        }
+/// Saves edits staged across `stores` as one unit. `edits` are the stores' edit locks,
+/// held since the documents were read, so readers keep the old catalogs while the new ones
+/// are serialized. The files are swapped in by [`replace_files`] under every store's
+/// `fingerprint` lock, taken in the stores' order (sorted by path), and each version is
+/// marked written there, so a save of an earlier edit still in flight can't overwrite it.
+/// Only then is the catalog in memory replaced; if writing fails it's left as it was.
+async fn commit_documents(
+    stores: &[Arc<XcStringsStore>],
+    edits: Vec<OwnedMutexGuard<()>>,
+    mut staged: Vec<XcStringsFile>,
+    changes: Vec<Vec<StoreChange>>,
+) -> Result<Vec<(PathBuf, Vec<StoreChange>)>, StoreError> {
+    let mut serialized = Vec::with_capacity(stores.len());
+    for (store, doc) in stores.iter().zip(&mut staged) {
+        store.normalize(doc);
+        serialized.push(store.format_json(&doc.to_json_value()));
+    }
 
-        normalize_strings_file(&mut doc);
-        let json_value = doc.to_json_value();
-        let serialized = apple_json_formatter::to_apple_format(&json_value);
-        drop(doc);
-        fs::write(&self.path, serialized).await?;
-        Ok(())
+    let mut fingerprints = Vec::with_capacity(stores.len());
+    for store in stores {
+        fingerprints.push(store.fingerprint.lock().await);
     }
+    let files: Vec<(&Path, &str)> = stores
+        .iter()
+        .zip(&serialized)
+        .map(|(store, serialized)| (store.path(), serialized.as_str()))
+        .collect();
+    replace_files(&files).await?;
+
+    let mut saves = Vec::with_capacity(stores.len());
+    for ((((store, edit), staged), serialized), fingerprint) in stores
+        .iter()
+        .zip(edits)
+        .zip(staged)
+        .zip(serialized)
+        .zip(&mut fingerprints)
+    {
+        let mut doc = store.write_doc_with(edit).await;
+        doc.replace(staged);
+        let PendingSave {
+            edit,
+            generation,
+            normalized,
+            ..
+        } = doc.commit();
+        store.record_revision(&serialized);
+        store
+            .written_generation
+            .store(generation, Ordering::Release);
+        **fingerprint = FileFingerprint::read(store.path()).await;
+        drop(edit);
+        saves.push(SerializedSave {
+            serialized,
+            generation,
+            normalized,
+        });
+    }
+    drop(fingerprints);
 
-    pub async fn remove_language(&self, language: &str) -> Result<(), StoreError> {
-        let trimmed = language.trim();
-        if trimmed.is_empty() {
-            return Err(StoreError::InvalidLanguage(
-                "Language code cannot be empty".to_string(),
-            ));
-        }
-        let language = trimmed.to_string();
+    let mut saved = Vec::with_capacity(stores.len());
+    for ((store, save), changes) in stores.iter().zip(saves).zip(changes) {
+        let change = StoreChange::Batch {
+            changes: changes.clone(),
+        };
+        store.write_serialized(save, change).await?;
+        saved.push((store.path().to_path_buf(), changes));
+    }
+    Ok(saved)
+}
 
-        let mut doc = self.data.write().await;
+/// Hidden file beside `catalog` that a transaction writes before renaming it into place.
+fn transaction_temp_path(catalog: &Path) -> PathBuf {
+    let name = catalog
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "catalog".to_string());
+    catalog.with_file_name(format!(".{name}.transaction"))
+}
 
-        // Cannot remove the source language
-        if language == doc.source_language {
-            return Err(StoreError::CannotRemoveSourceLanguage(language));
+/// Stamps what `change` touched in `index` with `at`, returning whether anything changed.
+/// Sorting, reformatting, adding a language, and restoring a snapshot leave it alone.
+fn apply_modified(index: &mut ModifiedIndex, change: &StoreChange, at: &str) -> bool {
+    match change {
+        StoreChange::TranslationUpserted { key, language } => index.touch(key, Some(language), at),
+        StoreChange::TranslationDeleted { key, language } => {
+            index.remove_translation(key, language, at)
         }
-
-        // Check if language exists
-        let mut language_exists = false;
-        for entry in doc.strings.values() {
-            if entry.localizations.contains_key(language.as_str()) {
-                language_exists = true;
-                break;
+        StoreChange::KeyCreated { key }
+        | StoreChange::CommentUpdated { key }
+        | StoreChange::ExtractionStateUpdated { key }
+        | StoreChange::ShouldTranslateUpdated { key } => index.touch(key, None, at),
+        StoreChange::KeyDeleted { key } => index.remove_key(key),
+        StoreChange::KeyRenamed { old_key, new_key } => index.rename_key(old_key, new_key, at),
+        StoreChange::LanguageRemoved { language } => index.remove_language(language),
+        StoreChange::LanguageRenamed {
+            old_language,
+            new_language,
+        } => index.rename_language(old_language, new_language),
+        StoreChange::LanguageAdded { .. }
+        | StoreChange::SnapshotRestored { .. }
+        | StoreChange::KeysSorted
+        | StoreChange::Reformatted
+        | StoreChange::Repaired => return false,
+        StoreChange::Batch { changes } => {
+            let mut applied = false;
+            for change in changes {
+                applied |= apply_modified(index, change, at);
             }
+            return applied;
         }
+    }
+    true
+}
 
-        if !language_exists {
-            return Err(StoreError::LanguageMissing(language.clone()));
-        }
+/// Write access to a store's catalog, ended by [`Self::commit`].
+struct DocWriteGuard<'a> {
+    doc: RwLockWriteGuard<'a, Arc<XcStringsFile>>,
+    edit: OwnedMutexGuard<()>,
+    store: &'a XcStringsStore,
+}
 
-        // Remove the language from all string entries
-        for entry in doc.strings.values_mut() {
-            entry.localizations.shift_remove(language.as_str());
+impl DocWriteGuard<'_> {
+    /// Swaps in a whole new catalog.
+    fn replace(&mut self, doc: XcStringsFile) {
+        *self.doc = Arc::new(doc);
+    }
+
+    /// Ends the edit, releasing the catalog to readers, and returns the version to save.
+    /// The version is numbered while the lock is held, so saves can't be reordered on disk.
+    fn commit(self) -> PendingSave {
+        let generation = self.store.generation.fetch_add(1, Ordering::AcqRel) + 1;
+        let normalized = std::mem::take(
+            &mut *self
+                .store
+                .normalized
+                .lock()
+                .unwrap_or_else(|err| err.into_inner()),
+        );
+        PendingSave {
+            doc: Arc::clone(&self.doc),
+            edit: self.edit,
+            generation,
+            normalized,
         }
+    }
+}
 
-        // Remove any string entries that have no localizations left
-        doc.strings
-            .retain(|_, entry| !entry.localizations.is_empty());
+impl std::ops::Deref for DocWriteGuard<'_> {
+    type Target = XcStringsFile;
 
-        normalize_strings_file(&mut doc);
-        let json_value = doc.to_json_value();
-        let serialized = apple_json_formatter::to_apple_format(&json_value);
-        drop(doc);
-        fs::write(&self.path, serialized).await?;
-        Ok(())
+    fn deref(&self) -> &XcStringsFile {
+        &self.doc
     }
+}
 
-    pub async fn update_language(
-        &self,
-        old_language: &str,
-        new_language: &str,
-    ) -> Result<(), StoreError> {
-        let old_trimmed = old_language.trim();
-        if old_trimmed.is_empty() {
-            return Err(StoreError::InvalidLanguage(
-                "Language code cannot be empty".to_string(),
-            ));
-        }
-        let new_trimmed = new_language.trim();
-        if new_trimmed.is_empty() {
-            return Err(StoreError::InvalidLanguage(
-                "Language code cannot be empty".to_string(),
-            ));
-        }
-
-        if old_trimmed == new_trimmed {
-            return Ok(()); // No change needed
-        }
+impl std::ops::DerefMut for DocWriteGuard<'_> {
+    fn deref_mut(&mut self) -> &mut XcStringsFile {
+        // Only copies when a snapshot from `to_apple_json` is still alive.
+        Arc::make_mut(&mut self.doc)
+    }
+}
 
-        let old_language = old_trimmed.to_string();
-        let new_language = new_trimmed.to_string();
+/// An edited catalog waiting to be serialized and written, with what normalization dropped
+/// from it.
+struct PendingSave {
+    doc: Arc<XcStringsFile>,
+    edit: OwnedMutexGuard<()>,
+    generation: u64,
+    normalized: Vec<NormalizedEntry>,
+}
 
-        let mut doc = self.data.write().await;
+/// A committed edit in its on-disk form.
+struct SerializedSave {
+    serialized: String,
+    generation: u64,
+    normalized: Vec<NormalizedEntry>,
+}
 
-        // Cannot rename the source language
-        if old_language == doc.source_language {
-            return Err(StoreError::CannotRenameSourceLanguage(old_language));
-        }
+fn format_revision(hash: u64) -> String {
+    format!("\"{hash:016x}\"")
+}
 
-        // Check if old language exists
-        let mut old_language_exists = false;
-        for entry in doc.strings.values() {
-            if entry.localizations.contains_key(old_language.as_str()) {
-                old_language_exists = true;
-                break;
-            }
-        }
+fn content_hash(contents: &str) -> u64 {
+    let mut hasher = ContentHasher::default();
+    hasher.update(contents.as_bytes());
+    hasher.0
+}
 
-        if !old_language_exists {
-            return Err(StoreError::LanguageMissing(old_language));
-        }
+/// FNV-1a over a catalog's bytes, which hashes the same however they're split into reads.
+struct ContentHasher(u64);
 
-        // Check if new language already exists
-        let mut new_language_exists = false;
-        for entry in doc.strings.values() {
-            if entry.localizations.contains_key(new_language.as_str()) {
-                new_language_exists = true;
-                break;
-            }
-        }
+impl Default for ContentHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
 
-        if new_language_exists {
-            return Err(StoreError::LanguageExists(new_language.clone()));
+impl ContentHasher {
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
         }
+    }
+}
 
-        // Rename the language in all string entries
-        for entry in doc.strings.values_mut() {
-            if let Some(localization) = entry.localizations.shift_remove(old_language.as_str()) {
-                entry
-                    .localizations
-                    .insert(new_language.clone(), localization);
-            }
-        }
+/// Hashes a catalog file and finds its style as [`read_catalog`] parses it.
+struct SniffingReader<R> {
+    inner: R,
+    hasher: ContentHasher,
+    style: StyleDetector,
+}
 
-        normalize_strings_file(&mut doc);
-        let json_value = doc.to_json_value();
-        let serialized = apple_json_formatter::to_apple_format(&json_value);
-        drop(doc);
-        fs::write(&self.path, serialized).await?;
-        Ok(())
+impl<R: io::Read> io::Read for SniffingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        self.style.feed(&buf[..read]);
+        Ok(read)
     }
+}
 
-    pub async fn list_records(&self, filter: Option<&str>) -> Vec<TranslationRecord> {
-        let query = filter.map(|s| s.to_lowercase());
-        let doc = self.data.read().await;
-        doc.strings
-            .iter()
-            .filter_map(|(key, entry)| {
-                if let Some(q) = &query {
-                    let matches_key = key.to_lowercase().contains(q);
-                    let matches_value = entry
-                        .localizations
-                        .values()
-                        .any(|loc| localization_contains(loc, q));
-                    if !matches_key && !matches_value {
-                        return None;
-                    }
-                }
+/// Streams the catalog at `path` straight into typed entries, returning it with its
+/// revision hash and style. Both come from the bytes as they're read, so the text of a
+/// large catalog is never held in memory alongside the parsed one.
+fn read_catalog(path: &Path) -> Result<(XcStringsFile, u64, SourceStyle), StoreError> {
+    let mut reader = io::BufReader::new(SniffingReader {
+        inner: std::fs::File::open(path)?,
+        hasher: ContentHasher::default(),
+        style: StyleDetector::default(),
+    });
+    let doc = serde_json::from_reader(&mut reader).map_err(|err| {
+        if err.is_io() {
+            StoreError::ReadFailed(err.into())
+        } else {
+            StoreError::SerdeFailed(err)
+        }
+    })?;
+    let sniffer = reader.into_inner();
+    Ok((doc, sniffer.hasher.0, sniffer.style.finish()))
+}
 
-                let translations = entry
-                    .localizations
-                    .iter()
-                    .map(|(lang, loc)| (lang.clone(), TranslationValue::from_localization(loc)))
-                    .collect();
+/// [`read_catalog`] off the async runtime.
+async fn load_catalog(path: &Path) -> Result<(XcStringsFile, u64, SourceStyle), StoreError> {
+    let path = path.to_path_buf();
+    task::spawn_blocking(move || read_catalog(&path))
+        .await
+        .map_err(|err| StoreError::ReadFailed(io::Error::other(err)))?
+}
 
-                Some(TranslationRecord {
-                    key: key.clone(),
-                    comment: entry.comment.clone(),
-                    extraction_state: entry.extraction_state.clone(),
-                    should_translate: entry.should_translate,
-                    translations,
-                })
-            })
-            .collect()
-    }
+#[derive(Clone)]
+pub struct XcStringsStoreManager {
+    default_path: Option<PathBuf>,
+    search_root: PathBuf,
+    stores: Arc<RwLock<HashMap<PathBuf, Arc<XcStringsStore>>>>,
+    discovered_paths: Arc<RwLock<Vec<PathBuf>>>,
+    /// Catalogs outside the search root added with [`Self::register_path`].
+    registered_paths: Arc<RwLock<Vec<PathBuf>>>,
+    /// Xcode project or workspace whose build phases decide which catalogs are discovered.
+    project: Option<PathBuf>,
+    /// Targets building each discovered catalog, when a project is set.
+    catalog_targets: Arc<RwLock<HashMap<PathBuf, Vec<String>>>>,
+    /// Labels set by hand per catalog path; see [`Self::with_catalog_labels`].
+    catalog_labels: HashMap<String, String>,
+    events: broadcast::Sender<StoreEvent>,
+    audit_log: Option<Arc<AuditLog>>,
+    read_only: bool,
+    /// Glob patterns of catalogs that refuse edits; see [`Self::with_read_only_paths`].
+    read_only_paths: Vec<String>,
+    review_workflow: bool,
+    review_on_source_change: bool,
+    git_auto_commit: bool,
+    /// Serializes auto-commits so concurrent changes don't race for the git index.
+    #[cfg(feature = "git")]
+    git_lock: Arc<Mutex<()>>,
+    /// Latest background QA report per catalog; see [`Self::sweep_qa`].
+    qa_sweeps: Arc<RwLock<HashMap<PathBuf, QaReport>>>,
+    format: FormatOptions,
+    state_validation: StateValidation,
+    placeholder_validation: PlaceholderValidation,
+    require_comment: bool,
+}
 
-    pub async fn list_summaries(&self, filter: Option<&str>) -> Vec<TranslationSummary> {
-        let query = filter.map(|s| s.to_lowercase());
-        let doc = self.data.read().await;
-        doc.strings
-            .iter()
-            .filter_map(|(key, entry)| {
-                if let Some(q) = &query {
-                    let matches_key = key.to_lowercase().contains(q);
-                    let matches_value = entry
-                        .localizations
-                        .values()
-                        .any(|loc| localization_contains(loc, q));
-                    if !matches_key && !matches_value {
-                        return None;
-                    }
-                }
+impl XcStringsStoreManager {
+    pub async fn new(default_path: Option<PathBuf>) -> Result<Self, StoreError> {
+        let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let normalized_default = default_path.map(|path| {
+            if path.is_absolute() {
+                path
+            } else {
+                cwd.join(path)
+            }
+        });
 
-                let languages = entry.localizations.keys().cloned().collect();
-                let has_variations = entry
-                    .localizations
-                    .values()
-                    .any(|loc| !loc.variations.is_empty() || !loc.substitutions.is_empty());
+        let search_root = normalized_default
+            .as_ref()
+            .and_then(|path| path.parent().map(|p| p.to_path_buf()))
+            .unwrap_or_else(|| cwd.clone());
 
-                Some(TranslationSummary {
-                    key: key.clone(),
-                    comment: entry.comment.clone(),
-                    languages,
-                    has_variations,
-                })
-            })
-            .collect()
-    }
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let manager = Self {
+            default_path: normalized_default,
+            search_root,
+            stores: Arc::new(RwLock::new(HashMap::new())),
+            discovered_paths: Arc::new(RwLock::new(Vec::new())),
+            registered_paths: Arc::new(RwLock::new(Vec::new())),
+            project: None,
+            catalog_targets: Arc::new(RwLock::new(HashMap::new())),
+            catalog_labels: HashMap::new(),
+            events,
+            audit_log: None,
+            read_only: false,
+            read_only_paths: Vec::new(),
+            review_workflow: false,
+            review_on_source_change: false,
+            git_auto_commit: false,
+            #[cfg(feature = "git")]
+            git_lock: Arc::new(Mutex::new(())),
+            qa_sweeps: Arc::new(RwLock::new(HashMap::new())),
+            format: FormatOptions::default(),
+            state_validation: StateValidation::default(),
+            placeholder_validation: PlaceholderValidation::default(),
+            require_comment: false,
+        };
 
-    pub async fn get_translation(
-        &self,
-        key: &str,
-        language: &str,
-    ) -> Result<Option<TranslationValue>, StoreError> {
-        let doc = self.data.read().await;
-        Ok(doc
-            .strings
-            .get(key)
-            .and_then(|entry| entry.localizations.get(language))
-            .map(TranslationValue::from_localization))
-    }
+        manager.refresh_discovered_paths().await?;
 
-    pub async fn upsert_translation(
-        &self,
-        key: &str,
-        language: &str,
-        update: TranslationUpdate,
-    ) -> Result<TranslationValue, StoreError> {
-        let mut doc = self.data.write().await;
-        let entry = doc
-            .strings
-            .entry(key.to_string())
-            .or_insert_with(XcStringEntry::default);
+        if manager.default_path.is_some() {
+            manager.store_for(None).await?;
+        }
 
-        let loc = entry
-            .localizations
-            .entry(language.to_string())
-            .or_insert_with(XcLocalization::default);
+        Ok(manager)
+    }
 
-        apply_update(loc, update);
+    /// Records changes made by MCP sessions and the web API in one shared audit log.
+    pub fn with_audit_log(mut self, audit_log: AuditLog) -> Self {
+        self.audit_log = Some(Arc::new(audit_log));
+        self
+    }
 
-        let updated = TranslationValue::from_localization(loc);
+    pub fn audit_log(&self) -> Option<&AuditLog> {
+        self.audit_log.as_deref()
+    }
 
-        normalize_strings_file(&mut doc);
-        let json_value = doc.to_json_value();
-        let serialized = apple_json_formatter::to_apple_format(&json_value);
-        drop(doc);
-        fs::write(&self.path, serialized).await?;
+    /// Commits each recorded change to the git repository holding its catalog, along with
+    /// the catalog's assignment, frozen-language, and modified-index sidecars.
+    #[cfg(feature = "git")]
+    pub fn with_git_auto_commit(mut self, git_auto_commit: bool) -> Self {
+        self.git_auto_commit = git_auto_commit;
+        self
+    }
 
-        Ok(updated)
+    /// Whether [`Self::record_change`] does anything, so callers can skip building entries.
+    pub fn records_changes(&self) -> bool {
+        self.audit_log.is_some() || self.git_auto_commit
     }
 
-    pub async fn delete_translation(&self, key: &str, language: &str) -> Result<(), StoreError> {
-        let mut doc = self.data.write().await;
-        let translation_exists = if let Some(entry) = doc.strings.get_mut(key) {
-            if entry.localizations.shift_remove(language).is_some() {
-                if entry.localizations.is_empty() {
-                    doc.strings.shift_remove(key);
-                }
-                true
-            } else {
-                false
+    /// Appends a successful change to the audit log and, with git auto-commit on, commits
+    /// its catalog. Failures are logged rather than returned, since the change itself is
+    /// already saved.
+    pub async fn record_change(&self, entry: &AuditEntry) {
+        if let Some(audit_log) = &self.audit_log {
+            if let Err(err) = audit_log.record(entry).await {
+                tracing::warn!(?err, action = %entry.action, "Failed to write audit log entry");
             }
-        } else {
-            false
-        };
+        }
+        #[cfg(feature = "git")]
+        if self.git_auto_commit {
+            self.commit_change(entry).await;
+        }
+    }
 
-        if !translation_exists {
-            return Err(StoreError::TranslationMissing {
-                key: key.to_string(),
-                language: language.to_string(),
-            });
+    #[cfg(feature = "git")]
+    async fn commit_change(&self, entry: &AuditEntry) {
+        let Some(path) = entry.path.as_deref() else {
+            return;
+        };
+        let catalog = self.normalize_path(self.resolve_path(path));
+        let paths = [
+            sidecar_path(&catalog, "assignments"),
+            sidecar_path(&catalog, "frozen"),
+            sidecar_path(&catalog, "modified"),
+        ];
+        let paths: Vec<PathBuf> = std::iter::once(catalog.clone()).chain(paths).collect();
+        let message = git::commit_message(entry);
+        let _guard = self.git_lock.lock().await;
+        match git::commit_paths(&paths, &message).await {
+            Ok(Some(commit)) => {
+                tracing::info!(path = %catalog.display(), %commit, action = %entry.action, "Committed change");
+            }
+            Ok(None) => {}
+            Err(err) => {
+                tracing::warn!(?err, path = %catalog.display(), "Failed to commit change to git");
+            }
         }
+    }
 
-        normalize_strings_file(&mut doc);
-        let json_value = doc.to_json_value();
-        let serialized = apple_json_formatter::to_apple_format(&json_value);
-        drop(doc);
-        fs::write(&self.path, serialized).await?;
-        Ok(())
+    /// Opens every catalog read-only, so edits from MCP tools and the web API are refused.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        // Drop stores opened by `new` so they're reopened with the new setting.
+        self.stores = Arc::new(RwLock::new(HashMap::new()));
+        self
     }
 
-    pub async fn delete_key(&self, key: &str) -> Result<(), StoreError> {
-        let mut doc = self.data.write().await;
-        if doc.strings.shift_remove(key).is_none() {
-            return Err(StoreError::KeyMissing(key.to_string()));
-        }
-        normalize_strings_file(&mut doc);
-        let json_value = doc.to_json_value();
-        let serialized = apple_json_formatter::to_apple_format(&json_value);
-        drop(doc);
-        fs::write(&self.path, serialized).await?;
-        Ok(())
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
     }
 
-    pub async fn rename_key(&self, old_key: &str, new_key: &str) -> Result<(), StoreError> {
-        if old_key == new_key {
-            return Ok(());
-        }
+    /// Protects catalogs matching any of `patterns` from edits. Patterns are relative to the
+    /// search root unless absolute; `*` and `?` stay within a directory and `**` spans any
+    /// number of them, so `Vendor/**.xcstrings` covers every catalog under `Vendor`.
+    pub fn with_read_only_paths(mut self, patterns: impl IntoIterator<Item = String>) -> Self {
+        self.read_only_paths = patterns.into_iter().collect();
+        self.stores = Arc::new(RwLock::new(HashMap::new()));
+        self
+    }
 
-        let mut doc = self.data.write().await;
-        if doc.strings.contains_key(new_key) {
-            return Err(StoreError::KeyExists(new_key.to_string()));
-        }
+    /// Glob patterns of the catalogs protected by [`Self::with_read_only_paths`].
+    pub fn read_only_paths(&self) -> &[String] {
+        &self.read_only_paths
+    }
 
-        let entry = doc
-            .strings
-            .shift_remove(old_key)
-            .ok_or_else(|| StoreError::KeyMissing(old_key.to_string()))?;
+    /// Opens every catalog with the review workflow enforced; see
+    /// [`XcStringsStore::with_review_workflow`].
+    pub fn with_review_workflow(mut self, review_workflow: bool) -> Self {
+        self.review_workflow = review_workflow;
+        self.stores = Arc::new(RwLock::new(HashMap::new()));
+        self
+    }
 
-        doc.strings.insert(new_key.to_string(), entry);
+    /// Opens every catalog marking translations for review when their source text changes;
+    /// see [`XcStringsStore::with_review_on_source_change`].
+    pub fn with_review_on_source_change(mut self, review_on_source_change: bool) -> Self {
+        self.review_on_source_change = review_on_source_change;
+        self.stores = Arc::new(RwLock::new(HashMap::new()));
+        self
+    }
 
-        normalize_strings_file(&mut doc);
-        let json_value = doc.to_json_value();
-        let serialized = apple_json_formatter::to_apple_format(&json_value);
-        drop(doc);
-        fs::write(&self.path, serialized).await?;
-        Ok(())
+    /// Opens every catalog with `format`; see [`XcStringsStore::with_format_options`].
+    pub fn with_format_options(mut self, format: FormatOptions) -> Self {
+        self.format = format;
+        self.stores = Arc::new(RwLock::new(HashMap::new()));
+        self
     }
 
-    pub async fn set_extraction_state(
-        &self,
-        key: &str,
-        state: Option<String>,
-    ) -> Result<(), StoreError> {
-        let mut doc = self.data.write().await;
-        let entry = doc
-            .strings
-            .entry(key.to_string())
-            .or_insert_with(XcStringEntry::default);
-        entry.extraction_state = state;
+    /// Opens every catalog with `validation`; see [`XcStringsStore::with_state_validation`].
+    pub fn with_state_validation(mut self, validation: StateValidation) -> Self {
+        self.state_validation = validation;
+        self.stores = Arc::new(RwLock::new(HashMap::new()));
+        self
+    }
 
-        normalize_strings_file(&mut doc);
-        let json_value = doc.to_json_value();
-        let serialized = apple_json_formatter::to_apple_format(&json_value);
-        drop(doc);
-        fs::write(&self.path, serialized).await?;
-        Ok(())
+    /// Opens every catalog with `validation`; see
+    /// [`XcStringsStore::with_placeholder_validation`].
+    pub fn with_placeholder_validation(mut self, validation: PlaceholderValidation) -> Self {
+        self.placeholder_validation = validation;
+        self.stores = Arc::new(RwLock::new(HashMap::new()));
+        self
     }
 
-    pub async fn set_translation_state(
-        &self,
-        key: &str,
-        language: &str,
-        state: Option<String>,
-    ) -> Result<TranslationValue, StoreError> {
-        let mut update = TranslationUpdate::default();
-        update.state = Some(state);
-        self.upsert_translation(key, language, update).await
+    /// Opens every catalog requiring comments on new keys; see
+    /// [`XcStringsStore::with_require_comment`].
+    pub fn with_require_comment(mut self, require_comment: bool) -> Self {
+        self.require_comment = require_comment;
+        self.stores = Arc::new(RwLock::new(HashMap::new()));
+        self
     }
 
-    pub async fn set_comment(&self, key: &str, comment: Option<String>) -> Result<(), StoreError> {
-        let mut doc = self.data.write().await;
-        let entry = doc
-            .strings
-            .entry(key.to_string())
-            .or_insert_with(XcStringEntry::default);
-        entry.comment = comment;
-        normalize_strings_file(&mut doc);
-        let json_value = doc.to_json_value();
-        let serialized = apple_json_formatter::to_apple_format(&json_value);
-        drop(doc);
-        fs::write(&self.path, serialized).await?;
-        Ok(())
+    /// The first read-only rule matching `path`, if any.
+    pub fn protection_for(&self, path: &Path) -> Option<&str> {
+        // Catalog paths are canonical, but the search root may sit behind a symlink.
+        let root =
+            std::fs::canonicalize(&self.search_root).unwrap_or_else(|_| self.search_root.clone());
+        let relative = path
+            .strip_prefix(&root)
+            .or_else(|_| path.strip_prefix(&self.search_root))
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let absolute = path.to_string_lossy().replace('\\', "/");
+        self.read_only_paths
+            .iter()
+            .find(|pattern| {
+                let pattern = pattern.replace('\\', "/");
+                let target = if Path::new(&pattern).is_absolute() {
+                    &absolute
+                } else {
+                    &relative
+                };
+                let pattern: Vec<char> = pattern.chars().collect();
+                let target: Vec<char> = target.chars().collect();
+                glob_matches(&pattern, &target)
+            })
+            .map(String::as_str)
     }
 
-    pub async fn set_should_translate(
-        &self,
-        key: &str,
-        should_translate: Option<bool>,
-    ) -> Result<(), StoreError> {
-        let mut doc = self.data.write().await;
-        let entry = doc
-            .strings
-            .entry(key.to_string())
-            .or_insert_with(XcStringEntry::default);
-        entry.should_translate = should_translate;
-        normalize_strings_file(&mut doc);
-        let json_value = doc.to_json_value();
-        let serialized = apple_json_formatter::to_apple_format(&json_value);
-        drop(doc);
-        fs::write(&self.path, serialized).await?;
-        Ok(())
+    /// Discovers only the catalogs built by an `.xcodeproj` or `.xcworkspace` instead of
+    /// walking the search root, which becomes the directory holding the project.
+    pub async fn with_project(mut self, project: PathBuf) -> Result<Self, StoreError> {
+        let project = self.normalize_path(self.resolve_path(&project.to_string_lossy()));
+        if let Some(dir) = project.parent() {
+            self.search_root = dir.to_path_buf();
+        }
+        self.project = Some(project);
+        self.refresh_discovered_paths().await?;
+        Ok(self)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::{
-        path::PathBuf,
-        sync::{
-            atomic::{AtomicUsize, Ordering},
-            Arc,
-        },
-        time::{SystemTime, UNIX_EPOCH},
-    };
+    pub fn project(&self) -> Option<&Path> {
+        self.project.as_deref()
+    }
 
-    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+    /// Shows catalogs under the given labels instead of derived ones, keyed by path relative
+    /// to the search root unless absolute, such as `Modules/Auth/Localizable.xcstrings`.
+    pub fn with_catalog_labels(
+        mut self,
+        labels: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        self.catalog_labels = labels.into_iter().collect();
+        self
+    }
 
-    struct TempStorePath {
-        dir: PathBuf,
-        file: PathBuf,
+    /// Display name for a catalog, unless [`Self::with_catalog_labels`] names it: the Swift
+    /// package or Xcode project it belongs to and its file name, such as
+    /// `AuthKit/Localizable.xcstrings`. When that's ambiguous among the available catalogs or
+    /// no package or project is found, it's the path under the search root (or the file name
+    /// when it lives elsewhere). The targets that build it follow in project mode.
+    pub async fn catalog_label(&self, path: &Path) -> String {
+        let manual = self
+            .catalog_labels
+            .iter()
+            .find(|(raw, _)| self.normalize_path(self.resolve_path(raw)) == path);
+        if let Some((_, label)) = manual {
+            return label.clone();
+        }
+        let file_name = path.file_name();
+        let owned = |path: &Path| {
+            let owner = xcodeproj::owner_name(path, &self.search_root)?;
+            Some(format!("{owner}/{}", path.file_name()?.to_string_lossy()))
+        };
+        let mut label = match owned(path) {
+            Some(label)
+                if !self.available_paths().await.iter().any(|other| {
+                    other != path
+                        && other.file_name() == file_name
+                        && owned(other).as_ref() == Some(&label)
+                }) =>
+            {
+                label
+            }
+            _ => path
+                .strip_prefix(&self.search_root)
+                .ok()
+                .map(|relative| relative.to_string_lossy())
+                .filter(|relative| !relative.is_empty())
+                .or_else(|| path.file_name().map(|name| name.to_string_lossy()))
+                .unwrap_or_else(|| path.to_string_lossy())
+                .replace('\\', "/"),
+        };
+        let targets = self.targets_for(path).await;
+        if !targets.is_empty() {
+            label.push_str(&format!(" ({})", targets.join(", ")));
+        }
+        label
     }
 
-    impl TempStorePath {
-        fn new(test_name: &str) -> Self {
-            let mut dir = std::env::temp_dir();
-            let nanos = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_nanos();
-            let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
-            dir.push(format!("xcstrings_mcp_{test_name}_{nanos}_{id}"));
-            std::fs::create_dir_all(&dir).expect("create temp dir");
-            let file = dir.join("Localizable.xcstrings");
-            Self { dir, file }
+    /// Names of the project targets that build `path`; empty without a project.
+    pub async fn targets_for(&self, path: &Path) -> Vec<String> {
+        self.catalog_targets
+            .read()
+            .await
+            .get(path)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn resolve_path(&self, raw: &str) -> PathBuf {
+        let path = PathBuf::from(raw);
+        if path.is_absolute() {
+            path
+        } else {
+            self.search_root.join(path)
         }
     }
 
-    impl Drop for TempStorePath {
-        fn drop(&mut self) {
-            let _ = std::fs::remove_dir_all(&self.dir);
-        }
+    fn normalize_path(&self, path: PathBuf) -> PathBuf {
+        std::fs::canonicalize(&path).unwrap_or(path)
+    }
+
+    pub fn default_path(&self) -> Option<PathBuf> {
+        self.default_path.clone()
+    }
+
+    pub fn search_root(&self) -> &Path {
+        &self.search_root
+    }
+
+    pub async fn available_paths(&self) -> Vec<PathBuf> {
+        self.discovered_paths.read().await.clone()
+    }
+
+    pub async fn refresh_discovered_paths(&self) -> Result<Vec<PathBuf>, StoreError> {
+        let root = self.search_root.clone();
+        let project = self.project.clone();
+        let default_path = self.default_path.clone();
+        let registered = self.registered_paths.read().await.clone();
+
+        let (discovered, targets) = task::spawn_blocking(move || {
+            let mut targets = HashMap::new();
+            let mut matches = match &project {
+                Some(project) => xcodeproj::project_catalogs(project)
+                    .map_err(|err| StoreError::InvalidProject(format!("{err:#}")))?
+                    .into_iter()
+                    .filter(|catalog| catalog.path.is_file())
+                    .map(|catalog| {
+                        targets.insert(catalog.path.clone(), catalog.targets);
+                        catalog.path
+                    })
+                    .collect(),
+                None => discover_xcstrings(&root),
+            };
+            matches.extend(registered);
+
+            if let Some(default_path) = default_path {
+                let normalized = std::fs::canonicalize(&default_path).unwrap_or(default_path);
+                if !matches.iter().any(|existing| existing == &normalized) {
+                    matches.push(normalized);
+                }
+            }
+
+            matches.sort();
+            matches.dedup();
+            Ok::<_, StoreError>((matches, targets))
+        })
+        .await
+        .map_err(|err| StoreError::ReadFailed(io::Error::other(err.to_string())))??;
+
+        *self.catalog_targets.write().await = targets;
+        {
+            let mut guard = self.discovered_paths.write().await;
+            *guard = discovered.clone();
+        }
+
+        Ok(discovered)
+    }
+
+    /// Whether `path` was added with [`Self::register_path`] rather than discovered.
+    pub async fn is_registered(&self, path: &Path) -> bool {
+        self.registered_paths
+            .read()
+            .await
+            .iter()
+            .any(|registered| registered == path)
+    }
+
+    /// Adds an existing catalog anywhere on disk to the available paths. The path must be
+    /// absolute and name a readable `.xcstrings` file; the canonical path is returned.
+    pub async fn register_path(&self, raw: &str) -> Result<PathBuf, StoreError> {
+        let path = PathBuf::from(raw);
+        if !path.is_absolute() {
+            return Err(StoreError::InvalidCatalogPath(format!(
+                "'{raw}' must be an absolute path"
+            )));
+        }
+        let is_xcstrings = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("xcstrings"));
+        if !is_xcstrings {
+            return Err(StoreError::InvalidCatalogPath(format!(
+                "'{raw}' is not an .xcstrings file"
+            )));
+        }
+        let path = fs::canonicalize(&path).await.map_err(|err| {
+            StoreError::InvalidCatalogPath(format!("'{raw}' cannot be opened: {err}"))
+        })?;
+        if !fs::metadata(&path).await?.is_file() {
+            return Err(StoreError::InvalidCatalogPath(format!(
+                "'{raw}' is not a file"
+            )));
+        }
+        // Parse it now so a broken catalog is rejected instead of listed.
+        self.store_for(Some(&path.to_string_lossy()))
+            .await
+            .map_err(|err| match err {
+                StoreError::SerdeFailed(err) => StoreError::InvalidCatalogPath(format!(
+                    "'{raw}' is not a valid string catalog: {err}"
+                )),
+                other => other,
+            })?;
+
+        {
+            let mut registered = self.registered_paths.write().await;
+            if !registered.contains(&path) {
+                registered.push(path.clone());
+            }
+        }
+        self.refresh_discovered_paths().await?;
+        Ok(path)
+    }
+
+    /// Removes a catalog added with [`Self::register_path`] from the available paths.
+    /// Catalogs found under the search root can't be unregistered.
+    pub async fn unregister_path(&self, raw: &str) -> Result<PathBuf, StoreError> {
+        let path = self.normalize_path(self.resolve_path(raw));
+        {
+            let mut registered = self.registered_paths.write().await;
+            let Some(index) = registered.iter().position(|existing| existing == &path) else {
+                return Err(StoreError::CatalogNotRegistered(raw.to_string()));
+            };
+            registered.remove(index);
+        }
+        self.refresh_discovered_paths().await?;
+        Ok(path)
+    }
+
+    pub async fn store_for(&self, path: Option<&str>) -> Result<Arc<XcStringsStore>, StoreError> {
+        let resolved_path = match path {
+            Some(raw) => self.resolve_path(raw),
+            None => self.default_path.clone().ok_or(StoreError::PathRequired)?,
+        };
+        let resolved_path = self.normalize_path(resolved_path);
+
+        {
+            let stores = self.stores.read().await;
+            if let Some(store) = stores.get(&resolved_path) {
+                // Pick up edits made by other processes; if the reload fails, the cached
+                // contents are still served.
+                let _ = store.reload_if_changed().await;
+                return Ok(store.clone());
+            }
+        }
+
+        let store = Arc::new(
+            XcStringsStore::load_or_create(&resolved_path)
+                .await?
+                .with_events(self.events.clone())
+                .with_read_only(self.read_only)
+                .with_protection(self.protection_for(&resolved_path).map(str::to_string))
+                .with_review_workflow(self.review_workflow)
+                .with_review_on_source_change(self.review_on_source_change)
+                .with_format_options(self.format.clone())
+                .with_state_validation(self.state_validation)
+                .with_placeholder_validation(self.placeholder_validation)
+                .with_require_comment(self.require_comment),
+        );
+        let mut stores = self.stores.write().await;
+        let entry = stores
+            .entry(resolved_path.clone())
+            .or_insert_with(|| store.clone());
+        Ok(entry.clone())
+    }
+
+    pub async fn default_store(&self) -> Result<Arc<XcStringsStore>, StoreError> {
+        self.store_for(None).await
+    }
+
+    /// Subscribes to change events from every store managed by this instance.
+    pub fn subscribe(&self) -> broadcast::Receiver<StoreEvent> {
+        self.events.subscribe()
+    }
+
+    /// Runs [`XcStringsStore::rename_namespace`] on every available catalog, returning the
+    /// catalogs with keys under `old_prefix`. The catalogs are renamed as one unit: every one
+    /// is locked and checked for collisions, write access and frozen keys before any is
+    /// changed, and they're then written like [`Self::apply_transaction`].
+    pub async fn rename_namespace_everywhere(
+        &self,
+        old_prefix: &str,
+        new_prefix: &str,
+        apply: bool,
+    ) -> Result<Vec<(PathBuf, NamespaceRename)>, StoreError> {
+        let mut stores = BTreeMap::new();
+        for path in self.refresh_discovered_paths().await? {
+            let store = self.store_for(Some(&path.to_string_lossy())).await?;
+            stores
+                .entry(store.path().to_path_buf())
+                .or_insert((path, store));
+        }
+        if !apply {
+            let mut plans = Vec::new();
+            for (path, store) in stores.into_values() {
+                let plan = store
+                    .rename_namespace(old_prefix, new_prefix, false)
+                    .await?;
+                if !plan.renamed.is_empty() {
+                    plans.push((path, plan));
+                }
+            }
+            return Ok(plans);
+        }
+
+        let old_prefix = namespace_prefix(old_prefix)?;
+        let new_prefix = namespace_prefix(new_prefix)?;
+        if old_prefix == new_prefix {
+            return Ok(Vec::new());
+        }
+        // Locking in path order keeps this from deadlocking with transactions.
+        let mut plans = Vec::new();
+        for (path, store) in stores.into_values() {
+            let edit = match store.check_writable() {
+                Ok(()) => Some(store.lock_edit().await?),
+                Err(_) => None,
+            };
+            let doc = XcStringsFile::clone(&**store.data.read().await);
+            let plan = plan_namespace_rename(&doc, &old_prefix, &new_prefix);
+            if plan.renamed.is_empty() {
+                continue;
+            }
+            // A read-only catalog only fails the rename when it has keys to rename.
+            let edit = match edit {
+                Some(edit) => edit,
+                None => store.lock_edit().await?,
+            };
+            plans.push((path, store, edit, doc, plan));
+        }
+        let collisions: Vec<String> = plans
+            .iter()
+            .flat_map(|(path, _, _, _, plan)| {
+                plan.collisions
+                    .iter()
+                    .map(move |key| format!("{key} ({})", path.display()))
+            })
+            .collect();
+        if !collisions.is_empty() {
+            return Err(StoreError::NamespaceCollision(collisions));
+        }
+
+        let mut stores = Vec::with_capacity(plans.len());
+        let mut edits = Vec::with_capacity(plans.len());
+        let mut staged = Vec::with_capacity(plans.len());
+        let mut changes = Vec::with_capacity(plans.len());
+        let mut applied = Vec::with_capacity(plans.len());
+        for (path, store, edit, mut doc, plan) in plans {
+            changes.push(store.rename_planned_keys(&mut doc, &plan)?);
+            stores.push(store);
+            edits.push(edit);
+            staged.push(doc);
+            applied.push((path, plan));
+        }
+        commit_documents(&stores, edits, staged, changes).await?;
+        Ok(applied)
+    }
+
+    /// Applies edits across several catalogs as one unit, such as moving a feature's keys
+    /// between catalogs while updating their values. Edits go through the same checks as
+    /// [`XcStringsStore::apply_batch`]; unless every one succeeds, nothing is written. The
+    /// catalogs are then written to temporary files and renamed into place, and if any
+    /// rename fails, those already replaced get their previous contents back.
+    pub async fn apply_transaction(
+        &self,
+        operations: Vec<TransactionOperation>,
+    ) -> Result<TransactionOutcome, StoreError> {
+        let mut resolved = HashMap::new();
+        let mut stores = BTreeMap::new();
+        for operation in &operations {
+            for raw in operation.paths() {
+                let raw = raw.map(str::to_string);
+                if resolved.contains_key(&raw) {
+                    continue;
+                }
+                let store = self.store_for(raw.as_deref()).await?;
+                resolved.insert(raw, store.path().to_path_buf());
+                stores.entry(store.path().to_path_buf()).or_insert(store);
+            }
+        }
+        // Locking in path order keeps concurrent transactions from deadlocking.
+        let stores: Vec<Arc<XcStringsStore>> = stores.into_values().collect();
+        let catalogs: HashMap<Option<String>, usize> = resolved
+            .into_iter()
+            .filter_map(|(raw, path)| {
+                let index = stores.iter().position(|store| store.path() == path)?;
+                Some((raw, index))
+            })
+            .collect();
+        let mut edits = Vec::with_capacity(stores.len());
+        let mut staged = Vec::with_capacity(stores.len());
+        for store in &stores {
+            edits.push(store.lock_edit().await?);
+            staged.push(XcStringsFile::clone(&**store.data.read().await));
+        }
+        let mut changes = vec![Vec::new(); stores.len()];
+
+        let results: Vec<_> = operations
+            .into_iter()
+            .map(|operation| match operation {
+                TransactionOperation::Edit { path, operation } => {
+                    let index = catalogs[&path];
+                    let applied = stores[index].stage_operation(&mut staged[index], operation)?;
+                    applied.saved_changes(&mut changes[index]);
+                    Ok(applied)
+                }
+                TransactionOperation::MoveKey {
+                    key,
+                    from,
+                    to,
+                    position,
+                } => {
+                    let (from, to) = (catalogs[&from], catalogs[&to]);
+                    let entry = staged[from]
+                        .strings
+                        .get(&key)
+                        .ok_or_else(|| StoreError::KeyMissing(key.clone()))?;
+                    if from != to && staged[to].strings.contains_key(&key) {
+                        return Err(StoreError::KeyExists(key));
+                    }
+                    for language in entry.localizations.keys() {
+                        stores[from].check_frozen(language)?;
+                        stores[to].check_frozen(language)?;
+                    }
+                    stores[to].check_comment(&staged[to], &key, entry.comment.as_deref())?;
+                    let Some((current, _, entry)) = staged[from].strings.shift_remove_full(&key)
+                    else {
+                        return Err(StoreError::KeyMissing(key));
+                    };
+                    let index = match staged[to].insertion_index(&key, &position) {
+                        Ok(index) => index,
+                        Err(err) => {
+                            staged[from].strings.shift_insert(current, key, entry);
+                            return Err(err);
+                        }
+                    };
+                    staged[to].strings.shift_insert(index, key.clone(), entry);
+                    let change = StoreChange::KeyCreated { key: key.clone() };
+                    // Within one catalog only the position changed, so nothing is recorded
+                    // as deleted or created.
+                    if from != to {
+                        changes[from].push(StoreChange::KeyDeleted { key });
+                        changes[to].push(change.clone());
+                    }
+                    Ok(BatchApplied {
+                        change,
+                        value: None,
+                        warnings: Vec::new(),
+                        needs_review: Vec::new(),
+                    })
+                }
+            })
+            .collect();
+        let committed = !results.is_empty() && results.iter().all(Result::is_ok);
+        if !committed {
+            return Ok(TransactionOutcome {
+                committed,
+                results,
+                saved: Vec::new(),
+            });
+        }
+
+        let saved = commit_documents(&stores, edits, staged, changes).await?;
+        Ok(TransactionOutcome {
+            committed,
+            results,
+            saved,
+        })
+    }
+
+    pub async fn loaded_stores(&self) -> Vec<Arc<XcStringsStore>> {
+        self.stores.read().await.values().cloned().collect()
+    }
+
+    /// Reloads every loaded store whose file changed on disk since it was last read or written.
+    /// Returns the paths that were reloaded.
+    pub async fn poll_external_changes(&self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for store in self.loaded_stores().await {
+            if store.reload_if_changed().await.unwrap_or(false) {
+                changed.push(store.path().to_path_buf());
+            }
+        }
+        changed
+    }
+
+    /// Spawns a background task polling loaded catalogs for modifications made by other
+    /// processes (Xcode, git, editors). Changes are published through [`Self::subscribe`].
+    pub fn spawn_watcher(self: &Arc<Self>, interval: Duration) -> task::JoinHandle<()> {
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                manager.poll_external_changes().await;
+            }
+        })
+    }
+
+    /// Ranked suggestions for translating `key` of `store` into `language`: translations of
+    /// the same or a similar source text in any discovered catalog with the same source
+    /// language, and the `glossary` terms the source text contains.
+    pub async fn suggest_translations(
+        &self,
+        store: &Arc<XcStringsStore>,
+        key: &str,
+        language: &str,
+        glossary: &BTreeMap<String, BTreeMap<String, String>>,
+        limit: usize,
+    ) -> Result<Vec<Suggestion>, StoreError> {
+        let source = store.source_text(key).await?;
+        let source_language = store.source_language().await;
+        let mut paths = self.available_paths().await;
+        if !paths.iter().any(|path| path == store.path()) {
+            paths.push(store.path().to_path_buf());
+        }
+        let mut memory = Vec::new();
+        for path in paths {
+            let other = match self.store_for(Some(&path.to_string_lossy())).await {
+                Ok(other) => other,
+                Err(err) => {
+                    tracing::warn!(path = %path.display(), ?err, "Suggestions skipped a catalog");
+                    continue;
+                }
+            };
+            if other.source_language().await != source_language {
+                continue;
+            }
+            let label = self.catalog_label(other.path()).await;
+            let skip = Arc::ptr_eq(&other, store).then_some(key);
+            memory.extend(other.memory_entries(language, &label, skip).await);
+        }
+        Ok(suggest::suggest(
+            &source, language, &memory, glossary, limit,
+        ))
+    }
+
+    /// Runs every QA rule over every discovered catalog and keeps the reports. Errors and
+    /// warnings the previous sweep of a catalog didn't report are published through
+    /// [`Self::subscribe`] as [`StoreEvent::QaFindings`] and returned; a catalog's first sweep
+    /// only records its baseline.
+    pub async fn sweep_qa(&self, options: &QaOptions) -> Vec<(PathBuf, Vec<Finding>)> {
+        let paths = match self.refresh_discovered_paths().await {
+            Ok(paths) => paths,
+            Err(err) => {
+                tracing::warn!(?err, "QA sweep could not list catalogs");
+                self.available_paths().await
+            }
+        };
+        let mut found = Vec::new();
+        for path in paths {
+            let store = match self.store_for(Some(&path.to_string_lossy())).await {
+                Ok(store) => store,
+                Err(err) => {
+                    tracing::warn!(path = %path.display(), ?err, "QA sweep skipped a catalog");
+                    continue;
+                }
+            };
+            let report = store.validate(&[], options).await;
+            let previous = self
+                .qa_sweeps
+                .write()
+                .await
+                .insert(path.clone(), report.clone());
+            let Some(previous) = previous else {
+                continue;
+            };
+            let known: Vec<&Finding> = previous.errors.iter().chain(&previous.warnings).collect();
+            let findings: Vec<Finding> = report
+                .errors
+                .into_iter()
+                .chain(report.warnings)
+                .filter(|finding| !known.contains(&finding))
+                .collect();
+            if findings.is_empty() {
+                continue;
+            }
+            tracing::warn!(
+                path = %path.display(),
+                count = findings.len(),
+                "QA sweep found new issues"
+            );
+            let _ = self.events.send(StoreEvent::QaFindings {
+                path: path.clone(),
+                findings: findings.clone(),
+            });
+            found.push((path, findings));
+        }
+        found
+    }
+
+    /// The report from the latest QA sweep of `path`, if it has been swept.
+    pub async fn qa_sweep_report(&self, path: &Path) -> Option<QaReport> {
+        self.qa_sweeps.read().await.get(path).cloned()
+    }
+
+    /// Spawns a background task running [`Self::sweep_qa`] every `interval`, so problems
+    /// introduced outside this server, such as edits in Xcode, are still reported.
+    pub fn spawn_qa_sweeps(
+        self: &Arc<Self>,
+        interval: Duration,
+        options: QaOptions,
+    ) -> task::JoinHandle<()> {
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                manager.sweep_qa(&options).await;
+            }
+        })
+    }
+}
+
+fn merge_documents(
+    target: &mut XcStringsFile,
+    source: &XcStringsFile,
+    overwrite: bool,
+) -> MergeOutcome {
+    let mut outcome = MergeOutcome::default();
+    for (key, entry) in &source.strings {
+        let Some(existing) = target.strings.get_mut(key) else {
+            target.strings.insert(key.clone(), entry.clone());
+            outcome.added_keys.push(key.clone());
+            continue;
+        };
+        for (language, loc) in &entry.localizations {
+            let merged = MergedTranslation {
+                key: key.clone(),
+                language: language.clone(),
+            };
+            match existing.localizations.get(language) {
+                Some(current)
+                    if TranslationValue::from_localization(current)
+                        == TranslationValue::from_localization(loc) => {}
+                Some(current) if localization_has_content(current) && !overwrite => {
+                    outcome.conflicts.push(merged);
+                }
+                _ => {
+                    existing.localizations.insert(language.clone(), loc.clone());
+                    outcome.merged.push(merged);
+                }
+            }
+        }
+    }
+    outcome
+}
+
+fn localization_has_content(loc: &XcLocalization) -> bool {
+    extract_translation_value(loc).is_some_and(|value| !value.is_empty())
+        || !loc.variations.is_empty()
+        || !loc.substitutions.is_empty()
+}
+
+fn validate_snapshot_label(label: &str) -> Result<(), StoreError> {
+    let valid = !label.is_empty()
+        && !label.starts_with('.')
+        && label
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(StoreError::InvalidSnapshotLabel(format!(
+            "'{label}' must be non-empty, use only letters, digits, '.', '_' or '-', and not start with '.'"
+        )))
+    }
+}
+
+/// Matches a path against a glob where `*` and `?` don't cross `/`, `**` does, and `**/`
+/// also matches no directory at all.
+fn glob_matches(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => {
+            if let ['/', after @ ..] = rest {
+                if glob_matches(after, path) {
+                    return true;
+                }
+            }
+            (0..=path.len()).any(|skip| glob_matches(rest, &path[skip..]))
+        }
+        ['*', rest @ ..] => {
+            for skip in 0..=path.len() {
+                if glob_matches(rest, &path[skip..]) {
+                    return true;
+                }
+                if path.get(skip) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        ['?', rest @ ..] => {
+            matches!(path, [first, tail @ ..] if *first != '/' && glob_matches(rest, tail))
+        }
+        [expected, rest @ ..] => {
+            matches!(path, [first, tail @ ..] if first == expected && glob_matches(rest, tail))
+        }
+    }
+}
+
+fn discover_xcstrings(root: &Path) -> Vec<PathBuf> {
+    if !root.exists() {
+        return Vec::new();
+    }
+
+    let mut results = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let file_type = match entry.file_type() {
+                Ok(kind) => kind,
+                Err(_) => continue,
+            };
+
+            if file_type.is_dir() {
+                if let Some(name) = path.file_name().and_then(|value| value.to_str()) {
+                    let lowered = name.to_ascii_lowercase();
+                    if lowered == "target"
+                        || lowered == ".git"
+                        || lowered == "node_modules"
+                        || lowered == SNAPSHOT_DIR
+                    {
+                        continue;
+                    }
+                }
+                stack.push(path);
+            } else if file_type.is_file() {
+                let is_xcstrings = path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| ext.eq_ignore_ascii_case("xcstrings"))
+                    .unwrap_or(false);
+                if is_xcstrings {
+                    let normalized = std::fs::canonicalize(&path).unwrap_or(path);
+                    results.push(normalized);
+                }
+            }
+        }
+    }
+
+    results
+}
+
+impl XcStringsStore {
+    pub async fn load_or_create(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent).await?;
+            }
+        }
+
+        let (mut doc, revision, style) = if path.exists() {
+            load_catalog(&path).await?
+        } else {
+            (
+                XcStringsFile::default(),
+                content_hash(""),
+                SourceStyle::default(),
+            )
+        };
+
+        normalize_strings_file(&mut doc);
+
+        let fingerprint = FileFingerprint::read(&path).await;
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let frozen = FrozenLanguages::load(&sidecar_path(&path, "frozen")).await?;
+        let modified = ModifiedIndex::load(&sidecar_path(&path, "modified")).await?;
+
+        Ok(Self {
+            path,
+            data: Arc::new(RwLock::new(Arc::new(doc))),
+            edit_lock: Arc::new(Mutex::new(())),
+            fingerprint: Arc::new(Mutex::new(fingerprint)),
+            generation: Arc::new(AtomicU64::new(0)),
+            written_generation: Arc::new(AtomicU64::new(0)),
+            revision: Arc::new(AtomicU64::new(revision)),
+            expected: None,
+            written: Arc::new(AtomicU64::new(0)),
+            events,
+            read_only: false,
+            protected_by: None,
+            review_workflow: false,
+            review_on_source_change: false,
+            frozen: Arc::new(std::sync::RwLock::new(frozen)),
+            force: false,
+            modified: Arc::new(std::sync::RwLock::new(modified)),
+            sidecar_lock: Arc::new(Mutex::new(())),
+            format: FormatOptions::default(),
+            state_validation: StateValidation::default(),
+            placeholder_validation: PlaceholderValidation::default(),
+            require_comment: false,
+            style: Arc::new(std::sync::Mutex::new(style)),
+            normalized: Arc::default(),
+        })
+    }
+
+    /// Publishes this store's change events on a shared channel instead of its own.
+    pub fn with_events(mut self, events: broadcast::Sender<StoreEvent>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Refuses every edit and snapshot with [`StoreError::ReadOnly`].
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Writes the catalog with `format` instead of the default Xcode layout.
+    pub fn with_format_options(mut self, format: FormatOptions) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets what upserts do with states outside [`state::KNOWN_STATES`]: correct them with a
+    /// warning, or refuse them with [`StoreError::InvalidState`].
+    pub fn with_state_validation(mut self, validation: StateValidation) -> Self {
+        self.state_validation = validation;
+        self
+    }
+
+    /// Sets what upserts do when a translation's format specifiers don't match the source
+    /// text, catching `%lld` turned into `%d` or dropped: save it with a warning, refuse it
+    /// with [`StoreError::PlaceholderMismatch`], or not check at all.
+    pub fn with_placeholder_validation(mut self, validation: PlaceholderValidation) -> Self {
+        self.placeholder_validation = validation;
+        self
+    }
+
+    /// Makes upserts and [`Self::create_key`] refuse to add a key without a non-empty
+    /// comment, with [`StoreError::CommentRequired`], so translators never get a string
+    /// without context. Existing keys can be updated either way.
+    pub fn with_require_comment(mut self, require_comment: bool) -> Self {
+        self.require_comment = require_comment;
+        self
+    }
+
+    /// Refuses to create `key` without a comment when comments are required.
+    fn check_comment(
+        &self,
+        doc: &XcStringsFile,
+        key: &str,
+        comment: Option<&str>,
+    ) -> Result<(), StoreError> {
+        let commented = comment.is_some_and(|comment| !comment.trim().is_empty());
+        if self.require_comment && !commented && !doc.strings.contains_key(key) {
+            return Err(StoreError::CommentRequired(key.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Checks the format specifiers `key` would have in `language` after `update` against
+    /// its source text. Upserts of the source language, of keys marked
+    /// `shouldTranslate: false`, and those leaving the text as it was aren't checked.
+    /// Returns a warning per mismatch unless validation is strict.
+    fn check_placeholders(
+        &self,
+        doc: &XcStringsFile,
+        key: &str,
+        language: &str,
+        update: &TranslationUpdate,
+    ) -> Result<Vec<VariationWarning>, StoreError> {
+        if self.placeholder_validation == PlaceholderValidation::Off
+            || language == doc.source_language
+        {
+            return Ok(Vec::new());
+        }
+        let before = doc
+            .strings
+            .get(key)
+            .and_then(|entry| entry.localizations.get(language));
+        let mut after = before.cloned().unwrap_or_default();
+        apply_update(&mut after, update.clone());
+        normalize_localization(&mut after, &mut Vec::new());
+        self.check_placeholder_edit(doc, key, language, before, &after)
+    }
+
+    /// Checks the format specifiers of `key` in `language` after an edit from `before` to
+    /// `after`, against the source text in `doc`; see [`Self::check_placeholders`].
+    fn check_placeholder_edit(
+        &self,
+        doc: &XcStringsFile,
+        key: &str,
+        language: &str,
+        before: Option<&XcLocalization>,
+        after: &XcLocalization,
+    ) -> Result<Vec<VariationWarning>, StoreError> {
+        if self.placeholder_validation == PlaceholderValidation::Off
+            || language == doc.source_language
+        {
+            return Ok(Vec::new());
+        }
+        let entry = doc.strings.get(key);
+        if entry.and_then(|entry| entry.should_translate) == Some(false) {
+            return Ok(Vec::new());
+        }
+        let text = |loc: &XcLocalization| text_only(&TranslationValue::from_localization(loc));
+        if before.map(text) == Some(text(after)) {
+            return Ok(Vec::new());
+        }
+        let source = entry.and_then(|entry| entry.localizations.get(&doc.source_language));
+        let findings = qa::placeholder_findings(key, language, source, after);
+        if findings.is_empty() {
+            return Ok(Vec::new());
+        }
+        if self.placeholder_validation == PlaceholderValidation::Strict {
+            let reason = findings
+                .iter()
+                .map(|finding| match &finding.location {
+                    Some(location) => format!("{location}: {}", finding.message),
+                    None => finding.message.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(StoreError::PlaceholderMismatch {
+                key: key.to_string(),
+                language: language.to_string(),
+                reason,
+            });
+        }
+        Ok(findings
+            .into_iter()
+            .map(|finding| VariationWarning {
+                path: finding.location.unwrap_or_else(|| "value".to_string()),
+                message: finding.message,
+            })
+            .collect())
+    }
+
+    /// Checks every state `update` sets, correcting misspelled ones in place unless
+    /// validation is strict. Returns a warning for each state it corrected or couldn't.
+    fn check_states(
+        &self,
+        update: &mut TranslationUpdate,
+    ) -> Result<Vec<VariationWarning>, StoreError> {
+        let mut warnings = Vec::new();
+        self.check_update_states(update, "", &mut warnings)?;
+        Ok(warnings)
+    }
+
+    fn check_update_states(
+        &self,
+        update: &mut TranslationUpdate,
+        prefix: &str,
+        warnings: &mut Vec<VariationWarning>,
+    ) -> Result<(), StoreError> {
+        if let Some(Some(state)) = &mut update.state {
+            self.check_state(state, format!("{prefix}state"), warnings)?;
+        }
+        for (name, substitution) in update.substitutions.iter_mut().flatten() {
+            let Some(substitution) = substitution else {
+                continue;
+            };
+            let prefix = format!("{prefix}substitutions.{name}.");
+            if let Some(Some(state)) = &mut substitution.state {
+                self.check_state(state, format!("{prefix}state"), warnings)?;
+            }
+            self.check_variation_states(&mut substitution.variations, &prefix, warnings)?;
+        }
+        self.check_variation_states(&mut update.variations, prefix, warnings)
+    }
+
+    fn check_variation_states(
+        &self,
+        variations: &mut Option<IndexMap<String, IndexMap<String, TranslationUpdate>>>,
+        prefix: &str,
+        warnings: &mut Vec<VariationWarning>,
+    ) -> Result<(), StoreError> {
+        for (selector, cases) in variations.iter_mut().flatten() {
+            for (case, update) in cases {
+                let prefix = format!("{prefix}variations.{selector}.{case}.");
+                self.check_update_states(update, &prefix, warnings)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_state(
+        &self,
+        state: &mut String,
+        path: String,
+        warnings: &mut Vec<VariationWarning>,
+    ) -> Result<(), StoreError> {
+        let known = state::normalize(state);
+        if known == Some(state.as_str()) {
+            return Ok(());
+        }
+        let message = match (known, self.state_validation) {
+            (Some(known), StateValidation::Strict) => {
+                return Err(StoreError::InvalidState(format!(
+                    "'{state}' at {path} is not a known state; did you mean '{known}'?"
+                )))
+            }
+            (None, StateValidation::Strict) => {
+                return Err(StoreError::InvalidState(format!(
+                    "'{state}' at {path} is not a known state; expected one of {}",
+                    state::KNOWN_STATES.join(", ")
+                )))
+            }
+            (Some(known), StateValidation::Warn) => {
+                let message = format!("'{state}' is not a known state; saved as '{known}'");
+                *state = known.to_string();
+                message
+            }
+            (None, StateValidation::Warn) => format!(
+                "'{state}' is not a known state and was saved as is; expected one of {}",
+                state::KNOWN_STATES.join(", ")
+            ),
+        };
+        warnings.push(VariationWarning { path, message });
+        Ok(())
+    }
+
+    /// Refuses every edit and snapshot with [`StoreError::Protected`], naming `rule`.
+    pub fn with_protection(mut self, rule: Option<String>) -> Self {
+        self.protected_by = rule;
+        self
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only || self.protected_by.is_some()
+    }
+
+    /// Makes translation edits follow `new` → `translated` → `needs_review` → `reviewed`,
+    /// failing with [`StoreError::WorkflowViolation`] otherwise. Only
+    /// [`Self::approve_translation`] marks a translation reviewed, and translations in review
+    /// or reviewed can't be edited until they are approved or rejected.
+    pub fn with_review_workflow(mut self, review_workflow: bool) -> Self {
+        self.review_workflow = review_workflow;
+        self
+    }
+
+    /// Makes upserts that change the source text of a key mark its `translated` and
+    /// `reviewed` translations `needs_review`, as Xcode does, so they aren't shipped
+    /// unnoticed. Frozen languages are left alone unless forced. The review workflow
+    /// doesn't apply, since sending a translation back to review is always allowed here.
+    pub fn with_review_on_source_change(mut self, review_on_source_change: bool) -> Self {
+        self.review_on_source_change = review_on_source_change;
+        self
+    }
+
+    /// After an upsert of `key` in `language`, marks the other translations of the key
+    /// `needs_review` when [`Self::with_review_on_source_change`] is on and the upsert
+    /// reworded the source text, which was `before`. Returns the languages marked.
+    fn review_after_source_change(
+        &self,
+        doc: &mut XcStringsFile,
+        key: &str,
+        language: &str,
+        before: Option<TranslationValue>,
+    ) -> Vec<String> {
+        if !self.review_on_source_change || language != doc.source_language {
+            return Vec::new();
+        }
+        let Some(entry) = doc.strings.get_mut(key) else {
+            return Vec::new();
+        };
+        let after = entry
+            .localizations
+            .get(language)
+            .map(|loc| text_only(&TranslationValue::from_localization(loc)));
+        // Giving a key its first source text doesn't reword anything.
+        if before.is_none() || before == after {
+            return Vec::new();
+        }
+        let mut marked = Vec::new();
+        for (other, loc) in entry.localizations.iter_mut() {
+            let sent_back = matches!(
+                localization_state(loc).as_deref(),
+                Some(workflow::TRANSLATED | workflow::REVIEWED)
+            );
+            if other == language || !sent_back || self.check_frozen(other).is_err() {
+                continue;
+            }
+            set_states(loc, workflow::NEEDS_REVIEW);
+            marked.push(other.clone());
+        }
+        marked
+    }
+
+    /// With the review workflow on, refuses changing `key` in `language` from `before` to
+    /// `after` when that skips or leaves a review step.
+    fn check_workflow_edit(
+        &self,
+        key: &str,
+        language: &str,
+        before: Option<&XcLocalization>,
+        after: &XcLocalization,
+    ) -> Result<(), StoreError> {
+        if !self.review_workflow {
+            return Ok(());
+        }
+        let text = |loc: &XcLocalization| text_only(&TranslationValue::from_localization(loc));
+        workflow::check_edit(
+            before.and_then(localization_state).as_deref(),
+            localization_state(after).as_deref(),
+            before.map(text) != Some(text(after)),
+        )
+        .map_err(|reason| StoreError::WorkflowViolation {
+            key: key.to_string(),
+            language: language.to_string(),
+            reason,
+        })
+    }
+
+    /// Checks `update` against the review workflow without applying it to `doc`.
+    fn check_workflow_update(
+        &self,
+        doc: &XcStringsFile,
+        key: &str,
+        language: &str,
+        update: &TranslationUpdate,
+    ) -> Result<(), StoreError> {
+        if !self.review_workflow {
+            return Ok(());
+        }
+        let before = doc
+            .strings
+            .get(key)
+            .and_then(|entry| entry.localizations.get(language));
+        let mut after = before.cloned().unwrap_or_default();
+        apply_update(&mut after, update.clone());
+        normalize_localization(&mut after, &mut Vec::new());
+        self.check_workflow_edit(key, language, before, &after)
+    }
+
+    /// Returns a handle to the same catalog whose edits go through to frozen languages.
+    pub fn forcing(&self) -> Self {
+        Self {
+            force: true,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a handle to the same catalog whose edits fail with
+    /// [`StoreError::RevisionMismatch`] unless the catalog is still at one of the `If-Match`
+    /// entity tags in `tags` (`*` matches any) or as this handle's own last edit left it.
+    /// The comparison happens under the edit lock, so two clients holding the same tag
+    /// can't both write.
+    pub fn expecting(&self, tags: Vec<String>) -> Self {
+        Self {
+            expected: Some(tags.into()),
+            written: Arc::new(AtomicU64::new(0)),
+            ..self.clone()
+        }
+    }
+
+    /// Revision the last edit through this handle saved, or the current one when it made
+    /// none; the `ETag` to answer a write with.
+    pub fn written_revision(&self) -> String {
+        match self.written.load(Ordering::Acquire) {
+            0 => self.revision(),
+            hash => format_revision(hash),
+        }
+    }
+
+    fn check_revision(&self) -> Result<(), StoreError> {
+        let Some(tags) = &self.expected else {
+            return Ok(());
+        };
+        let current = self.revision.load(Ordering::Acquire);
+        if self.written.load(Ordering::Acquire) == current {
+            return Ok(());
+        }
+        let revision = format_revision(current);
+        let matches = tags
+            .iter()
+            .any(|tag| tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == revision);
+        if matches {
+            Ok(())
+        } else {
+            Err(StoreError::RevisionMismatch)
+        }
+    }
+
+    /// Refuses edits to `language` while it is frozen, unless this handle is forcing.
+    fn check_frozen(&self, language: &str) -> Result<(), StoreError> {
+        let frozen = self.frozen.read().unwrap_or_else(|err| err.into_inner());
+        if !self.force && frozen.contains(language) {
+            return Err(StoreError::LanguageFrozen(language.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Refuses to delete or move `key` while any language it has a localization in is
+    /// frozen, unless this handle is forcing.
+    fn check_key_frozen(&self, doc: &XcStringsFile, key: &str) -> Result<(), StoreError> {
+        let Some(entry) = doc.strings.get(key) else {
+            return Ok(());
+        };
+        for language in entry.localizations.keys() {
+            self.check_frozen(language)?;
+        }
+        Ok(())
+    }
+
+    fn check_writable(&self) -> Result<(), StoreError> {
+        let path = self.path.display().to_string();
+        if self.read_only {
+            return Err(StoreError::ReadOnly(path));
+        }
+        match &self.protected_by {
+            Some(rule) => Err(StoreError::Protected {
+                path,
+                rule: rule.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Locks the catalog for an edit, failing up front when the store is read-only.
+    async fn write_doc(&self) -> Result<DocWriteGuard<'_>, StoreError> {
+        let edit = self.lock_edit().await?;
+        Ok(self.write_doc_with(edit).await)
+    }
+
+    /// Takes the edit lock alone, so an edit can be prepared and written while readers still
+    /// see the catalog; [`Self::write_doc_with`] then swaps it in.
+    async fn lock_edit(&self) -> Result<OwnedMutexGuard<()>, StoreError> {
+        self.check_writable()?;
+        let edit = Arc::clone(&self.edit_lock).lock_owned().await;
+        self.check_revision()?;
+        Ok(edit)
+    }
+
+    async fn write_doc_with(&self, edit: OwnedMutexGuard<()>) -> DocWriteGuard<'_> {
+        DocWriteGuard {
+            doc: self.data.write().await,
+            edit,
+            store: self,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<StoreEvent> {
+        self.events.subscribe()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Opaque token that changes whenever the catalog is saved or reloaded with different
+    /// contents, formatted as a quoted HTTP entity tag.
+    pub fn revision(&self) -> String {
+        format_revision(self.revision.load(Ordering::Acquire))
+    }
+
+    /// Records `serialized` as the catalog's revision. Called before the edit lock is
+    /// released, so the next edit's [`Self::check_revision`] sees it.
+    fn record_revision(&self, serialized: &str) {
+        let hash = content_hash(serialized);
+        self.revision.store(hash, Ordering::Release);
+        if self.expected.is_some() {
+            self.written.store(hash, Ordering::Release);
+        }
+    }
+
+    /// Normalizes `doc` before it's saved. Empty entries are kept; see [`Self::clean`].
+    fn normalize(&self, doc: &mut XcStringsFile) {
+        normalize_strings_file(doc);
+    }
+
+    /// Writes `value` in Xcode's formatting, adjusted by [`Self::with_format_options`] and
+    /// keeping the escaping and line endings the file had when it was read.
+    fn format_json(&self, value: &serde_json::Value) -> String {
+        let style = *self.style.lock().unwrap_or_else(|err| err.into_inner());
+        apple_json_formatter::to_apple_format_with(value, &self.format.with_style(style))
+    }
+
+    /// Serializes the in-memory catalog exactly as it would be written to disk.
+    pub async fn to_apple_json(&self) -> String {
+        let doc = Arc::clone(&*self.data.read().await);
+        self.format_json(&doc.to_json_value())
+    }
+
+    pub async fn reload(&self) -> Result<(), StoreError> {
+        // Locks in the order edits take them, so an edit can't be staged on the catalog this
+        // replaces, and its revision can't be overwritten by the one read here.
+        let _edit = self.edit_lock.lock().await;
+        let mut fingerprint = self.fingerprint.lock().await;
+        let (mut doc, revision, style) = load_catalog(&self.path).await?;
+        normalize_strings_file(&mut doc);
+        *self.data.write().await = Arc::new(doc);
+        // Saves of edits made before the reload must not overwrite what was read.
+        let generation = self.generation.fetch_add(1, Ordering::AcqRel) + 1;
+        self.written_generation.store(generation, Ordering::Release);
+        self.normalized
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+        self.revision.store(revision, Ordering::Release);
+        *self.style.lock().unwrap_or_else(|err| err.into_inner()) = style;
+
+        let current = FileFingerprint::read(&self.path).await;
+        let changed = fingerprint.is_some() && *fingerprint != current;
+        *fingerprint = current;
+        drop(fingerprint);
+
+        if changed {
+            tracing::warn!(
+                path = %self.path.display(),
+                "Catalog changed on disk; reloaded external edits"
+            );
+            let _ = self.events.send(StoreEvent::ExternalChange {
+                path: self.path.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Reloads the file only when [`Self::has_external_changes`], so callers can check for
+    /// outside edits without re-parsing an unchanged catalog. Returns whether it reloaded.
+    pub async fn reload_if_changed(&self) -> Result<bool, StoreError> {
+        if !self.has_external_changes().await {
+            return Ok(false);
+        }
+        self.reload().await?;
+        Ok(true)
+    }
+
+    /// Whether an edit is committed in memory but not yet written to disk.
+    pub fn has_pending_save(&self) -> bool {
+        self.generation.load(Ordering::Acquire) > self.written_generation.load(Ordering::Acquire)
+    }
+
+    /// Returns true when the file on disk no longer matches what this store last read or wrote.
+    pub async fn has_external_changes(&self) -> bool {
+        let fingerprint = self.fingerprint.lock().await;
+        *fingerprint != FileFingerprint::read(&self.path).await
+    }
+
+    /// Serializes a committed edit off the async runtime, then lets the next edit start.
+    async fn serialize(&self, save: PendingSave) -> Result<SerializedSave, StoreError> {
+        let PendingSave {
+            doc,
+            edit,
+            generation,
+            normalized,
+        } = save;
+        let style = *self.style.lock().unwrap_or_else(|err| err.into_inner());
+        let options = self.format.with_style(style);
+        let serialized = task::spawn_blocking(move || {
+            apple_json_formatter::to_apple_format_with(&doc.to_json_value(), &options)
+        })
+        .await
+        .map_err(|err| StoreError::ReadFailed(io::Error::other(err)))?;
+        self.record_revision(&serialized);
+        drop(edit);
+        Ok(SerializedSave {
+            serialized,
+            generation,
+            normalized,
+        })
+    }
+
+    async fn persist(&self, save: PendingSave, change: StoreChange) -> Result<(), StoreError> {
+        let save = self.serialize(save).await?;
+        self.write_serialized(save, change).await
+    }
+
+    async fn write_serialized(
+        &self,
+        save: SerializedSave,
+        change: StoreChange,
+    ) -> Result<(), StoreError> {
+        let SerializedSave {
+            serialized,
+            generation,
+            normalized,
+        } = save;
+        let mut fingerprint = self.fingerprint.lock().await;
+        // Versions are cumulative, so when a later edit already reached the disk it carries
+        // this one too.
+        if generation > self.written_generation.load(Ordering::Acquire) {
+            fs::write(&self.path, &serialized).await?;
+            *fingerprint = FileFingerprint::read(&self.path).await;
+            self.written_generation.store(generation, Ordering::Release);
+        }
+        drop(fingerprint);
+
+        if !normalized.is_empty() {
+            let keys: Vec<&str> = normalized.iter().map(|entry| entry.key.as_str()).collect();
+            tracing::warn!(path = %self.path.display(), ?keys, "Normalization changed entries beyond the requested edit");
+            let _ = NORMALIZED.try_with(|entries| {
+                merge_normalized(&mut entries.borrow_mut(), normalized);
+            });
+        }
+        tracing::info!(path = %self.path.display(), "Saved catalog");
+        self.record_modified(&change).await;
+        let _ = self.events.send(StoreEvent::Saved {
+            path: self.path.clone(),
+            change,
+        });
+        Ok(())
+    }
+
+    /// Directory holding this catalog's snapshots: `<dir>/.xcstrings-snapshots/<file stem>/`.
+    pub fn snapshot_dir(&self) -> PathBuf {
+        let parent = self.path.parent().unwrap_or_else(|| Path::new("."));
+        let stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_os_string())
+            .unwrap_or_else(|| "catalog".into());
+        parent.join(SNAPSHOT_DIR).join(stem)
+    }
+
+    fn snapshot_path(&self, label: &str) -> Result<PathBuf, StoreError> {
+        validate_snapshot_label(label)?;
+        Ok(self.snapshot_dir().join(format!("{label}.xcstrings")))
+    }
+
+    /// Saves the in-memory catalog as a named snapshot, replacing any snapshot with that label.
+    pub async fn snapshot(&self, label: &str) -> Result<PathBuf, StoreError> {
+        self.check_writable()?;
+        let target = self.snapshot_path(label)?;
+        fs::create_dir_all(self.snapshot_dir()).await?;
+        fs::write(&target, self.to_apple_json().await).await?;
+        tracing::info!(path = %self.path.display(), label, "Saved snapshot");
+        Ok(target)
+    }
+
+    /// Labels of the saved snapshots, sorted alphabetically.
+    pub async fn list_snapshots(&self) -> Result<Vec<String>, StoreError> {
+        let mut entries = match fs::read_dir(self.snapshot_dir()).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut labels = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some("xcstrings") {
+                if let Some(label) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    labels.push(label.to_string());
+                }
+            }
+        }
+        labels.sort();
+        Ok(labels)
+    }
+
+    /// Replaces the catalog with a previously saved snapshot and writes it to disk.
+    pub async fn restore_snapshot(&self, label: &str) -> Result<(), StoreError> {
+        let source = self.snapshot_path(label)?;
+        let raw = match fs::read_to_string(&source).await {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(StoreError::SnapshotMissing(label.to_string()));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let mut restored = XcStringsFile::parse(&raw)?;
+        self.normalize(&mut restored);
+
+        let mut doc = self.write_doc().await?;
+        doc.replace(restored);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::SnapshotRestored {
+                label: label.to_string(),
+            },
+        )
+        .await?;
+        tracing::info!(path = %self.path.display(), label, "Restored snapshot");
+        Ok(())
+    }
+
+    /// Sidecar file holding this catalog's assignments: `<dir>/<file stem>.assignments.json`.
+    pub fn assignments_path(&self) -> PathBuf {
+        sidecar_path(&self.path, "assignments")
+    }
+
+    /// Returns who owns which language and who is assigned which key.
+    pub async fn assignments(&self) -> Result<Assignments, StoreError> {
+        Ok(Assignments::load(&self.assignments_path()).await?)
+    }
+
+    /// Assigns `keys` to `assignee` and makes them the owner of `languages`. A missing or
+    /// blank assignee removes the assignments instead. Keys and languages must exist.
+    pub async fn assign(
+        &self,
+        keys: &[String],
+        languages: &[String],
+        assignee: Option<&str>,
+    ) -> Result<Assignments, StoreError> {
+        self.check_writable()?;
+        let assignee = assignee.map(str::trim).filter(|name| !name.is_empty());
+        {
+            let doc = self.data.read().await;
+            if let Some(key) = keys.iter().find(|key| !doc.strings.contains_key(*key)) {
+                return Err(StoreError::KeyMissing(key.clone()));
+            }
+        }
+        if !languages.is_empty() {
+            let known = self.list_languages().await;
+            if let Some(language) = languages.iter().find(|language| !known.contains(language)) {
+                return Err(StoreError::LanguageMissing(language.clone()));
+            }
+        }
+
+        let _guard = self.sidecar_lock.lock().await;
+        let path = self.assignments_path();
+        let mut assignments = Assignments::load(&path).await?;
+        assignments.assign_keys(keys.iter().map(String::as_str), assignee);
+        assignments.assign_languages(languages.iter().map(String::as_str), assignee);
+        assignments.save(&path).await?;
+        tracing::info!(
+            path = %self.path.display(),
+            keys = keys.len(),
+            languages = languages.len(),
+            assignee = assignee.unwrap_or("-"),
+            "Updated assignments"
+        );
+        Ok(assignments)
+    }
+
+    /// Sidecar file holding when this catalog's keys and translations last changed:
+    /// `<dir>/<file stem>.modified.json`.
+    pub fn modified_path(&self) -> PathBuf {
+        sidecar_path(&self.path, "modified")
+    }
+
+    /// When `key` and its translations last changed through this server, if they have
+    /// since the index was started. Edits made outside the server aren't tracked.
+    pub fn modified(&self, key: &str) -> Option<KeyModified> {
+        let index = self.modified.read().unwrap_or_else(|err| err.into_inner());
+        index.get(key).cloned()
+    }
+
+    /// Records a saved `change` in the modified index and writes its sidecar. Failures are
+    /// logged rather than returned, since the catalog itself is already saved.
+    async fn record_modified(&self, change: &StoreChange) {
+        let _guard = self.sidecar_lock.lock().await;
+        let updated = {
+            let mut index = self.modified.write().unwrap_or_else(|err| err.into_inner());
+            if !apply_modified(&mut index, change, &modified::now()) {
+                return;
+            }
+            index.clone()
+        };
+        if let Err(err) = updated.save(&self.modified_path()).await {
+            tracing::warn!(path = %self.path.display(), ?err, "Failed to write modified index");
+        }
+    }
+
+    /// Sidecar file listing this catalog's frozen languages: `<dir>/<file stem>.frozen.json`.
+    pub fn frozen_path(&self) -> PathBuf {
+        sidecar_path(&self.path, "frozen")
+    }
+
+    /// Languages signed off with [`Self::freeze_language`].
+    pub fn frozen_languages(&self) -> Vec<String> {
+        let frozen = self.frozen.read().unwrap_or_else(|err| err.into_inner());
+        frozen.languages.iter().cloned().collect()
+    }
+
+    /// Keys that keep `language` from being complete: translatable keys without text, or
+    /// whose state is new, needs review, or stale. Keys without a source-language
+    /// localization use the key itself as their text, so they count as complete there.
+    pub async fn incomplete_keys(&self, language: &str) -> Vec<String> {
+        let doc = self.data.read().await;
+        doc.strings
+            .iter()
+            .filter(|(_, entry)| entry.should_translate != Some(false))
+            .filter(|(_, entry)| match entry.localizations.get(language) {
+                None => language != doc.source_language,
+                Some(loc) => {
+                    !supplies_text(loc)
+                        || matches!(
+                            localization_state(loc).as_deref(),
+                            Some(
+                                "new"
+                                    | "needs_review"
+                                    | "needs-review"
+                                    | "needs-translation"
+                                    | "stale"
+                            )
+                        )
+                }
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// Freezes `language` so further edits to it fail with [`StoreError::LanguageFrozen`]
+    /// unless they come through [`Self::forcing`]. Fails with
+    /// [`StoreError::LanguageIncomplete`] while [`Self::incomplete_keys`] reports any keys,
+    /// unless `force` is set. Returns every frozen language.
+    pub async fn freeze_language(
+        &self,
+        language: &str,
+        force: bool,
+    ) -> Result<Vec<String>, StoreError> {
+        self.check_writable()?;
+        let language = language.trim();
+        if !self
+            .list_languages()
+            .await
+            .iter()
+            .any(|known| known == language)
+        {
+            return Err(StoreError::LanguageMissing(language.to_string()));
+        }
+        if !force {
+            let keys = self.incomplete_keys(language).await;
+            if !keys.is_empty() {
+                return Err(StoreError::LanguageIncomplete {
+                    language: language.to_string(),
+                    keys,
+                });
+            }
+        }
+        self.update_frozen(|frozen| frozen.languages.insert(language.to_string()))
+            .await?;
+        tracing::info!(path = %self.path.display(), language, force, "Froze language");
+        Ok(self.frozen_languages())
+    }
+
+    /// Lifts [`Self::freeze_language`]. Returns the languages still frozen.
+    pub async fn unfreeze_language(&self, language: &str) -> Result<Vec<String>, StoreError> {
+        self.check_writable()?;
+        let language = language.trim();
+        self.update_frozen(|frozen| frozen.languages.remove(language))
+            .await?;
+        tracing::info!(path = %self.path.display(), language, "Unfroze language");
+        Ok(self.frozen_languages())
+    }
+
+    /// Applies `change` to the frozen languages and saves the sidecar when it reports a change.
+    async fn update_frozen(
+        &self,
+        change: impl FnOnce(&mut FrozenLanguages) -> bool,
+    ) -> Result<(), StoreError> {
+        let _guard = self.sidecar_lock.lock().await;
+        let mut updated = self
+            .frozen
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone();
+        if !change(&mut updated) {
+            return Ok(());
+        }
+        updated.save(&self.frozen_path()).await?;
+        *self.frozen.write().unwrap_or_else(|err| err.into_inner()) = updated;
+        Ok(())
+    }
+
+    pub async fn list_languages(&self) -> Vec<String> {
+        let doc = self.data.read().await;
+        let mut langs: BTreeSet<String> = BTreeSet::new();
+        langs.insert(doc.source_language.clone());
+        for entry in doc.strings.values() {
+            langs.extend(entry.localizations.keys().cloned());
+        }
+        langs.into_iter().collect()
+    }
+
+    pub async fn source_language(&self) -> String {
+        self.data.read().await.source_language.clone()
+    }
+
+    /// Returns every string key in file order.
+    pub async fn keys(&self) -> Vec<String> {
+        let doc = self.data.read().await;
+        doc.strings.keys().cloned().collect()
+    }
+
+    /// Keys whose name or translations contain `filter` (case-insensitive), in file order;
+    /// every key when `filter` is `None`.
+    pub async fn matching_keys(&self, filter: Option<&str>) -> Vec<String> {
+        let query = filter.map(str::to_lowercase);
+        let doc = self.data.read().await;
+        doc.strings
+            .iter()
+            .filter(|(key, entry)| query.as_ref().is_none_or(|q| entry_matches(key, entry, q)))
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// How many keys [`Self::matching_keys`] would return, without copying them.
+    pub async fn count_keys(&self, filter: Option<&str>) -> usize {
+        let query = filter.map(str::to_lowercase);
+        let doc = self.data.read().await;
+        doc.strings
+            .iter()
+            .filter(|(key, entry)| query.as_ref().is_none_or(|q| entry_matches(key, entry, q)))
+            .count()
+    }
+
+    pub async fn contains_key(&self, key: &str) -> bool {
+        self.data.read().await.strings.contains_key(key)
+    }
+
+    /// Existing keys close to `key`, for agents that guessed a name slightly wrong; see
+    /// [`similar::rank`].
+    pub async fn similar_keys(
+        &self,
+        key: &str,
+        max_distance: usize,
+        limit: usize,
+    ) -> Vec<SimilarKey> {
+        let doc = self.data.read().await;
+        similar::rank(key, doc.strings.keys(), max_distance, limit)
+    }
+
+    /// Runs the selected QA rules (all when empty) over the catalog as currently loaded.
+    /// Per-language expansion of translations over the source text, with each language's
+    /// `top` longest texts; see [`analytics::length_report`].
+    pub async fn length_report(&self, top: usize) -> LengthReport {
+        let records = self.list_records(None).await;
+        analytics::length_report(&records, &self.source_language().await, top)
+    }
+
+    /// Prices the work left in `language`, or in every language but the source, at
+    /// `rate_per_word` source words; see [`analytics::estimate_cost`].
+    pub async fn estimate_cost(
+        &self,
+        language: Option<&str>,
+        rate_per_word: f64,
+        only_untranslated: bool,
+        include_do_not_translate: bool,
+    ) -> Result<CostEstimate, StoreError> {
+        let source_language = self.source_language().await;
+        let mut languages = self.list_languages().await;
+        match language {
+            Some(language) if !languages.iter().any(|known| known == language) => {
+                return Err(StoreError::LanguageMissing(language.to_string()));
+            }
+            Some(language) => languages = vec![language.to_string()],
+            None => languages.retain(|language| *language != source_language),
+        }
+        let records = self.list_records(None).await;
+        Ok(analytics::estimate_cost(
+            &records,
+            &source_language,
+            &languages,
+            rate_per_word,
+            only_untranslated,
+            include_do_not_translate,
+        ))
+    }
+
+    /// Phrases recurring in the source text of at least `min_occurrences` keys, each with its
+    /// usual translation per language, to bootstrap a glossary; terms already in `glossary`
+    /// are left out. See [`terminology::extract`].
+    pub async fn extract_terminology(
+        &self,
+        min_occurrences: usize,
+        glossary: &BTreeMap<String, BTreeMap<String, String>>,
+    ) -> Vec<TermCandidate> {
+        let records = self.list_records(None).await;
+        terminology::extract(
+            &records,
+            &self.source_language().await,
+            glossary,
+            min_occurrences,
+        )
+    }
+
+    /// Lists the commits, among the last `depth` that touched the catalog, in which the
+    /// translation of `key` into `language` was added, changed, or removed.
+    #[cfg(feature = "git")]
+    pub async fn key_history(
+        &self,
+        key: &str,
+        language: &str,
+        depth: usize,
+    ) -> Result<KeyHistory, StoreError> {
+        // Translations removed since still have a history.
+        let current = self.get_translation(key, language).await.ok();
+        let (root, log) = git::file_log(&self.path, depth)
+            .await?
+            .ok_or_else(|| StoreError::NotInGitRepository(self.path.display().to_string()))?;
+        let mut revisions = Vec::with_capacity(log.len());
+        for revision in log {
+            let value = match git::show(&root, &revision.commit, &revision.path).await? {
+                Some(raw) => {
+                    // Revisions that aren't valid catalogs, such as conflicted merges, hold
+                    // no translation.
+                    XcStringsFile::parse(&raw).ok().and_then(|doc| {
+                        doc.strings
+                            .get(key)
+                            .and_then(|entry| entry.localizations.get(language))
+                            .map(TranslationValue::from_localization)
+                    })
+                }
+                None => None,
+            };
+            revisions.push((revision, value));
+        }
+        let uncommitted = revisions
+            .first()
+            .map_or(current.is_some(), |(_, committed)| *committed != current);
+        Ok(KeyHistory {
+            key: key.to_string(),
+            language: language.to_string(),
+            current,
+            uncommitted,
+            changes: git::changes(revisions),
+        })
+    }
+
+    /// Lists translations into `language` whose source text changed in a later commit than
+    /// the translation itself, among the last `depth` commits that touched the catalog.
+    /// Uncommitted edits count as the newest change. Keys marked `shouldTranslate: false`
+    /// are left out.
+    #[cfg(feature = "git")]
+    pub async fn list_outdated(
+        &self,
+        language: &str,
+        depth: usize,
+    ) -> Result<OutdatedReport, StoreError> {
+        if !self
+            .list_languages()
+            .await
+            .iter()
+            .any(|known| known == language)
+        {
+            return Err(StoreError::LanguageMissing(language.to_string()));
+        }
+        // The source text of a key, `None` inside when it is the key itself; the outer
+        // `None` marks a version without the key.
+        fn source_text(entry: &XcStringEntry, source_language: &str) -> Option<TranslationValue> {
+            entry
+                .localizations
+                .get(source_language)
+                .map(|loc| text_only(&TranslationValue::from_localization(loc)))
+        }
+
+        let (source_language, current) = {
+            let doc = self.data.read().await;
+            if doc.source_language == language {
+                return Err(StoreError::InvalidLanguage(format!(
+                    "'{language}' is the source language"
+                )));
+            }
+            let current: Vec<_> = doc
+                .strings
+                .iter()
+                .filter(|(_, entry)| entry.should_translate != Some(false))
+                .filter_map(|(key, entry)| {
+                    let translation =
+                        TranslationValue::from_localization(entry.localizations.get(language)?);
+                    Some((
+                        key.clone(),
+                        source_text(entry, &doc.source_language),
+                        translation,
+                    ))
+                })
+                .collect();
+            (doc.source_language.clone(), current)
+        };
+
+        let (root, log) = git::file_log(&self.path, depth)
+            .await?
+            .ok_or_else(|| StoreError::NotInGitRepository(self.path.display().to_string()))?;
+        let commits = log.len();
+        let mut revisions = Vec::with_capacity(commits);
+        for revision in log {
+            // Revisions that aren't valid catalogs, such as conflicted merges, are skipped.
+            let doc = git::show(&root, &revision.commit, &revision.path)
+                .await?
+                .and_then(|raw| XcStringsFile::parse(&raw).ok());
+            if let Some(doc) = doc {
+                revisions.push((revision, doc));
+            }
+        }
+
+        let mut outdated = Vec::new();
+        for (key, source, translation) in current {
+            // Version 0 is the catalog as the server holds it, then each revision, newest
+            // first.
+            let mut sources = vec![Some(source)];
+            let mut translations = vec![Some(text_only(&translation))];
+            for (_, doc) in &revisions {
+                let entry = doc.strings.get(&key);
+                sources.push(entry.map(|entry| source_text(entry, &source_language)));
+                translations.push(
+                    entry
+                        .and_then(|entry| entry.localizations.get(language))
+                        .map(|loc| text_only(&TranslationValue::from_localization(loc))),
+                );
+            }
+            // With the whole history searched, the catalog was created by the oldest commit.
+            if commits < depth {
+                sources.push(None);
+                translations.push(None);
+            }
+            let Some(source_change) = git::last_change(&sources) else {
+                continue;
+            };
+            let translation_change = git::last_change(&translations);
+            // Translations changed in the same commit as their source kept up with it.
+            if translation_change.is_some_and(|change| change <= source_change) {
+                continue;
+            }
+            let commit = |version: usize| {
+                version
+                    .checked_sub(1)
+                    .map(|index| (&revisions[index].0).into())
+            };
+            outdated.push(OutdatedTranslation {
+                key,
+                value: translation.value,
+                state: translation.state,
+                source_changed: commit(source_change),
+                translation_changed: translation_change.and_then(commit),
+            });
+        }
+        Ok(OutdatedReport {
+            language: language.to_string(),
+            source_language,
+            commits,
+            outdated,
+        })
+    }
+
+    /// Compares the catalog as committed at `reference` (a branch, tag, or commit) with the
+    /// current one, so changes made since then show up as added, removed, or changed. A
+    /// catalog that didn't exist at `reference` counts as empty.
+    #[cfg(feature = "git")]
+    pub async fn diff_against_ref(&self, reference: &str) -> Result<CatalogDiff, StoreError> {
+        let reference = reference.trim();
+        let (root, path) = git::locate(&self.path)
+            .await?
+            .ok_or_else(|| StoreError::NotInGitRepository(self.path.display().to_string()))?;
+        let commit = git::resolve_commit(&root, reference)
+            .await?
+            .ok_or_else(|| StoreError::GitRevisionMissing(reference.to_string()))?;
+        let before = match git::show(&root, &commit, &path).await? {
+            Some(raw) => {
+                let doc = XcStringsFile::parse(&raw)?;
+                doc.strings
+                    .iter()
+                    .enumerate()
+                    .map(|(ordinal, (key, entry))| {
+                        TranslationRecord::from_entry(ordinal, key, entry)
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        let after = self.list_records(None).await;
+        Ok(diff::diff_records(&before, &after))
+    }
+
+    pub async fn validate(&self, rules: &[QaRule], options: &QaOptions) -> QaReport {
+        let doc = self.data.read().await;
+        qa::validate(&doc, rules, options)
+    }
+
+    /// Runs every QA rule and checks each language against `thresholds`, falling back to
+    /// the `minCompletion` of `options` when empty; see [`qa::release_readiness`].
+    pub async fn release_readiness(
+        &self,
+        thresholds: &BTreeMap<String, f64>,
+        options: &QaOptions,
+    ) -> ReleaseReadiness {
+        let report = self.validate(&[], options).await;
+        let percentages = self
+            .get_translation_percentages()
+            .await
+            .into_iter()
+            .collect();
+        let thresholds = if thresholds.is_empty() {
+            &options.min_completion
+        } else {
+            thresholds
+        };
+        qa::release_readiness(&report, &percentages, thresholds)
+    }
+
+    /// Returns a map of languages to their untranslated keys.
+    /// A translation is considered untranslated if:
+    /// - The value is empty/None
+    /// - No localization exists for that language
+    ///
+    /// Keys marked as should_translate=false are left out, since nobody should translate
+    /// them; see [`Self::list_untranslated_with`] to include them.
+    pub async fn list_untranslated(&self) -> HashMap<String, Vec<String>> {
+        self.list_untranslated_with(false).await
+    }
+
+    /// Like [`Self::list_untranslated`], also listing keys marked should_translate=false
+    /// when `include_do_not_translate` is set.
+    pub async fn list_untranslated_with(
+        &self,
+        include_do_not_translate: bool,
+    ) -> HashMap<String, Vec<String>> {
+        let doc = self.data.read().await;
+        let mut result: HashMap<String, Vec<String>> = HashMap::new();
+
+        // Get all languages
+        let mut langs: BTreeSet<String> = BTreeSet::new();
+        langs.insert(doc.source_language.clone());
+        for entry in doc.strings.values() {
+            langs.extend(entry.localizations.keys().cloned());
+        }
+
+        // For each key, check which languages have untranslated values
+        for (key, entry) in doc
+            .strings
+            .iter()
+            .filter(|(_, entry)| include_do_not_translate || entry.should_translate != Some(false))
+        {
+            // Check each language for untranslated status
+            for lang in langs.iter() {
+                let is_untranslated = if let Some(localization) = entry.localizations.get(lang) {
+                    match extract_translation_value(localization) {
+                        None => true,                            // No value
+                        Some(value) if value.is_empty() => true, // Empty value
+                        Some(_) => false, // Has a value (even if same as other languages)
+                    }
+                } else {
+                    true // No localization for this language
+                };
+
+                if is_untranslated {
+                    result.entry(lang.clone()).or_default().push(key.clone());
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Returns a map of languages to their translation percentage (0-100)
+    /// Keys marked as should_translate=false are excluded from the calculation
+    /// A translation is considered complete if it has a non-empty value
+    pub async fn get_translation_percentages(&self) -> HashMap<String, f64> {
+        self.get_translation_percentages_with(false).await
+    }
+
+    /// Like [`Self::get_translation_percentages`], counting keys marked
+    /// should_translate=false too when `include_do_not_translate` is set.
+    pub async fn get_translation_percentages_with(
+        &self,
+        include_do_not_translate: bool,
+    ) -> HashMap<String, f64> {
+        let doc = self.data.read().await;
+        let mut result: HashMap<String, f64> = HashMap::new();
+
+        // Get all languages
+        let mut langs: BTreeSet<String> = BTreeSet::new();
+        langs.insert(doc.source_language.clone());
+        for entry in doc.strings.values() {
+            langs.extend(entry.localizations.keys().cloned());
+        }
+
+        // Count only keys that should be translated
+        let translatable_keys: Vec<&String> = doc
+            .strings
+            .iter()
+            .filter(|(_, entry)| include_do_not_translate || entry.should_translate.unwrap_or(true))
+            .map(|(key, _)| key)
+            .collect();
+
+        if translatable_keys.is_empty() {
+            return result;
+        }
+
+        let total_keys = translatable_keys.len() as f64;
+
+        for lang in langs.iter() {
+            let mut translated_count = 0;
+
+            for key in translatable_keys.iter() {
+                let entry = &doc.strings[*key];
+
+                // Check if this language has a valid translation (non-empty value)
+                let is_translated = if let Some(localization) = entry.localizations.get(lang) {
+                    match extract_translation_value(localization) {
+                        None => false,
+                        Some(value) if value.is_empty() => false,
+                        Some(_) => true, // Has a non-empty value
+                    }
+                } else {
+                    false
+                };
+
+                if is_translated {
+                    translated_count += 1;
+                }
+            }
+
+            let percentage = (translated_count as f64 / total_keys) * 100.0;
+            result.insert(lang.clone(), percentage);
+        }
+
+        result
+    }
+
+    /// Adds `language` to every key, returning the code as stored after normalization.
+    pub async fn add_language(&self, language: &str) -> Result<String, StoreError> {
+        let language = normalize_language_code(language)?;
+
+        let mut doc = self.write_doc().await?;
+
+        // Check if language already exists
+        let mut existing_langs: BTreeSet<String> = BTreeSet::new();
+        existing_langs.insert(doc.source_language.clone());
+        for entry in doc.strings.values() {
+            existing_langs.extend(entry.localizations.keys().cloned());
+        }
+
+        if existing_langs.contains(&language) {
+            return Err(StoreError::LanguageExists(language));
+        }
+
+        // Add placeholder localizations for the new language so editors can immediately
+        // surface translation slots without clobbering existing values.
+        for entry in doc.strings.values_mut() {
+            entry
+                .localizations
+                .entry(language.clone())
+                .or_insert_with(placeholder_localization);
+        }
+
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::LanguageAdded {
+                language: language.clone(),
+            },
+        )
+        .await?;
+        Ok(language)
+    }
+
+    pub async fn remove_language(&self, language: &str) -> Result<(), StoreError> {
+        let trimmed = language.trim();
+        if trimmed.is_empty() {
+            return Err(StoreError::InvalidLanguage(
+                "Language code cannot be empty".to_string(),
+            ));
+        }
+        let language = trimmed.to_string();
+
+        let mut doc = self.write_doc().await?;
+        self.check_frozen(&language)?;
+
+        // Cannot remove the source language
+        if language == doc.source_language {
+            return Err(StoreError::CannotRemoveSourceLanguage(language));
+        }
+
+        // Check if language exists
+        let mut language_exists = false;
+        for entry in doc.strings.values() {
+            if entry.localizations.contains_key(language.as_str()) {
+                language_exists = true;
+                break;
+            }
+        }
+
+        if !language_exists {
+            return Err(StoreError::LanguageMissing(language.clone()));
+        }
+
+        // Remove the language from all string entries, along with entries it was the last
+        // localization of, even if they have a comment, and report those like a cleanup.
+        let mut emptied = Vec::new();
+        doc.strings.retain(|key, entry| {
+            let keep = entry
+                .localizations
+                .shift_remove(language.as_str())
+                .is_none()
+                || !entry.localizations.is_empty();
+            if !keep {
+                emptied.push(NormalizedEntry {
+                    key: key.clone(),
+                    removed: true,
+                    dropped_languages: Vec::new(),
+                });
+            }
+            keep
+        });
+        merge_normalized(
+            &mut self
+                .normalized
+                .lock()
+                .unwrap_or_else(|err| err.into_inner()),
+            emptied,
+        );
+
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(save, StoreChange::LanguageRemoved { language })
+            .await?;
+        Ok(())
+    }
+
+    /// Renames `old_language`, returning the new code as stored after normalization. The old
+    /// code is only trimmed so malformed codes already in a catalog can be fixed this way.
+    pub async fn update_language(
+        &self,
+        old_language: &str,
+        new_language: &str,
+    ) -> Result<String, StoreError> {
+        let old_trimmed = old_language.trim();
+        if old_trimmed.is_empty() {
+            return Err(StoreError::InvalidLanguage(
+                "Language code cannot be empty".to_string(),
+            ));
+        }
+        let new_language = normalize_language_code(new_language)?;
+
+        if old_trimmed == new_language {
+            return Ok(new_language); // No change needed
+        }
+
+        let old_language = old_trimmed.to_string();
+
+        let mut doc = self.write_doc().await?;
+        self.check_frozen(&old_language)?;
+
+        // Cannot rename the source language
+        if old_language == doc.source_language {
+            return Err(StoreError::CannotRenameSourceLanguage(old_language));
+        }
+
+        // Check if old language exists
+        let mut old_language_exists = false;
+        for entry in doc.strings.values() {
+            if entry.localizations.contains_key(old_language.as_str()) {
+                old_language_exists = true;
+                break;
+            }
+        }
+
+        if !old_language_exists {
+            return Err(StoreError::LanguageMissing(old_language));
+        }
+
+        // Check if new language already exists
+        let mut new_language_exists = false;
+        for entry in doc.strings.values() {
+            if entry.localizations.contains_key(new_language.as_str()) {
+                new_language_exists = true;
+                break;
+            }
+        }
+
+        if new_language_exists {
+            return Err(StoreError::LanguageExists(new_language.clone()));
+        }
+
+        // Rename the language in all string entries
+        for entry in doc.strings.values_mut() {
+            if let Some(localization) = entry.localizations.shift_remove(old_language.as_str()) {
+                entry
+                    .localizations
+                    .insert(new_language.clone(), localization);
+            }
+        }
+
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::LanguageRenamed {
+                old_language,
+                new_language: new_language.clone(),
+            },
+        )
+        .await?;
+        Ok(new_language)
+    }
+
+    /// Every key, or with a `filter` only those whose name or translations contain it
+    /// (case-insensitive), each with the [`SearchMatch`]es that say where.
+    pub async fn list_records(&self, filter: Option<&str>) -> Vec<TranslationRecord> {
+        let query = filter.filter(|query| !query.is_empty());
+        let doc = self.data.read().await;
+        let index = self.modified.read().unwrap_or_else(|err| err.into_inner());
+        doc.strings
+            .iter()
+            .enumerate()
+            .filter_map(|(ordinal, (key, entry))| {
+                let matches = match query {
+                    Some(query) if !entry_matches(key, entry, &query.to_lowercase()) => {
+                        return None
+                    }
+                    Some(query) => search_matches(key, entry, query),
+                    None => Vec::new(),
+                };
+                Some(TranslationRecord {
+                    matches,
+                    modified: index.get(key).cloned(),
+                    ..TranslationRecord::from_entry(ordinal, key, entry)
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the full entry for one key.
+    pub async fn get_record(&self, key: &str) -> Option<TranslationRecord> {
+        let doc = self.data.read().await;
+        doc.strings
+            .get_full(key)
+            .map(|(ordinal, key, entry)| TranslationRecord {
+                modified: self.modified(key),
+                ..TranslationRecord::from_entry(ordinal, key, entry)
+            })
+    }
+
+    /// The JSON of one key exactly as it's saved in the catalog.
+    pub async fn raw_entry(&self, key: &str) -> Result<serde_json::Value, StoreError> {
+        let doc = self.data.read().await;
+        let entry = doc
+            .strings
+            .get(key)
+            .ok_or_else(|| StoreError::KeyMissing(key.to_string()))?;
+        Ok(serde_json::to_value(entry)?)
+    }
+
+    /// Adds a new key the way Xcode does for manually added strings, with extraction state
+    /// `manual`, an optional comment, and an optional value in the source language, placed
+    /// at `position`.
+    pub async fn create_key(
+        &self,
+        key: &str,
+        comment: Option<String>,
+        source_value: Option<String>,
+        position: &InsertPosition,
+    ) -> Result<TranslationRecord, StoreError> {
+        let mut doc = self.write_doc().await?;
+        if doc.strings.contains_key(key) {
+            return Err(StoreError::KeyExists(key.to_string()));
+        }
+        let index = doc.insertion_index(key, position)?;
+        self.check_comment(&doc, key, comment.as_deref())?;
+        let mut entry = XcStringEntry {
+            comment,
+            extraction_state: Some(MANUAL_EXTRACTION_STATE.to_string()),
+            ..Default::default()
+        };
+        if let Some(value) = source_value.filter(|value| !value.trim().is_empty()) {
+            let mut loc = XcLocalization::default();
+            apply_update(
+                &mut loc,
+                TranslationUpdate::from_value_state(Some(value), None),
+            );
+            entry.localizations.insert(doc.source_language.clone(), loc);
+        }
+        doc.strings.shift_insert(index, key.to_string(), entry);
+
+        self.normalize(&mut doc);
+        let record = doc
+            .strings
+            .get_full(key)
+            .map(|(ordinal, key, entry)| TranslationRecord::from_entry(ordinal, key, entry))
+            .ok_or_else(|| StoreError::KeyMissing(key.to_string()))?;
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::KeyCreated {
+                key: key.to_string(),
+            },
+        )
+        .await?;
+        Ok(record)
+    }
+
+    /// Like [`Self::list_records`], with summaries instead of full translations.
+    pub async fn list_summaries(&self, filter: Option<&str>) -> Vec<TranslationSummary> {
+        let query = filter.filter(|query| !query.is_empty());
+        let doc = self.data.read().await;
+        let index = self.modified.read().unwrap_or_else(|err| err.into_inner());
+        doc.strings
+            .iter()
+            .enumerate()
+            .filter_map(|(ordinal, (key, entry))| {
+                let matches = match query {
+                    Some(query) if !entry_matches(key, entry, &query.to_lowercase()) => {
+                        return None
+                    }
+                    Some(query) => search_matches(key, entry, query),
+                    None => Vec::new(),
+                };
+
+                let languages = entry.localizations.keys().cloned().collect();
+                let has_variations = entry
+                    .localizations
+                    .values()
+                    .any(|loc| !loc.variations.is_empty() || !loc.substitutions.is_empty());
+
+                let states = entry
+                    .localizations
+                    .iter()
+                    .filter_map(|(lang, loc)| Some((lang.clone(), localization_state(loc)?)))
+                    .collect();
+
+                Some(TranslationSummary {
+                    key: key.clone(),
+                    ordinal,
+                    comment: entry.comment.clone(),
+                    languages,
+                    has_variations,
+                    states,
+                    modified: index.get(key).map(|modified| modified.at.clone()),
+                    matches,
+                })
+            })
+            .collect()
+    }
+
+    /// The text `key` shows in the source language: its value there, or the key itself as
+    /// Xcode shows it when there is none.
+    pub async fn source_text(&self, key: &str) -> Result<String, StoreError> {
+        let doc = self.data.read().await;
+        let entry = doc
+            .strings
+            .get(key)
+            .ok_or_else(|| StoreError::KeyMissing(key.to_string()))?;
+        Ok(entry
+            .localizations
+            .get(&doc.source_language)
+            .and_then(extract_translation_value)
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| key.to_string()))
+    }
+
+    /// The keys with a plain, non-empty translation into `language`, paired with their
+    /// source text, to suggest from; `skip` leaves out the key being translated.
+    pub async fn memory_entries(
+        &self,
+        language: &str,
+        catalog: &str,
+        skip: Option<&str>,
+    ) -> Vec<MemoryEntry> {
+        let doc = self.data.read().await;
+        doc.strings
+            .iter()
+            .filter(|(key, _)| skip != Some(key.as_str()))
+            .filter_map(|(key, entry)| {
+                let localization = entry.localizations.get(language)?;
+                let translation = extract_translation_value(localization)?;
+                if translation.is_empty() {
+                    return None;
+                }
+                let source = entry
+                    .localizations
+                    .get(&doc.source_language)
+                    .and_then(extract_translation_value)
+                    .filter(|value| !value.is_empty())
+                    .unwrap_or_else(|| key.clone());
+                Some(MemoryEntry {
+                    catalog: catalog.to_string(),
+                    key: key.clone(),
+                    source,
+                    translation,
+                    state: localization_state(localization),
+                })
+            })
+            .collect()
+    }
+
+    /// Each key's text and state in `language`, in file order, for agents that work through
+    /// one language at a time. Keys matching `filter` as in [`Self::list_summaries`] are
+    /// listed, including those without a localization in the language.
+    pub async fn list_language_values(
+        &self,
+        language: &str,
+        filter: Option<&str>,
+    ) -> Result<Vec<LanguageValue>, StoreError> {
+        let query = filter
+            .filter(|query| !query.is_empty())
+            .map(str::to_lowercase);
+        let doc = self.data.read().await;
+        if language != doc.source_language
+            && !doc
+                .strings
+                .values()
+                .any(|entry| entry.localizations.contains_key(language))
+        {
+            return Err(StoreError::LanguageMissing(language.to_string()));
+        }
+        Ok(doc
+            .strings
+            .iter()
+            .filter(|(key, entry)| {
+                query
+                    .as_deref()
+                    .is_none_or(|query| entry_matches(key, entry, query))
+            })
+            .map(|(key, entry)| {
+                let localization = entry.localizations.get(language);
+                LanguageValue {
+                    key: key.clone(),
+                    value: localization.and_then(extract_translation_value),
+                    state: localization.and_then(localization_state),
+                    has_variations: localization.is_some_and(|loc| {
+                        !loc.variations.is_empty() || !loc.substitutions.is_empty()
+                    }),
+                }
+            })
+            .collect())
+    }
+
+    /// Fetches one translation. A missing key is [`StoreError::KeyMissing`], a language no
+    /// key has is [`StoreError::LanguageMissing`], and a key that only lacks this language
+    /// is [`StoreError::TranslationMissing`], so callers can tell what needs creating.
+    pub async fn get_translation(
+        &self,
+        key: &str,
+        language: &str,
+    ) -> Result<TranslationValue, StoreError> {
+        let doc = self.data.read().await;
+        let entry = doc
+            .strings
+            .get(key)
+            .ok_or_else(|| StoreError::KeyMissing(key.to_string()))?;
+        if let Some(localization) = entry.localizations.get(language) {
+            return Ok(TranslationValue::from_localization(localization));
+        }
+        let known = language == doc.source_language
+            || doc
+                .strings
+                .values()
+                .any(|entry| entry.localizations.contains_key(language));
+        Err(if known {
+            StoreError::TranslationMissing {
+                key: key.to_string(),
+                language: language.to_string(),
+            }
+        } else {
+            StoreError::LanguageMissing(language.to_string())
+        })
+    }
+
+    /// Keys with a `language` text (the source language when `None`) equal to `text`,
+    /// ignoring case and surrounding whitespace; exact matches come first. Variation cases
+    /// count, and in the source language so does a key without a value, which Xcode shows
+    /// as the key itself.
+    pub async fn find_keys_by_value(
+        &self,
+        text: &str,
+        language: Option<&str>,
+    ) -> Result<Vec<ValueMatch>, StoreError> {
+        let doc = self.data.read().await;
+        let language = language.unwrap_or(&doc.source_language);
+        let is_source = language == doc.source_language;
+        if !is_source
+            && !doc
+                .strings
+                .values()
+                .any(|entry| entry.localizations.contains_key(language))
+        {
+            return Err(StoreError::LanguageMissing(language.to_string()));
+        }
+        let wanted = text.trim().to_lowercase();
+        let mut matches = Vec::new();
+        for (key, entry) in &doc.strings {
+            let texts = match entry.localizations.get(language) {
+                Some(localization) => {
+                    analytics::texts(&TranslationValue::from_localization(localization))
+                }
+                None if is_source => vec![(String::new(), key.clone())],
+                None => continue,
+            };
+            for (variation, value) in texts {
+                if value.trim().to_lowercase() == wanted {
+                    matches.push(ValueMatch {
+                        key: key.clone(),
+                        language: language.to_string(),
+                        variation,
+                        exact: value == text,
+                        value,
+                    });
+                }
+            }
+        }
+        // Stable, so each group keeps file order.
+        matches.sort_by_key(|m| !m.exact);
+        Ok(matches)
+    }
+
+    /// Looks up `key` the way the OS picks a localization: `language`, then each parent
+    /// locale (`zh-Hans-CN`, `zh-Hans`, `zh`), then the source language. Placeholders left by
+    /// [`Self::add_language`] don't count. A key without a source localization resolves to
+    /// the key itself, as Xcode does.
+    pub async fn get_translation_resolved(
+        &self,
+        key: &str,
+        language: &str,
+    ) -> Result<ResolvedTranslation, StoreError> {
+        let doc = self.data.read().await;
+        let entry = doc
+            .strings
+            .get(key)
+            .ok_or_else(|| StoreError::KeyMissing(key.to_string()))?;
+        let (resolved_language, value) = resolve(key, entry, language, &doc.source_language);
+        Ok(ResolvedTranslation {
+            requested_language: language.to_string(),
+            fallback: resolved_language != language,
+            resolved_language,
+            value,
+        })
+    }
+
+    /// Groups keys into a tree by their dot-separated prefixes (`checkout.payment.title` sits
+    /// under `checkout` and `checkout.payment`) with key counts and completion per namespace.
+    /// Keys containing whitespace or empty segments are sentences rather than identifiers, so
+    /// they stay at the root.
+    pub async fn key_namespaces(&self) -> KeyNamespace {
+        let languages: BTreeSet<String> = self.list_languages().await.into_iter().collect();
+        let doc = self.data.read().await;
+        let mut root = NamespaceCounts::default();
+        for (key, entry) in &doc.strings {
+            let mut segments: Vec<&str> = key.split('.').collect();
+            if key.contains(char::is_whitespace) || segments.iter().any(|s| s.is_empty()) {
+                segments = vec![key];
+            }
+            segments.pop();
+
+            let translated: Vec<&String> = entry
+                .localizations
+                .iter()
+                .filter(|(_, loc)| extract_translation_value(loc).is_some_and(|v| !v.is_empty()))
+                .map(|(language, _)| language)
+                .collect();
+            let translatable = entry.should_translate.unwrap_or(true);
+            let mut node = &mut root;
+            for depth in 0..=segments.len() {
+                node.total += 1;
+                if translatable {
+                    node.translatable += 1;
+                    for language in &translated {
+                        *node.translated.entry((*language).clone()).or_default() += 1;
+                    }
+                }
+                match segments.get(depth) {
+                    Some(segment) => {
+                        node = node.children.entry(segment.to_string()).or_default();
+                    }
+                    None => node.direct += 1,
+                }
+            }
+        }
+        root.into_namespace(String::new(), &languages)
+    }
+
+    /// Compares every regional locale with the nearest parent locale in the catalog (which
+    /// may be the source language), showing which keys actually need regional upkeep.
+    pub async fn regional_overrides(&self) -> Vec<RegionalOverrides> {
+        let languages = self.list_languages().await;
+        let doc = self.data.read().await;
+
+        let mut reports = Vec::new();
+        for language in &languages {
+            let Some(parent) =
+                language::parents(language).find(|parent| languages.iter().any(|l| l == parent))
+            else {
+                continue;
+            };
+            let mut report = RegionalOverrides {
+                language: language.clone(),
+                parent: parent.to_string(),
+                overridden_keys: Vec::new(),
+                redundant_keys: Vec::new(),
+                inherited: 0,
+            };
+            for (key, entry) in &doc.strings {
+                let Some(regional) = entry
+                    .localizations
+                    .get(language)
+                    .filter(|loc| supplies_text(loc))
+                else {
+                    report.inherited += 1;
+                    continue;
+                };
+                let (_, inherited) = resolve(key, entry, parent, &doc.source_language);
+                if text_only(&TranslationValue::from_localization(regional))
+                    == text_only(&inherited)
+                {
+                    report.redundant_keys.push(key.clone());
+                } else {
+                    report.overridden_keys.push(key.clone());
+                }
+            }
+            reports.push(report);
+        }
+        reports
+    }
+
+    pub async fn upsert_translation(
+        &self,
+        key: &str,
+        language: &str,
+        update: TranslationUpdate,
+    ) -> Result<UpsertOutcome, StoreError> {
+        self.upsert_translation_with(key, language, update, None, &InsertPosition::End, true)
+            .await
+    }
+
+    /// Like [`Self::upsert_translation`], but with `allow_create` false an unknown key fails
+    /// with [`StoreError::KeyMissing`] instead of being created, so a typo can't add a key.
+    /// A key the upsert creates gets `comment` and goes at `position`; existing keys keep
+    /// their comment and place.
+    pub async fn upsert_translation_with(
+        &self,
+        key: &str,
+        language: &str,
+        mut update: TranslationUpdate,
+        comment: Option<String>,
+        position: &InsertPosition,
+        allow_create: bool,
+    ) -> Result<UpsertOutcome, StoreError> {
+        let state_warnings = self.check_states(&mut update)?;
+        let mut doc = self.write_doc().await?;
+        if !allow_create && !doc.strings.contains_key(key) {
+            return Err(StoreError::KeyMissing(key.to_string()));
+        }
+        self.check_comment(&doc, key, comment.as_deref())?;
+        self.check_frozen(language)?;
+        self.check_workflow_update(&doc, key, language, &update)?;
+        let placeholder_warnings = self.check_placeholders(&doc, key, language, &update)?;
+        let before = source_value(&doc, key, language);
+        let mut updated = doc.upsert_translation(key, language, update, comment, position)?;
+        updated.warnings.splice(0..0, state_warnings);
+        updated.warnings.extend(placeholder_warnings);
+        updated.needs_review = self.review_after_source_change(&mut doc, key, language, before);
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        let upserted = |language: &str| StoreChange::TranslationUpserted {
+            key: key.to_string(),
+            language: language.to_string(),
+        };
+        let change = if updated.needs_review.is_empty() {
+            upserted(language)
+        } else {
+            StoreChange::Batch {
+                changes: std::iter::once(language)
+                    .chain(updated.needs_review.iter().map(String::as_str))
+                    .map(upserted)
+                    .collect(),
+            }
+        };
+        self.persist(save, change).await?;
+
+        Ok(updated)
+    }
+
+    /// Sets the text `key` shows on one `device` in `language`, or removes that device case
+    /// when `value` is `None`. Unlike [`Self::upsert_translation`], which drops invalid
+    /// variation combinations with a warning, this refuses edits that would break Xcode's
+    /// nesting rules: device variations can't sit alongside or under plural or width ones.
+    pub async fn set_device_variant(
+        &self,
+        key: &str,
+        language: &str,
+        device: &str,
+        value: Option<String>,
+    ) -> Result<TranslationValue, StoreError> {
+        let device = device.trim().to_ascii_lowercase();
+        if !DEVICES.contains(&device.as_str()) {
+            return Err(StoreError::InvalidDevice(device));
+        }
+
+        let mut doc = self.write_doc().await?;
+        self.check_frozen(language)?;
+        let entry = doc
+            .strings
+            .get_mut(key)
+            .ok_or_else(|| StoreError::KeyMissing(key.to_string()))?;
+        let existing = entry.localizations.get(language);
+        let conflicting = ["plural", "width"]
+            .into_iter()
+            .find(|selector| existing.is_some_and(|loc| loc.variations.contains_key(*selector)));
+        if let Some(selector) = conflicting {
+            return Err(StoreError::InvalidVariation(format!(
+                "'{key}' ({language}) varies by {selector}, and a localization can't vary by \
+                 both {selector} and device at the top level; nest the {selector} variations \
+                 under each device instead"
+            )));
+        }
+        let nested = existing
+            .and_then(|loc| loc.variations.get("device"))
+            .and_then(|cases| cases.get(&device))
+            .is_some_and(|case| !case.variations.is_empty());
+        if nested && value.is_some() {
+            return Err(StoreError::InvalidVariation(format!(
+                "the '{device}' case of '{key}' ({language}) has nested variations; edit it \
+                 with upsert_translation"
+            )));
+        }
+
+        let mut loc = existing.cloned().unwrap_or_default();
+        let cases = loc.variations.entry("device".to_string()).or_default();
+        match value {
+            Some(value) => {
+                cases.insert(
+                    device,
+                    XcLocalization {
+                        string_unit: Some(XcStringUnit {
+                            state: Some(DEFAULT_TRANSLATION_STATE.to_string()),
+                            value: Some(value),
+                        }),
+                        ..Default::default()
+                    },
+                );
+            }
+            None => {
+                cases.shift_remove(&device);
+            }
+        }
+        if cases.is_empty() {
+            loc.variations.shift_remove("device");
+        }
+        self.check_workflow_edit(key, language, existing, &loc)?;
+        let updated = TranslationValue::from_localization(&loc);
+        entry.localizations.insert(language.to_string(), loc);
+
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::TranslationUpserted {
+                key: key.to_string(),
+                language: language.to_string(),
+            },
+        )
+        .await?;
+        Ok(updated)
+    }
+
+    /// Returns the device cases of `key` for every language that varies by device.
+    pub async fn list_device_variants(
+        &self,
+        key: &str,
+    ) -> Result<IndexMap<String, IndexMap<String, TranslationValue>>, StoreError> {
+        let doc = self.data.read().await;
+        let entry = doc
+            .strings
+            .get(key)
+            .ok_or_else(|| StoreError::KeyMissing(key.to_string()))?;
+        Ok(entry
+            .localizations
+            .iter()
+            .filter_map(|(language, loc)| {
+                let cases = loc.variations.get("device")?;
+                let cases = cases
+                    .iter()
+                    .map(|(device, case)| {
+                        (device.clone(), TranslationValue::from_localization(case))
+                    })
+                    .collect();
+                Some((language.clone(), cases))
+            })
+            .collect())
+    }
+
+    pub async fn delete_translation(&self, key: &str, language: &str) -> Result<(), StoreError> {
+        let mut doc = self.write_doc().await?;
+        self.check_frozen(language)?;
+        doc.delete_translation(key, language)?;
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::TranslationDeleted {
+                key: key.to_string(),
+                language: language.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_key(&self, key: &str) -> Result<(), StoreError> {
+        let mut doc = self.write_doc().await?;
+        self.check_key_frozen(&doc, key)?;
+        doc.delete_key(key)?;
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::KeyDeleted {
+                key: key.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Applies every operation to a copy of the catalog and saves it in a single write, or
+    /// leaves the catalog untouched when any operation fails.
+    pub async fn apply_batch(
+        &self,
+        operations: Vec<BatchOperation>,
+    ) -> Result<BatchOutcome, StoreError> {
+        let mut doc = self.write_doc().await?;
+        let mut staged = doc.clone();
+        let results: Vec<_> = operations
+            .into_iter()
+            .map(|operation| self.stage_operation(&mut staged, operation))
+            .collect();
+        let committed = !results.is_empty() && results.iter().all(Result::is_ok);
+        if !committed {
+            return Ok(BatchOutcome { committed, results });
+        }
+
+        self.normalize(&mut staged);
+        doc.replace(staged);
+        let save = doc.commit();
+        let mut changes = Vec::new();
+        for applied in results.iter().filter_map(|result| result.as_ref().ok()) {
+            applied.saved_changes(&mut changes);
+        }
+        self.persist(save, StoreChange::Batch { changes }).await?;
+        Ok(BatchOutcome { committed, results })
+    }
+
+    /// Checks one batch operation the way the matching single edit would and applies it to
+    /// `staged`.
+    fn stage_operation(
+        &self,
+        staged: &mut XcStringsFile,
+        mut operation: BatchOperation,
+    ) -> Result<BatchApplied, StoreError> {
+        let mut state_warnings = Vec::new();
+        let mut placeholder_warnings = Vec::new();
+        let mut source_before = None;
+        match &mut operation {
+            BatchOperation::Upsert {
+                key,
+                language,
+                update,
+                comment,
+                ..
+            } => {
+                state_warnings = self.check_states(update)?;
+                self.check_comment(staged, key, comment.as_deref())?;
+                self.check_frozen(language)?;
+                self.check_workflow_update(staged, key, language, update)?;
+                placeholder_warnings = self.check_placeholders(staged, key, language, update)?;
+                source_before = Some((
+                    key.clone(),
+                    language.clone(),
+                    source_value(staged, key, language),
+                ));
+            }
+            BatchOperation::DeleteTranslation { language, .. } => {
+                self.check_frozen(language)?;
+            }
+            BatchOperation::DeleteKey { key } => {
+                self.check_key_frozen(staged, key)?;
+            }
+        }
+        let mut applied = staged.apply(operation)?;
+        applied.warnings.splice(0..0, state_warnings);
+        applied.warnings.extend(placeholder_warnings);
+        if let Some((key, language, before)) = source_before {
+            applied.needs_review = self.review_after_source_change(staged, &key, &language, before);
+        }
+        Ok(applied)
+    }
+
+    /// Applies RFC 6902 operations to the keys of the catalog, as described by
+    /// [`patch::PatchOperation`], and saves the result in a single write. Patched entries
+    /// must still read as catalog entries, and every translation they change goes through
+    /// the same state, frozen-language, review-workflow, and format-specifier checks as an
+    /// upsert; when anything fails, the catalog is left untouched.
+    pub async fn apply_patch(
+        &self,
+        operations: &[patch::PatchOperation],
+    ) -> Result<PatchOutcome, StoreError> {
+        let keys = patch::keys(operations).map_err(StoreError::InvalidPatch)?;
+        let mut doc = self.write_doc().await?;
+        let mut staged = doc.clone();
+        let mut target = serde_json::Map::new();
+        for key in &keys {
+            if let Some(entry) = staged.strings.get(key) {
+                target.insert(key.clone(), serde_json::to_value(entry)?);
+            }
+        }
+        let mut target = serde_json::Value::Object(target);
+        patch::apply(&mut target, operations).map_err(StoreError::InvalidPatch)?;
+
+        let mut outcome = PatchOutcome::default();
+        for key in &keys {
+            let before = staged.strings.get(key).cloned();
+            let Some(value) = target.get(key) else {
+                if before.is_some() {
+                    self.check_key_frozen(&staged, key)?;
+                    staged.strings.shift_remove(key);
+                    outcome
+                        .changes
+                        .push(StoreChange::KeyDeleted { key: key.clone() });
+                }
+                continue;
+            };
+            let mut entry = patch::entry(key, value).map_err(StoreError::InvalidPatch)?;
+            let before_loc = |language: &str| before.as_ref()?.localizations.get(language).cloned();
+            let mut edited = Vec::new();
+            for (language, loc) in entry.localizations.iter_mut() {
+                let unchanged = before_loc(language).is_some_and(|before| {
+                    serde_json::to_value(before).ok() == serde_json::to_value(&*loc).ok()
+                });
+                if unchanged {
+                    continue;
+                }
+                self.check_frozen(language)?;
+                let mut update = TranslationUpdate::from(TranslationValue::from_localization(loc));
+                let mut warnings = self.check_states(&mut update)?;
+                let mut rebuilt = XcLocalization::default();
+                apply_update(&mut rebuilt, update);
+                normalize_localization(&mut rebuilt, &mut warnings);
+                self.check_workflow_edit(key, language, before_loc(language).as_ref(), &rebuilt)?;
+                *loc = rebuilt;
+                outcome
+                    .warnings
+                    .extend(warnings.into_iter().map(|warning| VariationWarning {
+                        path: format!("{key} ({language}) {}", warning.path),
+                        message: warning.message,
+                    }));
+                edited.push(language.clone());
+            }
+            let mut removed = Vec::new();
+            for language in before.iter().flat_map(|before| before.localizations.keys()) {
+                if !entry.localizations.contains_key(language) {
+                    self.check_frozen(language)?;
+                    removed.push(language.clone());
+                }
+            }
+
+            match &before {
+                None => {
+                    self.check_comment(&staged, key, entry.comment.as_deref())?;
+                    outcome
+                        .changes
+                        .push(StoreChange::KeyCreated { key: key.clone() });
+                }
+                Some(before) => {
+                    if before.comment != entry.comment {
+                        outcome
+                            .changes
+                            .push(StoreChange::CommentUpdated { key: key.clone() });
+                    }
+                    if before.extraction_state != entry.extraction_state {
+                        outcome
+                            .changes
+                            .push(StoreChange::ExtractionStateUpdated { key: key.clone() });
+                    }
+                    if before.should_translate != entry.should_translate {
+                        outcome
+                            .changes
+                            .push(StoreChange::ShouldTranslateUpdated { key: key.clone() });
+                    }
+                }
+            }
+            let source_language = staged.source_language.clone();
+            let source_before = source_value(&staged, key, &source_language);
+            staged.strings.insert(key.clone(), entry);
+            for language in &edited {
+                let warnings = self.check_placeholder_edit(
+                    &staged,
+                    key,
+                    language,
+                    before_loc(language).as_ref(),
+                    &staged.strings[key].localizations[language],
+                )?;
+                outcome
+                    .warnings
+                    .extend(warnings.into_iter().map(|warning| VariationWarning {
+                        path: format!("{key} ({language}) {}", warning.path),
+                        message: warning.message,
+                    }));
+            }
+            if edited.contains(&source_language) {
+                let needs_review = self.review_after_source_change(
+                    &mut staged,
+                    key,
+                    &source_language,
+                    source_before,
+                );
+                edited.extend(needs_review);
+            }
+            outcome.changes.extend(edited.into_iter().map(|language| {
+                StoreChange::TranslationUpserted {
+                    key: key.clone(),
+                    language,
+                }
+            }));
+            outcome.changes.extend(removed.into_iter().map(|language| {
+                StoreChange::TranslationDeleted {
+                    key: key.clone(),
+                    language,
+                }
+            }));
+        }
+        if outcome.changes.is_empty() {
+            return Ok(outcome);
+        }
+
+        self.normalize(&mut staged);
+        doc.replace(staged);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::Batch {
+                changes: outcome.changes.clone(),
+            },
+        )
+        .await?;
+        Ok(outcome)
+    }
+
+    /// Replaces the whole entry of `key`, creating it if needed, with JSON in the catalog's
+    /// own format. It's validated and checked like a patch adding the key; see
+    /// [`Self::apply_patch`].
+    pub async fn set_raw_entry(
+        &self,
+        key: &str,
+        entry: serde_json::Value,
+    ) -> Result<PatchOutcome, StoreError> {
+        self.apply_patch(&[patch::PatchOperation::Add {
+            path: patch::key_path(key),
+            value: entry,
+        }])
+        .await
+    }
+
+    /// Replaces text in translation values, including variations and substitutions. With
+    /// `apply` false this only reports what would change; otherwise every replacement is
+    /// saved in one write.
+    pub async fn find_replace(
+        &self,
+        search: &FindReplace,
+        apply: bool,
+    ) -> Result<Vec<Replacement>, StoreError> {
+        if !apply {
+            let mut staged = XcStringsFile::clone(&*self.data.read().await);
+            return Ok(staged.find_replace(search));
+        }
+
+        let mut doc = self.write_doc().await?;
+        let mut staged = doc.clone();
+        let replacements = staged.find_replace(search);
+        if replacements.is_empty() {
+            return Ok(replacements);
+        }
+        for replacement in &replacements {
+            let (key, language) = (&replacement.key, &replacement.language);
+            self.check_frozen(language)?;
+            let localization =
+                |doc: &XcStringsFile| doc.strings.get(key)?.localizations.get(language).cloned();
+            if let Some(after) = localization(&staged) {
+                self.check_workflow_edit(key, language, localization(&doc).as_ref(), &after)?;
+            }
+        }
+        doc.replace(staged);
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        let mut changes: Vec<StoreChange> = Vec::new();
+        for replacement in &replacements {
+            let change = StoreChange::TranslationUpserted {
+                key: replacement.key.clone(),
+                language: replacement.language.clone(),
+            };
+            if !changes.contains(&change) {
+                changes.push(change);
+            }
+        }
+        self.persist(save, StoreChange::Batch { changes }).await?;
+        Ok(replacements)
+    }
+
+    /// Reorders keys by code point, as `sort=key` lists them. Returns whether the order
+    /// changed; with `apply` false nothing is written.
+    pub async fn sort_keys(&self, apply: bool) -> Result<bool, StoreError> {
+        if !apply {
+            let doc = self.data.read().await;
+            return Ok(!doc.strings.keys().is_sorted());
+        }
+
+        let mut doc = self.write_doc().await?;
+        if doc.strings.keys().is_sorted() {
+            return Ok(false);
+        }
+        doc.strings.sort_keys();
+        let save = doc.commit();
+        self.persist(save, StoreChange::KeysSorted).await?;
+        Ok(true)
+    }
+
+    /// Rewrites the file exactly as this store would save it: normalized and in Xcode's
+    /// formatting. Returns whether the file on disk differed; with `apply` false nothing is
+    /// written.
+    pub async fn reformat(&self, apply: bool) -> Result<bool, StoreError> {
+        let on_disk = fs::read_to_string(&self.path).await?;
+        if !apply {
+            return Ok(on_disk != self.to_apple_json().await);
+        }
+
+        let save = self.write_doc().await?.commit();
+        let save = self.serialize(save).await?;
+        if save.serialized == on_disk {
+            return Ok(false);
+        }
+        self.write_serialized(save, StoreChange::Reformatted)
+            .await?;
+        Ok(true)
+    }
+
+    /// Removes empty localizations, and keys left with no localizations, comment, or flags,
+    /// which ordinary saves keep. Returns what was removed; with `apply` false nothing is
+    /// written.
+    pub async fn clean(&self, apply: bool) -> Result<Vec<NormalizedEntry>, StoreError> {
+        if !apply {
+            let mut staged = XcStringsFile::clone(&*self.data.read().await);
+            return Ok(prune_strings_file(&mut staged));
+        }
+
+        let mut doc = self.write_doc().await?;
+        let mut staged = doc.clone();
+        let pruned = prune_strings_file(&mut staged);
+        if pruned.is_empty() {
+            return Ok(pruned);
+        }
+        let mut changes = Vec::new();
+        for entry in &pruned {
+            for language in &entry.dropped_languages {
+                self.check_frozen(language)?;
+                changes.push(StoreChange::TranslationDeleted {
+                    key: entry.key.clone(),
+                    language: language.clone(),
+                });
+            }
+            if entry.removed {
+                changes.push(StoreChange::KeyDeleted {
+                    key: entry.key.clone(),
+                });
+            }
+        }
+        doc.replace(staged);
+        let save = doc.commit();
+        self.persist(save, StoreChange::Batch { changes }).await?;
+        Ok(pruned)
+    }
+
+    /// Checks the file on disk for problems that loading tolerates or hides: duplicate members, variations
+    /// the schema doesn't allow, substitutions with a bad `argNum` or that nothing uses, and
+    /// empty localizations and keys. With `apply` the fixable ones are fixed and the file
+    /// rewritten; otherwise this only reports them.
+    pub async fn repair(&self, apply: bool) -> Result<RepairReport, StoreError> {
+        if !apply {
+            let raw = fs::read_to_string(&self.path).await?;
+            let (_, issues) = repair_document(&raw)?;
+            return Ok(RepairReport {
+                repaired: false,
+                issues,
+            });
+        }
+
+        let mut doc = self.write_doc().await?;
+        let raw = fs::read_to_string(&self.path).await?;
+        let (repaired, issues) = repair_document(&raw)?;
+        if !issues.iter().any(|issue| issue.fixable) {
+            return Ok(RepairReport {
+                repaired: false,
+                issues,
+            });
+        }
+        doc.replace(repaired);
+        let save = doc.commit();
+        self.persist(save, StoreChange::Repaired).await?;
+        Ok(RepairReport {
+            repaired: true,
+            issues,
+        })
+    }
+
+    /// Copies keys and translations from `source` that this catalog lacks. Translations
+    /// present in both are replaced only with `overwrite`; otherwise they're reported as
+    /// conflicts. With `apply` false this only reports what would change.
+    pub async fn merge_from(
+        &self,
+        source: &XcStringsStore,
+        overwrite: bool,
+        apply: bool,
+    ) -> Result<MergeOutcome, StoreError> {
+        let incoming = source.data.read().await.clone();
+        if !apply {
+            let mut staged = XcStringsFile::clone(&*self.data.read().await);
+            return Ok(merge_documents(&mut staged, &incoming, overwrite));
+        }
+
+        let mut doc = self.write_doc().await?;
+        let mut staged = doc.clone();
+        let outcome = merge_documents(&mut staged, &incoming, overwrite);
+        if outcome.is_empty() {
+            return Ok(outcome);
+        }
+        for merged in &outcome.merged {
+            self.check_frozen(&merged.language)?;
+        }
+        doc.replace(staged);
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        let changes = outcome
+            .added_keys
+            .iter()
+            .map(|key| StoreChange::KeyCreated { key: key.clone() })
+            .chain(
+                outcome
+                    .merged
+                    .iter()
+                    .map(|merged| StoreChange::TranslationUpserted {
+                        key: merged.key.clone(),
+                        language: merged.language.clone(),
+                    }),
+            )
+            .collect();
+        self.persist(save, StoreChange::Batch { changes }).await?;
+        Ok(outcome)
+    }
+
+    /// Moves every key under `old_prefix` (`checkout.title`, `checkout.payment.card`) to
+    /// `new_prefix` in one save, keeping file order. Fails with
+    /// [`StoreError::NamespaceCollision`] if a new key already exists; with `apply` false it
+    /// only reports the renames and collisions.
+    pub async fn rename_namespace(
+        &self,
+        old_prefix: &str,
+        new_prefix: &str,
+        apply: bool,
+    ) -> Result<NamespaceRename, StoreError> {
+        let old_prefix = namespace_prefix(old_prefix)?;
+        let new_prefix = namespace_prefix(new_prefix)?;
+        if old_prefix == new_prefix {
+            return Ok(NamespaceRename::default());
+        }
+        if !apply {
+            let doc = self.data.read().await;
+            return Ok(plan_namespace_rename(&doc, &old_prefix, &new_prefix));
+        }
+
+        let mut doc = self.write_doc().await?;
+        let plan = plan_namespace_rename(&doc, &old_prefix, &new_prefix);
+        if !plan.collisions.is_empty() {
+            return Err(StoreError::NamespaceCollision(plan.collisions));
+        }
+        if plan.renamed.is_empty() {
+            return Ok(plan);
+        }
+        let changes = self.rename_planned_keys(&mut doc, &plan)?;
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(save, StoreChange::Batch { changes }).await?;
+        Ok(plan)
+    }
+
+    /// Renames the keys `plan` moves to the new namespace in `doc`, unless one is frozen.
+    fn rename_planned_keys(
+        &self,
+        doc: &mut XcStringsFile,
+        plan: &NamespaceRename,
+    ) -> Result<Vec<StoreChange>, StoreError> {
+        for rename in &plan.renamed {
+            self.check_key_frozen(doc, &rename.old_key)?;
+        }
+        let new_names: HashMap<&str, &str> = plan
+            .renamed
+            .iter()
+            .map(|rename| (rename.old_key.as_str(), rename.new_key.as_str()))
+            .collect();
+        doc.strings = std::mem::take(&mut doc.strings)
+            .into_iter()
+            .map(|(key, entry)| match new_names.get(key.as_str()) {
+                Some(new_key) => (new_key.to_string(), entry),
+                None => (key, entry),
+            })
+            .collect();
+        Ok(plan
+            .renamed
+            .iter()
+            .map(|rename| StoreChange::KeyRenamed {
+                old_key: rename.old_key.clone(),
+                new_key: rename.new_key.clone(),
+            })
+            .collect())
+    }
+
+    pub async fn rename_key(&self, old_key: &str, new_key: &str) -> Result<(), StoreError> {
+        if old_key == new_key {
+            return Ok(());
+        }
+
+        let mut doc = self.write_doc().await?;
+        if doc.strings.contains_key(new_key) {
+            return Err(StoreError::KeyExists(new_key.to_string()));
+        }
+        self.check_key_frozen(&doc, old_key)?;
+
+        let entry = doc
+            .strings
+            .shift_remove(old_key)
+            .ok_or_else(|| StoreError::KeyMissing(old_key.to_string()))?;
+
+        doc.strings.insert(new_key.to_string(), entry);
+
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::KeyRenamed {
+                old_key: old_key.to_string(),
+                new_key: new_key.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_extraction_state(
+        &self,
+        key: &str,
+        state: Option<String>,
+    ) -> Result<(), StoreError> {
+        let mut doc = self.write_doc().await?;
+        let entry = doc
+            .strings
+            .entry(key.to_string())
+            .or_insert_with(XcStringEntry::default);
+        entry.extraction_state = state;
+
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::ExtractionStateUpdated {
+                key: key.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_translation_state(
+        &self,
+        key: &str,
+        language: &str,
+        state: Option<String>,
+    ) -> Result<TranslationValue, StoreError> {
+        let update = TranslationUpdate {
+            state: Some(state),
+            ..Default::default()
+        };
+        self.upsert_translation(key, language, update)
+            .await
+            .map(|outcome| outcome.value)
+    }
+
+    /// Marks a translation waiting for review as `reviewed`, in every variation.
+    pub async fn approve_translation(
+        &self,
+        key: &str,
+        language: &str,
+    ) -> Result<TranslationValue, StoreError> {
+        self.conclude_review(key, language, workflow::REVIEWED, workflow::check_approve)
+            .await
+    }
+
+    /// Sends a translation in review, or already reviewed, back to `new` for another pass.
+    /// A `reason` is required; callers record it in the audit log.
+    pub async fn reject_translation(
+        &self,
+        key: &str,
+        language: &str,
+        reason: &str,
+    ) -> Result<TranslationValue, StoreError> {
+        if reason.trim().is_empty() {
+            return Err(StoreError::WorkflowViolation {
+                key: key.to_string(),
+                language: language.to_string(),
+                reason: "can't be rejected without a reason".to_string(),
+            });
+        }
+        self.conclude_review(key, language, workflow::NEW, workflow::check_reject)
+            .await
+    }
+
+    async fn conclude_review(
+        &self,
+        key: &str,
+        language: &str,
+        state: &str,
+        check: fn(Option<&str>) -> Result<(), String>,
+    ) -> Result<TranslationValue, StoreError> {
+        let mut doc = self.write_doc().await?;
+        self.check_frozen(language)?;
+        let loc = doc
+            .strings
+            .get_mut(key)
+            .and_then(|entry| entry.localizations.get_mut(language))
+            .ok_or_else(|| StoreError::TranslationMissing {
+                key: key.to_string(),
+                language: language.to_string(),
+            })?;
+        check(localization_state(loc).as_deref()).map_err(|reason| {
+            StoreError::WorkflowViolation {
+                key: key.to_string(),
+                language: language.to_string(),
+                reason,
+            }
+        })?;
+        set_states(loc, state);
+        let updated = TranslationValue::from_localization(loc);
+
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::TranslationUpserted {
+                key: key.to_string(),
+                language: language.to_string(),
+            },
+        )
+        .await?;
+        Ok(updated)
+    }
+
+    pub async fn set_comment(&self, key: &str, comment: Option<String>) -> Result<(), StoreError> {
+        let mut doc = self.write_doc().await?;
+        let entry = doc
+            .strings
+            .entry(key.to_string())
+            .or_insert_with(XcStringEntry::default);
+        entry.comment = comment;
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::CommentUpdated {
+                key: key.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Replaces the metadata line of `key`'s comment, keeping the free text, and returns
+    /// the new comment. Empty metadata removes the line.
+    pub async fn set_metadata(
+        &self,
+        key: &str,
+        metadata: &KeyMetadata,
+    ) -> Result<Option<String>, StoreError> {
+        metadata.check().map_err(StoreError::InvalidMetadata)?;
+        let mut doc = self.write_doc().await?;
+        let entry = doc
+            .strings
+            .get_mut(key)
+            .ok_or_else(|| StoreError::KeyMissing(key.to_string()))?;
+        entry.comment = metadata::with_metadata(entry.comment.as_deref(), metadata);
+        let comment = entry.comment.clone();
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::CommentUpdated {
+                key: key.to_string(),
+            },
+        )
+        .await?;
+        Ok(comment)
+    }
+
+    pub async fn set_should_translate(
+        &self,
+        key: &str,
+        should_translate: Option<bool>,
+    ) -> Result<(), StoreError> {
+        let mut doc = self.write_doc().await?;
+        let entry = doc
+            .strings
+            .entry(key.to_string())
+            .or_insert_with(XcStringEntry::default);
+        entry.should_translate = should_translate;
+        self.normalize(&mut doc);
+        let save = doc.commit();
+        self.persist(
+            save,
+            StoreChange::ShouldTranslateUpdated {
+                key: key.to_string(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        path::PathBuf,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+    struct TempStorePath {
+        dir: PathBuf,
+        file: PathBuf,
+    }
+
+    impl TempStorePath {
+        fn new(test_name: &str) -> Self {
+            let mut dir = std::env::temp_dir();
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos();
+            let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+            dir.push(format!("xcstrings_mcp_{test_name}_{nanos}_{id}"));
+            std::fs::create_dir_all(&dir).expect("create temp dir");
+            let file = dir.join("Localizable.xcstrings");
+            Self { dir, file }
+        }
+    }
+
+    impl Drop for TempStorePath {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[tokio::test]
+    async fn upsert_without_create_refuses_unknown_keys() {
+        let tmp = TempStorePath::new("upsert_without_create");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+        store
+            .upsert_translation("login.button", "en", set("Log in"))
+            .await
+            .expect("create key");
+
+        let err = store
+            .upsert_translation_with(
+                "loginn.button",
+                "en",
+                set("Log in"),
+                None,
+                &InsertPosition::End,
+                false,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StoreError::KeyMissing(key) if key == "loginn.button"));
+        assert_eq!(store.keys().await, ["login.button"]);
+
+        store
+            .upsert_translation_with(
+                "login.button",
+                "fr",
+                set("Connexion"),
+                None,
+                &InsertPosition::End,
+                false,
+            )
+            .await
+            .expect("update existing key");
+        let value = store.get_translation("login.button", "fr").await.unwrap();
+        assert_eq!(value.value.as_deref(), Some("Connexion"));
+    }
+
+    #[tokio::test]
+    async fn require_comment_refuses_uncommented_new_keys() {
+        let tmp = TempStorePath::new("require_comment");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store")
+            .with_require_comment(true);
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+
+        for comment in [None, Some("  ".to_string())] {
+            let err = store
+                .upsert_translation_with(
+                    "open",
+                    "en",
+                    set("Open"),
+                    comment,
+                    &InsertPosition::End,
+                    true,
+                )
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(&err, StoreError::CommentRequired(key) if key == "open"),
+                "{err}"
+            );
+        }
+        let err = store
+            .create_key("close", None, None, &InsertPosition::End)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StoreError::CommentRequired(_)));
+        let batch = store
+            .apply_batch(vec![BatchOperation::Upsert {
+                key: "open".into(),
+                language: "en".into(),
+                update: Box::new(set("Open")),
+                comment: None,
+                position: InsertPosition::End,
+            }])
+            .await
+            .unwrap();
+        assert!(!batch.committed);
+        assert!(store.keys().await.is_empty());
+
+        let comment = Some("File menu item that opens a document".to_string());
+        store
+            .upsert_translation_with(
+                "open",
+                "en",
+                set("Open"),
+                comment.clone(),
+                &InsertPosition::End,
+                true,
+            )
+            .await
+            .expect("commented key");
+        assert_eq!(store.get_record("open").await.unwrap().comment, comment);
+        store
+            .upsert_translation("open", "fr", set("Ouvrir"))
+            .await
+            .expect("existing keys need no comment");
+        store
+            .apply_batch(vec![BatchOperation::Upsert {
+                key: "close".into(),
+                language: "en".into(),
+                update: Box::new(set("Close")),
+                comment: Some("File menu item".into()),
+                position: InsertPosition::End,
+            }])
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_record("close").await.unwrap().comment.as_deref(),
+            Some("File menu item")
+        );
+    }
+
+    #[tokio::test]
+    async fn upsert_checks_states_against_known_set() {
+        let tmp = TempStorePath::new("upsert_states");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        let update: TranslationUpdate = serde_json::from_value(serde_json::json!({
+            "state": "Translated",
+            "value": "%lld files",
+            "variations": { "plural": { "one": { "state": "done", "value": "%lld file" } } },
+        }))
+        .unwrap();
+
+        let outcome = store
+            .upsert_translation("files", "en", update.clone())
+            .await
+            .expect("warn corrects states");
+        let paths: Vec<_> = outcome.warnings.iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(paths, ["state", "variations.plural.one.state"]);
+        let value = store.get_translation("files", "en").await.unwrap();
+        assert_eq!(value.state.as_deref(), Some("translated"));
+        assert_eq!(
+            value.variations["plural"]["one"].state.as_deref(),
+            Some("translated")
+        );
+
+        let outcome = store
+            .upsert_translation(
+                "files",
+                "fr",
+                TranslationUpdate::from_value_state(
+                    Some("%lld fichiers".into()),
+                    Some("banana".into()),
+                ),
+            )
+            .await
+            .expect("warn keeps unknown states");
+        assert_eq!(outcome.warnings.len(), 1);
+        let value = store.get_translation("files", "fr").await.unwrap();
+        assert_eq!(value.state.as_deref(), Some("banana"));
+
+        let strict = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store")
+            .with_state_validation(StateValidation::Strict);
+        let err = strict
+            .upsert_translation("files", "de", update)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, StoreError::InvalidState(msg) if msg.contains("did you mean 'translated'")),
+            "{err}"
+        );
+        assert!(strict.get_translation("files", "de").await.is_err());
+        let ok = strict
+            .upsert_translation(
+                "files",
+                "de",
+                TranslationUpdate::from_value_state(
+                    Some("%lld Dateien".into()),
+                    Some("new".into()),
+                ),
+            )
+            .await
+            .expect("known state");
+        assert!(ok.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn upsert_checks_format_specifiers_against_source() {
+        let tmp = TempStorePath::new("upsert_placeholders");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+        store
+            .upsert_translation("files", "en", set("%lld files in %@"))
+            .await
+            .unwrap();
+
+        let outcome = store
+            .upsert_translation("files", "fr", set("%d fichiers"))
+            .await
+            .expect("warn saves mismatches");
+        assert!(!outcome.warnings.is_empty());
+        assert!(outcome
+            .warnings
+            .iter()
+            .all(|warning| warning.path == "value"));
+        let value = store.get_translation("files", "fr").await.unwrap();
+        assert_eq!(value.value.as_deref(), Some("%d fichiers"));
+        let ok = store
+            .upsert_translation("files", "fr", set("%lld fichiers dans %@"))
+            .await
+            .unwrap();
+        assert!(ok.warnings.is_empty());
+
+        let strict = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store")
+            .with_placeholder_validation(PlaceholderValidation::Strict);
+        let err = strict
+            .upsert_translation("files", "de", set("Dateien"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, StoreError::PlaceholderMismatch { key, .. } if key == "files"),
+            "{err}"
+        );
+        assert!(strict.get_translation("files", "de").await.is_err());
+        let batch = strict
+            .apply_batch(vec![BatchOperation::Upsert {
+                key: "files".into(),
+                language: "de".into(),
+                update: Box::new(set("%d Dateien in %@")),
+                comment: None,
+                position: InsertPosition::End,
+            }])
+            .await
+            .unwrap();
+        assert!(!batch.committed);
+        assert!(matches!(
+            batch.results[0],
+            Err(StoreError::PlaceholderMismatch { .. })
+        ));
+        strict
+            .upsert_translation("files", "en", set("%lld files"))
+            .await
+            .expect("source language isn't checked");
+
+        let off = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store")
+            .with_placeholder_validation(PlaceholderValidation::Off);
+        let outcome = off
+            .upsert_translation("files", "de", set("Dateien"))
+            .await
+            .unwrap();
+        assert!(outcome.warnings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn find_keys_by_value_matches_texts_and_bare_keys() {
+        let tmp = TempStorePath::new("find_by_value");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+        store
+            .upsert_translation("ok.button", "en", set("done"))
+            .await
+            .unwrap();
+        store
+            .upsert_translation("Done", "fr", set("Terminé"))
+            .await
+            .unwrap();
+        let files: TranslationUpdate = serde_json::from_value(serde_json::json!({
+            "variations": { "plural": {
+                "one": { "value": "%lld file" },
+                "other": { "value": "%lld files" },
+            } },
+        }))
+        .unwrap();
+        store
+            .upsert_translation("files", "en", files)
+            .await
+            .unwrap();
+
+        let found = store.find_keys_by_value("Done", None).await.unwrap();
+        let keys: Vec<_> = found.iter().map(|m| (m.key.as_str(), m.exact)).collect();
+        assert_eq!(keys, [("Done", true), ("ok.button", false)]);
+
+        let found = store.find_keys_by_value(" %LLD File", None).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].variation, "plural.one");
+        assert_eq!(found[0].value, "%lld file");
+
+        let found = store
+            .find_keys_by_value("terminé", Some("fr"))
+            .await
+            .unwrap();
+        assert_eq!(found[0].key, "Done");
+        assert!(store
+            .find_keys_by_value("Done", Some("fr"))
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            store.find_keys_by_value("Done", Some("de")).await,
+            Err(StoreError::LanguageMissing(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn search_results_say_where_the_query_matched() {
+        let tmp = TempStorePath::new("search_matches");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        let update: TranslationUpdate = serde_json::from_value(serde_json::json!({
+            "value": "%#@files@ à Café",
+            "substitutions": { "files": {
+                "argNum": 1,
+                "formatSpecifier": "lld",
+                "variations": { "plural": { "other": { "value": "%arg cafés, CAFÉ" } } },
+            } },
+        }))
+        .unwrap();
+        store
+            .upsert_translation("cafe.count", "fr", update)
+            .await
+            .unwrap();
+        store
+            .upsert_translation(
+                "greeting",
+                "fr",
+                TranslationUpdate::from_value_state(Some("Bonjour".into()), None),
+            )
+            .await
+            .unwrap();
+
+        let records = store.list_records(Some("café")).await;
+        assert_eq!(records.len(), 1);
+        let hits: Vec<_> = records[0]
+            .matches
+            .iter()
+            .map(|hit| {
+                (
+                    hit.location.as_str(),
+                    hit.start,
+                    hit.end,
+                    hit.fragment.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            hits,
+            [
+                ("", 13, 18, "Café"),
+                ("substitutions.files.variations.plural.other", 5, 10, "café"),
+                (
+                    "substitutions.files.variations.plural.other",
+                    13,
+                    18,
+                    "CAFÉ"
+                ),
+            ]
+        );
+        assert_eq!(records[0].matches[0].language.as_deref(), Some("fr"));
+
+        let summaries = store.list_summaries(Some("CAFE.")).await;
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].matches.len(), 1);
+        assert_eq!(summaries[0].matches[0].language, None);
+        assert_eq!(summaries[0].matches[0].fragment, "cafe.");
+
+        assert!(store
+            .list_records(None)
+            .await
+            .iter()
+            .all(|r| r.matches.is_empty()));
+        assert_eq!(store.list_records(Some("")).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn upsert_and_fetch_translation() {
+        let tmp = TempStorePath::new("upsert_fetch");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+
+        store
+            .upsert_translation(
+                "greeting",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .expect("upsert");
+        store
+            .upsert_translation(
+                "greeting",
+                "fr",
+                TranslationUpdate::from_value_state(Some("Bonjour".into()), None),
+            )
+            .await
+            .expect("upsert");
+
+        let value = store.get_translation("greeting", "en").await.expect("get");
+        assert_eq!(value.value.as_deref(), Some("Hello"));
+
+        let languages = store.list_languages().await;
+        assert!(languages.contains(&"en".to_string()));
+        assert!(languages.contains(&"fr".to_string()));
+
+        let records = store.list_records(None).await;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].key, "greeting");
+        assert!(records[0].translations.contains_key("fr"));
+    }
+
+    #[tokio::test]
+    async fn delete_translation_removes_empty_keys() {
+        let tmp = TempStorePath::new("delete_translation");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+
+        store
+            .upsert_translation(
+                "farewell",
+                "en",
+                TranslationUpdate::from_value_state(Some("Bye".into()), None),
+            )
+            .await
+            .expect("upsert");
+
+        store
+            .delete_translation("farewell", "en")
+            .await
+            .expect("delete translation");
+
+        assert!(store.get_translation("farewell", "en").await.ok().is_none());
+
+        let err = store.delete_key("farewell").await.unwrap_err();
+        assert!(matches!(err, StoreError::KeyMissing(_)));
+    }
+
+    #[tokio::test]
+    async fn read_only_manager_refuses_edits_and_snapshots() {
+        let tmp = TempStorePath::new("read_only");
+        XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store")
+            .upsert_translation(
+                "greeting",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .expect("seed translation");
+        let before = std::fs::read_to_string(&tmp.file).expect("read catalog");
+
+        let manager = XcStringsStoreManager::new(Some(tmp.file.clone()))
+            .await
+            .expect("create manager")
+            .with_read_only(true);
+        let store = manager.default_store().await.expect("default store");
+        assert!(store.is_read_only());
+        assert!(matches!(
+            store
+                .upsert_translation(
+                    "greeting",
+                    "en",
+                    TranslationUpdate::from_value_state(Some("Hi".into()), None),
+                )
+                .await
+                .unwrap_err(),
+            StoreError::ReadOnly(_)
+        ));
+        assert!(matches!(
+            store.delete_key("greeting").await.unwrap_err(),
+            StoreError::ReadOnly(_)
+        ));
+        assert!(matches!(
+            store.snapshot("backup").await.unwrap_err(),
+            StoreError::ReadOnly(_)
+        ));
+        assert_eq!(
+            store
+                .get_translation("greeting", "en")
+                .await
+                .ok()
+                .and_then(|record| record.value)
+                .as_deref(),
+            Some("Hello")
+        );
+        assert_eq!(std::fs::read_to_string(&tmp.file).unwrap(), before);
+    }
+
+    #[tokio::test]
+    async fn read_only_paths_protect_matching_catalogs() {
+        let tmp = TempStorePath::new("protected_paths");
+        let vendor = tmp.dir.join("Vendor/Kit/Kit.xcstrings");
+        std::fs::create_dir_all(vendor.parent().unwrap()).unwrap();
+        std::fs::write(&vendor, "{}").unwrap();
+        let manager = XcStringsStoreManager::new(Some(tmp.file.clone()))
+            .await
+            .expect("create manager")
+            .with_read_only_paths(["Vendor/**.xcstrings".to_string()]);
+
+        let vendor_store = manager
+            .store_for(Some(&vendor.to_string_lossy()))
+            .await
+            .expect("vendor store");
+        assert!(vendor_store.is_read_only());
+        assert!(matches!(
+            vendor_store.add_language("fr").await.unwrap_err(),
+            StoreError::Protected { rule, .. } if rule == "Vendor/**.xcstrings"
+        ));
+        let app_store = manager.default_store().await.expect("default store");
+        assert!(!app_store.is_read_only());
+        app_store
+            .add_language("fr")
+            .await
+            .expect("edit unprotected catalog");
+
+        let protected = |pattern: &str, path: &str| {
+            let pattern: Vec<char> = pattern.chars().collect();
+            let path: Vec<char> = path.chars().collect();
+            glob_matches(&pattern, &path)
+        };
+        assert!(protected("Vendor/**/*.xcstrings", "Vendor/A.xcstrings"));
+        assert!(protected("Vendor/**/*.xcstrings", "Vendor/a/b/A.xcstrings"));
+        assert!(protected("*/Shared?.xcstrings", "App/Shared2.xcstrings"));
+        assert!(!protected("*.xcstrings", "App/Localizable.xcstrings"));
+        assert!(!protected("Vendor/**", "Vendored/A.xcstrings"));
+    }
+
+    #[tokio::test]
+    async fn transactions_write_every_catalog_or_none() {
+        let tmp = TempStorePath::new("transaction");
+        let manager = XcStringsStoreManager::new(Some(tmp.file.clone()))
+            .await
+            .expect("create manager");
+        let store = manager.default_store().await.expect("default store");
+        for key in ["checkout.title", "checkout.pay", "home.title"] {
+            store
+                .upsert_translation(
+                    key,
+                    "en",
+                    TranslationUpdate::from_value_state(Some(key.into()), None),
+                )
+                .await
+                .unwrap();
+        }
+        let other = tmp.dir.join("Checkout.xcstrings");
+        std::fs::write(
+            &other,
+            r#"{"sourceLanguage":"en","version":"1.0","strings":{"checkout.done":{"comment":"Done"}}}"#,
+        )
+        .unwrap();
+        let other_path = Some(other.to_string_lossy().into_owned());
+        let move_key = |key: &str| TransactionOperation::MoveKey {
+            key: key.into(),
+            from: None,
+            to: other_path.clone(),
+            position: InsertPosition::Sorted,
+        };
+        let before = std::fs::read_to_string(&tmp.file).unwrap();
+
+        let outcome = manager
+            .apply_transaction(vec![move_key("checkout.title"), move_key("missing")])
+            .await
+            .unwrap();
+        assert!(!outcome.committed);
+        assert!(matches!(
+            &outcome.results[1],
+            Err(StoreError::KeyMissing(key)) if key == "missing"
+        ));
+        assert_eq!(std::fs::read_to_string(&tmp.file).unwrap(), before);
+
+        // A temporary file that can't be written fails the commit before either catalog
+        // changes, and the one already written is cleaned up.
+        let blocker = transaction_temp_path(&tmp.file);
+        std::fs::create_dir(&blocker).unwrap();
+        assert!(manager
+            .apply_transaction(vec![move_key("checkout.title")])
+            .await
+            .is_err());
+        assert_eq!(std::fs::read_to_string(&tmp.file).unwrap(), before);
+        assert_eq!(store.keys().await.len(), 3);
+        assert!(!transaction_temp_path(&other).exists());
+        std::fs::remove_dir(&blocker).unwrap();
+
+        let outcome = manager
+            .apply_transaction(vec![
+                move_key("checkout.title"),
+                move_key("checkout.pay"),
+                TransactionOperation::Edit {
+                    path: other_path.clone(),
+                    operation: BatchOperation::Upsert {
+                        key: "checkout.pay".into(),
+                        language: "en".into(),
+                        update: Box::new(TranslationUpdate::from_value_state(
+                            Some("Pay now".into()),
+                            None,
+                        )),
+                        comment: None,
+                        position: InsertPosition::End,
+                    },
+                },
+            ])
+            .await
+            .unwrap();
+        assert!(outcome.committed);
+        assert_eq!(outcome.saved.len(), 2);
+        assert_eq!(store.keys().await, ["home.title"]);
+        let checkout = manager.store_for(other_path.as_deref()).await.unwrap();
+        assert_eq!(
+            checkout.keys().await,
+            ["checkout.done", "checkout.pay", "checkout.title"]
+        );
+        assert!(!checkout.has_external_changes().await);
+        let reloaded = XcStringsStore::load_or_create(&other).await.unwrap();
+        let pay = reloaded
+            .get_record("checkout.pay")
+            .await
+            .expect("moved key");
+        assert_eq!(pay.translations["en"].value.as_deref(), Some("Pay now"));
+
+        // Within one catalog a move only changes the key's position.
+        let reorder = |position: InsertPosition| TransactionOperation::MoveKey {
+            key: "checkout.done".into(),
+            from: other_path.clone(),
+            to: other_path.clone(),
+            position,
+        };
+        let outcome = manager
+            .apply_transaction(vec![reorder(InsertPosition::End)])
+            .await
+            .unwrap();
+        assert!(outcome.committed, "{:?}", outcome.results);
+        assert_eq!(
+            checkout.keys().await,
+            ["checkout.pay", "checkout.title", "checkout.done"]
+        );
+        let outcome = manager
+            .apply_transaction(vec![reorder(InsertPosition::After("checkout.done".into()))])
+            .await
+            .unwrap();
+        assert!(matches!(
+            &outcome.results[0],
+            Err(StoreError::KeyMissing(key)) if key == "checkout.done"
+        ));
+        assert_eq!(
+            checkout.keys().await,
+            ["checkout.pay", "checkout.title", "checkout.done"]
+        );
+    }
+
+    #[tokio::test]
+    async fn rename_namespace_moves_keys_in_place() {
+        let tmp = TempStorePath::new("rename_namespace");
+        let manager = XcStringsStoreManager::new(Some(tmp.file.clone()))
+            .await
+            .expect("create manager");
+        let store = manager.default_store().await.expect("default store");
+        for key in [
+            "checkout.title",
+            "home.title",
+            "checkout.payment.card",
+            "checkoutButton",
+        ] {
+            store
+                .upsert_translation(
+                    key,
+                    "en",
+                    TranslationUpdate::from_value_state(Some(key.into()), None),
+                )
+                .await
+                .unwrap();
+        }
+        let other = tmp.dir.join("Other.xcstrings");
+        std::fs::write(
+            &other,
+            r#"{"sourceLanguage":"en","version":"1.0","strings":{
+                "checkout.done":{"comment":"Done"},"payment.done":{"comment":"Done"}}}"#,
+        )
+        .unwrap();
+
+        let preview = store
+            .rename_namespace("checkout.", "payment", false)
+            .await
+            .unwrap();
+        assert_eq!(preview.renamed.len(), 2);
+        assert!(preview.collisions.is_empty());
+        assert_eq!(
+            store.keys().await[0],
+            "checkout.title",
+            "dry run writes nothing"
+        );
+
+        let everywhere = manager
+            .rename_namespace_everywhere("checkout", "payment", true)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&everywhere, StoreError::NamespaceCollision(keys) if keys.len() == 1 && keys[0].starts_with("payment.done")),
+            "{everywhere:?}"
+        );
+        assert_eq!(
+            store.keys().await[0],
+            "checkout.title",
+            "collisions abort every catalog"
+        );
+
+        store
+            .rename_namespace("checkout", "payment", true)
+            .await
+            .unwrap();
+        assert_eq!(
+            store.keys().await,
+            vec![
+                "payment.title",
+                "home.title",
+                "payment.payment.card",
+                "checkoutButton"
+            ]
+        );
+        assert!(matches!(
+            store.rename_namespace(" ", "payment", true).await,
+            Err(StoreError::InvalidNamespace(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn rename_namespace_everywhere_changes_no_catalog_when_one_refuses() {
+        let tmp = TempStorePath::new("rename_namespace_frozen");
+        let manager = XcStringsStoreManager::new(Some(tmp.file.clone()))
+            .await
+            .expect("create manager");
+        let store = manager.default_store().await.expect("default store");
+        store
+            .upsert_translation(
+                "checkout.title",
+                "en",
+                TranslationUpdate::from_value_state(Some("Checkout".into()), None),
+            )
+            .await
+            .unwrap();
+        // Sorts after `Localizable.xcstrings`, so it's the second catalog renamed.
+        let other = tmp.dir.join("Settings.xcstrings");
+        std::fs::write(
+            &other,
+            r#"{"sourceLanguage":"en","version":"1.0","strings":{"checkout.done":{
+                "localizations":{"it":{"stringUnit":{"state":"translated","value":"Fatto"}}}}}}"#,
+        )
+        .unwrap();
+        let settings = manager
+            .store_for(Some(&other.to_string_lossy()))
+            .await
+            .unwrap();
+        settings.freeze_language("it", true).await.unwrap();
+        let before = std::fs::read_to_string(&tmp.file).unwrap();
+
+        let result = manager
+            .rename_namespace_everywhere("checkout", "payment", true)
+            .await;
+        assert!(
+            matches!(&result, Err(StoreError::LanguageFrozen(language)) if language == "it"),
+            "{result:?}"
+        );
+        assert_eq!(store.keys().await, ["checkout.title"]);
+        assert_eq!(std::fs::read_to_string(&tmp.file).unwrap(), before);
+        assert_eq!(settings.keys().await, ["checkout.done"]);
+    }
+
+    #[tokio::test]
+    async fn project_mode_discovers_only_built_catalogs() {
+        let temp = TempStorePath::new("project_mode");
+        let project = temp.dir.join("App.xcodeproj");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(
+            project.join("project.pbxproj"),
+            r#"{ objects = {
+                B1 = {isa = PBXBuildFile; fileRef = F1; };
+                F1 = {isa = PBXFileReference; path = Localizable.xcstrings; sourceTree = "<group>"; };
+                G1 = {isa = PBXGroup; children = (F1); path = App; sourceTree = "<group>"; };
+                G0 = {isa = PBXGroup; children = (G1); sourceTree = "<group>"; };
+                P1 = {isa = PBXProject; mainGroup = G0; };
+                R1 = {isa = PBXResourcesBuildPhase; files = (B1); };
+                T1 = {isa = PBXNativeTarget; buildPhases = (R1); name = App; };
+            }; rootObject = P1; }"#,
+        )
+        .unwrap();
+        let built = temp.dir.join("App/Localizable.xcstrings");
+        std::fs::create_dir_all(built.parent().unwrap()).unwrap();
+        std::fs::write(&built, "{}").unwrap();
+        std::fs::write(&temp.file, "{}").unwrap();
+
+        let manager = XcStringsStoreManager::new(None)
+            .await
+            .unwrap()
+            .with_project(project)
+            .await
+            .expect("open project");
+        let built = std::fs::canonicalize(&built).unwrap();
+        assert_eq!(manager.available_paths().await, vec![built.clone()]);
+        assert_eq!(manager.targets_for(&built).await, vec!["App"]);
+        assert_eq!(
+            manager.catalog_label(&built).await,
+            "App/Localizable.xcstrings (App)"
+        );
+
+        let missing = XcStringsStoreManager::new(None)
+            .await
+            .unwrap()
+            .with_project(temp.dir.join("Missing.xcodeproj"))
+            .await;
+        assert!(matches!(missing, Err(StoreError::InvalidProject(_))));
+    }
+
+    #[tokio::test]
+    async fn catalogs_are_labeled_by_package_or_project() {
+        let temp = TempStorePath::new("catalog_labels");
+        std::fs::create_dir_all(temp.dir.join("App.xcodeproj")).unwrap();
+        let package = temp.dir.join("Packages/Auth");
+        std::fs::create_dir_all(package.join("Sources")).unwrap();
+        std::fs::write(
+            package.join("Package.swift"),
+            r#"let package = Package(name: "AuthKit", targets: [])"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(temp.dir.join("App/Resources")).unwrap();
+        let files = [
+            temp.file.clone(),
+            temp.dir.join("App/Localizable.xcstrings"),
+            temp.dir.join("App/Resources/InfoPlist.xcstrings"),
+            package.join("Sources/Localizable.xcstrings"),
+        ];
+        for file in &files {
+            std::fs::write(file, "{}").unwrap();
+        }
+
+        let manager = XcStringsStoreManager::new(Some(temp.file.clone()))
+            .await
+            .unwrap();
+        let files = &files;
+        let labels = |manager: XcStringsStoreManager| async move {
+            let mut labels = Vec::new();
+            for file in files {
+                labels.push(
+                    manager
+                        .catalog_label(&std::fs::canonicalize(file).unwrap())
+                        .await,
+                );
+            }
+            labels
+        };
+        assert_eq!(
+            labels(manager.clone()).await,
+            [
+                // Both belong to App, so they keep their paths.
+                "Localizable.xcstrings",
+                "App/Localizable.xcstrings",
+                "App/InfoPlist.xcstrings",
+                "AuthKit/Localizable.xcstrings",
+            ]
+        );
+        let manager = manager.with_catalog_labels([(
+            "Packages/Auth/Sources/Localizable.xcstrings".to_string(),
+            "Sign-in".to_string(),
+        )]);
+        assert_eq!(labels(manager).await[3], "Sign-in");
+    }
+
+    #[tokio::test]
+    async fn merge_fills_gaps_and_reports_conflicts() {
+        let target_path = TempStorePath::new("merge_target");
+        let source_path = TempStorePath::new("merge_source");
+        let target = XcStringsStore::load_or_create(&target_path.file)
+            .await
+            .expect("load target");
+        let source = XcStringsStore::load_or_create(&source_path.file)
+            .await
+            .expect("load source");
+        for (store, key, language, value) in [
+            (&target, "shared", "en", "Shared"),
+            (&target, "shared", "fr", "Partagé"),
+            (&source, "shared", "fr", "Commun"),
+            (&source, "shared", "de", "Geteilt"),
+            (&source, "new", "en", "New"),
+        ] {
+            store
+                .upsert_translation(
+                    key,
+                    language,
+                    TranslationUpdate::from_value_state(Some(value.into()), None),
+                )
+                .await
+                .expect("seed translation");
+        }
+
+        let preview = target.merge_from(&source, false, false).await.unwrap();
+        assert_eq!(preview.added_keys, vec!["new"]);
+        assert_eq!(
+            preview.merged,
+            vec![MergedTranslation {
+                key: "shared".into(),
+                language: "de".into()
+            }]
+        );
+        assert_eq!(preview.conflicts.len(), 1);
+        assert!(target.get_record("new").await.is_none());
+
+        let applied = target.merge_from(&source, true, true).await.unwrap();
+        assert!(applied.conflicts.is_empty());
+        assert_eq!(applied.merged.len(), 2);
+        let reloaded = XcStringsStore::load_or_create(&target_path.file)
+            .await
+            .expect("reload");
+        assert_eq!(reloaded.keys().await, vec!["shared", "new"]);
+        assert_eq!(
+            reloaded
+                .get_translation("shared", "fr")
+                .await
+                .ok()
+                .and_then(|record| record.value)
+                .as_deref(),
+            Some("Commun")
+        );
+    }
+
+    #[tokio::test]
+    async fn snapshot_restores_catalog_and_is_not_discovered() {
+        let tmp = TempStorePath::new("snapshot_restore");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        store
+            .upsert_translation(
+                "greeting",
+                "fr",
+                TranslationUpdate::from_value_state(Some("Bonjour".into()), None),
+            )
+            .await
+            .expect("seed translation");
+
+        let snapshot = store.snapshot("before-bulk").await.expect("snapshot");
+        assert!(snapshot.starts_with(tmp.dir.join(SNAPSHOT_DIR)));
+        assert_eq!(store.list_snapshots().await.unwrap(), vec!["before-bulk"]);
+
+        store.delete_key("greeting").await.expect("delete key");
+        store
+            .restore_snapshot("before-bulk")
+            .await
+            .expect("restore");
+        let restored = store
+            .get_translation("greeting", "fr")
+            .await
+            .expect("fetch");
+        assert_eq!(restored.value.as_deref(), Some("Bonjour"));
+        let reloaded = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("reload");
+        assert_eq!(reloaded.keys().await, vec!["greeting".to_string()]);
+
+        assert!(matches!(
+            store.restore_snapshot("missing").await.unwrap_err(),
+            StoreError::SnapshotMissing(label) if label == "missing"
+        ));
+        for label in ["", "../escape", ".hidden", "a/b"] {
+            assert!(matches!(
+                store.snapshot(label).await.unwrap_err(),
+                StoreError::InvalidSnapshotLabel(_)
+            ));
+        }
+        assert_eq!(discover_xcstrings(&tmp.dir).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn revision_tracks_file_contents() {
+        let tmp = TempStorePath::new("revision");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        let initial = store.revision();
+
+        store
+            .upsert_translation(
+                "greeting",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .expect("seed translation");
+        let saved = store.revision();
+        assert_ne!(saved, initial);
+
+        store.reload().await.expect("reload");
+        assert_eq!(store.revision(), saved);
+        let reopened = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("reopen store");
+        assert_eq!(reopened.revision(), saved);
+    }
+
+    #[tokio::test]
+    async fn expecting_handles_refuse_edits_from_a_stale_revision() {
+        let tmp = TempStorePath::new("expecting");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        let loaded = store.revision();
+        let update = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+
+        let first = store.expecting(vec![loaded.clone()]);
+        let second = store.expecting(vec![format!("W/{loaded}")]);
+        first
+            .upsert_translation("greeting", "en", update("Hello"))
+            .await
+            .expect("first writer");
+        assert_eq!(first.written_revision(), store.revision());
+        let err = second
+            .upsert_translation("greeting", "en", update("Hi"))
+            .await
+            .expect_err("second writer is stale");
+        assert!(matches!(err, StoreError::RevisionMismatch), "{err}");
+
+        // A handle builds on its own edits, and `*` matches whatever is there.
+        first
+            .upsert_translation("farewell", "en", update("Bye"))
+            .await
+            .expect("follow-up edit");
+        store
+            .expecting(vec!["*".to_string()])
+            .delete_key("farewell")
+            .await
+            .expect("unconditional write");
+        assert_eq!(store.keys().await, ["greeting"]);
+    }
+
+    #[tokio::test]
+    async fn apply_batch_writes_once_or_not_at_all() {
+        let tmp = TempStorePath::new("apply_batch");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        let mut events = store.subscribe();
+        let upsert = |key: &str| BatchOperation::Upsert {
+            key: key.into(),
+            language: "fr".into(),
+            update: Box::new(TranslationUpdate::from_value_state(
+                Some("Bonjour".into()),
+                None,
+            )),
+            comment: None,
+            position: InsertPosition::End,
+        };
+
+        let failed = store
+            .apply_batch(vec![
+                upsert("greeting"),
+                BatchOperation::DeleteKey {
+                    key: "missing".into(),
+                },
+            ])
+            .await
+            .expect("batch");
+        assert!(!failed.committed);
+        assert!(failed.results[0].is_ok());
+        assert!(matches!(failed.results[1], Err(StoreError::KeyMissing(_))));
+        assert!(store.keys().await.is_empty());
+
+        let applied = store
+            .apply_batch(vec![upsert("greeting"), upsert("farewell")])
+            .await
+            .expect("batch");
+        assert!(applied.committed);
+        assert_eq!(store.keys().await.len(), 2);
+        match events.recv().await.expect("saved event") {
+            StoreEvent::Saved {
+                change: StoreChange::Batch { changes },
+                ..
+            } => assert_eq!(changes.len(), 2),
+            other => panic!("unexpected event {other:?}"),
+        }
+        assert!(events.try_recv().is_err(), "batch saves exactly once");
+    }
+
+    #[tokio::test]
+    async fn apply_patch_validates_and_writes_atomically() {
+        let tmp = TempStorePath::new("apply_patch");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store")
+            .with_placeholder_validation(PlaceholderValidation::Strict);
+        for (language, value) in [("en", "%lld items"), ("fr", "%lld éléments")] {
+            store
+                .upsert_translation(
+                    "items",
+                    language,
+                    TranslationUpdate::from_value_state(Some(value.into()), None),
+                )
+                .await
+                .expect("upsert");
+        }
+        let ops = |raw: serde_json::Value| -> Vec<patch::PatchOperation> {
+            serde_json::from_value(raw).expect("operations")
+        };
+
+        let before = fs::read_to_string(&tmp.file).await.expect("read");
+        for (raw, expected) in [
+            (
+                serde_json::json!([
+                    { "op": "replace", "path": "/items/comment", "value": "Cart badge" },
+                ]),
+                "'/items/comment' doesn't exist",
+            ),
+            (
+                serde_json::json!([
+                    { "op": "add", "path": "/items/comment", "value": "Cart badge" },
+                    { "op": "add", "path": "/items/localizations/fr/stringUnit/tone", "value": "formal" },
+                ]),
+                "isn't part of the string catalog format",
+            ),
+            (
+                serde_json::json!([
+                    { "op": "replace", "path": "/items/localizations/fr/stringUnit/value", "value": "éléments" },
+                ]),
+                "format specifiers",
+            ),
+        ] {
+            let err = store.apply_patch(&ops(raw)).await.unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+        assert_eq!(fs::read_to_string(&tmp.file).await.expect("read"), before);
+
+        let outcome = store
+            .apply_patch(&ops(serde_json::json!([
+                { "op": "add", "path": "/items/comment", "value": "Cart badge" },
+                { "op": "copy", "from": "/items/localizations/fr", "path": "/items/localizations/fr-CA" },
+                { "op": "move", "from": "/items", "path": "/cart.items" },
+                { "op": "add", "path": "/cart.items/localizations/fr-CA/stringUnit/state", "value": "Needs Review" },
+            ])))
+            .await
+            .expect("patch");
+        assert_eq!(
+            outcome.changes,
+            [
+                StoreChange::KeyDeleted {
+                    key: "items".into()
+                },
+                StoreChange::KeyCreated {
+                    key: "cart.items".into()
+                },
+                StoreChange::TranslationUpserted {
+                    key: "cart.items".into(),
+                    language: "en".into()
+                },
+                StoreChange::TranslationUpserted {
+                    key: "cart.items".into(),
+                    language: "fr".into()
+                },
+                StoreChange::TranslationUpserted {
+                    key: "cart.items".into(),
+                    language: "fr-CA".into()
+                },
+            ]
+        );
+        assert_eq!(outcome.warnings.len(), 1, "{:?}", outcome.warnings);
+        assert_eq!(store.keys().await, ["cart.items"]);
+        let record = store.get_record("cart.items").await.expect("record");
+        assert_eq!(record.comment.as_deref(), Some("Cart badge"));
+        let fr_ca = store
+            .get_translation("cart.items", "fr-CA")
+            .await
+            .expect("fr-CA");
+        assert_eq!(fr_ca.state.as_deref(), Some("needs_review"));
+        assert_eq!(fr_ca.value.as_deref(), Some("%lld éléments"));
+
+        store.freeze_language("fr", false).await.expect("freeze");
+        let remove = ops(serde_json::json!([{ "op": "remove", "path": "/cart.items" }]));
+        assert!(matches!(
+            store.apply_patch(&remove).await,
+            Err(StoreError::LanguageFrozen(language)) if language == "fr"
+        ));
+        assert_eq!(store.keys().await, ["cart.items"]);
+        store.forcing().apply_patch(&remove).await.expect("forced");
+        assert!(store.keys().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn raw_entries_round_trip_and_are_validated() {
+        let tmp = TempStorePath::new("raw_entry");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        assert!(matches!(
+            store.raw_entry("files").await,
+            Err(StoreError::KeyMissing(_))
+        ));
+
+        let unit = |value: &str| serde_json::json!({ "stringUnit": { "state": "translated", "value": value } });
+        let entry = serde_json::json!({
+            "comment": "File count",
+            "localizations": {
+                "en": { "variations": { "device": {
+                    "mac": { "variations": { "plural": {
+                        "one": unit("%lld file"),
+                        "other": unit("%lld files"),
+                    } } },
+                    "other": unit("%lld files"),
+                } } },
+            },
+        });
+        let outcome = store
+            .set_raw_entry("files", entry.clone())
+            .await
+            .expect("set");
+        assert!(outcome.warnings.is_empty());
+        assert_eq!(store.raw_entry("files").await.expect("get"), entry);
+
+        let err = store
+            .set_raw_entry("files", serde_json::json!({ "comment": 3 }))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StoreError::InvalidPatch(_)), "{err}");
+        let err = store
+            .set_raw_entry("files", serde_json::json!({ "localisations": {} }))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("/files/localisations"), "{err}");
+        assert_eq!(store.raw_entry("files").await.expect("get"), entry);
     }
 
     #[tokio::test]
-    async fn upsert_and_fetch_translation() {
-        let tmp = TempStorePath::new("upsert_fetch");
+    async fn find_replace_previews_then_applies() {
+        let tmp = TempStorePath::new("find_replace");
         let store = XcStringsStore::load_or_create(&tmp.file)
             .await
             .expect("load store");
-
         store
             .upsert_translation(
                 "greeting",
-                "en",
-                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+                "fr",
+                TranslationUpdate::from_value_state(Some("Bonjour, Ami".into()), None),
             )
             .await
             .expect("upsert");
+        let plural = TranslationUpdate::default()
+            .add_variation(
+                "plural",
+                "one",
+                TranslationUpdate::from_value_state(Some("%d ami".into()), None),
+            )
+            .add_variation(
+                "plural",
+                "other",
+                TranslationUpdate::from_value_state(Some("%d amis".into()), None),
+            );
+        store
+            .upsert_translation("friends", "fr", plural)
+            .await
+            .expect("upsert plural");
         store
             .upsert_translation(
                 "greeting",
-                "fr",
-                TranslationUpdate::from_value_state(Some("Bonjour".into()), None),
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello, ami".into()), None),
             )
             .await
-            .expect("upsert");
-
-        let value = store
-            .get_translation("greeting", "en")
-            .await
-            .expect("get")
-            .expect("value");
-        assert_eq!(value.value.as_deref(), Some("Hello"));
-
-        let languages = store.list_languages().await;
-        assert!(languages.contains(&"en".to_string()));
-        assert!(languages.contains(&"fr".to_string()));
+            .expect("upsert en");
 
-        let records = store.list_records(None).await;
-        assert_eq!(records.len(), 1);
-        assert_eq!(records[0].key, "greeting");
-        assert!(records[0].translations.contains_key("fr"));
+        let search = FindReplace {
+            find: "AMI".into(),
+            replace: "copain".into(),
+            case_sensitive: false,
+            language: Some("fr".into()),
+        };
+        let preview = store.find_replace(&search, false).await.expect("preview");
+        assert_eq!(preview.len(), 3);
+        assert_eq!(preview[0].before, "Bonjour, Ami");
+        assert_eq!(preview[0].after, "Bonjour, copain");
+        assert_eq!(preview[1].location, "variations.plural.one");
+        assert_eq!(preview[2].after, "%d copains");
+        let unchanged = store.get_translation("greeting", "fr").await.expect("get");
+        assert_eq!(unchanged.value.as_deref(), Some("Bonjour, Ami"));
+
+        let mut events = store.subscribe();
+        let applied = store.find_replace(&search, true).await.expect("apply");
+        assert_eq!(applied, preview);
+        match events.recv().await.expect("saved event") {
+            StoreEvent::Saved {
+                change: StoreChange::Batch { changes },
+                ..
+            } => assert_eq!(changes.len(), 2),
+            other => panic!("unexpected event {other:?}"),
+        }
+        let english = store.get_translation("greeting", "en").await.expect("get");
+        assert_eq!(english.value.as_deref(), Some("Hello, ami"));
+
+        let case_sensitive = FindReplace {
+            find: "copain".into(),
+            replace: "Copain".into(),
+            case_sensitive: true,
+            language: None,
+        };
+        assert_eq!(
+            store
+                .find_replace(&case_sensitive, false)
+                .await
+                .expect("preview")
+                .len(),
+            3
+        );
     }
 
     #[tokio::test]
-    async fn delete_translation_removes_empty_keys() {
-        let tmp = TempStorePath::new("delete_translation");
+    async fn create_key_adds_manual_entry_with_source_value() {
+        let tmp = TempStorePath::new("create_key");
         let store = XcStringsStore::load_or_create(&tmp.file)
             .await
             .expect("load store");
 
-        store
-            .upsert_translation(
-                "farewell",
-                "en",
-                TranslationUpdate::from_value_state(Some("Bye".into()), None),
+        let record = store
+            .create_key(
+                "welcome",
+                Some("Home title".into()),
+                Some("Welcome".into()),
+                &InsertPosition::End,
             )
             .await
-            .expect("upsert");
+            .expect("create key");
+        assert_eq!(record.extraction_state.as_deref(), Some("manual"));
+        assert_eq!(record.comment.as_deref(), Some("Home title"));
+        assert_eq!(record.translations["en"].value.as_deref(), Some("Welcome"));
+        assert_eq!(
+            record.translations["en"].state.as_deref(),
+            Some("translated")
+        );
 
-        store
-            .delete_translation("farewell", "en")
+        let bare = store
+            .create_key("empty", None, None, &InsertPosition::End)
             .await
-            .expect("delete translation");
+            .expect("create bare key");
+        assert!(bare.translations.is_empty());
+        assert!(
+            store.get_record("empty").await.is_some(),
+            "bare keys persist"
+        );
 
         assert!(matches!(
-            store.get_translation("farewell", "en").await.expect("get"),
-            None
+            store
+                .create_key("welcome", None, None, &InsertPosition::End)
+                .await,
+            Err(StoreError::KeyExists(_))
+        ));
+        assert!(store.get_record("missing").await.is_none());
+
+        let subtitle = store
+            .create_key(
+                "welcome.subtitle",
+                None,
+                None,
+                &InsertPosition::After("welcome".into()),
+            )
+            .await
+            .expect("create key after welcome");
+        assert_eq!(subtitle.ordinal, 1);
+        assert_eq!(store.keys().await, ["welcome", "welcome.subtitle", "empty"]);
+        let summaries = store.list_summaries(None).await;
+        assert_eq!(summaries[2].key, "empty");
+        assert_eq!(summaries[2].ordinal, 2);
+        assert_eq!(store.get_record("empty").await.expect("record").ordinal, 2);
+        assert!(matches!(
+            store
+                .create_key("other", None, None, &InsertPosition::After("missing".into()))
+                .await,
+            Err(StoreError::KeyMissing(key)) if key == "missing"
         ));
+    }
 
-        let err = store.delete_key("farewell").await.unwrap_err();
-        assert!(matches!(err, StoreError::KeyMissing(_)));
+    #[tokio::test]
+    async fn upserts_place_new_keys_at_insert_position() {
+        let tmp = TempStorePath::new("insert_position");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        let upsert = |key: &'static str, position: InsertPosition| {
+            let store = &store;
+            async move {
+                let update = TranslationUpdate::from_value_state(Some(key.into()), None);
+                store
+                    .upsert_translation_with(key, "en", update, None, &position, true)
+                    .await
+            }
+        };
+
+        for key in ["alpha", "login.title", "zeta"] {
+            upsert(key, InsertPosition::End).await.expect("append");
+        }
+        upsert("beta", InsertPosition::Sorted)
+            .await
+            .expect("sorted");
+        upsert("login.button", InsertPosition::After("login.title".into()))
+            .await
+            .expect("after");
+        upsert("alpha", InsertPosition::After("zeta".into()))
+            .await
+            .expect("existing keys stay put");
+        assert_eq!(
+            store.keys().await,
+            ["alpha", "beta", "login.title", "login.button", "zeta"]
+        );
+        assert!(matches!(
+            upsert("signup.title", InsertPosition::After("signup".into())).await,
+            Err(StoreError::KeyMissing(key)) if key == "signup"
+        ));
+
+        let batch = store
+            .apply_batch(vec![BatchOperation::Upsert {
+                key: "omega".into(),
+                language: "en".into(),
+                update: Box::new(TranslationUpdate::from_value_state(
+                    Some("Omega".into()),
+                    None,
+                )),
+                comment: None,
+                position: InsertPosition::Sorted,
+            }])
+            .await
+            .expect("batch");
+        assert!(batch.committed);
+        assert_eq!(store.keys().await.last().map(String::as_str), Some("zeta"));
+
+        let positions: Vec<InsertPosition> =
+            serde_json::from_str(r#"["end", "sorted", {"after": "alpha"}]"#).expect("parse");
+        assert_eq!(positions[2], InsertPosition::After("alpha".into()));
     }
 
     #[tokio::test]
@@ -1719,18 +7937,13 @@ mod tests {
             .await
             .expect("rename");
 
-        let missing = store
-            .get_translation("old.key", "en")
-            .await
-            .expect("fetch old")
-            .is_none();
+        let missing = store.get_translation("old.key", "en").await.ok().is_none();
         assert!(missing);
 
         let renamed = store
             .get_translation("new.key", "en")
             .await
-            .expect("fetch new")
-            .expect("translation exists");
+            .expect("fetch new");
         assert_eq!(renamed.value.as_deref(), Some("Original"));
 
         store
@@ -1861,13 +8074,17 @@ mod tests {
             .await
             .expect("load store");
 
-        let mut update = TranslationUpdate::default();
-        update.value = Some(Some("Found %#@arg1@".into()));
+        let mut update = TranslationUpdate {
+            value: Some(Some("Found %#@arg1@".into())),
+            ..Default::default()
+        };
         let mut substitutions = IndexMap::new();
-        let mut substitution = SubstitutionUpdate::default();
-        substitution.value = Some(Some("%arg item".into()));
-        substitution.arg_num = Some(Some(1));
-        substitution.format_specifier = Some(Some("ld".into()));
+        let substitution = SubstitutionUpdate {
+            value: Some(Some("%arg item".into())),
+            arg_num: Some(Some(1)),
+            format_specifier: Some(Some("ld".into())),
+            ..Default::default()
+        };
         substitutions.insert("arg1".to_string(), Some(substitution));
         update.substitutions = Some(substitutions);
 
@@ -1879,8 +8096,7 @@ mod tests {
         let en_translation = store
             .get_translation("message", "en")
             .await
-            .expect("fetch translation")
-            .expect("translation exists");
+            .expect("fetch translation");
 
         let arg1 = en_translation
             .substitutions
@@ -1903,8 +8119,7 @@ mod tests {
         let en_translation = store
             .get_translation("message", "en")
             .await
-            .expect("fetch translation")
-            .expect("translation exists");
+            .expect("fetch translation");
         assert!(en_translation.substitutions.is_empty());
     }
 
@@ -1939,6 +8154,8 @@ mod tests {
         assert_eq!(summary.key, "greeting");
         assert_eq!(summary.languages, vec!["en".to_string(), "de".to_string()]);
         assert!(summary.has_variations);
+        assert_eq!(summary.states["en"], "translated");
+        assert_eq!(summary.states["de"], "translated");
     }
 
     #[tokio::test]
@@ -1974,8 +8191,7 @@ mod tests {
         let value = store
             .get_translation("file_count", "en")
             .await
-            .expect("fetch translation")
-            .expect("translation exists");
+            .expect("fetch translation");
 
         assert!(value.value.is_none());
         let plural = value
@@ -2000,6 +8216,57 @@ mod tests {
             .is_some());
     }
 
+    #[tokio::test]
+    async fn width_variations_round_trip() {
+        let tmp = TempStorePath::new("width_round_trip");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("load store");
+        let text = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+
+        let update = TranslationUpdate::default()
+            .add_width_variation(50, text("Download the latest version"))
+            .add_width_variation(1, text("Update"))
+            .add_width_variation(
+                20,
+                TranslationUpdate::default()
+                    .add_variation("plural", "one", text("%lld update"))
+                    .add_variation("plural", "other", text("%lld updates")),
+            );
+        let outcome = store
+            .upsert_translation("update.button", "en", update)
+            .await
+            .expect("save width");
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .expect("reload store");
+        let value = store
+            .get_translation("update.button", "en")
+            .await
+            .expect("fetch translation");
+        let widths: Vec<_> = value
+            .width_variations()
+            .into_iter()
+            .map(|(width, case)| (width, case.value.as_deref()))
+            .collect();
+        assert_eq!(
+            widths,
+            [
+                (1, Some("Update")),
+                (20, None),
+                (50, Some("Download the latest version"))
+            ]
+        );
+        assert_eq!(
+            value.variations["width"]["20"].variations["plural"]["other"]
+                .value
+                .as_deref(),
+            Some("%lld updates")
+        );
+    }
+
     #[tokio::test]
     async fn plural_variation_merge_preserves_existing() {
         let tmp = TempStorePath::new("plural_merge");
@@ -2033,11 +8300,7 @@ mod tests {
             .await
             .expect("patch");
 
-        let value = store
-            .get_translation("items", "en")
-            .await
-            .expect("fetch")
-            .expect("exists");
+        let value = store.get_translation("items", "en").await.expect("fetch");
         let plural = value
             .variations
             .get("plural")
@@ -2081,6 +8344,188 @@ mod tests {
         assert!(Arc::ptr_eq(&store_a, &store_b));
     }
 
+    #[test]
+    fn parse_keeps_field_order_without_copying_strings() {
+        let raw = r#"{"strings":{"greeting":{"localizations":{"en":{"stringUnit":{"state":"translated","value":"Hello"}}}}},"custom":[1],"version":2,"sourceLanguage":"de"}"#;
+        let doc = XcStringsFile::parse(raw).unwrap();
+        assert_eq!(doc.source_language, "de");
+        assert_eq!(doc.version, DEFAULT_VERSION);
+        assert_eq!(doc.raw["strings"], serde_json::Value::Null);
+        let fields: Vec<String> = doc
+            .to_json_value()
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        assert_eq!(fields, ["strings", "custom", "version", "sourceLanguage"]);
+        assert_eq!(doc.to_json_value()["custom"], serde_json::json!([1]));
+
+        assert!(XcStringsFile::parse(r#"{"strings":{"greeting":"Hello"}}"#).is_err());
+        assert!(XcStringsFile::parse("[]").is_err());
+    }
+
+    #[tokio::test]
+    async fn manager_reloads_reused_store_only_when_file_changed() {
+        let tmp = TempStorePath::new("manager_reload_changed");
+        let manager = XcStringsStoreManager::new(None)
+            .await
+            .expect("create manager");
+        let path_str = tmp.file.to_str().unwrap().to_string();
+        let store = manager.store_for(Some(&path_str)).await.expect("load");
+        store
+            .upsert_translation(
+                "greeting",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .expect("seed translation");
+        let value = |store: Arc<XcStringsStore>| async move {
+            store.get_translation("greeting", "en").await.unwrap().value
+        };
+
+        // Same size and modification time: the cached contents are reused without re-reading.
+        let modified = std::fs::metadata(&tmp.file).unwrap().modified().unwrap();
+        let raw = std::fs::read_to_string(&tmp.file).unwrap();
+        std::fs::write(&tmp.file, raw.replace("Hello", "Hallo")).unwrap();
+        let file = std::fs::File::options()
+            .write(true)
+            .open(&tmp.file)
+            .unwrap();
+        file.set_modified(modified).unwrap();
+        let store = manager.store_for(Some(&path_str)).await.unwrap();
+        assert_eq!(value(store.clone()).await.as_deref(), Some("Hello"));
+
+        file.set_modified(modified + Duration::from_secs(1))
+            .unwrap();
+        let store = manager.store_for(Some(&path_str)).await.unwrap();
+        assert_eq!(value(store).await.as_deref(), Some("Hallo"));
+    }
+
+    #[tokio::test]
+    async fn manager_detects_external_changes() {
+        let tmp = TempStorePath::new("manager_external_changes");
+        let manager = XcStringsStoreManager::new(None)
+            .await
+            .expect("create manager");
+        let path_str = tmp.file.to_str().unwrap().to_string();
+        let store = manager
+            .store_for(Some(path_str.as_str()))
+            .await
+            .expect("load store");
+        let mut events = manager.subscribe();
+
+        store
+            .upsert_translation(
+                "greeting",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .expect("seed translation");
+        assert_eq!(
+            events.recv().await.expect("saved event"),
+            StoreEvent::Saved {
+                path: store.path().to_path_buf(),
+                change: StoreChange::TranslationUpserted {
+                    key: "greeting".into(),
+                    language: "en".into(),
+                },
+            }
+        );
+        assert!(!store.has_external_changes().await);
+        assert!(manager.poll_external_changes().await.is_empty());
+
+        let external = serde_json::json!({
+            "sourceLanguage": "en",
+            "version": "1.0",
+            "strings": {
+                "greeting": {
+                    "localizations": {
+                        "en": { "stringUnit": { "state": "translated", "value": "Hello from Xcode" } }
+                    }
+                }
+            }
+        });
+        std::fs::write(store.path(), external.to_string()).expect("external write");
+
+        assert!(store.has_external_changes().await);
+        let changed = manager.poll_external_changes().await;
+        assert_eq!(changed, vec![store.path().to_path_buf()]);
+        assert_eq!(
+            events.recv().await.expect("external event"),
+            StoreEvent::ExternalChange {
+                path: store.path().to_path_buf()
+            }
+        );
+
+        let value = store.get_translation("greeting", "en").await.expect("get");
+        assert_eq!(value.value.as_deref(), Some("Hello from Xcode"));
+        assert!(manager.poll_external_changes().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn qa_sweeps_report_only_new_findings() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("Localizable.xcstrings");
+        let catalog = |german: &str| {
+            serde_json::json!({
+                "sourceLanguage": "en",
+                "version": "1.0",
+                "strings": {
+                    "count": {
+                        "localizations": {
+                            "en": { "stringUnit": { "state": "translated", "value": "%lld items" } },
+                            "de": { "stringUnit": { "state": "translated", "value": german } }
+                        }
+                    }
+                }
+            })
+            .to_string()
+        };
+        std::fs::write(&path, catalog("%lld Artikel")).expect("write catalog");
+        let manager = XcStringsStoreManager::new(Some(path.clone()))
+            .await
+            .expect("create manager");
+        let path = manager.default_path().expect("default path");
+        let mut events = manager.subscribe();
+        let options = QaOptions::default();
+
+        assert!(manager.sweep_qa(&options).await.is_empty(), "baseline");
+        assert!(manager
+            .qa_sweep_report(&path)
+            .await
+            .expect("recorded report")
+            .is_clean());
+
+        // An edit made outside the server, such as in Xcode, drops the placeholder.
+        std::fs::write(&path, catalog("Artikel")).expect("external write");
+        let found = manager.sweep_qa(&options).await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, path);
+        assert_eq!(found[0].1[0].rule, QaRule::Placeholders);
+        loop {
+            match events.recv().await.expect("event") {
+                StoreEvent::QaFindings {
+                    path: event_path,
+                    findings,
+                } => {
+                    assert_eq!(event_path, path);
+                    assert_eq!(findings, found[0].1);
+                    break;
+                }
+                StoreEvent::ExternalChange { .. } => continue,
+                other => panic!("unexpected event {other:?}"),
+            }
+        }
+
+        assert!(
+            manager.sweep_qa(&options).await.is_empty(),
+            "known findings are not reported again"
+        );
+    }
+
     #[tokio::test]
     async fn test_add_substitution_with_empty_value_and_state() {
         let temp = TempStorePath::new("test_substitution_with_state");
@@ -2112,9 +8557,11 @@ mod tests {
         let mut update = TranslationUpdate::default();
         let mut substitutions = IndexMap::new();
 
-        let mut sub_update = SubstitutionUpdate::default();
-        sub_update.value = Some(Some("".to_string()));
-        sub_update.state = Some(Some("new".to_string()));
+        let sub_update = SubstitutionUpdate {
+            value: Some(Some("".to_string())),
+            state: Some(Some("new".to_string())),
+            ..Default::default()
+        };
 
         substitutions.insert("userName".to_string(), Some(sub_update));
         update.substitutions = Some(substitutions);
@@ -2122,7 +8569,8 @@ mod tests {
         let result = store
             .upsert_translation("test.key", "en", update)
             .await
-            .unwrap();
+            .unwrap()
+            .value;
 
         // Verify the substitution was added
         assert!(!result.substitutions.is_empty());
@@ -2171,9 +8619,11 @@ mod tests {
         let mut variations = IndexMap::new();
         let mut plural_cases = IndexMap::new();
 
-        let mut one_update = TranslationUpdate::default();
-        one_update.value = Some(Some("".to_string()));
-        one_update.state = Some(Some("new".to_string()));
+        let one_update = TranslationUpdate {
+            value: Some(Some("".to_string())),
+            state: Some(Some("new".to_string())),
+            ..Default::default()
+        };
 
         plural_cases.insert("one".to_string(), one_update);
         variations.insert("plural".to_string(), plural_cases);
@@ -2182,7 +8632,8 @@ mod tests {
         let result = store
             .upsert_translation("message.count", "en", update)
             .await
-            .unwrap();
+            .unwrap()
+            .value;
 
         // Verify the variation was added
         assert!(!result.variations.is_empty());
@@ -2234,8 +8685,10 @@ mod tests {
         let mut update = TranslationUpdate::default();
         let mut substitutions = IndexMap::new();
 
-        let mut sub_update = SubstitutionUpdate::default();
-        sub_update.value = Some(Some("".to_string()));
+        let sub_update = SubstitutionUpdate {
+            value: Some(Some("".to_string())),
+            ..Default::default()
+        };
         // No state set!
 
         substitutions.insert("userName".to_string(), Some(sub_update));
@@ -2244,7 +8697,8 @@ mod tests {
         let result = store
             .upsert_translation("test.key", "en", update)
             .await
-            .unwrap();
+            .unwrap()
+            .value;
 
         // The substitution should be filtered out because it has no content
         assert!(result.substitutions.is_empty());
@@ -2299,14 +8753,18 @@ mod tests {
         let mut variations = IndexMap::new();
         let mut plural_cases = IndexMap::new();
 
-        let mut one_update = TranslationUpdate::default();
-        one_update.value = Some(Some("".to_string()));
-        one_update.state = Some(Some("new".to_string()));
+        let one_update = TranslationUpdate {
+            value: Some(Some("".to_string())),
+            state: Some(Some("new".to_string())),
+            ..Default::default()
+        };
         plural_cases.insert("one".to_string(), one_update);
 
-        let mut other_update = TranslationUpdate::default();
-        other_update.value = Some(Some("".to_string()));
-        other_update.state = Some(Some("new".to_string()));
+        let other_update = TranslationUpdate {
+            value: Some(Some("".to_string())),
+            state: Some(Some("new".to_string())),
+            ..Default::default()
+        };
         plural_cases.insert("other".to_string(), other_update);
 
         variations.insert("plural".to_string(), plural_cases);
@@ -2318,7 +8776,8 @@ mod tests {
         let result = store
             .upsert_translation("test.key", "en", update)
             .await
-            .unwrap();
+            .unwrap()
+            .value;
 
         // Verify the substitution variations were added
         assert!(!result.substitutions.is_empty());
@@ -2417,10 +8876,14 @@ mod tests {
                 .collect(),
         );
 
-        let result = store
+        let outcome = store
             .upsert_translation("test.key", "en", update)
             .await
             .unwrap();
+        let result = outcome.value;
+        assert_eq!(outcome.warnings.len(), 1);
+        assert_eq!(outcome.warnings[0].path, "variations.device");
+        assert!(outcome.warnings[0].message.contains("plural and device"));
 
         // Verify that only plural remains (device should be removed)
         assert!(result.variations.contains_key("plural"));
@@ -2438,11 +8901,13 @@ mod tests {
         let mut variations = IndexMap::new();
 
         let mut plural_cases = IndexMap::new();
-        let mut one_loc = XcLocalization::default();
-        one_loc.string_unit = Some(XcStringUnit {
-            state: Some("translated".to_string()),
-            value: Some("One".to_string()),
-        });
+        let mut one_loc = XcLocalization {
+            string_unit: Some(XcStringUnit {
+                state: Some("translated".to_string()),
+                value: Some("One".to_string()),
+            }),
+            ..Default::default()
+        };
 
         // Try to add device variation under plural/one (should be rejected)
         let mut device_cases = IndexMap::new();
@@ -2482,10 +8947,17 @@ mod tests {
                 .collect(),
         );
 
-        let result = store
+        let outcome = store
             .upsert_translation("test.key2", "en", update)
             .await
             .unwrap();
+        let result = outcome.value;
+        assert_eq!(outcome.warnings.len(), 1);
+        assert_eq!(
+            outcome.warnings[0].path,
+            "variations.plural.one.variations.device"
+        );
+        assert!(outcome.warnings[0].message.contains("under plural"));
 
         // Verify that device was removed from under plural
         let plural_vars = result.variations.get("plural").unwrap();
@@ -2504,11 +8976,13 @@ mod tests {
         let mut variations = IndexMap::new();
 
         let mut device_cases = IndexMap::new();
-        let mut iphone_loc = XcLocalization::default();
-        iphone_loc.string_unit = Some(XcStringUnit {
-            state: Some("translated".to_string()),
-            value: Some("iPhone".to_string()),
-        });
+        let mut iphone_loc = XcLocalization {
+            string_unit: Some(XcStringUnit {
+                state: Some("translated".to_string()),
+                value: Some("iPhone".to_string()),
+            }),
+            ..Default::default()
+        };
 
         // Try to add another device variation under device/iphone (should be rejected)
         let mut nested_device = IndexMap::new();
@@ -2548,10 +9022,17 @@ mod tests {
                 .collect(),
         );
 
-        let result = store
+        let outcome = store
             .upsert_translation("test.key3", "en", update)
             .await
             .unwrap();
+        let result = outcome.value;
+        assert_eq!(outcome.warnings.len(), 1);
+        assert_eq!(
+            outcome.warnings[0].path,
+            "variations.device.iphone.variations.device"
+        );
+        assert!(outcome.warnings[0].message.contains("another device"));
 
         // Verify that nested device was removed
         let device_vars = result.variations.get("device").unwrap();
@@ -2714,15 +9195,90 @@ mod tests {
         let result = store
             .upsert_translation("test.key4", "en", update)
             .await
+            .unwrap()
+            .value;
+
+        // Verify that plural under device was preserved
+        let device_vars = result.variations.get("device").unwrap();
+        let iphone_var = device_vars.get("iphone").unwrap();
+        assert!(iphone_var.variations.contains_key("plural"));
+        let plural_vars = iphone_var.variations.get("plural").unwrap();
+        assert!(plural_vars.contains_key("one"));
+        assert!(plural_vars.contains_key("other"));
+    }
+
+    #[tokio::test]
+    async fn test_variation_constraints_width() {
+        let tmp = TempStorePath::new("variation_constraints_width");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        let text = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+
+        // Width nests under device, and plural under width.
+        let nested = TranslationUpdate::default().add_variation(
+            "device",
+            "iphone",
+            TranslationUpdate::default().add_width_variation(
+                1,
+                TranslationUpdate::default().add_variation("plural", "other", text("%lld")),
+            ),
+        );
+        let outcome = store
+            .upsert_translation("allowed", "en", nested)
+            .await
+            .unwrap();
+        assert!(outcome.warnings.is_empty(), "{:?}", outcome.warnings);
+
+        // Width doesn't sit alongside plural, nor nest under plural or another width, and
+        // device doesn't nest under width.
+        let invalid = TranslationUpdate::default()
+            .add_variation(
+                "plural",
+                "one",
+                text("One").add_width_variation(1, text("Short one")),
+            )
+            .add_variation("plural", "other", text("Many"))
+            .add_width_variation(
+                1,
+                TranslationUpdate::default()
+                    .add_width_variation(2, text("Nested"))
+                    .add_variation("device", "mac", text("Mac")),
+            );
+        let outcome = store
+            .upsert_translation("invalid", "en", invalid)
+            .await
+            .unwrap();
+        let paths: Vec<_> = outcome
+            .warnings
+            .iter()
+            .map(|warning| warning.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "variations.plural.one.variations.width",
+                "variations.width.1.variations.device",
+                "variations.width.1.variations.width",
+                "variations.width",
+            ]
+        );
+        assert!(outcome.warnings[3].message.contains("alongside plural"));
+        assert!(outcome.value.width_variations().is_empty());
+        assert_eq!(outcome.value.variations["plural"].len(), 2);
+
+        // Setting a device case refuses to drop the width variations it would conflict with.
+        store
+            .upsert_translation(
+                "width.only",
+                "en",
+                TranslationUpdate::default().add_width_variation(1, text("Short")),
+            )
+            .await
             .unwrap();
-
-        // Verify that plural under device was preserved
-        let device_vars = result.variations.get("device").unwrap();
-        let iphone_var = device_vars.get("iphone").unwrap();
-        assert!(iphone_var.variations.contains_key("plural"));
-        let plural_vars = iphone_var.variations.get("plural").unwrap();
-        assert!(plural_vars.contains_key("one"));
-        assert!(plural_vars.contains_key("other"));
+        let err = store
+            .set_device_variant("width.only", "en", "mac", Some("Mac".into()))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("varies by width"), "{err}");
     }
 
     #[tokio::test]
@@ -2760,8 +9316,7 @@ mod tests {
         let result = store
             .get_translation("items.count", "en")
             .await
-            .expect("fetch initial")
-            .expect("translation exists");
+            .expect("fetch initial");
 
         let plural_vars = result.variations.get("plural").expect("has plural");
         assert_eq!(plural_vars.len(), 2);
@@ -2799,8 +9354,7 @@ mod tests {
         let result = store
             .get_translation("items.count", "en")
             .await
-            .expect("fetch after delete")
-            .expect("translation still exists");
+            .expect("fetch after delete");
 
         let plural_vars = result.variations.get("plural").expect("still has plural");
         assert_eq!(
@@ -2843,8 +9397,7 @@ mod tests {
         let placeholder = store
             .get_translation("greeting", "fr")
             .await
-            .expect("lookup succeeds")
-            .expect("placeholder created");
+            .expect("lookup succeeds");
         assert_eq!(placeholder.state.as_deref(), Some(NEEDS_TRANSLATION_STATE));
         assert_eq!(placeholder.value.as_deref(), Some(""));
 
@@ -2862,11 +9415,7 @@ mod tests {
         let languages = store.list_languages().await;
         assert!(languages.contains(&"fr".to_string()));
 
-        let greeting = store
-            .get_translation("greeting", "fr")
-            .await
-            .unwrap()
-            .unwrap();
+        let greeting = store.get_translation("greeting", "fr").await.unwrap();
         assert_eq!(greeting.value.as_deref(), Some("Bonjour"));
         assert_eq!(greeting.state.as_deref(), Some(DEFAULT_TRANSLATION_STATE));
     }
@@ -2890,8 +9439,10 @@ mod tests {
         store.add_language("fr").await.expect("add language");
 
         // Update only the value (no explicit state), simulating the web UI payload.
-        let mut update = TranslationUpdate::default();
-        update.value = Some(Some("Bonjour".into()));
+        let update = TranslationUpdate {
+            value: Some(Some("Bonjour".into())),
+            ..Default::default()
+        };
         store
             .upsert_translation("greeting", "fr", update)
             .await
@@ -2900,8 +9451,7 @@ mod tests {
         let greeting = store
             .get_translation("greeting", "fr")
             .await
-            .expect("fetch translation")
-            .expect("translation exists");
+            .expect("fetch translation");
 
         assert_eq!(greeting.value.as_deref(), Some("Bonjour"));
         assert_eq!(greeting.state.as_deref(), Some(DEFAULT_TRANSLATION_STATE));
@@ -2925,8 +9475,7 @@ mod tests {
         let fetched = store
             .get_translation("welcome", "es")
             .await
-            .expect("fetch translation")
-            .expect("translation exists");
+            .expect("fetch translation");
 
         assert_eq!(fetched.state.as_deref(), Some(NEEDS_TRANSLATION_STATE));
         assert_eq!(fetched.value.as_deref(), Some(""));
@@ -2954,15 +9503,15 @@ mod tests {
             .expect("set state");
 
         assert_eq!(updated.value.as_deref(), Some("Bonjour"));
-        assert_eq!(updated.state.as_deref(), Some("needs-review"));
+        // Spelling variants are saved as the known state.
+        assert_eq!(updated.state.as_deref(), Some("needs_review"));
 
         let fetched = store
             .get_translation("welcome", "fr")
             .await
-            .expect("fetch translation")
-            .expect("translation exists");
+            .expect("fetch translation");
         assert_eq!(fetched.value.as_deref(), Some("Bonjour"));
-        assert_eq!(fetched.state.as_deref(), Some("needs-review"));
+        assert_eq!(fetched.state.as_deref(), Some("needs_review"));
     }
 
     #[tokio::test]
@@ -2990,6 +9539,290 @@ mod tests {
         assert_eq!(placeholder_value, Some(""));
     }
 
+    #[tokio::test]
+    async fn saves_keep_empty_entries_until_cleaned() {
+        let tmp = TempStorePath::new("clean_catalog");
+        let raw = r#"{"sourceLanguage":"en","strings":{"greeting":{"localizations":{"en":{"stringUnit":{"state":"translated","value":"Hello"}},"fr":{"stringUnit":{}}}},"orphan":{}},"version":"1.0"}"#;
+        fs::write(&tmp.file, raw).await.unwrap();
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+
+        let (result, normalized) =
+            track_normalization(store.upsert_translation("greeting", "de", set("Hallo"))).await;
+        result.unwrap();
+        assert!(normalized.is_empty());
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&tmp.file).await.unwrap()).unwrap();
+        assert_eq!(
+            saved["strings"]["greeting"]["localizations"]["fr"],
+            serde_json::json!({})
+        );
+        assert_eq!(saved["strings"]["orphan"], serde_json::json!({}));
+
+        let expected = vec![
+            NormalizedEntry {
+                key: "greeting".into(),
+                removed: false,
+                dropped_languages: vec!["fr".into()],
+            },
+            NormalizedEntry {
+                key: "orphan".into(),
+                removed: true,
+                dropped_languages: Vec::new(),
+            },
+        ];
+        assert_eq!(store.clean(false).await.unwrap(), expected);
+        assert_eq!(store.keys().await, ["greeting", "orphan"]);
+        assert_eq!(store.clean(true).await.unwrap(), expected);
+        assert_eq!(store.keys().await, ["greeting"]);
+        assert!(store.clean(true).await.unwrap().is_empty());
+
+        // Removing a language still reports the keys it leaves empty.
+        store
+            .upsert_translation("farewell", "de", set("Tschüss"))
+            .await
+            .unwrap();
+        let (result, normalized) = track_normalization(store.remove_language("de")).await;
+        result.unwrap();
+        assert_eq!(
+            normalized,
+            vec![NormalizedEntry {
+                key: "farewell".into(),
+                removed: true,
+                dropped_languages: Vec::new(),
+            }]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_edits_all_reach_the_disk() {
+        let tmp = TempStorePath::new("concurrent_edits");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        let edits = (0..32).map(|index| {
+            let store = store.clone();
+            tokio::spawn(async move {
+                store
+                    .upsert_translation(
+                        &format!("key{index}"),
+                        "en",
+                        TranslationUpdate::from_value_state(Some(format!("Value {index}")), None),
+                    )
+                    .await
+                    .unwrap();
+            })
+        });
+        for edit in edits.collect::<Vec<_>>() {
+            edit.await.unwrap();
+        }
+
+        // Saves run concurrently, but an older version never overwrites a newer one.
+        let reopened = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        assert_eq!(reopened.list_records(None).await.len(), 32);
+        assert_eq!(
+            fs::read_to_string(&tmp.file).await.unwrap(),
+            store.to_apple_json().await
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn transactions_and_single_edits_race_to_the_newest_file() {
+        let tmp = TempStorePath::new("transaction_race");
+        let manager = Arc::new(
+            XcStringsStoreManager::new(Some(tmp.file.clone()))
+                .await
+                .expect("create manager"),
+        );
+        let store = manager.default_store().await.expect("default store");
+        let edits = (0..16).map(|index| {
+            let manager = Arc::clone(&manager);
+            let store = store.clone();
+            tokio::spawn(async move {
+                let key = format!("key{index}");
+                let update = TranslationUpdate::from_value_state(Some(key.clone()), None);
+                if index % 2 == 0 {
+                    store.upsert_translation(&key, "en", update).await.unwrap();
+                } else {
+                    let outcome = manager
+                        .apply_transaction(vec![TransactionOperation::Edit {
+                            path: None,
+                            operation: BatchOperation::Upsert {
+                                key,
+                                language: "en".into(),
+                                update: Box::new(update),
+                                comment: None,
+                                position: InsertPosition::Sorted,
+                            },
+                        }])
+                        .await
+                        .unwrap();
+                    assert!(outcome.committed);
+                }
+            })
+        });
+        for edit in edits.collect::<Vec<_>>() {
+            edit.await.unwrap();
+        }
+
+        // A single edit's save still in flight never overwrites a transaction written after it.
+        assert_eq!(store.list_records(None).await.len(), 16);
+        assert_eq!(
+            fs::read_to_string(&tmp.file).await.unwrap(),
+            store.to_apple_json().await
+        );
+    }
+
+    #[tokio::test]
+    async fn reloads_wait_for_an_edit_in_progress() {
+        let tmp = TempStorePath::new("reload_during_edit");
+        fs::write(
+            &tmp.file,
+            r#"{"sourceLanguage":"en","version":"1.0","strings":{}}"#,
+        )
+        .await
+        .unwrap();
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        let edit = store.lock_edit().await.unwrap();
+        let reload = tokio::spawn({
+            let store = store.clone();
+            async move { store.reload().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(
+            !reload.is_finished(),
+            "reload replaced the catalog mid-edit"
+        );
+
+        drop(edit);
+        reload.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn saves_keep_escaping_and_line_endings_of_the_file() {
+        let tmp = TempStorePath::new("source_style");
+        let original = "{\r\n  \"sourceLanguage\" : \"en\",\r\n  \"strings\" : {\r\n    \"caf\\u00e9\" : {\r\n\r\n    }\r\n  },\r\n  \"version\" : \"1.0\"\r\n}";
+        fs::write(&tmp.file, original).await.unwrap();
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+
+        store
+            .upsert_translation(
+                "café",
+                "fr",
+                TranslationUpdate::from_value_state(Some("Café".into()), None),
+            )
+            .await
+            .unwrap();
+        let raw = fs::read_to_string(&tmp.file).await.unwrap();
+        assert!(raw.contains("\"caf\\u00e9\" : {\r\n"), "{raw}");
+        assert!(raw.contains("\"Caf\\u00e9\""), "{raw}");
+        assert!(!raw.replace("\r\n", "").contains('\n'));
+        assert!(!store.reformat(false).await.unwrap());
+
+        // An external rewrite in the default style is kept from then on.
+        let plain = raw.replace("\r\n", "\n").replace("\\u00e9", "é");
+        fs::write(&tmp.file, &plain).await.unwrap();
+        store.reload().await.unwrap();
+        assert!(!store.reformat(false).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn repair_reports_and_fixes_structural_problems() {
+        let tmp = TempStorePath::new("repair_catalog");
+        let original = r#"{
+  "sourceLanguage" : "en",
+  "strings" : {
+    "greeting" : {
+      "localizations" : {
+        "de" : { "stringUnit" : { "state" : "translated", "value" : "Hallo" } },
+        "de" : { "stringUnit" : { "state" : "translated", "value" : "Hi" } },
+        "fr" : { }
+      }
+    },
+    "items" : {
+      "localizations" : {
+        "en" : {
+          "stringUnit" : { "state" : "new", "value" : "%@ has %#@count@" },
+          "substitutions" : {
+            "count" : {
+              "argNum" : 5,
+              "formatSpecifier" : "lld",
+              "variations" : { "plural" : {
+                "other" : { "stringUnit" : { "state" : "new", "value" : "%arg items" } }
+              } }
+            },
+            "stale" : { "argNum" : 1, "formatSpecifier" : "lld" }
+          }
+        },
+        "fr" : {
+          "variations" : { "plural" : {
+            "one" : { "variations" : { "device" : {
+              "iphone" : { "stringUnit" : { "state" : "translated", "value" : "un" } }
+            } } },
+            "other" : { "stringUnit" : { "state" : "translated", "value" : "plusieurs" } }
+          } }
+        }
+      }
+    },
+    "unused" : { }
+  },
+  "version" : "1.0"
+}"#;
+        fs::write(&tmp.file, original).await.unwrap();
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+
+        let report = store.repair(false).await.unwrap();
+        assert!(!report.repaired);
+        let issues: Vec<_> = report
+            .issues
+            .iter()
+            .map(|issue| (issue.kind, issue.path.as_str(), issue.fixable))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                (
+                    RepairKind::DuplicateKey,
+                    "/strings/greeting/localizations/de",
+                    true
+                ),
+                (
+                    RepairKind::InvalidArgNum,
+                    "/strings/items/localizations/en/substitutions/count",
+                    true
+                ),
+                (
+                    RepairKind::UnusedSubstitution,
+                    "/strings/items/localizations/en/substitutions/stale",
+                    true
+                ),
+                (
+                    RepairKind::InvalidVariation,
+                    "/strings/items/localizations/fr/variations/plural/one/variations/device",
+                    true
+                ),
+                (
+                    RepairKind::EmptyLocalization,
+                    "/strings/greeting/localizations/fr",
+                    true
+                ),
+                (RepairKind::EmptyEntry, "/strings/unused", true),
+            ]
+        );
+        assert_eq!(fs::read_to_string(&tmp.file).await.unwrap(), original);
+
+        let report = store.repair(true).await.unwrap();
+        assert!(report.repaired);
+        let raw = fs::read_to_string(&tmp.file).await.unwrap();
+        assert!(
+            !raw.contains("stale") && !raw.contains("\"unused\""),
+            "{raw}"
+        );
+        assert!(raw.contains("\"argNum\" : 2"), "{raw}");
+        let greeting = store.get_translation("greeting", "de").await.unwrap();
+        assert_eq!(greeting.value.as_deref(), Some("Hi"));
+        let report = store.repair(false).await.unwrap();
+        assert!(report.is_clean(), "{report:?}");
+    }
+
     #[tokio::test]
     async fn add_language_to_empty_file_succeeds_but_not_visible() {
         let tmp = TempStorePath::new("add_language_empty");
@@ -3063,6 +9896,42 @@ mod tests {
         assert!(matches!(result, Err(StoreError::InvalidLanguage(_))));
     }
 
+    #[tokio::test]
+    async fn language_codes_are_validated_and_normalized() {
+        let tmp = TempStorePath::new("add_language_normalized");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        store
+            .upsert_translation(
+                "greeting",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(store.add_language("pt_br").await.unwrap(), "pt-BR");
+        assert!(matches!(
+            store.add_language("pt-BR").await,
+            Err(StoreError::LanguageExists(_))
+        ));
+        let err = store.add_language("Frnech").await.unwrap_err();
+        assert!(
+            matches!(&err, StoreError::InvalidLanguageCode { suggestions, .. } if suggestions == &["fr"]),
+            "{err:?}"
+        );
+        assert!(err.to_string().contains("did you mean fr?"), "{err}");
+
+        assert_eq!(
+            store.update_language("pt-BR", "PT_pt").await.unwrap(),
+            "pt-PT"
+        );
+        assert!(matches!(
+            store.update_language("pt-PT", "en_UK_x").await,
+            Err(StoreError::InvalidLanguageCode { .. })
+        ));
+        assert!(store.list_languages().await.contains(&"pt-PT".to_string()));
+    }
+
     #[tokio::test]
     async fn remove_language_deletes_localizations() {
         let tmp = TempStorePath::new("remove_language");
@@ -3105,10 +9974,10 @@ mod tests {
         assert!(languages.contains(&"en".to_string()));
         assert!(languages.contains(&"es".to_string()));
 
-        let greeting_fr = store.get_translation("greeting", "fr").await.unwrap();
+        let greeting_fr = store.get_translation("greeting", "fr").await.ok();
         assert!(greeting_fr.is_none());
 
-        let greeting_en = store.get_translation("greeting", "en").await.unwrap();
+        let greeting_en = store.get_translation("greeting", "en").await.ok();
         assert!(greeting_en.is_some());
         assert_eq!(greeting_en.unwrap().value.as_deref(), Some("Hello"));
     }
@@ -3166,10 +10035,10 @@ mod tests {
         assert!(!languages.contains(&"fr".to_string()));
         assert!(languages.contains(&"fr-FR".to_string()));
 
-        let greeting_fr = store.get_translation("greeting", "fr").await.unwrap();
+        let greeting_fr = store.get_translation("greeting", "fr").await.ok();
         assert!(greeting_fr.is_none());
 
-        let greeting_fr_fr = store.get_translation("greeting", "fr-FR").await.unwrap();
+        let greeting_fr_fr = store.get_translation("greeting", "fr-FR").await.ok();
         assert!(greeting_fr_fr.is_some());
         assert_eq!(greeting_fr_fr.unwrap().value.as_deref(), Some("Bonjour"));
     }
@@ -3191,37 +10060,426 @@ mod tests {
         let tmp = TempStorePath::new("update_language_missing");
         let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
 
-        let result = store.update_language("fr", "fr-FR").await;
-        assert!(matches!(result, Err(StoreError::LanguageMissing(_))));
+        let result = store.update_language("fr", "fr-FR").await;
+        assert!(matches!(result, Err(StoreError::LanguageMissing(_))));
+    }
+
+    #[tokio::test]
+    async fn update_language_fails_if_new_exists() {
+        let tmp = TempStorePath::new("update_language_exists");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+
+        // Add translations
+        store
+            .upsert_translation(
+                "greeting",
+                "fr",
+                TranslationUpdate::from_value_state(Some("Bonjour".into()), None),
+            )
+            .await
+            .unwrap();
+
+        store
+            .upsert_translation(
+                "greeting",
+                "es",
+                TranslationUpdate::from_value_state(Some("Hola".into()), None),
+            )
+            .await
+            .unwrap();
+
+        // Try to rename French to Spanish (which already exists)
+        let result = store.update_language("fr", "es").await;
+        assert!(matches!(result, Err(StoreError::LanguageExists(_))));
+    }
+
+    #[tokio::test]
+    async fn key_namespaces_group_keys_by_prefix() {
+        let tmp = TempStorePath::new("key_namespaces");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        for (key, language, value) in [
+            ("checkout.title", "en", "Checkout"),
+            ("checkout.title", "fr", "Paiement"),
+            ("checkout.payment.card", "en", "Card"),
+            ("checkout.payment.cash", "en", "Cash"),
+            ("settings.title", "en", "Settings"),
+            ("Done.", "en", "Done."),
+        ] {
+            store
+                .upsert_translation(
+                    key,
+                    language,
+                    TranslationUpdate::from_value_state(Some(value.into()), None),
+                )
+                .await
+                .unwrap();
+        }
+
+        let root = store.key_namespaces().await;
+        assert_eq!((root.total_keys, root.direct_keys), (5, 1));
+        let names: Vec<_> = root.children.iter().map(|n| n.namespace.as_str()).collect();
+        assert_eq!(names, vec!["checkout", "settings"]);
+        let checkout = &root.children[0];
+        assert_eq!((checkout.total_keys, checkout.direct_keys), (3, 1));
+        assert_eq!(checkout.completion["en"], 100.0);
+        assert!((checkout.completion["fr"] - 100.0 / 3.0).abs() < 1e-9);
+        assert_eq!(checkout.children[0].namespace, "checkout.payment");
+        assert_eq!(checkout.children[0].completion["fr"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn resolved_lookups_follow_locale_fallback() {
+        let tmp = TempStorePath::new("resolved_lookups");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        for (key, language, value) in [
+            ("greeting", "en", "Hello"),
+            ("greeting", "fr", "Bonjour"),
+            ("greeting", "fr-CA", "Allô"),
+            ("farewell", "en", "Bye"),
+            ("farewell", "fr", "Au revoir"),
+            ("farewell", "fr-CA", "Au revoir"),
+            ("color", "en-GB", "Colour"),
+        ] {
+            store
+                .upsert_translation(
+                    key,
+                    language,
+                    TranslationUpdate::from_value_state(Some(value.into()), None),
+                )
+                .await
+                .unwrap();
+        }
+        store.add_language("de").await.unwrap();
+
+        let resolved = store
+            .get_translation_resolved("greeting", "fr-CA")
+            .await
+            .unwrap();
+        assert_eq!(resolved.resolved_language, "fr-CA");
+        assert!(!resolved.fallback);
+        let resolved = store
+            .get_translation_resolved("farewell", "fr-BE")
+            .await
+            .unwrap();
+        assert_eq!(resolved.resolved_language, "fr");
+        assert_eq!(resolved.value.value.as_deref(), Some("Au revoir"));
+        // The `de` placeholder is skipped, and a key without an English value shows itself.
+        let resolved = store.get_translation_resolved("color", "de").await.unwrap();
+        assert_eq!(resolved.resolved_language, "en");
+        assert_eq!(resolved.value.value.as_deref(), Some("color"));
+        assert!(matches!(
+            store.get_translation_resolved("missing", "fr").await,
+            Err(StoreError::KeyMissing(_))
+        ));
+
+        let reports = store.regional_overrides().await;
+        let summary: Vec<_> = reports
+            .iter()
+            .map(|report| {
+                (
+                    report.language.as_str(),
+                    report.parent.as_str(),
+                    report.overridden_keys.clone(),
+                    report.redundant_keys.clone(),
+                    report.inherited,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("en-GB", "en", vec!["color".to_string()], vec![], 2),
+                (
+                    "fr-CA",
+                    "fr",
+                    vec!["greeting".to_string()],
+                    vec!["farewell".to_string()],
+                    1
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn device_variants_validate_devices_and_nesting() {
+        let tmp = TempStorePath::new("device_variants");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        for (key, value) in [("tap", "Tap"), ("files", "Files")] {
+            store
+                .upsert_translation(
+                    key,
+                    "en",
+                    TranslationUpdate::from_value_state(Some(value.into()), None),
+                )
+                .await
+                .unwrap();
+        }
+        store
+            .upsert_translation(
+                "files",
+                "en",
+                TranslationUpdate::from_value_state(None, None).add_variation(
+                    "plural",
+                    "other",
+                    TranslationUpdate::from_value_state(Some("{count} files".into()), None),
+                ),
+            )
+            .await
+            .unwrap();
+
+        store
+            .set_device_variant("tap", "en", "iPhone", Some("Tap".into()))
+            .await
+            .unwrap();
+        let updated = store
+            .set_device_variant("tap", "en", "mac", Some("Click".into()))
+            .await
+            .unwrap();
+        assert_eq!(
+            updated.variations["device"]["mac"].value.as_deref(),
+            Some("Click")
+        );
+        assert!(matches!(
+            store
+                .set_device_variant("tap", "en", "android", Some("Tap".into()))
+                .await,
+            Err(StoreError::InvalidDevice(device)) if device == "android"
+        ));
+        assert!(matches!(
+            store
+                .set_device_variant("files", "en", "mac", Some("Files".into()))
+                .await,
+            Err(StoreError::InvalidVariation(_))
+        ));
+        assert!(matches!(
+            store.set_device_variant("missing", "en", "mac", None).await,
+            Err(StoreError::KeyMissing(_))
+        ));
+
+        store
+            .set_device_variant("tap", "en", "iphone", None)
+            .await
+            .unwrap();
+        let variants = store.list_device_variants("tap").await.unwrap();
+        let devices: Vec<_> = variants["en"].keys().map(String::as_str).collect();
+        assert_eq!(devices, vec!["mac"]);
+        assert!(store
+            .list_device_variants("files")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn set_metadata_rewrites_the_comment_metadata_line() {
+        let tmp = TempStorePath::new("set_metadata");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        store
+            .set_comment("login", Some("Login button\ntone=casual".into()))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_record("login").await.unwrap().metadata,
+            Some(KeyMetadata {
+                tone: Some("casual".into()),
+                ..KeyMetadata::default()
+            })
+        );
+
+        let metadata = KeyMetadata {
+            max_length: Some(20),
+            screenshot: Some("login.png".into()),
+            ..KeyMetadata::default()
+        };
+        let comment = store.set_metadata("login", &metadata).await.unwrap();
+        assert_eq!(
+            comment.as_deref(),
+            Some("Login button\nmax_length=20; screenshot=login.png")
+        );
+        assert_eq!(
+            store.get_record("login").await.unwrap().metadata,
+            Some(metadata)
+        );
+
+        let invalid = KeyMetadata {
+            tone: Some("formal;".into()),
+            ..KeyMetadata::default()
+        };
+        assert!(matches!(
+            store.set_metadata("login", &invalid).await,
+            Err(StoreError::InvalidMetadata(_))
+        ));
+        assert!(matches!(
+            store.set_metadata("missing", &KeyMetadata::default()).await,
+            Err(StoreError::KeyMissing(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn source_changes_send_translations_back_to_review() {
+        let tmp = TempStorePath::new("review_on_source_change");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .unwrap()
+            .with_review_on_source_change(true);
+        let set = |value: &str, state: &str| {
+            TranslationUpdate::from_value_state(Some(value.into()), Some(state.into()))
+        };
+        let state = |language: &'static str| {
+            let store = store.clone();
+            async move {
+                store
+                    .get_translation("greeting", language)
+                    .await
+                    .unwrap()
+                    .state
+            }
+        };
+
+        let created = store
+            .upsert_translation("greeting", "en", set("Hello", "translated"))
+            .await
+            .unwrap();
+        assert!(created.needs_review.is_empty());
+        for (language, value, state) in [
+            ("de", "Hallo", "translated"),
+            ("fr", "Bonjour", "reviewed"),
+            ("it", "Ciao", "new"),
+            ("es", "Hola", "translated"),
+        ] {
+            store
+                .upsert_translation("greeting", language, set(value, state))
+                .await
+                .unwrap();
+        }
+        store.freeze_language("es", true).await.unwrap();
+
+        // Changing only the source's state isn't a rewording.
+        let restated = store
+            .upsert_translation("greeting", "en", set("Hello", "reviewed"))
+            .await
+            .unwrap();
+        assert!(restated.needs_review.is_empty());
+
+        let reworded = store
+            .upsert_translation("greeting", "en", set("Hi there", "translated"))
+            .await
+            .unwrap();
+        assert_eq!(reworded.needs_review, ["de", "fr"]);
+        assert_eq!(state("de").await.as_deref(), Some("needs_review"));
+        assert_eq!(state("fr").await.as_deref(), Some("needs_review"));
+        assert_eq!(state("it").await.as_deref(), Some("new"));
+        assert_eq!(state("es").await.as_deref(), Some("translated"));
+
+        store
+            .upsert_translation("greeting", "de", set("Hallo zusammen", "translated"))
+            .await
+            .unwrap();
+        let outcome = store
+            .apply_batch(vec![BatchOperation::Upsert {
+                key: "greeting".into(),
+                language: "en".into(),
+                update: Box::new(set("Hi", "translated")),
+                comment: None,
+                position: InsertPosition::End,
+            }])
+            .await
+            .unwrap();
+        let applied = outcome.results[0].as_ref().unwrap();
+        assert_eq!(applied.needs_review, ["de"]);
+        assert_eq!(state("de").await.as_deref(), Some("needs_review"));
+
+        // Off by default.
+        let plain = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        plain
+            .upsert_translation("greeting", "de", set("Hallo", "translated"))
+            .await
+            .unwrap();
+        let reworded = plain
+            .upsert_translation("greeting", "en", set("Hey", "translated"))
+            .await
+            .unwrap();
+        assert!(reworded.needs_review.is_empty());
     }
 
     #[tokio::test]
-    async fn update_language_fails_if_new_exists() {
-        let tmp = TempStorePath::new("update_language_exists");
-        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+    async fn review_workflow_enforces_state_order() {
+        let tmp = TempStorePath::new("review_workflow");
+        let store = XcStringsStore::load_or_create(&tmp.file)
+            .await
+            .unwrap()
+            .with_review_workflow(true);
+        let set = |value: &str, state: &str| {
+            TranslationUpdate::from_value_state(Some(value.into()), Some(state.into()))
+        };
+        let is_violation = |result: Result<_, StoreError>| {
+            matches!(result, Err(StoreError::WorkflowViolation { .. }))
+        };
 
-        // Add translations
         store
-            .upsert_translation(
-                "greeting",
-                "fr",
-                TranslationUpdate::from_value_state(Some("Bonjour".into()), None),
-            )
+            .upsert_translation("greeting", "fr", set("Bonjour", "translated"))
             .await
             .unwrap();
-
+        assert!(is_violation(
+            store
+                .upsert_translation("greeting", "fr", set("Bonjour", "reviewed"))
+                .await
+                .map(|_| ())
+        ));
         store
-            .upsert_translation(
-                "greeting",
-                "es",
-                TranslationUpdate::from_value_state(Some("Hola".into()), None),
-            )
+            .set_translation_state("greeting", "fr", Some("needs_review".into()))
             .await
             .unwrap();
+        assert!(is_violation(
+            store
+                .upsert_translation("greeting", "fr", set("Salut", "needs_review"))
+                .await
+                .map(|_| ())
+        ));
+        let search = FindReplace {
+            find: "Bonjour".into(),
+            replace: "Salut".into(),
+            ..FindReplace::default()
+        };
+        assert!(is_violation(
+            store.find_replace(&search, true).await.map(|_| ())
+        ));
 
-        // Try to rename French to Spanish (which already exists)
-        let result = store.update_language("fr", "es").await;
-        assert!(matches!(result, Err(StoreError::LanguageExists(_))));
+        let approved = store.approve_translation("greeting", "fr").await.unwrap();
+        assert_eq!(approved.state.as_deref(), Some("reviewed"));
+        let outcome = store
+            .apply_batch(vec![BatchOperation::Upsert {
+                key: "greeting".into(),
+                language: "fr".into(),
+                update: Box::new(set("Salut", "translated")),
+                comment: None,
+                position: InsertPosition::End,
+            }])
+            .await
+            .unwrap();
+        assert!(!outcome.committed);
+        assert!(is_violation(
+            store
+                .reject_translation("greeting", "fr", " ")
+                .await
+                .map(|_| ())
+        ));
+
+        let rejected = store
+            .reject_translation("greeting", "fr", "Too formal")
+            .await
+            .unwrap();
+        assert_eq!(rejected.state.as_deref(), Some("new"));
+        store
+            .upsert_translation("greeting", "fr", set("Salut", "translated"))
+            .await
+            .unwrap();
+        assert!(is_violation(
+            store
+                .approve_translation("greeting", "fr")
+                .await
+                .map(|_| ())
+        ));
     }
 
     #[tokio::test]
@@ -3244,7 +10502,7 @@ mod tests {
         assert!(result.is_ok());
 
         // Verify nothing changed
-        let greeting_fr = store.get_translation("greeting", "fr").await.unwrap();
+        let greeting_fr = store.get_translation("greeting", "fr").await.ok();
         assert!(greeting_fr.is_some());
         assert_eq!(greeting_fr.unwrap().value.as_deref(), Some("Bonjour"));
     }
@@ -3357,6 +10615,40 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn list_untranslated_skips_do_not_translate_keys() {
+        let tmp = TempStorePath::new("list_untranslated_do_not_translate");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+        store
+            .upsert_translation("greeting", "en", set("Hello"))
+            .await
+            .unwrap();
+        store
+            .upsert_translation("brand", "en", set("Acme"))
+            .await
+            .unwrap();
+        store
+            .upsert_translation("greeting", "fr", set("Bonjour"))
+            .await
+            .unwrap();
+        store
+            .set_should_translate("brand", Some(false))
+            .await
+            .unwrap();
+
+        let untranslated = store.list_untranslated().await;
+        assert!(untranslated.get("fr").is_none_or(Vec::is_empty));
+        assert_eq!(store.get_translation_percentages().await["fr"], 100.0);
+
+        let untranslated = store.list_untranslated_with(true).await;
+        assert_eq!(untranslated["fr"], ["brand"]);
+        assert_eq!(
+            store.get_translation_percentages_with(true).await["fr"],
+            50.0
+        );
+    }
+
     #[tokio::test]
     async fn list_untranslated_with_no_translations() {
         let tmp = TempStorePath::new("list_untranslated_none");
@@ -3697,4 +10989,515 @@ mod tests {
         let fr_percentage = percentages.get("fr").unwrap();
         assert_eq!(*fr_percentage, 50.0);
     }
+
+    #[tokio::test]
+    async fn assign_validates_targets_and_writes_a_sidecar() {
+        let tmp = TempStorePath::new("assign");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        store
+            .upsert_translation(
+                "login.title",
+                "fr",
+                TranslationUpdate::from_value_state(Some("Connexion".into()), None),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            store
+                .assign(&["missing".to_string()], &[], Some("alice"))
+                .await,
+            Err(StoreError::KeyMissing(key)) if key == "missing"
+        ));
+        assert!(matches!(
+            store.assign(&[], &["de".to_string()], Some("alice")).await,
+            Err(StoreError::LanguageMissing(language)) if language == "de"
+        ));
+
+        store
+            .assign(
+                &["login.title".to_string()],
+                &["fr".to_string()],
+                Some("alice"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            store.assignments_path(),
+            tmp.dir.join("Localizable.assignments.json")
+        );
+        let assignments = store.assignments().await.unwrap();
+        assert_eq!(assignments.keys["login.title"], "alice");
+        assert_eq!(assignments.owners["fr"], "alice");
+
+        let cleared = store
+            .assign(&["login.title".to_string()], &[], Some("  "))
+            .await
+            .unwrap();
+        assert!(cleared.keys.is_empty());
+        assert_eq!(cleared.owners["fr"], "alice");
+    }
+
+    #[tokio::test]
+    async fn saves_track_when_keys_and_translations_changed() {
+        let tmp = TempStorePath::new("modified_index");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+        let start = chrono::Utc::now() - chrono::Duration::seconds(1);
+        store
+            .upsert_translation("greeting", "en", set("Hello"))
+            .await
+            .unwrap();
+        store
+            .upsert_translation("greeting", "fr", set("Bonjour"))
+            .await
+            .unwrap();
+        store
+            .upsert_translation("farewell", "en", set("Bye"))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.modified_path(),
+            tmp.dir.join("Localizable.modified.json")
+        );
+        assert!(store.modified_path().exists());
+
+        let record = store.get_record("greeting").await.unwrap();
+        let modified = record.modified.expect("tracked");
+        assert!(modified.is_since(start));
+        assert_eq!(modified.languages.keys().collect::<Vec<_>>(), ["en", "fr"]);
+        let fr = modified.languages["fr"].clone();
+
+        store.rename_key("greeting", "welcome").await.unwrap();
+        store.delete_key("farewell").await.unwrap();
+        store.update_language("fr", "fr-CA").await.unwrap();
+        let reopened = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        assert!(reopened.modified("farewell").is_none());
+        let welcome = reopened.modified("welcome").expect("renamed");
+        assert_eq!(welcome.languages["fr-CA"], fr);
+        let summaries = reopened.list_summaries(None).await;
+        assert_eq!(summaries[0].modified.as_deref(), Some(welcome.at.as_str()));
+    }
+
+    #[tokio::test]
+    async fn frozen_languages_refuse_edits_until_forced() {
+        let tmp = TempStorePath::new("frozen");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+        store
+            .upsert_translation("greeting", "it", set("Ciao"))
+            .await
+            .unwrap();
+        store
+            .upsert_translation("farewell", "en", set("Bye"))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            store.freeze_language("it", false).await,
+            Err(StoreError::LanguageIncomplete { keys, .. }) if keys == ["farewell"]
+        ));
+        store
+            .upsert_translation("farewell", "it", set("Ciao ciao"))
+            .await
+            .unwrap();
+        assert_eq!(store.freeze_language("it", false).await.unwrap(), ["it"]);
+        assert!(store.frozen_path().exists());
+
+        let is_frozen = |result: Result<_, StoreError>| matches!(result, Err(StoreError::LanguageFrozen(language)) if language == "it");
+        assert!(is_frozen(
+            store
+                .upsert_translation("greeting", "it", set("Salve"))
+                .await
+                .map(|_| ())
+        ));
+        assert!(is_frozen(store.delete_translation("greeting", "it").await));
+        assert!(is_frozen(store.remove_language("it").await));
+        let batch = store
+            .apply_batch(vec![BatchOperation::Upsert {
+                key: "greeting".to_string(),
+                language: "it".to_string(),
+                update: Box::new(set("Salve")),
+                comment: None,
+                position: InsertPosition::End,
+            }])
+            .await
+            .unwrap();
+        assert!(!batch.committed);
+        store
+            .upsert_translation("greeting", "en", set("Hello"))
+            .await
+            .unwrap();
+
+        let reopened = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        assert_eq!(reopened.frozen_languages(), ["it"]);
+        reopened
+            .forcing()
+            .upsert_translation("greeting", "it", set("Salve"))
+            .await
+            .unwrap();
+
+        assert!(store.unfreeze_language("it").await.unwrap().is_empty());
+        assert!(!store.frozen_path().exists());
+        store.delete_translation("greeting", "it").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn frozen_languages_refuse_key_deletes_and_renames_until_forced() {
+        let tmp = TempStorePath::new("frozen_keys");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+        for (key, language, value) in [
+            ("greeting", "en", "Hello"),
+            ("greeting", "it", "Ciao"),
+            ("menu.open", "en", "Open"),
+            ("menu.open", "it", "Apri"),
+            ("draft", "en", "Draft"),
+        ] {
+            store
+                .upsert_translation(key, language, set(value))
+                .await
+                .unwrap();
+        }
+        store.freeze_language("it", true).await.unwrap();
+
+        let is_frozen = |result: Result<_, StoreError>| matches!(result, Err(StoreError::LanguageFrozen(language)) if language == "it");
+        assert!(is_frozen(store.delete_key("greeting").await));
+        assert!(is_frozen(store.rename_key("greeting", "hello").await));
+        assert!(is_frozen(
+            store
+                .rename_namespace("menu", "nav", true)
+                .await
+                .map(|_| ())
+        ));
+        let delete = |key: &str| {
+            vec![BatchOperation::DeleteKey {
+                key: key.to_string(),
+            }]
+        };
+        let batch = store.apply_batch(delete("greeting")).await.unwrap();
+        assert!(!batch.committed);
+        assert!(is_frozen(
+            batch.results.into_iter().next().unwrap().map(|_| ())
+        ));
+        assert_eq!(store.keys().await, ["greeting", "menu.open", "draft"]);
+
+        // Keys without a frozen translation stay editable.
+        store.rename_key("draft", "draft.title").await.unwrap();
+        let forced = store.forcing();
+        assert!(
+            forced
+                .apply_batch(delete("greeting"))
+                .await
+                .unwrap()
+                .committed
+        );
+        forced.rename_key("menu.open", "nav.open").await.unwrap();
+        forced.delete_key("nav.open").await.unwrap();
+        assert_eq!(store.keys().await, ["draft.title"]);
+    }
+
+    #[cfg(feature = "git")]
+    #[tokio::test]
+    async fn key_history_lists_commits_that_changed_the_translation() {
+        let tmp = TempStorePath::new("history");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        assert!(matches!(
+            store.key_history("greeting", "de", 10).await,
+            Err(StoreError::NotInGitRepository(_))
+        ));
+
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .current_dir(&tmp.dir)
+                .args(["-c", "user.name=Ada", "-c", "user.email=ada@example.com"])
+                .args(args)
+                .output()
+                .expect("run git")
+                .status;
+            assert!(status.success(), "git {args:?} failed");
+        };
+        let commit = |message: &str| {
+            git(&["add", "-A"]);
+            git(&["commit", "-q", "-m", message]);
+        };
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+        git(&["init", "-q"]);
+        store
+            .upsert_translation("greeting", "de", set("Hallo"))
+            .await
+            .unwrap();
+        commit("Add greeting");
+        store
+            .upsert_translation("greeting", "fr", set("Bonjour"))
+            .await
+            .unwrap();
+        commit("Add French");
+        store
+            .upsert_translation("greeting", "de", set("Guten Tag"))
+            .await
+            .unwrap();
+        commit("Reword German greeting");
+
+        let history = store.key_history("greeting", "de", 10).await.unwrap();
+        assert!(!history.uncommitted);
+        let summaries: Vec<_> = history
+            .changes
+            .iter()
+            .map(|change| change.summary.as_str())
+            .collect();
+        assert_eq!(summaries, ["Reword German greeting", "Add greeting"]);
+        assert_eq!(history.changes[0].author, "Ada");
+        assert_eq!(
+            history.changes[0]
+                .value
+                .as_ref()
+                .and_then(|value| value.value.as_deref()),
+            Some("Guten Tag")
+        );
+
+        store.delete_translation("greeting", "de").await.unwrap();
+        let history = store.key_history("greeting", "de", 1).await.unwrap();
+        assert!(history.uncommitted);
+        assert!(history.current.is_none());
+        assert_eq!(history.changes.len(), 1);
+    }
+
+    #[cfg(feature = "git")]
+    #[tokio::test]
+    async fn list_outdated_finds_translations_older_than_their_source() {
+        let tmp = TempStorePath::new("outdated");
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .current_dir(&tmp.dir)
+                .args(["-c", "user.name=Ada", "-c", "user.email=ada@example.com"])
+                .args(args)
+                .output()
+                .expect("run git")
+                .status;
+            assert!(status.success(), "git {args:?} failed");
+        };
+        let commit = |message: &str| {
+            git(&["add", "-A"]);
+            git(&["commit", "-q", "-m", message]);
+        };
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+        git(&["init", "-q"]);
+        for (key, en, de) in [
+            ("greeting", "Hello", "Hallo"),
+            ("farewell", "Goodbye", "Tschüss"),
+            ("thanks", "Thanks", "Danke"),
+        ] {
+            store.upsert_translation(key, "en", set(en)).await.unwrap();
+            store.upsert_translation(key, "de", set(de)).await.unwrap();
+        }
+        commit("Add strings");
+        store
+            .upsert_translation("greeting", "en", set("Hi there"))
+            .await
+            .unwrap();
+        store
+            .upsert_translation("farewell", "en", set("See you"))
+            .await
+            .unwrap();
+        store
+            .upsert_translation("farewell", "de", set("Bis bald"))
+            .await
+            .unwrap();
+        commit("Reword greetings");
+        // Reviewing without rewording doesn't count as keeping up.
+        store
+            .set_translation_state("greeting", "de", Some("reviewed".into()))
+            .await
+            .unwrap();
+        commit("Review German");
+        store
+            .upsert_translation("thanks", "en", set("Thank you"))
+            .await
+            .unwrap();
+
+        let report = store.list_outdated("de", 10).await.unwrap();
+        assert_eq!(report.source_language, "en");
+        assert_eq!(report.commits, 3);
+        let keys: Vec<_> = report
+            .outdated
+            .iter()
+            .map(|item| item.key.as_str())
+            .collect();
+        assert_eq!(keys, ["greeting", "thanks"]);
+        let greeting = &report.outdated[0];
+        assert_eq!(greeting.state.as_deref(), Some("reviewed"));
+        assert_eq!(
+            greeting
+                .source_changed
+                .as_ref()
+                .map(|commit| commit.summary.as_str()),
+            Some("Reword greetings")
+        );
+        assert_eq!(
+            greeting
+                .translation_changed
+                .as_ref()
+                .map(|commit| commit.summary.as_str()),
+            Some("Add strings")
+        );
+        // An uncommitted source edit is the newest change.
+        assert!(report.outdated[1].source_changed.is_none());
+
+        assert!(matches!(
+            store.list_outdated("en", 10).await,
+            Err(StoreError::InvalidLanguage(_))
+        ));
+        assert!(matches!(
+            store.list_outdated("fr", 10).await,
+            Err(StoreError::LanguageMissing(_))
+        ));
+    }
+
+    #[cfg(feature = "git")]
+    #[tokio::test]
+    async fn git_auto_commit_commits_recorded_changes() {
+        let tmp = TempStorePath::new("auto_commit");
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .current_dir(&tmp.dir)
+                .args(args)
+                .output()
+                .expect("run git");
+            assert!(output.status.success(), "git {args:?} failed");
+            String::from_utf8(output.stdout).unwrap()
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.name", "Ada"]);
+        git(&["config", "user.email", "ada@example.com"]);
+        std::fs::write(tmp.dir.join("notes.txt"), "staged by the user").unwrap();
+        git(&["add", "notes.txt"]);
+
+        let manager = XcStringsStoreManager::new(Some(tmp.file.clone()))
+            .await
+            .unwrap()
+            .with_git_auto_commit(true);
+        let store = manager.store_for(None).await.unwrap();
+        store
+            .upsert_translation(
+                "greeting",
+                "de",
+                TranslationUpdate::from_value_state(Some("Hallo".into()), None),
+            )
+            .await
+            .unwrap();
+        store
+            .assign(&["greeting".to_string()], &[], Some("bruno"))
+            .await
+            .unwrap();
+        let mut entry = AuditEntry::new("stdio", "upsert_translation");
+        entry.client = Some("agent-a".to_string());
+        entry.path = Some(tmp.file.to_string_lossy().to_string());
+        entry.details = serde_json::json!({ "key": "greeting", "language": "de" });
+        manager.record_change(&entry).await;
+
+        let message = git(&["log", "-1", "--format=%B"]);
+        assert!(message.starts_with("upsert_translation: greeting\n"));
+        assert!(message.contains("Client: agent-a"));
+        let committed = git(&["show", "--name-only", "--format=", "HEAD"]);
+        assert_eq!(
+            committed.lines().collect::<Vec<_>>(),
+            [
+                "Localizable.assignments.json",
+                "Localizable.modified.json",
+                "Localizable.xcstrings"
+            ]
+        );
+        assert_eq!(git(&["diff", "--cached", "--name-only"]), "notes.txt\n");
+
+        // Nothing left to commit: no empty commit is made.
+        manager.record_change(&entry).await;
+        assert_eq!(git(&["rev-list", "--count", "HEAD"]), "1\n");
+
+        // Hooks run, so one rejecting the commit leaves the change saved but uncommitted.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let hook = tmp.dir.join(".git/hooks/pre-commit");
+            std::fs::write(&hook, "#!/bin/sh\nexit 1\n").unwrap();
+            std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+            store
+                .upsert_translation(
+                    "greeting",
+                    "de",
+                    TranslationUpdate::from_value_state(Some("Servus".into()), None),
+                )
+                .await
+                .unwrap();
+            manager.record_change(&entry).await;
+            assert_eq!(git(&["rev-list", "--count", "HEAD"]), "1\n");
+            let saved = std::fs::read_to_string(&tmp.file).unwrap();
+            assert!(saved.contains("Servus"));
+        }
+    }
+
+    #[cfg(feature = "git")]
+    #[tokio::test]
+    async fn diff_against_ref_compares_with_a_committed_version() {
+        let tmp = TempStorePath::new("diff_ref");
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .current_dir(&tmp.dir)
+                .args(["-c", "user.name=Ada", "-c", "user.email=ada@example.com"])
+                .args(args)
+                .output()
+                .expect("run git")
+                .status;
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q"]);
+        std::fs::write(tmp.dir.join("README"), "app").unwrap();
+        git(&["add", "README"]);
+        git(&["commit", "-q", "-m", "Start"]);
+        git(&["tag", "before-catalog"]);
+
+        let store = XcStringsStore::load_or_create(&tmp.file).await.unwrap();
+        let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+        store
+            .upsert_translation("greeting", "de", set("Hallo"))
+            .await
+            .unwrap();
+        store
+            .upsert_translation("farewell", "de", set("Tschüss"))
+            .await
+            .unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-q", "-m", "Add catalog"]);
+
+        store
+            .upsert_translation("greeting", "de", set("Guten Tag"))
+            .await
+            .unwrap();
+        store.delete_key("farewell").await.unwrap();
+        let diff = store.diff_against_ref("HEAD").await.unwrap();
+        assert!(diff.added_keys.is_empty());
+        assert_eq!(diff.removed_keys, ["farewell"]);
+        assert_eq!(diff.changed_keys.len(), 1);
+        let translation = &diff.changed_keys[0].translations[0];
+        assert_eq!(
+            translation
+                .after
+                .as_ref()
+                .and_then(|value| value.value.as_deref()),
+            Some("Guten Tag")
+        );
+
+        let diff = store.diff_against_ref("before-catalog").await.unwrap();
+        assert_eq!(diff.added_keys, ["greeting"]);
+        assert!(matches!(
+            store.diff_against_ref("--output=x").await,
+            Err(StoreError::GitRevisionMissing(_))
+        ));
+        assert!(matches!(
+            store.diff_against_ref("no-such-branch").await,
+            Err(StoreError::GitRevisionMissing(_))
+        ));
+    }
 }
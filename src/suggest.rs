@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{language::edit_distance, qa::contains_word};
+
+/// Suggestions returned when the caller doesn't say.
+pub const DEFAULT_LIMIT: usize = 10;
+/// Least similarity a source string needs for its translation to be suggested.
+const MIN_MEMORY_SCORE: f64 = 0.6;
+
+/// Why a translation is suggested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionSource {
+    /// Another key with the same source text, ignoring case, spacing, and final punctuation.
+    SameSource,
+    /// Another key whose source text is similar.
+    Memory,
+    /// A glossary term that appears in the source text, with its approved translation.
+    Glossary,
+}
+
+/// A candidate translation and where it came from, so an agent can pick one rather than
+/// invent its own.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Suggestion {
+    pub text: String,
+    pub source: SuggestionSource,
+    /// How much of the source text the suggestion covers, from `0` to `1`: the similarity of
+    /// the two source texts, or for a glossary term its share of the source text.
+    pub score: f64,
+    /// Catalog holding the translated key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catalog: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Source text of the translated key, or the glossary term.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_text: Option<String>,
+    /// State of the translated key's translation, such as `needs_review`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
+
+/// A translated string that [`suggest`] can draw on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryEntry {
+    pub catalog: String,
+    pub key: String,
+    pub source: String,
+    pub translation: String,
+    pub state: Option<String>,
+}
+
+/// Ranks translations of `source` into `language`: those of `memory` entries with the same
+/// or a similar source text, and the glossary terms `source` contains. Each text is
+/// suggested once, best score first.
+pub fn suggest(
+    source: &str,
+    language: &str,
+    memory: &[MemoryEntry],
+    glossary: &BTreeMap<String, BTreeMap<String, String>>,
+    limit: usize,
+) -> Vec<Suggestion> {
+    let wanted = comparable(source);
+    let mut suggestions: Vec<Suggestion> = memory
+        .iter()
+        .filter_map(|entry| {
+            let candidate = comparable(&entry.source);
+            let (source, score) = if candidate == wanted {
+                let exact = entry.source == source;
+                (SuggestionSource::SameSource, if exact { 1.0 } else { 0.95 })
+            } else {
+                (SuggestionSource::Memory, similarity(&wanted, &candidate)?)
+            };
+            Some(Suggestion {
+                text: entry.translation.clone(),
+                source,
+                score,
+                catalog: Some(entry.catalog.clone()),
+                key: Some(entry.key.clone()),
+                source_text: Some(entry.source.clone()),
+                state: entry.state.clone(),
+            })
+        })
+        .collect();
+
+    let lowered = source.to_lowercase();
+    let source_len = source.chars().count().max(1);
+    for (term, translations) in glossary {
+        let Some(translation) = translations.get(language) else {
+            continue;
+        };
+        if !contains_word(&lowered, &term.to_lowercase()) {
+            continue;
+        }
+        let share = term.chars().count() as f64 / source_len as f64;
+        suggestions.push(Suggestion {
+            text: translation.clone(),
+            source: SuggestionSource::Glossary,
+            score: round(share.min(1.0)),
+            catalog: None,
+            key: None,
+            source_text: Some(term.clone()),
+            state: None,
+        });
+    }
+
+    suggestions.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.text.cmp(&b.text))
+    });
+    let mut seen = Vec::new();
+    suggestions.retain(|suggestion| {
+        let new = !seen.contains(&suggestion.text);
+        if new {
+            seen.push(suggestion.text.clone());
+        }
+        new
+    });
+    suggestions.truncate(limit);
+    suggestions
+}
+
+/// Lowercase text with runs of whitespace collapsed and final punctuation dropped, so
+/// `Save changes.` and `save  changes` compare equal.
+fn comparable(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    words
+        .join(" ")
+        .to_lowercase()
+        .trim_end_matches(['.', '!', '?', ':', '…'])
+        .to_string()
+}
+
+/// Share of characters two texts have in common by edit distance, when it reaches
+/// [`MIN_MEMORY_SCORE`].
+fn similarity(a: &str, b: &str) -> Option<f64> {
+    let (a_len, b_len) = (a.chars().count(), b.chars().count());
+    let longest = a_len.max(b_len);
+    if longest == 0 {
+        return None;
+    }
+    // Texts of very different lengths can't be similar, so skip the distance for them.
+    let best = 1.0 - a_len.abs_diff(b_len) as f64 / longest as f64;
+    if best < MIN_MEMORY_SCORE {
+        return None;
+    }
+    let score = 1.0 - edit_distance(a, b) as f64 / longest as f64;
+    (score >= MIN_MEMORY_SCORE).then(|| round(score))
+}
+
+fn round(score: f64) -> f64 {
+    (score * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, source: &str, translation: &str) -> MemoryEntry {
+        MemoryEntry {
+            catalog: "Localizable.xcstrings".to_string(),
+            key: key.to_string(),
+            source: source.to_string(),
+            translation: translation.to_string(),
+            state: None,
+        }
+    }
+
+    #[test]
+    fn ranks_same_source_memory_and_glossary_matches() {
+        let memory = [
+            entry("settings.save", "Save changes.", "Änderungen sichern"),
+            entry("editor.save", "Save change", "Änderung sichern"),
+            entry("profile.save", "Save changes.", "Änderungen sichern"),
+            entry("logout", "Log out", "Abmelden"),
+        ];
+        let glossary = BTreeMap::from([(
+            "changes".to_string(),
+            BTreeMap::from([("de".to_string(), "Änderungen".to_string())]),
+        )]);
+
+        let suggestions = suggest("Save changes", "de", &memory, &glossary, DEFAULT_LIMIT);
+        let ranked: Vec<_> = suggestions
+            .iter()
+            .map(|suggestion| {
+                (
+                    suggestion.text.as_str(),
+                    suggestion.source,
+                    suggestion.score,
+                )
+            })
+            .collect();
+        assert_eq!(
+            ranked,
+            [
+                ("Änderungen sichern", SuggestionSource::SameSource, 0.95),
+                ("Änderung sichern", SuggestionSource::Memory, 0.92),
+                ("Änderungen", SuggestionSource::Glossary, 0.58),
+            ]
+        );
+        assert_eq!(suggestions[0].key.as_deref(), Some("settings.save"));
+        assert!(suggest("Save changes", "fr", &memory[3..], &glossary, 10).is_empty());
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use async_trait::async_trait;
+use hyper::Method;
+use serde::Deserialize;
+
+use super::{
+    http::send_json, RemoteString, RemoteTranslation, SourceString, SyncError, SyncProvider,
+};
+
+const DEFAULT_BASE_URL: &str = "https://api.crowdin.com/api/v2";
+/// Largest page the Crowdin API returns.
+const PAGE_SIZE: usize = 500;
+/// Environment variable holding the API token, which overrides `token` in the config file.
+pub const TOKEN_ENV: &str = "CROWDIN_TOKEN";
+
+/// Catalog languages whose Crowdin id differs from the code, unless `languages` overrides
+/// them.
+const LANGUAGE_IDS: [(&str, &str); 4] = [
+    ("zh-Hans", "zh-CN"),
+    ("zh-Hant", "zh-TW"),
+    ("es", "es-ES"),
+    ("pt", "pt-PT"),
+];
+
+/// A Crowdin project, through the strings API (API v2). Enterprise organizations set
+/// `baseUrl` to `https://<organization>.api.crowdin.com/api/v2`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrowdinConfig {
+    pub project_id: u64,
+    /// File the strings belong to in file-based projects; omit for string-based projects.
+    #[serde(default)]
+    pub file_id: Option<u64>,
+    /// Personal access token; `CROWDIN_TOKEN` overrides it.
+    #[serde(default)]
+    pub token: Option<String>,
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Catalog language → Crowdin language id, e.g. `{"pt-BR": "pt-BR", "nb": "no"}`.
+    #[serde(default)]
+    pub languages: BTreeMap<String, String>,
+}
+
+#[derive(Debug)]
+pub struct Crowdin {
+    config: CrowdinConfig,
+    token: String,
+    base_url: String,
+}
+
+impl Crowdin {
+    pub fn new(config: CrowdinConfig) -> Result<Self, SyncError> {
+        let token = std::env::var(TOKEN_ENV)
+            .ok()
+            .or_else(|| config.token.clone())
+            .filter(|token| !token.trim().is_empty())
+            .ok_or_else(|| {
+                SyncError::Config(format!(
+                    "set {TOKEN_ENV} or `sync.crowdin.token` to a Crowdin API token"
+                ))
+            })?;
+        let base_url = config
+            .base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+            .to_string();
+        Ok(Self {
+            config,
+            token,
+            base_url,
+        })
+    }
+
+    fn language_id<'a>(&'a self, language: &'a str) -> &'a str {
+        if let Some(id) = self.config.languages.get(language) {
+            return id;
+        }
+        LANGUAGE_IDS
+            .iter()
+            .find(|(code, _)| *code == language)
+            .map_or(language, |(_, id)| id)
+    }
+
+    fn project_url(&self, path: &str) -> String {
+        format!(
+            "{}/projects/{}{path}",
+            self.base_url, self.config.project_id
+        )
+    }
+
+    /// Reads every page of a list endpoint; `query` is appended to the paging parameters.
+    async fn list<T: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+        query: &str,
+    ) -> Result<Vec<T>, SyncError> {
+        let file = self
+            .config
+            .file_id
+            .map(|id| format!("&fileId={id}"))
+            .unwrap_or_default();
+        let mut items = Vec::new();
+        loop {
+            let url = self.project_url(&format!(
+                "{path}?limit={PAGE_SIZE}&offset={}{file}{query}",
+                items.len()
+            ));
+            let page: Page<T> =
+                serde_json::from_value(send_json(Method::GET, &url, &self.token, None).await?)
+                    .map_err(|err| SyncError::Response(format!("{path}: {err}")))?;
+            let count = page.data.len();
+            items.extend(page.data.into_iter().map(|item| item.data));
+            if count < PAGE_SIZE {
+                return Ok(items);
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Page<T> {
+    data: Vec<Item<T>>,
+}
+
+#[derive(Deserialize)]
+struct Item<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct CrowdinString {
+    id: u64,
+    identifier: String,
+    /// A string, or an object of plural forms for plural strings.
+    text: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CrowdinTranslation {
+    string_id: u64,
+    /// A string, or an object of plural forms for plural strings.
+    text: serde_json::Value,
+}
+
+#[async_trait]
+impl SyncProvider for Crowdin {
+    fn name(&self) -> &'static str {
+        "crowdin"
+    }
+
+    async fn list_sources(&self) -> Result<Vec<RemoteString>, SyncError> {
+        Ok(self
+            .list::<CrowdinString>("/strings", "")
+            .await?
+            .into_iter()
+            .filter_map(|string| {
+                Some(RemoteString {
+                    id: string.id.to_string(),
+                    key: string.identifier,
+                    text: string.text.as_str()?.to_string(),
+                })
+            })
+            .collect())
+    }
+
+    async fn add_source(&self, source: &SourceString) -> Result<(), SyncError> {
+        let mut body = serde_json::json!({
+            "identifier": source.key,
+            "text": source.text,
+        });
+        if let Some(comment) = &source.comment {
+            body["context"] = comment.clone().into();
+        }
+        if let Some(file_id) = self.config.file_id {
+            body["fileId"] = file_id.into();
+        }
+        send_json(
+            Method::POST,
+            &self.project_url("/strings"),
+            &self.token,
+            Some(&body),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_source(&self, id: &str, source: &SourceString) -> Result<(), SyncError> {
+        let mut patch = vec![serde_json::json!({
+            "op": "replace",
+            "path": "/text",
+            "value": source.text,
+        })];
+        if let Some(comment) = &source.comment {
+            patch.push(serde_json::json!({
+                "op": "replace",
+                "path": "/context",
+                "value": comment,
+            }));
+        }
+        send_json(
+            Method::PATCH,
+            &self.project_url(&format!("/strings/{id}")),
+            &self.token,
+            Some(&serde_json::Value::Array(patch)),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn list_translations(&self, language: &str) -> Result<Vec<RemoteTranslation>, SyncError> {
+        let keys: HashMap<u64, String> = self
+            .list::<CrowdinString>("/strings", "")
+            .await?
+            .into_iter()
+            .map(|string| (string.id, string.identifier))
+            .collect();
+        let path = format!("/languages/{}/translations", self.language_id(language));
+        let approved: HashSet<u64> = self
+            .list::<CrowdinTranslation>(&path, "&approvedOnly=1")
+            .await?
+            .into_iter()
+            .map(|translation| translation.string_id)
+            .collect();
+        Ok(self
+            .list::<CrowdinTranslation>(&path, "")
+            .await?
+            .into_iter()
+            .filter_map(|translation| {
+                Some(RemoteTranslation {
+                    key: keys.get(&translation.string_id)?.clone(),
+                    text: translation.text.as_str()?.to_string(),
+                    approved: approved.contains(&translation.string_id),
+                })
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    body::Bytes,
+    header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, HOST, USER_AGENT},
+    Method, Request, Uri,
+};
+use hyper_util::rt::TokioIo;
+use rustls::{pki_types::ServerName, ClientConfig, RootCertStore};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
+};
+use tokio_rustls::TlsConnector;
+
+use super::SyncError;
+
+/// How long one request, from connecting to reading the whole response, may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Certificate bundles tried, in order, when `SSL_CERT_FILE` isn't set.
+const CA_BUNDLES: [&str; 4] = [
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/cert.pem",
+    "/usr/local/etc/openssl/cert.pem",
+];
+
+/// Sends one JSON request with a bearer token and returns the parsed response body, or
+/// `Null` for empty responses. Plain `http` URLs are allowed for local test servers.
+pub async fn send_json(
+    method: Method,
+    url: &str,
+    token: &str,
+    body: Option<&serde_json::Value>,
+) -> Result<serde_json::Value, SyncError> {
+    tokio::time::timeout(REQUEST_TIMEOUT, send(method, url, token, body))
+        .await
+        .map_err(|_| SyncError::Transport(format!("{url} timed out")))?
+}
+
+async fn send(
+    method: Method,
+    url: &str,
+    token: &str,
+    body: Option<&serde_json::Value>,
+) -> Result<serde_json::Value, SyncError> {
+    let uri: Uri = url
+        .parse()
+        .map_err(|err| SyncError::Config(format!("invalid URL '{url}': {err}")))?;
+    let https = match uri.scheme_str() {
+        Some("https") => true,
+        Some("http") => false,
+        _ => return Err(SyncError::Config(format!("unsupported URL '{url}'"))),
+    };
+    let host = uri
+        .host()
+        .ok_or_else(|| SyncError::Config(format!("URL '{url}' has no host")))?
+        .to_string();
+    let port = uri.port_u16().unwrap_or(if https { 443 } else { 80 });
+    let transport = |err: std::io::Error| SyncError::Transport(format!("{host}: {err}"));
+
+    let stream = TcpStream::connect((host.as_str(), port))
+        .await
+        .map_err(transport)?;
+    let body = body.map(serde_json::to_vec).transpose()?;
+    let request = Request::builder()
+        .method(method)
+        .uri(uri.path_and_query().map_or("/", |path| path.as_str()))
+        .header(HOST, uri.authority().map_or(host.as_str(), |a| a.as_str()))
+        .header(AUTHORIZATION, format!("Bearer {token}"))
+        .header(ACCEPT, "application/json")
+        .header(CONTENT_TYPE, "application/json")
+        .header(
+            USER_AGENT,
+            concat!("xcstrings-mcp/", env!("CARGO_PKG_VERSION")),
+        )
+        .body(Full::new(Bytes::from(body.unwrap_or_default())))
+        .map_err(|err| SyncError::Transport(err.to_string()))?;
+
+    let (status, raw) = if https {
+        let server_name = ServerName::try_from(host.clone())
+            .map_err(|err| SyncError::Config(format!("invalid host '{host}': {err}")))?;
+        let stream = TlsConnector::from(tls_config()?)
+            .connect(server_name, stream)
+            .await
+            .map_err(transport)?;
+        exchange(stream, request).await?
+    } else {
+        exchange(stream, request).await?
+    };
+
+    if !status.is_success() {
+        let message = serde_json::from_slice::<serde_json::Value>(&raw)
+            .ok()
+            .and_then(|body| error_message(&body))
+            .unwrap_or_else(|| String::from_utf8_lossy(&raw).trim().to_string());
+        return Err(SyncError::Http {
+            status: status.as_u16(),
+            message,
+        });
+    }
+    if raw.is_empty() {
+        return Ok(serde_json::Value::Null);
+    }
+    Ok(serde_json::from_slice(&raw)?)
+}
+
+async fn exchange<S>(
+    stream: S,
+    request: Request<Full<Bytes>>,
+) -> Result<(hyper::StatusCode, Bytes), SyncError>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let failed = |err: hyper::Error| SyncError::Transport(err.to_string());
+    let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+        .await
+        .map_err(failed)?;
+    tokio::spawn(async move {
+        if let Err(err) = connection.await {
+            tracing::debug!(?err, "Sync connection closed with an error");
+        }
+    });
+    let response = sender.send_request(request).await.map_err(failed)?;
+    let status = response.status();
+    let raw = response.into_body().collect().await.map_err(failed)?;
+    Ok((status, raw.to_bytes()))
+}
+
+/// Pulls a readable message out of an API error body, such as Crowdin's
+/// `{"error": {"message": ...}}` or `{"errors": [{"error": {"errors": [{"message": ...}]}}]}`.
+fn error_message(body: &serde_json::Value) -> Option<String> {
+    if let Some(message) = body.pointer("/error/message").and_then(|m| m.as_str()) {
+        return Some(message.to_string());
+    }
+    let messages: Vec<&str> = body
+        .get("errors")?
+        .as_array()?
+        .iter()
+        .filter_map(|error| error.pointer("/error/errors"))
+        .filter_map(|errors| errors.as_array())
+        .flatten()
+        .filter_map(|error| error.get("message").and_then(|m| m.as_str()))
+        .collect();
+    (!messages.is_empty()).then(|| messages.join("; "))
+}
+
+/// TLS settings trusting the system's certificate bundle, loaded once.
+fn tls_config() -> Result<Arc<ClientConfig>, SyncError> {
+    static CONFIG: OnceLock<Arc<ClientConfig>> = OnceLock::new();
+    if let Some(config) = CONFIG.get() {
+        return Ok(config.clone());
+    }
+    let bundle = std::env::var_os("SSL_CERT_FILE")
+        .map(PathBuf::from)
+        .or_else(|| {
+            CA_BUNDLES
+                .iter()
+                .map(Path::new)
+                .find(|path| path.is_file())
+                .map(Path::to_path_buf)
+        })
+        .ok_or_else(|| {
+            SyncError::Config(
+                "no CA certificate bundle found; set SSL_CERT_FILE to a PEM bundle".to_string(),
+            )
+        })?;
+    let file = std::fs::File::open(&bundle)
+        .map_err(|err| SyncError::Config(format!("failed to read {}: {err}", bundle.display())))?;
+    let mut roots = RootCertStore::empty();
+    for cert in rustls_pemfile::certs(&mut BufReader::new(file)).flatten() {
+        // Skip certificates rustls can't parse instead of distrusting the whole bundle.
+        let _ = roots.add(cert);
+    }
+    let config =
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|err| SyncError::Config(err.to_string()))?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+    Ok(CONFIG.get_or_init(|| Arc::new(config)).clone())
+}
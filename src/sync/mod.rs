@@ -0,0 +1,311 @@
+mod crowdin;
+mod http;
+
+use std::collections::{BTreeSet, HashMap};
+
+use async_trait::async_trait;
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::store::{BatchOperation, InsertPosition, StoreError, TranslationUpdate, XcStringsStore};
+
+pub use crowdin::{Crowdin, CrowdinConfig};
+
+/// State given to pulled translations a reviewer approved in the TMS.
+const APPROVED_STATE: &str = "translated";
+/// State given to pulled translations still waiting for approval in the TMS.
+const UNAPPROVED_STATE: &str = "needs_review";
+
+#[derive(Debug, Error)]
+pub enum SyncError {
+    #[error("sync is not configured: {0}")]
+    Config(String),
+    #[error("translation service answered {status}: {message}")]
+    Http { status: u16, message: String },
+    #[error("failed to reach the translation service: {0}")]
+    Transport(String),
+    #[error("unexpected response from the translation service: {0}")]
+    Response(String),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Store(#[from] StoreError),
+}
+
+/// Translation management services to sync with, from the `sync` section of the config file.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncConfig {
+    #[serde(default)]
+    pub crowdin: Option<CrowdinConfig>,
+}
+
+impl SyncConfig {
+    /// The configured provider called `name`, or the only one configured when `name` is
+    /// `None`.
+    pub fn provider(&self, name: Option<&str>) -> Result<Box<dyn SyncProvider>, SyncError> {
+        match name.map(str::trim) {
+            None | Some("crowdin") => match &self.crowdin {
+                Some(config) => Ok(Box::new(Crowdin::new(config.clone())?)),
+                None => Err(SyncError::Config(
+                    "add a `sync.crowdin` section to the config file".to_string(),
+                )),
+            },
+            Some(other) => Err(SyncError::Config(format!(
+                "unknown provider '{other}'; expected crowdin"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum SyncDirection {
+    /// Upload source strings to the service
+    Push,
+    /// Download translations from the service into the catalog
+    Pull,
+}
+
+/// A source string as the catalog holds it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceString {
+    pub key: String,
+    pub text: String,
+    pub comment: Option<String>,
+}
+
+/// A source string as the service holds it, with the service's own id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteString {
+    pub id: String,
+    pub key: String,
+    pub text: String,
+}
+
+/// A translation downloaded from the service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteTranslation {
+    pub key: String,
+    pub text: String,
+    pub approved: bool,
+}
+
+/// A translation management service. Languages are catalog codes; providers map them to
+/// their own.
+#[async_trait]
+pub trait SyncProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn list_sources(&self) -> Result<Vec<RemoteString>, SyncError>;
+
+    async fn add_source(&self, source: &SourceString) -> Result<(), SyncError>;
+
+    async fn update_source(&self, id: &str, source: &SourceString) -> Result<(), SyncError>;
+
+    /// Every translation into `language`, approved or not.
+    async fn list_translations(&self, language: &str) -> Result<Vec<RemoteTranslation>, SyncError>;
+}
+
+/// What a push or pull changed, or would change in a dry run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    pub provider: String,
+    pub dry_run: bool,
+    /// Source strings created on the service.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<String>,
+    /// Source strings whose text was updated on the service.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub updated: Vec<String>,
+    /// Keys left out of a push because their source text only exists in variations.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+    /// Translations written to the catalog.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pulled: Vec<PulledTranslation>,
+    /// Keys the service has translations for that the catalog doesn't.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unknown_keys: Vec<String>,
+    /// Strings or translations that already matched.
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PulledTranslation {
+    pub key: String,
+    pub language: String,
+    pub value: String,
+    pub state: String,
+    /// Text the pull replaced, when the catalog already had a translation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<String>,
+}
+
+impl SyncReport {
+    /// Pulled translations that replace text the catalog already had.
+    pub fn overwritten(&self) -> usize {
+        self.pulled
+            .iter()
+            .filter(|pulled| pulled.previous.is_some())
+            .count()
+    }
+}
+
+/// Uploads source-language text: creates strings the service lacks and updates those whose
+/// text changed. Keys marked `shouldTranslate: false` stay local, and keys without a source
+/// value use the key, as in Xcode.
+pub async fn push(
+    store: &XcStringsStore,
+    provider: &dyn SyncProvider,
+    dry_run: bool,
+) -> Result<SyncReport, SyncError> {
+    let mut report = SyncReport {
+        provider: provider.name().to_string(),
+        dry_run,
+        ..SyncReport::default()
+    };
+    let source_language = store.source_language().await;
+    let remote: HashMap<String, RemoteString> = provider
+        .list_sources()
+        .await?
+        .into_iter()
+        .map(|string| (string.key.clone(), string))
+        .collect();
+
+    for record in store.list_records(None).await {
+        if record.should_translate == Some(false) {
+            continue;
+        }
+        let text = match record.translations.get(&source_language) {
+            None => record.key.clone(),
+            Some(value) => match value.value.as_deref().filter(|text| !text.is_empty()) {
+                Some(text) => text.to_string(),
+                None => {
+                    report.skipped.push(record.key);
+                    continue;
+                }
+            },
+        };
+        let source = SourceString {
+            key: record.key,
+            text,
+            comment: record.comment,
+        };
+        match remote.get(&source.key) {
+            None => {
+                if !dry_run {
+                    provider.add_source(&source).await?;
+                }
+                report.added.push(source.key);
+            }
+            Some(existing) if existing.text != source.text => {
+                if !dry_run {
+                    provider.update_source(&existing.id, &source).await?;
+                }
+                report.updated.push(source.key);
+            }
+            Some(_) => report.unchanged += 1,
+        }
+    }
+    Ok(report)
+}
+
+/// Downloads translations into `languages` (every language but the source when empty).
+/// Approved translations become `translated` and others `needs_review`; translations whose
+/// text already matches are left alone, keeping their state. All writes are saved as one
+/// batch, so a refused write (such as to a frozen language) saves nothing.
+pub async fn pull(
+    store: &XcStringsStore,
+    provider: &dyn SyncProvider,
+    languages: &[String],
+    dry_run: bool,
+) -> Result<SyncReport, SyncError> {
+    let mut report = SyncReport {
+        provider: provider.name().to_string(),
+        dry_run,
+        ..SyncReport::default()
+    };
+    let source_language = store.source_language().await;
+    let known = store.list_languages().await;
+    let languages = if languages.is_empty() {
+        known
+            .iter()
+            .filter(|language| **language != source_language)
+            .cloned()
+            .collect()
+    } else {
+        for language in languages {
+            if !known.contains(language) {
+                return Err(StoreError::LanguageMissing(language.clone()).into());
+            }
+        }
+        languages.to_vec()
+    };
+    let records: HashMap<String, _> = store
+        .list_records(None)
+        .await
+        .into_iter()
+        .map(|record| (record.key.clone(), record))
+        .collect();
+
+    let mut unknown = BTreeSet::new();
+    let mut operations = Vec::new();
+    for language in &languages {
+        for translation in provider.list_translations(language).await? {
+            let Some(record) = records.get(&translation.key) else {
+                unknown.insert(translation.key);
+                continue;
+            };
+            if record.should_translate == Some(false) {
+                continue;
+            }
+            let current = record
+                .translations
+                .get(language)
+                .and_then(|value| value.value.as_deref());
+            if current == Some(translation.text.as_str()) {
+                report.unchanged += 1;
+                continue;
+            }
+            let state = match translation.approved {
+                true => APPROVED_STATE,
+                false => UNAPPROVED_STATE,
+            };
+            operations.push(BatchOperation::Upsert {
+                key: translation.key.clone(),
+                language: language.clone(),
+                comment: None,
+                position: InsertPosition::End,
+                update: Box::new(TranslationUpdate::from_value_state(
+                    Some(translation.text.clone()),
+                    Some(state.to_string()),
+                )),
+            });
+            report.pulled.push(PulledTranslation {
+                key: translation.key,
+                language: language.clone(),
+                value: translation.text,
+                state: state.to_string(),
+                previous: current.filter(|text| !text.is_empty()).map(str::to_string),
+            });
+        }
+    }
+    report.unknown_keys = unknown.into_iter().collect();
+
+    if !dry_run && !operations.is_empty() {
+        let outcome = store.apply_batch(operations).await?;
+        if !outcome.committed {
+            if let Some(Err(err)) = outcome.results.into_iter().find(Result::is_err) {
+                return Err(err.into());
+            }
+        }
+    }
+    Ok(report)
+}
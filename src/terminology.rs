@@ -0,0 +1,356 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{analytics::texts, store::TranslationRecord};
+
+/// Keys a term must appear in when the caller doesn't say.
+pub const DEFAULT_MIN_OCCURRENCES: usize = 3;
+/// Longest phrase, in words, mined as a term.
+const MAX_TERM_WORDS: usize = 3;
+/// Least Dice coefficient between a term and a translation for it to be proposed.
+const MIN_CONFIDENCE: f64 = 0.5;
+/// Keys listed per term as examples.
+const EXAMPLE_KEYS: usize = 5;
+/// Words that don't start or end a term, for English source text. Particles such as `in`
+/// and `up` are kept for phrasal verbs like `Sign in`.
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "after", "all", "an", "and", "any", "are", "as", "at", "be", "been", "before",
+    "but", "by", "can", "could", "do", "does", "for", "from", "has", "have", "he", "her", "his",
+    "how", "i", "if", "into", "is", "it", "its", "may", "me", "more", "must", "my", "no", "not",
+    "of", "or", "our", "she", "so", "some", "than", "that", "the", "their", "them", "then",
+    "there", "these", "they", "this", "those", "to", "too", "us", "was", "we", "were", "what",
+    "when", "which", "who", "why", "will", "with", "would", "you", "your",
+];
+
+/// A recurring source phrase and the translation each language most often gives it, for a
+/// person to approve into the glossary.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TermCandidate {
+    /// The phrase as most often written in the source text.
+    pub term: String,
+    /// Keys whose source text contains the phrase.
+    pub occurrences: usize,
+    /// A few of those keys, in catalog order.
+    pub keys: Vec<String>,
+    /// The likeliest translation per language; languages without a reliable one are left out.
+    pub translations: BTreeMap<String, TermTranslation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TermTranslation {
+    pub text: String,
+    /// Keys containing the term whose translation contains this text.
+    pub support: usize,
+    /// Dice coefficient of the keys containing the term and those whose translation contains
+    /// the text, from `0` to `1`; `1` when they are the same keys.
+    pub confidence: f64,
+}
+
+/// Mines phrases of up to three words that recur in the source text of at least
+/// `min_occurrences` keys, most frequent first, and pairs each with the phrase its keys'
+/// translations share most exclusively in every language. Keys marked
+/// `shouldTranslate: false`, glossary terms, and phrases only ever seen inside a longer one
+/// are left out. Translations are split at spaces, so in languages written without them only
+/// terms that make up a whole string get a translation.
+pub fn extract(
+    records: &[TranslationRecord],
+    source_language: &str,
+    glossary: &BTreeMap<String, BTreeMap<String, String>>,
+    min_occurrences: usize,
+) -> Vec<TermCandidate> {
+    let min_occurrences = min_occurrences.max(2);
+    let known: BTreeSet<String> = glossary.keys().map(|term| term.to_lowercase()).collect();
+    let records: Vec<&TranslationRecord> = records
+        .iter()
+        .filter(|record| record.should_translate != Some(false))
+        .collect();
+
+    let mut terms = Phrases::default();
+    for (index, record) in records.iter().enumerate() {
+        let mut sources: Vec<String> = record
+            .translations
+            .get(source_language)
+            .map(|value| texts(value).into_iter().map(|(_, text)| text).collect())
+            .unwrap_or_default();
+        if sources.is_empty() {
+            sources.push(record.key.clone());
+        }
+        let mut seen = BTreeSet::new();
+        for text in &sources {
+            for words in phrases(text) {
+                for term in ngrams(&words) {
+                    let first = term.first().map(|word| word.to_lowercase());
+                    let last = term.last().map(|word| word.to_lowercase());
+                    if [first, last]
+                        .iter()
+                        .flatten()
+                        .any(|word| STOP_WORDS.contains(&word.as_str()))
+                    {
+                        continue;
+                    }
+                    terms.add(term, index, &mut seen);
+                }
+            }
+        }
+    }
+    terms.keys.retain(|_, keys| keys.len() >= min_occurrences);
+    let nested: BTreeSet<String> = terms
+        .keys
+        .iter()
+        .filter(|(term, keys)| {
+            let padded = format!(" {term} ");
+            terms.keys.iter().any(|(longer, other)| {
+                longer.len() > term.len()
+                    && other.len() == keys.len()
+                    && format!(" {longer} ").contains(&padded)
+            })
+        })
+        .map(|(term, _)| term.clone())
+        .collect();
+    terms
+        .keys
+        .retain(|term, _| !nested.contains(term) && !known.contains(term));
+
+    // Each language's translation phrases per key, and the keys each phrase appears in.
+    let mut languages: BTreeMap<&str, (Vec<BTreeSet<String>>, Phrases)> = BTreeMap::new();
+    for (index, record) in records.iter().enumerate() {
+        for (language, value) in &record.translations {
+            if language == source_language {
+                continue;
+            }
+            let (per_key, phrases_in) = languages
+                .entry(language.as_str())
+                .or_insert_with(|| (vec![BTreeSet::new(); records.len()], Phrases::default()));
+            let mut seen = BTreeSet::new();
+            for (_, text) in texts(value) {
+                for words in phrases(&text) {
+                    for phrase in ngrams(&words) {
+                        phrases_in.add(phrase, index, &mut seen);
+                    }
+                }
+            }
+            per_key[index] = seen;
+        }
+    }
+
+    let mut candidates: Vec<TermCandidate> = terms
+        .keys
+        .iter()
+        .map(|(term, keys)| {
+            let words = term.split(' ').count();
+            let translations = languages
+                .iter()
+                .filter_map(|(language, (per_key, phrases_in))| {
+                    let translated = keys.iter().filter(|index| !per_key[**index].is_empty());
+                    let translated: Vec<usize> = translated.copied().collect();
+                    let mut support: HashMap<&str, usize> = HashMap::new();
+                    for index in &translated {
+                        for phrase in &per_key[*index] {
+                            *support.entry(phrase.as_str()).or_default() += 1;
+                        }
+                    }
+                    let best = support
+                        .into_iter()
+                        .filter(|(_, support)| *support >= 2)
+                        .map(|(phrase, support)| {
+                            let total = phrases_in.keys[phrase].len();
+                            let confidence =
+                                2.0 * support as f64 / (translated.len() + total) as f64;
+                            (phrase, support, confidence)
+                        })
+                        .filter(|(_, _, confidence)| *confidence >= MIN_CONFIDENCE)
+                        .max_by(|a, b| {
+                            a.2.total_cmp(&b.2)
+                                .then(a.1.cmp(&b.1))
+                                .then_with(|| {
+                                    let gap =
+                                        |phrase: &str| phrase.split(' ').count().abs_diff(words);
+                                    gap(b.0).cmp(&gap(a.0))
+                                })
+                                // Longer words are less likely to be articles or particles.
+                                .then(a.0.chars().count().cmp(&b.0.chars().count()))
+                                .then_with(|| b.0.cmp(a.0))
+                        })?;
+                    Some((
+                        language.to_string(),
+                        TermTranslation {
+                            text: phrases_in.spelling(best.0),
+                            support: best.1,
+                            confidence: (best.2 * 100.0).round() / 100.0,
+                        },
+                    ))
+                })
+                .collect();
+            TermCandidate {
+                term: terms.spelling(term),
+                occurrences: keys.len(),
+                keys: keys
+                    .iter()
+                    .take(EXAMPLE_KEYS)
+                    .map(|index| records[*index].key.clone())
+                    .collect(),
+                translations,
+            }
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.occurrences
+            .cmp(&a.occurrences)
+            .then_with(|| a.term.to_lowercase().cmp(&b.term.to_lowercase()))
+    });
+    candidates
+}
+
+/// Lowercase phrases with the keys they appear in and how often each spelling is used.
+#[derive(Default)]
+struct Phrases {
+    keys: BTreeMap<String, Vec<usize>>,
+    spellings: HashMap<String, BTreeMap<String, usize>>,
+}
+
+impl Phrases {
+    /// Records `words` for the key at `index`, once per key however often it repeats there.
+    fn add(&mut self, words: &[&str], index: usize, seen: &mut BTreeSet<String>) {
+        let spelling = words.join(" ");
+        let phrase = spelling.to_lowercase();
+        *self
+            .spellings
+            .entry(phrase.clone())
+            .or_default()
+            .entry(spelling)
+            .or_default() += 1;
+        if seen.insert(phrase.clone()) {
+            self.keys.entry(phrase).or_default().push(index);
+        }
+    }
+
+    /// The most common spelling of a lowercase phrase.
+    fn spelling(&self, phrase: &str) -> String {
+        self.spellings
+            .get(phrase)
+            .and_then(|spellings| {
+                spellings
+                    .iter()
+                    .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            })
+            .map_or_else(|| phrase.to_string(), |(spelling, _)| spelling.clone())
+    }
+}
+
+/// Runs of words in `text`, broken at punctuation, numbers, and format specifiers such as
+/// `%@` so no phrase spans them.
+fn phrases(text: &str) -> Vec<Vec<&str>> {
+    let mut phrases = Vec::new();
+    let mut current = Vec::new();
+    for chunk in text.split_whitespace() {
+        if chunk.contains('%') {
+            phrases.push(std::mem::take(&mut current));
+            continue;
+        }
+        let pieces = chunk.split(|c: char| !(c.is_alphanumeric() || matches!(c, '\'' | '’' | '-')));
+        for (index, piece) in pieces.enumerate() {
+            let word = piece.trim_matches(['\'', '’', '-']);
+            if index > 0 || word.chars().all(|c| c.is_numeric()) {
+                phrases.push(std::mem::take(&mut current));
+            }
+            if word.chars().any(char::is_alphabetic) {
+                current.push(word);
+            }
+        }
+    }
+    phrases.push(current);
+    phrases.retain(|phrase| !phrase.is_empty());
+    phrases
+}
+
+/// Every run of one to [`MAX_TERM_WORDS`] consecutive words.
+fn ngrams<'a>(words: &'a [&'a str]) -> impl Iterator<Item = &'a [&'a str]> {
+    (1..=MAX_TERM_WORDS.min(words.len())).flat_map(move |size| words.windows(size))
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+    use crate::store::TranslationValue;
+
+    fn record(key: &str, translations: &[(&str, &str)]) -> TranslationRecord {
+        TranslationRecord {
+            key: key.to_string(),
+            ordinal: 0,
+            comment: None,
+            extraction_state: None,
+            should_translate: None,
+            metadata: None,
+            translations: translations
+                .iter()
+                .map(|(language, text)| {
+                    let value = TranslationValue {
+                        value: Some(text.to_string()),
+                        ..Default::default()
+                    };
+                    (language.to_string(), value)
+                })
+                .collect::<IndexMap<_, _>>(),
+            modified: None,
+            matches: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn pairs_recurring_phrases_with_their_usual_translation() {
+        let records = [
+            record(
+                "a",
+                &[
+                    ("en", "Sign in to sync"),
+                    ("de", "Anmelden, um zu synchronisieren"),
+                ],
+            ),
+            record("b", &[("en", "Sign in with %@"), ("de", "Mit %@ anmelden")]),
+            record(
+                "c",
+                &[
+                    ("en", "Sign in failed."),
+                    ("de", "Anmelden fehlgeschlagen."),
+                ],
+            ),
+            record(
+                "d",
+                &[("en", "Delete the file"), ("de", "Die Datei löschen")],
+            ),
+            record(
+                "e",
+                &[("en", "Rename the file"), ("de", "Die Datei umbenennen")],
+            ),
+        ];
+
+        let candidates = extract(&records, "en", &BTreeMap::new(), 2);
+        let pairs: Vec<_> = candidates
+            .iter()
+            .map(|candidate| {
+                let de = &candidate.translations["de"];
+                (
+                    candidate.term.as_str(),
+                    candidate.occurrences,
+                    de.text.as_str(),
+                    de.confidence,
+                )
+            })
+            .collect();
+        assert_eq!(
+            pairs,
+            [("Sign in", 3, "Anmelden", 1.0), ("file", 2, "Datei", 1.0)]
+        );
+        assert_eq!(candidates[0].keys, ["a", "b", "c"]);
+
+        let glossary = BTreeMap::from([("Sign in".to_string(), BTreeMap::new())]);
+        let candidates = extract(&records, "en", &glossary, 3);
+        assert!(candidates.is_empty(), "{candidates:?}");
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Context;
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use serde::Deserialize;
+
+/// PEM certificate chain and private key for serving HTTPS.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TlsFiles {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
+
+impl TlsFiles {
+    /// Reads and validates both files, so a bad path or mismatched key fails at startup
+    /// rather than on the first connection.
+    pub fn load(&self) -> anyhow::Result<RustlsConfig> {
+        let certs = read_certs(&self.cert)?;
+        let key = read_key(&self.key)?;
+        let config = rustls::ServerConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .context("failed to configure TLS protocol versions")?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .with_context(|| {
+            format!(
+                "TLS key {} does not match certificate {}",
+                self.key.display(),
+                self.cert.display()
+            )
+        })?;
+        Ok(RustlsConfig::from_config(Arc::new(config)))
+    }
+}
+
+fn open(path: &Path, what: &str) -> anyhow::Result<BufReader<File>> {
+    let file = File::open(path)
+        .with_context(|| format!("failed to open TLS {what} {}", path.display()))?;
+    Ok(BufReader::new(file))
+}
+
+fn read_certs(path: &Path) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let certs = rustls_pemfile::certs(&mut open(path, "certificate")?)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("invalid PEM in TLS certificate {}", path.display()))?;
+    if certs.is_empty() {
+        anyhow::bail!("no certificates found in {}", path.display());
+    }
+    Ok(certs)
+}
+
+fn read_key(path: &Path) -> anyhow::Result<PrivateKeyDer<'static>> {
+    rustls_pemfile::private_key(&mut open(path, "key")?)
+        .with_context(|| format!("invalid PEM in TLS key {}", path.display()))?
+        .with_context(|| format!("no private key found in {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_pair(dir: &Path) -> TlsFiles {
+        let generated = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let files = TlsFiles {
+            cert: dir.join("cert.pem"),
+            key: dir.join("key.pem"),
+        };
+        std::fs::write(&files.cert, generated.cert.pem()).unwrap();
+        std::fs::write(&files.key, generated.key_pair.serialize_pem()).unwrap();
+        files
+    }
+
+    #[test]
+    fn loads_pem_pair_and_reports_bad_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = write_pair(dir.path());
+        files.load().expect("valid pair");
+
+        let missing = TlsFiles {
+            cert: dir.path().join("missing.pem"),
+            key: files.key.clone(),
+        };
+        let err = missing.load().unwrap_err().to_string();
+        assert!(err.contains("failed to open TLS certificate"), "{err}");
+
+        let swapped = TlsFiles {
+            cert: files.key.clone(),
+            key: files.cert.clone(),
+        };
+        let err = swapped.load().unwrap_err().to_string();
+        assert!(err.contains("no certificates found"), "{err}");
+
+        let other_dir = dir.path().join("other");
+        std::fs::create_dir(&other_dir).unwrap();
+        let other = write_pair(&other_dir).key;
+        let err = TlsFiles {
+            cert: files.cert.clone(),
+            key: other,
+        }
+        .load()
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("does not match"), "{err}");
+    }
+}
@@ -0,0 +1,116 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{Query, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::Deserialize;
+
+use super::ApiError;
+
+/// Query parameter carrying the bearer token for clients that cannot set headers, such as
+/// `EventSource` and browser WebSockets.
+const TOKEN_QUERY_PARAM: &str = "access_token";
+
+/// Credentials required on every `/api` route. With neither a token nor a username and
+/// password configured, the API is open and the server only binds to loopback addresses.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebAuth {
+    /// Accepted as `Authorization: Bearer <token>` or the `access_token` query parameter.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// HTTP basic auth user; only used together with `password`.
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+impl WebAuth {
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_some() || self.basic().is_some()
+    }
+
+    fn basic(&self) -> Option<(&str, &str)> {
+        Some((self.username.as_deref()?, self.password.as_deref()?))
+    }
+
+    fn authorizes(&self, headers: &HeaderMap, uri: &Uri) -> bool {
+        let authorization = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok());
+        if let Some((scheme, credentials)) = authorization.and_then(|value| value.split_once(' ')) {
+            let credentials = credentials.trim();
+            if scheme.eq_ignore_ascii_case("bearer") {
+                return self.token_matches(credentials);
+            }
+            if scheme.eq_ignore_ascii_case("basic") {
+                return self.basic_matches(credentials);
+            }
+            return false;
+        }
+
+        Query::<HashMap<String, String>>::try_from_uri(uri)
+            .ok()
+            .and_then(|Query(params)| params.get(TOKEN_QUERY_PARAM).cloned())
+            .is_some_and(|token| self.token_matches(&token))
+    }
+
+    fn token_matches(&self, candidate: &str) -> bool {
+        self.token
+            .as_deref()
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), candidate.as_bytes()))
+    }
+
+    fn basic_matches(&self, encoded: &str) -> bool {
+        let Some((username, password)) = self.basic() else {
+            return false;
+        };
+        let Ok(decoded) = STANDARD.decode(encoded) else {
+            return false;
+        };
+        let expected = format!("{username}:{password}");
+        constant_time_eq(expected.as_bytes(), &decoded)
+    }
+
+    fn challenge(&self) -> HeaderValue {
+        if self.basic().is_some() {
+            HeaderValue::from_static("Basic realm=\"xcstrings\", charset=\"UTF-8\"")
+        } else {
+            HeaderValue::from_static("Bearer realm=\"xcstrings\"")
+        }
+    }
+}
+
+/// Compares secrets without returning early on the first differing byte.
+fn constant_time_eq(expected: &[u8], candidate: &[u8]) -> bool {
+    expected.len() == candidate.len()
+        && expected
+            .iter()
+            .zip(candidate)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+pub(super) async fn require_auth(
+    State(auth): State<Arc<WebAuth>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if auth.authorizes(request.headers(), request.uri()) {
+        return next.run(request).await;
+    }
+    let mut response = ApiError {
+        status: StatusCode::UNAUTHORIZED,
+        message: "Authentication required".to_string(),
+    }
+    .into_response();
+    response
+        .headers_mut()
+        .insert(header::WWW_AUTHENTICATE, auth.challenge());
+    response
+}
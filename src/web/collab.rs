@@ -0,0 +1,418 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path as FsPath, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Query,
+    },
+    http::StatusCode,
+    response::Response,
+    Extension,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, Mutex};
+use utoipa::IntoParams;
+
+use super::{path_token, resolve_store, ApiError, UpsertRequest};
+use crate::store::{StoreChange, StoreEvent, XcStringsStore, XcStringsStoreManager};
+
+const PRESENCE_CHANNEL_CAPACITY: usize = 256;
+
+/// Key -> client id -> display name, for one catalog.
+type KeyEditors = BTreeMap<String, BTreeMap<u64, String>>;
+
+/// Tracks who is editing which key, per catalog, across all WebSocket sessions.
+pub(super) struct CollabHub {
+    next_client: AtomicU64,
+    editors: Mutex<HashMap<PathBuf, KeyEditors>>,
+    presence: broadcast::Sender<PresenceUpdate>,
+}
+
+impl Default for CollabHub {
+    fn default() -> Self {
+        let (presence, _) = broadcast::channel(PRESENCE_CHANNEL_CAPACITY);
+        Self {
+            next_client: AtomicU64::new(1),
+            editors: Mutex::new(HashMap::new()),
+            presence,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PresenceUpdate {
+    path: PathBuf,
+    key: String,
+    client: u64,
+    name: String,
+    editing: bool,
+}
+
+impl CollabHub {
+    async fn set_editing(&self, path: &FsPath, key: &str, client: u64, name: &str, editing: bool) {
+        let mut editors = self.editors.lock().await;
+        let keys = editors.entry(path.to_path_buf()).or_default();
+        let changed = if editing {
+            keys.entry(key.to_string())
+                .or_default()
+                .insert(client, name.to_string())
+                .is_none()
+        } else {
+            let removed = keys
+                .get_mut(key)
+                .is_some_and(|clients| clients.remove(&client).is_some());
+            keys.retain(|_, clients| !clients.is_empty());
+            removed
+        };
+        drop(editors);
+
+        if changed {
+            let _ = self.presence.send(PresenceUpdate {
+                path: path.to_path_buf(),
+                key: key.to_string(),
+                client,
+                name: name.to_string(),
+                editing,
+            });
+        }
+    }
+
+    /// Clears every key the client was editing, announcing each as idle.
+    async fn leave(&self, path: &FsPath, client: u64, name: &str) {
+        let keys: Vec<String> = self
+            .editors
+            .lock()
+            .await
+            .get(path)
+            .map(|keys| {
+                keys.iter()
+                    .filter(|(_, clients)| clients.contains_key(&client))
+                    .map(|(key, _)| key.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        for key in keys {
+            self.set_editing(path, &key, client, name, false).await;
+        }
+    }
+
+    async fn editors_of(&self, path: &FsPath) -> Vec<Editor> {
+        let editors = self.editors.lock().await;
+        editors
+            .get(path)
+            .into_iter()
+            .flat_map(|keys| keys.iter())
+            .flat_map(|(key, clients)| {
+                clients.iter().map(|(client, name)| Editor {
+                    key: key.clone(),
+                    client: *client,
+                    name: name.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct CollabQuery {
+    #[serde(default)]
+    path: Option<String>,
+    /// Name shown to other editors; defaults to `editor-<id>`.
+    #[serde(default)]
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    /// The client started editing a key.
+    Editing { key: String },
+    /// The client stopped editing a key.
+    Idle { key: String },
+    /// Edits carry the catalog `revision` they were made against, from the welcome message,
+    /// the last ack, or an `ETag`, and are refused like HTTP writes when it's stale.
+    Upsert {
+        #[serde(default)]
+        id: Option<u64>,
+        #[serde(default)]
+        revision: Option<String>,
+        #[serde(flatten)]
+        request: Box<UpsertRequest>,
+    },
+    DeleteTranslation {
+        #[serde(default)]
+        id: Option<u64>,
+        #[serde(default)]
+        revision: Option<String>,
+        key: String,
+        language: String,
+    },
+    DeleteKey {
+        #[serde(default)]
+        id: Option<u64>,
+        #[serde(default)]
+        revision: Option<String>,
+        key: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct Editor {
+    key: String,
+    client: u64,
+    name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    /// First message of every session, listing keys other editors have open.
+    Welcome {
+        client: u64,
+        path: String,
+        /// Catalog revision to send with the first edit.
+        revision: String,
+        editors: Vec<Editor>,
+    },
+    Presence {
+        key: String,
+        client: u64,
+        name: String,
+        editing: bool,
+    },
+    Change {
+        change: StoreChange,
+    },
+    ExternalChange,
+    /// Events were dropped because the client fell behind; it should refetch.
+    Resync,
+    Ack {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<u64>,
+        /// Catalog revision after the edit, to send with the next one.
+        revision: String,
+    },
+    Error {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<u64>,
+        status: u16,
+        error: String,
+    },
+}
+
+/// Upgrades to a WebSocket that pushes store changes and presence for one catalog and
+/// accepts edit operations.
+#[utoipa::path(
+    get,
+    path = "/api/ws",
+    tag = "events",
+    params(CollabQuery),
+    responses(
+        (status = 101, description = "Switches to a WebSocket carrying JSON messages tagged by `type`"),
+        (status = 404, description = "Catalog not found", body = super::ErrorResponse),
+    )
+)]
+pub(super) async fn collab_socket(
+    ws: WebSocketUpgrade,
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Extension(hub): Extension<Arc<CollabHub>>,
+    Query(query): Query<CollabQuery>,
+) -> Result<Response, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let client = hub.next_client.fetch_add(1, Ordering::Relaxed);
+    let name = query
+        .name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| format!("editor-{client}"));
+    Ok(ws.on_upgrade(move |socket| run_session(socket, manager, hub, store, client, name)))
+}
+
+async fn run_session(
+    mut socket: WebSocket,
+    manager: Arc<XcStringsStoreManager>,
+    hub: Arc<CollabHub>,
+    store: Arc<XcStringsStore>,
+    client: u64,
+    name: String,
+) {
+    let path = store.path().to_path_buf();
+    let mut store_events = manager.subscribe();
+    let mut presence = hub.presence.subscribe();
+
+    let welcome = ServerMessage::Welcome {
+        client,
+        path: path_token(manager.as_ref(), &path),
+        revision: store.revision(),
+        editors: hub.editors_of(&path).await,
+    };
+    if send(&mut socket, &welcome).await.is_err() {
+        return;
+    }
+
+    loop {
+        let outgoing = tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    handle_message(&text, &store, &hub, client, &name).await
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => None,
+            },
+            event = store_events.recv() => match event {
+                Ok(event) if event.path() == path => match event {
+                    StoreEvent::Saved { change, .. } => Some(ServerMessage::Change { change }),
+                    StoreEvent::ExternalChange { .. } => Some(ServerMessage::ExternalChange),
+                    // QA findings are announced on `/api/events`; editing sessions don't need them.
+                    StoreEvent::QaFindings { .. } => None,
+                },
+                Ok(_) => None,
+                Err(broadcast::error::RecvError::Lagged(_)) => Some(ServerMessage::Resync),
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            update = presence.recv() => match update {
+                Ok(update) if update.path == path && update.client != client => {
+                    Some(ServerMessage::Presence {
+                        key: update.key,
+                        client: update.client,
+                        name: update.name,
+                        editing: update.editing,
+                    })
+                }
+                Ok(_) => None,
+                Err(broadcast::error::RecvError::Lagged(_)) => Some(ServerMessage::Resync),
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        };
+        if let Some(message) = outgoing {
+            if send(&mut socket, &message).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    hub.leave(&path, client, &name).await;
+}
+
+async fn handle_message(
+    text: &str,
+    store: &XcStringsStore,
+    hub: &CollabHub,
+    client: u64,
+    name: &str,
+) -> Option<ServerMessage> {
+    let message = match serde_json::from_str::<ClientMessage>(text) {
+        Ok(message) => message,
+        Err(err) => {
+            return Some(ServerMessage::Error {
+                id: None,
+                status: 400,
+                error: format!("Invalid message: {err}"),
+            });
+        }
+    };
+
+    let (id, result) = match message {
+        ClientMessage::Editing { key } => {
+            hub.set_editing(store.path(), &key, client, name, true)
+                .await;
+            return None;
+        }
+        ClientMessage::Idle { key } => {
+            hub.set_editing(store.path(), &key, client, name, false)
+                .await;
+            return None;
+        }
+        ClientMessage::Upsert {
+            id,
+            revision,
+            request,
+        } => {
+            let key = request.key.clone();
+            let language = request.language.clone();
+            let allow_create = request.allow_create.unwrap_or(true);
+            let comment = request.comment.clone();
+            let position = request.insert_position.clone();
+            let update = (*request).into_update();
+            let result = match expecting(store, revision) {
+                Ok(writer) => writer
+                    .upsert_translation_with(
+                        &key,
+                        &language,
+                        update,
+                        comment,
+                        &position,
+                        allow_create,
+                    )
+                    .await
+                    .map(|_| writer.written_revision())
+                    .map_err(ApiError::from),
+                Err(err) => Err(err),
+            };
+            (id, result)
+        }
+        ClientMessage::DeleteTranslation {
+            id,
+            revision,
+            key,
+            language,
+        } => {
+            let result = match expecting(store, revision) {
+                Ok(writer) => writer
+                    .delete_translation(&key, &language)
+                    .await
+                    .map(|()| writer.written_revision())
+                    .map_err(ApiError::from),
+                Err(err) => Err(err),
+            };
+            (id, result)
+        }
+        ClientMessage::DeleteKey { id, revision, key } => {
+            let result = match expecting(store, revision) {
+                Ok(writer) => writer
+                    .delete_key(&key)
+                    .await
+                    .map(|()| writer.written_revision())
+                    .map_err(ApiError::from),
+                Err(err) => Err(err),
+            };
+            (id, result)
+        }
+    };
+
+    Some(match result {
+        Ok(revision) => ServerMessage::Ack { id, revision },
+        Err(err) => ServerMessage::Error {
+            id,
+            status: err.status.as_u16(),
+            error: err.message,
+        },
+    })
+}
+
+/// A handle that refuses the edit unless the catalog is still at `revision`, as `If-Match`
+/// does for HTTP writes; edits without one are refused with 428.
+fn expecting(store: &XcStringsStore, revision: Option<String>) -> Result<XcStringsStore, ApiError> {
+    let Some(revision) = revision else {
+        return Err(ApiError {
+            status: StatusCode::PRECONDITION_REQUIRED,
+            message: "revision is required; send the one from the welcome message or the last \
+                      ack, or * to overwrite unconditionally"
+                .to_string(),
+        });
+    };
+    Ok(store.expecting(vec![revision]))
+}
+
+async fn send(socket: &mut WebSocket, message: &ServerMessage) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(message).unwrap_or_else(|_| "{}".to_string());
+    socket.send(Message::Text(text)).await
+}
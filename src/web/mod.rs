@@ -1,22 +1,58 @@
-use std::{net::SocketAddr, sync::Arc};
+mod auth;
+mod collab;
+mod openapi;
+mod webhook;
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashSet},
+    convert::Infallible,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::Arc,
+};
 
 use indexmap::IndexMap;
 
 use axum::{
-    extract::{Path, Query},
-    http::StatusCode,
-    response::{Html, IntoResponse},
-    routing::{delete, get, post},
+    extract::{NestedPath, Path, Query},
+    http::{header, HeaderMap, HeaderName, StatusCode},
+    middleware,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse,
+    },
+    routing::{get, patch, post},
     Extension, Json, Router,
 };
+use axum_server::tls_rustls::RustlsConfig;
 use serde::{Deserialize, Deserializer, Serialize};
 use tokio::net::TcpListener;
-use tracing::info;
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream, StreamExt,
+};
+use tower_http::{compression::CompressionLayer, services::ServeDir};
+use tracing::{debug, info};
+use utoipa::{IntoParams, ToSchema};
+
+pub use auth::WebAuth;
 
+use crate::analytics::{self, CostEstimate, LengthReport};
+use crate::assignments::Assignments;
+use crate::audit::AuditEntry;
+use crate::digest::Digest;
+#[cfg(feature = "git")]
+use crate::git::{self, KeyHistory, OutdatedReport};
+use crate::modified;
+use crate::qa::{Finding, QaOptions, QaReport, QaRule};
+use crate::request_trace;
 use crate::store::{
-    StoreError, SubstitutionUpdate, TranslationRecord, TranslationUpdate, TranslationValue,
-    XcStringsStore, XcStringsStoreManager,
+    self, BatchOperation, BatchOutcome, FindReplace, InsertPosition, KeyNamespace, Replacement,
+    StoreChange, StoreError, StoreEvent, SubstitutionUpdate, TranslationRecord, TranslationSummary,
+    TranslationUpdate, TranslationValue, VariationWarning, XcStringsStore, XcStringsStoreManager,
 };
+use crate::suggest::{self, Suggestion};
 
 /// Custom deserializer for Option<Option<T>> that properly handles JSON null values.
 /// - JSON null -> Some(None) (explicitly set to null/delete)
@@ -31,52 +67,322 @@ where
     Ok(Some(Option::<T>::deserialize(deserializer)?))
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct ErrorResponse {
     error: String,
 }
 
-#[derive(Debug, Deserialize)]
+/// Page size used when `page` is given without `per_page`.
+const DEFAULT_PER_PAGE: usize = 100;
+/// Pseudo-state matching languages that have no value for a key yet.
+const MISSING_STATE: &str = "missing";
+
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct ListQuery {
     q: Option<String>,
     #[serde(default)]
     path: Option<String>,
+    /// 1-based page number; without `page` or `per_page` every match is returned.
+    #[serde(default)]
+    page: Option<usize>,
+    #[serde(default)]
+    per_page: Option<usize>,
+    #[serde(default)]
+    sort: Option<SortOrder>,
+    /// Translation state to keep, e.g. `needs-translation`, or `missing` for absent values.
+    #[serde(default)]
+    state: Option<String>,
+    /// Language the `state` filter applies to; any language when omitted.
+    #[serde(default)]
+    language: Option<String>,
+    /// Comma-separated languages to include in each record, e.g. `de,fr`; all when omitted.
+    #[serde(default)]
+    languages: Option<String>,
+    /// Translator whose assigned keys to keep.
+    #[serde(default)]
+    assignee: Option<String>,
+    /// Keep keys changed through the server at or after this RFC 3339 timestamp or
+    /// `YYYY-MM-DD` date.
+    #[serde(default)]
+    modified_since: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+enum SortOrder {
+    /// Order of the keys in the catalog file.
+    #[default]
+    #[serde(rename = "file")]
+    File,
+    #[serde(rename = "key")]
+    Key,
+    #[serde(rename = "-key")]
+    KeyDesc,
+}
+
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct SummariesQuery {
+    q: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    page: Option<usize>,
+    #[serde(default)]
+    per_page: Option<usize>,
+    /// Translator whose assigned keys to keep.
+    #[serde(default)]
+    assignee: Option<String>,
+    /// Keep keys changed through the server at or after this RFC 3339 timestamp or
+    /// `YYYY-MM-DD` date.
+    #[serde(default)]
+    modified_since: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
+struct PageInfo {
+    /// Items matching the search and filters, before pagination.
+    total: usize,
+    page: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    per_page: Option<usize>,
+    total_pages: usize,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 struct TranslationsResponse {
     items: Vec<TranslationRecord>,
+    /// Keys in the whole catalog.
+    total_keys: usize,
+    #[serde(flatten)]
+    pagination: PageInfo,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct SummariesResponse {
+    items: Vec<TranslationSummary>,
+    total_keys: usize,
+    #[serde(flatten)]
+    pagination: PageInfo,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct FileEntryResponse {
     path: String,
     label: String,
+    /// Targets of the Xcode project that build this catalog, in project mode.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    targets: Vec<String>,
+    /// Added with `POST /api/files` rather than found under the search root.
+    registered: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct FilesResponse {
     files: Vec<FileEntryResponse>,
     default: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ActivityQuery {
+    /// Only entries recorded at or after this RFC 3339 timestamp or `YYYY-MM-DD` date (UTC).
+    #[serde(default)]
+    since: Option<String>,
+    /// Most entries to return; 50 when omitted.
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct DigestQuery {
+    /// Start of the period: an RFC 3339 timestamp or `YYYY-MM-DD` date (UTC).
+    since: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct ActivityResponse {
+    path: String,
+    /// Newest first.
+    entries: Vec<AuditEntry>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 struct LanguagesResponse {
     languages: Vec<String>,
+    /// Languages signed off for release; edits to them need `force`.
+    frozen: Vec<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct TranslationPercentagesResponse {
     percentages: std::collections::HashMap<String, f64>,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Serialize, ToSchema)]
+struct LanguageProgress {
+    language: String,
+    percentage: f64,
+    /// Keys without a value in this language.
+    untranslated: usize,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct ProgressResponse {
+    languages: Vec<LanguageProgress>,
+}
+
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct UntranslatedQuery {
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    lang: Option<String>,
+    /// Also list keys marked `shouldTranslate: false`.
+    #[serde(default)]
+    include_do_not_translate: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct UntranslatedResponse {
+    untranslated: BTreeMap<String, Vec<String>>,
+}
+
+/// Data of one `/api/events` message; the SSE event name carries the kind of event.
+#[derive(Debug, Serialize, ToSchema)]
+struct StoreEventPayload {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    change: Option<StoreChange>,
+    /// New errors and warnings, on `qa_findings` events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    findings: Option<Vec<Finding>>,
+}
+
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ValidateQuery {
+    #[serde(default)]
+    path: Option<String>,
+    /// Only report findings for this language.
+    #[serde(default)]
+    lang: Option<String>,
+    /// Comma-separated rules to run, e.g. `plural,placeholders`; all rules when omitted.
+    #[serde(default)]
+    rule: Option<String>,
+    /// Only report findings for this key.
+    #[serde(default)]
+    key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct SuggestionsQuery {
+    #[serde(default)]
+    path: Option<String>,
+    key: String,
+    language: String,
+    /// Suggestions to return; 10 when omitted.
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct SuggestionsResponse {
+    /// The text being translated: the key's source-language value, or the key itself.
+    source_text: String,
+    suggestions: Vec<Suggestion>,
+}
+
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct LengthsQuery {
+    #[serde(default)]
+    path: Option<String>,
+    /// Only report on this language.
+    #[serde(default)]
+    language: Option<String>,
+    /// Longest texts to list per language; 10 when omitted.
+    #[serde(default)]
+    top: Option<usize>,
+}
+
+#[cfg(feature = "git")]
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct HistoryQuery {
+    #[serde(default)]
+    path: Option<String>,
+    language: String,
+    /// Recent commits to search; 100 when omitted.
+    #[serde(default)]
+    depth: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct CostQuery {
+    #[serde(default)]
+    path: Option<String>,
+    /// Only price this language; every language but the source when omitted.
+    #[serde(default)]
+    language: Option<String>,
+    /// Vendor price per source word.
+    rate_per_word: f64,
+    /// Count only untranslated keys, leaving out those needing review.
+    #[serde(default)]
+    only_untranslated: bool,
+    /// Also price keys marked `shouldTranslate: false`.
+    #[serde(default)]
+    include_do_not_translate: bool,
+}
+
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct PathQuery {
     #[serde(default)]
     path: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ProgressQuery {
+    #[serde(default)]
+    path: Option<String>,
+    /// Also count keys marked `shouldTranslate: false`.
+    #[serde(default)]
+    include_do_not_translate: bool,
+}
+
+#[derive(Debug, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ForceQuery {
+    #[serde(default)]
+    path: Option<String>,
+    /// Deletes even when the language is frozen.
+    #[serde(default)]
+    force: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+struct FreezeRequest {
+    language: String,
+    /// Freezes even when keys still need work; ignored when unfreezing.
+    #[serde(default)]
+    force: bool,
+    #[serde(default)]
+    path: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct FrozenResponse {
+    frozen: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 struct UpsertRequest {
     key: String,
     language: String,
@@ -87,44 +393,71 @@ struct UpsertRequest {
         skip_serializing_if = "Option::is_none",
         default
     )]
+    #[schema(value_type = Option<String>)]
     value: Option<Option<String>>,
     #[serde(
         deserialize_with = "deserialize_explicit_option",
         skip_serializing_if = "Option::is_none",
         default
     )]
+    #[schema(value_type = Option<String>)]
     state: Option<Option<String>>,
+    #[schema(no_recursion)]
     #[serde(default)]
     variations: Option<IndexMap<String, IndexMap<String, VariationUpdatePayload>>>,
+    /// Substitutions to update by name; `null` removes one.
     #[serde(default)]
+    #[schema(value_type = Option<IndexMap<String, SubstitutionUpdatePayload>>)]
     substitutions: Option<IndexMap<String, Option<SubstitutionUpdatePayload>>>,
+    /// Writes even when the language is frozen.
+    #[serde(default)]
+    force: bool,
+    /// Set to false to only update existing keys; unknown keys answer `404` instead of
+    /// being created. Defaults to true.
+    #[serde(default)]
+    allow_create: Option<bool>,
+    /// Developer comment for a key this upsert creates; existing keys keep theirs.
+    /// Required for new keys when the server is configured with `requireComment`.
+    #[serde(default)]
+    comment: Option<String>,
+    /// Where a key this upsert creates goes: `"end"` (the default), `"sorted"`, or
+    /// `{"after": "other.key"}`.
+    #[serde(default)]
+    insert_position: InsertPosition,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct VariationUpdatePayload {
     #[serde(
         deserialize_with = "deserialize_explicit_option",
         skip_serializing_if = "Option::is_none",
         default
     )]
+    #[schema(value_type = Option<String>)]
     value: Option<Option<String>>,
     #[serde(
         deserialize_with = "deserialize_explicit_option",
         skip_serializing_if = "Option::is_none",
         default
     )]
+    #[schema(value_type = Option<String>)]
     state: Option<Option<String>>,
+    #[schema(no_recursion)]
     #[serde(default)]
     variations: Option<IndexMap<String, IndexMap<String, VariationUpdatePayload>>>,
+    /// Substitutions to update by name; `null` removes one.
     #[serde(default)]
+    #[schema(value_type = Option<IndexMap<String, SubstitutionUpdatePayload>>)]
     substitutions: Option<IndexMap<String, Option<SubstitutionUpdatePayload>>>,
 }
 
 impl VariationUpdatePayload {
     fn into_update(self) -> TranslationUpdate {
-        let mut update = TranslationUpdate::default();
-        update.state = self.state;
-        update.value = self.value;
+        let mut update = TranslationUpdate {
+            state: self.state,
+            value: self.value,
+            ..Default::default()
+        };
         if let Some(variations) = self.variations {
             update.variations = Some(
                 variations
@@ -151,43 +484,50 @@ impl VariationUpdatePayload {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct SubstitutionUpdatePayload {
     #[serde(
         deserialize_with = "deserialize_explicit_option",
         skip_serializing_if = "Option::is_none",
         default
     )]
+    #[schema(value_type = Option<String>)]
     value: Option<Option<String>>,
     #[serde(
         deserialize_with = "deserialize_explicit_option",
         skip_serializing_if = "Option::is_none",
         default
     )]
+    #[schema(value_type = Option<String>)]
     state: Option<Option<String>>,
     #[serde(
         rename = "argNum",
         default,
         deserialize_with = "deserialize_explicit_option"
     )]
+    #[schema(value_type = Option<i64>)]
     arg_num: Option<Option<i64>>,
     #[serde(
         rename = "formatSpecifier",
         default,
         deserialize_with = "deserialize_explicit_option"
     )]
+    #[schema(value_type = Option<String>)]
     format_specifier: Option<Option<String>>,
+    #[schema(no_recursion)]
     #[serde(default)]
     variations: Option<IndexMap<String, IndexMap<String, VariationUpdatePayload>>>,
 }
 
 impl SubstitutionUpdatePayload {
     fn into_update(self) -> SubstitutionUpdate {
-        let mut update = SubstitutionUpdate::default();
-        update.value = self.value;
-        update.state = self.state;
-        update.arg_num = self.arg_num;
-        update.format_specifier = self.format_specifier;
+        let mut update = SubstitutionUpdate {
+            value: self.value,
+            state: self.state,
+            arg_num: self.arg_num,
+            format_specifier: self.format_specifier,
+            ..Default::default()
+        };
         if let Some(variations) = self.variations {
             update.variations = Some(
                 variations
@@ -208,9 +548,11 @@ impl SubstitutionUpdatePayload {
 
 impl UpsertRequest {
     fn into_update(self) -> TranslationUpdate {
-        let mut update = TranslationUpdate::default();
-        update.state = self.state;
-        update.value = self.value;
+        let mut update = TranslationUpdate {
+            state: self.state,
+            value: self.value,
+            ..Default::default()
+        };
         if let Some(variations) = self.variations {
             update.variations = Some(
                 variations
@@ -237,7 +579,111 @@ impl UpsertRequest {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+struct BatchRequest {
+    #[serde(default)]
+    path: Option<String>,
+    operations: Vec<BatchItemRequest>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum BatchItemRequest {
+    /// Same fields as `PUT /api/translations`; a per-item `path` is ignored.
+    Upsert(Box<UpsertRequest>),
+    DeleteTranslation {
+        key: String,
+        language: String,
+    },
+    DeleteKey {
+        key: String,
+    },
+}
+
+impl BatchItemRequest {
+    fn into_operation(self) -> BatchOperation {
+        match self {
+            BatchItemRequest::Upsert(request) => BatchOperation::Upsert {
+                key: request.key.clone(),
+                language: request.language.clone(),
+                comment: request.comment.clone(),
+                position: request.insert_position.clone(),
+                update: Box::new(request.into_update()),
+            },
+            BatchItemRequest::DeleteTranslation { key, language } => {
+                BatchOperation::DeleteTranslation { key, language }
+            }
+            BatchItemRequest::DeleteKey { key } => BatchOperation::DeleteKey { key },
+        }
+    }
+}
+
+/// The translation after an upsert, plus any variations the schema made it drop.
+#[derive(Debug, Serialize, ToSchema)]
+struct UpsertResponse {
+    #[serde(flatten)]
+    value: TranslationValue,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<VariationWarning>,
+    /// Languages sent back to review because the source text changed.
+    #[serde(rename = "needsReview", skip_serializing_if = "Vec::is_empty")]
+    needs_review: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct BatchItemResponse {
+    index: usize,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<TranslationValue>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<VariationWarning>,
+    /// Languages sent back to review because the source text changed.
+    #[serde(rename = "needsReview", skip_serializing_if = "Vec::is_empty")]
+    needs_review: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct BatchResponse {
+    /// False when any operation failed; nothing was written in that case.
+    committed: bool,
+    results: Vec<BatchItemResponse>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+struct FindReplaceRequest {
+    find: String,
+    #[serde(default)]
+    replace: String,
+    #[serde(rename = "caseSensitive", default)]
+    case_sensitive: bool,
+    #[serde(default)]
+    language: Option<String>,
+    /// Report what would change without writing.
+    #[serde(default)]
+    preview: bool,
+    #[serde(default)]
+    path: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct FindReplaceResponse {
+    preview: bool,
+    keys: Vec<String>,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+struct RegisterFileRequest {
+    /// Absolute path of an existing `.xcstrings` file.
+    path: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 struct CommentRequest {
     key: String,
     comment: Option<String>,
@@ -245,14 +691,55 @@ struct CommentRequest {
     path: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+struct ReviewRequest {
+    key: String,
+    language: String,
+    /// Why the translation was rejected; required by `/api/translations/reject`.
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+struct AssignRequest {
+    /// Translator to assign; omit or leave blank to remove the assignments instead.
+    #[serde(default)]
+    assignee: Option<String>,
+    /// Keys to assign to the translator.
+    #[serde(default)]
+    keys: Vec<String>,
+    /// Languages the translator owns.
+    #[serde(default)]
+    languages: Vec<String>,
+    #[serde(default)]
+    path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+struct CreateKeyRequest {
+    key: String,
+    #[serde(default)]
+    comment: Option<String>,
+    /// Initial value in the catalog's source language.
+    #[serde(default)]
+    value: Option<String>,
+    /// Where the key goes: `"end"` (the default), `"sorted"`, or `{"after": "other.key"}`.
+    #[serde(default)]
+    insert_position: InsertPosition,
+    #[serde(default)]
+    path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 struct RenameKeyRequest {
     new_key: String,
     #[serde(default)]
     path: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct ExtractionStateRequest {
     key: String,
     #[serde(rename = "extractionState", default)]
@@ -261,7 +748,7 @@ struct ExtractionStateRequest {
     path: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct ShouldTranslateRequest {
     key: String,
     #[serde(rename = "shouldTranslate", default)]
@@ -270,38 +757,256 @@ struct ShouldTranslateRequest {
     path: Option<String>,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+struct VersionResponse {
+    version: &'static str,
+    #[serde(rename = "gitSha")]
+    git_sha: &'static str,
+    #[serde(rename = "buildDate")]
+    build_date: &'static str,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct ReadinessCheck {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+impl ReadinessCheck {
+    fn from_result<E: std::fmt::Display>(result: Result<(), E>) -> Self {
+        match result {
+            Ok(()) => ReadinessCheck {
+                ok: true,
+                detail: None,
+            },
+            Err(err) => ReadinessCheck {
+                ok: false,
+                detail: Some(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct ReadinessResponse {
+    ready: bool,
+    #[serde(rename = "defaultCatalog")]
+    default_catalog: ReadinessCheck,
+    #[serde(rename = "searchRoot")]
+    search_root: ReadinessCheck,
+}
+
+/// Settings for the web app beyond the catalogs it serves.
+#[derive(Debug, Clone, Default)]
+pub struct WebOptions {
+    pub auth: WebAuth,
+    /// Glossary and other inputs for `/api/validate`.
+    pub qa: QaOptions,
+    /// Directory whose files are served in place of the embedded UI; paths it doesn't have
+    /// fall back to the embedded page and assets.
+    pub assets_dir: Option<PathBuf>,
+    /// Secret signing `POST /api/webhooks/translations`; the webhook is refused without one.
+    pub webhook_secret: Option<String>,
+}
+
+/// Builds the web app with default options: the API, the embedded UI, and the health
+/// checks. Other axum apps can serve it with `merge`, or under a prefix with `nest`; the
+/// UI uses relative URLs, so it keeps working at `/prefix`.
 pub fn router(manager: Arc<XcStringsStoreManager>) -> Router {
-    Router::new()
-        .route("/", get(index))
-        .route("/api/files", get(list_files))
+    router_with_options(manager, WebOptions::default())
+}
+
+/// Builds the web app, requiring `options.auth` on every `/api` route when it is enabled.
+/// The UI stays public so it can load and ask for credentials.
+pub fn router_with_options(manager: Arc<XcStringsStoreManager>, options: WebOptions) -> Router {
+    let WebOptions {
+        auth,
+        qa,
+        assets_dir,
+        webhook_secret,
+    } = options;
+    let mut api = Router::new()
+        .route(
+            "/api/files",
+            get(list_files).post(register_file).delete(unregister_file),
+        )
+        .route("/api/files/:path/activity", get(file_activity))
+        .route("/api/files/:path/digest", get(file_digest))
+        .route("/api/version", get(version))
+        .route("/api/openapi.json", get(openapi::openapi_json))
         .route(
             "/api/translations",
             get(list_translations).put(upsert_translation),
         )
+        .route("/api/summaries", get(list_summaries))
+        .route("/api/translations/batch", patch(apply_batch))
+        .route("/api/translations/approve", post(approve_translation))
+        .route("/api/translations/reject", post(reject_translation))
         .route(
             "/api/translations/:key/:language",
-            delete(delete_translation),
+            get(get_translation).delete(delete_translation),
+        )
+        .route("/api/keys", post(create_key))
+        .route("/api/assignments", get(get_assignments).put(assign))
+        .route("/api/keys/tree", get(get_key_tree))
+        .route(
+            "/api/keys/:key",
+            get(get_key)
+                .head(key_exists)
+                .delete(delete_key)
+                .put(rename_key),
         )
-        .route("/api/keys/:key", delete(delete_key).put(rename_key))
+        .route("/api/find-replace", post(find_replace))
         .route("/api/comments", post(update_comment))
         .route("/api/extraction-state", post(update_extraction_state))
         .route("/api/should-translate", post(update_should_translate))
         .route("/api/languages", get(list_languages))
+        .route("/api/languages/freeze", post(freeze_language))
+        .route("/api/languages/unfreeze", post(unfreeze_language))
         .route(
             "/api/translation-percentages",
             get(get_translation_percentages),
         )
+        .route("/api/progress", get(get_progress))
+        .route("/api/events", get(stream_events))
+        .route("/api/ws", get(collab::collab_socket))
+        .route("/api/untranslated", get(list_untranslated))
+        .route("/api/validate", get(validate_catalog))
+        .route("/api/suggestions", get(suggest_translations))
+        .route("/api/lengths", get(length_report))
+        .route("/api/cost", get(estimate_cost));
+    #[cfg(feature = "git")]
+    {
+        api = api.route("/api/keys/:key/history", get(key_history));
+        api = api.route("/api/outdated", get(list_outdated));
+    }
+    if auth.is_enabled() {
+        api = api.route_layer(middleware::from_fn_with_state(
+            Arc::new(auth),
+            auth::require_auth,
+        ));
+    }
+    // Webhooks authenticate by signature, so they sit outside the credentials layer.
+    let app = api
+        .route(
+            "/api/webhooks/translations",
+            post(webhook::receive_translations),
+        )
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route_layer(middleware::from_fn(report_normalization));
+    let app = match assets_dir {
+        Some(dir) => app.fallback_service(ServeDir::new(dir).fallback(embedded_ui())),
+        None => app.merge(embedded_ui()),
+    };
+    let app = app
+        .layer(Extension(Arc::new(collab::CollabHub::default())))
+        .layer(Extension(Arc::new(qa)))
+        .layer(Extension(Arc::new(webhook::WebhookSecret(webhook_secret))))
         .layer(Extension(manager))
+        // Negotiated via Accept-Encoding; event streams and tiny bodies are left as is.
+        .layer(CompressionLayer::new());
+    request_trace::layer(app)
 }
 
-pub async fn serve(addr: SocketAddr, manager: Arc<XcStringsStoreManager>) -> anyhow::Result<()> {
-    let app = router(manager);
-    info!(%addr, "Starting web UI");
-    let listener = TcpListener::bind(addr).await?;
-    axum::serve(listener, app.into_make_service()).await?;
+pub async fn serve(
+    addr: SocketAddr,
+    manager: Arc<XcStringsStoreManager>,
+    options: WebOptions,
+    tls: Option<RustlsConfig>,
+) -> anyhow::Result<()> {
+    let listener = bind(addr, &options).await?;
+    serve_listener(listener, manager, options, tls).await
+}
+
+/// Binds the web UI's TCP listener. Port 0 picks a free port; check the listener's local
+/// address for the port actually bound. Any other port fails when it's already taken.
+pub async fn bind(addr: SocketAddr, options: &WebOptions) -> anyhow::Result<TcpListener> {
+    use anyhow::Context;
+
+    if !options.auth.is_enabled() && !addr.ip().is_loopback() {
+        anyhow::bail!(
+            "refusing to serve the web UI on {addr} without authentication; set WEB_AUTH_TOKEN \
+             (or WEB_AUTH_USER and WEB_AUTH_PASSWORD), or bind WEB_HOST to 127.0.0.1"
+        );
+    }
+    TcpListener::bind(addr).await.with_context(|| {
+        format!("failed to bind the web UI to {addr}; set WEB_PORT=0 to pick a free port")
+    })
+}
+
+/// Serves the web app on a listener returned by [`bind`].
+pub async fn serve_listener(
+    listener: TcpListener,
+    manager: Arc<XcStringsStoreManager>,
+    options: WebOptions,
+    tls: Option<RustlsConfig>,
+) -> anyhow::Result<()> {
+    let url = url(listener.local_addr()?, tls.is_some());
+    let app = router_with_options(manager, options);
+    info!(%url, "Starting web UI");
+    if let Some(tls) = tls {
+        axum_server::from_tcp_rustls(listener.into_std()?, tls)
+            .serve(app.into_make_service())
+            .await?;
+    } else {
+        axum::serve(listener, app.into_make_service()).await?;
+    }
     Ok(())
 }
 
+/// Browser URL for a bound address. A wildcard host such as `0.0.0.0` is replaced with
+/// loopback, since it can't be browsed to.
+pub fn url(addr: SocketAddr, tls: bool) -> String {
+    let mut addr = addr;
+    if addr.ip().is_unspecified() {
+        addr.set_ip(match addr {
+            SocketAddr::V4(_) => std::net::Ipv4Addr::LOCALHOST.into(),
+            SocketAddr::V6(_) => std::net::Ipv6Addr::LOCALHOST.into(),
+        });
+    }
+    let scheme = if tls { "https" } else { "http" };
+    format!("{scheme}://{addr}/")
+}
+
+/// Serves the web app on a Unix domain socket instead of a TCP port. A stale socket file
+/// left by a previous run is replaced.
+#[cfg(unix)]
+pub async fn serve_unix(
+    path: &std::path::Path,
+    manager: Arc<XcStringsStoreManager>,
+    options: WebOptions,
+) -> anyhow::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    use anyhow::Context;
+    use hyper_util::{rt::TokioIo, service::TowerToHyperService};
+    use tokio::net::UnixListener;
+
+    if std::fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_socket()) {
+        std::fs::remove_file(path)
+            .with_context(|| format!("failed to remove stale socket {}", path.display()))?;
+    }
+    let listener = UnixListener::bind(path)
+        .with_context(|| format!("failed to bind web socket {}", path.display()))?;
+    let app = router_with_options(manager, options);
+    info!(path = %path.display(), "Starting web UI on Unix socket");
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let service = TowerToHyperService::new(app.clone());
+        tokio::spawn(async move {
+            let connection = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .with_upgrades();
+            if let Err(err) = connection.await {
+                debug!(?err, "Web UI connection on Unix socket ended with error");
+            }
+        });
+    }
+}
+
 fn path_token(manager: &XcStringsStoreManager, path: &std::path::Path) -> String {
     if let Ok(relative) = path.strip_prefix(manager.search_root()) {
         let display = relative.to_string_lossy();
@@ -312,17 +1017,51 @@ fn path_token(manager: &XcStringsStoreManager, path: &std::path::Path) -> String
     path.to_string_lossy().replace('\\', "/")
 }
 
-fn path_label(manager: &XcStringsStoreManager, path: &std::path::Path) -> String {
-    if let Ok(relative) = path.strip_prefix(manager.search_root()) {
-        let text = relative.to_string_lossy();
-        if !text.is_empty() {
-            return text.replace('\\', "/");
-        }
+/// A response carrying the catalog revision as its `ETag`.
+type Tagged<T> = ([(HeaderName, String); 1], T);
+
+/// Session label for changes made through the web API in the audit log.
+const WEB_SESSION: &str = "web";
+
+/// Records a successful web write in the audit log and git auto-commits, tagged with the
+/// request's correlation id.
+async fn record_audit(
+    manager: &XcStringsStoreManager,
+    headers: &HeaderMap,
+    store: &XcStringsStore,
+    action: &str,
+    details: serde_json::Value,
+) {
+    if !manager.records_changes() {
+        return;
     }
-    path.file_name()
-        .and_then(|value| value.to_str())
-        .map(|value| value.to_string())
-        .unwrap_or_else(|| path.to_string_lossy().replace('\\', "/"))
+    let mut entry = AuditEntry::new(WEB_SESSION, action);
+    entry.request_id = request_trace::request_id(headers);
+    entry.path = Some(store.path().to_string_lossy().to_string());
+    entry.details = details;
+    manager.record_change(&entry).await;
+}
+
+/// Returns a handle to `store` whose writes fail with 412 unless the catalog is still at
+/// the revision `If-Match` names (or it is `*`), so a client working from a stale copy
+/// can't overwrite newer edits. The store compares it under its edit lock.
+fn check_if_match(headers: &HeaderMap, store: &XcStringsStore) -> Result<XcStringsStore, ApiError> {
+    let Some(if_match) = headers
+        .get(header::IF_MATCH)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return Err(ApiError {
+            status: StatusCode::PRECONDITION_REQUIRED,
+            message: "If-Match header is required; send the ETag from a previous GET, or * to \
+                      overwrite unconditionally"
+                .to_string(),
+        });
+    };
+    let tags = if_match
+        .split(',')
+        .map(|tag| tag.trim().to_string())
+        .collect();
+    Ok(store.expecting(tags))
 }
 
 async fn resolve_store(
@@ -332,21 +1071,126 @@ async fn resolve_store(
     manager.store_for(path).await.map_err(ApiError::from)
 }
 
-async fn index() -> Html<&'static str> {
-    Html(INDEX_HTML)
+/// The UI compiled into the binary.
+fn embedded_ui() -> Router {
+    Router::new()
+        .route("/", get(index))
+        .route("/assets/app.js", get(app_js))
+        .route("/assets/app.css", get(app_css))
+}
+
+/// The UI page. Nested under a prefix, it's served at `/prefix` rather than `/prefix/`, so
+/// a `<base>` points its relative asset and API URLs back under the prefix.
+async fn index(nested: Option<NestedPath>) -> Html<Cow<'static, str>> {
+    match nested {
+        Some(nested) => Html(Cow::Owned(INDEX_HTML.replacen(
+            "<head>",
+            &format!("<head>\n    <base href=\"{}/\" />", nested.as_str()),
+            1,
+        ))),
+        None => Html(Cow::Borrowed(INDEX_HTML)),
+    }
+}
+
+async fn app_js() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/javascript; charset=utf-8")],
+        APP_JS,
+    )
+}
+
+async fn app_css() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "text/css; charset=utf-8")], APP_CSS)
+}
+
+/// Liveness probe: answers as long as the process is serving requests.
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "server",
+    security(()),
+    responses((status = 200, description = "The server is up", body = String, content_type = "text/plain"))
+)]
+async fn healthz() -> &'static str {
+    "ok"
+}
+
+/// Readiness probe: the default catalog (when configured) loads and the search root can be
+/// listed. Responds 503 with the failing check otherwise.
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "server",
+    security(()),
+    responses(
+        (status = 200, description = "Ready to serve requests", body = ReadinessResponse),
+        (status = 503, description = "A check failed", body = ReadinessResponse),
+    )
+)]
+async fn readyz(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let default_catalog = ReadinessCheck::from_result(
+        async {
+            if manager.default_path().is_none() {
+                return Ok(());
+            }
+            // Cached stores keep serving their last good copy when a reload fails, so re-read
+            // the file explicitly; a catalog that hasn't been saved yet is fine.
+            let store = manager.default_store().await?;
+            if tokio::fs::try_exists(store.path()).await? {
+                store.reload().await?;
+            }
+            Ok::<_, StoreError>(())
+        }
+        .await,
+    );
+    let search_root =
+        ReadinessCheck::from_result(tokio::fs::read_dir(manager.search_root()).await.map(|_| ()));
+    let ready = default_catalog.ok && search_root.ok;
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(ReadinessResponse {
+            ready,
+            default_catalog,
+            search_root,
+        }),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/version",
+    tag = "server",
+    responses((status = 200, description = "Build information", body = VersionResponse))
+)]
+async fn version() -> Json<VersionResponse> {
+    Json(VersionResponse {
+        version: env!("CARGO_PKG_VERSION"),
+        git_sha: env!("XCSTRINGS_GIT_SHA"),
+        build_date: env!("XCSTRINGS_BUILD_DATE"),
+    })
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/files",
+    tag = "catalogs",
+    responses((status = 200, description = "Catalogs found under the search root", body = FilesResponse))
+)]
 async fn list_files(
     Extension(manager): Extension<Arc<XcStringsStoreManager>>,
 ) -> Result<Json<FilesResponse>, ApiError> {
     let paths = manager.refresh_discovered_paths().await?;
-    let files = paths
-        .iter()
-        .map(|path| FileEntryResponse {
-            path: path_token(manager.as_ref(), path),
-            label: path_label(manager.as_ref(), path),
-        })
-        .collect();
+    let mut files = Vec::with_capacity(paths.len());
+    for path in &paths {
+        files.push(file_entry(manager.as_ref(), path).await);
+    }
     let default = manager
         .default_path()
         .as_ref()
@@ -355,116 +1199,1585 @@ async fn list_files(
     Ok(Json(FilesResponse { files, default }))
 }
 
-async fn list_translations(
-    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
-    Query(query): Query<ListQuery>,
-) -> Result<Json<TranslationsResponse>, ApiError> {
-    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
-    let items = store.list_records(query.q.as_deref()).await;
-    Ok(Json(TranslationsResponse { items }))
+async fn file_entry(manager: &XcStringsStoreManager, path: &std::path::Path) -> FileEntryResponse {
+    FileEntryResponse {
+        path: path_token(manager, path),
+        label: manager.catalog_label(path).await,
+        targets: manager.targets_for(path).await,
+        registered: manager.is_registered(path).await,
+    }
 }
 
-async fn list_languages(
+/// Lists a catalog outside the search root alongside the discovered ones, so the file picker
+/// can open it. Registrations last until the server restarts.
+#[utoipa::path(
+    post,
+    path = "/api/files",
+    tag = "catalogs",
+    request_body = RegisterFileRequest,
+    responses(
+        (status = 201, description = "The catalog is now listed", body = FileEntryResponse),
+        (status = 400, description = "Not an absolute path to a readable catalog", body = ErrorResponse),
+    )
+)]
+async fn register_file(
     Extension(manager): Extension<Arc<XcStringsStoreManager>>,
-    Query(query): Query<PathQuery>,
-) -> Result<Json<LanguagesResponse>, ApiError> {
-    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
-    let languages = store.list_languages().await;
-    Ok(Json(LanguagesResponse { languages }))
+    Json(payload): Json<RegisterFileRequest>,
+) -> Result<(StatusCode, Json<FileEntryResponse>), ApiError> {
+    let path = manager.register_path(payload.path.trim()).await?;
+    info!(path = %path.display(), "Registered catalog");
+    Ok((
+        StatusCode::CREATED,
+        Json(file_entry(manager.as_ref(), &path).await),
+    ))
 }
 
-async fn get_translation_percentages(
+/// Removes a catalog added with `POST /api/files` from the list.
+#[utoipa::path(
+    delete,
+    path = "/api/files",
+    tag = "catalogs",
+    params(PathQuery),
+    responses(
+        (status = 204, description = "The catalog is no longer listed"),
+        (status = 400, description = "`path` is missing", body = ErrorResponse),
+        (status = 404, description = "The catalog was not registered", body = ErrorResponse),
+    )
+)]
+async fn unregister_file(
     Extension(manager): Extension<Arc<XcStringsStoreManager>>,
     Query(query): Query<PathQuery>,
-) -> Result<Json<TranslationPercentagesResponse>, ApiError> {
-    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
-    let percentages = store.get_translation_percentages().await;
-    Ok(Json(TranslationPercentagesResponse { percentages }))
+) -> Result<StatusCode, ApiError> {
+    let Some(raw) = query.path.as_deref().filter(|path| !path.trim().is_empty()) else {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: "path is required".to_string(),
+        });
+    };
+    let path = manager.unregister_path(raw.trim()).await?;
+    info!(path = %path.display(), "Unregistered catalog");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Recent audit-log entries for one catalog: who changed what, and when.
+#[utoipa::path(
+    get,
+    path = "/api/files/{path}/activity",
+    tag = "catalogs",
+    params(("path" = String, Path, description = "Catalog path as listed by `GET /api/files`, URL-encoded"), ActivityQuery),
+    responses(
+        (status = 200, description = "Changes to the catalog, newest first", body = ActivityResponse),
+        (status = 400, description = "Invalid `since`", body = ErrorResponse),
+        (status = 404, description = "Catalog not found, or no audit log is configured", body = ErrorResponse),
+    )
+)]
+async fn file_activity(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Path(path): Path<String>,
+    Query(query): Query<ActivityQuery>,
+) -> Result<Json<ActivityResponse>, ApiError> {
+    let store = resolve_store(manager.as_ref(), Some(&path)).await?;
+    let Some(audit_log) = manager.audit_log() else {
+        return Err(ApiError {
+            status: StatusCode::NOT_FOUND,
+            message: "Activity comes from the audit log; set AUDIT_LOG to record it".to_string(),
+        });
+    };
+    let since = query.since.as_deref().map(parse_since).transpose()?;
+    let entries = audit_log
+        .activity(
+            store.path(),
+            since,
+            query.limit.unwrap_or(DEFAULT_ACTIVITY_LIMIT),
+        )
+        .await
+        .map_err(StoreError::ReadFailed)?;
+    Ok(Json(ActivityResponse {
+        path: path_token(manager.as_ref(), store.path()),
+        entries,
+    }))
+}
+
+/// A Markdown summary of the changes made to one catalog since a date, for pasting into
+/// Slack or release notes.
+#[utoipa::path(
+    get,
+    path = "/api/files/{path}/digest",
+    tag = "catalogs",
+    params(("path" = String, Path, description = "Catalog path as listed by `GET /api/files`, URL-encoded"), DigestQuery),
+    responses(
+        (status = 200, description = "New keys, translations updated per language, renames, and deletions", body = String, content_type = "text/markdown"),
+        (status = 400, description = "Invalid `since`", body = ErrorResponse),
+        (status = 404, description = "Catalog not found, or no audit log is configured", body = ErrorResponse),
+    )
+)]
+async fn file_digest(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Path(path): Path<String>,
+    Query(query): Query<DigestQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let store = resolve_store(manager.as_ref(), Some(&path)).await?;
+    let Some(audit_log) = manager.audit_log() else {
+        return Err(ApiError {
+            status: StatusCode::NOT_FOUND,
+            message: "Digests come from the audit log; set AUDIT_LOG to record it".to_string(),
+        });
+    };
+    let since = parse_since(&query.since)?;
+    let mut entries = audit_log
+        .activity(store.path(), Some(since), usize::MAX)
+        .await
+        .map_err(StoreError::ReadFailed)?;
+    entries.reverse();
+    let title = format!(
+        "{} since {}",
+        path_token(manager.as_ref(), store.path()),
+        query.since.trim()
+    );
+    Ok((
+        [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+        Digest::from_entries(&entries).to_markdown(&title),
+    ))
+}
+
+/// Entries returned by `GET /api/files/{path}/activity` without a `limit`.
+const DEFAULT_ACTIVITY_LIMIT: usize = 50;
+
+fn parse_since(since: &str) -> Result<chrono::DateTime<chrono::Utc>, ApiError> {
+    modified::parse_since(since).ok_or_else(|| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message: format!(
+            "Invalid since '{}'; expected an RFC 3339 timestamp or YYYY-MM-DD",
+            since.trim()
+        ),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/translations",
+    tag = "translations",
+    params(ListQuery),
+    responses(
+        (status = 200, description = "Matching keys with their translations", body = TranslationsResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+    )
+)]
+async fn list_translations(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<ListQuery>,
+) -> Result<Tagged<Json<TranslationsResponse>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let total_keys = store.keys().await.len();
+    let mut items = store.list_records(query.q.as_deref()).await;
+    if let Some(assignments) = assignments_filter(&store, query.assignee.as_deref()).await? {
+        items.retain(|record| assignments(&record.key));
+    }
+    if let Some(since) = query
+        .modified_since
+        .as_deref()
+        .map(parse_since)
+        .transpose()?
+    {
+        items.retain(|record| {
+            record
+                .modified
+                .as_ref()
+                .is_some_and(|modified| modified.is_since(since))
+        });
+    }
+
+    if let Some(state) = query
+        .state
+        .as_deref()
+        .map(str::trim)
+        .filter(|state| !state.is_empty())
+    {
+        let languages = match query.language.as_deref() {
+            Some(language) => vec![language.to_string()],
+            None => store.list_languages().await,
+        };
+        items.retain(|record| {
+            languages
+                .iter()
+                .any(|language| record_matches_state(record, language, state))
+        });
+    }
+
+    match query.sort.unwrap_or_default() {
+        SortOrder::File => {}
+        SortOrder::Key => items.sort_by(|a, b| a.key.cmp(&b.key)),
+        SortOrder::KeyDesc => items.sort_by(|a, b| b.key.cmp(&a.key)),
+    }
+
+    let (mut items, pagination) = paginate(items, query.page, query.per_page);
+
+    if let Some(languages) = query.languages.as_deref() {
+        let wanted: HashSet<&str> = languages
+            .split(',')
+            .map(str::trim)
+            .filter(|language| !language.is_empty())
+            .collect();
+        if !wanted.is_empty() {
+            for record in &mut items {
+                record
+                    .translations
+                    .retain(|language, _| wanted.contains(language.as_str()));
+                record.matches.retain(|hit| {
+                    hit.language
+                        .as_deref()
+                        .is_none_or(|language| wanted.contains(language))
+                });
+            }
+        }
+    }
+
+    Ok((
+        [(header::ETAG, etag)],
+        Json(TranslationsResponse {
+            items,
+            total_keys,
+            pagination,
+        }),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/summaries",
+    tag = "translations",
+    params(SummariesQuery),
+    responses(
+        (status = 200, description = "Matching keys with per-language states", body = SummariesResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+    )
+)]
+async fn list_summaries(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<SummariesQuery>,
+) -> Result<Tagged<Json<SummariesResponse>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let total_keys = store.keys().await.len();
+    let mut summaries = store.list_summaries(query.q.as_deref()).await;
+    if let Some(assignments) = assignments_filter(&store, query.assignee.as_deref()).await? {
+        summaries.retain(|summary| assignments(&summary.key));
+    }
+    if let Some(since) = query
+        .modified_since
+        .as_deref()
+        .map(parse_since)
+        .transpose()?
+    {
+        summaries.retain(|summary| {
+            summary
+                .modified
+                .as_deref()
+                .is_some_and(|modified| modified::is_since(modified, since))
+        });
+    }
+    let (items, pagination) = paginate(summaries, query.page, query.per_page);
+    Ok((
+        [(header::ETAG, etag)],
+        Json(SummariesResponse {
+            items,
+            total_keys,
+            pagination,
+        }),
+    ))
+}
+
+/// Matches the keys assigned to `assignee`, or `None` when no assignee filter was given.
+async fn assignments_filter(
+    store: &XcStringsStore,
+    assignee: Option<&str>,
+) -> Result<Option<impl Fn(&str) -> bool>, ApiError> {
+    let Some(assignee) = assignee.map(str::trim).filter(|name| !name.is_empty()) else {
+        return Ok(None);
+    };
+    let assignments = store.assignments().await?;
+    let assignee = assignee.to_string();
+    Ok(Some(move |key: &str| {
+        assignments.is_assigned(key, &assignee)
+    }))
+}
+
+/// Cuts out the requested 1-based page. Without `page` or `per_page` every item is kept.
+fn paginate<T>(items: Vec<T>, page: Option<usize>, per_page: Option<usize>) -> (Vec<T>, PageInfo) {
+    let total = items.len();
+    let per_page = match (page, per_page) {
+        (None, None) => None,
+        (_, per_page) => Some(per_page.unwrap_or(DEFAULT_PER_PAGE).max(1)),
+    };
+    let page = page.unwrap_or(1).max(1);
+    let (items, total_pages) = match per_page {
+        Some(per_page) => (
+            items
+                .into_iter()
+                .skip((page - 1).saturating_mul(per_page))
+                .take(per_page)
+                .collect(),
+            total.div_ceil(per_page),
+        ),
+        None => (items, usize::from(total > 0)),
+    };
+    (
+        items,
+        PageInfo {
+            total,
+            page,
+            per_page,
+            total_pages,
+        },
+    )
+}
+
+/// True when `language` of the record is in `state` at the top level or in any variation or
+/// substitution. `missing` matches languages without a value.
+fn record_matches_state(record: &TranslationRecord, language: &str, state: &str) -> bool {
+    let Some(translation) = record.translations.get(language) else {
+        return state == MISSING_STATE;
+    };
+    if state == MISSING_STATE {
+        return translation_is_missing(translation);
+    }
+    translation_has_state(translation, state)
+}
+
+fn translation_is_missing(translation: &TranslationValue) -> bool {
+    translation
+        .value
+        .as_deref()
+        .is_none_or(|value| value.trim().is_empty())
+        && translation.variations.is_empty()
+        && translation.substitutions.is_empty()
+}
+
+fn translation_has_state(translation: &TranslationValue, state: &str) -> bool {
+    translation.state.as_deref() == Some(state)
+        || translation
+            .variations
+            .values()
+            .flat_map(|cases| cases.values())
+            .any(|case| translation_has_state(case, state))
+        || translation.substitutions.values().any(|substitution| {
+            substitution.state.as_deref() == Some(state)
+                || substitution
+                    .variations
+                    .values()
+                    .flat_map(|cases| cases.values())
+                    .any(|case| translation_has_state(case, state))
+        })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/languages",
+    tag = "catalogs",
+    params(PathQuery),
+    responses(
+        (status = 200, description = "Languages in the catalog", body = LanguagesResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+    )
+)]
+async fn list_languages(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<PathQuery>,
+) -> Result<Tagged<Json<LanguagesResponse>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let languages = store.list_languages().await;
+    Ok((
+        [(header::ETAG, etag)],
+        Json(LanguagesResponse {
+            languages,
+            frozen: store.frozen_languages(),
+        }),
+    ))
+}
+
+/// Freezes a language signed off for release. Fails with `409` while keys still need work,
+/// unless `force` is set.
+#[utoipa::path(
+    post,
+    path = "/api/languages/freeze",
+    tag = "catalogs",
+    request_body = FreezeRequest,
+    responses(
+        (status = 200, description = "Every frozen language", body = FrozenResponse),
+        (status = 404, description = "Catalog or language not found", body = ErrorResponse),
+        (status = 409, description = "Keys in the language still need work", body = ErrorResponse),
+        (status = 403, description = "The catalog is read-only", body = ErrorResponse),
+    )
+)]
+async fn freeze_language(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
+    Json(payload): Json<FreezeRequest>,
+) -> Result<Json<FrozenResponse>, ApiError> {
+    let store = resolve_store(manager.as_ref(), payload.path.as_deref()).await?;
+    let frozen = store
+        .freeze_language(&payload.language, payload.force)
+        .await?;
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "freeze_language",
+        serde_json::json!({ "language": payload.language, "force": payload.force }),
+    )
+    .await;
+    Ok(Json(FrozenResponse { frozen }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/languages/unfreeze",
+    tag = "catalogs",
+    request_body = FreezeRequest,
+    responses(
+        (status = 200, description = "Languages still frozen", body = FrozenResponse),
+        (status = 404, description = "Catalog not found", body = ErrorResponse),
+        (status = 403, description = "The catalog is read-only", body = ErrorResponse),
+    )
+)]
+async fn unfreeze_language(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
+    Json(payload): Json<FreezeRequest>,
+) -> Result<Json<FrozenResponse>, ApiError> {
+    let store = resolve_store(manager.as_ref(), payload.path.as_deref()).await?;
+    let frozen = store.unfreeze_language(&payload.language).await?;
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "unfreeze_language",
+        serde_json::json!({ "language": payload.language }),
+    )
+    .await;
+    Ok(Json(FrozenResponse { frozen }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/translation-percentages",
+    tag = "quality",
+    params(ProgressQuery),
+    responses(
+        (status = 200, description = "Percentage of keys translated per language", body = TranslationPercentagesResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+    )
+)]
+async fn get_translation_percentages(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<ProgressQuery>,
+) -> Result<Tagged<Json<TranslationPercentagesResponse>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let percentages = store
+        .get_translation_percentages_with(query.include_do_not_translate)
+        .await;
+    Ok((
+        [(header::ETAG, etag)],
+        Json(TranslationPercentagesResponse { percentages }),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/progress",
+    tag = "quality",
+    params(ProgressQuery),
+    responses(
+        (status = 200, description = "Translation progress per language", body = ProgressResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+    )
+)]
+async fn get_progress(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<ProgressQuery>,
+) -> Result<Tagged<Json<ProgressResponse>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let percentages = store
+        .get_translation_percentages_with(query.include_do_not_translate)
+        .await;
+    let untranslated = store
+        .list_untranslated_with(query.include_do_not_translate)
+        .await;
+    let mut languages: Vec<_> = percentages
+        .into_iter()
+        .map(|(language, percentage)| LanguageProgress {
+            untranslated: untranslated.get(&language).map_or(0, Vec::len),
+            language,
+            percentage,
+        })
+        .collect();
+    languages.sort_by(|a, b| a.language.cmp(&b.language));
+    Ok(([(header::ETAG, etag)], Json(ProgressResponse { languages })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/untranslated",
+    tag = "quality",
+    params(UntranslatedQuery),
+    responses(
+        (status = 200, description = "Untranslated keys per language", body = UntranslatedResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+    )
+)]
+async fn list_untranslated(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<UntranslatedQuery>,
+) -> Result<Tagged<Json<UntranslatedResponse>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let mut untranslated = store
+        .list_untranslated_with(query.include_do_not_translate)
+        .await;
+    let untranslated = match query.lang.as_deref().map(str::trim) {
+        Some(language) if !language.is_empty() => {
+            if !store
+                .list_languages()
+                .await
+                .iter()
+                .any(|known| known == language)
+            {
+                return Err(StoreError::LanguageMissing(language.to_string()).into());
+            }
+            let keys = untranslated.remove(language).unwrap_or_default();
+            BTreeMap::from([(language.to_string(), keys)])
+        }
+        _ => untranslated.into_iter().collect(),
+    };
+    Ok((
+        [(header::ETAG, etag)],
+        Json(UntranslatedResponse { untranslated }),
+    ))
+}
+
+/// Ranked translation suggestions for one key and language, from the same and similar
+/// source texts in every catalog and from the glossary.
+#[utoipa::path(
+    get,
+    path = "/api/suggestions",
+    tag = "translations",
+    params(SuggestionsQuery),
+    responses(
+        (status = 200, description = "Suggestions, best first, with where each came from", body = SuggestionsResponse),
+        (status = 404, description = "Catalog or key not found", body = ErrorResponse),
+    )
+)]
+async fn suggest_translations(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Extension(qa): Extension<Arc<QaOptions>>,
+    Query(query): Query<SuggestionsQuery>,
+) -> Result<Json<SuggestionsResponse>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let source_text = store.source_text(&query.key).await?;
+    let suggestions = manager
+        .suggest_translations(
+            &store,
+            &query.key,
+            &query.language,
+            &qa.glossary,
+            query.limit.unwrap_or(suggest::DEFAULT_LIMIT),
+        )
+        .await?;
+    Ok(Json(SuggestionsResponse {
+        source_text,
+        suggestions,
+    }))
+}
+
+/// Reports how much longer translations are than their source text, per language.
+#[utoipa::path(
+    get,
+    path = "/api/lengths",
+    tag = "quality",
+    params(LengthsQuery),
+    responses(
+        (status = 200, description = "Expansion ratios and the longest translations per language", body = LengthReport, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog not found", body = ErrorResponse),
+    )
+)]
+async fn length_report(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<LengthsQuery>,
+) -> Result<Tagged<Json<LengthReport>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let mut report = store
+        .length_report(query.top.unwrap_or(analytics::DEFAULT_TOP))
+        .await;
+    if let Some(language) = query.language.as_deref() {
+        report
+            .languages
+            .retain(|lengths| lengths.language == language);
+    }
+    Ok(([(header::ETAG, etag)], Json(report)))
+}
+
+/// Estimates what translating the remaining work would cost, per language.
+#[utoipa::path(
+    get,
+    path = "/api/cost",
+    tag = "quality",
+    params(CostQuery),
+    responses(
+        (status = 200, description = "Keys, source words, and cost left per language", body = CostEstimate, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 400, description = "Invalid rate", body = ErrorResponse),
+        (status = 404, description = "Catalog or language not found", body = ErrorResponse),
+    )
+)]
+async fn estimate_cost(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<CostQuery>,
+) -> Result<Tagged<Json<CostEstimate>>, ApiError> {
+    if !query.rate_per_word.is_finite() || query.rate_per_word < 0.0 {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: "rate_per_word must be a non-negative number".to_string(),
+        });
+    }
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let estimate = store
+        .estimate_cost(
+            query.language.as_deref(),
+            query.rate_per_word,
+            query.only_untranslated,
+            query.include_do_not_translate,
+        )
+        .await?;
+    Ok(([(header::ETAG, etag)], Json(estimate)))
+}
+
+/// Runs the QA rules and returns findings grouped by severity, optionally narrowed to one
+/// language or key so the UI can annotate individual cells.
+#[utoipa::path(
+    get,
+    path = "/api/validate",
+    tag = "quality",
+    params(ValidateQuery),
+    responses(
+        (status = 200, description = "QA findings grouped by severity", body = QaReport, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 400, description = "Unknown rule", body = ErrorResponse),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+    )
+)]
+async fn validate_catalog(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Extension(qa): Extension<Arc<QaOptions>>,
+    Query(query): Query<ValidateQuery>,
+) -> Result<Tagged<Json<QaReport>>, ApiError> {
+    let rules = query
+        .rule
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|rule| !rule.is_empty())
+        .map(|rule| {
+            serde_json::from_value::<QaRule>(serde_json::Value::String(rule.to_string())).map_err(
+                |_| ApiError {
+                    status: StatusCode::BAD_REQUEST,
+                    message: format!(
+                        "Unknown rule '{rule}'; expected plural, placeholders, terminology, lint, keys, consistency, or spelling"
+                    ),
+                },
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let language = query
+        .lang
+        .as_deref()
+        .map(str::trim)
+        .filter(|language| !language.is_empty());
+    if let Some(language) = language {
+        if !store
+            .list_languages()
+            .await
+            .iter()
+            .any(|known| known == language)
+        {
+            return Err(StoreError::LanguageMissing(language.to_string()).into());
+        }
+    }
+
+    let mut report = store.validate(&rules, &qa).await;
+    let key = query.key.as_deref().filter(|key| !key.is_empty());
+    if language.is_some() || key.is_some() {
+        report.retain(|finding| {
+            language.is_none_or(|language| finding.language.as_deref() == Some(language))
+                && key.is_none_or(|key| finding.key == key)
+        });
+    }
+    Ok(([(header::ETAG, etag)], Json(report)))
+}
+
+/// Streams store changes as server-sent events: `saved` (with the `change` made),
+/// `external_change` after a reload from disk, and `resync` when messages were dropped
+/// because the client fell behind. Pass `path` to only receive events for one catalog.
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "events",
+    params(PathQuery),
+    responses(
+        (status = 200, description = "Server-sent events named `saved`, `external_change`, `qa_findings`, or `resync`; all but `resync` carry a JSON payload", body = StoreEventPayload, content_type = "text/event-stream"),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+    )
+)]
+async fn stream_events(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<PathQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let only = match query.path.as_deref() {
+        Some(path) => Some(
+            resolve_store(manager.as_ref(), Some(path))
+                .await?
+                .path()
+                .to_path_buf(),
+        ),
+        None => None,
+    };
+    let receiver = manager.subscribe();
+    let stream = BroadcastStream::new(receiver).filter_map(move |message| {
+        let event = match message {
+            Ok(event) => event,
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                return Some(Ok(Event::default()
+                    .event("resync")
+                    .data(skipped.to_string())));
+            }
+        };
+        if only.as_deref().is_some_and(|path| path != event.path()) {
+            return None;
+        }
+        let path = path_token(manager.as_ref(), event.path());
+        let (name, change, findings) = match event {
+            StoreEvent::Saved { change, .. } => ("saved", Some(change), None),
+            StoreEvent::ExternalChange { .. } => ("external_change", None, None),
+            StoreEvent::QaFindings { findings, .. } => ("qa_findings", None, Some(findings)),
+        };
+        let data = serde_json::to_string(&StoreEventPayload {
+            path,
+            change,
+            findings,
+        })
+        .unwrap_or_else(|_| "{}".to_string());
+        Some(Ok(Event::default().event(name).data(data)))
+    });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/translations",
+    tag = "translations",
+    params(openapi::IfMatch),
+    request_body = UpsertRequest,
+    responses(
+        (status = 200, description = "The translation after the update, with warnings for dropped variations", body = UpsertResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+        (status = 409, description = "The language is frozen, or the edit breaks the review workflow", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
 async fn upsert_translation(
     Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
     Json(payload): Json<UpsertRequest>,
-) -> Result<Json<TranslationValue>, ApiError> {
+) -> Result<Tagged<Json<UpsertResponse>>, ApiError> {
     let path = payload.path.clone();
     let key = payload.key.clone();
     let language = payload.language.clone();
+    let force = payload.force;
+    let allow_create = payload.allow_create.unwrap_or(true);
+    let comment = payload.comment.clone();
+    let position = payload.insert_position.clone();
     let update = payload.into_update();
     let store = resolve_store(manager.as_ref(), path.as_deref()).await?;
-    let value = store
-        .upsert_translation(&key, &language, update)
+    let store = check_if_match(&headers, &store)?;
+    let writer = if force {
+        store.forcing()
+    } else {
+        store.clone()
+    };
+    let outcome = writer
+        .upsert_translation_with(&key, &language, update, comment, &position, allow_create)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(value))
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "upsert_translation",
+        serde_json::json!({ "key": key, "language": language, "force": force }),
+    )
+    .await;
+    let response = UpsertResponse {
+        value: outcome.value,
+        warnings: outcome.warnings,
+        needs_review: outcome.needs_review,
+    };
+    Ok(([(header::ETAG, store.written_revision())], Json(response)))
 }
 
-async fn delete_translation(
+#[utoipa::path(
+    patch,
+    path = "/api/translations/batch",
+    tag = "translations",
+    params(openapi::IfMatch),
+    request_body = BatchRequest,
+    responses(
+        (status = 200, description = "Every operation succeeded and the batch was saved", body = BatchResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 422, description = "An operation failed; nothing was saved", body = BatchResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
+async fn apply_batch(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
+    Json(payload): Json<BatchRequest>,
+) -> Result<(StatusCode, Tagged<Json<BatchResponse>>), ApiError> {
+    if payload.operations.is_empty() {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: "Batch must contain at least one operation".to_string(),
+        });
+    }
+    let store = resolve_store(manager.as_ref(), payload.path.as_deref()).await?;
+    let store = check_if_match(&headers, &store)?;
+
+    let operations = payload
+        .operations
+        .into_iter()
+        .map(BatchItemRequest::into_operation)
+        .collect();
+    let outcome = store.apply_batch(operations).await?;
+    if outcome.committed {
+        let changes: Vec<_> = outcome
+            .results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .map(|applied| &applied.change)
+            .collect();
+        record_audit(
+            &manager,
+            &headers,
+            &store,
+            "apply_batch",
+            serde_json::json!({ "changes": changes }),
+        )
+        .await;
+    }
+    Ok(batch_response(&store, outcome))
+}
+
+/// The per-operation results of a batch, answered with 422 when nothing was saved.
+fn batch_response(
+    store: &XcStringsStore,
+    outcome: BatchOutcome,
+) -> (StatusCode, Tagged<Json<BatchResponse>>) {
+    let results = outcome
+        .results
+        .into_iter()
+        .enumerate()
+        .map(|(index, result)| match result {
+            Ok(applied) => BatchItemResponse {
+                index,
+                ok: true,
+                value: applied.value,
+                warnings: applied.warnings,
+                needs_review: applied.needs_review,
+                status: None,
+                error: None,
+            },
+            Err(err) => {
+                let err = ApiError::from(err);
+                BatchItemResponse {
+                    index,
+                    ok: false,
+                    value: None,
+                    warnings: Vec::new(),
+                    needs_review: Vec::new(),
+                    status: Some(err.status.as_u16()),
+                    error: Some(err.message),
+                }
+            }
+        })
+        .collect();
+
+    let status = if outcome.committed {
+        StatusCode::OK
+    } else {
+        StatusCode::UNPROCESSABLE_ENTITY
+    };
+    (
+        status,
+        (
+            [(header::ETAG, store.written_revision())],
+            Json(BatchResponse {
+                committed: outcome.committed,
+                results,
+            }),
+        ),
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/translations/approve",
+    tag = "translations",
+    params(openapi::IfMatch),
+    request_body = ReviewRequest,
+    responses(
+        (status = 200, description = "The translation, now reviewed", body = TranslationValue, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog or translation not found", body = ErrorResponse),
+        (status = 409, description = "The translation isn't waiting for review", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
+async fn approve_translation(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
+    Json(payload): Json<ReviewRequest>,
+) -> Result<Tagged<Json<TranslationValue>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), payload.path.as_deref()).await?;
+    let store = check_if_match(&headers, &store)?;
+    let value = store
+        .approve_translation(&payload.key, &payload.language)
+        .await?;
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "approve_translation",
+        serde_json::json!({ "key": payload.key, "language": payload.language }),
+    )
+    .await;
+    Ok(([(header::ETAG, store.written_revision())], Json(value)))
+}
+
+/// Sends a translation back to `new`. The reason is kept in the audit log.
+#[utoipa::path(
+    post,
+    path = "/api/translations/reject",
+    tag = "translations",
+    params(openapi::IfMatch),
+    request_body = ReviewRequest,
+    responses(
+        (status = 200, description = "The translation, now back to new", body = TranslationValue, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog or translation not found", body = ErrorResponse),
+        (status = 409, description = "The translation isn't in review or reviewed, or no reason was given", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
+async fn reject_translation(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
+    Json(payload): Json<ReviewRequest>,
+) -> Result<Tagged<Json<TranslationValue>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), payload.path.as_deref()).await?;
+    let store = check_if_match(&headers, &store)?;
+    let reason = payload.reason.unwrap_or_default();
+    let value = store
+        .reject_translation(&payload.key, &payload.language, &reason)
+        .await?;
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "reject_translation",
+        serde_json::json!({
+            "key": payload.key,
+            "language": payload.language,
+            "reason": reason,
+        }),
+    )
+    .await;
+    Ok(([(header::ETAG, store.written_revision())], Json(value)))
+}
+
+/// Replaces text across translation values. Previews only need the catalog to exist;
+/// applying is a write and goes through the usual `If-Match` check.
+#[utoipa::path(
+    post,
+    path = "/api/find-replace",
+    tag = "translations",
+    params(("If-Match" = Option<String>, Header, description = "Required unless `preview` is true")),
+    request_body = FindReplaceRequest,
+    responses(
+        (status = 200, description = "Values that were, or would be, rewritten", body = FindReplaceResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
+async fn find_replace(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
+    Json(payload): Json<FindReplaceRequest>,
+) -> Result<Tagged<Json<FindReplaceResponse>>, ApiError> {
+    if payload.find.is_empty() {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: "Search text must not be empty".to_string(),
+        });
+    }
+    let store = resolve_store(manager.as_ref(), payload.path.as_deref()).await?;
+    let store = if payload.preview {
+        (*store).clone()
+    } else {
+        check_if_match(&headers, &store)?
+    };
+    let language = payload
+        .language
+        .map(|language| language.trim().to_string())
+        .filter(|language| !language.is_empty());
+    if let Some(language) = &language {
+        if !store.list_languages().await.contains(language) {
+            return Err(StoreError::LanguageMissing(language.clone()).into());
+        }
+    }
+
+    let search = FindReplace {
+        find: payload.find,
+        replace: payload.replace,
+        case_sensitive: payload.case_sensitive,
+        language,
+    };
+    let replacements = store.find_replace(&search, !payload.preview).await?;
+    if !payload.preview && !replacements.is_empty() {
+        record_audit(
+            &manager,
+            &headers,
+            &store,
+            "find_replace",
+            serde_json::json!({
+                "find": search.find,
+                "replace": search.replace,
+                "language": search.language,
+                "replacements": replacements.len(),
+            }),
+        )
+        .await;
+    }
+    let mut keys: Vec<String> = Vec::new();
+    for replacement in &replacements {
+        if !keys.contains(&replacement.key) {
+            keys.push(replacement.key.clone());
+        }
+    }
+    Ok((
+        [(header::ETAG, store.written_revision())],
+        Json(FindReplaceResponse {
+            preview: payload.preview,
+            keys,
+            replacements,
+        }),
+    ))
+}
+
+/// Fetches one translation. The 404 message says whether the key, the language, or only
+/// this key's translation into it is missing, so clients know what to create.
+#[utoipa::path(
+    get,
+    path = "/api/translations/{key}/{language}",
+    tag = "translations",
+    params(
+        ("key" = String, Path, description = "Key to read"),
+        ("language" = String, Path, description = "Language to read"),
+        PathQuery,
+    ),
+    responses(
+        (status = 200, description = "The translation", body = TranslationValue, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, language, or translation not found", body = ErrorResponse),
+    )
+)]
+async fn get_translation(
     Extension(manager): Extension<Arc<XcStringsStoreManager>>,
     Path((key, language)): Path<(String, String)>,
     Query(query): Query<PathQuery>,
-) -> Result<StatusCode, ApiError> {
+) -> Result<Tagged<Json<TranslationValue>>, ApiError> {
     let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
-    store
+    let etag = store.revision();
+    let value = store.get_translation(&key, &language).await?;
+    Ok(([(header::ETAG, etag)], Json(value)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/translations/{key}/{language}",
+    tag = "translations",
+    params(
+        ("key" = String, Path, description = "Key to edit"),
+        ("language" = String, Path, description = "Language to remove"),
+        ForceQuery,
+        openapi::IfMatch,
+    ),
+    responses(
+        (status = 204, description = "Translation removed", headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+        (status = 409, description = "The language is frozen", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
+async fn delete_translation(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
+    Path((key, language)): Path<(String, String)>,
+    Query(query): Query<ForceQuery>,
+) -> Result<Tagged<StatusCode>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let store = check_if_match(&headers, &store)?;
+    let writer = if query.force {
+        store.forcing()
+    } else {
+        store.clone()
+    };
+    writer
         .delete_translation(&key, &language)
         .await
         .map_err(ApiError::from)?;
-    Ok(StatusCode::NO_CONTENT)
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "delete_translation",
+        serde_json::json!({ "key": key, "language": language, "force": query.force }),
+    )
+    .await;
+    Ok((
+        [(header::ETAG, store.written_revision())],
+        StatusCode::NO_CONTENT,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/assignments",
+    tag = "keys",
+    params(PathQuery),
+    responses(
+        (status = 200, description = "Language owners and key assignees from the catalog's sidecar file", body = Assignments),
+        (status = 404, description = "Catalog not found", body = ErrorResponse),
+    )
+)]
+async fn get_assignments(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<PathQuery>,
+) -> Result<Json<Assignments>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    Ok(Json(store.assignments().await?))
+}
+
+/// Assigns keys and languages to a translator. Assignments live in a sidecar file, so the
+/// catalog revision is unchanged and `If-Match` isn't needed.
+#[utoipa::path(
+    put,
+    path = "/api/assignments",
+    tag = "keys",
+    request_body = AssignRequest,
+    responses(
+        (status = 200, description = "All assignments after the change", body = Assignments),
+        (status = 400, description = "No keys or languages given", body = ErrorResponse),
+        (status = 403, description = "The catalog is read-only", body = ErrorResponse),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+    )
+)]
+async fn assign(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
+    Json(payload): Json<AssignRequest>,
+) -> Result<Json<Assignments>, ApiError> {
+    if payload.keys.is_empty() && payload.languages.is_empty() {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: "at least one key or language is required".to_string(),
+        });
+    }
+    let store = resolve_store(manager.as_ref(), payload.path.as_deref()).await?;
+    let assignments = store
+        .assign(
+            &payload.keys,
+            &payload.languages,
+            payload.assignee.as_deref(),
+        )
+        .await?;
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "assign_keys",
+        serde_json::json!({
+            "assignee": payload.assignee,
+            "keys": payload.keys,
+            "languages": payload.languages,
+        }),
+    )
+    .await;
+    Ok(Json(assignments))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/keys/tree",
+    tag = "keys",
+    params(PathQuery),
+    responses(
+        (status = 200, description = "Keys grouped by dot-separated prefix with counts and completion per language", body = KeyNamespace, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+    )
+)]
+async fn get_key_tree(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<PathQuery>,
+) -> Result<Tagged<Json<KeyNamespace>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    Ok(([(header::ETAG, etag)], Json(store.key_namespaces().await)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/keys/{key}",
+    tag = "keys",
+    params(("key" = String, Path, description = "Key to read"), PathQuery),
+    responses(
+        (status = 200, description = "The key with its metadata and translations", body = TranslationRecord, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+    )
+)]
+async fn get_key(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Path(key): Path<String>,
+    Query(query): Query<PathQuery>,
+) -> Result<Tagged<Json<TranslationRecord>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let record = store
+        .get_record(&key)
+        .await
+        .ok_or(StoreError::KeyMissing(key))?;
+    Ok(([(header::ETAG, etag)], Json(record)))
+}
+
+/// Answers whether a key exists without serializing it, so clients can check before
+/// creating one.
+#[utoipa::path(
+    head,
+    path = "/api/keys/{key}",
+    tag = "keys",
+    params(("key" = String, Path, description = "Key to look up"), PathQuery),
+    responses(
+        (status = 200, description = "The key exists", headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog or key not found"),
+    )
+)]
+async fn key_exists(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Path(key): Path<String>,
+    Query(query): Query<PathQuery>,
+) -> Result<Tagged<StatusCode>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    if !store.contains_key(&key).await {
+        return Err(StoreError::KeyMissing(key).into());
+    }
+    Ok(([(header::ETAG, etag)], StatusCode::OK))
+}
+
+/// Lists the commits that added, changed, or removed one translation of a key, for
+/// catalogs inside a git repository.
+#[cfg(feature = "git")]
+#[utoipa::path(
+    get,
+    path = "/api/keys/{key}/history",
+    tag = "keys",
+    params(("key" = String, Path, description = "Key to trace"), HistoryQuery),
+    responses(
+        (status = 200, description = "Commits that changed the translation, newest first", body = KeyHistory, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog not found or not inside a git repository", body = ErrorResponse),
+    )
+)]
+async fn key_history(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Path(key): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Tagged<Json<KeyHistory>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let history = store
+        .key_history(
+            &key,
+            &query.language,
+            query.depth.unwrap_or(git::DEFAULT_DEPTH),
+        )
+        .await?;
+    Ok(([(header::ETAG, etag)], Json(history)))
+}
+
+/// Lists translations whose source text changed after they did, for catalogs inside a git
+/// repository.
+#[cfg(feature = "git")]
+#[utoipa::path(
+    get,
+    path = "/api/outdated",
+    tag = "quality",
+    params(HistoryQuery),
+    responses(
+        (status = 200, description = "Translations older than their source text, in catalog order", body = OutdatedReport, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 400, description = "The language is the source language", body = ErrorResponse),
+        (status = 404, description = "Catalog or language not found, or the catalog is not inside a git repository", body = ErrorResponse),
+    )
+)]
+async fn list_outdated(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Tagged<Json<OutdatedReport>>, ApiError> {
+    let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let etag = store.revision();
+    let report = store
+        .list_outdated(&query.language, query.depth.unwrap_or(git::DEFAULT_DEPTH))
+        .await?;
+    Ok(([(header::ETAG, etag)], Json(report)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/keys",
+    tag = "keys",
+    params(openapi::IfMatch),
+    request_body = CreateKeyRequest,
+    responses(
+        (status = 201, description = "The new key", body = TranslationRecord, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "The key named by `insert_position` doesn't exist", body = ErrorResponse),
+        (status = 409, description = "The key already exists", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
+async fn create_key(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateKeyRequest>,
+) -> Result<(StatusCode, Tagged<Json<TranslationRecord>>), ApiError> {
+    let key = payload.key.trim();
+    if key.is_empty() {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: "Key must not be empty".to_string(),
+        });
+    }
+    let store = resolve_store(manager.as_ref(), payload.path.as_deref()).await?;
+    let store = check_if_match(&headers, &store)?;
+    let record = store
+        .create_key(
+            key,
+            payload.comment.clone(),
+            payload.value.clone(),
+            &payload.insert_position,
+        )
+        .await?;
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "create_key",
+        serde_json::json!({
+            "key": key,
+            "comment": payload.comment,
+            "value": payload.value,
+            "insertPosition": payload.insert_position,
+        }),
+    )
+    .await;
+    Ok((
+        StatusCode::CREATED,
+        ([(header::ETAG, store.written_revision())], Json(record)),
+    ))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/keys/{key}",
+    tag = "keys",
+    params(("key" = String, Path, description = "Key to remove"), PathQuery, openapi::IfMatch),
+    responses(
+        (status = 204, description = "Key removed", headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
 async fn delete_key(
     Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
     Path(key): Path<String>,
     Query(query): Query<PathQuery>,
-) -> Result<StatusCode, ApiError> {
+) -> Result<Tagged<StatusCode>, ApiError> {
     let store = resolve_store(manager.as_ref(), query.path.as_deref()).await?;
+    let store = check_if_match(&headers, &store)?;
     store.delete_key(&key).await.map_err(ApiError::from)?;
-    Ok(StatusCode::NO_CONTENT)
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "delete_key",
+        serde_json::json!({ "key": key }),
+    )
+    .await;
+    Ok((
+        [(header::ETAG, store.written_revision())],
+        StatusCode::NO_CONTENT,
+    ))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/comments",
+    tag = "keys",
+    params(openapi::IfMatch),
+    request_body = CommentRequest,
+    responses(
+        (status = 204, description = "Comment updated", headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
 async fn update_comment(
     Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
     Json(payload): Json<CommentRequest>,
-) -> Result<StatusCode, ApiError> {
+) -> Result<Tagged<StatusCode>, ApiError> {
     let path = payload.path.clone();
     let store = resolve_store(manager.as_ref(), path.as_deref()).await?;
+    let store = check_if_match(&headers, &store)?;
     store
         .set_comment(&payload.key, payload.comment.clone())
         .await
         .map_err(ApiError::from)?;
-    Ok(StatusCode::NO_CONTENT)
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "set_comment",
+        serde_json::json!({ "key": payload.key, "comment": payload.comment }),
+    )
+    .await;
+    Ok((
+        [(header::ETAG, store.written_revision())],
+        StatusCode::NO_CONTENT,
+    ))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/extraction-state",
+    tag = "keys",
+    params(openapi::IfMatch),
+    request_body = ExtractionStateRequest,
+    responses(
+        (status = 204, description = "Extraction state updated", headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
 async fn update_extraction_state(
     Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
     Json(payload): Json<ExtractionStateRequest>,
-) -> Result<StatusCode, ApiError> {
+) -> Result<Tagged<StatusCode>, ApiError> {
     let path = payload.path.clone();
     let store = resolve_store(manager.as_ref(), path.as_deref()).await?;
+    let store = check_if_match(&headers, &store)?;
     store
         .set_extraction_state(&payload.key, payload.extraction_state.clone())
         .await
         .map_err(ApiError::from)?;
-    Ok(StatusCode::NO_CONTENT)
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "set_extraction_state",
+        serde_json::json!({ "key": payload.key, "state": payload.extraction_state }),
+    )
+    .await;
+    Ok((
+        [(header::ETAG, store.written_revision())],
+        StatusCode::NO_CONTENT,
+    ))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/should-translate",
+    tag = "keys",
+    params(openapi::IfMatch),
+    request_body = ShouldTranslateRequest,
+    responses(
+        (status = 204, description = "Flag updated", headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
 async fn update_should_translate(
     Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
     Json(payload): Json<ShouldTranslateRequest>,
-) -> Result<StatusCode, ApiError> {
+) -> Result<Tagged<StatusCode>, ApiError> {
     let path = payload.path.clone();
     let store = resolve_store(manager.as_ref(), path.as_deref()).await?;
+    let store = check_if_match(&headers, &store)?;
     store
         .set_should_translate(&payload.key, payload.should_translate)
         .await
         .map_err(ApiError::from)?;
-    Ok(StatusCode::NO_CONTENT)
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "set_should_translate",
+        serde_json::json!({ "key": payload.key, "shouldTranslate": payload.should_translate }),
+    )
+    .await;
+    Ok((
+        [(header::ETAG, store.written_revision())],
+        StatusCode::NO_CONTENT,
+    ))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/keys/{key}",
+    tag = "keys",
+    params(("key" = String, Path, description = "Key to rename"), openapi::IfMatch),
+    request_body = RenameKeyRequest,
+    responses(
+        (status = 204, description = "Key renamed", headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "Catalog, key, or language not found", body = ErrorResponse),
+        (status = 409, description = "The new key already exists", body = ErrorResponse),
+        openapi::WriteRejected,
+    )
+)]
 async fn rename_key(
     Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    headers: HeaderMap,
     Path(old_key): Path<String>,
     Json(payload): Json<RenameKeyRequest>,
-) -> Result<StatusCode, ApiError> {
+) -> Result<Tagged<StatusCode>, ApiError> {
     let new_key = payload.new_key.trim();
     if new_key.is_empty() {
         return Err(ApiError {
@@ -475,13 +2788,25 @@ async fn rename_key(
 
     let path = payload.path.clone();
     let store = resolve_store(manager.as_ref(), path.as_deref()).await?;
+    let store = check_if_match(&headers, &store)?;
 
     store
         .rename_key(&old_key, new_key)
         .await
         .map_err(ApiError::from)?;
+    record_audit(
+        &manager,
+        &headers,
+        &store,
+        "rename_key",
+        serde_json::json!({ "key": old_key, "newKey": new_key }),
+    )
+    .await;
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok((
+        [(header::ETAG, store.written_revision())],
+        StatusCode::NO_CONTENT,
+    ))
 }
 
 #[derive(Debug)]
@@ -495,16 +2820,39 @@ impl From<StoreError> for ApiError {
         let status = match value {
             StoreError::TranslationMissing { .. } => StatusCode::NOT_FOUND,
             StoreError::KeyMissing(_) => StatusCode::NOT_FOUND,
-            StoreError::KeyExists(_) => StatusCode::CONFLICT,
+            StoreError::KeyExists(_)
+            | StoreError::NamespaceCollision(_)
+            | StoreError::WorkflowViolation { .. }
+            | StoreError::LanguageFrozen(_)
+            | StoreError::LanguageIncomplete { .. } => StatusCode::CONFLICT,
+            StoreError::NotInGitRepository(_) | StoreError::GitRevisionMissing(_) => {
+                StatusCode::NOT_FOUND
+            }
+            StoreError::InvalidNamespace(_)
+            | StoreError::InvalidDevice(_)
+            | StoreError::InvalidVariation(_)
+            | StoreError::InvalidMetadata(_)
+            | StoreError::InvalidPatch(_)
+            | StoreError::InvalidState(_)
+            | StoreError::PlaceholderMismatch { .. }
+            | StoreError::CommentRequired(_) => StatusCode::BAD_REQUEST,
             StoreError::LanguageMissing(_) => StatusCode::NOT_FOUND,
             StoreError::LanguageExists(_) => StatusCode::CONFLICT,
-            StoreError::InvalidLanguage(_) => StatusCode::BAD_REQUEST,
+            StoreError::InvalidLanguage(_) | StoreError::InvalidLanguageCode { .. } => {
+                StatusCode::BAD_REQUEST
+            }
             StoreError::CannotRemoveSourceLanguage(_) => StatusCode::BAD_REQUEST,
             StoreError::CannotRenameSourceLanguage(_) => StatusCode::BAD_REQUEST,
-            StoreError::SerdeFailed(_) | StoreError::ReadFailed(_) => {
-                StatusCode::INTERNAL_SERVER_ERROR
-            }
+            StoreError::InvalidSnapshotLabel(_) => StatusCode::BAD_REQUEST,
+            StoreError::SnapshotMissing(_) => StatusCode::NOT_FOUND,
+            StoreError::InvalidCatalogPath(_) => StatusCode::BAD_REQUEST,
+            StoreError::CatalogNotRegistered(_) => StatusCode::NOT_FOUND,
+            StoreError::ReadOnly(_) | StoreError::Protected { .. } => StatusCode::FORBIDDEN,
+            StoreError::SerdeFailed(_)
+            | StoreError::ReadFailed(_)
+            | StoreError::InvalidProject(_) => StatusCode::INTERNAL_SERVER_ERROR,
             StoreError::PathRequired => StatusCode::BAD_REQUEST,
+            StoreError::RevisionMismatch => StatusCode::PRECONDITION_FAILED,
         };
         ApiError {
             status,
@@ -513,6 +2861,52 @@ impl From<StoreError> for ApiError {
     }
 }
 
+/// Lists the entries normalization changed while a request saved a catalog, as
+/// `normalizedEntries` in the JSON object the handler returned. Responses without a body
+/// become `200` with just that list, so writes never drop entries silently.
+async fn report_normalization(
+    request: axum::extract::Request,
+    next: middleware::Next,
+) -> axum::response::Response {
+    let (response, normalized) = store::track_normalization(next.run(request)).await;
+    if normalized.is_empty() {
+        return response;
+    }
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            return ApiError {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                message: format!("Failed to read response: {err}"),
+            }
+            .into_response()
+        }
+    };
+    let mut object = if bytes.is_empty() {
+        serde_json::Map::new()
+    } else {
+        match serde_json::from_slice(&bytes) {
+            Ok(serde_json::Value::Object(object)) => object,
+            _ => return axum::response::Response::from_parts(parts, bytes.into()),
+        }
+    };
+    object.insert(
+        "normalizedEntries".to_string(),
+        serde_json::to_value(normalized).unwrap_or_default(),
+    );
+    if parts.status == StatusCode::NO_CONTENT {
+        parts.status = StatusCode::OK;
+    }
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    let body = serde_json::to_vec(&object).unwrap_or_default();
+    axum::response::Response::from_parts(parts, body.into())
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
         let status = self.status;
@@ -524,6 +2918,8 @@ impl IntoResponse for ApiError {
 }
 
 const INDEX_HTML: &str = include_str!("index.html");
+const APP_JS: &str = include_str!("app.js");
+const APP_CSS: &str = include_str!("app.css");
 
 #[cfg(test)]
 mod tests {
@@ -608,15 +3004,1454 @@ mod tests {
         );
     }
 
-    #[tokio::test]
-    async fn test_web_api_delete_plural_variation() {
+    #[test]
+    fn api_error_maps_every_store_error() {
+        // No wildcard arm, so a new variant doesn't compile until it has a status here.
+        let expected = |err: &StoreError| match err {
+            StoreError::ReadFailed(_)
+            | StoreError::SerdeFailed(_)
+            | StoreError::InvalidProject(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            StoreError::TranslationMissing { .. }
+            | StoreError::KeyMissing(_)
+            | StoreError::LanguageMissing(_)
+            | StoreError::SnapshotMissing(_)
+            | StoreError::CatalogNotRegistered(_)
+            | StoreError::NotInGitRepository(_)
+            | StoreError::GitRevisionMissing(_) => StatusCode::NOT_FOUND,
+            StoreError::KeyExists(_)
+            | StoreError::LanguageExists(_)
+            | StoreError::NamespaceCollision(_)
+            | StoreError::WorkflowViolation { .. }
+            | StoreError::LanguageFrozen(_)
+            | StoreError::LanguageIncomplete { .. } => StatusCode::CONFLICT,
+            StoreError::PathRequired
+            | StoreError::InvalidLanguage(_)
+            | StoreError::InvalidLanguageCode { .. }
+            | StoreError::InvalidDevice(_)
+            | StoreError::InvalidVariation(_)
+            | StoreError::InvalidMetadata(_)
+            | StoreError::InvalidState(_)
+            | StoreError::InvalidNamespace(_)
+            | StoreError::InvalidPatch(_)
+            | StoreError::InvalidCatalogPath(_)
+            | StoreError::PlaceholderMismatch { .. }
+            | StoreError::CommentRequired(_)
+            | StoreError::CannotRemoveSourceLanguage(_)
+            | StoreError::CannotRenameSourceLanguage(_)
+            | StoreError::InvalidSnapshotLabel(_) => StatusCode::BAD_REQUEST,
+            StoreError::ReadOnly(_) | StoreError::Protected { .. } => StatusCode::FORBIDDEN,
+            StoreError::RevisionMismatch => StatusCode::PRECONDITION_FAILED,
+        };
+        let errors = [
+            StoreError::ReadFailed(std::io::Error::other("disk")),
+            StoreError::SerdeFailed(serde_json::from_str::<u8>("x").unwrap_err()),
+            StoreError::TranslationMissing {
+                key: "k".into(),
+                language: "fr".into(),
+            },
+            StoreError::KeyMissing("k".into()),
+            StoreError::KeyExists("k".into()),
+            StoreError::PathRequired,
+            StoreError::LanguageMissing("fr".into()),
+            StoreError::LanguageExists("fr".into()),
+            StoreError::InvalidLanguage("empty".into()),
+            StoreError::InvalidDevice("watch".into()),
+            StoreError::InvalidVariation("unknown selector".into()),
+            StoreError::InvalidMetadata("reserved field".into()),
+            StoreError::InvalidState("'dnoe' at state".into()),
+            StoreError::InvalidNamespace("empty prefix".into()),
+            StoreError::InvalidPatch("operation 0 (remove /k): '/k' doesn't exist".into()),
+            StoreError::NamespaceCollision(vec!["home.title".into()]),
+            StoreError::InvalidLanguageCode {
+                code: "english".into(),
+                reason: "unknown language".into(),
+                suggestions: vec!["en".into()],
+            },
+            StoreError::CannotRemoveSourceLanguage("en".into()),
+            StoreError::CannotRenameSourceLanguage("en".into()),
+            StoreError::InvalidSnapshotLabel("..".into()),
+            StoreError::SnapshotMissing("before".into()),
+            StoreError::InvalidCatalogPath("not an .xcstrings file".into()),
+            StoreError::CatalogNotRegistered("/tmp/Other.xcstrings".into()),
+            StoreError::ReadOnly("Localizable.xcstrings".into()),
+            StoreError::Protected {
+                path: "Vendor/Localizable.xcstrings".into(),
+                rule: "Vendor/**".into(),
+            },
+            StoreError::InvalidProject("missing project.pbxproj".into()),
+            StoreError::WorkflowViolation {
+                key: "k".into(),
+                language: "fr".into(),
+                reason: "needs review first".into(),
+            },
+            StoreError::PlaceholderMismatch {
+                key: "k".into(),
+                language: "fr".into(),
+                reason: "missing %@".into(),
+            },
+            StoreError::CommentRequired("k".into()),
+            StoreError::LanguageFrozen("fr".into()),
+            StoreError::LanguageIncomplete {
+                language: "fr".into(),
+                keys: vec!["k".into()],
+            },
+            StoreError::NotInGitRepository("Localizable.xcstrings".into()),
+            StoreError::GitRevisionMissing("HEAD~3".into()),
+            StoreError::RevisionMismatch,
+        ];
+
+        for err in errors {
+            let message = err.to_string();
+            let status = expected(&err);
+            let api_error = ApiError::from(err);
+            assert_eq!(api_error.status, status, "{message}");
+            assert_eq!(api_error.message, message);
+        }
+    }
+
+    #[tokio::test]
+    async fn language_errors_surface_as_http_statuses() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Localizable.xcstrings");
+        let manager = Arc::new(XcStringsStoreManager::new(Some(path)).await.unwrap());
+        let store = manager.default_store().await.unwrap();
+        store
+            .upsert_translation(
+                "greeting",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .unwrap();
+        store.add_language("fr").await.unwrap();
+
+        let responses = [
+            store.add_language("fr").await.unwrap_err(),
+            store.remove_language("de").await.unwrap_err(),
+            store.remove_language("en").await.unwrap_err(),
+            store.add_language(" ").await.unwrap_err(),
+        ]
+        .map(|err| ApiError::from(err).into_response().status());
+        assert_eq!(
+            responses,
+            [
+                StatusCode::CONFLICT,
+                StatusCode::NOT_FOUND,
+                StatusCode::BAD_REQUEST,
+                StatusCode::BAD_REQUEST,
+            ]
+        );
+    }
+
+    async fn get_json(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        use tower::Service;
+
+        // Router is always ready, so it can be called without polling for readiness first.
+        let response = app
+            .clone()
+            .call(
+                axum::http::Request::get(uri)
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+
+    async fn seeded_app(dir: &std::path::Path) -> Router {
+        let path = dir.join("Localizable.xcstrings");
+        let manager = Arc::new(XcStringsStoreManager::new(Some(path)).await.unwrap());
+        let store = manager.default_store().await.unwrap();
+        for (key, fr) in [
+            ("b.title", Some("Titre")),
+            ("a.title", None),
+            ("c.title", Some("Texte")),
+        ] {
+            store
+                .upsert_translation(
+                    key,
+                    "en",
+                    TranslationUpdate::from_value_state(Some(key.to_uppercase()), None),
+                )
+                .await
+                .unwrap();
+            if let Some(fr) = fr {
+                store
+                    .upsert_translation(
+                        key,
+                        "fr",
+                        TranslationUpdate::from_value_state(
+                            Some(fr.into()),
+                            Some("needs_review".into()),
+                        ),
+                    )
+                    .await
+                    .unwrap();
+            }
+        }
+        router(manager)
+    }
+
+    #[tokio::test]
+    async fn get_translation_says_what_is_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = seeded_app(dir.path()).await;
+
+        let (status, body) = get_json(&app, "/api/translations/b.title/fr").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["value"], "Titre");
+
+        for (uri, error) in [
+            (
+                "/api/translations/z.title/fr",
+                "string key 'z.title' not found",
+            ),
+            ("/api/translations/b.title/de", "language 'de' not found"),
+            (
+                "/api/translations/a.title/fr",
+                "translation not found for key 'a.title' and language 'fr'",
+            ),
+        ] {
+            let (status, body) = get_json(&app, uri).await;
+            assert_eq!(status, StatusCode::NOT_FOUND, "{uri}");
+            assert_eq!(body["error"], error, "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn list_translations_paginates_sorts_and_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = seeded_app(dir.path()).await;
+
+        let (status, body) = get_json(&app, "/api/translations").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 3);
+        assert_eq!(body["total_pages"], 1);
+        assert!(body.get("per_page").is_none());
+        assert_eq!(body["items"][0]["key"], "b.title", "file order by default");
+
+        let (_, body) = get_json(&app, "/api/translations?sort=key&page=2&per_page=2").await;
+        assert_eq!(body["total"], 3);
+        assert_eq!(body["total_keys"], 3);
+        assert_eq!(body["page"], 2);
+        assert_eq!(body["per_page"], 2);
+        assert_eq!(body["total_pages"], 2);
+        let keys: Vec<_> = body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["key"].as_str().unwrap())
+            .collect();
+        assert_eq!(keys, vec!["c.title"]);
+
+        let (_, body) = get_json(
+            &app,
+            "/api/translations?state=needs_review&language=fr&sort=-key",
+        )
+        .await;
+        assert_eq!(body["total"], 2);
+        assert_eq!(body["items"][0]["key"], "c.title");
+        assert_eq!(body["items"][1]["key"], "b.title");
+
+        let (_, body) = get_json(&app, "/api/translations?state=missing&language=fr").await;
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["items"][0]["key"], "a.title");
+
+        let (_, body) = get_json(&app, "/api/translations?languages=fr,%20de&per_page=1").await;
+        let translations = body["items"][0]["translations"].as_object().unwrap();
+        assert_eq!(translations.keys().collect::<Vec<_>>(), vec!["fr"]);
+
+        let (status, _) = get_json(&app, "/api/translations?sort=random").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn list_summaries_returns_states_with_pagination() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = seeded_app(dir.path()).await;
+
+        let (status, body) = get_json(&app, "/api/summaries?page=1&per_page=2").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 3);
+        assert_eq!(body["total_pages"], 2);
+        assert_eq!(body["items"].as_array().unwrap().len(), 2);
+        let first = &body["items"][0];
+        assert_eq!(first["key"], "b.title");
+        assert_eq!(first["languages"], serde_json::json!(["en", "fr"]));
+        assert_eq!(first["hasVariations"], false);
+        assert_eq!(first["states"]["fr"], "needs_review");
+        assert!(first.get("translations").is_none());
+
+        let (_, body) = get_json(&app, "/api/summaries?q=texte").await;
+        assert_eq!(body["total"], 1);
+        assert_eq!(body["items"][0]["key"], "c.title");
+
+        let (_, body) = get_json(&app, "/api/summaries?modified_since=2000-01-01").await;
+        assert_eq!(body["total"], 3);
+        assert!(body["items"][0]["modified"].is_string());
+        let (_, body) = get_json(&app, "/api/translations?modified_since=2999-01-01").await;
+        assert_eq!(body["items"], serde_json::json!([]));
+        let (status, _) = get_json(&app, "/api/summaries?modified_since=yesterday").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn progress_and_untranslated_endpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = seeded_app(dir.path()).await;
+
+        let (status, body) = get_json(&app, "/api/progress").await;
+        assert_eq!(status, StatusCode::OK);
+        let languages = body["languages"].as_array().unwrap();
+        assert_eq!(languages.len(), 2);
+        assert_eq!(languages[0]["language"], "en");
+        assert_eq!(languages[0]["untranslated"], 0);
+        assert_eq!(languages[1]["language"], "fr");
+        assert_eq!(languages[1]["untranslated"], 1);
+        let fr = languages[1]["percentage"].as_f64().unwrap();
+        assert!((fr - 200.0 / 3.0).abs() < 1e-9);
+
+        let (_, body) = get_json(&app, "/api/untranslated?lang=fr").await;
+        assert_eq!(
+            body["untranslated"],
+            serde_json::json!({ "fr": ["a.title"] })
+        );
+
+        let (_, body) = get_json(&app, "/api/untranslated?lang=en").await;
+        assert_eq!(body["untranslated"], serde_json::json!({ "en": [] }));
+
+        let (status, _) = get_json(&app, "/api/untranslated?lang=de").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, body) = get_json(&app, "/api/keys/tree").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["totalKeys"], 3);
+        let namespaces: Vec<_> = body["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|child| {
+                (
+                    child["namespace"].as_str().unwrap(),
+                    child["completion"]["fr"].as_f64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(namespaces, vec![("a", 0.0), ("b", 100.0), ("c", 100.0)]);
+    }
+
+    #[tokio::test]
+    async fn events_stream_store_changes() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Localizable.xcstrings");
+        let manager = Arc::new(XcStringsStoreManager::new(Some(path)).await.unwrap());
+        let store = manager.default_store().await.unwrap();
+        let response = router(manager.clone())
+            .call(
+                axum::http::Request::get("/api/events")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+        let mut body = response.into_body().into_data_stream();
+
+        store
+            .upsert_translation(
+                "greeting",
+                "fr",
+                TranslationUpdate::from_value_state(Some("Bonjour".into()), None),
+            )
+            .await
+            .unwrap();
+        store.delete_key("greeting").await.unwrap();
+
+        let mut received = String::new();
+        while received.matches("\n\n").count() < 2 {
+            let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), body.next())
+                .await
+                .expect("event before timeout")
+                .expect("stream open")
+                .unwrap();
+            received.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+        let changes: Vec<_> = received
+            .split("\n\n")
+            .take(2)
+            .map(|message| {
+                let data = message
+                    .strip_prefix("event: saved\ndata: ")
+                    .unwrap_or_else(|| panic!("unexpected message {message}"));
+                serde_json::from_str::<serde_json::Value>(data).unwrap()["change"].clone()
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                serde_json::json!({ "type": "translation_upserted", "key": "greeting", "language": "fr" }),
+                serde_json::json!({ "type": "key_deleted", "key": "greeting" }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn writes_require_current_etag() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let app = seeded_app(dir.path()).await;
+        let put = |if_match: Option<&str>, value: &str| {
+            let mut request = axum::http::Request::put("/api/translations")
+                .header(header::CONTENT_TYPE, "application/json");
+            if let Some(tag) = if_match {
+                request = request.header(header::IF_MATCH, tag);
+            }
+            request
+                .body(axum::body::Body::from(
+                    serde_json::json!({ "key": "a.title", "language": "fr", "value": value })
+                        .to_string(),
+                ))
+                .unwrap()
+        };
+
+        let listed = app
+            .clone()
+            .call(
+                axum::http::Request::get("/api/translations")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let etag = listed.headers()[header::ETAG].to_str().unwrap().to_string();
+
+        let missing = app.clone().call(put(None, "Un")).await.unwrap();
+        assert_eq!(missing.status(), StatusCode::PRECONDITION_REQUIRED);
+
+        let saved = app.clone().call(put(Some(&etag), "Un")).await.unwrap();
+        assert_eq!(saved.status(), StatusCode::OK);
+        let next = saved.headers()[header::ETAG].to_str().unwrap().to_string();
+        assert_ne!(next, etag);
+
+        let stale = app.clone().call(put(Some(&etag), "Deux")).await.unwrap();
+        assert_eq!(stale.status(), StatusCode::PRECONDITION_FAILED);
+
+        let forced = app.clone().call(put(Some("*"), "Trois")).await.unwrap();
+        assert_eq!(forced.status(), StatusCode::OK);
+
+        let (_, body) = get_json(&app, "/api/translations?languages=fr&sort=key").await;
+        assert_eq!(body["items"][0]["translations"]["fr"]["value"], "Trois");
+    }
+
+    #[tokio::test]
+    async fn read_only_catalogs_reject_writes() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let _ = seeded_app(dir.path()).await;
+        let manager = XcStringsStoreManager::new(Some(dir.path().join("Localizable.xcstrings")))
+            .await
+            .unwrap()
+            .with_read_only(true);
+        let app = router(Arc::new(manager));
+
+        let response = app
+            .clone()
+            .call(
+                axum::http::Request::put("/api/translations")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header(header::IF_MATCH, "*")
+                    .body(axum::body::Body::from(
+                        serde_json::json!({ "key": "a.title", "language": "fr", "value": "Un" })
+                            .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let (status, body) = get_json(&app, "/api/translations?languages=fr&sort=key").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["items"][0]["translations"].get("fr").is_none());
+    }
+
+    #[tokio::test]
+    async fn batch_applies_all_operations_or_none() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let app = seeded_app(dir.path()).await;
+        let patch = |operations: serde_json::Value| {
+            axum::http::Request::patch("/api/translations/batch")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::IF_MATCH, "*")
+                .body(axum::body::Body::from(
+                    serde_json::json!({ "operations": operations }).to_string(),
+                ))
+                .unwrap()
+        };
+        let read = |response: axum::response::Response| async move {
+            let status = response.status();
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (
+                status,
+                serde_json::from_slice::<serde_json::Value>(&bytes).unwrap(),
+            )
+        };
+
+        let rejected = app
+            .clone()
+            .call(patch(serde_json::json!([
+                { "op": "upsert", "key": "a.title", "language": "fr", "value": "Titre A" },
+                { "op": "delete_key", "key": "missing" },
+            ])))
+            .await
+            .unwrap();
+        let (status, body) = read(rejected).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["committed"], false);
+        assert_eq!(body["results"][0]["ok"], true);
+        assert_eq!(body["results"][1]["status"], 404);
+        let (_, listed) = get_json(&app, "/api/translations?state=missing&language=fr").await;
+        assert_eq!(listed["total"], 1, "failed batch must not write");
+
+        let applied = app
+            .clone()
+            .call(patch(serde_json::json!([
+                { "op": "upsert", "key": "a.title", "language": "fr", "value": "Titre A" },
+                { "op": "delete_translation", "key": "b.title", "language": "fr" },
+                { "op": "delete_key", "key": "c.title" },
+            ])))
+            .await
+            .unwrap();
+        let (status, body) = read(applied).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["committed"], true);
+        assert_eq!(body["results"][0]["value"]["value"], "Titre A");
+
+        let (_, listed) = get_json(&app, "/api/translations?sort=key&languages=fr").await;
+        assert_eq!(listed["total"], 2);
+        assert_eq!(listed["items"][0]["translations"]["fr"]["value"], "Titre A");
+        assert!(listed["items"][1]["translations"].get("fr").is_none());
+    }
+
+    #[tokio::test]
+    async fn find_replace_previews_without_writing() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let app = seeded_app(dir.path()).await;
+        let post = |body: serde_json::Value, if_match: Option<&str>| {
+            let mut request = axum::http::Request::post("/api/find-replace")
+                .header(header::CONTENT_TYPE, "application/json");
+            if let Some(tag) = if_match {
+                request = request.header(header::IF_MATCH, tag);
+            }
+            request
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap()
+        };
+        let read = |response: axum::response::Response| async move {
+            let status = response.status();
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (
+                status,
+                serde_json::from_slice::<serde_json::Value>(&bytes).unwrap(),
+            )
+        };
+        let search = serde_json::json!({ "find": "t", "replace": "T", "language": "fr" });
+
+        let mut preview = search.clone();
+        preview["preview"] = true.into();
+        let (status, body) = read(app.clone().call(post(preview, None)).await.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["preview"], true);
+        assert_eq!(body["keys"], serde_json::json!(["b.title", "c.title"]));
+        assert_eq!(body["replacements"][0]["before"], "Titre");
+        assert_eq!(body["replacements"][0]["after"], "TiTre");
+
+        let (status, _) = read(app.clone().call(post(search.clone(), None)).await.unwrap()).await;
+        assert_eq!(status, StatusCode::PRECONDITION_REQUIRED);
+        let (_, listed) = get_json(&app, "/api/translations?q=titre").await;
+        assert_eq!(listed["total"], 1, "preview must not write");
+
+        let (status, body) = read(app.clone().call(post(search, Some("*"))).await.unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["replacements"].as_array().unwrap().len(), 2);
+        let (_, listed) = get_json(&app, "/api/translations?q=TiTre").await;
+        assert_eq!(listed["items"][0]["translations"]["fr"]["value"], "TiTre");
+
+        let (status, _) = read(
+            app.clone()
+                .call(post(
+                    serde_json::json!({ "find": "x", "language": "de", "preview": true }),
+                    None,
+                ))
+                .await
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn api_routes_require_configured_credentials() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(Some(dir.path().join("Localizable.xcstrings")))
+                .await
+                .unwrap(),
+        );
+        let auth = WebAuth {
+            token: Some("s3cret".into()),
+            username: Some("translator".into()),
+            password: Some("hunter2".into()),
+        };
+        let app = router_with_options(
+            manager.clone(),
+            WebOptions {
+                auth,
+                ..WebOptions::default()
+            },
+        );
+        let status = |uri: &str, authorization: Option<&str>| {
+            let mut request = axum::http::Request::get(uri);
+            if let Some(value) = authorization {
+                request = request.header(header::AUTHORIZATION, value);
+            }
+            let request = request.body(axum::body::Body::empty()).unwrap();
+            let mut app = app.clone();
+            async move { app.call(request).await.unwrap() }
+        };
+
+        let denied = status("/api/files", None).await;
+        assert_eq!(denied.status(), StatusCode::UNAUTHORIZED);
+        assert!(denied.headers()[header::WWW_AUTHENTICATE]
+            .to_str()
+            .unwrap()
+            .starts_with("Basic"));
+        assert_eq!(
+            status("/api/files", Some("Bearer wrong")).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status("/api/files", Some("Bearer s3cret")).await.status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            status("/api/files?access_token=s3cret", None)
+                .await
+                .status(),
+            StatusCode::OK
+        );
+        // "translator:hunter2"
+        assert_eq!(
+            status("/api/files", Some("Basic dHJhbnNsYXRvcjpodW50ZXIy"))
+                .await
+                .status(),
+            StatusCode::OK
+        );
+        assert_eq!(status("/", None).await.status(), StatusCode::OK);
+
+        let open = serve(
+            "0.0.0.0:0".parse().unwrap(),
+            manager,
+            WebOptions::default(),
+            None,
+        )
+        .await;
+        assert!(open.is_err(), "unauthenticated LAN binding must be refused");
+    }
+
+    #[tokio::test]
+    async fn bind_picks_a_free_port_only_for_port_zero() {
+        let options = WebOptions::default();
+        let first = bind("127.0.0.1:0".parse().unwrap(), &options)
+            .await
+            .expect("bind ephemeral port");
+        let taken = first.local_addr().unwrap();
+        assert_ne!(taken.port(), 0);
+
+        // A port that was asked for by number isn't swapped for another one.
+        let err = bind(taken, &options).await.expect_err("port is taken");
+        let io = err.root_cause().downcast_ref::<std::io::Error>();
+        assert_eq!(
+            io.map(std::io::Error::kind),
+            Some(std::io::ErrorKind::AddrInUse),
+            "{err:#}"
+        );
+        assert!(err.to_string().contains("WEB_PORT=0"), "{err:#}");
+
+        assert_eq!(
+            url("0.0.0.0:8787".parse().unwrap(), false),
+            "http://127.0.0.1:8787/"
+        );
+        assert_eq!(url("[::]:443".parse().unwrap(), true), "https://[::1]:443/");
+    }
+
+    #[tokio::test]
+    async fn assets_dir_overrides_embedded_ui() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(Some(dir.path().join("Localizable.xcstrings")))
+                .await
+                .unwrap(),
+        );
+        let assets = dir.path().join("assets-root");
+        std::fs::create_dir_all(assets.join("assets")).unwrap();
+        std::fs::write(assets.join("assets/app.css"), "body { color: red; }").unwrap();
+        std::fs::write(assets.join("logo.svg"), "<svg/>").unwrap();
+
+        let fetch = |app: Router, uri: &str| {
+            let request = axum::http::Request::get(uri)
+                .body(axum::body::Body::empty())
+                .unwrap();
+            async move {
+                let mut app = app;
+                let response = app.call(request).await.unwrap();
+                let status = response.status();
+                let content_type = response
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .map(|value| value.to_str().unwrap().to_string())
+                    .unwrap_or_default();
+                let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (
+                    status,
+                    content_type,
+                    String::from_utf8(bytes.to_vec()).unwrap(),
+                )
+            }
+        };
+
+        let embedded = router(manager.clone());
+        let (status, _, page) = fetch(embedded.clone(), "/").await;
+        assert_eq!(status, StatusCode::OK);
+        // Relative, so the page also works when the router is nested under a prefix.
+        assert!(page.contains(r#"src="assets/app.js""#));
+        assert!(!page.contains("<base"));
+        let (status, content_type, script) = fetch(embedded.clone(), "/assets/app.js").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(content_type.starts_with("text/javascript"));
+        assert_eq!(script, APP_JS);
+        let (status, _, _) = fetch(embedded, "/logo.svg").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let overridden = router_with_options(
+            manager,
+            WebOptions {
+                assets_dir: Some(assets),
+                ..WebOptions::default()
+            },
+        );
+        let (_, content_type, css) = fetch(overridden.clone(), "/assets/app.css").await;
+        assert!(content_type.starts_with("text/css"));
+        assert_eq!(css, "body { color: red; }");
+        let (status, _, svg) = fetch(overridden.clone(), "/logo.svg").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(svg, "<svg/>");
+        // Files missing from the directory come from the binary.
+        let (_, _, page) = fetch(overridden.clone(), "/").await;
+        assert_eq!(page, INDEX_HTML);
+        let (_, _, script) = fetch(overridden.clone(), "/assets/app.js").await;
+        assert_eq!(script, APP_JS);
+        let (status, _, _) = fetch(overridden.clone(), "/../Localizable.xcstrings").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _, _) = fetch(overridden, "/api/files").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn serves_api_over_unix_socket() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(Some(dir.path().join("Localizable.xcstrings")))
+                .await
+                .unwrap(),
+        );
+        let socket = dir.path().join("web.sock");
+        // A leftover socket from an earlier run must not block binding.
+        drop(std::os::unix::net::UnixListener::bind(&socket).unwrap());
+        let server = tokio::spawn({
+            let socket = socket.clone();
+            async move { serve_unix(&socket, manager, WebOptions::default()).await }
+        });
+
+        let mut stream = loop {
+            match tokio::net::UnixStream::connect(&socket).await {
+                Ok(stream) => break stream,
+                Err(_) => tokio::time::sleep(std::time::Duration::from_millis(10)).await,
+            }
+        };
+        stream
+            .write_all(b"GET /api/files HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains("\"files\""), "{response}");
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn probes_report_health_readiness_and_version() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let catalog = dir.path().join("Localizable.xcstrings");
+        let manager = Arc::new(
+            XcStringsStoreManager::new(Some(catalog.clone()))
+                .await
+                .unwrap(),
+        );
+        let app = router_with_options(
+            manager,
+            WebOptions {
+                auth: WebAuth {
+                    token: Some("s3cret".into()),
+                    ..WebAuth::default()
+                },
+                ..WebOptions::default()
+            },
+        );
+
+        let health = app
+            .clone()
+            .call(
+                axum::http::Request::get("/healthz")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(health.status(), StatusCode::OK, "probes bypass auth");
+
+        let (status, body) = get_json(&app, "/readyz").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["ready"], true);
+
+        let (_, body) = get_json(&app, "/api/version?access_token=s3cret").await;
+        assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+        assert!(body["gitSha"].as_str().is_some_and(|sha| !sha.is_empty()));
+
+        std::fs::write(&catalog, "not json").unwrap();
+        let (status, body) = get_json(&app, "/readyz").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["defaultCatalog"]["ok"], false);
+        assert_eq!(body["searchRoot"]["ok"], true);
+    }
+
+    #[tokio::test]
+    async fn compresses_json_when_client_accepts_it() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let app = seeded_app(dir.path()).await;
+        let fetch = |encoding: Option<&'static str>| {
+            let mut request = axum::http::Request::get("/api/translations");
+            if let Some(encoding) = encoding {
+                request = request.header(header::ACCEPT_ENCODING, encoding);
+            }
+            let request = request.body(axum::body::Body::empty()).unwrap();
+            let mut app = app.clone();
+            async move { app.call(request).await.unwrap() }
+        };
+
+        for encoding in ["gzip", "br"] {
+            let response = fetch(Some(encoding)).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[header::CONTENT_ENCODING], encoding);
+        }
+        let plain = fetch(None).await;
+        assert!(plain.headers().get(header::CONTENT_ENCODING).is_none());
+        let bytes = axum::body::to_bytes(plain.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(serde_json::from_slice::<serde_json::Value>(&bytes).is_ok());
+    }
+
+    #[tokio::test]
+    async fn writes_keep_empty_entries_of_other_tools() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Localizable.xcstrings");
+        std::fs::write(
+            &path,
+            r#"{"sourceLanguage":"en","strings":{"greeting":{"localizations":{"en":{"stringUnit":{"state":"translated","value":"Hello"}}}},"orphan":{"localizations":{"fr":{}}}},"version":"1.0"}"#,
+        )
+        .unwrap();
+        let app = router(Arc::new(
+            XcStringsStoreManager::new(Some(path.clone()))
+                .await
+                .unwrap(),
+        ));
+        let request = axum::http::Request::delete("/api/keys/greeting")
+            .header(header::IF_MATCH, "*")
+            .body(axum::body::Body::empty())
+            .unwrap();
+
+        let response = app.clone().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            saved["strings"],
+            serde_json::json!({ "orphan": { "localizations": { "fr": {} } } })
+        );
+    }
+
+    #[tokio::test]
+    async fn writes_are_audited_with_request_id() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let audit_path = dir.path().join("audit.jsonl");
+        let manager = XcStringsStoreManager::new(Some(dir.path().join("Localizable.xcstrings")))
+            .await
+            .unwrap()
+            .with_audit_log(crate::audit::AuditLog::new(&audit_path));
+        let manager = Arc::new(manager);
+        manager
+            .default_store()
+            .await
+            .unwrap()
+            .upsert_translation(
+                "greeting",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .unwrap();
+        let app = router(manager);
+
+        let response = app
+            .clone()
+            .call(
+                axum::http::Request::delete("/api/keys/greeting")
+                    .header(header::IF_MATCH, "*")
+                    .header(request_trace::REQUEST_ID_HEADER, "trace-123")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers()[request_trace::REQUEST_ID_HEADER],
+            "trace-123"
+        );
+
+        let generated = app
+            .clone()
+            .call(
+                axum::http::Request::get("/api/files")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(generated
+            .headers()
+            .get(request_trace::REQUEST_ID_HEADER)
+            .is_some_and(|id| !id.is_empty()));
+
+        let entries = crate::audit::AuditLog::new(&audit_path)
+            .entries()
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1, "reads are not audited");
+        assert_eq!(entries[0].session, "web");
+        assert_eq!(entries[0].action, "delete_key");
+        assert_eq!(entries[0].request_id.as_deref(), Some("trace-123"));
+        assert_eq!(entries[0].details["key"], "greeting");
+
+        let (status, activity) = get_json(&app, "/api/files/Localizable.xcstrings/activity").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(activity["path"], "Localizable.xcstrings");
+        assert_eq!(activity["entries"][0]["action"], "delete_key");
+        assert_eq!(activity["entries"][0]["requestId"], "trace-123");
+        let (status, activity) = get_json(
+            &app,
+            "/api/files/Localizable.xcstrings/activity?since=2999-01-01",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(activity["entries"], serde_json::json!([]));
+        let (status, _) = get_json(
+            &app,
+            "/api/files/Localizable.xcstrings/activity?since=yesterday",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let digest = app
+            .clone()
+            .call(
+                axum::http::Request::get(
+                    "/api/files/Localizable.xcstrings/digest?since=2020-01-01",
+                )
+                .body(axum::body::Body::empty())
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(digest.status(), StatusCode::OK);
+        assert_eq!(
+            digest.headers()[header::CONTENT_TYPE],
+            "text/markdown; charset=utf-8"
+        );
+        let digest = axum::body::to_bytes(digest.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            std::str::from_utf8(&digest).unwrap(),
+            "## Localizable.xcstrings since 2020-01-01\n\n1 change by web.\n\n\
+             **Deleted keys (1):** `greeting`\n"
+        );
+
+        let unaudited = seeded_app(dir.path()).await;
+        let (status, _) = get_json(&unaudited, "/api/files/Localizable.xcstrings/activity").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = get_json(
+            &unaudited,
+            "/api/files/Localizable.xcstrings/digest?since=2020-01-01",
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn review_endpoints_approve_and_reject_with_audited_reason() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let audit_path = dir.path().join("audit.jsonl");
+        let manager = XcStringsStoreManager::new(Some(dir.path().join("Localizable.xcstrings")))
+            .await
+            .unwrap()
+            .with_audit_log(crate::audit::AuditLog::new(&audit_path))
+            .with_review_workflow(true);
+        let store = manager.default_store().await.unwrap();
+        store
+            .upsert_translation(
+                "greeting",
+                "fr",
+                TranslationUpdate::from_value_state(Some("Bonjour".into()), None),
+            )
+            .await
+            .unwrap();
+        store
+            .set_translation_state("greeting", "fr", Some("needs_review".into()))
+            .await
+            .unwrap();
+        let app = router(Arc::new(manager));
+        let review = |action: &str, body: serde_json::Value| {
+            let request = axum::http::Request::post(format!("/api/translations/{action}"))
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::IF_MATCH, "*")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap();
+            let mut app = app.clone();
+            async move {
+                let response = app.call(request).await.unwrap();
+                let status = response.status();
+                let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (
+                    status,
+                    serde_json::from_slice::<serde_json::Value>(&bytes).unwrap(),
+                )
+            }
+        };
+
+        let (status, body) = review(
+            "approve",
+            serde_json::json!({ "key": "greeting", "language": "fr" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["state"], "reviewed");
+        let (status, _) = review(
+            "approve",
+            serde_json::json!({ "key": "greeting", "language": "fr" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = review(
+            "reject",
+            serde_json::json!({ "key": "greeting", "language": "fr" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, body) = review(
+            "reject",
+            serde_json::json!({ "key": "greeting", "language": "fr", "reason": "Too formal" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["state"], "new");
+
+        let entries = crate::audit::AuditLog::new(&audit_path)
+            .entries()
+            .await
+            .unwrap();
+        let actions: Vec<_> = entries.iter().map(|entry| entry.action.as_str()).collect();
+        assert_eq!(actions, vec!["approve_translation", "reject_translation"]);
+        assert_eq!(entries[1].details["reason"], "Too formal");
+    }
+
+    #[tokio::test]
+    async fn assignments_endpoint_filters_summaries_by_assignee() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = XcStringsStoreManager::new(Some(dir.path().join("Localizable.xcstrings")))
+            .await
+            .unwrap();
+        let store = manager.default_store().await.unwrap();
+        for key in ["login.title", "login.button"] {
+            store
+                .upsert_translation(
+                    key,
+                    "en",
+                    TranslationUpdate::from_value_state(Some("Log in".into()), None),
+                )
+                .await
+                .unwrap();
+        }
+        let mut app = router(Arc::new(manager));
+
+        let body = serde_json::json!({
+            "assignee": "alice",
+            "keys": ["login.button"],
+            "languages": ["en"],
+        });
+        let request = axum::http::Request::put("/api/assignments")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap();
+        let response = app.call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(dir.path().join("Localizable.assignments.json").exists());
+
+        let request = axum::http::Request::get("/api/summaries?assignee=alice")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let keys: Vec<_> = body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["key"].as_str().unwrap())
+            .collect();
+        assert_eq!(keys, vec!["login.button"]);
+    }
+
+    #[tokio::test]
+    async fn files_api_registers_catalogs_outside_search_root() {
+        use tower::Service;
+
+        let project = tempfile::tempdir().unwrap();
+        let elsewhere = tempfile::tempdir().unwrap();
+        let app = seeded_app(project.path()).await;
+        let external = elsewhere.path().join("Other.xcstrings");
+        let store = XcStringsStore::load_or_create(&external).await.unwrap();
+        store
+            .upsert_translation(
+                "hello",
+                "en",
+                TranslationUpdate::from_value_state(Some("Hello".into()), None),
+            )
+            .await
+            .unwrap();
+        std::fs::write(elsewhere.path().join("notes.txt"), "").unwrap();
+        std::fs::write(elsewhere.path().join("Broken.xcstrings"), "{").unwrap();
+
+        let register = |path: String| {
+            let request = axum::http::Request::post("/api/files")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(axum::body::Body::from(
+                    serde_json::json!({ "path": path }).to_string(),
+                ))
+                .unwrap();
+            let mut app = app.clone();
+            async move { app.call(request).await.unwrap() }
+        };
+        let unregister = |query: String| {
+            let request = axum::http::Request::delete(format!("/api/files{query}"))
+                .body(axum::body::Body::empty())
+                .unwrap();
+            let mut app = app.clone();
+            async move { app.call(request).await.unwrap().status() }
+        };
+
+        for rejected in [
+            "Other.xcstrings".to_string(),
+            elsewhere.path().join("notes.txt").display().to_string(),
+            elsewhere
+                .path()
+                .join("Missing.xcstrings")
+                .display()
+                .to_string(),
+            elsewhere
+                .path()
+                .join("Broken.xcstrings")
+                .display()
+                .to_string(),
+        ] {
+            let status = register(rejected.clone()).await.status();
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{rejected}");
+        }
+
+        let response = register(external.display().to_string()).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let entry: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(entry["registered"], true);
+        let token = entry["path"].as_str().unwrap().to_string();
+        assert_eq!(
+            std::path::Path::new(&token),
+            std::fs::canonicalize(&external).unwrap()
+        );
+
+        let (_, files) = get_json(&app, "/api/files").await;
+        let listed = files["files"].as_array().unwrap();
+        assert_eq!(listed.len(), 2);
+        assert!(listed
+            .iter()
+            .any(|file| file["path"] == token.as_str() && file["registered"] == true));
+        assert!(listed
+            .iter()
+            .any(|file| file["label"] == "Localizable.xcstrings" && file["registered"] == false));
+
+        let query = format!("?path={}", token.replace('/', "%2F"));
+        let (status, body) = get_json(&app, &format!("/api/translations{query}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["items"][0]["key"], "hello");
+
+        assert_eq!(unregister(String::new()).await, StatusCode::BAD_REQUEST);
+        assert_eq!(unregister(query.clone()).await, StatusCode::NO_CONTENT);
+        assert_eq!(unregister(query).await, StatusCode::NOT_FOUND);
+        let (_, files) = get_json(&app, "/api/files").await;
+        assert_eq!(files["files"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn keys_resource_creates_and_fetches_entries() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let app = seeded_app(dir.path()).await;
+        let create = |body: serde_json::Value| {
+            axum::http::Request::post("/api/keys")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::IF_MATCH, "*")
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let created = app
+            .clone()
+            .call(create(serde_json::json!({
+                "key": "d.title",
+                "comment": "Detail screen",
+                "value": "Details"
+            })))
+            .await
+            .unwrap();
+        assert_eq!(created.status(), StatusCode::CREATED);
+        assert!(created.headers().contains_key(header::ETAG));
+
+        let (status, body) = get_json(&app, "/api/keys/d.title").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["comment"], "Detail screen");
+        assert_eq!(body["extractionState"], "manual");
+        assert_eq!(body["translations"]["en"]["value"], "Details");
+
+        let (_, body) = get_json(&app, "/api/keys/b.title").await;
+        assert_eq!(body["translations"]["fr"]["value"], "Titre");
+
+        let duplicate = app
+            .clone()
+            .call(create(serde_json::json!({ "key": "d.title" })))
+            .await
+            .unwrap();
+        assert_eq!(duplicate.status(), StatusCode::CONFLICT);
+        let (status, _) = get_json(&app, "/api/keys/missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let head = |key: &str| {
+            axum::http::Request::head(format!("/api/keys/{key}"))
+                .body(axum::body::Body::empty())
+                .unwrap()
+        };
+        let found = app.clone().call(head("d.title")).await.unwrap();
+        assert_eq!(found.status(), StatusCode::OK);
+        assert!(found.headers().contains_key(header::ETAG));
+        let missing = app.clone().call(head("missing")).await.unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn validate_endpoint_filters_findings() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(Some(dir.path().join("Localizable.xcstrings")))
+                .await
+                .unwrap(),
+        );
+        let store = manager.default_store().await.unwrap();
+        for (key, language, value) in [
+            ("count", "en", "%d items"),
+            ("count", "fr", "articles"),
+            ("count", "de", "%d Artikel"),
+            ("title", "en", "Title"),
+            ("title", "fr", " Titre"),
+        ] {
+            store
+                .upsert_translation(
+                    key,
+                    language,
+                    TranslationUpdate::from_value_state(Some(value.into()), None),
+                )
+                .await
+                .unwrap();
+        }
+        let app = router(manager);
+
+        let (status, all) = get_json(&app, "/api/validate").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(all["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|finding| finding["key"] == "count" && finding["language"] == "fr"));
+
+        let (_, placeholders) = get_json(&app, "/api/validate?lang=fr&rule=placeholders").await;
+        assert_eq!(placeholders["rules"], serde_json::json!(["placeholders"]));
+        let findings: Vec<_> = ["errors", "warnings", "info"]
+            .iter()
+            .flat_map(|severity| placeholders[severity].as_array().unwrap().clone())
+            .collect();
+        assert!(!findings.is_empty());
+        assert!(findings
+            .iter()
+            .all(|finding| finding["language"] == "fr" && finding["rule"] == "placeholders"));
+        let total = ["errors", "warnings", "info"]
+            .iter()
+            .map(|severity| placeholders["summary"][severity].as_u64().unwrap())
+            .sum::<u64>();
+        assert_eq!(
+            total as usize,
+            findings.len(),
+            "summary matches filtered findings"
+        );
+
+        let (_, title) = get_json(&app, "/api/validate?key=title&lang=de").await;
+        assert_eq!(title["summary"]["errors"], 0);
+        assert_eq!(title["summary"]["warnings"], 0);
+
+        let (status, _) = get_json(&app, "/api/validate?rule=grammar").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = get_json(&app, "/api/validate?lang=ja").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    fn collect_refs<'a>(value: &'a serde_json::Value, refs: &mut Vec<&'a str>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                if let Some(reference) = map.get("$ref").and_then(|value| value.as_str()) {
+                    refs.push(reference);
+                }
+                map.values().for_each(|value| collect_refs(value, refs));
+            }
+            serde_json::Value::Array(items) => {
+                items.iter().for_each(|value| collect_refs(value, refs))
+            }
+            _ => {}
+        }
+    }
+
+    #[tokio::test]
+    async fn openapi_document_describes_routes_and_resolves_schemas() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = seeded_app(dir.path()).await;
+
+        let (status, doc) = get_json(&app, "/api/openapi.json").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(doc["openapi"].as_str().unwrap().starts_with("3."));
+
+        let paths = &doc["paths"];
+        assert!(paths["/api/translations"]["get"].is_object());
+        let upsert = &paths["/api/translations"]["put"];
+        assert!(upsert["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|param| param["name"] == "If-Match" && param["in"] == "header"));
+        assert!(upsert["responses"]["412"].is_object());
+        assert!(paths["/api/keys/{key}"]["delete"].is_object());
+        assert!(paths["/api/translations/{key}/{language}"]["delete"].is_object());
+        assert_eq!(
+            paths["/api/translations"]["get"]["responses"]["200"]["content"]["application/json"]
+                ["schema"]["$ref"],
+            "#/components/schemas/TranslationsResponse"
+        );
+
+        let schemas = doc["components"]["schemas"].as_object().unwrap();
+        let mut refs = Vec::new();
+        collect_refs(&doc, &mut refs);
+        assert!(!refs.is_empty());
+        for reference in refs {
+            let name = reference
+                .strip_prefix("#/components/schemas/")
+                .unwrap_or_else(|| panic!("unexpected reference {reference}"));
+            assert!(schemas.contains_key(name), "missing schema {name}");
+        }
+        assert!(doc["components"]["securitySchemes"]["bearer"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_web_api_delete_plural_variation() {
         use crate::store::XcStringsStore;
         use std::env;
-        use std::path::PathBuf;
         use tokio::fs;
 
         // Create a temporary test file
-        let test_file = PathBuf::from(env::temp_dir()).join(format!(
+        let test_file = env::temp_dir().join(format!(
             "test_web_api_delete_plural_{}.xcstrings",
             std::process::id()
         ));
@@ -691,11 +4526,7 @@ mod tests {
             .unwrap();
 
         // Verify the "one" case was deleted
-        let translation = store
-            .get_translation("items.count", "en")
-            .await
-            .unwrap()
-            .unwrap();
+        let translation = store.get_translation("items.count", "en").await.unwrap();
 
         let plural_vars = translation.variations.get("plural").unwrap();
         assert_eq!(
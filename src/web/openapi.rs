@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+
+use axum::Json;
+use utoipa::{
+    openapi::{
+        path::{Parameter, ParameterBuilder, ParameterIn},
+        security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+        ContentBuilder, Object, Ref, RefOr, Required, ResponseBuilder, Type,
+    },
+    IntoParams, IntoResponses, Modify, OpenApi, ToSchema,
+};
+
+use super::{ErrorResponse, SortOrder, SubstitutionUpdatePayload, VariationUpdatePayload};
+use crate::store::{NormalizedEntry, StoreChange, SubstitutionValue, TranslationValue};
+
+/// OpenAPI description of the routes in [`super::router_with_options`] that every build
+/// has. Recursive schemas and types only used by query parameters are listed explicitly
+/// because they aren't collected from the paths.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "xcstrings web API",
+        description = "Browse and edit Xcode String Catalogs. Writes require `If-Match` with \
+                       the catalog revision from a previous response's `ETag`."
+    ),
+    paths(
+        super::healthz,
+        super::readyz,
+        super::version,
+        openapi_json,
+        super::list_files,
+        super::register_file,
+        super::unregister_file,
+        super::file_activity,
+        super::file_digest,
+        super::list_translations,
+        super::upsert_translation,
+        super::apply_batch,
+        super::approve_translation,
+        super::reject_translation,
+        super::get_translation,
+        super::delete_translation,
+        super::list_summaries,
+        super::create_key,
+        super::get_assignments,
+        super::assign,
+        super::get_key_tree,
+        super::get_key,
+        super::key_exists,
+        super::delete_key,
+        super::rename_key,
+        super::find_replace,
+        super::update_comment,
+        super::update_extraction_state,
+        super::update_should_translate,
+        super::list_languages,
+        super::freeze_language,
+        super::unfreeze_language,
+        super::get_translation_percentages,
+        super::get_progress,
+        super::list_untranslated,
+        super::validate_catalog,
+        super::suggest_translations,
+        super::length_report,
+        super::estimate_cost,
+        super::stream_events,
+        super::collab::collab_socket,
+        super::webhook::receive_translations,
+    ),
+    components(schemas(
+        ErrorResponse,
+        NormalizedEntry,
+        SortOrder,
+        StoreChange,
+        SubstitutionUpdatePayload,
+        SubstitutionValue,
+        TranslationValue,
+        VariationUpdatePayload,
+    )),
+    modifiers(&AuthSchemes),
+    security((), ("bearer" = []), ("basic" = [])),
+    tags(
+        (name = "server", description = "Health, readiness, and build information"),
+        (name = "catalogs", description = "String catalogs under the search root"),
+        (name = "translations", description = "Read and edit translation values"),
+        (name = "keys", description = "Create, rename, and annotate keys"),
+        (name = "quality", description = "Progress and QA checks"),
+        (name = "events", description = "Live change notifications"),
+    )
+)]
+pub(super) struct ApiDoc;
+
+/// Routes only built with the `git` feature, merged in by [`api_doc`].
+#[cfg(feature = "git")]
+#[derive(OpenApi)]
+#[openapi(paths(super::key_history, super::list_outdated))]
+struct GitApiDoc;
+
+/// The OpenAPI document for the routes built into this binary.
+pub(super) fn api_doc() -> utoipa::openapi::OpenApi {
+    let doc = ApiDoc::openapi();
+    #[cfg(feature = "git")]
+    let doc = doc.merge_from(GitApiDoc::openapi());
+    doc
+}
+
+/// Adds the bearer and basic schemes accepted when web auth is configured. With auth off,
+/// requests without credentials are allowed, hence the empty requirement in `security`.
+struct AuthSchemes;
+
+impl Modify for AuthSchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+        components.add_security_scheme(
+            "basic",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Basic).build()),
+        );
+    }
+}
+
+/// The `If-Match` header every write must send.
+pub(super) struct IfMatch;
+
+impl IntoParams for IfMatch {
+    fn into_params(_: impl Fn() -> Option<ParameterIn>) -> Vec<Parameter> {
+        vec![ParameterBuilder::new()
+            .name("If-Match")
+            .parameter_in(ParameterIn::Header)
+            .required(Required::True)
+            .description(Some(
+                "`ETag` from a previous response, or `*` to write unconditionally",
+            ))
+            .schema(Some(Object::with_type(Type::String)))
+            .build()]
+    }
+}
+
+/// Rejections shared by every write: a read-only server and the `If-Match` check.
+pub(super) struct WriteRejected;
+
+impl IntoResponses for WriteRejected {
+    fn responses() -> BTreeMap<String, RefOr<utoipa::openapi::response::Response>> {
+        let error = || {
+            ContentBuilder::new()
+                .schema(Some(Ref::from_schema_name(ErrorResponse::name())))
+                .build()
+        };
+        let response = |description: &str| {
+            ResponseBuilder::new()
+                .description(description)
+                .content("application/json", error())
+                .build()
+                .into()
+        };
+        BTreeMap::from([
+            ("403".to_string(), response("The catalog is read-only")),
+            (
+                "412".to_string(),
+                response("The catalog changed since the `If-Match` revision"),
+            ),
+            ("428".to_string(), response("`If-Match` is missing")),
+        ])
+    }
+}
+
+/// Serves the OpenAPI document so other tools can generate typed clients.
+#[utoipa::path(
+    get,
+    path = "/api/openapi.json",
+    tag = "server",
+    responses((status = 200, description = "OpenAPI 3 document for this API"))
+)]
+pub(super) async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(api_doc())
+}
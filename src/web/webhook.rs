@@ -0,0 +1,272 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+    Extension, Json,
+};
+use ring::hmac;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use super::{record_audit, resolve_store, ApiError, BatchResponse, ErrorResponse, Tagged};
+use crate::store::{BatchOperation, InsertPosition, TranslationUpdate, XcStringsStoreManager};
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of the raw body>`, as GitHub and most webhook
+/// senders sign requests.
+const SIGNATURE_HEADER: &str = "x-hub-signature-256";
+
+/// Shared secret that signs inbound webhooks; webhooks are refused when it's unset.
+#[derive(Debug, Clone, Default)]
+pub(super) struct WebhookSecret(pub(super) Option<String>);
+
+/// Completed translations pushed by a translation management system.
+#[derive(Debug, Deserialize, ToSchema)]
+pub(super) struct WebhookTranslations {
+    /// Catalog to write; may be omitted when the server manages a single catalog.
+    #[serde(default)]
+    path: Option<String>,
+    translations: Vec<WebhookTranslation>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+struct WebhookTranslation {
+    key: String,
+    language: String,
+    value: String,
+    /// Translation state to set; defaults to `translated`.
+    #[serde(default)]
+    state: Option<String>,
+}
+
+/// Checks `signature` (`sha256=<hex>`) against the HMAC of `body` in constant time.
+fn signature_matches(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(digest) = signature
+        .trim()
+        .strip_prefix("sha256=")
+        .and_then(decode_hex)
+    else {
+        return false;
+    };
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::verify(&key, body, &digest).is_ok()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).ok())
+        .collect()
+}
+
+/// Writes translations from an external TMS in one batch. The request is authenticated by
+/// its HMAC signature rather than the web API credentials, and needs no `If-Match`.
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/translations",
+    tag = "translations",
+    request_body = WebhookTranslations,
+    params(
+        ("X-Hub-Signature-256" = String, Header, description = "`sha256=` followed by the hex HMAC-SHA256 of the raw body, keyed with the webhook secret"),
+    ),
+    security(()),
+    responses(
+        (status = 200, description = "Every translation was saved", body = BatchResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 422, description = "A translation was refused; nothing was saved", body = BatchResponse, headers(("ETag" = String, description = "Catalog revision"))),
+        (status = 400, description = "Invalid payload", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid signature", body = ErrorResponse),
+        (status = 404, description = "Webhooks are not configured, or the catalog was not found", body = ErrorResponse),
+    )
+)]
+pub(super) async fn receive_translations(
+    Extension(manager): Extension<Arc<XcStringsStoreManager>>,
+    Extension(secret): Extension<Arc<WebhookSecret>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, Tagged<Json<BatchResponse>>), ApiError> {
+    let Some(secret) = secret.0.as_deref() else {
+        return Err(ApiError {
+            status: StatusCode::NOT_FOUND,
+            message: "Webhooks are not configured; set WEBHOOK_SECRET to enable them".to_string(),
+        });
+    };
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if !signature_matches(secret, &body, signature) {
+        return Err(ApiError {
+            status: StatusCode::UNAUTHORIZED,
+            message: "Missing or invalid webhook signature".to_string(),
+        });
+    }
+    let payload: WebhookTranslations = serde_json::from_slice(&body).map_err(|err| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message: format!("Invalid webhook payload: {err}"),
+    })?;
+    if payload.translations.is_empty() {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: "Webhook must contain at least one translation".to_string(),
+        });
+    }
+    let store = resolve_store(manager.as_ref(), payload.path.as_deref()).await?;
+
+    let operations = payload
+        .translations
+        .into_iter()
+        .map(|translation| BatchOperation::Upsert {
+            key: translation.key,
+            language: translation.language,
+            comment: None,
+            position: InsertPosition::End,
+            update: Box::new(TranslationUpdate::from_value_state(
+                Some(translation.value),
+                Some(
+                    translation
+                        .state
+                        .unwrap_or_else(|| "translated".to_string()),
+                ),
+            )),
+        })
+        .collect();
+    let outcome = store.apply_batch(operations).await?;
+    if outcome.committed {
+        let changes: Vec<_> = outcome
+            .results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .map(|applied| &applied.change)
+            .collect();
+        record_audit(
+            &manager,
+            &headers,
+            &store,
+            "webhook_translations",
+            serde_json::json!({ "changes": changes }),
+        )
+        .await;
+    }
+    Ok(super::batch_response(&store, outcome))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+        let tag = hmac::sign(&key, body);
+        let hex: String = tag
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        format!("sha256={hex}")
+    }
+
+    #[test]
+    fn verifies_hex_hmac_signatures() {
+        let body = br#"{"translations":[]}"#;
+        let signature = sign("s3cret", body);
+        assert!(signature_matches("s3cret", body, &signature));
+        assert!(signature_matches(
+            "s3cret",
+            body,
+            &signature.to_ascii_uppercase().replace("SHA256=", "sha256=")
+        ));
+        assert!(!signature_matches("other", body, &signature));
+        assert!(!signature_matches("s3cret", b"{}", &signature));
+        assert!(!signature_matches("s3cret", body, &signature[7..]));
+        assert!(!signature_matches("s3cret", body, "sha256=zz"));
+        assert!(!signature_matches("s3cret", body, ""));
+    }
+
+    #[tokio::test]
+    async fn webhook_writes_signed_translations() {
+        use tower::Service;
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = Arc::new(
+            XcStringsStoreManager::new(Some(dir.path().join("Localizable.xcstrings")))
+                .await
+                .unwrap(),
+        );
+        let store = manager.default_store().await.unwrap();
+        for language in ["en", "de"] {
+            store
+                .upsert_translation(
+                    "greeting",
+                    language,
+                    TranslationUpdate::from_value_state(Some("Hello".into()), None),
+                )
+                .await
+                .unwrap();
+        }
+        let options = |secret: Option<&str>| super::super::WebOptions {
+            auth: super::super::WebAuth {
+                token: Some("api-token".into()),
+                ..Default::default()
+            },
+            webhook_secret: secret.map(str::to_string),
+            ..Default::default()
+        };
+        let post = |app: axum::Router, body: &'static str, signature: Option<String>| async move {
+            let mut request = axum::http::Request::post("/api/webhooks/translations");
+            if let Some(signature) = signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+            let request = request.body(axum::body::Body::from(body)).unwrap();
+            app.clone().call(request).await.unwrap().status()
+        };
+        let body = r#"{"translations":[{"key":"greeting","language":"de","value":"Hallo"}]}"#;
+
+        let disabled = super::super::router_with_options(manager.clone(), options(None));
+        assert_eq!(
+            post(disabled, body, Some(sign("s3cret", body.as_bytes()))).await,
+            StatusCode::NOT_FOUND
+        );
+
+        let app = super::super::router_with_options(manager.clone(), options(Some("s3cret")));
+        assert_eq!(
+            post(app.clone(), body, None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            post(app.clone(), body, Some(sign("wrong", body.as_bytes()))).await,
+            StatusCode::UNAUTHORIZED
+        );
+        let invalid = r#"{"translations":"nope"}"#;
+        assert_eq!(
+            post(
+                app.clone(),
+                invalid,
+                Some(sign("s3cret", invalid.as_bytes()))
+            )
+            .await,
+            StatusCode::BAD_REQUEST
+        );
+        // Signed requests need neither the API token nor If-Match.
+        assert_eq!(
+            post(app.clone(), body, Some(sign("s3cret", body.as_bytes()))).await,
+            StatusCode::OK
+        );
+        let greeting = store.get_translation("greeting", "de").await.unwrap();
+        assert_eq!(greeting.value.as_deref(), Some("Hallo"));
+        assert_eq!(greeting.state.as_deref(), Some("translated"));
+
+        // A frozen language refuses the write, so nothing in the batch is saved.
+        store.freeze_language("de", true).await.unwrap();
+        let unknown = r#"{"translations":[{"key":"farewell","language":"en","value":"Bye"},{"key":"greeting","language":"de","value":"Servus"}]}"#;
+        assert_eq!(
+            post(app, unknown, Some(sign("s3cret", unknown.as_bytes()))).await,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        let greeting = store.get_translation("greeting", "de").await.unwrap();
+        assert_eq!(greeting.value.as_deref(), Some("Hallo"));
+        assert!(store.get_translation("farewell", "en").await.ok().is_none());
+    }
+}
@@ -0,0 +1,107 @@
+/// Translation states of the opt-in review workflow, in the order a translation moves
+/// through them: `new` → `translated` → `needs_review` → `reviewed`.
+pub const NEW: &str = "new";
+pub const TRANSLATED: &str = "translated";
+pub const NEEDS_REVIEW: &str = "needs_review";
+pub const REVIEWED: &str = "reviewed";
+
+/// Where a state sits in the workflow. Placeholder, unknown, and missing states count as
+/// new, and `stale` (source text changed) counts as translated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    New,
+    Translated,
+    InReview,
+    Reviewed,
+}
+
+fn stage(state: Option<&str>) -> Stage {
+    match state {
+        Some(TRANSLATED | "stale") => Stage::Translated,
+        Some(NEEDS_REVIEW) => Stage::InReview,
+        Some(REVIEWED | "approved") => Stage::Reviewed,
+        _ => Stage::New,
+    }
+}
+
+/// Checks an edit that moves a translation from state `from` to `to`, changing its text
+/// when `text_changed`. Edits can't leave review or reach `reviewed`; only
+/// [`check_approve`] and [`check_reject`] can.
+pub fn check_edit(from: Option<&str>, to: Option<&str>, text_changed: bool) -> Result<(), String> {
+    let (from_stage, to_stage) = (stage(from), stage(to));
+    match from_stage {
+        Stage::InReview if text_changed || to_stage != Stage::InReview => {
+            return Err("is waiting for review; approve or reject it before editing it".to_string())
+        }
+        Stage::Reviewed if text_changed || to_stage != Stage::Reviewed => {
+            return Err("is reviewed; reject it to reopen it for editing".to_string())
+        }
+        _ => {}
+    }
+    match to_stage {
+        Stage::Reviewed if from_stage != Stage::Reviewed => {
+            Err("can only be marked reviewed with approve_translation".to_string())
+        }
+        Stage::InReview if !matches!(from_stage, Stage::Translated | Stage::InReview) => {
+            Err(format!(
+                "must be translated before it is sent to review, but it is {}",
+                state_name(from)
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that a translation in state `from` can be approved.
+pub fn check_approve(from: Option<&str>) -> Result<(), String> {
+    match stage(from) {
+        Stage::InReview => Ok(()),
+        _ => Err(format!(
+            "can only be approved while it is {NEEDS_REVIEW}, but it is {}",
+            state_name(from)
+        )),
+    }
+}
+
+/// Checks that a translation in state `from` can be rejected.
+pub fn check_reject(from: Option<&str>) -> Result<(), String> {
+    match stage(from) {
+        Stage::InReview | Stage::Reviewed => Ok(()),
+        _ => Err(format!(
+            "can only be rejected while it is {NEEDS_REVIEW} or {REVIEWED}, but it is {}",
+            state_name(from)
+        )),
+    }
+}
+
+fn state_name(state: Option<&str>) -> &str {
+    state.unwrap_or("without a state")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_follow_the_review_order() {
+        assert!(check_edit(None, Some(TRANSLATED), true).is_ok());
+        assert!(check_edit(Some(NEW), Some(TRANSLATED), true).is_ok());
+        assert!(check_edit(Some(TRANSLATED), Some(NEEDS_REVIEW), false).is_ok());
+        assert!(check_edit(Some("stale"), Some(TRANSLATED), true).is_ok());
+        assert!(check_edit(Some(NEW), Some(NEEDS_REVIEW), true).is_err());
+        assert!(check_edit(Some(TRANSLATED), Some(REVIEWED), false).is_err());
+        assert!(check_edit(Some(NEEDS_REVIEW), Some(NEEDS_REVIEW), false).is_ok());
+        assert!(check_edit(Some(NEEDS_REVIEW), Some(NEEDS_REVIEW), true).is_err());
+        assert!(check_edit(Some(NEEDS_REVIEW), Some(TRANSLATED), false).is_err());
+        assert!(check_edit(Some(REVIEWED), Some(TRANSLATED), true).is_err());
+    }
+
+    #[test]
+    fn approvals_and_rejections_need_a_review() {
+        assert!(check_approve(Some(NEEDS_REVIEW)).is_ok());
+        assert!(check_approve(Some(TRANSLATED)).is_err());
+        assert!(check_reject(Some(NEEDS_REVIEW)).is_ok());
+        assert!(check_reject(Some(REVIEWED)).is_ok());
+        assert!(check_reject(None).is_err());
+    }
+}
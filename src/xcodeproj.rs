@@ -0,0 +1,682 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use indexmap::IndexMap;
+
+/// A string catalog referenced by an Xcode project, with the targets that build it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectCatalog {
+    pub path: PathBuf,
+    pub targets: Vec<String>,
+}
+
+/// Finds the `.xcstrings` files built by the targets of an `.xcodeproj`, or of every project
+/// in an `.xcworkspace`. Catalogs are returned sorted by path with canonical paths where the
+/// file exists; targets are sorted by name.
+pub fn project_catalogs(path: &Path) -> anyhow::Result<Vec<ProjectCatalog>> {
+    let projects = match extension(path).as_deref() {
+        Some("xcodeproj") => vec![path.to_path_buf()],
+        Some("xcworkspace") => workspace_projects(path)?,
+        _ => bail!("{} is not an .xcodeproj or .xcworkspace", path.display()),
+    };
+
+    let mut catalogs: BTreeMap<PathBuf, BTreeSet<String>> = BTreeMap::new();
+    for project in projects {
+        for (catalog, target) in project_targets(&project)? {
+            let catalog = std::fs::canonicalize(&catalog).unwrap_or(catalog);
+            catalogs.entry(catalog).or_default().insert(target);
+        }
+    }
+    Ok(catalogs
+        .into_iter()
+        .map(|(path, targets)| ProjectCatalog {
+            path,
+            targets: targets.into_iter().collect(),
+        })
+        .collect())
+}
+
+/// Name of the Swift package or Xcode project nearest to `catalog`, looking in its
+/// directory and then each parent up to `root` (or the filesystem root when `catalog` is
+/// elsewhere). A package is named by its `Package.swift` manifest, a project by its
+/// `.xcodeproj` file name.
+pub fn owner_name(catalog: &Path, root: &Path) -> Option<String> {
+    let stop = catalog.starts_with(root).then_some(root);
+    for dir in catalog.parent()?.ancestors() {
+        if let Some(name) = dir_owner_name(dir) {
+            return Some(name);
+        }
+        if Some(dir) == stop {
+            break;
+        }
+    }
+    None
+}
+
+fn dir_owner_name(dir: &Path) -> Option<String> {
+    if let Ok(manifest) = std::fs::read_to_string(dir.join("Package.swift")) {
+        let fallback = || Some(dir.file_name()?.to_string_lossy().into_owned());
+        return package_name(&manifest).or_else(fallback);
+    }
+    let mut projects: Vec<String> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (extension(&path).as_deref() == Some("xcodeproj")).then(|| {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+            })?
+        })
+        .collect();
+    projects.sort();
+    projects.into_iter().next()
+}
+
+/// The `name:` given to `Package(...)` in a `Package.swift` manifest.
+fn package_name(manifest: &str) -> Option<String> {
+    let package = &manifest[manifest.find("Package(")?..];
+    let name = &package[package.find("name:")? + "name:".len()..];
+    let name = name.trim_start().strip_prefix('"')?;
+    Some(name[..name.find('"')?].to_string())
+}
+
+fn extension(path: &Path) -> Option<String> {
+    Some(path.extension()?.to_str()?.to_ascii_lowercase())
+}
+
+fn is_xcstrings(path: &Path) -> bool {
+    extension(path).as_deref() == Some("xcstrings")
+}
+
+/// Projects listed in a workspace's `contents.xcworkspacedata`, including nested groups.
+fn workspace_projects(workspace: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let data_path = workspace.join("contents.xcworkspacedata");
+    let data = std::fs::read_to_string(&data_path)
+        .with_context(|| format!("failed to read {}", data_path.display()))?;
+    let container = workspace.parent().unwrap_or(Path::new("."));
+
+    let mut groups = vec![container.to_path_buf()];
+    let mut projects = Vec::new();
+    for tag in data.split('<').skip(1) {
+        let tag = tag.split('>').next().unwrap_or_default().trim();
+        if tag.starts_with("/Group") {
+            if groups.len() > 1 {
+                groups.pop();
+            }
+            continue;
+        }
+        let is_group = tag.starts_with("Group");
+        if !is_group && !tag.starts_with("FileRef") {
+            continue;
+        }
+        let Some(location) = xml_attribute(tag, "location") else {
+            continue;
+        };
+        let group = groups.last().cloned().unwrap_or_default();
+        let resolved = match location.split_once(':') {
+            Some(("group", relative)) => group.join(relative),
+            Some(("container", relative)) => container.join(relative),
+            Some(("absolute", absolute)) => PathBuf::from(absolute),
+            _ => group.clone(),
+        };
+        if is_group {
+            if !tag.ends_with('/') {
+                groups.push(resolved);
+            }
+        } else if extension(&resolved).as_deref() == Some("xcodeproj") {
+            projects.push(resolved);
+        }
+    }
+    Ok(projects)
+}
+
+fn xml_attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag
+        .find(&format!("{name} = \""))
+        .map(|at| at + name.len() + 4);
+    let start = start.or_else(|| {
+        tag.find(&format!("{name}=\""))
+            .map(|at| at + name.len() + 2)
+    })?;
+    let value = &tag[start..];
+    let value = &value[..value.find('"')?];
+    Some(
+        value
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&"),
+    )
+}
+
+/// Pairs of catalog path and target name for one project.
+fn project_targets(project: &Path) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    let pbxproj = project.join("project.pbxproj");
+    let text = std::fs::read_to_string(&pbxproj)
+        .with_context(|| format!("failed to read {}", pbxproj.display()))?;
+    let root = Parser::new(&text)
+        .parse_document()
+        .with_context(|| format!("failed to parse {}", pbxproj.display()))?;
+    let graph = ObjectGraph::new(project, &root)?;
+
+    let mut pairs = Vec::new();
+    for target in graph.targets() {
+        let Some(name) = graph.string(target, "name") else {
+            continue;
+        };
+        for phase in graph.list(target, "buildPhases") {
+            for build_file in graph.list(phase, "files") {
+                if let Some(file) = graph.string(build_file, "fileRef") {
+                    for path in graph.file_paths(file) {
+                        if is_xcstrings(&path) {
+                            pairs.push((path, name.to_string()));
+                        }
+                    }
+                }
+            }
+        }
+        // Folders synced with the file system (Xcode 16) build every file they contain,
+        // except the ones excluded from this target.
+        for group in graph.list(target, "fileSystemSynchronizedGroups") {
+            let Some(dir) = graph.object_path(group) else {
+                continue;
+            };
+            let excluded: Vec<PathBuf> = graph
+                .list(group, "exceptions")
+                .filter(|exception| graph.string(exception, "target") == Some(target))
+                .flat_map(|exception| graph.list(exception, "membershipExceptions"))
+                .map(|relative| dir.join(relative))
+                .collect();
+            for path in walk_xcstrings(&dir) {
+                if !excluded.contains(&path) {
+                    pairs.push((path, name.to_string()));
+                }
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+fn walk_xcstrings(dir: &Path) -> Vec<PathBuf> {
+    let mut results = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match entry.file_type() {
+                Ok(kind) if kind.is_dir() => stack.push(path),
+                Ok(kind) if kind.is_file() && is_xcstrings(&path) => results.push(path),
+                _ => {}
+            }
+        }
+    }
+    results
+}
+
+/// The `objects` table of a pbxproj, with each object's parent group for resolving paths.
+struct ObjectGraph<'a> {
+    objects: &'a IndexMap<String, Plist>,
+    parents: HashMap<&'a str, &'a str>,
+    /// Directory the main group is relative to.
+    project_dir: PathBuf,
+    main_group: Option<&'a str>,
+}
+
+impl<'a> ObjectGraph<'a> {
+    fn new(project: &Path, root: &'a Plist) -> anyhow::Result<Self> {
+        let Some(objects) = root.get("objects").and_then(Plist::as_dict) else {
+            bail!("project has no objects table");
+        };
+        let mut parents = HashMap::new();
+        for (id, object) in objects {
+            if let Some(children) = object.get("children").and_then(Plist::as_array) {
+                for child in children.iter().filter_map(Plist::as_str) {
+                    parents.insert(child, id.as_str());
+                }
+            }
+        }
+        let project_object = objects
+            .values()
+            .find(|object| object.isa() == Some("PBXProject"));
+        let mut project_dir = project.parent().unwrap_or(Path::new(".")).to_path_buf();
+        if let Some(dir) = project_object
+            .and_then(|object| object.get("projectDirPath"))
+            .and_then(Plist::as_str)
+            .filter(|dir| !dir.is_empty())
+        {
+            project_dir = project_dir.join(dir);
+        }
+        Ok(Self {
+            objects,
+            parents,
+            project_dir,
+            main_group: project_object
+                .and_then(|object| object.get("mainGroup"))
+                .and_then(Plist::as_str),
+        })
+    }
+
+    fn targets(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.objects
+            .iter()
+            .filter(|(_, object)| {
+                matches!(
+                    object.isa(),
+                    Some("PBXNativeTarget" | "PBXAggregateTarget" | "PBXLegacyTarget")
+                )
+            })
+            .map(|(id, _)| id.as_str())
+    }
+
+    fn string(&self, id: &str, field: &str) -> Option<&'a str> {
+        self.objects.get(id)?.get(field)?.as_str()
+    }
+
+    /// Strings, usually object ids, listed in an array field.
+    fn list(&self, id: &str, field: &str) -> impl Iterator<Item = &'a str> + '_ {
+        self.objects
+            .get(id)
+            .and_then(|object| object.get(field))
+            .and_then(Plist::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Plist::as_str)
+    }
+
+    /// Paths of a file reference, or of every file in a variant group.
+    fn file_paths(&self, id: &str) -> Vec<PathBuf> {
+        match self.objects.get(id).and_then(Plist::isa) {
+            Some("PBXVariantGroup") => self
+                .list(id, "children")
+                .filter_map(|child| self.object_path(child))
+                .collect(),
+            Some(_) => self.object_path(id).into_iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Resolves the on-disk path of a file or group. Paths relative to build products or
+    /// SDKs aren't catalogs in the project, so they resolve to `None`.
+    fn object_path(&self, id: &str) -> Option<PathBuf> {
+        if Some(id) == self.main_group {
+            let path = self.string(id, "path").unwrap_or_default();
+            return Some(self.project_dir.join(path));
+        }
+        let path = self.string(id, "path");
+        match self.string(id, "sourceTree").unwrap_or("<group>") {
+            "<absolute>" => path.map(PathBuf::from),
+            "SOURCE_ROOT" => Some(self.project_dir.join(path.unwrap_or_default())),
+            "<group>" => {
+                let parent = match self.parents.get(id) {
+                    Some(parent) => self.object_path(parent)?,
+                    None => self.project_dir.clone(),
+                };
+                Some(match path {
+                    Some(path) => parent.join(path),
+                    None => parent,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A value in an old-style (OpenStep) property list, the format of `project.pbxproj`.
+#[derive(Debug, Clone, PartialEq)]
+enum Plist {
+    String(String),
+    Array(Vec<Plist>),
+    Dict(IndexMap<String, Plist>),
+}
+
+impl Plist {
+    fn get(&self, key: &str) -> Option<&Plist> {
+        self.as_dict()?.get(key)
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn as_array(&self) -> Option<&[Plist]> {
+        match self {
+            Self::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    fn as_dict(&self) -> Option<&IndexMap<String, Plist>> {
+        match self {
+            Self::Dict(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    fn isa(&self) -> Option<&str> {
+        self.get("isa")?.as_str()
+    }
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    text: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.char_indices().peekable(),
+            text,
+        }
+    }
+
+    fn parse_document(mut self) -> anyhow::Result<Plist> {
+        let value = self.parse_value()?;
+        if let Some(ch) = self.skip_trivia() {
+            bail!("unexpected '{ch}' after the root value at {}", self.line());
+        }
+        Ok(value)
+    }
+
+    fn line(&mut self) -> String {
+        let offset = self.chars.peek().map_or(self.text.len(), |(at, _)| *at);
+        format!("line {}", self.text[..offset].matches('\n').count() + 1)
+    }
+
+    /// Skips whitespace and comments, returning the next significant character.
+    fn skip_trivia(&mut self) -> Option<char> {
+        loop {
+            let (at, ch) = *self.chars.peek()?;
+            if ch.is_whitespace() {
+                self.chars.next();
+            } else if self.text[at..].starts_with("/*") {
+                match self.text[at + 2..].find("*/") {
+                    Some(end) => self.advance_to(at + 2 + end + 2),
+                    None => self.advance_to(self.text.len()),
+                }
+            } else if self.text[at..].starts_with("//") {
+                match self.text[at..].find('\n') {
+                    Some(end) => self.advance_to(at + end + 1),
+                    None => self.advance_to(self.text.len()),
+                }
+            } else {
+                return Some(ch);
+            }
+        }
+    }
+
+    fn advance_to(&mut self, offset: usize) {
+        while self.chars.next_if(|(at, _)| *at < offset).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> anyhow::Result<()> {
+        match self.skip_trivia() {
+            Some(ch) if ch == expected => {
+                self.chars.next();
+                Ok(())
+            }
+            Some(ch) => bail!("expected '{expected}' but found '{ch}' at {}", self.line()),
+            None => bail!("expected '{expected}' but the file ended"),
+        }
+    }
+
+    fn parse_value(&mut self) -> anyhow::Result<Plist> {
+        match self.skip_trivia() {
+            Some('{') => {
+                self.chars.next();
+                let mut dict = IndexMap::new();
+                while self.skip_trivia() != Some('}') {
+                    let key = self.parse_string()?;
+                    self.expect('=')?;
+                    let value = self.parse_value()?;
+                    self.expect(';')?;
+                    dict.insert(key, value);
+                }
+                self.chars.next();
+                Ok(Plist::Dict(dict))
+            }
+            Some('(') => {
+                self.chars.next();
+                let mut array = Vec::new();
+                while self.skip_trivia() != Some(')') {
+                    array.push(self.parse_value()?);
+                    if self.skip_trivia() == Some(',') {
+                        self.chars.next();
+                    } else if self.skip_trivia() != Some(')') {
+                        bail!("expected ',' or ')' at {}", self.line());
+                    }
+                }
+                self.chars.next();
+                Ok(Plist::Array(array))
+            }
+            Some('<') => {
+                // Data blobs don't matter for finding catalogs; keep their hex digits.
+                self.chars.next();
+                let mut hex = String::new();
+                for (_, ch) in self.chars.by_ref() {
+                    if ch == '>' {
+                        return Ok(Plist::String(hex));
+                    }
+                    if !ch.is_whitespace() {
+                        hex.push(ch);
+                    }
+                }
+                bail!("unterminated data value")
+            }
+            Some(_) => Ok(Plist::String(self.parse_string()?)),
+            None => bail!("expected a value but the file ended"),
+        }
+    }
+
+    fn parse_string(&mut self) -> anyhow::Result<String> {
+        match self.skip_trivia() {
+            Some('"') => {
+                self.chars.next();
+                let mut value = String::new();
+                while let Some((_, ch)) = self.chars.next() {
+                    match ch {
+                        '"' => return Ok(value),
+                        '\\' => match self.chars.next().map(|(_, ch)| ch) {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some('r') => value.push('\r'),
+                            Some('U') => {
+                                let hex: String = (0..4)
+                                    .filter_map(|_| self.chars.next())
+                                    .map(|(_, ch)| ch)
+                                    .collect();
+                                let ch = u32::from_str_radix(&hex, 16)
+                                    .ok()
+                                    .and_then(char::from_u32)
+                                    .with_context(|| format!("invalid escape \\U{hex}"))?;
+                                value.push(ch);
+                            }
+                            Some(other) => value.push(other),
+                            None => break,
+                        },
+                        _ => value.push(ch),
+                    }
+                }
+                bail!("unterminated string")
+            }
+            Some(ch) if is_bare(ch) => {
+                let mut value = String::new();
+                while let Some((_, ch)) = self.chars.next_if(|(_, ch)| is_bare(*ch)) {
+                    value.push(ch);
+                }
+                Ok(value)
+            }
+            Some(ch) => bail!("unexpected '{ch}' at {}", self.line()),
+            None => bail!("expected a string but the file ended"),
+        }
+    }
+}
+
+/// Characters allowed in unquoted strings.
+fn is_bare(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || matches!(ch, '_' | '$' | '+' | '/' | ':' | '.' | '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owner_is_the_nearest_package_or_project() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("App.xcodeproj")).unwrap();
+        let package = root.join("Packages/Auth");
+        std::fs::create_dir_all(package.join("Sources/Auth/Resources")).unwrap();
+        std::fs::write(
+            package.join("Package.swift"),
+            "// swift-tools-version:5.9\nlet package = Package(\n    name: \"AuthKit\",\n)",
+        )
+        .unwrap();
+
+        let packaged = package.join("Sources/Auth/Resources/Localizable.xcstrings");
+        assert_eq!(owner_name(&packaged, root).as_deref(), Some("AuthKit"));
+        let app = root.join("App/Localizable.xcstrings");
+        assert_eq!(owner_name(&app, root).as_deref(), Some("App"));
+        assert_eq!(owner_name(&app, &root.join("App")), None);
+        assert_eq!(package_name("let package = Package()"), None);
+    }
+
+    const PBXPROJ: &str = r#"// !$*UTF8*$!
+{
+	archiveVersion = 1;
+	objects = {
+
+/* Begin PBXBuildFile section */
+		B1 /* Localizable.xcstrings in Resources */ = {isa = PBXBuildFile; fileRef = F1 /* Localizable.xcstrings */; };
+		B2 /* Localizable.xcstrings in Resources */ = {isa = PBXBuildFile; fileRef = F1 /* Localizable.xcstrings */; };
+		B3 /* InfoPlist.xcstrings in Resources */ = {isa = PBXBuildFile; fileRef = F2; };
+		B4 /* main.swift in Sources */ = {isa = PBXBuildFile; fileRef = F3; };
+/* End PBXBuildFile section */
+
+		F1 = {isa = PBXFileReference; lastKnownFileType = text.json.xcstrings; path = Localizable.xcstrings; sourceTree = "<group>"; };
+		F2 = {isa = PBXFileReference; path = "Widget Strings/InfoPlist.xcstrings"; sourceTree = SOURCE_ROOT; };
+		F3 = {isa = PBXFileReference; path = main.swift; sourceTree = "<group>"; };
+		G0 = {isa = PBXGroup; children = (G1, S1, ); sourceTree = "<group>"; };
+		G1 = {isa = PBXGroup; children = (F1, F3, ); path = App; sourceTree = "<group>"; };
+		S1 = {isa = PBXFileSystemSynchronizedRootGroup; exceptions = (E1, ); path = Shared; sourceTree = "<group>"; };
+		E1 = {isa = PBXFileSystemSynchronizedBuildFileExceptionSet; membershipExceptions = (Legacy.xcstrings, ); target = T2; };
+		P1 = {isa = PBXProject; mainGroup = G0; projectDirPath = ""; targets = (T1, T2, ); };
+		R1 = {isa = PBXResourcesBuildPhase; files = (B1, ); };
+		R2 = {isa = PBXResourcesBuildPhase; files = (B2, B3, ); };
+		C1 = {isa = PBXSourcesBuildPhase; files = (B4, ); };
+		T1 = {isa = PBXNativeTarget; buildPhases = (C1, R1, ); fileSystemSynchronizedGroups = (S1, ); name = App; };
+		T2 = {isa = PBXNativeTarget; buildPhases = (R2, ); fileSystemSynchronizedGroups = (S1, ); name = "App Widget"; };
+	};
+	rootObject = P1;
+}
+"#;
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn finds_catalogs_per_target_in_projects_and_workspaces() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        write(&root.join("iOS/App.xcodeproj/project.pbxproj"), PBXPROJ);
+        for catalog in [
+            "iOS/App/Localizable.xcstrings",
+            "iOS/Widget Strings/InfoPlist.xcstrings",
+            "iOS/Shared/Common.xcstrings",
+            "iOS/Shared/Legacy.xcstrings",
+            "iOS/Unreferenced.xcstrings",
+        ] {
+            write(&root.join(catalog), "{}");
+        }
+        let catalogs = project_catalogs(&root.join("iOS/App.xcodeproj")).expect("project");
+        let summary: Vec<(String, Vec<String>)> = catalogs
+            .iter()
+            .map(|catalog| {
+                (
+                    catalog
+                        .path
+                        .strip_prefix(&root)
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned(),
+                    catalog.targets.clone(),
+                )
+            })
+            .collect();
+        let targets = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "iOS/App/Localizable.xcstrings".into(),
+                    targets(&["App", "App Widget"])
+                ),
+                (
+                    "iOS/Shared/Common.xcstrings".into(),
+                    targets(&["App", "App Widget"])
+                ),
+                ("iOS/Shared/Legacy.xcstrings".into(), targets(&["App"])),
+                (
+                    "iOS/Widget Strings/InfoPlist.xcstrings".into(),
+                    targets(&["App Widget"])
+                ),
+            ]
+        );
+
+        write(
+            &root.join("App.xcworkspace/contents.xcworkspacedata"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Workspace version = "1.0">
+   <Group location = "group:iOS" name = "iOS">
+      <FileRef location = "group:App.xcodeproj"></FileRef>
+   </Group>
+   <FileRef location = "group:Missing.xcodeproj"></FileRef>
+</Workspace>
+"#,
+        );
+        let err = project_catalogs(&root.join("App.xcworkspace")).unwrap_err();
+        assert!(format!("{err:#}").contains("Missing.xcodeproj"), "{err:#}");
+        write(
+            &root.join("App.xcworkspace/contents.xcworkspacedata"),
+            r#"<Workspace version = "1.0"><Group location = "group:iOS"><FileRef location = "group:App.xcodeproj"/></Group></Workspace>"#,
+        );
+        assert_eq!(
+            project_catalogs(&root.join("App.xcworkspace")).expect("workspace"),
+            catalogs
+        );
+    }
+
+    #[test]
+    fn parses_openstep_plists() {
+        let parsed = Parser::new(r#"{ a = "x \"y\"\n"; b = (1, two, ); c = { d = <0A 0b>; }; }"#)
+            .parse_document()
+            .expect("parse");
+        assert_eq!(parsed.get("a").and_then(Plist::as_str), Some("x \"y\"\n"));
+        assert_eq!(
+            parsed.get("b").and_then(Plist::as_array).map(<[_]>::len),
+            Some(2)
+        );
+        assert_eq!(
+            parsed
+                .get("c")
+                .and_then(|c| c.get("d"))
+                .and_then(Plist::as_str),
+            Some("0A0b")
+        );
+        let err = Parser::new("{\n a = b\n}").parse_document().unwrap_err();
+        assert!(err.to_string().contains("line 3"), "{err}");
+    }
+}
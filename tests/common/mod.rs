@@ -0,0 +1,103 @@
+// Each integration test binary compiles this module and uses only part of it.
+#![allow(dead_code)]
+
+use rmcp::ServiceExt;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream, ReadHalf, WriteHalf};
+use xcstrings_mcp::mcp_server::XcStringsMcpServer;
+
+type RequestHandler = Box<dyn Fn(&str, &Value) -> Value + Send>;
+
+/// Minimal line-delimited JSON-RPC client speaking to one server session.
+pub struct Session {
+    reader: BufReader<ReadHalf<DuplexStream>>,
+    writer: WriteHalf<DuplexStream>,
+    on_request: RequestHandler,
+}
+
+impl Session {
+    pub async fn connect(server: XcStringsMcpServer, client_name: &str) -> Self {
+        Self::connect_with(server, client_name, json!({}), |method, _| {
+            panic!("unexpected server request {method}")
+        })
+        .await
+    }
+
+    /// Connects with the given client capabilities; `on_request` answers requests the server
+    /// sends back to the client (such as `elicitation/create`) with a JSON-RPC result.
+    pub async fn connect_with(
+        server: XcStringsMcpServer,
+        client_name: &str,
+        capabilities: Value,
+        on_request: impl Fn(&str, &Value) -> Value + Send + 'static,
+    ) -> Self {
+        let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move {
+            if let Ok(running) = server.serve(server_io).await {
+                let _ = running.waiting().await;
+            }
+        });
+
+        let (read, write) = tokio::io::split(client_io);
+        let mut session = Self {
+            reader: BufReader::new(read),
+            writer: write,
+            on_request: Box::new(on_request),
+        };
+        session
+            .request(
+                0,
+                "initialize",
+                json!({
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": capabilities,
+                    "clientInfo": { "name": client_name, "version": "1.0" }
+                }),
+            )
+            .await;
+        session
+            .send(json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .await;
+        session
+    }
+
+    async fn send(&mut self, message: Value) {
+        let mut line = message.to_string();
+        line.push('\n');
+        self.writer
+            .write_all(line.as_bytes())
+            .await
+            .expect("write message");
+    }
+
+    pub async fn request(&mut self, id: u64, method: &str, params: Value) -> Value {
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await;
+        loop {
+            let mut line = String::new();
+            self.reader.read_line(&mut line).await.expect("read line");
+            assert!(!line.is_empty(), "server closed the session");
+            let message: Value = serde_json::from_str(&line).expect("parse message");
+            if let Some(server_method) = message.get("method").and_then(Value::as_str) {
+                if message.get("id").is_some() {
+                    let result = (self.on_request)(server_method, &message["params"]);
+                    self.send(json!({ "jsonrpc": "2.0", "id": message["id"], "result": result }))
+                        .await;
+                }
+                continue;
+            }
+            if message["id"] == json!(id) {
+                return message;
+            }
+        }
+    }
+
+    pub async fn call_tool(&mut self, id: u64, name: &str, arguments: Value) -> Value {
+        self.request(
+            id,
+            "tools/call",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await
+    }
+}
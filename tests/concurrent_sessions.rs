@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+mod common;
+
+use common::Session;
+use serde_json::json;
+use xcstrings_mcp::{
+    audit::AuditLog,
+    mcp_server::XcStringsMcpServer,
+    store::{XcStringsStore, XcStringsStoreManager},
+};
+
+#[tokio::test]
+async fn concurrent_sessions_share_stores_and_are_audited_separately() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let catalog = dir.path().join("Localizable.xcstrings");
+    let audit_path = dir.path().join("audit.jsonl");
+    let catalog_arg = catalog.to_string_lossy().to_string();
+
+    let manager = XcStringsStoreManager::new(None)
+        .await
+        .expect("create manager")
+        .with_audit_log(AuditLog::new(&audit_path));
+    let server = XcStringsMcpServer::new(Arc::new(manager));
+
+    let mut first = Session::connect(server.new_session(), "agent-a").await;
+    let mut second = Session::connect(server.new_session(), "agent-b").await;
+
+    let call = |key: &str, value: &str| {
+        json!({
+            "name": "upsert_translation",
+            "arguments": {
+                "path": catalog_arg,
+                "key": key,
+                "language": "fr",
+                "value": value
+            }
+        })
+    };
+    let (first_response, second_response) = tokio::join!(
+        first.request(1, "tools/call", call("greeting", "Bonjour")),
+        second.request(1, "tools/call", call("farewell", "Au revoir")),
+    );
+    assert!(first_response.get("result").is_some(), "{first_response}");
+    assert!(second_response.get("result").is_some(), "{second_response}");
+
+    let listed = first
+        .call_tool(2, "list_keys", json!({ "path": catalog_arg }))
+        .await;
+    assert!(listed.get("result").is_some(), "{listed}");
+
+    let store = XcStringsStore::load_or_create(&catalog)
+        .await
+        .expect("reload catalog");
+    let mut keys = store.keys().await;
+    keys.sort();
+    assert_eq!(keys, vec!["farewell".to_string(), "greeting".to_string()]);
+
+    let entries = AuditLog::new(&audit_path)
+        .entries()
+        .await
+        .expect("read audit log");
+    assert_eq!(entries.len(), 2, "read-only calls are not audited");
+    let mut by_client: Vec<_> = entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.client.clone().unwrap_or_default(),
+                entry.action.clone(),
+                entry.details["key"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+            )
+        })
+        .collect();
+    by_client.sort();
+    assert_eq!(
+        by_client,
+        vec![
+            (
+                "agent-a".to_string(),
+                "upsert_translation".to_string(),
+                "greeting".to_string()
+            ),
+            (
+                "agent-b".to_string(),
+                "upsert_translation".to_string(),
+                "farewell".to_string()
+            ),
+        ]
+    );
+    assert!(entries
+        .iter()
+        .all(|entry| entry.path.as_deref() == Some(catalog_arg.as_str())));
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::Session;
+use serde_json::{json, Value};
+use xcstrings_mcp::{
+    mcp_server::XcStringsMcpServer,
+    store::{TranslationUpdate, XcStringsStoreManager},
+};
+
+async fn seeded_server(dir: &tempfile::TempDir) -> (XcStringsMcpServer, String) {
+    let catalog = dir.path().join("Localizable.xcstrings");
+    let catalog_arg = catalog.to_string_lossy().to_string();
+    let manager = Arc::new(
+        XcStringsStoreManager::new(None)
+            .await
+            .expect("create manager"),
+    );
+    let store = manager
+        .store_for(Some(catalog_arg.as_str()))
+        .await
+        .expect("load store");
+    store
+        .upsert_translation(
+            "greeting",
+            "en",
+            TranslationUpdate::from_value_state(Some("Hello".into()), None),
+        )
+        .await
+        .expect("seed translation");
+    (XcStringsMcpServer::new(manager), catalog_arg)
+}
+
+fn elicitation_client(answer: Value) -> impl Fn(&str, &Value) -> Value + Send + 'static {
+    move |method, params| {
+        assert_eq!(method, "elicitation/create");
+        assert!(params["message"]
+            .as_str()
+            .expect("message")
+            .contains("greeting"));
+        answer.clone()
+    }
+}
+
+async fn key_exists(session: &mut Session, id: u64, catalog: &str) -> bool {
+    let listed = session
+        .call_tool(id, "list_keys", json!({ "path": catalog }))
+        .await;
+    let text = listed["result"]["content"][0]["text"]
+        .as_str()
+        .expect("text content");
+    let payload: Value = serde_json::from_str(text).expect("json payload");
+    payload["keys"]
+        .as_array()
+        .expect("keys")
+        .iter()
+        .any(|key| key == "greeting")
+}
+
+#[tokio::test]
+async fn declined_confirmation_keeps_the_key() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let (server, catalog) = seeded_server(&dir).await;
+    let mut session = Session::connect_with(
+        server,
+        "agent",
+        json!({ "elicitation": {} }),
+        elicitation_client(json!({ "action": "decline" })),
+    )
+    .await;
+
+    let response = session
+        .call_tool(
+            1,
+            "delete_key",
+            json!({ "path": catalog, "key": "greeting" }),
+        )
+        .await;
+    assert_eq!(response["result"]["isError"], json!(true), "{response}");
+    assert!(key_exists(&mut session, 2, &catalog).await);
+}
+
+#[tokio::test]
+async fn accepted_confirmation_deletes_the_key() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let (server, catalog) = seeded_server(&dir).await;
+    let mut session = Session::connect_with(
+        server,
+        "agent",
+        json!({ "elicitation": {} }),
+        elicitation_client(json!({ "action": "accept", "content": { "confirm": true } })),
+    )
+    .await;
+
+    let response = session
+        .call_tool(
+            1,
+            "delete_key",
+            json!({ "path": catalog, "key": "greeting" }),
+        )
+        .await;
+    assert_ne!(response["result"]["isError"], json!(true), "{response}");
+    assert!(!key_exists(&mut session, 2, &catalog).await);
+}
+
+#[tokio::test]
+async fn clients_without_elicitation_and_disabled_prompts_skip_confirmation() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let (server, catalog) = seeded_server(&dir).await;
+    let mut session = Session::connect(server.new_session(), "agent").await;
+    let response = session
+        .call_tool(
+            1,
+            "delete_key",
+            json!({ "path": catalog, "key": "greeting" }),
+        )
+        .await;
+    assert_ne!(response["result"]["isError"], json!(true), "{response}");
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let (server, catalog) = seeded_server(&dir).await;
+    let mut session = Session::connect_with(
+        server.with_destructive_confirmation(false),
+        "ci",
+        json!({ "elicitation": {} }),
+        |method, _| panic!("unexpected server request {method}"),
+    )
+    .await;
+    let response = session
+        .call_tool(
+            1,
+            "delete_key",
+            json!({ "path": catalog, "key": "greeting" }),
+        )
+        .await;
+    assert_ne!(response["result"]["isError"], json!(true), "{response}");
+    assert!(!key_exists(&mut session, 2, &catalog).await);
+}
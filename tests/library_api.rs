@@ -0,0 +1,39 @@
+use xcstrings_mcp::{
+    export, import, Catalog, ExportFormat, QaOptions, QaRule, StoreError, TranslationUpdate,
+};
+
+#[tokio::test]
+async fn edits_validates_and_exports_through_the_facade() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("Localizable.xcstrings");
+    let catalog = Catalog::load_or_create(&path).await.unwrap();
+    let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+    catalog
+        .upsert_translation("greeting", "en", set("Hello %@"))
+        .await
+        .unwrap();
+    catalog
+        .upsert_translation("greeting", "de", set("Hallo"))
+        .await
+        .unwrap();
+    assert!(matches!(
+        catalog.get_translation("greeting", "fr").await,
+        Err(StoreError::LanguageMissing(_))
+    ));
+
+    let report = catalog
+        .validate(&[QaRule::Placeholders], &QaOptions::default())
+        .await;
+    assert_eq!(report.summary.errors, 1, "{report:?}");
+
+    let records = catalog.list_records(None).await;
+    let csv = export(&records, &["de".to_string()], ExportFormat::Csv, &[]).unwrap();
+    let imported = import(&csv, ExportFormat::Csv, Some("de")).unwrap();
+    assert_eq!(imported.len(), 1);
+    assert_eq!(imported[0].key, "greeting");
+    assert_eq!(imported[0].value, "Hallo");
+
+    let reloaded = Catalog::load_or_create(&path).await.unwrap();
+    let greeting = reloaded.get_translation("greeting", "de").await.unwrap();
+    assert_eq!(greeting.value.as_deref(), Some("Hallo"));
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::Session;
+use serde_json::{json, Value};
+use xcstrings_mcp::{mcp_server::XcStringsMcpServer, store::XcStringsStoreManager};
+
+/// A catalog another tool wrote, with an empty entry and a placeholder without a value or
+/// state, both of which saves keep until `clean_catalog` removes them.
+const CATALOG: &str = r#"{"sourceLanguage":"en","strings":{"farewell":{"localizations":{"de":{"stringUnit":{"state":"translated","value":"Tschüss"}}}},"greeting":{"localizations":{"en":{"stringUnit":{"state":"translated","value":"Hello"}},"fr":{"stringUnit":{}}}},"orphan":{}},"version":"1.0"}"#;
+
+fn text(response: &Value, index: usize) -> Value {
+    let text = response["result"]["content"][index]["text"]
+        .as_str()
+        .unwrap_or_else(|| panic!("text content {index}: {response}"));
+    serde_json::from_str(text).expect("json payload")
+}
+
+#[tokio::test]
+async fn tool_results_list_entries_normalization_changed() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let catalog = dir.path().join("Localizable.xcstrings");
+    std::fs::write(&catalog, CATALOG).expect("write catalog");
+    let catalog = catalog.to_string_lossy().to_string();
+    let manager = Arc::new(
+        XcStringsStoreManager::new(None)
+            .await
+            .expect("create manager"),
+    );
+    let server = XcStringsMcpServer::new(manager).with_destructive_confirmation(false);
+    let mut session = Session::connect(server, "agent").await;
+
+    // Ordinary edits keep the placeholders and report nothing extra.
+    let response = session
+        .call_tool(
+            1,
+            "upsert_translation",
+            json!({ "path": catalog, "key": "greeting", "language": "de", "value": "Hallo" }),
+        )
+        .await;
+    assert_ne!(response["result"]["isError"], json!(true), "{response}");
+    assert_eq!(text(&response, 0)["value"], "Hallo");
+    let content = response["result"]["content"].as_array().expect("content");
+    assert_eq!(content.len(), 1, "{response}");
+
+    let response = session
+        .call_tool(
+            2,
+            "remove_language",
+            json!({ "path": catalog, "language": "de" }),
+        )
+        .await;
+    assert_ne!(response["result"]["isError"], json!(true), "{response}");
+    assert_eq!(
+        text(&response, 1),
+        json!({ "normalizedEntries": [{ "key": "farewell", "removed": true }] })
+    );
+
+    let response = session
+        .call_tool(3, "clean_catalog", json!({ "path": catalog }))
+        .await;
+    assert_eq!(
+        text(&response, 0)["removed"],
+        json!([
+            { "key": "greeting", "removed": false, "droppedLanguages": ["fr"] },
+            { "key": "orphan", "removed": true },
+        ])
+    );
+}
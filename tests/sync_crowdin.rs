@@ -0,0 +1,215 @@
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    routing::{get, patch},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use xcstrings_mcp::{
+    store::{StoreError, TranslationUpdate, XcStringsStore},
+    sync::{self, Crowdin, CrowdinConfig, SyncError},
+};
+
+type Requests = Arc<Mutex<Vec<(String, Value)>>>;
+
+fn authorized(headers: &HeaderMap) -> Result<(), StatusCode> {
+    match headers.get("authorization").and_then(|v| v.to_str().ok()) {
+        Some("Bearer secret") => Ok(()),
+        _ => Err(StatusCode::UNAUTHORIZED),
+    }
+}
+
+async fn list_strings(headers: HeaderMap) -> Result<Json<Value>, StatusCode> {
+    authorized(&headers)?;
+    let strings = [
+        (1, "greeting", "Hello"),
+        (2, "removed", "Gone"),
+        (3, "title", "Old title"),
+    ];
+    Ok(Json(json!({
+        "data": strings
+            .iter()
+            .map(|(id, identifier, text)| json!({ "data": { "id": id, "identifier": identifier, "text": text } }))
+            .collect::<Vec<_>>(),
+        "pagination": { "offset": 0, "limit": 500 }
+    })))
+}
+
+async fn add_string(
+    State(requests): State<Requests>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Result<Json<Value>, StatusCode> {
+    authorized(&headers)?;
+    requests
+        .lock()
+        .unwrap()
+        .push(("POST /strings".to_string(), body));
+    Ok(Json(json!({ "data": { "id": 4 } })))
+}
+
+async fn edit_string(
+    State(requests): State<Requests>,
+    Path(id): Path<u64>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Result<Json<Value>, StatusCode> {
+    authorized(&headers)?;
+    requests
+        .lock()
+        .unwrap()
+        .push((format!("PATCH /strings/{id}"), body));
+    Ok(Json(json!({ "data": { "id": id } })))
+}
+
+async fn list_translations(
+    Path(language): Path<String>,
+    Query(query): Query<std::collections::HashMap<String, String>>,
+    headers: HeaderMap,
+) -> Result<Json<Value>, StatusCode> {
+    authorized(&headers)?;
+    if language != "de" {
+        return Ok(Json(json!({ "data": [] })));
+    }
+    let approved_only = query.get("approvedOnly").map(String::as_str) == Some("1");
+    let translations = [(1, "Hallo", true), (2, "Weg", false), (3, "Titel", false)];
+    Ok(Json(json!({
+        "data": translations
+            .iter()
+            .filter(|(_, _, approved)| *approved || !approved_only)
+            .map(|(id, text, _)| json!({ "data": { "stringId": id, "contentType": "text/plain", "text": text } }))
+            .collect::<Vec<_>>()
+    })))
+}
+
+async fn serve() -> (String, Requests) {
+    let requests = Requests::default();
+    let app = Router::new()
+        .route(
+            "/api/v2/projects/7/strings",
+            get(list_strings).post(add_string),
+        )
+        .route("/api/v2/projects/7/strings/:id", patch(edit_string))
+        .route(
+            "/api/v2/projects/7/languages/:language/translations",
+            get(list_translations),
+        )
+        .with_state(requests.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{addr}/api/v2"), requests)
+}
+
+fn crowdin(base_url: &str, token: &str) -> Crowdin {
+    Crowdin::new(CrowdinConfig {
+        project_id: 7,
+        file_id: None,
+        token: Some(token.to_string()),
+        base_url: Some(base_url.to_string()),
+        languages: Default::default(),
+    })
+    .unwrap()
+}
+
+async fn catalog(dir: &tempfile::TempDir) -> XcStringsStore {
+    let store = XcStringsStore::load_or_create(&dir.path().join("Localizable.xcstrings"))
+        .await
+        .unwrap();
+    let set = |value: &str| TranslationUpdate::from_value_state(Some(value.into()), None);
+    for (key, language, value) in [
+        ("greeting", "en", "Hello"),
+        ("farewell", "en", "Goodbye"),
+        ("title", "en", "New title"),
+        ("farewell", "de", "Tschüss"),
+    ] {
+        store
+            .upsert_translation(key, language, set(value))
+            .await
+            .unwrap();
+    }
+    store
+}
+
+#[tokio::test]
+async fn pushes_new_and_changed_source_strings() {
+    let (base_url, requests) = serve().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store = catalog(&dir).await;
+    let provider = crowdin(&base_url, "secret");
+
+    let preview = sync::push(&store, &provider, true).await.unwrap();
+    assert_eq!(preview.added, ["farewell"]);
+    assert_eq!(preview.updated, ["title"]);
+    assert_eq!(preview.unchanged, 1);
+    assert!(requests.lock().unwrap().is_empty());
+
+    let report = sync::push(&store, &provider, false).await.unwrap();
+    assert_eq!(report.added, preview.added);
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].0, "POST /strings");
+    assert_eq!(requests[0].1["identifier"], "farewell");
+    assert_eq!(requests[0].1["text"], "Goodbye");
+    assert_eq!(requests[1].0, "PATCH /strings/3");
+    assert_eq!(requests[1].1[0]["value"], "New title");
+}
+
+#[tokio::test]
+async fn pulls_translations_with_states_from_approvals() {
+    let (base_url, _) = serve().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store = catalog(&dir).await;
+    let provider = crowdin(&base_url, "secret");
+    store
+        .upsert_translation(
+            "title",
+            "de",
+            TranslationUpdate::from_value_state(Some("Alter Titel".into()), None),
+        )
+        .await
+        .unwrap();
+
+    let preview = sync::pull(&store, &provider, &[], true).await.unwrap();
+    assert_eq!(preview.pulled.len(), 2);
+    assert_eq!(preview.overwritten(), 1);
+    let replaced = preview.pulled.iter().find(|pulled| pulled.key == "title");
+    assert_eq!(
+        replaced.and_then(|pulled| pulled.previous.as_deref()),
+        Some("Alter Titel")
+    );
+    assert_eq!(preview.unknown_keys, ["removed"]);
+    assert!(matches!(
+        store.get_translation("greeting", "de").await,
+        Err(StoreError::TranslationMissing { .. })
+    ));
+
+    let report = sync::pull(&store, &provider, &["de".to_string()], false)
+        .await
+        .unwrap();
+    assert_eq!(report.pulled, preview.pulled);
+    let greeting = store.get_translation("greeting", "de").await.unwrap();
+    assert_eq!(greeting.value.as_deref(), Some("Hallo"));
+    assert_eq!(greeting.state.as_deref(), Some("translated"));
+    let title = store.get_translation("title", "de").await.unwrap();
+    assert_eq!(title.value.as_deref(), Some("Titel"));
+    assert_eq!(title.state.as_deref(), Some("needs_review"));
+
+    let again = sync::pull(&store, &provider, &[], false).await.unwrap();
+    assert!(again.pulled.is_empty());
+    assert_eq!(again.unchanged, 2);
+}
+
+#[tokio::test]
+async fn reports_rejected_tokens() {
+    let (base_url, _) = serve().await;
+    let dir = tempfile::tempdir().unwrap();
+    let store = catalog(&dir).await;
+    let provider = crowdin(&base_url, "wrong");
+    assert!(matches!(
+        sync::push(&store, &provider, true).await,
+        Err(SyncError::Http { status: 401, .. })
+    ));
+}
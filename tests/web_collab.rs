@@ -0,0 +1,131 @@
+use std::{sync::Arc, time::Duration};
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use xcstrings_mcp::{store::XcStringsStoreManager, web};
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn next_json(client: &mut Client) -> Value {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), client.next())
+            .await
+            .expect("message before timeout")
+            .expect("socket open")
+            .expect("valid frame");
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).expect("json message");
+        }
+    }
+}
+
+async fn send_json(client: &mut Client, value: Value) {
+    client
+        .send(Message::Text(value.to_string()))
+        .await
+        .expect("send message");
+}
+
+#[tokio::test]
+async fn websocket_pushes_presence_and_changes_between_editors() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let manager = Arc::new(
+        XcStringsStoreManager::new(Some(dir.path().join("Localizable.xcstrings")))
+            .await
+            .expect("create manager"),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("local addr");
+    tokio::spawn(async move {
+        axum::serve(listener, web::router(manager).into_make_service())
+            .await
+            .expect("serve");
+    });
+
+    let url = format!("ws://{addr}/api/ws");
+    let (mut alice, _) = tokio_tungstenite::connect_async(format!("{url}?name=alice"))
+        .await
+        .expect("connect alice");
+    let welcome = next_json(&mut alice).await;
+    assert_eq!(welcome["type"], "welcome");
+    assert_eq!(welcome["editors"], json!([]));
+
+    let (mut bob, _) = tokio_tungstenite::connect_async(format!("{url}?name=bob"))
+        .await
+        .expect("connect bob");
+    let welcome = next_json(&mut bob).await;
+    assert_eq!(welcome["type"], "welcome");
+    let loaded = welcome["revision"].clone();
+
+    send_json(&mut bob, json!({ "type": "editing", "key": "greeting" })).await;
+    let presence = next_json(&mut alice).await;
+    assert_eq!(presence["type"], "presence");
+    assert_eq!(presence["key"], "greeting");
+    assert_eq!(presence["name"], "bob");
+    assert_eq!(presence["editing"], true);
+
+    let upsert = |id: u64, revision: &Value| {
+        json!({
+            "type": "upsert",
+            "id": id,
+            "revision": revision,
+            "key": "greeting",
+            "language": "fr",
+            "value": "Bonjour"
+        })
+    };
+    send_json(&mut bob, upsert(6, &Value::Null)).await;
+    let error = next_json(&mut bob).await;
+    assert_eq!(error["id"], 6);
+    assert_eq!(error["status"], 428);
+
+    send_json(&mut bob, upsert(7, &loaded)).await;
+    let ack = next_json(&mut bob).await;
+    assert_eq!(ack["type"], "ack");
+    assert_eq!(ack["id"], 7);
+    assert_ne!(ack["revision"], loaded);
+    let change = next_json(&mut alice).await;
+    assert_eq!(
+        change,
+        json!({
+            "type": "change",
+            "change": { "type": "translation_upserted", "key": "greeting", "language": "fr" }
+        })
+    );
+
+    // Alice still holds the revision from before Bob's edit.
+    send_json(
+        &mut alice,
+        json!({ "type": "delete_key", "id": 9, "revision": loaded, "key": "greeting" }),
+    )
+    .await;
+    let stale = loop {
+        let message = next_json(&mut alice).await;
+        if message["type"] == "error" {
+            break message;
+        }
+    };
+    assert_eq!(stale["id"], 9);
+    assert_eq!(stale["status"], 412);
+
+    send_json(
+        &mut bob,
+        json!({ "type": "delete_key", "id": 8, "revision": "*", "key": "missing" }),
+    )
+    .await;
+    let error = loop {
+        let message = next_json(&mut bob).await;
+        if message["type"] == "error" {
+            break message;
+        }
+    };
+    assert_eq!(error["id"], 8);
+    assert_eq!(error["status"], 404);
+
+    bob.close(None).await.expect("close bob");
+    let idle = next_json(&mut alice).await;
+    assert_eq!(idle["type"], "presence");
+    assert_eq!(idle["editing"], false);
+}
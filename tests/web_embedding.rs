@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use axum::{body::Body, http::Request, routing::get, Router};
+use http_body_util::BodyExt;
+use tower::Service;
+use xcstrings_mcp::{
+    store::{TranslationUpdate, XcStringsStoreManager},
+    web,
+};
+
+async fn get_text(app: &Router, uri: &str) -> (u16, String) {
+    let response = app
+        .clone()
+        .call(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn router_nests_inside_a_host_app() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let manager = Arc::new(
+        XcStringsStoreManager::new(Some(dir.path().join("Localizable.xcstrings")))
+            .await
+            .expect("create manager"),
+    );
+    manager
+        .default_store()
+        .await
+        .expect("default store")
+        .upsert_translation(
+            "greeting",
+            "en",
+            TranslationUpdate::from_value_state(Some("Hello".into()), None),
+        )
+        .await
+        .expect("save translation");
+
+    let host = Router::new()
+        .route("/", get(|| async { "host home" }))
+        .nest("/l10n", web::router(manager));
+
+    assert_eq!(get_text(&host, "/").await, (200, "host home".to_string()));
+    let (status, record) = get_text(&host, "/l10n/api/keys/greeting").await;
+    assert_eq!(status, 200);
+    let record: serde_json::Value = serde_json::from_str(&record).expect("json record");
+    assert_eq!(record["translations"]["en"]["value"], "Hello");
+
+    let (status, page) = get_text(&host, "/l10n").await;
+    assert_eq!(status, 200);
+    assert!(page.contains(r#"<base href="/l10n/" />"#));
+    assert!(page.contains(r#"src="assets/app.js""#));
+    let (status, _) = get_text(&host, "/l10n/assets/app.js").await;
+    assert_eq!(status, 200);
+}